@@ -0,0 +1,192 @@
+use crate::i18n;
+use crate::index::IndexState;
+use crate::storage;
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+
+const JOURNAL_FILE: &str = "activity.json";
+const DEFAULT_RANGE_DAYS: i64 = 365;
+const MAX_RANGE_DAYS: i64 = 3660;
+
+#[derive(Debug, Clone, Copy)]
+pub enum ActivityKind {
+    Create,
+    Edit,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DayActivity {
+    created: BTreeSet<String>,
+    edited: BTreeSet<String>,
+}
+
+// Paths touched through the app, bucketed per local day. File mtimes only keep
+// the latest edit, so this is what lets older days keep their activity.
+type ActivityJournal = BTreeMap<String, DayActivity>;
+
+#[derive(Default)]
+pub struct ActivityState {
+    lock: Mutex<()>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HeatmapRange {
+    start: Option<String>,
+    end: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HeatmapDay {
+    date: String,
+    created: usize,
+    edited: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActivityHeatmap {
+    start: String,
+    end: String,
+    max_count: usize,
+    days: Vec<HeatmapDay>,
+}
+
+fn day_key(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+fn parse_day(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| i18n::tf("error.invalid_date", &[&value]))
+}
+
+pub fn record_activity(
+    app: &AppHandle,
+    activity_state: &ActivityState,
+    root: &Path,
+    path: &Path,
+    kind: ActivityKind,
+) -> Result<(), String> {
+    let _guard = activity_state
+        .lock
+        .lock()
+        .map_err(|e| i18n::tf("error.activity_journal_lock", &[&e]))?;
+
+    let journal_path = storage::workspace_data_dir(app, root)?.join(JOURNAL_FILE);
+    let mut journal: ActivityJournal = storage::read_json(&journal_path);
+
+    let day = journal
+        .entry(day_key(Local::now().date_naive()))
+        .or_default();
+    let bucket = match kind {
+        ActivityKind::Create => &mut day.created,
+        ActivityKind::Edit => &mut day.edited,
+    };
+
+    // Autosave fires often; only touch the disk the first time a note shows up today
+    if !bucket.insert(path.to_string_lossy().to_string()) {
+        return Ok(());
+    }
+
+    storage::write_json(&journal_path, &journal)
+}
+
+#[tauri::command]
+pub async fn get_activity_heatmap(
+    folder_path: String,
+    range: Option<HeatmapRange>,
+    app: AppHandle,
+    index_state: State<'_, IndexState>,
+    activity_state: State<'_, ActivityState>,
+) -> Result<ActivityHeatmap, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err(i18n::t("error.folder_missing"));
+    }
+
+    let today = Local::now().date_naive();
+    let (start, end) = match range {
+        Some(HeatmapRange { start, end }) => {
+            let end = end.as_deref().map(parse_day).transpose()?.unwrap_or(today);
+            let start = start
+                .as_deref()
+                .map(parse_day)
+                .transpose()?
+                .unwrap_or(end - Duration::days(DEFAULT_RANGE_DAYS - 1));
+            (start, end)
+        }
+        None => (today - Duration::days(DEFAULT_RANGE_DAYS - 1), today),
+    };
+
+    if start > end {
+        return Err(i18n::t("error.range_reversed"));
+    }
+    if (end - start).num_days() >= MAX_RANGE_DAYS {
+        return Err(i18n::t("error.range_too_large"));
+    }
+
+    let mut buckets: BTreeMap<String, DayActivity> = BTreeMap::new();
+
+    index_state.with_workspace(&root, |index| {
+        for note in index.notes.values() {
+            let path = note.path.to_string_lossy().to_string();
+            if let Some(created) = note.created {
+                let day = DateTime::<Local>::from(created).date_naive();
+                buckets
+                    .entry(day_key(day))
+                    .or_default()
+                    .created
+                    .insert(path.clone());
+            }
+            if let Some(modified) = note.modified {
+                let day = DateTime::<Local>::from(modified).date_naive();
+                buckets.entry(day_key(day)).or_default().edited.insert(path);
+            }
+        }
+    })?;
+
+    let journal: ActivityJournal = {
+        let _guard = activity_state
+            .lock
+            .lock()
+            .map_err(|e| i18n::tf("error.activity_journal_lock", &[&e]))?;
+        storage::read_json(&storage::workspace_data_dir(&app, &root)?.join(JOURNAL_FILE))
+    };
+    for (date, activity) in journal {
+        let bucket = buckets.entry(date).or_default();
+        bucket.created.extend(activity.created);
+        bucket.edited.extend(activity.edited);
+    }
+
+    let mut days = Vec::new();
+    let mut day = start;
+    while day <= end {
+        let key = day_key(day);
+        let (created, edited) = buckets
+            .get(&key)
+            .map(|activity| (activity.created.len(), activity.edited.len()))
+            .unwrap_or((0, 0));
+        days.push(HeatmapDay {
+            date: key,
+            created,
+            edited,
+        });
+        day += Duration::days(1);
+    }
+
+    let max_count = days
+        .iter()
+        .map(|day| day.created + day.edited)
+        .max()
+        .unwrap_or(0);
+
+    Ok(ActivityHeatmap {
+        start: day_key(start),
+        end: day_key(end),
+        max_count,
+        days,
+    })
+}
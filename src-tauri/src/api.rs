@@ -0,0 +1,841 @@
+use crate::activity::ActivityState;
+use crate::clipper;
+use crate::i18n;
+use crate::ics;
+use crate::index::IndexState;
+use crate::markdown;
+use crate::mcp;
+use crate::secrets;
+use crate::settings::{ApiSettings, SettingsState};
+use crate::vault;
+use crate::watcher::WatcherState;
+use crate::windows;
+use crate::writing::WritingState;
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+const TOKEN_SECRET: &str = "api.token";
+// The clipper gets a token of its own, so a browser extension can only add notes
+const CLIPPER_TOKEN_SECRET: &str = "api.clipper_token";
+// Calendar apps subscribe by URL alone, so the tasks feed token goes in the query
+// string and unlocks nothing else
+const TASKS_FEED_TOKEN_SECRET: &str = "api.tasks_feed_token";
+// Saves from the API aren't tied to a window, so every window reloads the note
+const ORIGIN: &str = "api";
+const MAX_HEADER_LINES: usize = 64;
+const MAX_LINE_BYTES: usize = 8 * 1024;
+// Connections past this get a 503 instead of a thread of their own
+const MAX_CONNECTIONS: usize = 16;
+const MAX_BODY_BYTES: usize = 5 * 1024 * 1024;
+const MAX_SEARCH_RESULTS: usize = 50;
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct RunningServer {
+    port: u16,
+    stop: Arc<AtomicBool>,
+}
+
+// An opt-in HTTP server on 127.0.0.1 so scripts and launchers can reach the
+// notes of the workspace open in the main window
+#[derive(Default)]
+pub struct ApiState {
+    server: Mutex<Option<RunningServer>>,
+    token: Mutex<Option<String>>,
+    clipper_token: Mutex<Option<String>>,
+    tasks_feed_token: Mutex<Option<String>>,
+    // Why the server last failed to start, e.g. the port being taken
+    error: Mutex<Option<String>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiStatus {
+    running: bool,
+    port: u16,
+    url: String,
+    error: Option<String>,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+const JSON_TYPE: &str = "application/json; charset=utf-8";
+const CALENDAR_TYPE: &str = "text/calendar; charset=utf-8";
+
+struct Response {
+    status: u16,
+    // A string body is sent as is when `content_type` isn't JSON
+    body: Value,
+    content_type: &'static str,
+}
+
+impl Response {
+    fn new(status: u16, body: Value) -> Response {
+        Response {
+            status,
+            body,
+            content_type: JSON_TYPE,
+        }
+    }
+
+    fn ok(body: Value) -> Response {
+        Response::new(200, body)
+    }
+
+    // Error bodies are read by scripts, so they stay in English whatever the locale
+    fn error(status: u16, message: impl Into<String>) -> Response {
+        Response::new(status, json!({ "error": message.into() }))
+    }
+}
+
+fn status_text(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Tokens are created on first use and kept in the credential store
+fn load_token(
+    app: &AppHandle,
+    secret: &str,
+    cache: &Mutex<Option<String>>,
+) -> Result<String, String> {
+    if let Some(token) = cache.lock().ok().and_then(|token| token.clone()) {
+        return Ok(token);
+    }
+    let token = match secrets::get(app, secret)? {
+        Some(token) => token,
+        None => {
+            let token = generate_token();
+            secrets::set(app, secret, &token)?;
+            token
+        }
+    };
+    if let Ok(mut cached) = cache.lock() {
+        *cached = Some(token.clone());
+    }
+    Ok(token)
+}
+
+fn replace_token(
+    app: &AppHandle,
+    secret: &str,
+    cache: &Mutex<Option<String>>,
+) -> Result<String, String> {
+    let token = generate_token();
+    secrets::set(app, secret, &token)?;
+    if let Ok(mut cached) = cache.lock() {
+        *cached = Some(token.clone());
+    }
+    Ok(token)
+}
+
+// Compares every byte so response timing doesn't leak how much of a guess matched
+fn token_matches(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected
+            .bytes()
+            .zip(given.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn decode_component(text: &str) -> String {
+    markdown::percent_decode(&text.replace('+', " "))
+}
+
+// Reads one line of the request head without buffering more than
+// `MAX_LINE_BYTES`, so an endless line can't grow memory. `None` means too long
+fn read_line(reader: &mut impl BufRead, line: &mut String) -> Result<Option<()>, Response> {
+    line.clear();
+    let read = reader
+        .take(MAX_LINE_BYTES as u64)
+        .read_line(line)
+        .map_err(|_| Response::error(400, "Malformed request"))?;
+    if read == MAX_LINE_BYTES && !line.ends_with('\n') {
+        return Ok(None);
+    }
+    Ok(Some(()))
+}
+
+fn read_request(stream: &TcpStream) -> Result<Request, Response> {
+    let bad = |_| Response::error(400, "Malformed request");
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    if read_line(&mut reader, &mut line)?.is_none() {
+        return Err(Response::error(400, "Request line is too long"));
+    }
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(Response::error(400, "Malformed request"));
+    };
+    let method = method.to_string();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let path = path.to_string();
+    let query = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('=').or(Some((pair, ""))))
+        .filter(|(key, _)| !key.is_empty())
+        .map(|(key, value)| (decode_component(key), decode_component(value)))
+        .collect();
+
+    let mut headers = HashMap::new();
+    let mut complete = false;
+    for _ in 0..=MAX_HEADER_LINES {
+        if read_line(&mut reader, &mut line)?.is_none() {
+            return Err(Response::error(431, "Request header is too long"));
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            complete = true;
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+    if !complete {
+        return Err(Response::error(431, "Too many request headers"));
+    }
+
+    let length = headers
+        .get("content-length")
+        .map(|length| length.parse::<usize>())
+        .transpose()
+        .map_err(|_| Response::error(400, "Invalid Content-Length"))?
+        .unwrap_or(0);
+    if length > MAX_BODY_BYTES {
+        return Err(Response::error(413, "Request body is too large"));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).map_err(bad)?;
+
+    Ok(Request {
+        method,
+        path,
+        query,
+        headers,
+        body,
+    })
+}
+
+fn write_response(mut stream: &TcpStream, response: Response) {
+    let body = match &response.body {
+        Value::Null => String::new(),
+        Value::String(text) if response.content_type != JSON_TYPE => text.clone(),
+        body => body.to_string(),
+    };
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        status_text(response.status),
+        response.content_type,
+        body.len()
+    );
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(body.as_bytes());
+    let _ = stream.flush();
+}
+
+// Browsers send an Origin header and may reach localhost through a rebound DNS
+// name, so anything that looks like a web page is turned away before auth.
+// Extensions are the exception, and only for the clipper
+fn check_caller(request: &Request, port: u16) -> Result<(), Response> {
+    if let Some(origin) = request.headers.get("origin") {
+        let from_extension = clipper::EXTENSION_SCHEMES
+            .iter()
+            .any(|scheme| origin.starts_with(scheme));
+        if !(from_extension && request.path == "/clip") {
+            return Err(Response::error(403, "Browser requests are not allowed"));
+        }
+    }
+    let host = request.headers.get("host").map(String::as_str);
+    let allowed = [format!("127.0.0.1:{}", port), format!("localhost:{}", port)];
+    if !host.is_some_and(|host| allowed.iter().any(|allowed| allowed == host)) {
+        return Err(Response::error(403, "Unexpected Host header"));
+    }
+    Ok(())
+}
+
+fn check_token(
+    app: &AppHandle,
+    request: &Request,
+    secret: &str,
+    cache: &Mutex<Option<String>>,
+) -> Result<(), Response> {
+    let expected = load_token(app, secret, cache).map_err(|e| Response::error(500, e))?;
+    let given = request
+        .headers
+        .get("authorization")
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    if token_matches(&expected, given.trim()) {
+        Ok(())
+    } else {
+        Err(Response::error(401, "Missing or invalid API token"))
+    }
+}
+
+fn active_root(app: &AppHandle) -> Result<PathBuf, Response> {
+    let root = app
+        .state::<WatcherState>()
+        .active_root(windows::MAIN_WINDOW)
+        .ok_or_else(|| Response::error(503, "No workspace is open"))?;
+    if vault::is_vault_root(&root) {
+        return Err(Response::error(
+            403,
+            "The API can't access encrypted vaults",
+        ));
+    }
+    Ok(root)
+}
+
+// Note paths are workspace-relative, like `Projects/Plan.md`
+pub fn note_path(root: &Path, raw: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(raw.trim_start_matches('/'));
+    if relative.as_os_str().is_empty()
+        || relative
+            .components()
+            .any(|part| !matches!(part, Component::Normal(_)))
+    {
+        return Err("Path must be inside the workspace".to_string());
+    }
+    let path = root.join(relative);
+    if vault::vault_root(&path).is_some() {
+        return Err("The API can't access encrypted vaults".to_string());
+    }
+    Ok(path)
+}
+
+pub fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn ensure_note(app: &AppHandle, path: &Path) -> Result<(), Response> {
+    let extensions = app.state::<SettingsState>().current().watched_extensions;
+    if markdown::has_note_extension(path, &extensions) {
+        Ok(())
+    } else {
+        Err(Response::error(400, "Only notes can be written"))
+    }
+}
+
+fn list_notes(app: &AppHandle, root: &Path) -> Response {
+    let notes = app.state::<IndexState>().with_workspace(root, |index| {
+        let mut notes: Vec<Value> = index
+            .notes
+            .values()
+            .map(|note| {
+                json!({
+                    "path": relative_path(root, &note.path),
+                    "title": note.title,
+                    "tags": note.tags,
+                    "modified": note.modified.map(|time| {
+                        chrono::DateTime::<chrono::Local>::from(time).to_rfc3339()
+                    }),
+                })
+            })
+            .collect();
+        notes.sort_by(|a, b| a["path"].as_str().cmp(&b["path"].as_str()));
+        notes
+    });
+    match notes {
+        Ok(notes) => Response::ok(Value::Array(notes)),
+        Err(e) => Response::error(500, e),
+    }
+}
+
+fn read_note(root: &Path, path: &Path) -> Response {
+    match fs::read_to_string(path) {
+        Ok(content) => Response::ok(json!({
+            "path": relative_path(root, path),
+            "content": content,
+        })),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            Response::error(404, "Note not found")
+        }
+        Err(e) => Response::error(500, format!("Failed to read note: {}", e)),
+    }
+}
+
+// `PUT /notes/<path>` takes the raw markdown as its body and creates the note
+// when it doesn't exist yet
+fn put_note(app: &AppHandle, root: &Path, path: &Path, body: Vec<u8>) -> Response {
+    if let Err(response) = ensure_note(app, path) {
+        return response;
+    }
+    let Ok(content) = String::from_utf8(body) else {
+        return Response::error(400, "Note content must be UTF-8");
+    };
+    if !path.parent().is_some_and(Path::is_dir) {
+        return Response::error(404, "Folder not found");
+    }
+    let created = !path.exists();
+
+    let result = crate::write_note(
+        app,
+        ORIGIN,
+        &path.to_string_lossy(),
+        content,
+        &app.state::<WatcherState>(),
+        &app.state::<WritingState>(),
+        &app.state::<ActivityState>(),
+    );
+    match result {
+        Ok(()) => Response::new(
+            if created { 201 } else { 200 },
+            json!({ "path": relative_path(root, path) }),
+        ),
+        Err(e) => Response::error(409, e.into_message()),
+    }
+}
+
+// `POST /notes` with `{"name": ..., "content"?: ..., "folder"?: ...}` creates a
+// note next to any of the same name, the way New Note does
+fn create_note(app: &AppHandle, root: &Path, body: &[u8]) -> Response {
+    let Ok(params) = serde_json::from_slice::<Value>(body) else {
+        return Response::error(400, "Body must be a JSON object");
+    };
+    let Some(name) = params.get("name").and_then(Value::as_str) else {
+        return Response::error(400, "Missing name");
+    };
+    let name = if Path::new(name).extension().is_some() {
+        name.to_string()
+    } else {
+        format!("{}.md", name)
+    };
+    if let Err(response) = ensure_note(app, Path::new(&name)) {
+        return response;
+    }
+    let folder = match params.get("folder").and_then(Value::as_str) {
+        Some(folder) if !folder.trim().is_empty() => match note_path(root, folder) {
+            Ok(folder) => folder,
+            Err(e) => return Response::error(400, e),
+        },
+        _ => root.to_path_buf(),
+    };
+
+    let result = crate::create_markdown_file(
+        folder.to_string_lossy().to_string(),
+        name,
+        params
+            .get("content")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        app.clone(),
+    );
+    match result {
+        Ok(path) => Response::new(
+            201,
+            json!({ "path": relative_path(root, Path::new(&path)) }),
+        ),
+        Err(e) => Response::error(400, e),
+    }
+}
+
+// Every query word must appear in the note's text or title; more hits rank higher
+pub fn search_notes(
+    app: &AppHandle,
+    root: &Path,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<Value>, String> {
+    let words: Vec<String> = markdown::tokenize(query).collect();
+    if words.is_empty() {
+        return Err("Missing search query".to_string());
+    }
+
+    app.state::<IndexState>().with_workspace(root, |index| {
+        let mut results: Vec<(u32, Value)> = index
+            .notes
+            .values()
+            .filter_map(|note| {
+                let title = note.title.to_lowercase();
+                let mut score = 0;
+                for word in &words {
+                    let in_title = title.contains(word.as_str());
+                    let hits = note.terms.get(word).copied().unwrap_or(0);
+                    if !in_title && hits == 0 {
+                        return None;
+                    }
+                    score += hits + if in_title { 10 } else { 0 };
+                }
+                Some((
+                    score,
+                    json!({
+                        "path": relative_path(root, &note.path),
+                        "title": note.title,
+                        "score": score,
+                    }),
+                ))
+            })
+            .collect();
+        results.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        results
+            .into_iter()
+            .take(limit)
+            .map(|(_, result)| result)
+            .collect()
+    })
+}
+
+// The Model Context Protocol over its Streamable HTTP transport: one JSON-RPC
+// message per POST, answered with plain JSON rather than an event stream
+fn mcp_response(app: &AppHandle, root: Option<&Path>, body: &[u8]) -> Response {
+    if !app.state::<SettingsState>().current().api.mcp_enabled {
+        return Response::error(404, "The MCP server is turned off");
+    }
+    let message = match serde_json::from_slice::<Value>(body) {
+        Ok(message) => message,
+        Err(e) => return Response::new(400, mcp::parse_error(&e.to_string())),
+    };
+    match mcp::handle(app, root, message) {
+        Some(reply) => Response::ok(reply),
+        // Notifications and responses get no reply
+        None => Response::new(202, Value::Null),
+    }
+}
+
+// `POST /clip` from the browser extension saves a page or selection as a note
+fn clip_response(app: &AppHandle, request: &Request) -> Response {
+    if !app.state::<SettingsState>().current().api.clipper_enabled {
+        return Response::error(404, "The web clipper is turned off");
+    }
+    if let Err(response) = check_token(
+        app,
+        request,
+        CLIPPER_TOKEN_SECRET,
+        &app.state::<ApiState>().clipper_token,
+    ) {
+        return response;
+    }
+    if request.method != "POST" {
+        return Response::error(405, "Method not allowed");
+    }
+    let root = match active_root(app) {
+        Ok(root) => root,
+        Err(response) => return response,
+    };
+    let clip = match serde_json::from_slice::<clipper::Clip>(&request.body) {
+        Ok(clip) => clip,
+        Err(_) => return Response::error(400, "Body must be a JSON object"),
+    };
+    match clipper::save(app, &root, clip) {
+        Ok(saved) => Response::new(
+            if saved.appended { 200 } else { 201 },
+            json!({
+                "path": relative_path(&root, Path::new(&saved.path)),
+                "title": saved.title,
+                "appended": saved.appended,
+            }),
+        ),
+        Err(e) => Response::error(400, e),
+    }
+}
+
+// `GET /tasks.ics?token=...` is the workspace's dated tasks as a calendar to
+// subscribe to; `&todos=1` sends them as VTODOs instead of all-day events
+fn tasks_feed_response(app: &AppHandle, request: &Request) -> Response {
+    if !app
+        .state::<SettingsState>()
+        .current()
+        .api
+        .tasks_feed_enabled
+    {
+        return Response::error(404, "The tasks calendar is turned off");
+    }
+    if request.method != "GET" {
+        return Response::error(405, "Method not allowed");
+    }
+    let expected = match load_token(
+        app,
+        TASKS_FEED_TOKEN_SECRET,
+        &app.state::<ApiState>().tasks_feed_token,
+    ) {
+        Ok(token) => token,
+        Err(e) => return Response::error(500, e),
+    };
+    let given = request.query.get("token").map(String::as_str).unwrap_or("");
+    if !token_matches(&expected, given) {
+        return Response::error(401, "Missing or invalid calendar token");
+    }
+    let root = match active_root(app) {
+        Ok(root) => root,
+        Err(response) => return response,
+    };
+    let as_todos = request
+        .query
+        .get("todos")
+        .is_some_and(|value| value == "1" || value == "true");
+    match ics::tasks_calendar(&app.state::<IndexState>(), &root, as_todos, false) {
+        Ok((calendar, _)) => Response {
+            status: 200,
+            body: Value::String(calendar),
+            content_type: CALENDAR_TYPE,
+        },
+        Err(e) => Response::error(500, e),
+    }
+}
+
+fn route(app: &AppHandle, request: Request) -> Response {
+    // Lets tools check the server is up without a token
+    if request.method == "GET" && request.path == "/" {
+        return Response::ok(json!({
+            "name": "Marky",
+            "version": app.package_info().version.to_string(),
+        }));
+    }
+    if request.path == "/clip" {
+        return clip_response(app, &request);
+    }
+    if request.path == "/tasks.ics" {
+        return tasks_feed_response(app, &request);
+    }
+    if let Err(response) = check_token(app, &request, TOKEN_SECRET, &app.state::<ApiState>().token)
+    {
+        return response;
+    }
+    // Assistants connect before a workspace may be open; tools report it instead
+    if request.path == "/mcp" {
+        return match request.method.as_str() {
+            "POST" => mcp_response(app, active_root(app).ok().as_deref(), &request.body),
+            _ => Response::error(405, "This server doesn't stream events"),
+        };
+    }
+    let root = match active_root(app) {
+        Ok(root) => root,
+        Err(response) => return response,
+    };
+
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/notes") | ("GET", "/notes/") => list_notes(app, &root),
+        ("POST", "/notes") | ("POST", "/notes/") => create_note(app, &root, &request.body),
+        ("GET", "/search") => {
+            let query = request.query.get("q").map(String::as_str).unwrap_or("");
+            match search_notes(app, &root, query, MAX_SEARCH_RESULTS) {
+                Ok(results) => Response::ok(Value::Array(results)),
+                Err(e) => Response::error(400, e),
+            }
+        }
+        (method, path) if path.starts_with("/notes/") => {
+            let raw = markdown::percent_decode(&path["/notes/".len()..]);
+            let path = match note_path(&root, &raw) {
+                Ok(path) => path,
+                Err(e) => return Response::error(400, e),
+            };
+            match method {
+                "GET" => read_note(&root, &path),
+                "PUT" => put_note(app, &root, &path, request.body),
+                _ => Response::error(405, "Method not allowed"),
+            }
+        }
+        _ => Response::error(404, "Not found"),
+    }
+}
+
+// Frees a connection slot when its handler thread ends, however it ends
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn handle(app: &AppHandle, stream: TcpStream, port: u16) {
+    let response = match read_request(&stream) {
+        Ok(request) => match check_caller(&request, port) {
+            Ok(()) => {
+                tracing::debug!("API {} {}", request.method, request.path);
+                route(app, request)
+            }
+            Err(response) => response,
+        },
+        Err(response) => response,
+    };
+    write_response(&stream, response);
+}
+
+fn start(app: &AppHandle, port: u16) -> Result<RunningServer, String> {
+    load_token(app, TOKEN_SECRET, &app.state::<ApiState>().token)?;
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .map_err(|e| i18n::tf("error.api_start", &[&port, &e]))?;
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let handle_app = app.clone();
+    let handle_stop = stop.clone();
+    let connections = Arc::new(AtomicUsize::new(0));
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            if handle_stop.load(Ordering::SeqCst) {
+                break;
+            }
+            let Ok(stream) = stream else {
+                continue;
+            };
+            let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+            let _ = stream.set_write_timeout(Some(READ_TIMEOUT));
+            if connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                connections.fetch_sub(1, Ordering::SeqCst);
+                write_response(&stream, Response::error(503, "Too many connections"));
+                continue;
+            }
+            let slot = ConnectionSlot(connections.clone());
+            let app = handle_app.clone();
+            thread::spawn(move || {
+                let _slot = slot;
+                handle(&app, stream, port);
+            });
+        }
+        tracing::info!("Local API stopped");
+    });
+
+    tracing::info!("Local API listening on 127.0.0.1:{}", port);
+    Ok(RunningServer { port, stop })
+}
+
+fn stop(server: RunningServer) {
+    server.stop.store(true, Ordering::SeqCst);
+    // `accept` only returns on a connection, so make one
+    let _ = TcpStream::connect((Ipv4Addr::LOCALHOST, server.port));
+}
+
+// Starts, stops, or moves the server to match the `api` settings
+pub fn apply(app: &AppHandle, settings: &ApiSettings) {
+    let state = app.state::<ApiState>();
+    let Ok(mut server) = state.server.lock() else {
+        return;
+    };
+
+    let wanted = settings.enabled.then_some(settings.port);
+    if server.as_ref().map(|server| server.port) == wanted {
+        return;
+    }
+    if let Some(running) = server.take() {
+        stop(running);
+    }
+
+    let mut error = None;
+    if let Some(port) = wanted {
+        match start(app, port) {
+            Ok(running) => *server = Some(running),
+            Err(e) => {
+                tracing::error!("{}", e);
+                error = Some(e);
+            }
+        }
+    }
+    let Ok(mut last) = state.error.lock() else {
+        return;
+    };
+    *last = error;
+}
+
+#[tauri::command]
+pub fn get_api_status(
+    api_state: State<ApiState>,
+    settings_state: State<SettingsState>,
+) -> ApiStatus {
+    let port = settings_state.current().api.port;
+    let running = api_state
+        .server
+        .lock()
+        .map(|server| server.is_some())
+        .unwrap_or(false);
+    ApiStatus {
+        running,
+        port,
+        url: format!("http://127.0.0.1:{}", port),
+        error: api_state.error.lock().ok().and_then(|error| error.clone()),
+    }
+}
+
+#[tauri::command]
+pub fn get_api_token(app: AppHandle, api_state: State<ApiState>) -> Result<String, String> {
+    load_token(&app, TOKEN_SECRET, &api_state.token)
+}
+
+// The old token stops working immediately
+#[tauri::command]
+pub fn regenerate_api_token(app: AppHandle, api_state: State<ApiState>) -> Result<String, String> {
+    replace_token(&app, TOKEN_SECRET, &api_state.token)
+}
+
+// The token the browser extension is paired with
+#[tauri::command]
+pub fn get_clipper_token(app: AppHandle, api_state: State<ApiState>) -> Result<String, String> {
+    load_token(&app, CLIPPER_TOKEN_SECRET, &api_state.clipper_token)
+}
+
+// Unpairs every browser the old token was pasted into
+#[tauri::command]
+pub fn regenerate_clipper_token(
+    app: AppHandle,
+    api_state: State<ApiState>,
+) -> Result<String, String> {
+    replace_token(&app, CLIPPER_TOKEN_SECRET, &api_state.clipper_token)
+}
+
+// The subscription URL for the tasks calendar, token included
+#[tauri::command]
+pub fn get_tasks_feed_url(
+    app: AppHandle,
+    api_state: State<ApiState>,
+    settings_state: State<SettingsState>,
+) -> Result<String, String> {
+    let token = load_token(&app, TASKS_FEED_TOKEN_SECRET, &api_state.tasks_feed_token)?;
+    Ok(format!(
+        "http://127.0.0.1:{}/tasks.ics?token={}",
+        settings_state.current().api.port,
+        token
+    ))
+}
+
+// Calendars subscribed with the old URL stop updating
+#[tauri::command]
+pub fn regenerate_tasks_feed_token(
+    app: AppHandle,
+    api_state: State<ApiState>,
+    settings_state: State<SettingsState>,
+) -> Result<String, String> {
+    replace_token(&app, TASKS_FEED_TOKEN_SECRET, &api_state.tasks_feed_token)?;
+    get_tasks_feed_url(app, api_state, settings_state)
+}
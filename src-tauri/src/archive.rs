@@ -0,0 +1,114 @@
+use crate::network;
+use crate::settings::SettingsState;
+use crate::storage;
+use crate::watcher::WatcherState;
+use crate::workspace_config;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+// The archive mirrors the workspace layout, so `Projects/old.md` lands in `Archive/Projects/old.md`
+fn archive_dir(root: &Path, settings_state: &SettingsState) -> Result<PathBuf, String> {
+    let folder = workspace_config::effective(root, &settings_state.current()).archive_folder;
+    let relative = Path::new(&folder);
+    if relative
+        .components()
+        .any(|part| !matches!(part, Component::Normal(_)))
+    {
+        return Err(format!("Invalid archive folder: {}", folder));
+    }
+
+    Ok(root.join(relative))
+}
+
+fn move_into(source: &Path, dest_folder: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(dest_folder)
+        .map_err(|e| format!("Failed to create archive folder: {}", e))?;
+
+    let file_name = source
+        .file_name()
+        .ok_or("Invalid source name")?
+        .to_string_lossy()
+        .to_string();
+    let (target, _) = crate::resolve_unique_path(dest_folder, &file_name, source.is_dir())?;
+
+    fs::rename(source, &target).map_err(|e| format!("Failed to move entry: {}", e))?;
+    Ok(target)
+}
+
+// Unarchiving would otherwise leave a trail of empty mirror folders behind
+fn remove_empty_parents(path: &Path, stop_at: &Path) {
+    let mut current = path.parent();
+    while let Some(dir) = current {
+        if dir == stop_at || !dir.starts_with(stop_at) || fs::remove_dir(dir).is_err() {
+            break;
+        }
+        current = dir.parent();
+    }
+}
+
+#[tauri::command]
+pub fn archive_note(path: String, app: AppHandle) -> Result<String, String> {
+    let source = PathBuf::from(path);
+    storage::ensure_accessible(&app, &source)?;
+    network::guard(&app, &source.clone(), move |app| archive(app, &source))?
+}
+
+fn archive(app: &AppHandle, source: &Path) -> Result<String, String> {
+    let watcher_state = app.state::<WatcherState>();
+    if !source.exists() {
+        return Err("Path does not exist".to_string());
+    }
+
+    let root = watcher_state
+        .root_for(source)
+        .ok_or("Path is not inside an open workspace")?;
+    let archive = archive_dir(&root, &app.state::<SettingsState>())?;
+
+    if source.starts_with(&archive) {
+        return Err("Already archived".to_string());
+    }
+    if archive.starts_with(source) {
+        return Err("Cannot archive a folder that contains the archive".to_string());
+    }
+
+    let relative = source
+        .parent()
+        .and_then(|parent| parent.strip_prefix(&root).ok())
+        .ok_or("Path is not inside an open workspace")?;
+    let target = move_into(source, &archive.join(relative))?;
+    crate::update_metadata_after_move(app, &watcher_state, source, &target);
+
+    Ok(target.to_string_lossy().to_string())
+}
+
+// Restores to the original folder, recreating it if it was removed in the meantime
+#[tauri::command]
+pub fn unarchive_note(path: String, app: AppHandle) -> Result<String, String> {
+    let source = PathBuf::from(path);
+    storage::ensure_accessible(&app, &source)?;
+    network::guard(&app, &source.clone(), move |app| unarchive(app, &source))?
+}
+
+fn unarchive(app: &AppHandle, source: &Path) -> Result<String, String> {
+    let watcher_state = app.state::<WatcherState>();
+    if !source.exists() {
+        return Err("Path does not exist".to_string());
+    }
+
+    let root = watcher_state
+        .root_for(source)
+        .ok_or("Path is not inside an open workspace")?;
+    let archive = archive_dir(&root, &app.state::<SettingsState>())?;
+
+    let relative = source
+        .parent()
+        .and_then(|parent| parent.strip_prefix(&archive).ok())
+        .filter(|_| source != archive)
+        .ok_or("Path is not archived")?;
+    let target = move_into(source, &root.join(relative))?;
+    remove_empty_parents(source, &archive);
+    crate::update_metadata_after_move(app, &watcher_state, source, &target);
+
+    Ok(target.to_string_lossy().to_string())
+}
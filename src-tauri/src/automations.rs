@@ -0,0 +1,511 @@
+use crate::activity::ActivityState;
+use crate::embeds;
+use crate::export;
+use crate::markdown;
+use crate::safe_mode::SafeModeState;
+use crate::settings::SettingsState;
+use crate::storage;
+use crate::watcher::WatcherState;
+use crate::workspace_config::{self, CONFIG_DIR};
+use crate::writing::WritingState;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+const AUTOMATIONS_FILE: &str = "automations.json";
+const TRIGGERS: [&str; 2] = ["save", "create"];
+const ACTIONS: [&str; 3] = ["export_html", "apply_template", "add_tag"];
+const DEFAULT_EXPORT_FOLDER: &str = "Exports";
+const MAX_LOG_ENTRIES: usize = 200;
+const ORIGIN: &str = "automations";
+
+thread_local! {
+    // Rules save notes too; those saves mustn't set off the rules again
+    static RUNNING: Cell<bool> = const { Cell::new(false) };
+}
+
+// One rule: when `trigger` fires for a note matching `tag` and `folder`, run
+// `action`. Dry-run rules only log what they would have done.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutomationRule {
+    pub id: String,
+    pub name: String,
+    pub enabled: bool,
+    pub dry_run: bool,
+    pub trigger: String,
+    // Without the `#`; the note must carry it
+    pub tag: Option<String>,
+    // Relative to the workspace root; the note must be somewhere inside it
+    pub folder: Option<String>,
+    pub action: String,
+    // `apply_template`: a template in the workspace's templates folder
+    pub template: Option<String>,
+    // `add_tag`: the tag to add, without the `#`
+    pub add_tag: Option<String>,
+    // `export_html`: where exports go, relative to the workspace root
+    pub export_folder: Option<String>,
+}
+
+// Stored in the workspace, next to its config, so rules travel with the notes
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct AutomationsFile {
+    rules: Vec<AutomationRule>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AutomationLogEntry {
+    time: String,
+    root: String,
+    rule_id: String,
+    rule_name: String,
+    trigger: String,
+    path: String,
+    action: String,
+    dry_run: bool,
+    // `done`, `skipped`, or `failed`
+    outcome: String,
+    message: String,
+}
+
+// Recent runs, newest last, for the Automations settings log
+#[derive(Default)]
+pub struct AutomationsState {
+    log: Mutex<VecDeque<AutomationLogEntry>>,
+}
+
+impl AutomationsState {
+    fn record(&self, entry: AutomationLogEntry) {
+        if let Ok(mut log) = self.log.lock() {
+            log.push_back(entry);
+            while log.len() > MAX_LOG_ENTRIES {
+                log.pop_front();
+            }
+        }
+    }
+}
+
+fn automations_path(root: &Path) -> PathBuf {
+    root.join(CONFIG_DIR).join(AUTOMATIONS_FILE)
+}
+
+fn load(root: &Path) -> Vec<AutomationRule> {
+    storage::read_json::<AutomationsFile>(&automations_path(root)).rules
+}
+
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').to_lowercase()
+}
+
+fn valid_tag(tag: &str) -> bool {
+    !tag.is_empty()
+        && tag
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+// Rule folders are workspace-relative and must stay inside it
+fn relative_folder(root: &Path, folder: &str) -> Result<PathBuf, String> {
+    let folder = folder.trim().trim_matches(['/', '\\']);
+    let relative = Path::new(folder);
+    if relative
+        .components()
+        .any(|part| !matches!(part, Component::Normal(_)))
+    {
+        return Err(format!("Folder must be inside the workspace: {}", folder));
+    }
+    Ok(root.join(relative))
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn validate(root: &Path, rule: &mut AutomationRule) -> Result<(), String> {
+    rule.name = rule.name.trim().to_string();
+    if rule.name.is_empty() {
+        return Err("Automation needs a name".to_string());
+    }
+    rule.trigger = rule.trigger.trim().to_lowercase();
+    if !TRIGGERS.contains(&rule.trigger.as_str()) {
+        return Err(format!("Unknown automation trigger: {}", rule.trigger));
+    }
+    rule.action = rule.action.trim().to_lowercase();
+    if !ACTIONS.contains(&rule.action.as_str()) {
+        return Err(format!("Unknown automation action: {}", rule.action));
+    }
+
+    rule.tag = non_empty(&rule.tag).map(normalize_tag);
+    if let Some(tag) = &rule.tag {
+        if !valid_tag(tag) {
+            return Err(format!("Invalid tag: {}", tag));
+        }
+    }
+    if let Some(folder) = non_empty(&rule.folder) {
+        relative_folder(root, folder)?;
+    }
+    rule.folder = non_empty(&rule.folder).map(str::to_string);
+
+    match rule.action.as_str() {
+        "apply_template" => {
+            if non_empty(&rule.template).is_none() {
+                return Err("Pick a template to apply".to_string());
+            }
+        }
+        "add_tag" => {
+            let tag = non_empty(&rule.add_tag)
+                .map(normalize_tag)
+                .filter(|tag| valid_tag(tag))
+                .ok_or("Enter a valid tag to add")?;
+            rule.add_tag = Some(tag);
+        }
+        _ => {
+            if let Some(folder) = non_empty(&rule.export_folder) {
+                relative_folder(root, folder)?;
+            }
+            rule.export_folder = non_empty(&rule.export_folder).map(str::to_string);
+        }
+    }
+    Ok(())
+}
+
+fn matches(root: &Path, rule: &AutomationRule, trigger: &str, path: &Path, content: &str) -> bool {
+    if !rule.enabled || rule.trigger != trigger {
+        return false;
+    }
+    if let Some(folder) = rule.folder.as_deref() {
+        match relative_folder(root, folder) {
+            Ok(folder) if path.starts_with(&folder) => {}
+            _ => return false,
+        }
+    }
+    match rule.tag.as_deref() {
+        Some(tag) => markdown::extract_tags(content).iter().any(|t| t == tag),
+        None => true,
+    }
+}
+
+// Same placeholders as meeting templates, minus the event-specific ones
+fn render_template(template: &str, path: &Path) -> String {
+    let now = Local::now();
+    [
+        ("{{title}}", markdown::note_title(path)),
+        ("{{date}}", now.format("%Y-%m-%d").to_string()),
+        ("{{time}}", now.format("%H:%M").to_string()),
+    ]
+    .iter()
+    .fold(template.to_string(), |text, (placeholder, value)| {
+        text.replace(placeholder, value)
+    })
+}
+
+// `name` is a file in the templates folder, with or without its extension
+fn find_template(folder: &Path, name: &str) -> Option<PathBuf> {
+    let name = name.trim().to_lowercase();
+    fs::read_dir(folder)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .find(|path| {
+            let matches = |part: Option<&std::ffi::OsStr>| {
+                part.is_some_and(|part| part.to_string_lossy().to_lowercase() == name)
+            };
+            matches(path.file_name()) || matches(path.file_stem())
+        })
+}
+
+// Whether `path`, with symlinks followed, is inside `root`
+fn resolves_inside(root: &Path, path: &Path) -> bool {
+    match (root.canonicalize(), path.canonicalize()) {
+        (Ok(root), Ok(path)) => path.starts_with(root),
+        _ => false,
+    }
+}
+
+// Rules edit notes the way any other writer does, so locks, encrypted notes
+// and vaults are respected
+fn write(app: &AppHandle, path: &Path, content: String) -> Result<(), String> {
+    crate::write_note(
+        app,
+        ORIGIN,
+        &path.to_string_lossy(),
+        content,
+        &app.state::<WatcherState>(),
+        &app.state::<WritingState>(),
+        &app.state::<ActivityState>(),
+    )
+    .map_err(|e| e.into_message())
+}
+
+// What happened, as the log's (outcome, message)
+type Outcome = (&'static str, String);
+
+fn run_action(
+    app: &AppHandle,
+    root: &Path,
+    rule: &AutomationRule,
+    path: &Path,
+    content: &str,
+) -> Result<Outcome, String> {
+    match rule.action.as_str() {
+        "export_html" => {
+            let folder = relative_folder(
+                root,
+                rule.export_folder
+                    .as_deref()
+                    .unwrap_or(DEFAULT_EXPORT_FOLDER),
+            )?;
+            // Mirror the note's folders so equally named notes don't collide
+            let relative = path
+                .parent()
+                .and_then(|parent| parent.strip_prefix(root).ok())
+                .unwrap_or(Path::new(""));
+            let target = folder
+                .join(relative)
+                .join(format!("{}.html", markdown::note_title(path)));
+            if rule.dry_run {
+                return Ok(("done", format!("Would export to {}", target.display())));
+            }
+
+            let composed = embeds::inline_embeds(app, path, content);
+            let html =
+                export::html_document(&markdown::note_title(path), &markdown::to_html(&composed));
+            export::write_html(&target, &html)?;
+            Ok(("done", format!("Exported to {}", target.display())))
+        }
+        "apply_template" => {
+            // Only fills new notes; anything already written is the user's
+            if !content.trim().is_empty() {
+                return Ok(("skipped", "Note already has content".to_string()));
+            }
+            let settings = app.state::<SettingsState>().current();
+            let config = workspace_config::effective(root, &settings);
+            let name = rule.template.as_deref().unwrap_or_default();
+            let folder = config
+                .templates_folder
+                .ok_or("No templates folder is set for this workspace")?;
+            let folder = relative_folder(root, &folder)?;
+            let template = find_template(&folder, name)
+                .ok_or_else(|| format!("Template not found: {}", name))?;
+            if !resolves_inside(root, &template) {
+                return Err(format!("Template must be inside the workspace: {}", name));
+            }
+            if rule.dry_run {
+                return Ok(("done", format!("Would apply {}", template.display())));
+            }
+
+            let template = fs::read_to_string(&template)
+                .map_err(|e| format!("Failed to read template: {}", e))?;
+            write(app, path, render_template(&template, path))?;
+            Ok(("done", format!("Applied template {}", name)))
+        }
+        _ => {
+            let tag = rule.add_tag.as_deref().unwrap_or_default();
+            if markdown::extract_tags(content).iter().any(|t| t == tag) {
+                return Ok(("skipped", format!("Note is already tagged #{}", tag)));
+            }
+            if rule.dry_run {
+                return Ok(("done", format!("Would add #{}", tag)));
+            }
+
+            let separator = if content.is_empty() || content.ends_with('\n') {
+                ""
+            } else {
+                "\n"
+            };
+            write(app, path, format!("{}{}#{}\n", content, separator, tag))?;
+            Ok(("done", format!("Added #{}", tag)))
+        }
+    }
+}
+
+fn run_rule(
+    app: &AppHandle,
+    root: &Path,
+    rule: AutomationRule,
+    trigger: &str,
+    path: &Path,
+    content: &str,
+) -> AutomationLogEntry {
+    let (outcome, message) = match run_action(app, root, &rule, path, content) {
+        Ok(result) => result,
+        Err(e) => {
+            tracing::warn!("Automation {} failed: {}", rule.name, e);
+            ("failed", e)
+        }
+    };
+    AutomationLogEntry {
+        time: Local::now().to_rfc3339(),
+        root: root.to_string_lossy().to_string(),
+        rule_id: rule.id,
+        rule_name: rule.name,
+        trigger: trigger.to_string(),
+        path: path.to_string_lossy().to_string(),
+        action: rule.action,
+        dry_run: rule.dry_run,
+        outcome: outcome.to_string(),
+        message,
+    }
+}
+
+fn run_rules(
+    app: &AppHandle,
+    root: &Path,
+    trigger: &str,
+    path: &Path,
+    force_dry_run: bool,
+) -> Vec<AutomationLogEntry> {
+    let mut entries = Vec::new();
+    for mut rule in load(root) {
+        rule.dry_run |= force_dry_run;
+        // Re-read so an earlier rule's template or tag is visible to the next
+        let Ok(content) = fs::read_to_string(path) else {
+            break;
+        };
+        if !matches(root, &rule, trigger, path, &content) {
+            continue;
+        }
+
+        entries.push(run_rule(app, root, rule, trigger, path, &content));
+    }
+
+    let state = app.state::<AutomationsState>();
+    for entry in &entries {
+        state.record(entry.clone());
+    }
+    entries
+}
+
+// Runs the workspace's rules for a note that was just saved or created. Rules
+// see the note as the trigger left it; later rules see earlier rules' edits.
+pub fn run(app: &AppHandle, root: &Path, trigger: &str, path: &Path) {
+    if app.state::<SafeModeState>().is_active() || RUNNING.with(Cell::get) {
+        return;
+    }
+    RUNNING.with(|running| running.set(true));
+    run_rules(app, root, trigger, path, false);
+    RUNNING.with(|running| running.set(false));
+}
+
+#[tauri::command]
+pub fn get_automations(folder_path: String, app: AppHandle) -> Result<Vec<AutomationRule>, String> {
+    let root = PathBuf::from(&folder_path);
+    storage::ensure_accessible(&app, &root)?;
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+    Ok(load(&root))
+}
+
+#[tauri::command]
+pub fn set_automations(
+    folder_path: String,
+    rules: Vec<AutomationRule>,
+    app: AppHandle,
+) -> Result<Vec<AutomationRule>, String> {
+    let root = PathBuf::from(&folder_path);
+    storage::ensure_accessible(&app, &root)?;
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+
+    let mut rules = rules;
+    let stamp = Local::now().timestamp_millis();
+    for (i, rule) in rules.iter_mut().enumerate() {
+        validate(&root, rule)?;
+        if rule.id.trim().is_empty() {
+            rule.id = format!("rule-{}-{}", stamp, i);
+        }
+    }
+
+    fs::create_dir_all(root.join(CONFIG_DIR))
+        .map_err(|e| format!("Failed to create workspace config folder: {}", e))?;
+    storage::write_json(&automations_path(&root), &AutomationsFile { rules })?;
+    Ok(load(&root))
+}
+
+// Runs every enabled rule for `trigger` against a note as a dry run, so rules
+// can be checked before they touch anything
+#[tauri::command]
+pub fn dry_run_automations(
+    folder_path: String,
+    file_path: String,
+    trigger: String,
+    app: AppHandle,
+) -> Result<Vec<AutomationLogEntry>, String> {
+    let root = PathBuf::from(&folder_path);
+    let path = PathBuf::from(&file_path);
+    storage::ensure_accessible(&app, &path)?;
+    if !path.starts_with(&root) || !path.is_file() {
+        return Err("Note is not in this workspace".to_string());
+    }
+    let trigger = trigger.trim().to_lowercase();
+    if !TRIGGERS.contains(&trigger.as_str()) {
+        return Err(format!("Unknown automation trigger: {}", trigger));
+    }
+    Ok(run_rules(&app, &root, &trigger, &path, true))
+}
+
+// Runs one rule, picked by id or name, on a note right away, whatever its
+// trigger and filters say; the Custom menu uses this. Disabled rules still
+// run since they were asked for by name, and dry-run rules stay dry runs.
+#[tauri::command]
+pub fn run_automation(
+    folder_path: String,
+    rule: String,
+    file_path: String,
+    app: AppHandle,
+) -> Result<AutomationLogEntry, String> {
+    if app.state::<SafeModeState>().is_active() {
+        return Err("Automations don't run in safe mode".to_string());
+    }
+    let root = PathBuf::from(&folder_path);
+    let path = PathBuf::from(&file_path);
+    storage::ensure_accessible(&app, &path)?;
+    if !path.starts_with(&root) || !path.is_file() {
+        return Err("Note is not in this workspace".to_string());
+    }
+    let wanted = rule.trim();
+    let rule = load(&root)
+        .into_iter()
+        .find(|candidate| candidate.id == wanted || candidate.name.eq_ignore_ascii_case(wanted))
+        .ok_or_else(|| format!("No automation named {}", wanted))?;
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read note: {}", e))?;
+
+    let entry = run_rule(&app, &root, rule, "manual", &path, &content);
+    app.state::<AutomationsState>().record(entry.clone());
+    Ok(entry)
+}
+
+// Newest first, for one workspace
+#[tauri::command]
+pub fn get_automation_log(
+    folder_path: String,
+    automations_state: State<AutomationsState>,
+) -> Vec<AutomationLogEntry> {
+    let Ok(log) = automations_state.log.lock() else {
+        return Vec::new();
+    };
+    log.iter()
+        .rev()
+        .filter(|entry| entry.root == folder_path)
+        .cloned()
+        .collect()
+}
+
+#[tauri::command]
+pub fn clear_automation_log(folder_path: String, automations_state: State<AutomationsState>) {
+    if let Ok(mut log) = automations_state.log.lock() {
+        log.retain(|entry| entry.root != folder_path);
+    }
+}
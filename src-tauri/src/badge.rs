@@ -0,0 +1,179 @@
+use crate::index::IndexState;
+use crate::settings::SettingsState;
+use crate::watcher::WatcherState;
+use chrono::Local;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, Window};
+
+// Often enough that the count changes soon after midnight
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+// What the dock badge or taskbar overlay shows: the number of open tasks due
+// today (or overdue), a dot when the only thing pending is unsaved edits, or
+// nothing
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Badge {
+    Count(usize),
+    Dot,
+    None,
+}
+
+// Unsaved note counts, as each window last reported them
+#[derive(Default)]
+pub struct BadgeState {
+    unsaved: Mutex<HashMap<String, usize>>,
+    shown: Mutex<Option<Badge>>,
+}
+
+impl BadgeState {
+    pub fn forget_window(&self, label: &str) {
+        if let Ok(mut unsaved) = self.unsaved.lock() {
+            unsaved.remove(label);
+        }
+    }
+
+    fn unsaved(&self) -> usize {
+        self.unsaved
+            .lock()
+            .map(|unsaved| unsaved.values().sum())
+            .unwrap_or(0)
+    }
+}
+
+fn due_tasks(index_state: &IndexState, root: &Path) -> usize {
+    let today = Local::now().date_naive();
+    index_state
+        .with_workspace(root, |index| {
+            index
+                .notes
+                .values()
+                .flat_map(|note| &note.tasks)
+                .filter(|task| !task.done && task.due.is_some_and(|due| due <= today))
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+fn current(app: &AppHandle) -> Badge {
+    if !app.state::<SettingsState>().current().notifications.badge {
+        return Badge::None;
+    }
+    let index_state = app.state::<IndexState>();
+    let due: usize = app
+        .state::<WatcherState>()
+        .watched_roots()
+        .iter()
+        .map(|root| due_tasks(&index_state, root))
+        .sum();
+    if due > 0 {
+        Badge::Count(due)
+    } else if app.state::<BadgeState>().unsaved() > 0 {
+        Badge::Dot
+    } else {
+        Badge::None
+    }
+}
+
+// The macOS dock badge takes any label, so the dot is drawn as text
+#[cfg(target_os = "macos")]
+fn show(app: &AppHandle, badge: Badge) -> tauri::Result<()> {
+    let Some(window) = app.webview_windows().into_values().next() else {
+        return Ok(());
+    };
+    match badge {
+        Badge::Count(count) => window.set_badge_label(Some(count.to_string())),
+        Badge::Dot => window.set_badge_label(Some("\u{25cf}".to_string())),
+        Badge::None => window.set_badge_label(None),
+    }
+}
+
+// A filled accent-coloured circle, the size Windows draws overlay icons at
+#[cfg(target_os = "windows")]
+fn overlay_dot() -> tauri::image::Image<'static> {
+    const SIZE: u32 = 16;
+    let center = (SIZE as f32 - 1.0) / 2.0;
+    let mut rgba = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let distance = ((x as f32 - center).powi(2) + (y as f32 - center).powi(2)).sqrt();
+            let alpha = ((center + 0.5 - distance).clamp(0.0, 1.0) * 255.0) as u8;
+            rgba.extend_from_slice(&[0xe5, 0x48, 0x4d, alpha]);
+        }
+    }
+    tauri::image::Image::new_owned(rgba, SIZE, SIZE)
+}
+
+// Taskbar overlays are per window and can't carry a number, so counts and
+// unsaved edits both show as a dot
+#[cfg(target_os = "windows")]
+fn show(app: &AppHandle, badge: Badge) -> tauri::Result<()> {
+    for window in app.webview_windows().into_values() {
+        let icon = (badge != Badge::None).then(overlay_dot);
+        window.set_overlay_icon(icon)?;
+    }
+    Ok(())
+}
+
+// Linux launchers only show counts
+#[cfg(all(desktop, not(any(target_os = "macos", target_os = "windows"))))]
+fn show(app: &AppHandle, badge: Badge) -> tauri::Result<()> {
+    let count = match badge {
+        Badge::Count(count) => Some(count as i64),
+        Badge::Dot | Badge::None => None,
+    };
+    for window in app.webview_windows().into_values() {
+        window.set_badge_count(count)?;
+    }
+    Ok(())
+}
+
+// Tauri has no badge API on mobile
+#[cfg(mobile)]
+fn show(_app: &AppHandle, _badge: Badge) -> tauri::Result<()> {
+    Ok(())
+}
+
+// Updates the badge when what it should show has changed
+pub fn refresh(app: &AppHandle) {
+    let badge = current(app);
+    let badge_state = app.state::<BadgeState>();
+    let Ok(mut shown) = badge_state.shown.lock() else {
+        return;
+    };
+    if *shown == Some(badge) {
+        return;
+    }
+    match show(app, badge) {
+        Ok(()) => *shown = Some(badge),
+        Err(e) => tracing::debug!("Failed to set app badge: {}", e),
+    }
+}
+
+// Tasks come due as the clock moves, not only when notes change
+pub fn spawn_scheduler(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(REFRESH_INTERVAL);
+        refresh(&app);
+    });
+}
+
+// Each window reports how many of its notes have unsaved edits; the badge
+// combines that with the tasks due in every open workspace
+#[tauri::command]
+pub fn set_badge(unsaved: usize, window: Window, app: AppHandle) {
+    let badge_state = app.state::<BadgeState>();
+    let Ok(mut counts) = badge_state.unsaved.lock() else {
+        return;
+    };
+    // Windows overlays belong to each window, so a new one needs it set again
+    if counts.insert(window.label().to_string(), unsaved).is_none() {
+        if let Ok(mut shown) = badge_state.shown.lock() {
+            *shown = None;
+        }
+    }
+    drop(counts);
+    refresh(&app);
+}
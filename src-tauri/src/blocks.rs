@@ -0,0 +1,190 @@
+use crate::encryption;
+use crate::index::IndexState;
+use crate::markdown::{self, Block, BlockKind};
+use crate::network;
+use crate::properties;
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, State, Window};
+
+const ID_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+const ID_LEN: usize = 6;
+// Characters of block text shown next to its references
+const PREVIEW_CHARS: usize = 80;
+
+#[derive(Debug, Serialize)]
+pub struct Referrer {
+    path: String,
+    title: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BlockReferences {
+    id: String,
+    // Where the block is now; None when notes reference an id it no longer has
+    line: Option<usize>,
+    text: String,
+    references: Vec<Referrer>,
+}
+
+fn new_block_id(taken: &[String]) -> String {
+    loop {
+        let mut bytes = [0u8; ID_LEN];
+        OsRng.fill_bytes(&mut bytes);
+        let id: String = bytes
+            .iter()
+            .map(|byte| ID_CHARS[*byte as usize % ID_CHARS.len()] as char)
+            .collect();
+        if !taken.contains(&id) {
+            return id;
+        }
+    }
+}
+
+// `content` with `id` attached to `block`: after its last line, or on a line
+// of its own for blocks a trailing marker would end up inside
+fn with_block_id(content: &str, block: &Block, id: &str) -> String {
+    let marker = if block.id_on_own_line() {
+        let line_ending = if content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        format!("{}{}^{}", line_ending, line_ending, id)
+    } else {
+        format!(" ^{}", id)
+    };
+    let mut next = content.to_string();
+    next.insert_str(block.end, &marker);
+    next
+}
+
+fn preview(content: &str, block: &Block) -> String {
+    let text = content[block.start..block.end].trim();
+    let text = block
+        .id
+        .as_ref()
+        .and_then(|id| text.strip_suffix(format!("^{}", id).as_str()))
+        .map_or(text, str::trim_end);
+    let mut preview: String = text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(PREVIEW_CHARS)
+        .collect();
+    if text.chars().count() > PREVIEW_CHARS {
+        preview.push('…');
+    }
+    preview
+}
+
+// The `^id` of the block on `line` (1-based), adding one first if it has
+// none, so the block can be linked as `[[Note#^id]]`
+#[tauri::command]
+pub fn ensure_block_id(
+    path: String,
+    line: usize,
+    app: AppHandle,
+    window: Window,
+) -> Result<String, String> {
+    let origin = window.label().to_string();
+    let note = PathBuf::from(&path);
+    let target = note.clone();
+    network::guard(&app, &target, move |app| {
+        let content = properties::read_note(app, &note)?;
+        let blocks = markdown::extract_block_spans(&content);
+        let block = blocks
+            .iter()
+            .find(|block| (block.start_line..=block.end_line).contains(&line))
+            .ok_or_else(|| "There's no paragraph, list item or table on that line".to_string())?;
+        if let Some(id) = &block.id {
+            return Ok(id.clone());
+        }
+        if block.kind == BlockKind::Heading {
+            return Err("Link to a heading with [[Note#Heading]] instead".to_string());
+        }
+
+        let taken: Vec<String> = blocks.iter().filter_map(|block| block.id.clone()).collect();
+        let id = new_block_id(&taken);
+        properties::save_content(app, &origin, &path, with_block_id(&content, block, &id))?;
+        let index = app.state::<IndexState>();
+        if let Some(root) = index.root_for(&note) {
+            index.apply_change(&root, &note);
+        }
+        Ok(id)
+    })?
+}
+
+// The note's blocks that other notes (or the note itself) reference with
+// `[[Note#^id]]`, each with the notes doing it
+#[tauri::command]
+pub async fn get_block_references(
+    path: String,
+    index_state: State<'_, IndexState>,
+) -> Result<Vec<BlockReferences>, String> {
+    let target = PathBuf::from(&path);
+    let root = index_state
+        .root_for(&target)
+        .ok_or_else(|| "Note isn't in an open workspace".to_string())?;
+
+    let referrers = index_state.with_workspace(&root, |index| {
+        let mut referrers: BTreeMap<String, Vec<Referrer>> = BTreeMap::new();
+        for note in index.notes.values() {
+            for block_ref in &note.block_refs {
+                let linked = if block_ref.key.is_empty() {
+                    Some(note)
+                } else {
+                    index.resolve_link(&block_ref.key)
+                };
+                if linked.is_some_and(|linked| linked.path == target) {
+                    referrers
+                        .entry(block_ref.id.to_lowercase())
+                        .or_default()
+                        .push(Referrer {
+                            path: note.path.to_string_lossy().to_string(),
+                            title: note.title.clone(),
+                        });
+                }
+            }
+        }
+        referrers
+    })?;
+    if referrers.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&target).unwrap_or_default();
+    let blocks = if encryption::is_encrypted(&content) {
+        Vec::new()
+    } else {
+        markdown::extract_blocks(&content)
+    };
+    let mut found: Vec<BlockReferences> = referrers
+        .into_iter()
+        .map(|(id, mut references)| {
+            references.sort_by_cached_key(|referrer| referrer.title.to_lowercase());
+            let block = blocks.iter().find(|block| {
+                block
+                    .id
+                    .as_deref()
+                    .is_some_and(|own| own.eq_ignore_ascii_case(&id))
+            });
+            BlockReferences {
+                id: block.and_then(|block| block.id.clone()).unwrap_or(id),
+                line: block.map(|block| block.start_line),
+                text: block
+                    .map(|block| preview(&content, block))
+                    .unwrap_or_default(),
+                references,
+            }
+        })
+        .collect();
+    // Blocks in note order, then ids the note no longer has
+    found.sort_by_key(|block| (block.line.is_none(), block.line));
+    Ok(found)
+}
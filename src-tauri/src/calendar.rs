@@ -0,0 +1,222 @@
+use crate::i18n;
+use crate::index::{IndexState, NoteRecord};
+use crate::settings::SettingsState;
+use crate::workspace_config;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tauri::State;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+// A year view plus a little padding on either side
+const MAX_RANGE_DAYS: i64 = 400;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CalendarNote {
+    path: String,
+    title: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CalendarTask {
+    path: String,
+    title: String,
+    text: String,
+    done: bool,
+    line: usize,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct CalendarDay {
+    date: String,
+    daily_note: Option<CalendarNote>,
+    created: Vec<CalendarNote>,
+    modified: Vec<CalendarNote>,
+    tasks: Vec<CalendarTask>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyNote {
+    path: String,
+    title: String,
+    date: String,
+}
+
+enum Entry {
+    Daily(CalendarNote),
+    Created(CalendarNote),
+    Modified(CalendarNote),
+    Task(CalendarTask),
+}
+
+fn parse_date(text: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(text.trim(), DATE_FORMAT)
+        .map_err(|_| format!("Invalid date: {}", text))
+}
+
+fn local_date(time: SystemTime) -> NaiveDate {
+    DateTime::<Local>::from(time).date_naive()
+}
+
+fn calendar_note(note: &NoteRecord) -> CalendarNote {
+    CalendarNote {
+        path: note.path.to_string_lossy().to_string(),
+        title: note.title.clone(),
+    }
+}
+
+// The date a note stands for under `daily_format`, if it's a daily note
+fn daily_date(note: &NoteRecord, daily_format: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(&note.title, daily_format).ok()
+}
+
+// Everything a note puts on the calendar: its daily-note date, when it was
+// created and last modified, and its dated tasks
+fn note_entries(note: &NoteRecord, daily_format: &str) -> Vec<(NaiveDate, Entry)> {
+    let mut entries = Vec::new();
+
+    if let Some(date) = daily_date(note, daily_format) {
+        entries.push((date, Entry::Daily(calendar_note(note))));
+    }
+    if let Some(created) = note.created {
+        entries.push((local_date(created), Entry::Created(calendar_note(note))));
+    }
+    if let Some(modified) = note.modified {
+        entries.push((local_date(modified), Entry::Modified(calendar_note(note))));
+    }
+    for task in &note.tasks {
+        let Some(due) = task.due else {
+            continue;
+        };
+        entries.push((
+            due,
+            Entry::Task(CalendarTask {
+                path: note.path.to_string_lossy().to_string(),
+                title: note.title.clone(),
+                text: task.text.clone(),
+                done: task.done,
+                line: task.line,
+            }),
+        ));
+    }
+
+    entries
+}
+
+// Only days with something on them are returned, in date order; `start` and
+// `end` are inclusive `YYYY-MM-DD` local dates
+#[tauri::command]
+pub async fn get_notes_by_date(
+    folder_path: String,
+    start: String,
+    end: String,
+    index_state: State<'_, IndexState>,
+    settings_state: State<'_, SettingsState>,
+) -> Result<Vec<CalendarDay>, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err(i18n::t("error.folder_missing"));
+    }
+
+    let start = parse_date(&start)?;
+    let end = parse_date(&end)?;
+    if end < start {
+        return Err("The end date is before the start date".to_string());
+    }
+    if (end - start).num_days() > MAX_RANGE_DAYS {
+        return Err(format!(
+            "Date ranges are limited to {} days",
+            MAX_RANGE_DAYS
+        ));
+    }
+
+    let daily_format =
+        workspace_config::effective(&root, &settings_state.current()).daily_note_format;
+
+    index_state.with_workspace(&root, |index| {
+        // Sorted so notes within a day come back in a stable order
+        let mut notes: Vec<&NoteRecord> = index.notes.values().collect();
+        notes.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut days: BTreeMap<NaiveDate, CalendarDay> = BTreeMap::new();
+        for (date, entry) in notes
+            .into_iter()
+            .flat_map(|note| note_entries(note, &daily_format))
+            .filter(|(date, _)| *date >= start && *date <= end)
+        {
+            let day = days.entry(date).or_insert_with(|| CalendarDay {
+                date: date.format(DATE_FORMAT).to_string(),
+                ..CalendarDay::default()
+            });
+            match entry {
+                Entry::Daily(note) => day.daily_note = Some(note),
+                Entry::Created(note) => day.created.push(note),
+                Entry::Modified(note) => day.modified.push(note),
+                Entry::Task(task) => day.tasks.push(task),
+            }
+        }
+
+        days.into_values().collect()
+    })
+}
+
+// Nearest existing daily note strictly before or after `date`, skipping days
+// without one. `date` is `YYYY-MM-DD`, or the path of a daily note so the
+// frontend can step from the open note without knowing the format.
+#[tauri::command]
+pub async fn get_adjacent_daily_note(
+    folder_path: String,
+    date: String,
+    direction: String,
+    index_state: State<'_, IndexState>,
+    settings_state: State<'_, SettingsState>,
+) -> Result<Option<DailyNote>, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err(i18n::t("error.folder_missing"));
+    }
+
+    let earlier = match direction.as_str() {
+        "previous" => true,
+        "next" => false,
+        _ => return Err(format!("Unknown direction: {}", direction)),
+    };
+    let daily_format =
+        workspace_config::effective(&root, &settings_state.current()).daily_note_format;
+
+    index_state.with_workspace(&root, |index| {
+        let from = match parse_date(&date) {
+            Ok(from) => from,
+            Err(e) => index
+                .notes
+                .get(Path::new(&date))
+                .and_then(|note| daily_date(note, &daily_format))
+                .ok_or(e)?,
+        };
+
+        let candidates = index.notes.values().filter_map(|note| {
+            let day = daily_date(note, &daily_format)?;
+            let wanted = if earlier { day < from } else { day > from };
+            wanted.then_some((day, note))
+        });
+
+        // Two daily notes for one day resolve by path so the choice is stable
+        let nearest = if earlier {
+            candidates.max_by(|(a, a_note), (b, b_note)| {
+                a.cmp(b).then_with(|| b_note.path.cmp(&a_note.path))
+            })
+        } else {
+            candidates.min_by(|(a, a_note), (b, b_note)| {
+                a.cmp(b).then_with(|| a_note.path.cmp(&b_note.path))
+            })
+        };
+
+        Ok(nearest.map(|(day, note)| DailyNote {
+            path: note.path.to_string_lossy().to_string(),
+            title: note.title.clone(),
+            date: day.format(DATE_FORMAT).to_string(),
+        }))
+    })?
+}
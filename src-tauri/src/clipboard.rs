@@ -0,0 +1,143 @@
+use crate::embeds;
+use crate::encryption;
+use crate::markdown;
+use crate::properties;
+use crate::rtf;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+#[cfg(target_os = "macos")]
+fn write(html: &str, rtf: &str, text: &str) -> Result<(), String> {
+    use objc2_app_kit::{
+        NSPasteboard, NSPasteboardTypeHTML, NSPasteboardTypeRTF, NSPasteboardTypeString,
+    };
+    use objc2_foundation::{NSData, NSString};
+
+    let pasteboard = NSPasteboard::generalPasteboard();
+    pasteboard.clearContents();
+    // SAFETY: the pasteboard type constants are immutable strings AppKit exports
+    let written = unsafe {
+        pasteboard.setString_forType(&NSString::from_str(html), NSPasteboardTypeHTML)
+            && pasteboard.setData_forType(
+                Some(&NSData::with_bytes(rtf.as_bytes())),
+                NSPasteboardTypeRTF,
+            )
+            && pasteboard.setString_forType(&NSString::from_str(text), NSPasteboardTypeString)
+    };
+    if written {
+        Ok(())
+    } else {
+        Err("Failed to write to the clipboard".to_string())
+    }
+}
+
+// The "HTML Format" flavor wraps the fragment in a header giving byte offsets
+// into the UTF-8 data, each padded to a fixed width so the header's own
+// length is known up front
+#[cfg(target_os = "windows")]
+fn windows_html(html: &str) -> String {
+    const HEADER: &str = "Version:0.9\r\nStartHTML:{0000000000}\r\nEndHTML:{1111111111}\r\nStartFragment:{2222222222}\r\nEndFragment:{3333333333}\r\n";
+    const OPEN: &str = "<html><body>\r\n<!--StartFragment-->";
+    const CLOSE: &str = "<!--EndFragment-->\r\n</body></html>";
+
+    let start_html = HEADER.len() - 8;
+    let start_fragment = start_html + OPEN.len();
+    let end_fragment = start_fragment + html.len();
+    let end_html = end_fragment + CLOSE.len();
+    let header = HEADER
+        .replace("{0000000000}", &format!("{:010}", start_html))
+        .replace("{1111111111}", &format!("{:010}", end_html))
+        .replace("{2222222222}", &format!("{:010}", start_fragment))
+        .replace("{3333333333}", &format!("{:010}", end_fragment));
+    format!("{}{}{}{}", header, OPEN, html, CLOSE)
+}
+
+#[cfg(target_os = "windows")]
+fn write(html: &str, rtf: &str, text: &str) -> Result<(), String> {
+    use windows::core::w;
+    use windows::Win32::Foundation::{GlobalFree, HANDLE};
+    use windows::Win32::System::DataExchange::{
+        CloseClipboard, EmptyClipboard, OpenClipboard, RegisterClipboardFormatW, SetClipboardData,
+    };
+    use windows::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+    use windows::Win32::System::Ole::CF_UNICODETEXT;
+
+    // Copies `bytes` into memory the clipboard takes ownership of
+    fn set(format: u32, bytes: &[u8]) -> windows::core::Result<()> {
+        // SAFETY: the block is sized for `bytes`, locked while written, and only
+        // freed here when the clipboard didn't take it
+        unsafe {
+            let memory = GlobalAlloc(GMEM_MOVEABLE, bytes.len())?;
+            let target = GlobalLock(memory);
+            if target.is_null() {
+                let _ = GlobalFree(Some(memory));
+                return Err(windows::core::Error::from_thread());
+            }
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), target.cast::<u8>(), bytes.len());
+            let _ = GlobalUnlock(memory);
+            if let Err(e) = SetClipboardData(format, Some(HANDLE(memory.0))) {
+                let _ = GlobalFree(Some(memory));
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    let mut html = windows_html(html).into_bytes();
+    html.push(0);
+    let mut rtf = rtf.as_bytes().to_vec();
+    rtf.push(0);
+    let text: Vec<u8> = text
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .flat_map(u16::to_le_bytes)
+        .collect();
+
+    let copy = || -> windows::core::Result<()> {
+        // SAFETY: the clipboard is opened for this thread and closed below
+        unsafe {
+            OpenClipboard(None)?;
+            let written = EmptyClipboard().and_then(|_| {
+                set(RegisterClipboardFormatW(w!("HTML Format")), &html)?;
+                set(RegisterClipboardFormatW(w!("Rich Text Format")), &rtf)?;
+                set(CF_UNICODETEXT.0 as u32, &text)
+            });
+            let _ = CloseClipboard();
+            written
+        }
+    };
+    copy().map_err(|e| format!("Failed to write to the clipboard: {}", e))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn write(_html: &str, _rtf: &str, _text: &str) -> Result<(), String> {
+    Err("Copying as rich text isn't available on this platform".to_string())
+}
+
+// Puts `selection` (or the whole saved note at `path`) on the clipboard as
+// HTML and RTF, with the markdown as the plain-text flavor, so pasting into
+// a mail or word processor keeps the formatting. Embeds are inlined when
+// there's a note to resolve them from.
+#[tauri::command]
+pub fn copy_as_rich_text(
+    path: Option<String>,
+    selection: Option<String>,
+    app: AppHandle,
+) -> Result<(), String> {
+    let note = path.map(PathBuf::from);
+    let content = match (selection, &note) {
+        (Some(selection), _) if encryption::is_encrypted(&selection) => {
+            return Err("Note is encrypted; decrypt it first".to_string())
+        }
+        (Some(selection), _) => selection,
+        (None, Some(note)) => properties::read_note(&app, note)?,
+        (None, None) => return Err("Nothing to copy".to_string()),
+    };
+    let content = match &note {
+        Some(note) => embeds::inline_embeds(&app, note, &content),
+        None => content,
+    };
+    let html = markdown::to_html(&content);
+    let rtf = rtf::from_markdown(&content);
+    write(&html, &rtf, markdown::body(&content).trim())
+}
@@ -0,0 +1,150 @@
+use crate::api;
+use crate::markdown;
+use crate::settings::SettingsState;
+use crate::watcher::WatcherState;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tauri::{AppHandle, Emitter, Manager, Url};
+
+// Clips appended to a note aren't tied to a window, so every window reloads it
+const ORIGIN: &str = "clipper";
+const DEFAULT_TITLE: &str = "Clipping";
+const MAX_NAME_CHARS: usize = 80;
+// Browsers put these in the Origin header of requests made by extensions
+pub const EXTENSION_SCHEMES: [&str; 3] = [
+    "chrome-extension://",
+    "moz-extension://",
+    "safari-web-extension://",
+];
+
+// What the browser extension posts to `/clip`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Clip {
+    title: Option<String>,
+    url: Option<String>,
+    // The page or selection as HTML, unless the extension already made markdown
+    html: Option<String>,
+    markdown: Option<String>,
+    // Without the `#`
+    tags: Vec<String>,
+    // A workspace-relative note to append to instead of creating one
+    note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SavedClip {
+    pub path: String,
+    pub title: String,
+    pub appended: bool,
+}
+
+// Page titles often hold characters file names can't
+pub fn file_stem(title: &str, fallback: &str) -> String {
+    let cleaned: String = title
+        .chars()
+        .map(|c| {
+            if c.is_control() || matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*') {
+                ' '
+            } else {
+                c
+            }
+        })
+        .collect();
+    let stem: String = cleaned
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(MAX_NAME_CHARS)
+        .collect();
+    let stem = stem.trim_end_matches(['.', ' ']);
+    if stem.is_empty() {
+        fallback.to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+fn render(clip: &Clip, title: &str, heading: &str) -> Result<String, String> {
+    let base = clip
+        .url
+        .as_deref()
+        .and_then(|url| Url::parse(url).ok())
+        .filter(|url| matches!(url.scheme(), "http" | "https"));
+    let body = match (&clip.markdown, &clip.html) {
+        (Some(text), _) if !text.trim().is_empty() => text.trim().to_string(),
+        (_, Some(html)) if !html.trim().is_empty() => {
+            markdown::from_html(html, base.as_ref()).trim().to_string()
+        }
+        _ => return Err("The clip is empty".to_string()),
+    };
+
+    let date = chrono::Local::now().format("%Y-%m-%d");
+    let source = match &base {
+        Some(url) => format!("> Clipped from <{}> on {}", url, date),
+        None => format!("> Clipped on {}", date),
+    };
+    let mut sections = vec![format!("{} {}", heading, title), source, body];
+    let tags: Vec<String> = clip
+        .tags
+        .iter()
+        .map(|tag| tag.trim().trim_start_matches('#'))
+        .filter(|tag| !tag.is_empty() && !tag.contains(char::is_whitespace))
+        .map(|tag| format!("#{}", tag))
+        .collect();
+    if !tags.is_empty() {
+        sections.push(tags.join(" "));
+    }
+    Ok(sections.join("\n\n") + "\n")
+}
+
+pub fn save(app: &AppHandle, root: &Path, clip: Clip) -> Result<SavedClip, String> {
+    // Titles become a heading line, so any line breaks in them go
+    let title = clip
+        .title
+        .as_deref()
+        .map(|title| title.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| DEFAULT_TITLE.to_string());
+    let settings = app.state::<SettingsState>().current();
+
+    let (path, appended) = match clip.note.as_deref().filter(|note| !note.trim().is_empty()) {
+        Some(note) => {
+            let path = api::note_path(root, note)?;
+            if !markdown::has_note_extension(&path, &settings.watched_extensions) || !path.is_file()
+            {
+                return Err("Note not found".to_string());
+            }
+            // A blank line keeps the clip's heading apart from what came before
+            let text = format!("\n{}", render(&clip, &title, "##")?);
+            crate::append_to_note(app, ORIGIN, &path, &text).map_err(|e| e.into_message())?;
+            (path, true)
+        }
+        None => {
+            let content = render(&clip, &title, "#")?;
+            let folder = api::note_path(root, &settings.api.clipper_folder)?;
+            fs::create_dir_all(&folder)
+                .map_err(|e| format!("Failed to create the clippings folder: {}", e))?;
+            let path = crate::create_markdown_file(
+                folder.to_string_lossy().to_string(),
+                format!("{}.md", file_stem(&title, DEFAULT_TITLE)),
+                Some(content),
+                app.clone(),
+            )?;
+            (path.into(), false)
+        }
+    };
+
+    let saved = SavedClip {
+        path: path.to_string_lossy().to_string(),
+        title,
+        appended,
+    };
+    // Windows showing the workspace list the clip right away and offer to open it
+    for label in app.state::<WatcherState>().windows_for(root) {
+        let _ = app.emit_to(label.as_str(), "clip-received", saved.clone());
+    }
+    Ok(saved)
+}
@@ -0,0 +1,129 @@
+use crate::disk_space;
+use crate::encryption;
+use crate::i18n;
+use crate::markdown;
+use crate::notifications::{self, Category};
+use crate::storage;
+use crate::vault;
+use crate::watcher::WatcherState;
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager};
+
+// Copies made the same day are numbered after the first
+const MAX_COPIES: usize = 100;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncConflict {
+    pub path: String,
+    pub copy: String,
+}
+
+// `Note (conflicted copy 2024-06-01).md`, then `... 2024-06-01 2).md` and so on
+fn copy_name(path: &Path, date: &str, number: usize) -> String {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_default();
+    let suffix = if number > 1 {
+        format!(" {}", number)
+    } else {
+        String::new()
+    };
+    match path.extension() {
+        Some(ext) => format!(
+            "{} (conflicted copy {}{}).{}",
+            stem,
+            date,
+            suffix,
+            ext.to_string_lossy()
+        ),
+        None => format!("{} (conflicted copy {}{})", stem, date, suffix),
+    }
+}
+
+// Writes `content` next to `path` as a conflicted copy and tells the windows
+// showing the workspace about both files. Used for whichever side of an
+// unmergeable edit would otherwise be overwritten.
+pub fn write_copy(app: &AppHandle, path: &Path, content: &str) -> Result<PathBuf, String> {
+    storage::ensure_accessible(app, path)?;
+    let parent = path
+        .parent()
+        .ok_or_else(|| "Note has no parent folder".to_string())?;
+    // The copy must not end up readable when the original isn't
+    let original = fs::read_to_string(path).unwrap_or_default();
+    if encryption::is_encrypted(&original) && !encryption::is_encrypted(content) {
+        return Err("Encrypted notes can't get a plaintext conflicted copy".to_string());
+    }
+
+    disk_space::ensure_space(parent, content.len() as u64).map_err(|e| e.message)?;
+
+    let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let mut copy = None;
+    for number in 1..=MAX_COPIES {
+        let candidate = parent.join(copy_name(path, &date, number));
+        if vault::vault_root(&candidate).is_some() {
+            if candidate.exists() {
+                continue;
+            }
+            vault::write_file(app, &candidate, content)?;
+            copy = Some(candidate);
+            break;
+        }
+        // `create_new` so two windows resolving at once never share a copy
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&candidate)
+        {
+            Ok(mut file) => {
+                file.write_all(content.as_bytes())
+                    .map_err(|e| format!("Failed to write conflicted copy: {}", e))?;
+                copy = Some(candidate);
+                break;
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("Failed to write conflicted copy: {}", e)),
+        }
+    }
+    let copy = copy.ok_or_else(|| "Too many conflicted copies of this note today".to_string())?;
+    notifications::record_write(app, &copy);
+
+    let conflict = SyncConflict {
+        path: path.to_string_lossy().to_string(),
+        copy: copy.to_string_lossy().to_string(),
+    };
+    let labels = match app.state::<WatcherState>().root_for(path) {
+        Some(root) => app.state::<WatcherState>().windows_for(&root),
+        None => Vec::new(),
+    };
+    if labels.is_empty() {
+        let _ = app.emit("sync-conflict", conflict);
+    } else {
+        for label in labels {
+            let _ = app.emit_to(label.as_str(), "sync-conflict", conflict.clone());
+        }
+    }
+    notifications::notify(
+        app,
+        Category::SyncConflicts,
+        &i18n::t("notification.conflict_title"),
+        &i18n::tf("notification.conflict_body", &[&markdown::note_title(path)]),
+    );
+    Ok(copy)
+}
+
+#[tauri::command]
+pub fn save_conflict_copy(
+    file_path: String,
+    content: String,
+    app: AppHandle,
+) -> Result<String, String> {
+    let path = PathBuf::from(&file_path);
+    if !path.is_file() {
+        return Err("Note does not exist".to_string());
+    }
+    write_copy(&app, &path, &content).map(|copy| copy.to_string_lossy().to_string())
+}
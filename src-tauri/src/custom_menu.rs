@@ -0,0 +1,137 @@
+#[cfg(desktop)]
+use crate::index::IndexState;
+#[cfg(desktop)]
+use crate::settings::{CustomMenuEntry, SettingsState};
+#[cfg(desktop)]
+use crate::watcher::WatcherState;
+#[cfg(desktop)]
+use crate::windows;
+#[cfg(desktop)]
+use std::path::{Component, Path, PathBuf};
+#[cfg(desktop)]
+use std::process::Command;
+#[cfg(desktop)]
+use tauri::menu::MenuItem;
+#[cfg(desktop)]
+use tauri::{AppHandle, Emitter, Manager, Wry};
+
+// User-defined entries for the Custom menu, kept in settings
+pub const ACTIONS: [&str; 3] = ["open_note", "run_automation", "open_url"];
+// Schemes `open_url` entries may use; anything else could launch arbitrary handlers
+pub const URL_SCHEMES: [&str; 3] = ["http://", "https://", "mailto:"];
+
+pub fn valid_url(url: &str) -> bool {
+    let lower = url.to_lowercase();
+    URL_SCHEMES
+        .iter()
+        .any(|scheme| lower.starts_with(scheme) && lower.len() > scheme.len())
+}
+
+// Entries are addressed by their position in settings; the menu is rebuilt
+// whenever the list changes, so positions stay current
+#[cfg(desktop)]
+const ID_PREFIX: &str = "custom://";
+
+#[cfg(desktop)]
+pub fn items(app: &AppHandle) -> tauri::Result<Vec<MenuItem<Wry>>> {
+    app.state::<SettingsState>()
+        .current()
+        .custom_menu
+        .iter()
+        .enumerate()
+        .map(|(idx, entry)| {
+            MenuItem::with_id(
+                app,
+                format!("{}{}", ID_PREFIX, idx),
+                &entry.label,
+                true,
+                None::<&str>,
+            )
+        })
+        .collect()
+}
+
+// A workspace-relative path, or a note name resolved like a wiki link
+#[cfg(desktop)]
+fn resolve_note(app: &AppHandle, label: &str, target: &str) -> Option<PathBuf> {
+    let root = app.state::<WatcherState>().active_root(label)?;
+    let relative = Path::new(target.trim_matches(['/', '\\']));
+    let inside = relative
+        .components()
+        .all(|part| matches!(part, Component::Normal(_)));
+    if inside && root.join(relative).is_file() {
+        return Some(root.join(relative));
+    }
+    app.state::<IndexState>()
+        .with_workspace(&root, |index| {
+            index.resolve_link(target).map(|note| note.path.clone())
+        })
+        .ok()
+        .flatten()
+}
+
+#[cfg(desktop)]
+fn open_url(url: &str) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        // `start` would let cmd interpret `&` and friends in the URL
+        let mut command = Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    };
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = Command::new("xdg-open");
+
+    command
+        .arg(url)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open {}: {}", url, e))
+}
+
+#[cfg(desktop)]
+fn run(app: &AppHandle, entry: &CustomMenuEntry) {
+    let label = windows::focused_label(app);
+    match entry.action.as_str() {
+        "open_note" => match resolve_note(app, &label, &entry.target) {
+            Some(path) => {
+                let _ = app.emit_to(
+                    label.as_str(),
+                    "open-recent-note",
+                    path.to_string_lossy().to_string(),
+                );
+            }
+            None => tracing::warn!("Custom menu note not found: {}", entry.target),
+        },
+        // The window knows which note is open, so it asks for the run itself
+        "run_automation" => {
+            let _ = app.emit_to(label.as_str(), "menu://run-automation", &entry.target);
+        }
+        _ => {
+            if let Err(e) = open_url(&entry.target) {
+                tracing::error!("{}", e);
+            }
+        }
+    }
+}
+
+// Handles `custom://<index>` menu ids; false for anything else
+#[cfg(desktop)]
+pub fn handle(app: &AppHandle, id: &str) -> bool {
+    let Some(idx) = id.strip_prefix(ID_PREFIX) else {
+        return false;
+    };
+    let entry = idx.parse::<usize>().ok().and_then(|idx| {
+        app.state::<SettingsState>()
+            .current()
+            .custom_menu
+            .get(idx)
+            .cloned()
+    });
+    if let Some(entry) = entry {
+        run(app, &entry);
+    }
+    true
+}
@@ -0,0 +1,203 @@
+use crate::i18n;
+use crate::settings::SettingsState;
+use crate::storage;
+use crate::vault;
+use crate::watcher::WatcherState;
+use crate::workspaces;
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+// Long enough to read the dialog, short enough that a stale token can't
+// confirm a folder that has changed a lot since
+const TOKEN_TTL: Duration = Duration::from_secs(5 * 60);
+
+struct PendingDelete {
+    path: PathBuf,
+    files: u64,
+    issued: Instant,
+}
+
+// Tokens handed out by `preview_delete`, each good for one delete of its path
+#[derive(Default)]
+pub struct DeleteGuardState {
+    pending: Mutex<HashMap<String, PendingDelete>>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Size {
+    files: u64,
+    folders: u64,
+    bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeletePreview {
+    path: String,
+    is_dir: bool,
+    files: u64,
+    folders: u64,
+    bytes: u64,
+    // The path is, or holds, an open or registered workspace
+    is_workspace_root: bool,
+    requires_confirmation: bool,
+    // Pass to `delete_entry` when `requires_confirmation` is set
+    token: Option<String>,
+}
+
+fn generate_token() -> String {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Counts what's under `path` without following symlinks, giving up once it's
+// past `limit` files or bytes since the answer can't change after that
+fn measure(path: &Path, limit: Option<Size>) -> Size {
+    let mut size = Size::default();
+    let mut stack = vec![path.to_path_buf()];
+    while let Some(current) = stack.pop() {
+        let Ok(metadata) = fs::symlink_metadata(&current) else {
+            continue;
+        };
+        if !metadata.is_dir() {
+            size.files += 1;
+            size.bytes += metadata.len();
+        } else {
+            if current != path {
+                size.folders += 1;
+            }
+            if let Ok(entries) = fs::read_dir(&current) {
+                stack.extend(entries.flatten().map(|entry| entry.path()));
+            }
+        }
+        if limit.is_some_and(|limit| exceeds(size, limit)) {
+            break;
+        }
+    }
+    size
+}
+
+// A limit of 0 is off
+fn exceeds(size: Size, limit: Size) -> bool {
+    (limit.files > 0 && size.files > limit.files) || (limit.bytes > 0 && size.bytes > limit.bytes)
+}
+
+fn limit(app: &AppHandle) -> Size {
+    let settings = app.state::<SettingsState>().current().delete_confirmation;
+    Size {
+        files: settings.files,
+        folders: 0,
+        bytes: settings.megabytes.saturating_mul(1024 * 1024),
+    }
+}
+
+fn holds_workspace(app: &AppHandle, path: &Path) -> bool {
+    app.state::<WatcherState>()
+        .watched_roots()
+        .into_iter()
+        .chain(workspaces::registered_roots(app))
+        .any(|root| root.starts_with(path))
+}
+
+// Called by `delete_entry` before anything is removed. `physical` is where
+// `path` lives on disk (they differ inside a vault). Consumes the token.
+pub fn check(
+    app: &AppHandle,
+    path: &Path,
+    physical: &Path,
+    token: Option<&str>,
+) -> Result<(), String> {
+    let is_root = holds_workspace(app, path);
+    if !is_root && !physical.is_dir() {
+        return Ok(());
+    }
+    let limit = limit(app);
+    let size = measure(physical, Some(limit));
+    if !is_root && !exceeds(size, limit) {
+        return Ok(());
+    }
+
+    let pending = token.and_then(|token| {
+        app.state::<DeleteGuardState>()
+            .pending
+            .lock()
+            .ok()?
+            .remove(token)
+    });
+    match pending {
+        // More files than the user was shown means the preview is out of date
+        Some(pending)
+            if pending.path == path
+                && pending.issued.elapsed() < TOKEN_TTL
+                && size.files <= pending.files =>
+        {
+            Ok(())
+        }
+        _ => Err(i18n::t("error.delete_needs_confirmation")),
+    }
+}
+
+// What deleting `path` would remove, and a token for `delete_entry` when it's
+// a workspace or bigger than the delete confirmation limits
+#[tauri::command]
+pub async fn preview_delete(
+    path: String,
+    app: AppHandle,
+    guard_state: State<'_, DeleteGuardState>,
+) -> Result<DeletePreview, String> {
+    let path = PathBuf::from(&path);
+    storage::ensure_accessible(&app, &path)?;
+    if vault::vault_root(&path).is_some() {
+        vault::ensure_unlocked(&app, &path)?;
+    }
+    let physical = vault::physical_location(&app, &path)?;
+    if !physical.exists() {
+        return Err(i18n::t("error.path_missing"));
+    }
+
+    let is_dir = physical.is_dir();
+    let measured = physical.clone();
+    let size = tauri::async_runtime::spawn_blocking(move || measure(&measured, None))
+        .await
+        .map_err(|e| format!("Failed to measure folder: {}", e))?;
+    let is_workspace_root = holds_workspace(&app, &path);
+    let requires_confirmation = is_workspace_root || (is_dir && exceeds(size, limit(&app)));
+
+    let token = if requires_confirmation {
+        let token = generate_token();
+        let mut pending = guard_state
+            .pending
+            .lock()
+            .map_err(|e| format!("Failed to lock delete state: {}", e))?;
+        pending.retain(|_, entry| entry.issued.elapsed() < TOKEN_TTL);
+        pending.insert(
+            token.clone(),
+            PendingDelete {
+                path: path.clone(),
+                files: size.files,
+                issued: Instant::now(),
+            },
+        );
+        Some(token)
+    } else {
+        None
+    };
+
+    Ok(DeletePreview {
+        path: path.to_string_lossy().to_string(),
+        is_dir,
+        files: size.files,
+        folders: size.folders,
+        bytes: size.bytes,
+        is_workspace_root,
+        requires_confirmation,
+        token,
+    })
+}
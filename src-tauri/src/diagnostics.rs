@@ -0,0 +1,222 @@
+use crate::logging::LoggingState;
+use crate::settings::SettingsState;
+use crate::storage;
+use crate::vault;
+use crate::watcher::WatcherState;
+use crate::workspace_config::NoteFilter;
+use chrono::Local;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, State};
+use zip::write::SimpleFileOptions;
+
+const MAX_CRASH_REPORTS: usize = 10;
+const CRASH_DIR: &str = "crashes";
+
+fn crash_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(storage::app_data_dir(app)?.join(CRASH_DIR))
+}
+
+// Writes a report for every backend panic before the default hook runs. Release builds
+// abort on panic, so this is the only trace a crash leaves behind.
+pub fn install_panic_hook(app: &AppHandle) {
+    let Ok(dir) = crash_dir(app) else {
+        return;
+    };
+    let version = app.package_info().version.to_string();
+    let previous = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        let report = format!(
+            "Marky {}\n{} {}\n{}\n\n{}\n",
+            version,
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            info,
+            std::backtrace::Backtrace::force_capture()
+        );
+        tracing::error!("Backend panicked: {}", info);
+        let name = format!("crash-{}.txt", Local::now().format("%Y%m%d-%H%M%S%.3f"));
+        let _ = fs::create_dir_all(&dir).and_then(|_| fs::write(dir.join(name), report));
+        previous(info);
+    }));
+}
+
+#[derive(Debug, Serialize)]
+struct WorkspaceSummary {
+    // Hashed so reports never reveal where the user keeps their notes
+    id: String,
+    is_vault: bool,
+    notes: usize,
+    folders: usize,
+    total_bytes: u64,
+    extensions: BTreeMap<String, usize>,
+    open_windows: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct SettingsSummary {
+    watched_extensions: Vec<String>,
+    watch_debounce_ms: u64,
+    theme_id: String,
+    ignore_glob_count: usize,
+    secure_delete: bool,
+    update_channel: String,
+    vault_auto_lock_minutes: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct DiagnosticsReport {
+    generated_at: String,
+    app_version: String,
+    tauri_version: String,
+    os: String,
+    os_family: String,
+    arch: String,
+    settings: SettingsSummary,
+    workspaces: Vec<WorkspaceSummary>,
+    recent_errors: Vec<String>,
+}
+
+fn summarize_workspace(root: &Path, filter: &NoteFilter, open_windows: usize) -> WorkspaceSummary {
+    fn walk(dir: &Path, filter: &NoteFilter, summary: &mut WorkspaceSummary) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if filter.is_ignored(&path) {
+                continue;
+            }
+            if path.is_dir() {
+                summary.folders += 1;
+                walk(&path, filter, summary);
+            } else if filter.includes_note(&path) {
+                summary.notes += 1;
+                summary.total_bytes += entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+                let ext = path
+                    .extension()
+                    .map(|ext| ext.to_string_lossy().to_lowercase())
+                    .unwrap_or_default();
+                *summary.extensions.entry(ext).or_default() += 1;
+            }
+        }
+    }
+
+    let is_vault = vault::is_vault_root(root);
+    let mut summary = WorkspaceSummary {
+        id: storage::workspace_key(root),
+        is_vault,
+        notes: 0,
+        folders: 0,
+        total_bytes: 0,
+        extensions: BTreeMap::new(),
+        open_windows,
+    };
+    // A vault's file tree would only show ciphertext sizes; its counts stay zero
+    if !is_vault {
+        walk(root, filter, &mut summary);
+    }
+    summary
+}
+
+fn crash_reports(app: &AppHandle) -> Vec<PathBuf> {
+    let Ok(entries) = crash_dir(app).and_then(|dir| {
+        fs::read_dir(dir).map_err(|e| format!("Failed to read crash reports: {}", e))
+    }) else {
+        return Vec::new();
+    };
+    let mut reports: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    // Names embed the timestamp, so the newest sort last
+    reports.sort();
+    let skip = reports.len().saturating_sub(MAX_CRASH_REPORTS);
+    reports.split_off(skip)
+}
+
+fn write_bundle(
+    output: &Path,
+    report: &DiagnosticsReport,
+    crashes: &[PathBuf],
+) -> Result<(), String> {
+    let file = fs::File::create(output).map_err(|e| format!("Failed to create bundle: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default();
+    let zip_error = |e: zip::result::ZipError| format!("Failed to write bundle: {}", e);
+    let io_error = |e: std::io::Error| format!("Failed to write bundle: {}", e);
+
+    let json = serde_json::to_string_pretty(report)
+        .map_err(|e| format!("Failed to serialize diagnostics: {}", e))?;
+    zip.start_file("diagnostics.json", options)
+        .map_err(zip_error)?;
+    zip.write_all(json.as_bytes()).map_err(io_error)?;
+
+    for crash in crashes {
+        let Some(name) = crash.file_name() else {
+            continue;
+        };
+        let Ok(content) = fs::read(crash) else {
+            continue;
+        };
+        zip.start_file(format!("crashes/{}", name.to_string_lossy()), options)
+            .map_err(zip_error)?;
+        zip.write_all(&content).map_err(io_error)?;
+    }
+
+    zip.finish().map_err(zip_error)?;
+    Ok(())
+}
+
+// Bundles what a bug report needs without note names, paths, or contents
+#[tauri::command]
+pub async fn generate_diagnostics(
+    output_path: String,
+    app: AppHandle,
+    watcher_state: State<'_, WatcherState>,
+    settings_state: State<'_, SettingsState>,
+) -> Result<String, String> {
+    let settings = settings_state.current();
+    let roots = watcher_state.watched_roots();
+
+    let workspaces = roots
+        .iter()
+        .map(|root| {
+            let filter = NoteFilter::for_workspace(root, &settings);
+            summarize_workspace(root, &filter, watcher_state.windows_for(root).len())
+        })
+        .collect();
+
+    let report = DiagnosticsReport {
+        generated_at: Local::now().to_rfc3339(),
+        app_version: app.package_info().version.to_string(),
+        tauri_version: tauri::VERSION.to_string(),
+        os: std::env::consts::OS.to_string(),
+        os_family: std::env::consts::FAMILY.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        settings: SettingsSummary {
+            watched_extensions: settings.watched_extensions.clone(),
+            watch_debounce_ms: settings.watch_debounce_ms,
+            theme_id: settings.theme_id.clone(),
+            ignore_glob_count: settings.ignore_globs.len(),
+            secure_delete: settings.secure_delete,
+            update_channel: settings.update_channel.clone(),
+            vault_auto_lock_minutes: settings.vault_auto_lock_minutes,
+        },
+        workspaces,
+        recent_errors: app
+            .try_state::<LoggingState>()
+            .map(|state| state.recent_errors())
+            .unwrap_or_default(),
+    };
+
+    let output = PathBuf::from(&output_path);
+    write_bundle(&output, &report, &crash_reports(&app))?;
+
+    Ok(output.to_string_lossy().to_string())
+}
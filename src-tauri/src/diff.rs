@@ -0,0 +1,216 @@
+use crate::{git, properties, storage, vault};
+use serde::Serialize;
+use similar::{ChangeTag, DiffOp, TextDiff};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+// Unchanged lines kept on either side of a change
+const CONTEXT_LINES: usize = 3;
+// Past this the diff settles for a coarser result, which is still correct
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+// A run of words on one line; `changed` runs differ from the other side
+#[derive(Debug, Clone, Serialize)]
+pub struct DiffSegment {
+    pub text: String,
+    pub changed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineKind {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiffLine {
+    pub kind: LineKind,
+    // 1-based, and None on the side the line isn't on
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+    pub segments: Vec<DiffSegment>,
+}
+
+// Starts are 1-based, as in a unified diff header
+#[derive(Debug, Clone, Serialize)]
+pub struct DiffHunk {
+    pub old_start: usize,
+    pub old_lines: usize,
+    pub new_start: usize,
+    pub new_lines: usize,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Diff {
+    pub hunks: Vec<DiffHunk>,
+    pub added: usize,
+    pub removed: usize,
+}
+
+// What a note's changes were measured from
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeadSource {
+    Git,
+    Snapshot,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HeadDiff {
+    pub source: HeadSource,
+    // Short hash of the commit, for a git base
+    pub commit: Option<String>,
+    pub diff: Diff,
+}
+
+// Adjacent runs with the same emphasis merged, without the line ending
+fn segments(values: &[(bool, &str)]) -> Vec<DiffSegment> {
+    let mut segments: Vec<DiffSegment> = Vec::new();
+    for &(changed, text) in values {
+        match segments.last_mut() {
+            Some(last) if last.changed == changed => last.text.push_str(text),
+            _ => segments.push(DiffSegment {
+                text: text.to_string(),
+                changed,
+            }),
+        }
+    }
+    if let Some(last) = segments.last_mut() {
+        let trimmed = last.text.trim_end_matches(['\n', '\r']).len();
+        last.text.truncate(trimmed);
+    }
+    segments.retain(|segment| !segment.text.is_empty());
+    segments
+}
+
+// Line hunks with word-level emphasis inside replaced lines, from `old` to `new`
+pub fn diff(old: &str, new: &str) -> Diff {
+    let text_diff = TextDiff::configure().timeout(TIMEOUT).diff_lines(old, new);
+    let deadline = Instant::now() + TIMEOUT;
+    let mut added = 0;
+    let mut removed = 0;
+
+    let mut hunks = Vec::new();
+    for group in text_diff.grouped_ops(CONTEXT_LINES) {
+        if group.iter().all(|op| matches!(op, DiffOp::Equal { .. })) {
+            continue;
+        }
+        let (Some(first), Some(last)) = (group.first(), group.last()) else {
+            continue;
+        };
+        let old_range = first.old_range().start..last.old_range().end;
+        let new_range = first.new_range().start..last.new_range().end;
+
+        let mut lines = Vec::new();
+        for op in &group {
+            for change in text_diff.iter_inline_changes_deadline(op, Some(deadline)) {
+                let kind = match change.tag() {
+                    ChangeTag::Equal => LineKind::Equal,
+                    ChangeTag::Insert => {
+                        added += 1;
+                        LineKind::Insert
+                    }
+                    ChangeTag::Delete => {
+                        removed += 1;
+                        LineKind::Delete
+                    }
+                };
+                lines.push(DiffLine {
+                    kind,
+                    old_line: change.old_index().map(|index| index + 1),
+                    new_line: change.new_index().map(|index| index + 1),
+                    segments: segments(change.values()),
+                });
+            }
+        }
+
+        hunks.push(DiffHunk {
+            old_start: old_range.start + 1,
+            old_lines: old_range.len(),
+            new_start: new_range.start + 1,
+            new_lines: new_range.len(),
+            lines,
+        });
+    }
+
+    Diff {
+        hunks,
+        added,
+        removed,
+    }
+}
+
+// Compares two texts the frontend already has, like a draft and a snapshot
+#[tauri::command]
+pub async fn diff_text(old: String, new: String) -> Result<Diff, String> {
+    tauri::async_runtime::spawn_blocking(move || diff(&old, &new))
+        .await
+        .map_err(|e| format!("Diff failed: {}", e))
+}
+
+// Compares two saved notes, `path_a` as the old side. Vault notes are read
+// decrypted; notes encrypted with a passphrase are refused.
+#[tauri::command]
+pub async fn diff_notes(path_a: String, path_b: String, app: AppHandle) -> Result<Diff, String> {
+    let old = properties::read_note(&app, Path::new(&path_a))?;
+    let new = properties::read_note(&app, Path::new(&path_b))?;
+    tauri::async_runtime::spawn_blocking(move || diff(&old, &new))
+        .await
+        .map_err(|e| format!("Diff failed: {}", e))
+}
+
+// A note's changes since its latest commit, or since `snapshot` (the newest
+// local-history snapshot, which the frontend keeps) when the note isn't
+// committed. `content` is a draft to review before saving; without it the
+// saved file is compared. Vault notes are committed encrypted, so they're
+// always compared with the snapshot.
+#[tauri::command]
+pub async fn diff_against_head(
+    path: String,
+    content: Option<String>,
+    snapshot: Option<String>,
+    app: AppHandle,
+) -> Result<HeadDiff, String> {
+    let path = PathBuf::from(path);
+    storage::ensure_accessible(&app, &path)?;
+    let current = match content {
+        Some(content) => content,
+        None => properties::read_note(&app, &path)?,
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let head = if vault::vault_root(&path).is_some() {
+            None
+        } else {
+            git::head_version(&path)
+        };
+        match (head, snapshot) {
+            (Some(head), _) => {
+                // Commits usually hold LF endings even where the checkout has CRLF
+                let committed = if current.contains("\r\n") && !head.content.contains("\r\n") {
+                    head.content.replace('\n', "\r\n")
+                } else {
+                    head.content
+                };
+                Ok(HeadDiff {
+                    source: HeadSource::Git,
+                    commit: Some(head.commit),
+                    diff: diff(&committed, &current),
+                })
+            }
+            (None, Some(snapshot)) => Ok(HeadDiff {
+                source: HeadSource::Snapshot,
+                commit: None,
+                diff: diff(&snapshot, &current),
+            }),
+            (None, None) => {
+                Err("This note has no commit or saved snapshot to compare with".to_string())
+            }
+        }
+    })
+    .await
+    .map_err(|e| format!("Diff failed: {}", e))?
+}
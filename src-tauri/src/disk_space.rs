@@ -0,0 +1,151 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Serialized like `NoteWriteError` so the frontend can show how much room is missing
+#[derive(Debug, Clone, Serialize)]
+pub struct InsufficientSpace {
+    code: &'static str,
+    pub message: String,
+    pub path: String,
+    pub required: u64,
+    pub available: u64,
+}
+
+// Commands that check space before writing fail with either the structured
+// error or the usual string; untagged so strings reach the frontend unchanged
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum SpaceError {
+    Insufficient(InsufficientSpace),
+    Other(String),
+}
+
+impl From<String> for SpaceError {
+    fn from(message: String) -> Self {
+        SpaceError::Other(message)
+    }
+}
+
+impl From<&str> for SpaceError {
+    fn from(message: &str) -> Self {
+        SpaceError::Other(message.to_string())
+    }
+}
+
+impl From<InsufficientSpace> for SpaceError {
+    fn from(error: InsufficientSpace) -> Self {
+        SpaceError::Insufficient(error)
+    }
+}
+
+// The destination may not exist yet; its volume is the nearest existing ancestor's
+fn existing_ancestor(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|ancestor| ancestor.exists())
+        .map(Path::to_path_buf)
+}
+
+#[cfg(unix)]
+fn free_bytes(path: &Path) -> Option<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    // SAFETY: statvfs only writes into the zeroed struct we hand it
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    // Blocks available to unprivileged users, not the root-reserved ones
+    #[allow(clippy::unnecessary_cast)]
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(windows)]
+fn free_bytes(path: &Path) -> Option<u64> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::PCWSTR;
+    use windows::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let wide: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut available = 0u64;
+    // SAFETY: `wide` is NUL-terminated and outlives the call; `available` is a valid out pointer
+    unsafe { GetDiskFreeSpaceExW(PCWSTR(wide.as_ptr()), Some(&mut available), None, None) }.ok()?;
+    Some(available)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn free_bytes(_path: &Path) -> Option<u64> {
+    None
+}
+
+// Free space on the volume `path` is on, or None when the platform can't tell
+pub fn available_bytes(path: &Path) -> Option<u64> {
+    free_bytes(&existing_ancestor(path)?)
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+// Fails before anything is written when `required` bytes won't fit at `path`.
+// Volumes that don't report free space are let through.
+pub fn ensure_space(path: &Path, required: u64) -> Result<(), InsufficientSpace> {
+    if required == 0 {
+        return Ok(());
+    }
+    match available_bytes(path) {
+        Some(available) if available < required => Err(InsufficientSpace {
+            code: "insufficient_space",
+            message: format!(
+                "Not enough disk space: needs {}, {} available",
+                format_bytes(required),
+                format_bytes(available)
+            ),
+            path: path.to_string_lossy().to_string(),
+            required,
+            available,
+        }),
+        _ => Ok(()),
+    }
+}
+
+// Bytes a recursive copy of `path` will write
+pub fn size_on_disk(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| size_on_disk(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+// For exports written from the frontend
+#[tauri::command]
+pub fn check_disk_space(path: String, required: u64) -> Result<(), InsufficientSpace> {
+    ensure_space(Path::new(&path), required)
+}
@@ -0,0 +1,107 @@
+use crate::i18n;
+use crate::markdown;
+use crate::settings::SettingsState;
+use crate::storage;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, State};
+use tauri_plugin_dialog::{DialogExt, FilePath};
+use tauri_plugin_fs::{FsExt, OpenOptions};
+
+// Picked documents are notes, so anything larger is almost certainly not one
+const MAX_DOCUMENT_BYTES: u64 = 10 * 1024 * 1024;
+
+// Providers hand out `content://` (Android) or file URLs whose last segment is
+// the document id; plain paths come from desktop pickers
+fn document_name(file: &FilePath) -> String {
+    let raw = match file {
+        FilePath::Path(path) => path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        FilePath::Url(url) => url
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .map(markdown::percent_decode)
+            .unwrap_or_default(),
+    };
+    // Android document ids look like `primary:Documents/Note.md`
+    let name = raw.rsplit(['/', ':']).next().unwrap_or_default().trim();
+    if name.is_empty() {
+        "Imported note.md".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+fn read_document(app: &AppHandle, file: &FilePath) -> Result<String, String> {
+    let mut options = OpenOptions::new();
+    options.read(true);
+    let handle = app
+        .fs()
+        .open(file.clone(), options)
+        .map_err(|e| i18n::tf("error.read_note", &[&e]))?;
+
+    let mut contents = String::new();
+    let read = handle
+        .take(MAX_DOCUMENT_BYTES + 1)
+        .read_to_string(&mut contents);
+
+    // The security-scoped grant only needs to last for the read
+    #[cfg(target_os = "ios")]
+    let _ = app
+        .fs()
+        .stop_accessing_security_scoped_resource(file.clone());
+
+    read.map_err(|e| i18n::tf("error.read_note", &[&e]))?;
+    if contents.len() as u64 > MAX_DOCUMENT_BYTES {
+        return Err("Document is too large to import as a note".to_string());
+    }
+    Ok(contents)
+}
+
+// Copies notes picked through the system document picker (the Files app on iOS,
+// the Storage Access Framework on Android, the file dialog on desktop) into a
+// workspace folder. Providers only grant access to the picked documents, so the
+// workspace keeps its own copy rather than a path it could lose access to.
+#[tauri::command]
+pub async fn import_documents(
+    folder_path: String,
+    app: AppHandle,
+    settings_state: State<'_, SettingsState>,
+) -> Result<Vec<String>, String> {
+    let folder = PathBuf::from(&folder_path);
+    storage::ensure_accessible(&app, &folder)?;
+    if !folder.is_dir() {
+        return Err(i18n::t("error.folder_missing"));
+    }
+
+    let extensions = settings_state.current().watched_extensions;
+    let dialog = app.dialog().file();
+    // Mobile pickers filter by MIME type, which few providers report for markdown
+    #[cfg(desktop)]
+    let dialog = {
+        let filter: Vec<&str> = extensions.iter().map(String::as_str).collect();
+        dialog.add_filter("Notes", &filter)
+    };
+    let Some(picked) = dialog.blocking_pick_files() else {
+        return Ok(Vec::new());
+    };
+
+    let mut imported = Vec::new();
+    for file in picked {
+        let name = document_name(&file);
+        // Some providers drop the extension, and the note would vanish from the tree
+        let name = if markdown::has_note_extension(Path::new(&name), &extensions) {
+            name
+        } else {
+            format!("{}.md", name)
+        };
+        let contents = read_document(&app, &file)?;
+        let path =
+            crate::create_markdown_file(folder_path.clone(), name, Some(contents), app.clone())?;
+        imported.push(path);
+    }
+
+    Ok(imported)
+}
@@ -0,0 +1,204 @@
+use crate::share;
+use std::path::PathBuf;
+use tauri::{AppHandle, WebviewWindow};
+
+#[cfg(target_os = "macos")]
+use objc2::rc::Retained;
+#[cfg(target_os = "macos")]
+use objc2::runtime::{NSObject, NSObjectProtocol};
+#[cfg(target_os = "macos")]
+use objc2::{define_class, msg_send, MainThreadMarker, MainThreadOnly};
+#[cfg(target_os = "macos")]
+use objc2_app_kit::{NSDragOperation, NSDraggingContext, NSDraggingSession, NSDraggingSource};
+
+// Drops are only offered as copies, so Finder never moves a note out of its
+// workspace
+#[cfg(target_os = "macos")]
+define_class!(
+    #[unsafe(super(NSObject))]
+    #[thread_kind = MainThreadOnly]
+    #[name = "MarkyDragSource"]
+    struct DragSource;
+
+    unsafe impl NSObjectProtocol for DragSource {}
+
+    unsafe impl NSDraggingSource for DragSource {
+        #[unsafe(method(draggingSession:sourceOperationMaskForDraggingContext:))]
+        fn source_operation_mask(
+            &self,
+            _session: &NSDraggingSession,
+            _context: NSDraggingContext,
+        ) -> NSDragOperation {
+            NSDragOperation::Copy
+        }
+    }
+);
+
+#[cfg(target_os = "macos")]
+impl DragSource {
+    fn new(mtm: MainThreadMarker) -> Retained<Self> {
+        let this = Self::alloc(mtm).set_ivars(());
+        // SAFETY: NSObject's `init` takes no arguments
+        unsafe { msg_send![super(this), init] }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn begin(app: &AppHandle, window: &WebviewWindow, file: PathBuf) -> Result<(), String> {
+    use objc2::runtime::{AnyObject, ProtocolObject};
+    use objc2::AllocAnyThread;
+    use objc2_app_kit::{
+        NSDraggingItem, NSEvent, NSEventModifierFlags, NSEventType, NSWindow, NSWorkspace,
+    };
+    use objc2_foundation::{NSArray, NSPoint, NSRect, NSSize, NSString, NSURL};
+    use std::cell::RefCell;
+
+    thread_local! {
+        // The session doesn't keep its source alive
+        static SOURCE: RefCell<Option<Retained<DragSource>>> = const { RefCell::new(None) };
+    }
+
+    // The raw pointer isn't Send, so it's looked up again on the main thread
+    let window = window.clone();
+    app.run_on_main_thread(move || {
+        let Some(mtm) = MainThreadMarker::new() else {
+            return;
+        };
+        let Ok(ns_window) = window.ns_window() else {
+            tracing::error!("Failed to start drag: window has no native handle");
+            return;
+        };
+        // SAFETY: Tauri hands out a valid NSWindow pointer for as long as the window is open
+        let ns_window: &NSWindow = unsafe { &*ns_window.cast() };
+        let Some(view) = ns_window.contentView() else {
+            return;
+        };
+
+        let path = NSString::from_str(&file.to_string_lossy());
+        let url = NSURL::fileURLWithPath(&path);
+        let item = NSDraggingItem::initWithPasteboardWriter(
+            NSDraggingItem::alloc(),
+            ProtocolObject::from_ref(&*url),
+        );
+        // The file's own Finder icon, centered under the pointer
+        let icon = NSWorkspace::sharedWorkspace().iconForFile(&path);
+        let icon: &AnyObject = &icon;
+        let location = ns_window.mouseLocationOutsideOfEventStream();
+        let point = view.convertPoint_fromView(location, None);
+        let frame = NSRect::new(
+            NSPoint::new(point.x - 16.0, point.y - 16.0),
+            NSSize::new(32.0, 32.0),
+        );
+        // SAFETY: an NSImage is valid dragging contents
+        unsafe { item.setDraggingFrame_contents(frame, Some(icon)) };
+
+        // The webview already consumed the mouse-down, so the session starts
+        // from a drag event made up at the pointer
+        let Some(event) = NSEvent::mouseEventWithType_location_modifierFlags_timestamp_windowNumber_context_eventNumber_clickCount_pressure(
+            NSEventType::LeftMouseDragged,
+            location,
+            NSEventModifierFlags::empty(),
+            0.0,
+            ns_window.windowNumber(),
+            None,
+            0,
+            1,
+            1.0,
+        ) else {
+            tracing::error!("Failed to start drag: no mouse event to start it from");
+            return;
+        };
+
+        let source = DragSource::new(mtm);
+        view.beginDraggingSessionWithItems_event_source(
+            &NSArray::from_retained_slice(&[item]),
+            &event,
+            ProtocolObject::from_ref(&*source),
+        );
+        SOURCE.with(|current| *current.borrow_mut() = Some(source));
+    })
+    .map_err(|e| format!("Failed to start drag: {}", e))
+}
+
+#[cfg(target_os = "windows")]
+use windows::Win32::System::Ole::{IDropSource, IDropSource_Impl, DROPEFFECT};
+
+// Finishes the drop when the button comes up and cancels it on Escape
+#[cfg(target_os = "windows")]
+#[windows::core::implement(IDropSource)]
+struct DropSource;
+
+#[cfg(target_os = "windows")]
+impl IDropSource_Impl for DropSource_Impl {
+    fn QueryContinueDrag(
+        &self,
+        escape_pressed: windows::core::BOOL,
+        key_state: windows::Win32::System::SystemServices::MODIFIERKEYS_FLAGS,
+    ) -> windows::core::HRESULT {
+        use windows::Win32::Foundation::{DRAGDROP_S_CANCEL, DRAGDROP_S_DROP, S_OK};
+        use windows::Win32::System::SystemServices::MK_LBUTTON;
+
+        if escape_pressed.as_bool() {
+            DRAGDROP_S_CANCEL
+        } else if key_state.0 & MK_LBUTTON.0 == 0 {
+            DRAGDROP_S_DROP
+        } else {
+            S_OK
+        }
+    }
+
+    fn GiveFeedback(&self, _effect: DROPEFFECT) -> windows::core::HRESULT {
+        windows::Win32::Foundation::DRAGDROP_S_USEDEFAULTCURSORS
+    }
+}
+
+// The shell builds the data object, so drop targets see the same file
+// formats as a drag out of Explorer
+#[cfg(target_os = "windows")]
+fn begin(app: &AppHandle, _window: &WebviewWindow, file: PathBuf) -> Result<(), String> {
+    use windows::core::HSTRING;
+    use windows::Win32::System::Com::IDataObject;
+    use windows::Win32::System::Ole::{DoDragDrop, DROPEFFECT_COPY, DROPEFFECT_NONE};
+    use windows::Win32::UI::Shell::{BHID_DataObject, IShellItem, SHCreateItemFromParsingName};
+
+    let drag = move || -> windows::core::Result<()> {
+        // SAFETY: the path is a valid null-terminated wide string for the call's duration
+        let item: IShellItem =
+            unsafe { SHCreateItemFromParsingName(&HSTRING::from(file.as_os_str()), None) }?;
+        // SAFETY: `BHID_DataObject` asks the item for an IDataObject, as requested
+        let data: IDataObject = unsafe { item.BindToHandler(None, &BHID_DataObject) }?;
+        let source: IDropSource = DropSource.into();
+        let mut effect = DROPEFFECT_NONE;
+        // SAFETY: runs on the main thread, where the event loop has initialized OLE;
+        // the call pumps messages until the drop ends
+        unsafe { DoDragDrop(&data, &source, DROPEFFECT_COPY, &mut effect) }.ok()
+    };
+
+    app.run_on_main_thread(move || {
+        if let Err(e) = drag() {
+            tracing::error!("Failed to start drag: {}", e);
+        }
+    })
+    .map_err(|e| format!("Failed to start drag: {}", e))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn begin(_app: &AppHandle, _window: &WebviewWindow, _file: PathBuf) -> Result<(), String> {
+    Err("Dragging notes out isn't available on this platform; export the note instead".to_string())
+}
+
+// Drags the note itself (`markdown`) or an export the frontend rendered
+// (`html` text or `pdf` bytes) out of the app as a real file, for dropping
+// into Finder, Explorer or a mail message. Call while the mouse button is
+// still held.
+#[tauri::command]
+pub fn drag_note(
+    path: String,
+    format: String,
+    contents: Option<Vec<u8>>,
+    app: AppHandle,
+    window: WebviewWindow,
+) -> Result<(), String> {
+    let file = share::export_file(&app, &path, &format, contents)?;
+    begin(&app, &window, file)
+}
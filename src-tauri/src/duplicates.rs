@@ -0,0 +1,551 @@
+use crate::index::{collect_markdown_files, IndexState};
+use crate::markdown;
+use crate::pins;
+use crate::secure_delete;
+use crate::settings::SettingsState;
+use crate::storage;
+use crate::vault;
+use crate::workspace_config::{self, NoteFilter};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, State};
+
+const DEFAULT_THRESHOLD: f64 = 0.8;
+const SHINGLE_WORDS: usize = 5;
+// 32 bands of 4 rows: pairs around 0.5 similarity already have good odds of colliding
+const MINHASH_BANDS: usize = 32;
+const MINHASH_ROWS: usize = 4;
+const MINHASH_SIZE: usize = MINHASH_BANDS * MINHASH_ROWS;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DuplicateNote {
+    name: String,
+    path: String,
+    words: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExactDuplicateGroup {
+    hash: String,
+    notes: Vec<DuplicateNote>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NearDuplicatePair {
+    similarity: f64,
+    first: DuplicateNote,
+    second: DuplicateNote,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateReport {
+    exact: Vec<ExactDuplicateGroup>,
+    near: Vec<NearDuplicatePair>,
+}
+
+struct Candidate {
+    note: DuplicateNote,
+    hash: String,
+    shingles: HashSet<u64>,
+    signature: Vec<u64>,
+}
+
+// Line endings and trailing whitespace differences shouldn't hide an exact copy
+fn content_hash(content: &str) -> String {
+    let mut hasher = Sha256::new();
+    for line in content.trim().lines() {
+        hasher.update(line.trim_end().as_bytes());
+        hasher.update(b"\n");
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+fn shingles(content: &str) -> HashSet<u64> {
+    let words: Vec<String> = content
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    let width = SHINGLE_WORDS.min(words.len());
+    if width == 0 {
+        return HashSet::new();
+    }
+
+    words
+        .windows(width)
+        .map(|window| {
+            let mut hasher = DefaultHasher::new();
+            window.hash(&mut hasher);
+            hasher.finish()
+        })
+        .collect()
+}
+
+fn splitmix64(mut value: u64) -> u64 {
+    value = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^ (value >> 31)
+}
+
+fn minhash(shingles: &HashSet<u64>) -> Vec<u64> {
+    (0..MINHASH_SIZE as u64)
+        .map(|seed| {
+            let salt = splitmix64(seed);
+            shingles
+                .iter()
+                .map(|shingle| splitmix64(shingle ^ salt))
+                .min()
+                .unwrap_or(u64::MAX)
+        })
+        .collect()
+}
+
+fn jaccard(a: &HashSet<u64>, b: &HashSet<u64>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+fn load_candidate(path: &Path) -> Option<Candidate> {
+    let content = fs::read_to_string(path).ok()?;
+    let shingles = shingles(&content);
+    let signature = minhash(&shingles);
+
+    Some(Candidate {
+        note: DuplicateNote {
+            name: markdown::note_title(path),
+            path: path.to_string_lossy().to_string(),
+            words: markdown::count_words(&content),
+        },
+        hash: content_hash(&content),
+        shingles,
+        signature,
+    })
+}
+
+#[tauri::command]
+pub async fn find_duplicate_notes(
+    folder_path: String,
+    threshold: Option<f64>,
+    index_state: State<'_, IndexState>,
+) -> Result<DuplicateReport, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+
+    let threshold = threshold.unwrap_or(DEFAULT_THRESHOLD);
+    if !(threshold > 0.0 && threshold <= 1.0) {
+        return Err("Threshold must be between 0 and 1".to_string());
+    }
+
+    let mut paths: Vec<PathBuf> =
+        index_state.with_workspace(&root, |index| index.notes.keys().cloned().collect())?;
+    paths.sort();
+
+    let candidates: Vec<Candidate> = paths.iter().filter_map(|p| load_candidate(p)).collect();
+
+    let mut by_hash: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (idx, candidate) in candidates.iter().enumerate() {
+        by_hash.entry(&candidate.hash).or_default().push(idx);
+    }
+
+    let mut exact: Vec<ExactDuplicateGroup> = by_hash
+        .iter()
+        .filter(|(_, members)| members.len() > 1)
+        .map(|(hash, members)| ExactDuplicateGroup {
+            hash: hash.to_string(),
+            notes: members
+                .iter()
+                .map(|idx| candidates[*idx].note.clone())
+                .collect(),
+        })
+        .collect();
+    exact.sort_by(|a, b| a.notes[0].path.cmp(&b.notes[0].path));
+
+    // Locality-sensitive hashing: only notes sharing a whole band get compared
+    let mut pairs: HashSet<(usize, usize)> = HashSet::new();
+    for band in 0..MINHASH_BANDS {
+        let mut buckets: HashMap<&[u64], Vec<usize>> = HashMap::new();
+        for (idx, candidate) in candidates.iter().enumerate() {
+            if candidate.shingles.is_empty() {
+                continue;
+            }
+            let rows = &candidate.signature[band * MINHASH_ROWS..(band + 1) * MINHASH_ROWS];
+            buckets.entry(rows).or_default().push(idx);
+        }
+
+        for members in buckets.values() {
+            for (i, first) in members.iter().enumerate() {
+                for second in &members[i + 1..] {
+                    pairs.insert((*first, *second));
+                }
+            }
+        }
+    }
+
+    let mut near: Vec<NearDuplicatePair> = pairs
+        .into_iter()
+        .filter(|(a, b)| candidates[*a].hash != candidates[*b].hash)
+        .filter_map(|(a, b)| {
+            let similarity = jaccard(&candidates[a].shingles, &candidates[b].shingles);
+            (similarity >= threshold).then(|| NearDuplicatePair {
+                similarity,
+                first: candidates[a].note.clone(),
+                second: candidates[b].note.clone(),
+            })
+        })
+        .collect();
+    near.sort_by(|a, b| {
+        b.similarity
+            .total_cmp(&a.similarity)
+            .then_with(|| a.first.path.cmp(&b.first.path))
+    });
+
+    Ok(DuplicateReport { exact, near })
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateFile {
+    name: String,
+    path: String,
+    is_note: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateFileGroup {
+    hash: String,
+    size: u64,
+    files: Vec<DuplicateFile>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MergeReport {
+    relinked_notes: usize,
+    removed: usize,
+}
+
+// Every file under `dir` the workspace doesn't ignore, attachments included
+fn collect_files(dir: &Path, filter: &NoteFilter, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if filter.is_ignored(&path) {
+            continue;
+        }
+        if path.is_dir() {
+            collect_files(&path, filter, files);
+        } else if path.is_file() {
+            files.push(path);
+        }
+    }
+}
+
+fn file_hash(path: &Path) -> Result<String, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(format!("{:x}", Sha256::digest(&bytes)))
+}
+
+// Byte-identical files (notes and attachments), largest savings first. Only
+// files of equal size get hashed.
+#[tauri::command]
+pub async fn find_duplicate_files(
+    folder_path: String,
+    settings_state: State<'_, SettingsState>,
+) -> Result<Vec<DuplicateFileGroup>, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+    // Vault files are encrypted with fresh nonces, so copies never match
+    if vault::is_vault_root(&root) {
+        return Err("Vaults can't be checked for duplicate files".to_string());
+    }
+
+    let settings = settings_state.current();
+    let filter = NoteFilter::for_workspace(&root, &settings);
+    let mut paths = Vec::new();
+    collect_files(&root, &filter, &mut paths);
+    paths.sort();
+
+    let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+    for path in paths {
+        let size = fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0);
+        // Empty files all match and there's nothing to reclaim
+        if size > 0 {
+            by_size.entry(size).or_default().push(path);
+        }
+    }
+
+    let mut groups = Vec::new();
+    for (size, paths) in by_size.into_iter().filter(|(_, paths)| paths.len() > 1) {
+        let mut by_hash: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        for path in paths {
+            match file_hash(&path) {
+                Ok(hash) => by_hash.entry(hash).or_default().push(path),
+                Err(e) => tracing::warn!("{}", e),
+            }
+        }
+        groups.extend(
+            by_hash
+                .into_iter()
+                .filter(|(_, paths)| paths.len() > 1)
+                .map(|(hash, paths)| DuplicateFileGroup {
+                    hash,
+                    size,
+                    files: paths
+                        .iter()
+                        .map(|path| DuplicateFile {
+                            name: path
+                                .file_name()
+                                .map(|name| name.to_string_lossy().to_string())
+                                .unwrap_or_default(),
+                            path: path.to_string_lossy().to_string(),
+                            is_note: markdown::has_note_extension(
+                                path,
+                                &settings.watched_extensions,
+                            ),
+                        })
+                        .collect(),
+                }),
+        );
+    }
+    groups.sort_by(|a, b| {
+        let wasted = |group: &DuplicateFileGroup| group.size * (group.files.len() as u64 - 1);
+        wasted(b)
+            .cmp(&wasted(a))
+            .then_with(|| a.files[0].path.cmp(&b.files[0].path))
+    });
+    Ok(groups)
+}
+
+// `to` relative to `from_dir`, with `/` separators like note links use
+fn relative_link(from_dir: &Path, to: &Path) -> String {
+    let from: Vec<_> = from_dir.components().collect();
+    let target: Vec<_> = to.components().collect();
+    let common = from.iter().zip(&target).take_while(|(a, b)| a == b).count();
+    let mut parts = vec!["..".to_string(); from.len() - common];
+    parts.extend(
+        target[common..]
+            .iter()
+            .map(|part| part.as_os_str().to_string_lossy().to_string()),
+    );
+    parts.join("/")
+}
+
+// Rewrites the path part of every `[text](target)` / `![alt](target)` that
+// `replace` maps to a new one. `replace` is told whether the target was `<wrapped>`.
+fn rewrite_link_targets(content: &str, replace: &dyn Fn(&str, bool) -> Option<String>) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("](") {
+        let (before, after) = rest.split_at(start + 2);
+        output.push_str(before);
+        let Some(end) = after.find(')') else {
+            rest = after;
+            break;
+        };
+        let inner = &after[..end];
+        let trimmed = inner.trim_start();
+        let leading = inner.len() - trimmed.len();
+        let (offset, target, wrapped) = match trimmed.strip_prefix('<') {
+            Some(unwrapped) => (leading + 1, unwrapped.split('>').next().unwrap_or(""), true),
+            None => (
+                leading,
+                trimmed.split_whitespace().next().unwrap_or(""),
+                false,
+            ),
+        };
+        let path_part = target.split('#').next().unwrap_or("");
+        match replace(path_part, wrapped) {
+            Some(new_target) => {
+                output.push_str(&inner[..offset]);
+                output.push_str(&new_target);
+                output.push_str(&inner[offset + path_part.len()..]);
+            }
+            None => output.push_str(inner),
+        }
+        output.push(')');
+        rest = &after[end + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+// Same for the target of `[[target|alias]]`, `[[target#heading]]`, and `![[target]]`
+fn rewrite_wiki_targets(content: &str, replace: &dyn Fn(&str) -> Option<String>) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("[[") {
+        let (before, after) = rest.split_at(start + 2);
+        output.push_str(before);
+        let Some(end) = after.find("]]") else {
+            rest = after;
+            break;
+        };
+        let inner = &after[..end];
+        let target_end = inner.find(['|', '#']).unwrap_or(inner.len());
+        let target = inner[..target_end].trim();
+        match replace(target) {
+            Some(new_target) => {
+                output.push_str(&new_target);
+                output.push_str(&inner[target_end..]);
+            }
+            None => output.push_str(inner),
+        }
+        output.push_str("]]");
+        rest = &after[end + 2..];
+    }
+    output.push_str(rest);
+    output
+}
+
+// What a wiki link calls a file: notes by title, attachments by file name
+fn wiki_name(path: &Path, is_note: bool) -> String {
+    if is_note {
+        markdown::note_title(path)
+    } else {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+// Keeps `keep_path`, points every link to one of `duplicate_paths` at it, then
+// deletes the duplicates. Refuses unless all of them are byte-identical.
+#[tauri::command]
+pub async fn merge_duplicate_files(
+    folder_path: String,
+    keep_path: String,
+    duplicate_paths: Vec<String>,
+    app: AppHandle,
+    settings_state: State<'_, SettingsState>,
+) -> Result<MergeReport, String> {
+    let root = PathBuf::from(&folder_path);
+    storage::ensure_accessible(&app, &root)?;
+    let root = root
+        .canonicalize()
+        .map_err(|_| "Folder does not exist".to_string())?;
+    if vault::is_vault_root(&root) {
+        return Err("Vaults can't be checked for duplicate files".to_string());
+    }
+
+    let inside = |path: &str| -> Result<PathBuf, String> {
+        let path = Path::new(path)
+            .canonicalize()
+            .map_err(|_| format!("{} no longer exists", path))?;
+        if !path.starts_with(&root) || !path.is_file() {
+            return Err(format!(
+                "{} is not a file in this workspace",
+                path.display()
+            ));
+        }
+        Ok(path)
+    };
+    let keep = inside(&keep_path)?;
+    let keep_hash = file_hash(&keep)?;
+    let mut duplicates = Vec::new();
+    for path in &duplicate_paths {
+        let path = inside(path)?;
+        if path == keep {
+            continue;
+        }
+        if file_hash(&path)? != keep_hash {
+            return Err(format!(
+                "{} changed and is no longer a duplicate",
+                path.display()
+            ));
+        }
+        duplicates.push(path);
+    }
+    if duplicates.is_empty() {
+        return Ok(MergeReport {
+            relinked_notes: 0,
+            removed: 0,
+        });
+    }
+
+    let settings = settings_state.current();
+    let extensions = &settings.watched_extensions;
+    let attachment_dir = root.join(workspace_config::effective(&root, &settings).attachment_folder);
+    let keep_is_note = markdown::has_note_extension(&keep, extensions);
+    let keep_name = wiki_name(&keep, keep_is_note);
+    let renamed: Vec<String> = duplicates
+        .iter()
+        .map(|path| wiki_name(path, markdown::has_note_extension(path, extensions)))
+        .filter(|name| markdown::link_key(name) != markdown::link_key(&keep_name))
+        .map(|name| markdown::link_key(&name))
+        .collect();
+
+    let filter = NoteFilter::for_workspace(&root, &settings);
+    let mut notes = Vec::new();
+    collect_markdown_files(&root, &filter, &mut notes);
+
+    let mut relinked_notes = 0;
+    for note in notes {
+        if duplicates.contains(&note) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&note) else {
+            continue;
+        };
+        let note_dir = note.parent().unwrap_or(&root).to_path_buf();
+        let relinked = rewrite_link_targets(&content, &|target, wrapped| {
+            if target.is_empty() || target.contains("://") || target.starts_with("mailto:") {
+                return None;
+            }
+            let decoded = markdown::percent_decode(target);
+            let hit = [&note_dir, &attachment_dir, &root]
+                .iter()
+                .filter_map(|base| base.join(&decoded).canonicalize().ok())
+                .any(|candidate| duplicates.contains(&candidate));
+            if !hit {
+                return None;
+            }
+            let link = relative_link(&note_dir, &keep);
+            Some(if wrapped {
+                link
+            } else {
+                link.replace(' ', "%20")
+            })
+        });
+        let relinked = rewrite_wiki_targets(&relinked, &|target| {
+            let name = target.rsplit('/').next().unwrap_or(target);
+            renamed
+                .contains(&markdown::link_key(name))
+                .then(|| keep_name.clone())
+        });
+        if relinked == content {
+            continue;
+        }
+        fs::write(&note, &relinked)
+            .map_err(|e| format!("Failed to relink {}: {}", note.display(), e))?;
+        relinked_notes += 1;
+    }
+
+    for path in &duplicates {
+        secure_delete::remove(path, settings.secure_delete)?;
+    }
+    pins::refresh_menu(&app, &root);
+
+    Ok(MergeReport {
+        relinked_notes,
+        removed: duplicates.len(),
+    })
+}
@@ -0,0 +1,296 @@
+use crate::storage;
+use crate::vault;
+use chrono::{DateTime, Duration as ChronoDuration, Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, Window};
+
+// `.~marky-lock.Note.md` next to the note; hidden, so the tree and watcher skip it
+const LOCK_PREFIX: &str = ".~marky-lock.";
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
+// A Marky that crashed or lost its connection stops refreshing its lock
+const STALE_AFTER_MINUTES: i64 = 5;
+
+// Swap and lock files other editors keep next to a file they have open
+const FOREIGN_LOCKS: [(&str, &str, &str); 3] = [
+    ("Vim", ".", ".swp"),
+    ("Emacs", ".#", ""),
+    ("LibreOffice", ".~lock.", "#"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LockFile {
+    app: String,
+    // Which running Marky wrote it; each launch gets its own
+    instance: String,
+    host: String,
+    user: String,
+    since: String,
+    heartbeat: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LockHolder {
+    app: String,
+    host: String,
+    user: String,
+    since: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LockStatus {
+    acquired: bool,
+    // Who else has the note open when `acquired` is false
+    holder: Option<LockHolder>,
+}
+
+// Notes this instance holds locks on, and which windows want each one
+pub struct EditLockState {
+    instance: String,
+    held: Mutex<HashMap<PathBuf, HashSet<String>>>,
+}
+
+impl Default for EditLockState {
+    fn default() -> Self {
+        EditLockState {
+            instance: format!("{}-{}", std::process::id(), Utc::now().timestamp_millis()),
+            held: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+fn sibling(path: &Path, prefix: &str, suffix: &str) -> Option<PathBuf> {
+    let name = path.file_name()?.to_string_lossy();
+    Some(path.with_file_name(format!("{}{}{}", prefix, name, suffix)))
+}
+
+fn lock_path(path: &Path) -> Option<PathBuf> {
+    sibling(path, LOCK_PREFIX, "")
+}
+
+#[cfg(unix)]
+fn host_name() -> String {
+    let mut buffer = [0u8; 256];
+    let result = unsafe { libc::gethostname(buffer.as_mut_ptr().cast(), buffer.len()) };
+    if result != 0 {
+        return String::new();
+    }
+    let end = buffer.iter().position(|b| *b == 0).unwrap_or(buffer.len());
+    String::from_utf8_lossy(&buffer[..end]).to_string()
+}
+
+#[cfg(not(unix))]
+fn host_name() -> String {
+    std::env::var("COMPUTERNAME").unwrap_or_default()
+}
+
+fn user_name() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_default()
+}
+
+fn read_lock(path: &Path) -> Option<LockFile> {
+    let raw = fs::read_to_string(lock_path(path)?).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn is_fresh(lock: &LockFile) -> bool {
+    DateTime::parse_from_rfc3339(&lock.heartbeat).is_ok_and(|heartbeat| {
+        Utc::now().signed_duration_since(heartbeat) < ChronoDuration::minutes(STALE_AFTER_MINUTES)
+    })
+}
+
+fn foreign_holder(path: &Path) -> Option<LockHolder> {
+    FOREIGN_LOCKS.iter().find_map(|(app, prefix, suffix)| {
+        let candidate = sibling(path, prefix, suffix)?;
+        // Emacs uses a dangling symlink, so `exists` would miss it
+        let metadata = fs::symlink_metadata(&candidate).ok()?;
+        let since = metadata
+            .modified()
+            .ok()
+            .map(|modified| DateTime::<Local>::from(modified).to_rfc3339());
+        Some(LockHolder {
+            app: app.to_string(),
+            host: host_name(),
+            user: String::new(),
+            since,
+        })
+    })
+}
+
+fn write_lock(state: &EditLockState, path: &Path, since: Option<String>) -> Result<(), String> {
+    let Some(lock_path) = lock_path(path) else {
+        return Ok(());
+    };
+    let now = Local::now().to_rfc3339();
+    let lock = LockFile {
+        app: "Marky".to_string(),
+        instance: state.instance.clone(),
+        host: host_name(),
+        user: user_name(),
+        since: since.unwrap_or_else(|| now.clone()),
+        heartbeat: now,
+    };
+    let raw = serde_json::to_string_pretty(&lock)
+        .map_err(|e| format!("Failed to serialize note lock: {}", e))?;
+    fs::write(lock_path, raw).map_err(|e| format!("Failed to write note lock: {}", e))
+}
+
+// Removes the lock file only while it's still ours; someone may have taken over
+fn remove_lock(state: &EditLockState, path: &Path) {
+    let ours = read_lock(path).is_some_and(|lock| lock.instance == state.instance);
+    if let (true, Some(lock_path)) = (ours, lock_path(path)) {
+        if let Err(e) = fs::remove_file(&lock_path) {
+            tracing::warn!("Failed to remove note lock {}: {}", lock_path.display(), e);
+        }
+    }
+}
+
+fn acquire(app: &AppHandle, path: &Path, label: &str, force: bool) -> Result<LockStatus, String> {
+    storage::ensure_accessible(app, path)?;
+    // Names inside a vault are part of what it hides
+    if vault::vault_root(path).is_some() || !path.is_file() {
+        return Ok(LockStatus {
+            acquired: true,
+            holder: None,
+        });
+    }
+
+    let state = app.state::<EditLockState>();
+    let existing = read_lock(path);
+    let others = existing
+        .as_ref()
+        .filter(|lock| lock.instance != state.instance && is_fresh(lock));
+    if !force {
+        let holder = others
+            .map(|lock| LockHolder {
+                app: lock.app.clone(),
+                host: lock.host.clone(),
+                user: lock.user.clone(),
+                since: Some(lock.since.clone()),
+            })
+            .or_else(|| foreign_holder(path));
+        if holder.is_some() {
+            return Ok(LockStatus {
+                acquired: false,
+                holder,
+            });
+        }
+    }
+
+    let since = existing
+        .filter(|lock| lock.instance == state.instance)
+        .map(|lock| lock.since);
+    write_lock(&state, path, since)?;
+    state
+        .held
+        .lock()
+        .map_err(|_| "Note locks are unavailable".to_string())?
+        .entry(path.to_path_buf())
+        .or_default()
+        .insert(label.to_string());
+    Ok(LockStatus {
+        acquired: true,
+        holder: None,
+    })
+}
+
+fn release(app: &AppHandle, path: &Path, label: &str) -> Result<(), String> {
+    let state = app.state::<EditLockState>();
+    let mut held = state
+        .held
+        .lock()
+        .map_err(|_| "Note locks are unavailable".to_string())?;
+    let Some(labels) = held.get_mut(path) else {
+        return Ok(());
+    };
+    labels.remove(label);
+    if labels.is_empty() {
+        held.remove(path);
+        remove_lock(&state, path);
+    }
+    Ok(())
+}
+
+// Called when a window closes, so its notes don't stay locked until Marky quits
+pub fn release_window(app: &AppHandle, label: &str) {
+    let state = app.state::<EditLockState>();
+    let Ok(mut held) = state.held.lock() else {
+        return;
+    };
+    held.retain(|path, labels| {
+        labels.remove(label);
+        if labels.is_empty() {
+            remove_lock(&state, path);
+        }
+        !labels.is_empty()
+    });
+}
+
+pub fn release_all(app: &AppHandle) {
+    let state = app.state::<EditLockState>();
+    let Ok(mut held) = state.held.lock() else {
+        return;
+    };
+    for path in held.keys() {
+        remove_lock(&state, path);
+    }
+    held.clear();
+}
+
+// Keeps held locks fresh so other instances don't treat them as abandoned
+pub fn spawn_heartbeat(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(HEARTBEAT_INTERVAL);
+        let state = app.state::<EditLockState>();
+        let Ok(mut held) = state.held.lock() else {
+            continue;
+        };
+        // Renamed or deleted notes leave their lock behind
+        held.retain(|path, _| {
+            if path.is_file() {
+                return true;
+            }
+            remove_lock(&state, path);
+            false
+        });
+        for path in held.keys() {
+            // Someone chose to edit anyway; the next acquire reports them
+            let taken_over = read_lock(path).is_some_and(|lock| lock.instance != state.instance);
+            if taken_over {
+                continue;
+            }
+            let since = read_lock(path).map(|lock| lock.since);
+            if let Err(e) = write_lock(&state, path, since) {
+                tracing::warn!("Failed to refresh note lock: {}", e);
+            }
+        }
+    });
+}
+
+// Takes the note's lock for this window, unless another Marky or editor has
+// it open; `force` takes it anyway after the user was warned
+#[tauri::command]
+pub fn acquire_note_lock(
+    path: String,
+    force: Option<bool>,
+    app: AppHandle,
+    window: Window,
+) -> Result<LockStatus, String> {
+    acquire(
+        &app,
+        &PathBuf::from(path),
+        window.label(),
+        force.unwrap_or(false),
+    )
+}
+
+#[tauri::command]
+pub fn release_note_lock(path: String, app: AppHandle, window: Window) -> Result<(), String> {
+    release(&app, &PathBuf::from(path), window.label())
+}
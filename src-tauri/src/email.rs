@@ -0,0 +1,714 @@
+use crate::http;
+use crate::i18n;
+use crate::markdown;
+use crate::secrets;
+use crate::settings::{EmailSettings, SettingsState};
+use crate::storage;
+use crate::vault;
+use crate::watcher::WatcherState;
+use crate::workspace_config;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Local;
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, ClientConnection, StreamOwned};
+use rustls_platform_verifier::ConfigVerifierExt;
+use serde::Serialize;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, State};
+
+pub const METHODS: [&str; 2] = ["client", "smtp"];
+pub const SECURITY: [&str; 3] = ["tls", "starttls", "none"];
+pub const PASSWORD_SECRET: &str = "email.smtp_password";
+
+const FORMATS: [&str; 3] = ["html", "markdown", "attachment"];
+const DRAFTS_DIR: &str = "email-drafts";
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+// Most providers cap messages around 25 MB; base64 grows attachments by a third
+const MAX_ATTACHMENT_BYTES: u64 = 18 * 1024 * 1024;
+
+#[derive(Debug, Serialize)]
+pub struct EmailResult {
+    method: String,
+    // The draft handed to the mail client, when there is one
+    draft_path: Option<String>,
+    attachments: Vec<String>,
+    skipped_attachments: Vec<String>,
+}
+
+struct Attachment {
+    name: String,
+    // Referenced by `cid:` from the HTML body
+    content_id: String,
+    link: String,
+    mime: &'static str,
+    data: Vec<u8>,
+}
+
+pub fn mime_type(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        "txt" => "text/plain",
+        "md" | "markdown" => "text/markdown",
+        "html" | "htm" => "text/html",
+        "csv" => "text/csv",
+        "zip" => "application/zip",
+        _ => "application/octet-stream",
+    }
+}
+
+// Links resolve against the note's folder; bare embed names may also live in
+// the attachment folder. Nothing outside the workspace is attached.
+pub fn resolve_link(
+    root: &Path,
+    note_dir: &Path,
+    attachment_dir: &Path,
+    link: &str,
+) -> Option<PathBuf> {
+    let link = markdown::percent_decode(link);
+    let relative = Path::new(&link);
+    let candidates = if relative.is_absolute() {
+        vec![relative.to_path_buf()]
+    } else {
+        vec![
+            note_dir.join(relative),
+            attachment_dir.join(relative),
+            root.join(relative),
+        ]
+    };
+
+    let root = root.canonicalize().ok()?;
+    candidates.into_iter().find_map(|candidate| {
+        let candidate = candidate.canonicalize().ok()?;
+        (candidate.starts_with(&root) && candidate.is_file()).then_some(candidate)
+    })
+}
+
+fn collect_attachments(
+    root: &Path,
+    note_path: &Path,
+    content: &str,
+    attachment_folder: &str,
+    extensions: &[String],
+) -> (Vec<Attachment>, Vec<String>) {
+    let note_dir = note_path.parent().unwrap_or(root);
+    let attachment_dir = root.join(attachment_folder);
+    let mut attachments: Vec<Attachment> = Vec::new();
+    let mut skipped = Vec::new();
+    let mut total: u64 = 0;
+
+    for link in markdown::extract_local_links(content) {
+        let Some(path) = resolve_link(root, note_dir, &attachment_dir, &link) else {
+            continue;
+        };
+        // Linked notes are part of the workspace, not of this message
+        if markdown::has_note_extension(&path, extensions) {
+            continue;
+        }
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "attachment".to_string());
+        if attachments.iter().any(|existing| existing.link == link) {
+            continue;
+        }
+
+        let size = fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0);
+        if total + size > MAX_ATTACHMENT_BYTES {
+            skipped.push(name);
+            continue;
+        }
+        match fs::read(&path) {
+            Ok(data) => {
+                total += size;
+                attachments.push(Attachment {
+                    content_id: format!("att{}@marky", attachments.len() + 1),
+                    mime: mime_type(&path),
+                    name,
+                    link,
+                    data,
+                });
+            }
+            Err(e) => {
+                tracing::warn!("Failed to read attachment {}: {}", path.display(), e);
+                skipped.push(name);
+            }
+        }
+    }
+
+    (attachments, skipped)
+}
+
+// RFC 2047 for anything that isn't plain ASCII
+fn encode_header(text: &str) -> String {
+    if text.is_ascii() && !text.contains(['\r', '\n']) {
+        return text.to_string();
+    }
+    format!(
+        "=?UTF-8?B?{}?=",
+        STANDARD.encode(text.replace(['\r', '\n'], " "))
+    )
+}
+
+fn wrap_base64(data: &[u8]) -> String {
+    let encoded = STANDARD.encode(data);
+    let mut wrapped = String::with_capacity(encoded.len() + encoded.len() / 38);
+    for chunk in encoded.as_bytes().chunks(76) {
+        wrapped.push_str(&String::from_utf8_lossy(chunk));
+        wrapped.push_str("\r\n");
+    }
+    wrapped
+}
+
+fn text_part(mime: &str, text: &str) -> String {
+    format!(
+        "Content-Type: {}; charset=utf-8\r\nContent-Transfer-Encoding: base64\r\n\r\n{}",
+        mime,
+        wrap_base64(text.as_bytes())
+    )
+}
+
+// `key="value"`, or for names that aren't plain ASCII, RFC 2231's
+// `key*=UTF-8''...`; encoded-words aren't allowed inside parameters
+fn mime_param(key: &str, value: &str) -> String {
+    let value = value.replace(['\r', '\n'], " ");
+    if value.is_ascii() {
+        return format!("{}=\"{}\"", key, value.replace(['"', '\\'], "'"));
+    }
+    let encoded: String = value
+        .bytes()
+        .map(|byte| {
+            if byte.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&byte) {
+                (byte as char).to_string()
+            } else {
+                format!("%{:02X}", byte)
+            }
+        })
+        .collect();
+    format!("{}*=UTF-8''{}", key, encoded)
+}
+
+fn file_part(name: &str, mime: &str, data: &[u8], inline_id: Option<&str>) -> String {
+    let disposition = match inline_id {
+        Some(id) => format!(
+            "inline; {}\r\nContent-ID: <{}>",
+            mime_param("filename", name),
+            id
+        ),
+        None => format!("attachment; {}", mime_param("filename", name)),
+    };
+    format!(
+        "Content-Type: {}; {}\r\nContent-Transfer-Encoding: base64\r\nContent-Disposition: {}\r\n\r\n{}",
+        mime,
+        mime_param("name", name),
+        disposition,
+        wrap_base64(data)
+    )
+}
+
+fn multipart(kind: &str, boundary: &str, parts: &[String]) -> String {
+    let mut body = format!(
+        "Content-Type: multipart/{}; boundary=\"{}\"\r\n\r\n",
+        kind, boundary
+    );
+    for part in parts {
+        body.push_str(&format!("--{}\r\n{}\r\n", boundary, part));
+    }
+    body.push_str(&format!("--{}--\r\n", boundary));
+    body
+}
+
+struct Message<'a> {
+    from: &'a str,
+    to: &'a [String],
+    subject: &'a str,
+    // Drafts are opened for editing rather than treated as received mail
+    draft: bool,
+}
+
+fn build_message(
+    message: &Message,
+    format: &str,
+    title: &str,
+    markdown_content: &str,
+    html: Option<&str>,
+    attachments: &[Attachment],
+) -> String {
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or(0);
+    let boundary = |kind: &str| format!("marky-{}-{:x}", kind, stamp);
+
+    let mut headers = vec![
+        format!("Date: {}", Local::now().to_rfc2822()),
+        format!("Subject: {}", encode_header(message.subject)),
+        "MIME-Version: 1.0".to_string(),
+        format!("Message-ID: <{:x}.marky@localhost>", stamp),
+    ];
+    // Only ever a bare address, so nothing else can ride along in the header
+    if let Some(from) = email_address(message.from) {
+        headers.push(format!("From: {}", from));
+    }
+    if !message.to.is_empty() {
+        headers.push(format!("To: {}", message.to.join(", ")));
+    }
+    if message.draft {
+        headers.push("X-Unsent: 1".to_string());
+    }
+
+    let files: Vec<String> = attachments
+        .iter()
+        .map(|attachment| file_part(&attachment.name, attachment.mime, &attachment.data, None))
+        .collect();
+
+    let body = match format {
+        "html" => {
+            // Images the note shows are embedded where they appear
+            let mut html = html.unwrap_or_default().to_string();
+            let mut inline = Vec::new();
+            let mut others = Vec::new();
+            for attachment in attachments {
+                let source = format!("src=\"{}\"", attachment.link);
+                if attachment.mime.starts_with("image/") && html.contains(&source) {
+                    html = html.replace(&source, &format!("src=\"cid:{}\"", attachment.content_id));
+                    inline.push(file_part(
+                        &attachment.name,
+                        attachment.mime,
+                        &attachment.data,
+                        Some(&attachment.content_id),
+                    ));
+                } else {
+                    others.push(file_part(
+                        &attachment.name,
+                        attachment.mime,
+                        &attachment.data,
+                        None,
+                    ));
+                }
+            }
+
+            let alternative = multipart(
+                "alternative",
+                &boundary("alt"),
+                &[
+                    text_part("text/plain", markdown_content),
+                    text_part("text/html", &html),
+                ],
+            );
+            let related = if inline.is_empty() {
+                alternative
+            } else {
+                let mut parts = vec![alternative];
+                parts.extend(inline);
+                multipart("related", &boundary("rel"), &parts)
+            };
+            if others.is_empty() {
+                related
+            } else {
+                let mut parts = vec![related];
+                parts.extend(others);
+                multipart("mixed", &boundary("mix"), &parts)
+            }
+        }
+        "attachment" => {
+            let mut parts = vec![
+                text_part("text/plain", title),
+                file_part(
+                    &format!("{}.md", title),
+                    "text/markdown",
+                    markdown_content.as_bytes(),
+                    None,
+                ),
+            ];
+            if let Some(html) = html {
+                parts.push(file_part(
+                    &format!("{}.html", title),
+                    "text/html",
+                    html.as_bytes(),
+                    None,
+                ));
+            }
+            parts.extend(files);
+            multipart("mixed", &boundary("mix"), &parts)
+        }
+        _ if files.is_empty() => text_part("text/plain", markdown_content),
+        _ => {
+            let mut parts = vec![text_part("text/plain", markdown_content)];
+            parts.extend(files);
+            multipart("mixed", &boundary("mix"), &parts)
+        }
+    };
+
+    format!("{}\r\n{}", headers.join("\r\n"), body)
+}
+
+fn open_with_default_app(path: &Path) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    };
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = Command::new("xdg-open");
+
+    command
+        .arg(path)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open mail client: {}", e))
+}
+
+enum Connection {
+    Plain(TcpStream),
+    Tls(Box<StreamOwned<ClientConnection, TcpStream>>),
+}
+
+impl Read for Connection {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Connection::Plain(stream) => stream.read(buf),
+            Connection::Tls(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Connection {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Connection::Plain(stream) => stream.write(buf),
+            Connection::Tls(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Connection::Plain(stream) => stream.flush(),
+            Connection::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+// Just enough SMTP to hand one message to a submission server
+struct SmtpSession {
+    connection: Option<Connection>,
+    host: String,
+}
+
+impl SmtpSession {
+    fn connect(host: &str, port: u16, implicit_tls: bool) -> Result<SmtpSession, String> {
+        let address = (host, port)
+            .to_socket_addrs()
+            .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+            .next()
+            .ok_or_else(|| format!("Failed to resolve {}", host))?;
+        let stream = TcpStream::connect_timeout(&address, SMTP_TIMEOUT)
+            .map_err(|e| format!("Failed to connect to {}: {}", host, e))?;
+        stream
+            .set_read_timeout(Some(SMTP_TIMEOUT))
+            .and_then(|_| stream.set_write_timeout(Some(SMTP_TIMEOUT)))
+            .map_err(|e| format!("Failed to configure connection: {}", e))?;
+
+        let mut session = SmtpSession {
+            connection: Some(Connection::Plain(stream)),
+            host: host.to_string(),
+        };
+        if implicit_tls {
+            session.start_tls()?;
+        }
+        session.expect(220)?;
+        Ok(session)
+    }
+
+    fn start_tls(&mut self) -> Result<(), String> {
+        let Some(Connection::Plain(stream)) = self.connection.take() else {
+            return Err("Connection is already encrypted".to_string());
+        };
+        http::ensure_crypto_provider();
+        let config = ClientConfig::with_platform_verifier()
+            .map_err(|e| format!("Failed to set up TLS: {}", e))?;
+        let name = ServerName::try_from(self.host.clone())
+            .map_err(|e| format!("Invalid server name {}: {}", self.host, e))?;
+        let tls = ClientConnection::new(Arc::new(config), name)
+            .map_err(|e| format!("Failed to start TLS: {}", e))?;
+        self.connection = Some(Connection::Tls(Box::new(StreamOwned::new(tls, stream))));
+        Ok(())
+    }
+
+    fn connection(&mut self) -> Result<&mut Connection, String> {
+        self.connection
+            .as_mut()
+            .ok_or_else(|| "SMTP connection is closed".to_string())
+    }
+
+    fn send(&mut self, line: &str) -> Result<(), String> {
+        let connection = self.connection()?;
+        connection
+            .write_all(line.as_bytes())
+            .and_then(|_| connection.write_all(b"\r\n"))
+            .and_then(|_| connection.flush())
+            .map_err(|e| format!("Failed to talk to the mail server: {}", e))
+    }
+
+    // Multi-line replies use `250-` on every line but the last
+    fn reply(&mut self) -> Result<(u16, String), String> {
+        let connection = self.connection()?;
+        let mut text = String::new();
+        loop {
+            let mut line = Vec::new();
+            let mut byte = [0u8; 1];
+            while !line.ends_with(b"\r\n") {
+                match connection.read(&mut byte) {
+                    Ok(0) => return Err("The mail server closed the connection".to_string()),
+                    Ok(_) => line.push(byte[0]),
+                    Err(e) => return Err(format!("Failed to read from the mail server: {}", e)),
+                }
+            }
+            let line = String::from_utf8_lossy(&line).trim_end().to_string();
+            let code = line
+                .get(..3)
+                .and_then(|code| code.parse().ok())
+                .ok_or_else(|| format!("Unexpected reply from the mail server: {}", line))?;
+            text.push_str(line.get(4..).unwrap_or(""));
+            text.push('\n');
+            if line.as_bytes().get(3) != Some(&b'-') {
+                return Ok((code, text));
+            }
+        }
+    }
+
+    fn expect(&mut self, code: u16) -> Result<String, String> {
+        let (got, text) = self.reply()?;
+        if got != code {
+            return Err(format!("Mail server error {}: {}", got, text.trim()));
+        }
+        Ok(text)
+    }
+
+    fn command(&mut self, line: &str, code: u16) -> Result<String, String> {
+        self.send(line)?;
+        self.expect(code)
+    }
+}
+
+fn send_smtp(
+    settings: &EmailSettings,
+    password: Option<String>,
+    to: &[String],
+    message: &str,
+) -> Result<(), String> {
+    let host = settings.smtp_host.trim();
+    if host.is_empty() {
+        return Err(i18n::t("error.smtp_no_server"));
+    }
+    let from = settings.from_address.trim();
+    if from.is_empty() {
+        return Err(i18n::t("error.smtp_no_sender"));
+    }
+    let from =
+        email_address(from).ok_or_else(|| i18n::tf("error.smtp_invalid_sender", &[&from]))?;
+    let username = settings.smtp_username.trim();
+    if !username.is_empty() && settings.smtp_security == "none" {
+        return Err(i18n::t("error.smtp_unencrypted"));
+    }
+
+    let mut session =
+        SmtpSession::connect(host, settings.smtp_port, settings.smtp_security == "tls")?;
+    let mut capabilities = session.command("EHLO marky", 250)?;
+    if settings.smtp_security == "starttls" {
+        session.command("STARTTLS", 220)?;
+        session.start_tls()?;
+        capabilities = session.command("EHLO marky", 250)?;
+    }
+
+    if !username.is_empty() {
+        let password = password.ok_or_else(|| i18n::t("error.smtp_no_password"))?;
+        let auth_line = capabilities
+            .lines()
+            .find(|line| line.to_uppercase().starts_with("AUTH"))
+            .unwrap_or("")
+            .to_uppercase();
+        if auth_line.contains("PLAIN") || !auth_line.contains("LOGIN") {
+            let token = STANDARD.encode(format!("\0{}\0{}", username, password));
+            session.command(&format!("AUTH PLAIN {}", token), 235)?;
+        } else {
+            session.command("AUTH LOGIN", 334)?;
+            session.command(&STANDARD.encode(username), 334)?;
+            session.command(&STANDARD.encode(password), 235)?;
+        }
+    }
+
+    session.command(&format!("MAIL FROM:<{}>", from), 250)?;
+    for recipient in to {
+        session.send(&format!("RCPT TO:<{}>", recipient))?;
+        let (code, text) = session.reply()?;
+        if code != 250 && code != 251 {
+            return Err(i18n::tf("error.smtp_refused", &[recipient, &text.trim()]));
+        }
+    }
+    session.command("DATA", 354)?;
+
+    // Lines starting with a dot are doubled so they don't end the message early
+    let mut data = String::with_capacity(message.len() + 64);
+    for line in message.split("\r\n") {
+        if line.starts_with('.') {
+            data.push('.');
+        }
+        data.push_str(line);
+        data.push_str("\r\n");
+    }
+    data.push('.');
+    session.command(&data, 250)?;
+    let _ = session.send("QUIT");
+    Ok(())
+}
+
+fn email_address(text: &str) -> Option<String> {
+    let text = text.trim();
+    let address = match (text.rfind('<'), text.rfind('>')) {
+        (Some(start), Some(end)) if start < end => &text[start + 1..end],
+        _ => text,
+    };
+    let valid = address.contains('@')
+        && !address.starts_with('@')
+        && !address.ends_with('@')
+        && !address.contains(char::is_whitespace)
+        && !address.contains(['<', '>', '\r', '\n']);
+    valid.then(|| address.to_string())
+}
+
+// `html` is the note as the frontend renders it; with the `html` and
+// `attachment` formats it's sent as is
+#[tauri::command]
+pub async fn email_note(
+    path: String,
+    format: String,
+    html: Option<String>,
+    to: Vec<String>,
+    app: AppHandle,
+    settings_state: State<'_, SettingsState>,
+    watcher_state: State<'_, WatcherState>,
+) -> Result<EmailResult, String> {
+    let format = format.trim().to_lowercase();
+    if !FORMATS.contains(&format.as_str()) {
+        return Err(format!("Unknown email format: {}", format));
+    }
+    let note_path = PathBuf::from(&path);
+    // A draft or a relayed copy would leave the plaintext outside the vault
+    if vault::vault_root(&note_path).is_some() {
+        return Err("Notes in an encrypted vault can't be emailed".to_string());
+    }
+    let content =
+        fs::read_to_string(&note_path).map_err(|e| format!("Failed to read note: {}", e))?;
+
+    let settings = settings_state.current();
+    let email = settings.email.clone();
+    let recipients: Vec<String> = to
+        .iter()
+        .filter(|address| !address.trim().is_empty())
+        .map(|address| {
+            email_address(address).ok_or_else(|| format!("Invalid email address: {}", address))
+        })
+        .collect::<Result<_, _>>()?;
+    if email.method == "smtp" && recipients.is_empty() {
+        return Err("Add at least one recipient".to_string());
+    }
+    let from = email.from_address.trim();
+    if !from.is_empty() && email_address(from).is_none() {
+        return Err(i18n::tf("error.smtp_invalid_sender", &[&from]));
+    }
+
+    let root = watcher_state
+        .root_for(&note_path)
+        .or_else(|| note_path.parent().map(Path::to_path_buf))
+        .ok_or_else(|| "Note is not in a workspace".to_string())?;
+    let config = workspace_config::effective(&root, &settings);
+    let (attachments, skipped) = collect_attachments(
+        &root,
+        &note_path,
+        &content,
+        &config.attachment_folder,
+        &settings.watched_extensions,
+    );
+
+    let title = markdown::note_title(&note_path);
+    let html = html.filter(|html| !html.trim().is_empty()).or_else(|| {
+        (format == "html").then(|| {
+            let escaped = content
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+            format!("<pre>{}</pre>", escaped)
+        })
+    });
+    let message = build_message(
+        &Message {
+            from,
+            to: &recipients,
+            subject: &title,
+            draft: email.method == "client",
+        },
+        &format,
+        &title,
+        &content,
+        html.as_deref(),
+        &attachments,
+    );
+
+    let draft_path = if email.method == "smtp" {
+        let password = secrets::get(&app, PASSWORD_SECRET)?;
+        tauri::async_runtime::spawn_blocking(move || {
+            send_smtp(&email, password, &recipients, &message)
+        })
+        .await
+        .map_err(|e| format!("Failed to send email: {}", e))??;
+        tracing::info!("Emailed {} to {} recipients", title, to.len());
+        None
+    } else {
+        let dir = storage::app_data_dir(&app)?.join(DRAFTS_DIR);
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create drafts folder: {}", e))?;
+        let draft = dir.join(format!("{}.eml", storage::path_key(&note_path)));
+        fs::write(&draft, message).map_err(|e| format!("Failed to write email draft: {}", e))?;
+        open_with_default_app(&draft)?;
+        Some(draft.to_string_lossy().to_string())
+    };
+
+    Ok(EmailResult {
+        method: settings.email.method,
+        draft_path,
+        attachments: attachments
+            .into_iter()
+            .map(|attachment| attachment.name)
+            .collect(),
+        skipped_attachments: skipped,
+    })
+}
+
+// Drafts are only needed until the mail client has read them
+pub fn clear_drafts(app: &AppHandle) {
+    let Ok(dir) = storage::app_data_dir(app).map(|dir| dir.join(DRAFTS_DIR)) else {
+        return;
+    };
+    if dir.exists() {
+        if let Err(e) = fs::remove_dir_all(&dir) {
+            tracing::warn!("Failed to clear email drafts: {}", e);
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use crate::encryption;
+use crate::index::{IndexState, WorkspaceIndex};
+use crate::markdown;
+use crate::properties;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+// How many embeds deep a note may pull in others before the rest are skipped
+const MAX_DEPTH: usize = 5;
+
+// Inlines `![[Note]]`, `![[Note#Section]]` and `![[Note#^block]]` embeds,
+// recursively. The note being composed may have unsaved `content`, so it's
+// read from here rather than from disk when it embeds itself.
+struct Composer<'a> {
+    index: &'a WorkspaceIndex,
+    top: &'a Path,
+    top_content: &'a str,
+    // The notes and sections being inlined, outermost first
+    stack: Vec<(PathBuf, String)>,
+}
+
+fn notice(text: String) -> String {
+    format!("*{}*", text)
+}
+
+// The part of `content` a `#subpath` points at: a heading with everything up
+// to the next heading of the same or a higher level, or a `^block`
+fn section<'c>(content: &'c str, subpath: &str) -> Option<&'c str> {
+    if subpath.is_empty() {
+        return Some(markdown::body(content));
+    }
+    if let Some(id) = subpath.strip_prefix('^') {
+        let block = markdown::extract_blocks(content)
+            .into_iter()
+            .find(|block| {
+                block
+                    .id
+                    .as_deref()
+                    .is_some_and(|own| own.eq_ignore_ascii_case(id))
+            })?;
+        let text = &content[block.start..block.end];
+        let marker = format!("^{}", block.id.as_deref().unwrap_or(id));
+        return Some(
+            text.strip_suffix(marker.as_str())
+                .unwrap_or(text)
+                .trim_end(),
+        );
+    }
+    let headings = markdown::extract_headings(content);
+    let heading = markdown::find_heading(&headings, subpath)?;
+    let end = headings
+        .iter()
+        .find(|next| next.offset > heading.offset && next.level <= heading.level)
+        .map_or(content.len(), |next| next.offset);
+    Some(&content[heading.offset..end])
+}
+
+impl Composer<'_> {
+    fn compose(&mut self, from: &Path, content: &str) -> String {
+        let code = markdown::code_ranges(content);
+        let body_start = content.len() - markdown::body(content).len();
+        let mut composed = String::with_capacity(content.len());
+        let mut cursor = 0;
+        let mut offset = body_start;
+
+        while let Some(open) = content[offset..].find("![[") {
+            let open = offset + open;
+            let Some(close) = content[open + 3..].find("]]").map(|idx| open + 3 + idx) else {
+                break;
+            };
+            offset = close + 2;
+            if code.iter().any(|range| range.contains(&open)) {
+                continue;
+            }
+            let target = content[open + 3..close].split('|').next().unwrap_or("");
+            if target.contains('\n') {
+                continue;
+            }
+            if let Some(inlined) = self.embed(from, target.trim()) {
+                composed.push_str(&content[cursor..open]);
+                composed.push_str(&inlined);
+                cursor = offset;
+            }
+        }
+        composed.push_str(&content[cursor..]);
+        composed
+    }
+
+    // What replaces one embed; None leaves it as written (images and other files)
+    fn embed(&mut self, from: &Path, target: &str) -> Option<String> {
+        let (name, subpath) = match target.split_once('#') {
+            Some((name, subpath)) => (name.trim(), subpath.trim()),
+            None => (target, ""),
+        };
+        if markdown::strip_note_extension(name) == name && name.contains('.') {
+            return None;
+        }
+
+        let path = if name.is_empty() {
+            from.to_path_buf()
+        } else {
+            match self.index.resolve_link(name) {
+                Some(note) => note.path.clone(),
+                None => return Some(notice(format!("Embedded note not found: {}", target))),
+            }
+        };
+        let key = (path.clone(), subpath.to_lowercase());
+        if self.stack.contains(&key) {
+            return Some(notice(format!(
+                "Embed of {} skipped: it embeds itself",
+                target
+            )));
+        }
+        if self.stack.len() > MAX_DEPTH {
+            return Some(notice(format!(
+                "Embed of {} skipped: nested more than {} levels deep",
+                target, MAX_DEPTH
+            )));
+        }
+
+        let content = if path == self.top {
+            self.top_content.to_string()
+        } else {
+            match fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(_) => return Some(notice(format!("Embedded note not found: {}", target))),
+            }
+        };
+        if encryption::is_encrypted(&content) {
+            return Some(notice(format!(
+                "{} is encrypted and isn't embedded",
+                target
+            )));
+        }
+        let Some(section) = section(&content, subpath) else {
+            return Some(notice(format!("Embedded section not found: {}", target)));
+        };
+
+        self.stack.push(key);
+        let inlined = self.compose(&path, section);
+        self.stack.pop();
+        Some(inlined.trim().to_string())
+    }
+}
+
+// `content` (the note at `path`) with its embeds inlined, for exports that
+// should stand on their own. Outside an indexed workspace it's returned as is.
+pub fn inline_embeds(app: &AppHandle, path: &Path, content: &str) -> String {
+    let index_state = app.state::<IndexState>();
+    let Some(root) = index_state.root_for(path) else {
+        return content.to_string();
+    };
+    index_state
+        .with_workspace(&root, |index| {
+            let mut composer = Composer {
+                index,
+                top: path,
+                top_content: content,
+                stack: vec![(path.to_path_buf(), String::new())],
+            };
+            composer.compose(path, content)
+        })
+        .unwrap_or_else(|_| content.to_string())
+}
+
+// The note's markdown with embeds inlined; `content` is the editor's unsaved
+// text when there is some
+#[tauri::command]
+pub async fn compose_note(
+    path: String,
+    content: Option<String>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let note = PathBuf::from(&path);
+    let content = match content {
+        Some(content) if encryption::is_encrypted(&content) => {
+            return Err("Note is encrypted; decrypt it first".to_string())
+        }
+        Some(content) => content,
+        None => properties::read_note(&app, &note)?,
+    };
+    Ok(inline_embeds(&app, &note, &content))
+}
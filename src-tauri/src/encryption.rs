@@ -0,0 +1,252 @@
+use crate::locks::{self, NoteWriteError};
+use crate::network;
+use crate::storage;
+use crate::watcher::WatcherState;
+use crate::windows;
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, Window};
+
+const BEGIN_MARKER: &str = "-----BEGIN MARKY ENCRYPTED NOTE-----";
+const END_MARKER: &str = "-----END MARKY ENCRYPTED NOTE-----";
+const FORMAT_VERSION: &str = "1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const LINE_WIDTH: usize = 64;
+
+// The armored form keeps the note a plain text file with its usual extension, so it
+// stays in the tree, syncs like any other note, and diffs as opaque text
+struct Envelope {
+    salt: Vec<u8>,
+    nonce: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+pub fn is_encrypted(content: &str) -> bool {
+    content
+        .trim_start_matches('\u{feff}')
+        .starts_with(BEGIN_MARKER)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key<Aes256Gcm>, String> {
+    let mut key = Key::<Aes256Gcm>::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Failed to derive key: {}", e))?;
+    Ok(key)
+}
+
+fn armor(envelope: &Envelope) -> String {
+    let body = BASE64.encode(&envelope.ciphertext);
+    let mut lines = vec![
+        BEGIN_MARKER.to_string(),
+        format!("Version: {}", FORMAT_VERSION),
+        "Cipher: AES-256-GCM".to_string(),
+        "KDF: Argon2id".to_string(),
+        format!("Salt: {}", BASE64.encode(&envelope.salt)),
+        format!("Nonce: {}", BASE64.encode(&envelope.nonce)),
+        String::new(),
+    ];
+    // Base64 is ASCII, so splitting on byte boundaries is safe
+    lines.extend(
+        body.as_bytes()
+            .chunks(LINE_WIDTH)
+            .map(|chunk| String::from_utf8_lossy(chunk).to_string()),
+    );
+    lines.push(END_MARKER.to_string());
+    lines.join("\n") + "\n"
+}
+
+fn dearmor(content: &str) -> Result<Envelope, String> {
+    let invalid = || "Invalid encrypted note".to_string();
+    let mut lines = content.trim_start_matches('\u{feff}').lines();
+    if lines.next().map(str::trim) != Some(BEGIN_MARKER) {
+        return Err("Note is not encrypted".to_string());
+    }
+
+    let mut salt = None;
+    let mut nonce = None;
+    for line in lines.by_ref() {
+        let line = line.trim();
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(':').ok_or_else(invalid)?;
+        let value = value.trim();
+        match name.trim() {
+            "Version" if value != FORMAT_VERSION => {
+                return Err(format!("Unsupported encrypted note version: {}", value));
+            }
+            "Salt" => salt = Some(BASE64.decode(value).map_err(|_| invalid())?),
+            "Nonce" => nonce = Some(BASE64.decode(value).map_err(|_| invalid())?),
+            _ => {}
+        }
+    }
+
+    let body: String = lines
+        .map(str::trim)
+        .take_while(|line| *line != END_MARKER)
+        .collect();
+
+    let (Some(salt), Some(nonce)) = (salt, nonce) else {
+        return Err(invalid());
+    };
+    if nonce.len() != NONCE_LEN {
+        return Err(invalid());
+    }
+
+    Ok(Envelope {
+        salt,
+        nonce,
+        ciphertext: BASE64.decode(body).map_err(|_| invalid())?,
+    })
+}
+
+fn seal(plaintext: &str, passphrase: &str) -> Result<String, String> {
+    let mut salt = vec![0u8; SALT_LEN];
+    let mut nonce = vec![0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+
+    let cipher = Aes256Gcm::new(&derive_key(passphrase, &salt)?);
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_bytes())
+        .map_err(|_| "Failed to encrypt note".to_string())?;
+
+    Ok(armor(&Envelope {
+        salt,
+        nonce,
+        ciphertext,
+    }))
+}
+
+fn open(content: &str, passphrase: &str) -> Result<String, String> {
+    let envelope = dearmor(content)?;
+    let cipher = Aes256Gcm::new(&derive_key(passphrase, &envelope.salt)?);
+    // GCM can't tell a wrong passphrase from tampering, so both read the same
+    let plaintext = cipher
+        .decrypt(
+            Nonce::from_slice(&envelope.nonce),
+            envelope.ciphertext.as_slice(),
+        )
+        .map_err(|_| "Wrong passphrase or corrupted note".to_string())?;
+
+    String::from_utf8(plaintext).map_err(|_| "Decrypted note is not valid text".to_string())
+}
+
+// Written beside the note and renamed over it so a crash never leaves half a file
+fn write_replacing(path: &Path, content: &str) -> Result<(), String> {
+    let tmp = path.with_extension("marky-tmp");
+    fs::write(&tmp, content).map_err(|e| format!("Failed to write note: {}", e))?;
+    fs::rename(&tmp, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp);
+        format!("Failed to write note: {}", e)
+    })
+}
+
+fn is_note_locked(app: &AppHandle, path: &Path) -> bool {
+    app.state::<WatcherState>()
+        .root_for(path)
+        .is_some_and(|root| locks::is_locked(app, &root, path))
+}
+
+fn validate_passphrase(passphrase: &str) -> Result<(), String> {
+    if passphrase.is_empty() {
+        return Err("Passphrase cannot be empty".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn encrypt_note(path: String, passphrase: String, app: AppHandle) -> Result<String, String> {
+    validate_passphrase(&passphrase)?;
+    let target = PathBuf::from(path);
+    storage::ensure_accessible(&app, &target)?;
+    network::guard(&app, &target.clone(), move |app| {
+        if is_note_locked(app, &target) {
+            return Err("Note is locked".to_string());
+        }
+
+        let content =
+            fs::read_to_string(&target).map_err(|e| format!("Failed to read note: {}", e))?;
+        if is_encrypted(&content) {
+            return Err("Note is already encrypted".to_string());
+        }
+
+        let armored = seal(&content, &passphrase)?;
+        write_replacing(&target, &armored)?;
+
+        Ok(armored)
+    })?
+}
+
+// Plaintext only ever lives in the returned value; nothing is written back
+#[tauri::command]
+pub fn decrypt_note(path: String, passphrase: String, app: AppHandle) -> Result<String, String> {
+    let target = PathBuf::from(path);
+    storage::ensure_accessible(&app, &target)?;
+    let content = network::guard_read(&app, &target.clone(), move |_| {
+        fs::read_to_string(&target).map_err(|e| format!("Failed to read note: {}", e))
+    })??;
+    open(&content, &passphrase)
+}
+
+// Saves edits to an unlocked encrypted note; the passphrase must open the current file
+// so a typo can't silently re-key the note
+#[tauri::command]
+pub fn save_encrypted_note(
+    path: String,
+    content: String,
+    passphrase: String,
+    app: AppHandle,
+    window: Window,
+) -> Result<String, NoteWriteError> {
+    let target = PathBuf::from(path);
+    storage::ensure_accessible(&app, &target).map_err(NoteWriteError::io)?;
+    let origin = window.label().to_string();
+    network::guard(&app, &target.clone(), move |app| {
+        if is_note_locked(app, &target) {
+            return Err(NoteWriteError::locked(&target));
+        }
+
+        let existing = fs::read_to_string(&target)
+            .map_err(|e| NoteWriteError::io(format!("Failed to read note: {}", e)))?;
+        open(&existing, &passphrase).map_err(NoteWriteError::io)?;
+
+        let armored = seal(&content, &passphrase).map_err(NoteWriteError::io)?;
+        write_replacing(&target, &armored).map_err(NoteWriteError::io)?;
+        windows::emit_note_saved(app, &origin, &target);
+
+        Ok(armored)
+    })
+    .map_err(NoteWriteError::io)?
+}
+
+// Turns the note back into plain markdown on disk
+#[tauri::command]
+pub fn remove_note_encryption(
+    path: String,
+    passphrase: String,
+    app: AppHandle,
+) -> Result<String, String> {
+    let target = PathBuf::from(path);
+    storage::ensure_accessible(&app, &target)?;
+    network::guard(&app, &target.clone(), move |app| {
+        if is_note_locked(app, &target) {
+            return Err("Note is locked".to_string());
+        }
+
+        let content =
+            fs::read_to_string(&target).map_err(|e| format!("Failed to read note: {}", e))?;
+        let plaintext = open(&content, &passphrase)?;
+        write_replacing(&target, &plaintext)?;
+
+        Ok(plaintext)
+    })?
+}
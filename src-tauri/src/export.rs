@@ -0,0 +1,959 @@
+use crate::disk_space;
+use crate::email;
+use crate::embeds;
+use crate::index::{self, IndexState};
+use crate::markdown;
+use crate::network;
+use crate::properties;
+use crate::settings::SettingsState;
+use crate::share;
+use crate::storage;
+use crate::vault;
+use crate::watcher::WatcherState;
+use crate::workspace_config::{self, NoteFilter};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use tauri::http::{Request, Response};
+use tauri::{AppHandle, Manager, UriSchemeContext};
+use tauri_plugin_dialog::DialogExt;
+
+// Serves pages to the hidden webview that prints PDFs
+pub const SCHEME: &str = "marky-export";
+// Labels of the hidden windows that print them
+pub const WINDOW_PREFIX: &str = "export-";
+// Long enough for remote images on a slow connection
+#[cfg(any(target_os = "macos", windows))]
+const PDF_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+// Notes are untrusted HTML once rendered; nothing in them may run
+const PAGE_CSP: &str = "default-src 'none'; img-src data: http: https:; style-src 'unsafe-inline'";
+
+const STYLE: &str = r#"* { margin: 0; padding: 0; box-sizing: border-box; }
+body {
+  font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', 'Roboto', 'Helvetica', 'Arial', sans-serif;
+  line-height: 1.6;
+  color: #1e1e1e;
+  max-width: 800px;
+  margin: 0 auto;
+  padding: 40px 20px;
+  background: #ffffff;
+}
+h1, h2, h3, h4, h5, h6 { margin-top: 24px; margin-bottom: 16px; font-weight: 600; line-height: 1.25; color: #1a1a1a; }
+h1 { font-size: 2em; border-bottom: 1px solid #eaecef; padding-bottom: 0.3em; }
+h2 { font-size: 1.5em; border-bottom: 1px solid #eaecef; padding-bottom: 0.3em; }
+h3 { font-size: 1.25em; }
+h4 { font-size: 1em; }
+p { margin-bottom: 16px; }
+a { color: #0969da; text-decoration: none; }
+a:hover { text-decoration: underline; }
+code {
+  background-color: rgba(175, 184, 193, 0.2);
+  padding: 0.2em 0.4em;
+  border-radius: 6px;
+  font-size: 85%;
+  font-family: ui-monospace, SFMono-Regular, 'SF Mono', Menlo, Consolas, 'Liberation Mono', monospace;
+}
+pre { background-color: #f6f8fa; border-radius: 6px; padding: 16px; overflow: auto; margin-bottom: 16px; }
+pre code { background-color: transparent; padding: 0; font-size: 100%; }
+blockquote { border-left: 4px solid #d0d7de; padding-left: 16px; color: #656d76; margin-bottom: 16px; }
+ul, ol { margin-bottom: 16px; padding-left: 2em; }
+li { margin-bottom: 4px; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 16px; }
+table th, table td { border: 1px solid #d0d7de; padding: 6px 13px; }
+table th { background-color: #f6f8fa; font-weight: 600; }
+hr { height: 0.25em; padding: 0; margin: 24px 0; background-color: #d0d7de; border: 0; }
+img { max-width: 100%; height: auto; }
+input[type="checkbox"] { margin-right: 0.5em; }
+@media print { body { max-width: none; padding: 0; } pre { white-space: pre-wrap; } }
+"#;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Html,
+    Pdf,
+}
+
+impl Format {
+    fn parse(format: &str) -> Result<Format, String> {
+        match format.trim().to_lowercase().as_str() {
+            "html" => Ok(Format::Html),
+            "pdf" => Ok(Format::Pdf),
+            other => Err(format!("Unknown export format: {}", other)),
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Html => "html",
+            Format::Pdf => "pdf",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    // Inline `![[embeds]]` so each note reads as one document
+    inline_embeds: bool,
+    // Local images as data URLs so an HTML file stands on its own; otherwise
+    // they're linked relative to the export. PDFs always embed them.
+    embed_images: bool,
+    // A file for `export_note`, a folder for `export_folder`; the save or
+    // folder dialog asks when it's missing
+    destination: Option<String>,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        ExportOptions {
+            inline_embeds: true,
+            embed_images: true,
+            destination: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportResult {
+    // The file written, or the folder holding a batch export
+    destination: String,
+    exported: usize,
+    // Notes left out, relative to the exported folder (encrypted ones)
+    skipped: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct ExportState {
+    // Pages waiting for the PDF webview to load them, by URL path
+    pages: Mutex<HashMap<String, String>>,
+    // One PDF export at a time; they share the main thread's print machinery
+    #[cfg(any(target_os = "macos", windows))]
+    printing: std::sync::atomic::AtomicBool,
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// A standalone page around a rendered note body
+pub fn html_document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">\n\
+         <title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        STYLE,
+        body
+    )
+}
+
+// `[[Note#Heading|label]]` and `![[image.png]]` as the markdown links they
+// stand for, so the renderer and the link rewrite only see one kind of link
+fn wiki_links_to_markdown(content: &str) -> String {
+    let code = markdown::code_ranges(content);
+    let mut output = String::with_capacity(content.len());
+    let mut copied = 0;
+    let mut from = 0;
+    while let Some(found) = content[from..].find("[[") {
+        let start = from + found;
+        let Some(length) = content[start + 2..].find("]]") else {
+            break;
+        };
+        let inner = &content[start + 2..start + 2 + length];
+        let end = start + 2 + length + 2;
+        if inner.contains('\n') || code.iter().any(|range| range.contains(&start)) {
+            from = start + 2;
+            continue;
+        }
+        from = end;
+
+        let (target, label) = match inner.split_once('|') {
+            Some((target, label)) => (target.trim(), label.trim()),
+            None => (inner.trim(), inner.trim()),
+        };
+        let embed = content[..start].ends_with('!');
+        let begin = if embed { start - 1 } else { start };
+        output.push_str(&content[copied..begin]);
+        output.push_str(&format!(
+            "{}[{}](<{}>)",
+            if embed { "!" } else { "" },
+            label.replace('[', "\\[").replace(']', "\\]"),
+            target.replace('<', "%3C").replace('>', "%3E")
+        ));
+        copied = end;
+    }
+    output.push_str(&content[copied..]);
+    output
+}
+
+fn is_external(href: &str) -> bool {
+    href.contains("://") || href.to_lowercase().starts_with("mailto:") || href.starts_with("data:")
+}
+
+fn encode_segment(segment: &str) -> String {
+    segment
+        .chars()
+        .map(|c| match c {
+            ' ' => "%20".to_string(),
+            '#' => "%23".to_string(),
+            '?' => "%3F".to_string(),
+            '%' => "%25".to_string(),
+            '"' => "%22".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+// `to` as a link from a page in `from_dir`. Both are canonical; on another
+// drive there's no relative path, so it's a file URL.
+fn relative_href(from_dir: &Path, to: &Path) -> String {
+    let from: Vec<Component> = from_dir.components().collect();
+    let target: Vec<Component> = to.components().collect();
+    let common = from.iter().zip(&target).take_while(|(a, b)| a == b).count();
+    if common == 0 {
+        return tauri::Url::from_file_path(to)
+            .map(|url| url.to_string())
+            .unwrap_or_default();
+    }
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    parts.extend(
+        target[common..]
+            .iter()
+            .map(|part| encode_segment(&part.as_os_str().to_string_lossy())),
+    );
+    parts.join("/")
+}
+
+fn data_url(path: &Path) -> Option<String> {
+    let mime = email::mime_type(path);
+    if !mime.starts_with("image/") {
+        return None;
+    }
+    let bytes = fs::read(path).ok()?;
+    Some(format!("data:{};base64,{}", mime, STANDARD.encode(bytes)))
+}
+
+struct Export {
+    format: Format,
+    options: ExportOptions,
+    // Canonical
+    root: PathBuf,
+    attachment_dir: PathBuf,
+    extensions: Vec<String>,
+    // Canonical note path -> where its export is written, so links between
+    // exported notes can point at each other's files
+    targets: HashMap<PathBuf, PathBuf>,
+}
+
+impl Export {
+    fn new(app: &AppHandle, source: &Path, format: Format, options: ExportOptions) -> Export {
+        let settings = app.state::<SettingsState>().current();
+        let root = app
+            .state::<WatcherState>()
+            .root_for(source)
+            .or_else(|| {
+                if source.is_dir() {
+                    Some(source.to_path_buf())
+                } else {
+                    source.parent().map(Path::to_path_buf)
+                }
+            })
+            .unwrap_or_default();
+        let config = workspace_config::effective(&root, &settings);
+        let root = root.canonicalize().unwrap_or(root);
+        Export {
+            format,
+            options,
+            attachment_dir: root.join(&config.attachment_folder),
+            root,
+            extensions: settings.watched_extensions,
+            targets: HashMap::new(),
+        }
+    }
+
+    // The note a link target names: a path from the note's folder, or a title
+    // or alias the workspace index knows
+    fn resolve_note(&self, app: &AppHandle, note: &Path, target: &str) -> Option<PathBuf> {
+        let decoded = markdown::percent_decode(target);
+        let folder = note.parent()?;
+        let mut candidates = vec![folder.join(&decoded)];
+        if markdown::strip_note_extension(&decoded) == decoded {
+            candidates.push(folder.join(format!("{}.md", decoded)));
+        }
+        if let Some(found) = candidates.into_iter().find_map(|candidate| {
+            let canonical = candidate.canonicalize().ok()?;
+            (canonical.is_file() && markdown::has_note_extension(&canonical, &self.extensions))
+                .then_some(canonical)
+        }) {
+            return Some(found);
+        }
+
+        let index_state = app.state::<IndexState>();
+        let root = index_state.root_for(note)?;
+        index_state
+            .with_workspace(&root, |index| {
+                index
+                    .resolve_link(&decoded)
+                    .map(|record| record.path.clone())
+            })
+            .ok()
+            .flatten()
+            .and_then(|path| path.canonicalize().ok())
+    }
+
+    // Headings get the ids links use; links between exported notes point at
+    // their exports and local images are embedded or linked from `output`
+    fn rewrite(&self, app: &AppHandle, note: &Path, output: &Path, html: &str) -> String {
+        let document = dom_query::Document::from(html);
+        let source = note.canonicalize().unwrap_or_else(|_| note.to_path_buf());
+        let note_dir = source.parent().unwrap_or(&self.root);
+        let output_dir = output.parent().unwrap_or(Path::new(""));
+
+        for node in document.select("h1, h2, h3, h4, h5, h6").nodes() {
+            node.set_attr("id", &markdown::heading_slug(&node.text()));
+        }
+
+        for node in document.select("a[href]").nodes() {
+            let Some(href) = node.attr("href").map(|href| href.to_string()) else {
+                continue;
+            };
+            if is_external(&href) || href.starts_with('#') {
+                continue;
+            }
+            let (target, fragment) = match href.split_once('#') {
+                Some((target, fragment)) => (target, Some(fragment)),
+                None => (href.as_str(), None),
+            };
+            // Block ids don't survive rendering; headings do
+            let anchor = fragment
+                .filter(|fragment| !fragment.starts_with('^'))
+                .map(|fragment| {
+                    format!(
+                        "#{}",
+                        markdown::heading_slug(&markdown::percent_decode(fragment))
+                    )
+                })
+                .unwrap_or_default();
+
+            if let Some(linked) = self.resolve_note(app, note, target) {
+                match self.targets.get(&linked) {
+                    Some(_) if linked == source => node.set_attr("href", &anchor),
+                    Some(exported) => node.set_attr(
+                        "href",
+                        &format!("{}{}", relative_href(output_dir, exported), anchor),
+                    ),
+                    // The linked note isn't part of this export
+                    None => node.remove_attr("href"),
+                }
+            } else if let Some(file) =
+                email::resolve_link(&self.root, note_dir, &self.attachment_dir, target)
+            {
+                node.set_attr("href", &relative_href(output_dir, &file));
+            }
+        }
+
+        let embed_images = self.options.embed_images || self.format == Format::Pdf;
+        for node in document.select("img[src]").nodes() {
+            let Some(src) = node.attr("src").map(|src| src.to_string()) else {
+                continue;
+            };
+            if is_external(&src) {
+                continue;
+            }
+            let Some(file) = email::resolve_link(&self.root, note_dir, &self.attachment_dir, &src)
+            else {
+                continue;
+            };
+            let src = if embed_images {
+                data_url(&file).unwrap_or_else(|| relative_href(output_dir, &file))
+            } else {
+                relative_href(output_dir, &file)
+            };
+            node.set_attr("src", &src);
+        }
+
+        document.select("body").inner_html().to_string()
+    }
+
+    // The finished page for `note`, which will be written to `output`
+    fn render(
+        &self,
+        app: &AppHandle,
+        note: &Path,
+        content: Option<String>,
+        output: &Path,
+    ) -> Result<String, String> {
+        let content = match content {
+            Some(content) => content,
+            None => properties::read_note(app, note)?,
+        };
+        let content = if self.options.inline_embeds {
+            embeds::inline_embeds(app, note, &content)
+        } else {
+            content
+        };
+        let body = markdown::to_html(&wiki_links_to_markdown(&content));
+        let body = self.rewrite(app, note, output, &body);
+        Ok(html_document(&markdown::note_title(note), &body))
+    }
+
+    fn write(
+        &self,
+        printer: &mut Option<PdfPrinter>,
+        html: &str,
+        output: &Path,
+    ) -> Result<(), String> {
+        match printer {
+            Some(printer) => {
+                prepare_output(output, html.len())?;
+                printer.print(html, output)
+            }
+            None => write_html(output, html),
+        }
+    }
+}
+
+// Creates the folder `output` goes in and checks it has room for `size` bytes
+fn prepare_output(output: &Path, size: usize) -> Result<(), String> {
+    let parent = output.parent().unwrap_or(Path::new(""));
+    fs::create_dir_all(parent).map_err(|e| format!("Failed to create export folder: {}", e))?;
+    disk_space::ensure_space(parent, size as u64).map_err(|e| e.message)
+}
+
+// Writes an exported HTML page, with the same checks as a full export
+pub fn write_html(output: &Path, html: &str) -> Result<(), String> {
+    prepare_output(output, html.len())?;
+    fs::write(output, html).map_err(|e| format!("Failed to write export: {}", e))
+}
+
+// The custom protocol handler behind `SCHEME`
+pub fn serve(
+    context: UriSchemeContext<'_, tauri::Wry>,
+    request: Request<Vec<u8>>,
+) -> Response<Vec<u8>> {
+    let page = context
+        .app_handle()
+        .state::<ExportState>()
+        .pages
+        .lock()
+        .ok()
+        .and_then(|pages| pages.get(request.uri().path()).cloned());
+    let response = match page {
+        Some(page) => Response::builder()
+            .header("Content-Type", "text/html; charset=utf-8")
+            .header("Content-Security-Policy", PAGE_CSP)
+            .body(page.into_bytes()),
+        None => Response::builder().status(404).body(Vec::new()),
+    };
+    response.unwrap_or_else(|_| Response::new(Vec::new()))
+}
+
+#[cfg(any(target_os = "macos", windows))]
+fn page_url(path: &str) -> Result<tauri::Url, String> {
+    // WebView2 only routes custom schemes through `http://<scheme>.localhost`
+    #[cfg(windows)]
+    let url = format!("http://{}.localhost{}", SCHEME, path);
+    #[cfg(not(windows))]
+    let url = format!("{}://localhost{}", SCHEME, path);
+    url.parse()
+        .map_err(|e| format!("Failed to build the PDF page address: {}", e))
+}
+
+#[cfg(any(target_os = "macos", windows))]
+fn wait(done: &std::sync::mpsc::Receiver<Result<(), String>>) -> Result<(), String> {
+    use std::sync::mpsc::RecvTimeoutError;
+
+    match done.recv_timeout(PDF_TIMEOUT) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err("Timed out writing the PDF".to_string()),
+        Err(RecvTimeoutError::Disconnected) => {
+            Err("The PDF renderer stopped before finishing".to_string())
+        }
+    }
+}
+
+// AppKit reports when the print operation is done through a delegate selector
+#[cfg(target_os = "macos")]
+objc2::define_class!(
+    #[unsafe(super(objc2::runtime::NSObject))]
+    #[thread_kind = objc2::MainThreadOnly]
+    #[name = "MarkyPdfPrintDelegate"]
+    #[ivars = std::cell::RefCell<Option<std::sync::mpsc::Sender<Result<(), String>>>>]
+    struct PrintDelegate;
+
+    impl PrintDelegate {
+        #[unsafe(method(printOperationDidRun:success:contextInfo:))]
+        fn did_run(
+            &self,
+            _operation: &objc2_app_kit::NSPrintOperation,
+            success: objc2::runtime::Bool,
+            _context: *mut std::ffi::c_void,
+        ) {
+            use objc2::DefinedClass;
+
+            if let Some(done) = self.ivars().borrow_mut().take() {
+                let _ = done.send(if success.as_bool() {
+                    Ok(())
+                } else {
+                    Err("The webview couldn't print the note".to_string())
+                });
+            }
+        }
+    }
+);
+
+#[cfg(target_os = "macos")]
+impl PrintDelegate {
+    fn new(
+        mtm: objc2::MainThreadMarker,
+        done: std::sync::mpsc::Sender<Result<(), String>>,
+    ) -> objc2::rc::Retained<Self> {
+        use objc2::MainThreadOnly;
+
+        let this = Self::alloc(mtm).set_ivars(std::cell::RefCell::new(Some(done)));
+        // SAFETY: NSObject's `init` takes no arguments
+        unsafe { objc2::msg_send![super(this), init] }
+    }
+}
+
+// Paginated, the way the print dialog's "Save as PDF" would do it
+#[cfg(target_os = "macos")]
+fn print_to_pdf(window: &tauri::WebviewWindow, target: &Path) -> Result<(), String> {
+    use objc2::rc::Retained;
+    use objc2::runtime::AnyObject;
+    use objc2::{sel, MainThreadMarker};
+    use objc2_app_kit::{
+        NSPrintInfo, NSPrintJobSavingURL, NSPrintSaveJob, NSPrintingPaginationMode,
+    };
+    use objc2_foundation::{NSString, NSURL};
+    use objc2_web_kit::WKWebView;
+    use std::cell::RefCell;
+
+    thread_local! {
+        // The operation doesn't keep its delegate alive
+        static DELEGATE: RefCell<Option<Retained<PrintDelegate>>> = const { RefCell::new(None) };
+    }
+
+    let (done, finished) = std::sync::mpsc::channel();
+    let target = target.to_path_buf();
+    window
+        .with_webview(move |webview| {
+            let Some(mtm) = MainThreadMarker::new() else {
+                return;
+            };
+            // SAFETY: Tauri hands out a valid WKWebView pointer for as long as the webview is open
+            let view: &WKWebView = unsafe { &*webview.inner().cast() };
+            let Some(ns_window) = view.window() else {
+                let _ = done.send(Err("The PDF renderer has no window".to_string()));
+                return;
+            };
+
+            let url = NSURL::fileURLWithPath(&NSString::from_str(&target.to_string_lossy()));
+            let url: &AnyObject = &url;
+            let info = NSPrintInfo::new();
+            // SAFETY: the saving URL attribute takes an NSURL, and both keys are AppKit constants
+            unsafe {
+                info.dictionary().insert(NSPrintJobSavingURL, url);
+                info.setJobDisposition(NSPrintSaveJob);
+            }
+            info.setHorizontalPagination(NSPrintingPaginationMode::Fit);
+            info.setVerticallyCentered(false);
+            info.setTopMargin(36.0);
+            info.setBottomMargin(36.0);
+            info.setLeftMargin(36.0);
+            info.setRightMargin(36.0);
+
+            // SAFETY: called on the main thread, with the webview alive
+            let operation = unsafe { view.printOperationWithPrintInfo(&info) };
+            operation.setShowsPrintPanel(false);
+            operation.setShowsProgressPanel(false);
+            // WebKit prints blank pages unless its print view has a size
+            if let Some(print_view) = operation.view() {
+                print_view.setFrame(view.frame());
+            }
+
+            let delegate = PrintDelegate::new(mtm, done);
+            let delegate_object: &AnyObject = &delegate;
+            // SAFETY: the delegate implements the selector with the signature AppKit calls it with
+            unsafe {
+                operation.runOperationModalForWindow_delegate_didRunSelector_contextInfo(
+                    &ns_window,
+                    Some(delegate_object),
+                    Some(sel!(printOperationDidRun:success:contextInfo:)),
+                    std::ptr::null_mut(),
+                )
+            };
+            DELEGATE.with(|current| *current.borrow_mut() = Some(delegate));
+        })
+        .map_err(|e| format!("Failed to print the PDF: {}", e))?;
+    wait(&finished)
+}
+
+#[cfg(windows)]
+fn print_to_pdf(window: &tauri::WebviewWindow, target: &Path) -> Result<(), String> {
+    use webview2_com::Microsoft::Web::WebView2::Win32::{
+        ICoreWebView2PrintSettings, ICoreWebView2_7,
+    };
+    use webview2_com::PrintToPdfCompletedHandler;
+    use windows::core::{Interface, HSTRING};
+
+    let (done, finished) = std::sync::mpsc::channel();
+    let path = HSTRING::from(target.as_os_str());
+    window
+        .with_webview(move |webview| {
+            let start = || -> windows::core::Result<()> {
+                // SAFETY: the controller belongs to this live webview
+                let core =
+                    unsafe { webview.controller().CoreWebView2() }?.cast::<ICoreWebView2_7>()?;
+                let done = done.clone();
+                let handler =
+                    PrintToPdfCompletedHandler::create(Box::new(move |result, printed| {
+                        let _ = done.send(match result {
+                            Ok(()) if printed => Ok(()),
+                            Ok(()) => Err("The webview couldn't print the note".to_string()),
+                            Err(e) => Err(format!("Failed to print the PDF: {}", e)),
+                        });
+                        Ok(())
+                    }));
+                // SAFETY: `path` outlives the call, which copies it
+                unsafe { core.PrintToPdf(&path, None::<&ICoreWebView2PrintSettings>, &handler) }
+            };
+            if let Err(e) = start() {
+                let _ = done.send(Err(format!("Failed to print the PDF: {}", e)));
+            }
+        })
+        .map_err(|e| format!("Failed to print the PDF: {}", e))?;
+    wait(&finished)
+}
+
+// A hidden webview that loads each page from `SCHEME` and prints it
+#[cfg(any(target_os = "macos", windows))]
+struct PdfPrinter {
+    app: AppHandle,
+    window: tauri::WebviewWindow,
+    // URL paths of pages that finished loading
+    loaded: std::sync::mpsc::Receiver<String>,
+    id: u64,
+    pages: usize,
+}
+
+#[cfg(any(target_os = "macos", windows))]
+impl PdfPrinter {
+    fn open(app: &AppHandle) -> Result<PdfPrinter, String> {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use tauri::webview::PageLoadEvent;
+        use tauri::{WebviewUrl, WebviewWindowBuilder};
+
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+        let state = app.state::<ExportState>();
+        if state.printing.swap(true, Ordering::SeqCst) {
+            return Err("Another PDF export is still running".to_string());
+        }
+        let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+        let (loaded_tx, loaded) = std::sync::mpsc::channel();
+        let loaded_tx = Mutex::new(loaded_tx);
+        let window = WebviewWindowBuilder::new(
+            app,
+            format!("{}{}", WINDOW_PREFIX, id),
+            WebviewUrl::External("about:blank".parse().expect("valid URL")),
+        )
+        .visible(false)
+        // US Letter at 96 dpi; the print settings pick the real page size
+        .inner_size(816.0, 1056.0)
+        .on_page_load(move |_, payload| {
+            if payload.event() == PageLoadEvent::Finished {
+                if let Ok(loaded_tx) = loaded_tx.lock() {
+                    let _ = loaded_tx.send(payload.url().path().to_string());
+                }
+            }
+        })
+        .build();
+        match window {
+            Ok(window) => Ok(PdfPrinter {
+                app: app.clone(),
+                window,
+                loaded,
+                id,
+                pages: 0,
+            }),
+            Err(e) => {
+                state.printing.store(false, Ordering::SeqCst);
+                Err(format!("Failed to open the PDF renderer: {}", e))
+            }
+        }
+    }
+
+    fn print(&mut self, html: &str, target: &Path) -> Result<(), String> {
+        self.pages += 1;
+        let path = format!("/{}-{}", self.id, self.pages);
+        let state = self.app.state::<ExportState>();
+        if let Ok(mut pages) = state.pages.lock() {
+            pages.insert(path.clone(), html.to_string());
+        }
+        let result = self
+            .load(&path)
+            .and_then(|()| print_to_pdf(&self.window, target));
+        if let Ok(mut pages) = state.pages.lock() {
+            pages.remove(&path);
+        }
+        result
+    }
+
+    fn load(&self, path: &str) -> Result<(), String> {
+        self.window
+            .navigate(page_url(path)?)
+            .map_err(|e| format!("Failed to load the PDF page: {}", e))?;
+        let deadline = std::time::Instant::now() + PDF_TIMEOUT;
+        loop {
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            match self.loaded.recv_timeout(remaining) {
+                Ok(loaded) if loaded == path => return Ok(()),
+                // about:blank, or a page from before a timeout
+                Ok(_) => continue,
+                Err(_) => return Err("Timed out rendering the PDF".to_string()),
+            }
+        }
+    }
+}
+
+#[cfg(any(target_os = "macos", windows))]
+impl Drop for PdfPrinter {
+    fn drop(&mut self) {
+        let _ = self.window.destroy();
+        self.app
+            .state::<ExportState>()
+            .printing
+            .store(false, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+// Other webviews have no print-to-file API Marky can reach; the frontend
+// renders PDFs itself there
+#[cfg(not(any(target_os = "macos", windows)))]
+struct PdfPrinter;
+
+#[cfg(not(any(target_os = "macos", windows)))]
+impl PdfPrinter {
+    fn open(_app: &AppHandle) -> Result<PdfPrinter, String> {
+        Err("PDF export isn't available on this platform".to_string())
+    }
+
+    fn print(&mut self, _html: &str, _target: &Path) -> Result<(), String> {
+        Err("PDF export isn't available on this platform".to_string())
+    }
+}
+
+fn open_printer(app: &AppHandle, format: Format) -> Result<Option<PdfPrinter>, String> {
+    match format {
+        Format::Pdf => PdfPrinter::open(app).map(Some),
+        Format::Html => Ok(None),
+    }
+}
+
+// The formats `export_note` and `export_folder` can write here
+#[tauri::command]
+pub fn export_formats() -> Vec<&'static str> {
+    if cfg!(any(target_os = "macos", windows)) {
+        vec!["html", "pdf"]
+    } else {
+        vec!["html"]
+    }
+}
+
+// Renders one note to a standalone HTML page or a PDF. `content` is the
+// editor's unsaved text when there is some. None when the dialog is cancelled.
+#[tauri::command]
+pub async fn export_note(
+    path: String,
+    format: String,
+    content: Option<String>,
+    options: Option<ExportOptions>,
+    app: AppHandle,
+) -> Result<Option<ExportResult>, String> {
+    let note = PathBuf::from(&path);
+    storage::ensure_accessible(&app, &note)?;
+    let format = Format::parse(&format)?;
+    if vault::vault_root(&note).is_some() {
+        return Err("Notes in an encrypted vault can't leave it as plain files".to_string());
+    }
+    let exists = network::guard_read(&app, &note, {
+        let note = note.clone();
+        move |_| note.is_file()
+    })?;
+    if !exists {
+        return Err("Note does not exist".to_string());
+    }
+
+    let options = options.unwrap_or_default();
+    let destination = match &options.destination {
+        Some(destination) => PathBuf::from(destination),
+        None => {
+            let name = share::export_name(&markdown::note_title(&note), format.extension());
+            let Some(picked) = app
+                .dialog()
+                .file()
+                .set_file_name(name)
+                .add_filter(format.extension().to_uppercase(), &[format.extension()])
+                .blocking_save_file()
+            else {
+                return Ok(None);
+            };
+            picked
+                .into_path()
+                .map_err(|e| format!("Failed to use the chosen file: {}", e))?
+        }
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        network::guard_bulk(&app, &note.clone(), move |app| {
+            let name = destination
+                .file_name()
+                .ok_or("Choose a file to export to")?
+                .to_os_string();
+            let folder = destination
+                .parent()
+                .and_then(|parent| parent.canonicalize().ok())
+                .ok_or("The export folder doesn't exist")?;
+            let destination = folder.join(name);
+
+            let mut export = Export::new(app, &note, format, options);
+            let source = note.canonicalize().unwrap_or_else(|_| note.clone());
+            export.targets.insert(source, destination.clone());
+            let html = export.render(app, &note, content, &destination)?;
+            let mut printer = open_printer(app, format)?;
+            export.write(&mut printer, &html, &destination)?;
+
+            Ok(Some(ExportResult {
+                destination: destination.to_string_lossy().to_string(),
+                exported: 1,
+                skipped: Vec::new(),
+            }))
+        })?
+    })
+    .await
+    .map_err(|e| format!("Failed to export note: {}", e))?
+}
+
+#[cfg(desktop)]
+fn pick_folder(app: &AppHandle) -> Result<Option<PathBuf>, String> {
+    app.dialog()
+        .file()
+        .blocking_pick_folder()
+        .map(|picked| {
+            picked
+                .into_path()
+                .map_err(|e| format!("Failed to use the chosen folder: {}", e))
+        })
+        .transpose()
+}
+
+// Mobile pickers can't choose a folder to write into
+#[cfg(mobile)]
+fn pick_folder(_app: &AppHandle) -> Result<Option<PathBuf>, String> {
+    Err("Choose a destination folder to export to".to_string())
+}
+
+// Renders every note under `folder_path` into a folder of the same name at
+// the destination, keeping subfolders and pointing links between the notes at
+// their exports. None when the dialog is cancelled.
+#[tauri::command]
+pub async fn export_folder(
+    folder_path: String,
+    format: String,
+    options: Option<ExportOptions>,
+    app: AppHandle,
+) -> Result<Option<ExportResult>, String> {
+    let folder = PathBuf::from(&folder_path);
+    storage::ensure_accessible(&app, &folder)?;
+    let format = Format::parse(&format)?;
+    if vault::vault_root(&folder).is_some() {
+        return Err("Notes in an encrypted vault can't leave it as plain files".to_string());
+    }
+    let exists = network::guard_read(&app, &folder, {
+        let folder = folder.clone();
+        move |_| folder.is_dir()
+    })?;
+    if !exists {
+        return Err("Folder does not exist".to_string());
+    }
+
+    let options = options.unwrap_or_default();
+    let destination = match &options.destination {
+        Some(destination) => PathBuf::from(destination),
+        None => match pick_folder(&app)? {
+            Some(picked) => picked,
+            None => return Ok(None),
+        },
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        network::guard_bulk(&app, &folder.clone(), move |app| {
+            let source = folder
+                .canonicalize()
+                .map_err(|e| format!("Failed to read folder: {}", e))?;
+            let destination = destination
+                .canonicalize()
+                .map_err(|_| "The export folder doesn't exist".to_string())?;
+            if destination.starts_with(&source) {
+                return Err("Choose a destination outside the folder being exported".to_string());
+            }
+            let output_root = destination.join(source.file_name().unwrap_or_default());
+
+            let mut export = Export::new(app, &folder, format, options);
+            let settings = app.state::<SettingsState>().current();
+            let filter = NoteFilter::for_workspace(&export.root, &settings);
+            let mut files = Vec::new();
+            index::collect_markdown_files(&folder, &filter, &mut files);
+
+            let notes: Vec<(PathBuf, PathBuf)> = files
+                .into_iter()
+                .filter_map(|file| {
+                    let canonical = file.canonicalize().ok()?;
+                    let relative = canonical.strip_prefix(&source).ok()?.to_path_buf();
+                    Some((file, relative))
+                })
+                .collect();
+            for (file, relative) in &notes {
+                let output = output_root
+                    .join(relative)
+                    .with_extension(format.extension());
+                let canonical = file.canonicalize().unwrap_or_else(|_| file.clone());
+                export.targets.insert(canonical, output);
+            }
+
+            let mut printer = open_printer(app, format)?;
+            let mut exported = 0;
+            let mut skipped = Vec::new();
+            for (file, relative) in &notes {
+                let output = output_root
+                    .join(relative)
+                    .with_extension(format.extension());
+                match export.render(app, file, None, &output) {
+                    Ok(html) => {
+                        export.write(&mut printer, &html, &output)?;
+                        exported += 1;
+                    }
+                    Err(e) => {
+                        tracing::info!("Not exporting {}: {}", file.display(), e);
+                        skipped.push(relative.to_string_lossy().replace('\\', "/"));
+                    }
+                }
+            }
+
+            Ok(Some(ExportResult {
+                destination: output_root.to_string_lossy().to_string(),
+                exported,
+                skipped,
+            }))
+        })?
+    })
+    .await
+    .map_err(|e| format!("Failed to export folder: {}", e))?
+}
@@ -0,0 +1,607 @@
+use crate::activity::ActivityState;
+use crate::api;
+use crate::clipper;
+use crate::http;
+use crate::index::IndexState;
+use crate::markdown;
+use crate::safe_mode::SafeModeState;
+use crate::settings::SettingsState;
+use crate::storage;
+use crate::vault;
+use crate::watcher::WatcherState;
+use crate::workspace_config::{self, CONFIG_DIR};
+use crate::writing::WritingState;
+use chrono::{DateTime, Duration as ChronoDuration, Local};
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, XmlVersion};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const FEEDS_FILE: &str = "feeds.json";
+const STATE_FILE: &str = "feeds.json";
+const TARGETS: [&str; 2] = ["note", "daily"];
+const DEFAULT_FOLDER: &str = "Feeds";
+// Appends from feeds aren't tied to a window, so every window reloads the note
+const ORIGIN: &str = "feeds";
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const DEFAULT_INTERVAL_MINUTES: u32 = 60;
+const MIN_INTERVAL_MINUTES: u32 = 15;
+// A feed that was never read, or was down for a while, shouldn't bury the note
+const MAX_NEW_ITEMS: usize = 20;
+const MAX_SUMMARY_CHARS: usize = 500;
+// Feeds only list their latest items, so older ids can be forgotten
+const MAX_SEEN_ITEMS: usize = 1000;
+
+// One subscription: new items from `url` are appended to `note` (or a note
+// named after the feed in `Feeds/`), or to today's daily note
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Feed {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    pub enabled: bool,
+    // `note` or `daily`
+    pub target: String,
+    // `note`: workspace-relative path of the note items go in
+    pub note: Option<String>,
+    pub interval_minutes: u32,
+}
+
+// Stored in the workspace, next to its config, so subscriptions travel with the notes
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct FeedsFile {
+    feeds: Vec<Feed>,
+}
+
+// Which items were already delivered, kept with the app's data for the workspace
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct FeedRecord {
+    // Item ids, oldest first
+    seen: Vec<String>,
+    last_checked: Option<String>,
+    last_error: Option<String>,
+    last_added: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct FeedsLog {
+    feeds: HashMap<String, FeedRecord>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedStatus {
+    id: String,
+    last_checked: Option<String>,
+    last_error: Option<String>,
+    // Items appended by the last check
+    last_added: usize,
+    seen: usize,
+}
+
+// Checks of one workspace run one at a time, so no item is appended twice
+#[derive(Default)]
+pub struct FeedsState {
+    polling: Mutex<()>,
+}
+
+#[derive(Debug, Default)]
+struct Item {
+    id: String,
+    title: String,
+    link: Option<String>,
+    // HTML, as feeds send it
+    summary: String,
+    content: String,
+    published: Option<String>,
+}
+
+fn feeds_path(root: &Path) -> PathBuf {
+    root.join(CONFIG_DIR).join(FEEDS_FILE)
+}
+
+fn load(root: &Path) -> Vec<Feed> {
+    storage::read_json::<FeedsFile>(&feeds_path(root)).feeds
+}
+
+fn log_path(app: &AppHandle, root: &Path) -> Result<PathBuf, String> {
+    Ok(storage::workspace_data_dir(app, root)?.join(STATE_FILE))
+}
+
+fn is_web_url(url: &str) -> bool {
+    let url = url.trim().to_lowercase();
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn validate(root: &Path, feed: &mut Feed) -> Result<(), String> {
+    feed.name = feed.name.trim().to_string();
+    if feed.name.is_empty() {
+        return Err("Feed needs a name".to_string());
+    }
+    feed.url = feed.url.trim().to_string();
+    if !is_web_url(&feed.url) {
+        return Err(format!(
+            "Feed address must start with http:// or https://: {}",
+            feed.url
+        ));
+    }
+    feed.target = feed.target.trim().to_lowercase();
+    if !TARGETS.contains(&feed.target.as_str()) {
+        return Err(format!("Unknown feed target: {}", feed.target));
+    }
+    if let Some(note) = non_empty(&feed.note) {
+        api::note_path(root, note)?;
+    }
+    feed.note = non_empty(&feed.note).map(str::to_string);
+    if feed.interval_minutes == 0 {
+        feed.interval_minutes = DEFAULT_INTERVAL_MINUTES;
+    }
+    feed.interval_minutes = feed.interval_minutes.max(MIN_INTERVAL_MINUTES);
+    Ok(())
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Option<String> {
+    let attribute = element.try_get_attribute(name).ok()??;
+    attribute
+        .normalized_value(XmlVersion::Implicit1_0)
+        .ok()
+        .map(|value| value.to_string())
+}
+
+// Atom links are `<link href="...">`; only the page itself is wanted
+fn atom_link(element: &BytesStart) -> Option<String> {
+    let rel = attribute(element, "rel");
+    if rel.is_some_and(|rel| rel != "alternate") {
+        return None;
+    }
+    attribute(element, "href")
+}
+
+fn set_once(field: &mut String, value: &str) {
+    if field.is_empty() {
+        *field = value.trim().to_string();
+    }
+}
+
+// RSS 0.9x–2.0, RSS 1.0, and Atom, read loosely: elements are matched by local
+// name and the first of each kind wins, so `media:title` doesn't replace `title`
+fn parse_feed(xml: &str) -> Result<Vec<Item>, String> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().allow_dangling_amp = true;
+    reader.config_mut().check_end_names = false;
+
+    let mut items = Vec::new();
+    let mut current: Option<Item> = None;
+    let mut depth = 0usize;
+    let mut item_depth = 0usize;
+    let mut text = String::new();
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("Not a valid feed: {}", e))?;
+        match event {
+            Event::Start(element) => {
+                depth += 1;
+                let name = element.local_name().as_ref().to_string();
+                if current.is_none() && matches!(name.as_str(), "item" | "entry") {
+                    current = Some(Item::default());
+                    item_depth = depth;
+                } else if current.is_some() && depth == item_depth + 1 {
+                    text.clear();
+                    if name == "link" {
+                        if let (Some(item), Some(link)) = (current.as_mut(), atom_link(&element)) {
+                            item.link.get_or_insert(link);
+                        }
+                    }
+                }
+            }
+            Event::Empty(element)
+                if current.is_some()
+                    && depth == item_depth
+                    && element.local_name().as_ref() == "link" =>
+            {
+                if let (Some(item), Some(link)) = (current.as_mut(), atom_link(&element)) {
+                    item.link.get_or_insert(link);
+                }
+            }
+            Event::End(element) => {
+                let name = element.local_name().as_ref().to_string();
+                if current.is_some() && depth == item_depth {
+                    if let Some(mut item) = current.take() {
+                        if item.id.is_empty() {
+                            item.id = item.link.clone().unwrap_or_else(|| item.title.clone());
+                        }
+                        if !item.id.is_empty() {
+                            items.push(item);
+                        }
+                    }
+                } else if let Some(item) = current.as_mut().filter(|_| depth == item_depth + 1) {
+                    match name.as_str() {
+                        "title" => set_once(&mut item.title, &text),
+                        "link" if !text.trim().is_empty() => {
+                            item.link.get_or_insert(text.trim().to_string());
+                        }
+                        "guid" | "id" => set_once(&mut item.id, &text),
+                        "description" | "summary" => set_once(&mut item.summary, &text),
+                        "encoded" | "content" => set_once(&mut item.content, &text),
+                        "pubDate" | "published" | "updated" | "date" => {
+                            item.published.get_or_insert(text.trim().to_string());
+                        }
+                        _ => {}
+                    }
+                }
+                depth = depth.saturating_sub(1);
+            }
+            Event::Text(content) => text.push_str(&content.xml10_content()),
+            Event::CData(content) => text.push_str(&content.xml10_content()),
+            Event::GeneralRef(reference) => {
+                if let Ok(Some(c)) = reference.resolve_char_ref() {
+                    text.push(c);
+                } else if let Some(value) = resolve_predefined_entity(&reference) {
+                    text.push_str(value);
+                } else {
+                    // HTML entities in summaries are left for the HTML converter
+                    text.push('&');
+                    text.push_str(&reference);
+                    text.push(';');
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(items)
+}
+
+fn format_date(published: &str) -> Option<String> {
+    let date = DateTime::parse_from_rfc2822(published)
+        .or_else(|_| DateTime::parse_from_rfc3339(published))
+        .ok()?;
+    Some(date.with_timezone(&Local).format("%Y-%m-%d").to_string())
+}
+
+// Whole paragraphs up to the limit, so the markdown isn't cut mid-link
+fn shorten(summary: &str) -> String {
+    let mut output = String::new();
+    for block in summary.split("\n\n") {
+        if !output.is_empty() && output.chars().count() + block.chars().count() > MAX_SUMMARY_CHARS
+        {
+            output.push_str("\n\n…");
+            break;
+        }
+        if !output.is_empty() {
+            output.push_str("\n\n");
+        }
+        if block.chars().count() > MAX_SUMMARY_CHARS {
+            output.extend(block.chars().take(MAX_SUMMARY_CHARS));
+            output.push('…');
+            break;
+        }
+        output.push_str(block);
+    }
+    output
+}
+
+fn render_item(item: &Item) -> String {
+    let title = if item.title.is_empty() {
+        "Untitled".to_string()
+    } else {
+        item.title.replace('[', "\\[").replace(']', "\\]")
+    };
+    let mut sections = vec![match &item.link {
+        Some(link) => format!("### [{}]({})", title, link),
+        None => format!("### {}", title),
+    }];
+    if let Some(date) = item.published.as_deref().and_then(format_date) {
+        sections.push(format!("*{}*", date));
+    }
+
+    let html = if item.summary.is_empty() {
+        &item.content
+    } else {
+        &item.summary
+    };
+    let base = item
+        .link
+        .as_deref()
+        .and_then(|link| tauri::Url::parse(link).ok());
+    let summary = markdown::from_html(html, base.as_ref());
+    let summary = summary.trim();
+    if !summary.is_empty() {
+        sections.push(shorten(summary));
+    }
+    sections.join("\n\n")
+}
+
+// Today's daily note, wherever the workspace keeps it, or a new one at the root
+fn daily_note_path(app: &AppHandle, root: &Path) -> Result<PathBuf, String> {
+    let format = workspace_config::effective(root, &app.state::<SettingsState>().current())
+        .daily_note_format;
+    let title = Local::now().format(&format).to_string();
+    let existing = app.state::<IndexState>().with_workspace(root, |index| {
+        index
+            .notes
+            .values()
+            .find(|note| note.title == title)
+            .map(|note| note.path.clone())
+    })?;
+    if let Some(path) = existing {
+        return Ok(path);
+    }
+    api::note_path(root, &format!("{}.md", title))
+}
+
+fn target_note(app: &AppHandle, root: &Path, feed: &Feed) -> Result<PathBuf, String> {
+    if feed.target == "daily" {
+        return daily_note_path(app, root);
+    }
+    match non_empty(&feed.note) {
+        Some(note) => api::note_path(root, note),
+        None => api::note_path(
+            root,
+            &format!(
+                "{}/{}.md",
+                DEFAULT_FOLDER,
+                clipper::file_stem(&feed.name, "Feed")
+            ),
+        ),
+    }
+}
+
+fn deliver(app: &AppHandle, root: &Path, feed: &Feed, items: &[&Item]) -> Result<(), String> {
+    let path = target_note(app, root, feed)?;
+    let mut text = String::new();
+    if feed.target == "daily" {
+        text.push_str(&format!("\n## {}\n", feed.name));
+    }
+    for item in items {
+        text.push('\n');
+        text.push_str(&render_item(item));
+        text.push('\n');
+    }
+
+    if path.exists() {
+        return crate::append_to_note(app, ORIGIN, &path, &text).map_err(|e| e.into_message());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create folder: {}", e))?;
+    }
+    let heading = if feed.target == "daily" {
+        markdown::note_title(&path)
+    } else {
+        feed.name.clone()
+    };
+    crate::write_note(
+        app,
+        ORIGIN,
+        &path.to_string_lossy(),
+        format!("# {}\n{}", heading, text),
+        &app.state::<WatcherState>(),
+        &app.state::<WritingState>(),
+        &app.state::<ActivityState>(),
+    )
+    .map_err(|e| e.into_message())
+}
+
+// Fetches a feed and appends the items not seen yet, oldest first. With
+// `deliver_items` off they're only marked as read.
+fn check_feed(
+    app: &AppHandle,
+    root: &Path,
+    feed: &Feed,
+    record: &mut FeedRecord,
+    deliver_items: bool,
+) -> Result<(), String> {
+    let xml = tauri::async_runtime::block_on(http::get_text(&feed.url))?;
+    let items = parse_feed(&xml)?;
+
+    let seen: HashSet<&str> = record.seen.iter().map(String::as_str).collect();
+    // Feeds list newest first
+    let mut new_items: Vec<&Item> = items
+        .iter()
+        .filter(|item| !seen.contains(item.id.as_str()))
+        .take(MAX_NEW_ITEMS)
+        .collect();
+    new_items.reverse();
+
+    if deliver_items && !new_items.is_empty() {
+        deliver(app, root, feed, &new_items)?;
+    }
+    record.last_added = if deliver_items { new_items.len() } else { 0 };
+
+    // Everything the feed lists counts as read, including items past the limit
+    let unseen: Vec<String> = items
+        .iter()
+        .rev()
+        .filter(|item| !seen.contains(item.id.as_str()))
+        .map(|item| item.id.clone())
+        .collect();
+    record.seen.extend(unseen);
+    if record.seen.len() > MAX_SEEN_ITEMS {
+        let excess = record.seen.len() - MAX_SEEN_ITEMS;
+        record.seen.drain(..excess);
+    }
+    Ok(())
+}
+
+fn is_due(feed: &Feed, record: Option<&FeedRecord>) -> bool {
+    let Some(last) = record
+        .and_then(|record| record.last_checked.as_deref())
+        .and_then(|last| DateTime::parse_from_rfc3339(last).ok())
+    else {
+        return true;
+    };
+    let interval = ChronoDuration::minutes(feed.interval_minutes.max(MIN_INTERVAL_MINUTES) as i64);
+    Local::now() >= last + interval
+}
+
+fn status(id: &str, record: Option<&FeedRecord>) -> FeedStatus {
+    let record = record.cloned().unwrap_or_default();
+    FeedStatus {
+        id: id.to_string(),
+        last_checked: record.last_checked,
+        last_error: record.last_error,
+        last_added: record.last_added,
+        seen: record.seen.len(),
+    }
+}
+
+// Checks the workspace's feeds: all due ones, or just `only` whether due or not
+fn check_workspace(
+    app: &AppHandle,
+    root: &Path,
+    only: Option<&str>,
+    deliver_items: bool,
+) -> Result<Vec<FeedStatus>, String> {
+    let state = app.state::<FeedsState>();
+    let _polling = state
+        .polling
+        .lock()
+        .map_err(|_| "Feed checks are unavailable".to_string())?;
+
+    let path = log_path(app, root)?;
+    let mut log: FeedsLog = storage::read_json(&path);
+    let feeds = load(root);
+    let mut changed = false;
+
+    for feed in &feeds {
+        let wanted = match only {
+            Some(id) => feed.id == id,
+            None => feed.enabled && is_due(feed, log.feeds.get(&feed.id)),
+        };
+        if !wanted {
+            continue;
+        }
+
+        let record = log.feeds.entry(feed.id.clone()).or_default();
+        record.last_error = match check_feed(app, root, feed, record, deliver_items) {
+            Ok(()) => None,
+            Err(e) => {
+                tracing::warn!("Feed {} failed: {}", feed.name, e);
+                Some(e)
+            }
+        };
+        record.last_checked = Some(Local::now().to_rfc3339());
+        changed = true;
+    }
+
+    if changed {
+        storage::write_json(&path, &log)?;
+    }
+    Ok(feeds
+        .iter()
+        .map(|feed| status(&feed.id, log.feeds.get(&feed.id)))
+        .collect())
+}
+
+// Checks every watched workspace on a timer; each feed has its own interval
+pub fn spawn_scheduler(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(CHECK_INTERVAL);
+        if app.state::<SafeModeState>().is_active() {
+            continue;
+        }
+
+        for root in app.state::<WatcherState>().watched_roots() {
+            // Items would land in plaintext bookkeeping outside the vault
+            if vault::is_vault_root(&root) || !feeds_path(&root).is_file() {
+                continue;
+            }
+            if let Err(e) = check_workspace(&app, &root, None, true) {
+                tracing::error!("Failed to check feeds: {}", e);
+            }
+        }
+    });
+}
+
+fn workspace_root(folder_path: &str) -> Result<PathBuf, String> {
+    let root = PathBuf::from(folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+    if vault::is_vault_root(&root) {
+        return Err("Feeds can't deliver into encrypted vaults".to_string());
+    }
+    Ok(root)
+}
+
+#[tauri::command]
+pub fn get_feeds(folder_path: String) -> Result<Vec<Feed>, String> {
+    Ok(load(&workspace_root(&folder_path)?))
+}
+
+#[tauri::command]
+pub fn set_feeds(folder_path: String, feeds: Vec<Feed>) -> Result<Vec<Feed>, String> {
+    let root = workspace_root(&folder_path)?;
+
+    let mut feeds = feeds;
+    let stamp = Local::now().timestamp_millis();
+    for (i, feed) in feeds.iter_mut().enumerate() {
+        validate(&root, feed)?;
+        if feed.id.trim().is_empty() {
+            feed.id = format!("feed-{}-{}", stamp, i);
+        }
+    }
+
+    fs::create_dir_all(root.join(CONFIG_DIR))
+        .map_err(|e| format!("Failed to create workspace config folder: {}", e))?;
+    storage::write_json(&feeds_path(&root), &FeedsFile { feeds })?;
+    Ok(load(&root))
+}
+
+#[tauri::command]
+pub fn get_feed_status(folder_path: String, app: AppHandle) -> Result<Vec<FeedStatus>, String> {
+    let root = workspace_root(&folder_path)?;
+    let log: FeedsLog = storage::read_json(&log_path(&app, &root)?);
+    Ok(load(&root)
+        .iter()
+        .map(|feed| status(&feed.id, log.feeds.get(&feed.id)))
+        .collect())
+}
+
+// Checks one feed now, or every due feed when `feed_id` is missing
+#[tauri::command]
+pub async fn refresh_feeds(
+    folder_path: String,
+    feed_id: Option<String>,
+    app: AppHandle,
+) -> Result<Vec<FeedStatus>, String> {
+    let root = workspace_root(&folder_path)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        check_workspace(&app, &root, feed_id.as_deref(), true)
+    })
+    .await
+    .map_err(|e| format!("Feed check failed: {}", e))?
+}
+
+// Marks everything the feed currently lists as read without adding it anywhere
+#[tauri::command]
+pub async fn mark_feed_read(
+    folder_path: String,
+    feed_id: String,
+    app: AppHandle,
+) -> Result<Vec<FeedStatus>, String> {
+    let root = workspace_root(&folder_path)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        check_workspace(&app, &root, Some(&feed_id), false)
+    })
+    .await
+    .map_err(|e| format!("Feed check failed: {}", e))?
+}
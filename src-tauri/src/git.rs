@@ -0,0 +1,187 @@
+use crate::storage;
+use crate::vault;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::AppHandle;
+
+// Keeps each git call from a GUI process from flashing a console window
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+// A file as it was in the latest commit
+pub struct HeadVersion {
+    pub commit: String,
+    pub content: String,
+}
+
+// Where hooks are looked up instead of the repository's own hooks folder
+#[cfg(windows)]
+const NO_HOOKS: &str = "core.hooksPath=NUL";
+#[cfg(not(windows))]
+const NO_HOOKS: &str = "core.hooksPath=/dev/null";
+
+// git's stdout for `args`, run from `dir`. None when git isn't installed or
+// the command fails, which callers treat the same as "not a repository".
+// Opening a folder mustn't run anything its repository config names, so the
+// fsmonitor and hooks are switched off, and the app's own GIT_* environment
+// can't point git at a different repository.
+pub fn run(dir: &Path, args: &[&str]) -> Option<Vec<u8>> {
+    let mut command = Command::new("git");
+    command
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "core.fsmonitor=false", "-c", NO_HOOKS])
+        .args(args);
+    for (key, _) in std::env::vars_os() {
+        if key.to_string_lossy().starts_with("GIT_") {
+            command.env_remove(key);
+        }
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    let output = command.output().ok()?;
+    output.status.success().then_some(output.stdout)
+}
+
+// The committed text of the file at `path`, exactly as stored: checkout
+// filters are commands from the repository config, so they aren't applied.
+// None outside a repository, before the first commit, or when HEAD doesn't
+// have the file.
+pub fn head_version(path: &Path) -> Option<HeadVersion> {
+    let dir = path.parent()?;
+    let name = path.file_name()?.to_str()?;
+    let commit = String::from_utf8(run(dir, &["rev-parse", "--short", "HEAD"])?).ok()?;
+    // `./` resolves the name against `dir` instead of the repository root
+    let spec = format!("HEAD:./{}", name);
+    let content = String::from_utf8(run(dir, &["cat-file", "blob", &spec])?).ok()?;
+    Some(HeadVersion {
+        commit: commit.trim().to_string(),
+        content,
+    })
+}
+
+#[derive(Debug, Serialize)]
+pub struct GitStatuses {
+    // False outside a repository (or without git), with `files` empty
+    is_repo: bool,
+    // Path -> "modified", "added", "conflicted", "untracked", or "ignored".
+    // Unchanged files aren't listed; an ignored folder is listed once for
+    // everything in it.
+    files: HashMap<String, String>,
+}
+
+// One `git status --porcelain -z` entry: two status letters, a space, the path
+fn status_name(code: &[u8]) -> Option<&'static str> {
+    match code {
+        b"??" => Some("untracked"),
+        b"!!" => Some("ignored"),
+        [b'U', _] | [_, b'U'] | b"AA" | b"DD" => Some("conflicted"),
+        // Deleted files have nothing left to badge
+        [_, b'D'] | [b'D', _] => None,
+        [b'A', _] => Some("added"),
+        _ => Some("modified"),
+    }
+}
+
+fn statuses(folder: &Path) -> Option<HashMap<String, String>> {
+    // Paths come back relative to the repository root; the prefix is where
+    // `folder` sits inside it, so they can be joined onto `folder` as given
+    let prefix = String::from_utf8(run(folder, &["rev-parse", "--show-prefix"])?).ok()?;
+    let prefix = prefix.trim_end_matches(['\r', '\n']);
+    let output = run(
+        folder,
+        &[
+            "status",
+            "--porcelain=v1",
+            "-z",
+            "--untracked-files=all",
+            "--ignored=matching",
+            "--",
+            ".",
+        ],
+    )?;
+
+    let mut files = HashMap::new();
+    let mut entries = output.split(|byte| *byte == 0);
+    while let Some(entry) = entries.next() {
+        if entry.len() < 4 {
+            continue;
+        }
+        let code = &entry[..2];
+        // Renames and copies are followed by the old path, which is gone
+        if matches!(code[0], b'R' | b'C') {
+            entries.next();
+        }
+        let Some(status) = status_name(code) else {
+            continue;
+        };
+        let Ok(relative) = std::str::from_utf8(&entry[3..]) else {
+            continue;
+        };
+        let Some(relative) = relative.strip_prefix(prefix) else {
+            continue;
+        };
+        let path: PathBuf = relative
+            .split('/')
+            .filter(|part| !part.is_empty())
+            .fold(folder.to_path_buf(), |path, part| path.join(part));
+        files.insert(path.to_string_lossy().to_string(), status.to_string());
+    }
+    Some(files)
+}
+
+// Per-file git status for the workspace at `folder`, for badging the sidebar
+#[tauri::command]
+pub async fn get_git_statuses(folder: String, app: AppHandle) -> Result<GitStatuses, String> {
+    let folder = PathBuf::from(folder);
+    storage::ensure_accessible(&app, &folder)?;
+    // Vault file names on disk are ciphertext, which means nothing to the sidebar
+    if vault::vault_root(&folder).is_some() {
+        return Ok(GitStatuses {
+            is_repo: false,
+            files: HashMap::new(),
+        });
+    }
+
+    let files = tauri::async_runtime::spawn_blocking(move || statuses(&folder))
+        .await
+        .map_err(|e| format!("Failed to read git status: {}", e))?;
+    Ok(GitStatuses {
+        is_repo: files.is_some(),
+        files: files.unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_ignores_a_configured_fsmonitor() {
+        // Nothing to check on machines without git
+        if run(&std::env::temp_dir(), &["--version"]).is_none() {
+            return;
+        }
+        let dir = std::env::temp_dir().join(format!("marky-git-fsmonitor-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let marker = dir.join("fsmonitor-ran");
+        run(&dir, &["init", "-q"]).unwrap();
+        let hook = format!("touch '{}'", marker.display());
+        run(&dir, &["config", "core.fsmonitor", &hook]).unwrap();
+        std::fs::write(dir.join("note.md"), "# Note\n").unwrap();
+
+        let files = statuses(&dir).unwrap();
+        assert_eq!(
+            files.get(&dir.join("note.md").to_string_lossy().to_string()),
+            Some(&"untracked".to_string())
+        );
+        assert!(!marker.exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
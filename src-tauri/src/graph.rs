@@ -0,0 +1,214 @@
+use crate::index::{IndexState, WorkspaceIndex};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+struct GraphNode {
+    // Path relative to the workspace, with `/` separators; the node's id
+    id: String,
+    title: String,
+    folder: String,
+    tags: Vec<String>,
+    words: usize,
+}
+
+struct NoteGraph {
+    name: String,
+    nodes: Vec<GraphNode>,
+    edges: BTreeSet<(String, String)>,
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+// Notes and the wiki links between them, sorted so exports of an unchanged
+// workspace come out identical
+fn build_graph(root: &Path, index: &WorkspaceIndex) -> NoteGraph {
+    let mut nodes: Vec<GraphNode> = index
+        .notes
+        .values()
+        .map(|note| {
+            let id = relative_path(root, &note.path);
+            let folder = id.rsplit_once('/').map_or("", |(folder, _)| folder);
+            GraphNode {
+                folder: folder.to_string(),
+                id,
+                title: note.title.clone(),
+                tags: note.tags.clone(),
+                words: note.words,
+            }
+        })
+        .collect();
+    nodes.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut edges = BTreeSet::new();
+    for note in index.notes.values() {
+        for link in &note.links {
+            let Some(target) = index.resolve_link(link) else {
+                continue;
+            };
+            if target.path != note.path {
+                edges.insert((
+                    relative_path(root, &note.path),
+                    relative_path(root, &target.path),
+                ));
+            }
+        }
+    }
+
+    NoteGraph {
+        name: root
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Notes".to_string()),
+        nodes,
+        edges,
+    }
+}
+
+fn dot_string(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace(['\r', '\n'], " ");
+    format!("\"{}\"", escaped)
+}
+
+fn dot_node(node: &GraphNode, indent: &str) -> String {
+    format!(
+        "{}{} [label={}, folder={}, tags={}, words={}];\n",
+        indent,
+        dot_string(&node.id),
+        dot_string(&node.title),
+        dot_string(&node.folder),
+        dot_string(&node.tags.join(",")),
+        node.words
+    )
+}
+
+// One `cluster_` subgraph per folder, which Graphviz draws as a box around
+// its notes; tags and folders are also node attributes for other tools
+fn to_dot(graph: &NoteGraph) -> String {
+    let mut folders: BTreeMap<&str, Vec<&GraphNode>> = BTreeMap::new();
+    for node in &graph.nodes {
+        folders.entry(node.folder.as_str()).or_default().push(node);
+    }
+
+    let mut dot = format!("digraph {} {{\n", dot_string(&graph.name));
+    dot.push_str("  graph [rankdir=LR];\n");
+    dot.push_str("  node [shape=box, style=rounded];\n");
+    for (idx, (folder, nodes)) in folders.iter().enumerate() {
+        if folder.is_empty() {
+            for node in nodes {
+                dot.push_str(&dot_node(node, "  "));
+            }
+            continue;
+        }
+        dot.push_str(&format!("  subgraph \"cluster_{}\" {{\n", idx));
+        dot.push_str(&format!("    label={};\n", dot_string(folder)));
+        for node in nodes {
+            dot.push_str(&dot_node(node, "    "));
+        }
+        dot.push_str("  }\n");
+    }
+    for (source, target) in &graph.edges {
+        dot.push_str(&format!(
+            "  {} -> {};\n",
+            dot_string(source),
+            dot_string(target)
+        ));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+fn xml_escape(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .fold(String::with_capacity(text.len()), |mut escaped, c| {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&apos;"),
+                _ => escaped.push(c),
+            }
+            escaped
+        })
+}
+
+fn to_graphml(graph: &NoteGraph) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    for (key, kind) in [
+        ("label", "string"),
+        ("folder", "string"),
+        ("tags", "string"),
+        ("words", "int"),
+    ] {
+        xml.push_str(&format!(
+            "  <key id=\"{0}\" for=\"node\" attr.name=\"{0}\" attr.type=\"{1}\"/>\n",
+            key, kind
+        ));
+    }
+    xml.push_str(&format!(
+        "  <graph id=\"{}\" edgedefault=\"directed\">\n",
+        xml_escape(&graph.name)
+    ));
+    for node in &graph.nodes {
+        xml.push_str(&format!("    <node id=\"{}\">\n", xml_escape(&node.id)));
+        xml.push_str(&format!(
+            "      <data key=\"label\">{}</data>\n",
+            xml_escape(&node.title)
+        ));
+        xml.push_str(&format!(
+            "      <data key=\"folder\">{}</data>\n",
+            xml_escape(&node.folder)
+        ));
+        xml.push_str(&format!(
+            "      <data key=\"tags\">{}</data>\n",
+            xml_escape(&node.tags.join(","))
+        ));
+        xml.push_str(&format!(
+            "      <data key=\"words\">{}</data>\n",
+            node.words
+        ));
+        xml.push_str("    </node>\n");
+    }
+    for (idx, (source, target)) in graph.edges.iter().enumerate() {
+        xml.push_str(&format!(
+            "    <edge id=\"e{}\" source=\"{}\" target=\"{}\"/>\n",
+            idx,
+            xml_escape(source),
+            xml_escape(target)
+        ));
+    }
+    xml.push_str("  </graph>\n</graphml>\n");
+    xml
+}
+
+// The workspace's note graph as Graphviz DOT (`dot`) or GraphML (`graphml`),
+// for laying it out or analysing it in other tools
+#[tauri::command]
+pub async fn export_graph(
+    folder_path: String,
+    format: String,
+    index_state: State<'_, IndexState>,
+) -> Result<String, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+    let render = match format.to_lowercase().as_str() {
+        "dot" | "gv" => to_dot,
+        "graphml" => to_graphml,
+        _ => return Err(format!("Unknown graph format: {}", format)),
+    };
+    let graph = index_state.with_workspace(&root, |index| build_graph(&root, index))?;
+    Ok(render(&graph))
+}
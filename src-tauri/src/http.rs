@@ -0,0 +1,166 @@
+use reqwest::redirect::Policy;
+use reqwest::Url;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(15);
+// As many as reqwest follows on its own
+const MAX_REDIRECTS: usize = 10;
+// Generous for feeds and calendars, small enough to never stall the app
+const MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+// The updater may already have installed one; either way one has to exist
+// before any TLS connection is made
+pub fn ensure_crypto_provider() {
+    if rustls::crypto::CryptoProvider::get_default().is_none() {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+    }
+}
+
+fn build_client(redirects: Policy) -> Result<reqwest::Client, String> {
+    ensure_crypto_provider();
+    reqwest::Client::builder()
+        .timeout(TIMEOUT)
+        .redirect(redirects)
+        .user_agent(concat!("Marky/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+fn client() -> Result<&'static reqwest::Client, String> {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    if let Some(client) = CLIENT.get() {
+        return Ok(client);
+    }
+    let client = build_client(Policy::default())?;
+    Ok(CLIENT.get_or_init(|| client))
+}
+
+// Leaves redirects to the caller, so every hop can be checked before it's made
+fn manual_client() -> Result<&'static reqwest::Client, String> {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    if let Some(client) = CLIENT.get() {
+        return Ok(client);
+    }
+    let client = build_client(Policy::none())?;
+    Ok(CLIENT.get_or_init(|| client))
+}
+
+// `webcal://` is just an http(s) URL that calendar apps claim
+pub fn normalize_url(url: &str) -> String {
+    let url = url.trim();
+    match url.strip_prefix("webcal://") {
+        Some(rest) => format!("https://{}", rest),
+        None => url.to_string(),
+    }
+}
+
+pub fn is_remote(source: &str) -> bool {
+    let source = source.trim().to_lowercase();
+    ["http://", "https://", "webcal://"]
+        .iter()
+        .any(|scheme| source.starts_with(scheme))
+}
+
+pub async fn get_text(url: &str) -> Result<String, String> {
+    let url = normalize_url(url);
+    let response = client()?
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to fetch {}: {}", url, response.status()));
+    }
+    if response
+        .content_length()
+        .is_some_and(|length| length as usize > MAX_BODY_BYTES)
+    {
+        return Err(format!("{} is too large to download", url));
+    }
+
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", url, e))?;
+    if bytes.len() > MAX_BODY_BYTES {
+        return Err(format!("{} is too large to download", url));
+    }
+    Ok(String::from_utf8_lossy(&bytes).to_string())
+}
+
+// The start of a response, after redirects
+pub struct Prefix {
+    pub url: String,
+    pub content_type: Option<String>,
+    pub bytes: Vec<u8>,
+}
+
+// The start of a page, for when only the head matters. Reading stops at
+// `limit` bytes or as soon as `done` says enough arrived. Redirects are
+// followed only to http(s) URLs that `allow` accepts, the first one included.
+pub async fn get_prefix(
+    url: &str,
+    limit: usize,
+    timeout: Duration,
+    allow: impl Fn(&Url) -> bool,
+    done: impl Fn(&[u8]) -> bool,
+) -> Result<Prefix, String> {
+    let mut target = Url::parse(url).map_err(|e| format!("Invalid URL {}: {}", url, e))?;
+    let mut redirects = 0;
+    let mut response = loop {
+        if !matches!(target.scheme(), "http" | "https") || !allow(&target) {
+            return Err(format!("Not fetching {}, which isn't allowed", target));
+        }
+        let response = manual_client()?
+            .get(target.clone())
+            .timeout(timeout)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+        if !response.status().is_redirection() {
+            break response;
+        }
+        redirects += 1;
+        if redirects > MAX_REDIRECTS {
+            return Err(format!("{} redirects too many times", url));
+        }
+        let Some(location) = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|value| value.to_str().ok())
+        else {
+            break response;
+        };
+        target = target
+            .join(location)
+            .map_err(|e| format!("{} redirects to an invalid URL: {}", url, e))?;
+    };
+    if !response.status().is_success() {
+        return Err(format!("Failed to fetch {}: {}", url, response.status()));
+    }
+    let final_url = response.url().to_string();
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    let mut bytes = Vec::new();
+    while bytes.len() < limit && !done(&bytes) {
+        match response
+            .chunk()
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", url, e))?
+        {
+            Some(chunk) => bytes.extend_from_slice(&chunk),
+            None => break,
+        }
+    }
+    bytes.truncate(limit);
+    Ok(Prefix {
+        url: final_url,
+        content_type,
+        bytes,
+    })
+}
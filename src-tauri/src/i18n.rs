@@ -0,0 +1,729 @@
+use std::fmt::Display;
+use std::sync::RwLock;
+
+// "system" follows the OS language; the rest are the translated catalogs
+pub const LOCALES: [&str; 5] = ["system", "en", "de", "es", "fr"];
+
+// Resolved language code, read by every `t` call
+static LANGUAGE: RwLock<&str> = RwLock::new("en");
+
+// Languages without a catalog fall back to English
+pub fn set_locale(locale: &str) {
+    let requested = if locale == "system" {
+        sys_locale::get_locale().unwrap_or_default()
+    } else {
+        locale.to_string()
+    };
+    let language = requested
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    let resolved = LOCALES
+        .iter()
+        .skip(1)
+        .find(|code| **code == language)
+        .copied()
+        .unwrap_or("en");
+
+    if let Ok(mut current) = LANGUAGE.write() {
+        *current = resolved;
+    }
+}
+
+pub fn language() -> &'static str {
+    LANGUAGE.read().map(|language| *language).unwrap_or("en")
+}
+
+fn catalog(language: &str) -> &'static [(&'static str, &'static str)] {
+    match language {
+        "de" => DE,
+        "es" => ES,
+        "fr" => FR,
+        _ => EN,
+    }
+}
+
+fn lookup(language: &str, key: &str) -> Option<&'static str> {
+    catalog(language)
+        .iter()
+        .find(|(entry, _)| *entry == key)
+        .map(|(_, text)| *text)
+}
+
+// Message for `key` in the current language; untranslated keys use English
+pub fn t(key: &str) -> String {
+    lookup(language(), key)
+        .or_else(|| lookup("en", key))
+        .unwrap_or(key)
+        .to_string()
+}
+
+// `t` with each `{}` replaced by the next argument, like `format!`
+pub fn tf(key: &str, args: &[&dyn Display]) -> String {
+    let template = t(key);
+    let mut args = args.iter();
+    let mut message = String::with_capacity(template.len());
+    let mut rest = template.as_str();
+
+    while let Some(at) = rest.find("{}") {
+        message.push_str(&rest[..at]);
+        if let Some(arg) = args.next() {
+            message.push_str(&arg.to_string());
+        }
+        rest = &rest[at + 2..];
+    }
+    message.push_str(rest);
+
+    message
+}
+
+const EN: &[(&str, &str)] = &[
+    // Menu
+    ("menu.file", "File"),
+    ("menu.edit", "Edit"),
+    ("menu.view", "View"),
+    ("menu.window", "Window"),
+    ("menu.plugins", "Plugins"),
+    ("menu.custom", "Custom"),
+    ("menu.help", "Help"),
+    ("menu.new_note", "New Note"),
+    ("menu.new_folder", "New Folder"),
+    ("menu.open_file", "Open File\u{2026}"),
+    ("menu.open_folder", "Open Folder\u{2026}"),
+    ("menu.pinned_notes", "Pinned Notes"),
+    ("menu.no_pinned_notes", "No Pinned Notes"),
+    ("menu.save", "Save"),
+    ("menu.export_note", "Export Note\u{2026}"),
+    ("menu.backup_workspace", "Backup Workspace\u{2026}"),
+    ("menu.find_in_notes", "Find in Notes\u{2026}"),
+    ("menu.command_palette", "Command Palette\u{2026}"),
+    ("menu.toggle_sidebar", "Toggle Sidebar"),
+    ("menu.editor_only", "Editor Only"),
+    ("menu.split_view", "Split View"),
+    ("menu.preview_only", "Preview Only"),
+    ("menu.focus_mode", "Focus Mode"),
+    ("menu.graph_view", "Graph View"),
+    ("menu.preferences", "Preferences\u{2026}"),
+    ("menu.keyboard_shortcuts", "Keyboard Shortcuts\u{2026}"),
+    // Names
+    ("error.name_empty", "Name cannot be empty"),
+    (
+        "error.name_invalid_characters",
+        "Name contains invalid characters",
+    ),
+    (
+        "error.name_trailing",
+        "Name cannot end with trailing spaces or dots",
+    ),
+    (
+        "error.name_separators",
+        "Name cannot contain path separators",
+    ),
+    ("error.name_reserved", "Name is a reserved system name"),
+    ("error.no_available_name", "Unable to find available name"),
+    // Paths
+    ("error.folder_missing", "Folder does not exist"),
+    ("error.not_a_directory", "Path is not a directory"),
+    ("error.parent_missing", "Parent folder does not exist"),
+    ("error.path_missing", "Path does not exist"),
+    ("error.source_missing", "Source path does not exist"),
+    (
+        "error.destination_missing",
+        "Destination folder does not exist",
+    ),
+    ("error.move_into_itself", "Cannot move a folder into itself"),
+    (
+        "error.vault_move",
+        "Entries can't be moved into or out of an encrypted vault",
+    ),
+    (
+        "error.vault_copy",
+        "Copying is not supported in encrypted vaults",
+    ),
+    (
+        "error.delete_needs_confirmation",
+        "Deleting this much needs confirmation; preview the delete first",
+    ),
+    // File operations
+    ("error.create_folder", "Failed to create folder: {}"),
+    ("error.create_file", "Failed to create file: {}"),
+    ("error.read_note", "Failed to read note: {}"),
+    ("error.save_note", "Failed to save note: {}"),
+    ("error.read_directory", "Failed to read directory: {}"),
+    ("error.read_entry", "Failed to read entry: {}"),
+    ("error.rename_entry", "Failed to rename entry: {}"),
+    ("error.move_entry", "Failed to move entry: {}"),
+    ("error.copy_directory", "Failed to copy directory: {}"),
+    ("error.copy_file", "Failed to copy file: {}"),
+    ("error.emit_event", "Failed to emit event: {}"),
+    (
+        "error.outside_storage",
+        "That location is outside Marky's storage",
+    ),
+    // Workspace features
+    ("error.no_workspace", "No workspace is open"),
+    ("error.read_file", "Failed to read file: {}"),
+    ("error.invalid_date", "Invalid date: {}"),
+    (
+        "error.range_reversed",
+        "Range start must not be after its end",
+    ),
+    ("error.range_too_large", "Range is too large"),
+    (
+        "error.writing_journal_lock",
+        "Failed to lock writing journal: {}",
+    ),
+    (
+        "error.activity_journal_lock",
+        "Failed to lock activity journal: {}",
+    ),
+    ("error.api_start", "Failed to start the API on port {}: {}"),
+    (
+        "error.share_offline",
+        "{} is offline; Marky will reconnect when the share is reachable",
+    ),
+    (
+        "error.share_slow",
+        "{} is responding slowly; try again in a moment",
+    ),
+    ("error.vault_state_lock", "Failed to lock vault state: {}"),
+    ("error.vault_locked", "Vault is locked"),
+    ("error.vault_derive_key", "Failed to derive key: {}"),
+    ("error.vault_encrypt", "Failed to encrypt"),
+    ("error.vault_decrypt", "Failed to decrypt vault file"),
+    (
+        "error.vault_name_too_long",
+        "Name is too long for an encrypted vault",
+    ),
+    ("error.vault_outside", "Path is not inside a vault"),
+    ("error.vault_invalid_path", "Invalid vault path"),
+    ("error.note_not_text", "Note is not valid text"),
+    ("error.vault_read", "Failed to read vault: {}"),
+    ("error.vault_header", "Invalid vault header"),
+    ("error.vault_version", "Unsupported vault version: {}"),
+    ("error.vault_passphrase", "Wrong vault passphrase"),
+    ("error.vault_nested", "Folder is already inside a vault"),
+    ("error.passphrase_empty", "Passphrase cannot be empty"),
+    (
+        "error.vault_create_folder",
+        "Failed to create vault folder: {}",
+    ),
+    ("error.vault_write_file", "Failed to write vault file: {}"),
+    (
+        "error.config_folder",
+        "Failed to create workspace config folder: {}",
+    ),
+    (
+        "error.vault_serialize",
+        "Failed to serialize vault header: {}",
+    ),
+    ("error.vault_write", "Failed to write vault: {}"),
+    ("error.not_a_vault", "Folder is not a vault"),
+    ("error.delete_file", "Failed to delete file: {}"),
+    ("error.delete_folder", "Failed to delete folder: {}"),
+    ("error.smtp_no_server", "No SMTP server is configured"),
+    ("error.smtp_no_sender", "No sender address is configured"),
+    (
+        "error.smtp_unencrypted",
+        "Refusing to send the SMTP password over an unencrypted connection",
+    ),
+    ("error.smtp_no_password", "No SMTP password is stored"),
+    ("error.smtp_refused", "{} was refused: {}"),
+    ("error.smtp_invalid_sender", "Invalid sender address: {}"),
+    // Notifications
+    ("notification.note_changed_title", "Note changed on disk"),
+    ("notification.reminder_title", "Reminder"),
+    ("notification.conflict_title", "Conflicted copy saved"),
+    (
+        "notification.conflict_body",
+        "Both versions of \u{201c}{}\u{201d} were kept; the other one is in a conflicted copy",
+    ),
+    (
+        "notification.note_changed_body",
+        "\u{201c}{}\u{201d} was modified outside Marky",
+    ),
+];
+
+const DE: &[(&str, &str)] = &[
+    ("menu.file", "Ablage"),
+    ("menu.edit", "Bearbeiten"),
+    ("menu.view", "Darstellung"),
+    ("menu.window", "Fenster"),
+    ("menu.plugins", "Plugins"),
+    ("menu.custom", "Eigene"),
+    ("menu.help", "Hilfe"),
+    ("menu.new_note", "Neue Notiz"),
+    ("menu.new_folder", "Neuer Ordner"),
+    ("menu.open_file", "Datei öffnen\u{2026}"),
+    ("menu.open_folder", "Ordner öffnen\u{2026}"),
+    ("menu.pinned_notes", "Angeheftete Notizen"),
+    ("menu.no_pinned_notes", "Keine angehefteten Notizen"),
+    ("menu.save", "Sichern"),
+    ("menu.export_note", "Notiz exportieren\u{2026}"),
+    ("menu.backup_workspace", "Arbeitsbereich sichern\u{2026}"),
+    ("menu.find_in_notes", "In Notizen suchen\u{2026}"),
+    ("menu.command_palette", "Befehlspalette\u{2026}"),
+    ("menu.toggle_sidebar", "Seitenleiste ein-/ausblenden"),
+    ("menu.editor_only", "Nur Editor"),
+    ("menu.split_view", "Geteilte Ansicht"),
+    ("menu.preview_only", "Nur Vorschau"),
+    ("menu.focus_mode", "Fokusmodus"),
+    ("menu.graph_view", "Graphansicht"),
+    ("menu.preferences", "Einstellungen\u{2026}"),
+    ("menu.keyboard_shortcuts", "Tastaturkurzbefehle\u{2026}"),
+    ("error.name_empty", "Der Name darf nicht leer sein"),
+    (
+        "error.name_invalid_characters",
+        "Der Name enthält ungültige Zeichen",
+    ),
+    (
+        "error.name_trailing",
+        "Der Name darf nicht mit Leerzeichen oder Punkten enden",
+    ),
+    (
+        "error.name_separators",
+        "Der Name darf keine Pfadtrennzeichen enthalten",
+    ),
+    (
+        "error.name_reserved",
+        "Der Name ist ein reservierter Systemname",
+    ),
+    (
+        "error.no_available_name",
+        "Es wurde kein freier Name gefunden",
+    ),
+    ("error.folder_missing", "Der Ordner existiert nicht"),
+    ("error.not_a_directory", "Der Pfad ist kein Ordner"),
+    (
+        "error.parent_missing",
+        "Der übergeordnete Ordner existiert nicht",
+    ),
+    ("error.path_missing", "Der Pfad existiert nicht"),
+    ("error.source_missing", "Der Quellpfad existiert nicht"),
+    ("error.destination_missing", "Der Zielordner existiert nicht"),
+    (
+        "error.move_into_itself",
+        "Ein Ordner kann nicht in sich selbst verschoben werden",
+    ),
+    (
+        "error.vault_move",
+        "Einträge können nicht in einen verschlüsselten Tresor hinein oder aus ihm heraus verschoben werden",
+    ),
+    (
+        "error.vault_copy",
+        "Kopieren wird in verschlüsselten Tresoren nicht unterstützt",
+    ),
+    (
+        "error.delete_needs_confirmation",
+        "So viel zu löschen muss bestätigt werden; zuerst eine Vorschau anzeigen",
+    ),
+    (
+        "error.create_folder",
+        "Ordner konnte nicht erstellt werden: {}",
+    ),
+    ("error.create_file", "Datei konnte nicht erstellt werden: {}"),
+    ("error.read_note", "Notiz konnte nicht gelesen werden: {}"),
+    ("error.save_note", "Notiz konnte nicht gespeichert werden: {}"),
+    (
+        "error.read_directory",
+        "Ordner konnte nicht gelesen werden: {}",
+    ),
+    ("error.read_entry", "Eintrag konnte nicht gelesen werden: {}"),
+    (
+        "error.rename_entry",
+        "Eintrag konnte nicht umbenannt werden: {}",
+    ),
+    (
+        "error.move_entry",
+        "Eintrag konnte nicht verschoben werden: {}",
+    ),
+    (
+        "error.copy_directory",
+        "Ordner konnte nicht kopiert werden: {}",
+    ),
+    ("error.copy_file", "Datei konnte nicht kopiert werden: {}"),
+    (
+        "error.emit_event",
+        "Ereignis konnte nicht gesendet werden: {}",
+    ),
+    (
+        "error.outside_storage",
+        "Dieser Ort liegt außerhalb des Speichers von Marky",
+    ),
+    ("error.no_workspace", "Es ist kein Arbeitsbereich geöffnet"),
+    ("error.read_file", "Datei konnte nicht gelesen werden: {}"),
+    ("error.invalid_date", "Ungültiges Datum: {}"),
+    ("error.range_reversed", "Der Beginn des Zeitraums darf nicht nach seinem Ende liegen"),
+    ("error.range_too_large", "Der Zeitraum ist zu groß"),
+    ("error.writing_journal_lock", "Schreibjournal konnte nicht gesperrt werden: {}"),
+    ("error.activity_journal_lock", "Aktivitätsjournal konnte nicht gesperrt werden: {}"),
+    ("error.api_start", "Die API konnte auf Port {} nicht gestartet werden: {}"),
+    ("error.share_offline", "{} ist offline; Marky verbindet sich erneut, sobald die Freigabe erreichbar ist"),
+    ("error.share_slow", "{} antwortet langsam; versuche es gleich noch einmal"),
+    ("error.vault_state_lock", "Tresorstatus konnte nicht gesperrt werden: {}"),
+    ("error.vault_locked", "Der Tresor ist gesperrt"),
+    ("error.vault_derive_key", "Schlüssel konnte nicht abgeleitet werden: {}"),
+    ("error.vault_encrypt", "Verschlüsseln fehlgeschlagen"),
+    ("error.vault_decrypt", "Tresordatei konnte nicht entschlüsselt werden"),
+    ("error.vault_name_too_long", "Der Name ist für einen verschlüsselten Tresor zu lang"),
+    ("error.vault_outside", "Der Pfad liegt nicht in einem Tresor"),
+    ("error.vault_invalid_path", "Ungültiger Tresorpfad"),
+    ("error.note_not_text", "Die Notiz ist kein gültiger Text"),
+    ("error.vault_read", "Tresor konnte nicht gelesen werden: {}"),
+    ("error.vault_header", "Ungültiger Tresorkopf"),
+    ("error.vault_version", "Nicht unterstützte Tresorversion: {}"),
+    ("error.vault_passphrase", "Falsche Tresor-Passphrase"),
+    ("error.vault_nested", "Der Ordner liegt bereits in einem Tresor"),
+    ("error.passphrase_empty", "Die Passphrase darf nicht leer sein"),
+    ("error.vault_create_folder", "Tresorordner konnte nicht erstellt werden: {}"),
+    ("error.vault_write_file", "Tresordatei konnte nicht geschrieben werden: {}"),
+    ("error.config_folder", "Konfigurationsordner des Arbeitsbereichs konnte nicht erstellt werden: {}"),
+    ("error.vault_serialize", "Tresorkopf konnte nicht serialisiert werden: {}"),
+    ("error.vault_write", "Tresor konnte nicht geschrieben werden: {}"),
+    ("error.not_a_vault", "Der Ordner ist kein Tresor"),
+    ("error.delete_file", "Datei konnte nicht gelöscht werden: {}"),
+    ("error.delete_folder", "Ordner konnte nicht gelöscht werden: {}"),
+    ("error.smtp_no_server", "Es ist kein SMTP-Server eingerichtet"),
+    ("error.smtp_no_sender", "Es ist keine Absenderadresse eingerichtet"),
+    ("error.smtp_unencrypted", "Das SMTP-Passwort wird nicht über eine unverschlüsselte Verbindung gesendet"),
+    ("error.smtp_no_password", "Es ist kein SMTP-Passwort gespeichert"),
+    ("error.smtp_refused", "{} wurde abgelehnt: {}"),
+    ("error.smtp_invalid_sender", "Ungültige Absenderadresse: {}"),
+    (
+        "notification.note_changed_title",
+        "Notiz auf dem Datenträger geändert",
+    ),
+    ("notification.reminder_title", "Erinnerung"),
+    ("notification.conflict_title", "Konfliktkopie gespeichert"),
+    (
+        "notification.conflict_body",
+        "Beide Fassungen von \u{201e}{}\u{201c} wurden behalten; die andere liegt in einer Konfliktkopie",
+    ),
+    (
+        "notification.note_changed_body",
+        "\u{201e}{}\u{201c} wurde außerhalb von Marky geändert",
+    ),
+];
+
+const ES: &[(&str, &str)] = &[
+    ("menu.file", "Archivo"),
+    ("menu.edit", "Edición"),
+    ("menu.view", "Visualización"),
+    ("menu.window", "Ventana"),
+    ("menu.plugins", "Complementos"),
+    ("menu.custom", "Personalizado"),
+    ("menu.help", "Ayuda"),
+    ("menu.new_note", "Nueva nota"),
+    ("menu.new_folder", "Nueva carpeta"),
+    ("menu.open_file", "Abrir archivo\u{2026}"),
+    ("menu.open_folder", "Abrir carpeta\u{2026}"),
+    ("menu.pinned_notes", "Notas fijadas"),
+    ("menu.no_pinned_notes", "No hay notas fijadas"),
+    ("menu.save", "Guardar"),
+    ("menu.export_note", "Exportar nota\u{2026}"),
+    (
+        "menu.backup_workspace",
+        "Copia de seguridad del espacio de trabajo\u{2026}",
+    ),
+    ("menu.find_in_notes", "Buscar en las notas\u{2026}"),
+    ("menu.command_palette", "Paleta de comandos\u{2026}"),
+    ("menu.toggle_sidebar", "Mostrar u ocultar barra lateral"),
+    ("menu.editor_only", "Solo editor"),
+    ("menu.split_view", "Vista dividida"),
+    ("menu.preview_only", "Solo vista previa"),
+    ("menu.focus_mode", "Modo concentración"),
+    ("menu.graph_view", "Vista de grafo"),
+    ("menu.preferences", "Preferencias\u{2026}"),
+    ("menu.keyboard_shortcuts", "Atajos de teclado\u{2026}"),
+    ("error.name_empty", "El nombre no puede estar vacío"),
+    (
+        "error.name_invalid_characters",
+        "El nombre contiene caracteres no válidos",
+    ),
+    (
+        "error.name_trailing",
+        "El nombre no puede terminar en espacios o puntos",
+    ),
+    (
+        "error.name_separators",
+        "El nombre no puede contener separadores de ruta",
+    ),
+    (
+        "error.name_reserved",
+        "El nombre está reservado por el sistema",
+    ),
+    (
+        "error.no_available_name",
+        "No se encontró un nombre disponible",
+    ),
+    ("error.folder_missing", "La carpeta no existe"),
+    ("error.not_a_directory", "La ruta no es una carpeta"),
+    ("error.parent_missing", "La carpeta superior no existe"),
+    ("error.path_missing", "La ruta no existe"),
+    ("error.source_missing", "La ruta de origen no existe"),
+    (
+        "error.destination_missing",
+        "La carpeta de destino no existe",
+    ),
+    (
+        "error.move_into_itself",
+        "No se puede mover una carpeta dentro de sí misma",
+    ),
+    (
+        "error.vault_move",
+        "No se pueden mover elementos hacia dentro o fuera de una bóveda cifrada",
+    ),
+    (
+        "error.vault_copy",
+        "No se admite copiar en bóvedas cifradas",
+    ),
+    (
+        "error.delete_needs_confirmation",
+        "Eliminar tanto requiere confirmación; revisa primero la vista previa",
+    ),
+    ("error.create_folder", "No se pudo crear la carpeta: {}"),
+    ("error.create_file", "No se pudo crear el archivo: {}"),
+    ("error.read_note", "No se pudo leer la nota: {}"),
+    ("error.save_note", "No se pudo guardar la nota: {}"),
+    ("error.read_directory", "No se pudo leer la carpeta: {}"),
+    ("error.read_entry", "No se pudo leer el elemento: {}"),
+    (
+        "error.rename_entry",
+        "No se pudo cambiar el nombre del elemento: {}",
+    ),
+    ("error.move_entry", "No se pudo mover el elemento: {}"),
+    ("error.copy_directory", "No se pudo copiar la carpeta: {}"),
+    ("error.copy_file", "No se pudo copiar el archivo: {}"),
+    ("error.emit_event", "No se pudo enviar el evento: {}"),
+    (
+        "error.outside_storage",
+        "Esa ubicación está fuera del almacenamiento de Marky",
+    ),
+    ("error.no_workspace", "No hay ningún espacio de trabajo abierto"),
+    ("error.read_file", "No se pudo leer el archivo: {}"),
+    ("error.invalid_date", "Fecha no válida: {}"),
+    ("error.range_reversed", "El inicio del intervalo no puede ser posterior a su final"),
+    ("error.range_too_large", "El intervalo es demasiado grande"),
+    ("error.writing_journal_lock", "No se pudo bloquear el diario de escritura: {}"),
+    ("error.activity_journal_lock", "No se pudo bloquear el diario de actividad: {}"),
+    ("error.api_start", "No se pudo iniciar la API en el puerto {}: {}"),
+    ("error.share_offline", "{} está sin conexión; Marky volverá a conectarse cuando el recurso compartido esté disponible"),
+    ("error.share_slow", "{} responde con lentitud; inténtalo de nuevo en un momento"),
+    ("error.vault_state_lock", "No se pudo bloquear el estado de la bóveda: {}"),
+    ("error.vault_locked", "La bóveda está bloqueada"),
+    ("error.vault_derive_key", "No se pudo derivar la clave: {}"),
+    ("error.vault_encrypt", "No se pudo cifrar"),
+    ("error.vault_decrypt", "No se pudo descifrar el archivo de la bóveda"),
+    ("error.vault_name_too_long", "El nombre es demasiado largo para una bóveda cifrada"),
+    ("error.vault_outside", "La ruta no está dentro de una bóveda"),
+    ("error.vault_invalid_path", "Ruta de bóveda no válida"),
+    ("error.note_not_text", "La nota no es texto válido"),
+    ("error.vault_read", "No se pudo leer la bóveda: {}"),
+    ("error.vault_header", "Encabezado de bóveda no válido"),
+    ("error.vault_version", "Versión de bóveda no admitida: {}"),
+    ("error.vault_passphrase", "Frase de contraseña de la bóveda incorrecta"),
+    ("error.vault_nested", "La carpeta ya está dentro de una bóveda"),
+    ("error.passphrase_empty", "La frase de contraseña no puede estar vacía"),
+    ("error.vault_create_folder", "No se pudo crear la carpeta de la bóveda: {}"),
+    ("error.vault_write_file", "No se pudo escribir el archivo de la bóveda: {}"),
+    ("error.config_folder", "No se pudo crear la carpeta de configuración del espacio de trabajo: {}"),
+    ("error.vault_serialize", "No se pudo serializar el encabezado de la bóveda: {}"),
+    ("error.vault_write", "No se pudo escribir la bóveda: {}"),
+    ("error.not_a_vault", "La carpeta no es una bóveda"),
+    ("error.delete_file", "No se pudo eliminar el archivo: {}"),
+    ("error.delete_folder", "No se pudo eliminar la carpeta: {}"),
+    ("error.smtp_no_server", "No hay ningún servidor SMTP configurado"),
+    ("error.smtp_no_sender", "No hay ninguna dirección de remitente configurada"),
+    ("error.smtp_unencrypted", "No se enviará la contraseña SMTP por una conexión sin cifrar"),
+    ("error.smtp_no_password", "No hay ninguna contraseña SMTP guardada"),
+    ("error.smtp_refused", "{} fue rechazado: {}"),
+    ("error.smtp_invalid_sender", "Dirección de remitente no válida: {}"),
+    (
+        "notification.note_changed_title",
+        "Nota modificada en el disco",
+    ),
+    ("notification.reminder_title", "Recordatorio"),
+    ("notification.conflict_title", "Copia en conflicto guardada"),
+    (
+        "notification.conflict_body",
+        "Se conservaron ambas versiones de \u{201c}{}\u{201d}; la otra está en una copia en conflicto",
+    ),
+    (
+        "notification.note_changed_body",
+        "\u{201c}{}\u{201d} se modificó fuera de Marky",
+    ),
+];
+
+const FR: &[(&str, &str)] = &[
+    ("menu.file", "Fichier"),
+    ("menu.edit", "Édition"),
+    ("menu.view", "Présentation"),
+    ("menu.window", "Fenêtre"),
+    ("menu.plugins", "Extensions"),
+    ("menu.custom", "Personnalisé"),
+    ("menu.help", "Aide"),
+    ("menu.new_note", "Nouvelle note"),
+    ("menu.new_folder", "Nouveau dossier"),
+    ("menu.open_file", "Ouvrir un fichier\u{2026}"),
+    ("menu.open_folder", "Ouvrir un dossier\u{2026}"),
+    ("menu.pinned_notes", "Notes épinglées"),
+    ("menu.no_pinned_notes", "Aucune note épinglée"),
+    ("menu.save", "Enregistrer"),
+    ("menu.export_note", "Exporter la note\u{2026}"),
+    (
+        "menu.backup_workspace",
+        "Sauvegarder l\u{2019}espace de travail\u{2026}",
+    ),
+    ("menu.find_in_notes", "Rechercher dans les notes\u{2026}"),
+    ("menu.command_palette", "Palette de commandes\u{2026}"),
+    (
+        "menu.toggle_sidebar",
+        "Afficher ou masquer la barre latérale",
+    ),
+    ("menu.editor_only", "Éditeur seul"),
+    ("menu.split_view", "Vue partagée"),
+    ("menu.preview_only", "Aperçu seul"),
+    ("menu.focus_mode", "Mode concentration"),
+    ("menu.graph_view", "Vue en graphe"),
+    ("menu.preferences", "Préférences\u{2026}"),
+    ("menu.keyboard_shortcuts", "Raccourcis clavier\u{2026}"),
+    ("error.name_empty", "Le nom ne peut pas être vide"),
+    (
+        "error.name_invalid_characters",
+        "Le nom contient des caractères non valides",
+    ),
+    (
+        "error.name_trailing",
+        "Le nom ne peut pas se terminer par des espaces ou des points",
+    ),
+    (
+        "error.name_separators",
+        "Le nom ne peut pas contenir de séparateurs de chemin",
+    ),
+    ("error.name_reserved", "Ce nom est réservé par le système"),
+    (
+        "error.no_available_name",
+        "Impossible de trouver un nom disponible",
+    ),
+    ("error.folder_missing", "Le dossier n\u{2019}existe pas"),
+    (
+        "error.not_a_directory",
+        "Le chemin n\u{2019}est pas un dossier",
+    ),
+    (
+        "error.parent_missing",
+        "Le dossier parent n\u{2019}existe pas",
+    ),
+    ("error.path_missing", "Le chemin n\u{2019}existe pas"),
+    (
+        "error.source_missing",
+        "Le chemin source n\u{2019}existe pas",
+    ),
+    (
+        "error.destination_missing",
+        "Le dossier de destination n\u{2019}existe pas",
+    ),
+    (
+        "error.move_into_itself",
+        "Impossible de déplacer un dossier dans lui-même",
+    ),
+    (
+        "error.vault_move",
+        "Les éléments ne peuvent pas entrer dans un coffre chiffré ni en sortir",
+    ),
+    (
+        "error.vault_copy",
+        "La copie n\u{2019}est pas prise en charge dans les coffres chiffrés",
+    ),
+    (
+        "error.delete_needs_confirmation",
+        "Une suppression de cette taille doit être confirmée\u{a0}; affichez d\u{2019}abord l\u{2019}aperçu",
+    ),
+    ("error.create_folder", "Impossible de créer le dossier : {}"),
+    ("error.create_file", "Impossible de créer le fichier : {}"),
+    ("error.read_note", "Impossible de lire la note : {}"),
+    (
+        "error.save_note",
+        "Impossible d\u{2019}enregistrer la note : {}",
+    ),
+    ("error.read_directory", "Impossible de lire le dossier : {}"),
+    (
+        "error.read_entry",
+        "Impossible de lire l\u{2019}élément : {}",
+    ),
+    (
+        "error.rename_entry",
+        "Impossible de renommer l\u{2019}élément : {}",
+    ),
+    (
+        "error.move_entry",
+        "Impossible de déplacer l\u{2019}élément : {}",
+    ),
+    (
+        "error.copy_directory",
+        "Impossible de copier le dossier : {}",
+    ),
+    ("error.copy_file", "Impossible de copier le fichier : {}"),
+    (
+        "error.emit_event",
+        "Impossible d\u{2019}envoyer l\u{2019}événement : {}",
+    ),
+    (
+        "error.outside_storage",
+        "Cet emplacement est en dehors du stockage de Marky",
+    ),
+    ("error.no_workspace", "Aucun espace de travail n\u{2019}est ouvert"),
+    ("error.read_file", "Impossible de lire le fichier : {}"),
+    ("error.invalid_date", "Date non valide : {}"),
+    ("error.range_reversed", "Le début de la période ne peut pas être après sa fin"),
+    ("error.range_too_large", "La période est trop longue"),
+    ("error.writing_journal_lock", "Impossible de verrouiller le journal d\u{2019}écriture : {}"),
+    ("error.activity_journal_lock", "Impossible de verrouiller le journal d\u{2019}activité : {}"),
+    ("error.api_start", "Impossible de démarrer l\u{2019}API sur le port {} : {}"),
+    ("error.share_offline", "{} est hors ligne ; Marky se reconnectera lorsque le partage sera accessible"),
+    ("error.share_slow", "{} répond lentement ; réessayez dans un instant"),
+    ("error.vault_state_lock", "Impossible de verrouiller l\u{2019}état du coffre : {}"),
+    ("error.vault_locked", "Le coffre est verrouillé"),
+    ("error.vault_derive_key", "Impossible de dériver la clé : {}"),
+    ("error.vault_encrypt", "Échec du chiffrement"),
+    ("error.vault_decrypt", "Impossible de déchiffrer le fichier du coffre"),
+    ("error.vault_name_too_long", "Le nom est trop long pour un coffre chiffré"),
+    ("error.vault_outside", "Le chemin n\u{2019}est pas dans un coffre"),
+    ("error.vault_invalid_path", "Chemin de coffre non valide"),
+    ("error.note_not_text", "La note n\u{2019}est pas du texte valide"),
+    ("error.vault_read", "Impossible de lire le coffre : {}"),
+    ("error.vault_header", "En-tête de coffre non valide"),
+    ("error.vault_version", "Version de coffre non prise en charge : {}"),
+    ("error.vault_passphrase", "Phrase secrète du coffre incorrecte"),
+    ("error.vault_nested", "Le dossier est déjà dans un coffre"),
+    ("error.passphrase_empty", "La phrase secrète ne peut pas être vide"),
+    ("error.vault_create_folder", "Impossible de créer le dossier du coffre : {}"),
+    ("error.vault_write_file", "Impossible d\u{2019}écrire le fichier du coffre : {}"),
+    ("error.config_folder", "Impossible de créer le dossier de configuration de l\u{2019}espace de travail : {}"),
+    ("error.vault_serialize", "Impossible de sérialiser l\u{2019}en-tête du coffre : {}"),
+    ("error.vault_write", "Impossible d\u{2019}écrire le coffre : {}"),
+    ("error.not_a_vault", "Le dossier n\u{2019}est pas un coffre"),
+    ("error.delete_file", "Impossible de supprimer le fichier : {}"),
+    ("error.delete_folder", "Impossible de supprimer le dossier : {}"),
+    ("error.smtp_no_server", "Aucun serveur SMTP n\u{2019}est configuré"),
+    ("error.smtp_no_sender", "Aucune adresse d\u{2019}expéditeur n\u{2019}est configurée"),
+    ("error.smtp_unencrypted", "Le mot de passe SMTP ne sera pas envoyé sur une connexion non chiffrée"),
+    ("error.smtp_no_password", "Aucun mot de passe SMTP n\u{2019}est enregistré"),
+    ("error.smtp_refused", "{} a été refusé : {}"),
+    ("error.smtp_invalid_sender", "Adresse d\u{2019}expéditeur invalide : {}"),
+    (
+        "notification.note_changed_title",
+        "Note modifiée sur le disque",
+    ),
+    ("notification.reminder_title", "Rappel"),
+    ("notification.conflict_title", "Copie en conflit enregistrée"),
+    (
+        "notification.conflict_body",
+        "Les deux versions de \u{ab}\u{a0}{}\u{a0}\u{bb} ont été conservées\u{a0}; l’autre est dans une copie en conflit",
+    ),
+    (
+        "notification.note_changed_body",
+        "\u{ab}\u{a0}{}\u{a0}\u{bb} a été modifiée en dehors de Marky",
+    ),
+];
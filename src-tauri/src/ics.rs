@@ -0,0 +1,737 @@
+use crate::disk_space;
+use crate::http;
+use crate::index::IndexState;
+use crate::markdown;
+use crate::settings::SettingsState;
+use crate::workspace_config;
+use chrono::{
+    DateTime, Datelike, Duration as ChronoDuration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc,
+    Weekday,
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::State;
+
+const EVENT_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+// Template stems, compared case-insensitively; the first one found wins
+const MEETING_TEMPLATE_NAMES: &[&str] = &["meeting", "meeting notes", "meeting note"];
+const DEFAULT_MEETING_TEMPLATE: &str = "# {{title}}
+
+**Date:** {{date}}
+**Time:** {{time}}
+**Location:** {{location}}
+**Attendees:** {{attendees}}
+
+## Agenda
+-
+
+## Discussion
+
+
+## Action Items
+- [ ]
+
+## Next Steps
+
+";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CalendarEvent {
+    // UID plus the occurrence date, since recurring events share a UID
+    id: String,
+    title: String,
+    // Local `YYYY-MM-DDTHH:MM`, or `YYYY-MM-DD` for all-day events
+    start: String,
+    end: Option<String>,
+    all_day: bool,
+    location: Option<String>,
+    description: Option<String>,
+    attendees: Vec<String>,
+    source: String,
+}
+
+// What `list_todays_events` last returned, so a note can be made from one of them
+#[derive(Default)]
+pub struct IcsState {
+    events: Mutex<Vec<CalendarEvent>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[derive(Debug, Clone)]
+struct Recurrence {
+    frequency: Frequency,
+    interval: i64,
+    until: Option<NaiveDate>,
+    count: Option<usize>,
+    by_day: Vec<Weekday>,
+}
+
+#[derive(Debug, Default)]
+struct RawEvent {
+    uid: String,
+    summary: Option<String>,
+    start: Option<EventTime>,
+    end: Option<EventTime>,
+    location: Option<String>,
+    description: Option<String>,
+    attendees: Vec<String>,
+    rule: Option<Recurrence>,
+    exdates: Vec<NaiveDate>,
+    // Set on an edited occurrence of a recurring event
+    recurrence_id: Option<NaiveDate>,
+    cancelled: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum EventTime {
+    Date(NaiveDate),
+    DateTime(NaiveDateTime),
+}
+
+impl EventTime {
+    fn date(&self) -> NaiveDate {
+        match self {
+            EventTime::Date(date) => *date,
+            EventTime::DateTime(time) => time.date(),
+        }
+    }
+}
+
+struct Property<'a> {
+    name: String,
+    params: Vec<(String, &'a str)>,
+    value: &'a str,
+}
+
+impl Property<'_> {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.trim_matches('"'))
+    }
+}
+
+// Long lines are folded onto continuation lines that start with whitespace
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+// `NAME;PARAM=value;PARAM="quoted:value":VALUE`
+fn parse_property(line: &str) -> Option<Property<'_>> {
+    let mut in_quotes = false;
+    let colon = line.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            in_quotes = !in_quotes;
+            None
+        }
+        ':' if !in_quotes => Some(i),
+        _ => None,
+    })?;
+
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_uppercase();
+    let params = parts
+        .filter_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            Some((key.trim().to_uppercase(), value))
+        })
+        .collect();
+
+    Some(Property {
+        name,
+        params,
+        value,
+    })
+}
+
+fn unescape(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => text.push('\n'),
+            Some(other) => text.push(other),
+            None => {}
+        }
+    }
+    text.trim().to_string()
+}
+
+// There's no timezone database here, so TZID times are taken as local time;
+// UTC times (with a trailing `Z`) are converted properly
+fn parse_time(property: &Property) -> Option<EventTime> {
+    let value = property.value.trim();
+    if property.param("VALUE") == Some("DATE") || value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .map(EventTime::Date);
+    }
+
+    if let Some(utc) = value.strip_suffix('Z') {
+        let time = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        let local = DateTime::<Local>::from(Utc.from_utc_datetime(&time));
+        return Some(EventTime::DateTime(local.naive_local()));
+    }
+
+    NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+        .ok()
+        .map(EventTime::DateTime)
+}
+
+fn parse_weekday(code: &str) -> Option<Weekday> {
+    // Ordinals like `2TU` (second Tuesday) only make sense monthly and aren't supported
+    match code.trim() {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn parse_rule(value: &str) -> Option<Recurrence> {
+    let mut rule = Recurrence {
+        frequency: Frequency::Daily,
+        interval: 1,
+        until: None,
+        count: None,
+        by_day: Vec::new(),
+    };
+    let mut frequency = None;
+
+    for part in value.split(';') {
+        let Some((key, value)) = part.split_once('=') else {
+            continue;
+        };
+        match key.trim().to_uppercase().as_str() {
+            "FREQ" => {
+                frequency = match value.trim().to_uppercase().as_str() {
+                    "DAILY" => Some(Frequency::Daily),
+                    "WEEKLY" => Some(Frequency::Weekly),
+                    "MONTHLY" => Some(Frequency::Monthly),
+                    "YEARLY" => Some(Frequency::Yearly),
+                    _ => None,
+                }
+            }
+            "INTERVAL" => rule.interval = value.trim().parse().unwrap_or(1).max(1),
+            "COUNT" => rule.count = value.trim().parse().ok(),
+            "UNTIL" => {
+                rule.until = value
+                    .get(..8)
+                    .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+            }
+            "BYDAY" => rule.by_day = value.split(',').filter_map(parse_weekday).collect(),
+            _ => {}
+        }
+    }
+
+    rule.frequency = frequency?;
+    Some(rule)
+}
+
+fn attendee_name(property: &Property) -> Option<String> {
+    if let Some(name) = property.param("CN").filter(|name| !name.trim().is_empty()) {
+        return Some(name.trim().to_string());
+    }
+    let value = property.value.trim();
+    let address = value
+        .strip_prefix("mailto:")
+        .or_else(|| value.strip_prefix("MAILTO:"))
+        .unwrap_or(value);
+    (!address.is_empty()).then(|| address.to_string())
+}
+
+fn parse_events(text: &str) -> Vec<RawEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<RawEvent> = None;
+    // Alarms and other components nested in an event have their own properties
+    let mut nested = 0usize;
+
+    for line in unfold(text) {
+        let Some(property) = parse_property(&line) else {
+            continue;
+        };
+        let value = property.value.trim();
+
+        match property.name.as_str() {
+            "BEGIN" if value.eq_ignore_ascii_case("VEVENT") => {
+                current = Some(RawEvent::default());
+                nested = 0;
+                continue;
+            }
+            "END" if value.eq_ignore_ascii_case("VEVENT") => {
+                events.extend(current.take());
+                continue;
+            }
+            "BEGIN" if current.is_some() => nested += 1,
+            "END" if current.is_some() => nested = nested.saturating_sub(1),
+            _ => {}
+        }
+
+        let Some(event) = current.as_mut().filter(|_| nested == 0) else {
+            continue;
+        };
+        match property.name.as_str() {
+            "UID" => event.uid = value.to_string(),
+            "SUMMARY" => event.summary = Some(unescape(value)),
+            "LOCATION" => event.location = Some(unescape(value)).filter(|l| !l.is_empty()),
+            "DESCRIPTION" => event.description = Some(unescape(value)).filter(|d| !d.is_empty()),
+            "DTSTART" => event.start = parse_time(&property),
+            "DTEND" => event.end = parse_time(&property),
+            "RRULE" => event.rule = parse_rule(value),
+            "EXDATE" => event.exdates.extend(value.split(',').filter_map(|date| {
+                parse_time(&Property {
+                    name: property.name.clone(),
+                    params: property.params.clone(),
+                    value: date,
+                })
+                .map(|time| time.date())
+            })),
+            "RECURRENCE-ID" => event.recurrence_id = parse_time(&property).map(|t| t.date()),
+            "STATUS" => event.cancelled = value.eq_ignore_ascii_case("CANCELLED"),
+            "ATTENDEE" => event.attendees.extend(attendee_name(&property)),
+            _ => {}
+        }
+    }
+
+    events
+}
+
+fn months_between(from: NaiveDate, to: NaiveDate) -> i64 {
+    (to.year() as i64 - from.year() as i64) * 12 + to.month() as i64 - from.month() as i64
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+// Whether the rule alone, ignoring UNTIL and COUNT, puts an occurrence on `date`
+fn matches_rule(rule: &Recurrence, start: NaiveDate, date: NaiveDate) -> bool {
+    if date < start {
+        return false;
+    }
+    match rule.frequency {
+        Frequency::Daily => (date - start).num_days() % rule.interval == 0,
+        Frequency::Weekly => {
+            let weeks = (week_start(date) - week_start(start)).num_days() / 7;
+            let on_day = if rule.by_day.is_empty() {
+                date.weekday() == start.weekday()
+            } else {
+                rule.by_day.contains(&date.weekday())
+            };
+            on_day && weeks % rule.interval == 0
+        }
+        Frequency::Monthly => {
+            date.day() == start.day() && months_between(start, date) % rule.interval == 0
+        }
+        Frequency::Yearly => {
+            date.day() == start.day()
+                && date.month() == start.month()
+                && (date.year() - start.year()) as i64 % rule.interval == 0
+        }
+    }
+}
+
+fn occurs_on(rule: &Recurrence, start: NaiveDate, date: NaiveDate) -> bool {
+    if rule.until.is_some_and(|until| date > until) || !matches_rule(rule, start, date) {
+        return false;
+    }
+    let Some(count) = rule.count else {
+        return true;
+    };
+    let occurrences = start
+        .iter_days()
+        .take_while(|day| *day <= date)
+        .filter(|day| matches_rule(rule, start, *day))
+        .take(count + 1)
+        .count();
+    occurrences <= count
+}
+
+fn format_time(time: &EventTime) -> String {
+    match time {
+        EventTime::Date(date) => date.format(DATE_FORMAT).to_string(),
+        EventTime::DateTime(time) => time.format(EVENT_TIME_FORMAT).to_string(),
+    }
+}
+
+// Moves an event's times onto the occurrence date, keeping its length
+fn shift_to(time: &EventTime, days: i64) -> EventTime {
+    let offset = chrono::Duration::days(days);
+    match time {
+        EventTime::Date(date) => EventTime::Date(*date + offset),
+        EventTime::DateTime(time) => EventTime::DateTime(*time + offset),
+    }
+}
+
+fn events_on(events: &[RawEvent], date: NaiveDate, source: &str) -> Vec<CalendarEvent> {
+    // Occurrences that were edited appear as their own events, so skip the originals
+    let overridden: HashSet<(&str, NaiveDate)> = events
+        .iter()
+        .filter_map(|event| Some((event.uid.as_str(), event.recurrence_id?)))
+        .collect();
+
+    let mut found = Vec::new();
+    for event in events.iter().filter(|event| !event.cancelled) {
+        let Some(start) = event.start else {
+            continue;
+        };
+        let start_date = start.date();
+
+        let occurrence = match &event.rule {
+            Some(rule) if event.recurrence_id.is_none() => {
+                if !occurs_on(rule, start_date, date)
+                    || event.exdates.contains(&date)
+                    || overridden.contains(&(event.uid.as_str(), date))
+                {
+                    continue;
+                }
+                date
+            }
+            _ => {
+                // All-day events end on the day after their last one
+                let last = match (start, event.end) {
+                    (EventTime::Date(_), Some(end)) => end.date().pred_opt().unwrap_or(start_date),
+                    (_, Some(end)) => end.date(),
+                    (_, None) => start_date,
+                };
+                if date < start_date || date > last.max(start_date) {
+                    continue;
+                }
+                start_date
+            }
+        };
+
+        let days = (occurrence - start_date).num_days();
+        let start = shift_to(&start, days);
+        found.push(CalendarEvent {
+            id: format!("{}@{}", event.uid, occurrence.format(DATE_FORMAT)),
+            title: event
+                .summary
+                .clone()
+                .filter(|summary| !summary.is_empty())
+                .unwrap_or_else(|| "Untitled event".to_string()),
+            start: format_time(&start),
+            end: event.end.map(|end| format_time(&shift_to(&end, days))),
+            all_day: matches!(start, EventTime::Date(_)),
+            location: event.location.clone(),
+            description: event.description.clone(),
+            attendees: event.attendees.clone(),
+            source: source.to_string(),
+        });
+    }
+
+    found
+}
+
+async fn read_source(source: &str) -> Result<String, String> {
+    if http::is_remote(source) {
+        return http::get_text(source).await;
+    }
+    fs::read_to_string(source.trim()).map_err(|e| format!("Failed to read {}: {}", source, e))
+}
+
+// Unreadable subscriptions are logged and skipped so one bad URL doesn't hide the rest
+#[tauri::command]
+pub async fn list_todays_events(
+    settings_state: State<'_, SettingsState>,
+    ics_state: State<'_, IcsState>,
+) -> Result<Vec<CalendarEvent>, String> {
+    let today = Local::now().date_naive();
+    let mut events = Vec::new();
+
+    for source in settings_state.current().calendar_subscriptions {
+        match read_source(&source).await {
+            Ok(text) => events.extend(events_on(&parse_events(&text), today, &source)),
+            Err(e) => tracing::warn!("Failed to load calendar: {}", e),
+        }
+    }
+    // All-day events first, then by start time
+    events.sort_by(|a, b| {
+        b.all_day
+            .cmp(&a.all_day)
+            .then_with(|| a.start.cmp(&b.start))
+            .then_with(|| a.title.cmp(&b.title))
+    });
+
+    if let Ok(mut cached) = ics_state.events.lock() {
+        *cached = events.clone();
+    }
+    Ok(events)
+}
+
+fn find_meeting_template(root: &Path, templates_folder: Option<&str>) -> Option<String> {
+    let folder = root.join(templates_folder?);
+    let entries = fs::read_dir(folder).ok()?;
+    let mut templates: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    templates.sort();
+
+    MEETING_TEMPLATE_NAMES.iter().find_map(|name| {
+        templates
+            .iter()
+            .find(|path| {
+                path.file_stem()
+                    .is_some_and(|stem| stem.to_string_lossy().to_lowercase() == *name)
+            })
+            .and_then(|path| fs::read_to_string(path).ok())
+    })
+}
+
+fn event_time_range(event: &CalendarEvent) -> String {
+    if event.all_day {
+        return "All day".to_string();
+    }
+    let clock = |time: &str| time.split_once('T').map(|(_, clock)| clock.to_string());
+    match (clock(&event.start), event.end.as_deref().and_then(clock)) {
+        (Some(start), Some(end)) => format!("{} – {}", start, end),
+        (Some(start), None) => start,
+        _ => String::new(),
+    }
+}
+
+fn render_template(template: &str, event: &CalendarEvent) -> String {
+    let date = event.start.get(..10).unwrap_or(&event.start);
+    [
+        ("{{title}}", event.title.clone()),
+        ("{{date}}", date.to_string()),
+        ("{{time}}", event_time_range(event)),
+        ("{{location}}", event.location.clone().unwrap_or_default()),
+        ("{{attendees}}", event.attendees.join(", ")),
+        (
+            "{{description}}",
+            event.description.clone().unwrap_or_default(),
+        ),
+    ]
+    .iter()
+    .fold(template.to_string(), |text, (placeholder, value)| {
+        text.replace(placeholder, value)
+    })
+}
+
+// File names can't hold path separators and the like, which event titles often do
+fn note_file_name(event: &CalendarEvent) -> String {
+    let title: String = event
+        .title
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    let title = title.trim().trim_start_matches('.');
+    let date = event.start.get(..10).unwrap_or(&event.start);
+    format!("{} {}.md", title, date)
+}
+
+// Fills in a meeting template for an event from the last `list_todays_events`
+// and creates the note in `parent_folder_path`. `template` is the frontend's
+// own meeting template, if any; otherwise one from the workspace's templates
+// folder or a built-in one is used.
+#[tauri::command]
+pub async fn create_note_from_event(
+    event_id: String,
+    folder_path: String,
+    parent_folder_path: Option<String>,
+    template: Option<String>,
+    app: tauri::AppHandle,
+    settings_state: State<'_, SettingsState>,
+    ics_state: State<'_, IcsState>,
+) -> Result<String, String> {
+    let root = PathBuf::from(&folder_path);
+    let event = ics_state
+        .events
+        .lock()
+        .map_err(|_| "Calendar state is unavailable".to_string())?
+        .iter()
+        .find(|event| event.id == event_id)
+        .cloned()
+        .ok_or_else(|| "Event not found; refresh today's events and try again".to_string())?;
+
+    let config = workspace_config::effective(&root, &settings_state.current());
+    let template = template
+        .filter(|template| !template.trim().is_empty())
+        .or_else(|| find_meeting_template(&root, config.templates_folder.as_deref()))
+        .unwrap_or_else(|| DEFAULT_MEETING_TEMPLATE.to_string());
+
+    crate::create_markdown_file(
+        parent_folder_path.unwrap_or(folder_path),
+        note_file_name(&event),
+        Some(render_template(&template, &event)),
+        app,
+    )
+}
+
+// iCalendar text values escape `\`, `;`, `,`, and line breaks
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+// Lines longer than 75 octets continue on the next line after a space
+fn push_line(output: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            output.push_str("\r\n ");
+            width = 1;
+        }
+        output.push(c);
+        width += c.len_utf8();
+    }
+    output.push_str("\r\n");
+}
+
+// Stable across edits elsewhere in the note, so calendar apps update tasks in
+// place instead of duplicating them
+pub fn task_id(root: &Path, path: &Path, text: &str, occurrence: usize) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path).to_string_lossy();
+    let digest = Sha256::digest(format!("{}\n{}\n{}", relative, text, occurrence).as_bytes());
+    digest[..16].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn task_uid(root: &Path, path: &Path, text: &str, occurrence: usize) -> String {
+    format!("{}@marky", task_id(root, path, text, occurrence))
+}
+
+// Dated tasks from the workspace as a calendar, each as an all-day event or,
+// with `as_todos`, a VTODO for apps that show those
+pub fn tasks_calendar(
+    index_state: &IndexState,
+    root: &Path,
+    as_todos: bool,
+    include_done: bool,
+) -> Result<(String, usize), String> {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let entries = index_state.with_workspace(root, |index| {
+        let mut notes: Vec<_> = index.notes.values().collect();
+        notes.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut entries = Vec::new();
+        for note in notes {
+            let mut occurrences: HashMap<&str, usize> = HashMap::new();
+            for task in &note.tasks {
+                let occurrence = occurrences.entry(task.text.as_str()).or_default();
+                *occurrence += 1;
+                let Some(due) = task.due else {
+                    continue;
+                };
+                if task.done && !include_done {
+                    continue;
+                }
+                entries.push((
+                    task_uid(root, &note.path, &task.text, *occurrence),
+                    markdown::strip_due_date(&task.text),
+                    note.title.clone(),
+                    due,
+                    task.done,
+                ));
+            }
+        }
+        entries
+    })?;
+
+    let mut output = String::new();
+    push_line(&mut output, "BEGIN:VCALENDAR");
+    push_line(&mut output, "VERSION:2.0");
+    push_line(&mut output, "PRODID:-//Marky//Tasks//EN");
+    push_line(&mut output, "CALSCALE:GREGORIAN");
+    push_line(&mut output, "X-WR-CALNAME:Marky Tasks");
+    for (uid, summary, note_title, due, done) in &entries {
+        let date = due.format("%Y%m%d");
+        let component = if as_todos { "VTODO" } else { "VEVENT" };
+        push_line(&mut output, &format!("BEGIN:{}", component));
+        push_line(&mut output, &format!("UID:{}", uid));
+        push_line(&mut output, &format!("DTSTAMP:{}", stamp));
+        push_line(&mut output, &format!("SUMMARY:{}", escape_text(summary)));
+        push_line(
+            &mut output,
+            &format!(
+                "DESCRIPTION:{}",
+                escape_text(&format!("From {}", note_title))
+            ),
+        );
+        if as_todos {
+            push_line(&mut output, &format!("DUE;VALUE=DATE:{}", date));
+            push_line(
+                &mut output,
+                if *done {
+                    "STATUS:COMPLETED"
+                } else {
+                    "STATUS:NEEDS-ACTION"
+                },
+            );
+        } else {
+            let end = *due + ChronoDuration::days(1);
+            push_line(&mut output, &format!("DTSTART;VALUE=DATE:{}", date));
+            push_line(
+                &mut output,
+                &format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")),
+            );
+            push_line(&mut output, "TRANSP:TRANSPARENT");
+        }
+        push_line(&mut output, &format!("END:{}", component));
+    }
+    push_line(&mut output, "END:VCALENDAR");
+    Ok((output, entries.len()))
+}
+
+// Writes the workspace's dated tasks to an `.ics` file that calendar apps can
+// import; returns how many tasks it holds
+#[tauri::command]
+pub async fn export_tasks_ics(
+    folder_path: String,
+    output_path: String,
+    as_todos: Option<bool>,
+    include_done: Option<bool>,
+    index_state: State<'_, IndexState>,
+) -> Result<usize, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+    let (calendar, count) = tasks_calendar(
+        &index_state,
+        &root,
+        as_todos.unwrap_or(false),
+        include_done.unwrap_or(false),
+    )?;
+    disk_space::ensure_space(Path::new(&output_path), calendar.len() as u64)
+        .map_err(|e| e.message)?;
+    fs::write(&output_path, calendar).map_err(|e| format!("Failed to write calendar: {}", e))?;
+    Ok(count)
+}
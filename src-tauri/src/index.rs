@@ -0,0 +1,412 @@
+use crate::encryption;
+use crate::markdown;
+use crate::settings::Settings;
+use crate::workspace_config::NoteFilter;
+use chrono::NaiveDateTime;
+use serde_yaml::Mapping;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+// Shared by every index so a rebuilt one never reuses a generation a cache has seen
+static GENERATION: AtomicU64 = AtomicU64::new(1);
+
+fn next_generation() -> u64 {
+    GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
+#[derive(Debug, Clone)]
+pub struct NoteRecord {
+    pub path: PathBuf,
+    pub title: String,
+    pub size: u64,
+    pub words: usize,
+    pub created: Option<SystemTime>,
+    pub modified: Option<SystemTime>,
+    pub tags: Vec<String>,
+    pub links: Vec<String>,
+    // From the `aliases:` frontmatter key, as written
+    pub aliases: Vec<String>,
+    // ATX headings, for `[[Note#Heading]]` completion and resolution
+    pub headings: Vec<markdown::Heading>,
+    // Blocks with a `^id`, and the `[[Note#^id]]` references this note makes
+    pub blocks: Vec<markdown::Block>,
+    pub block_refs: Vec<markdown::BlockRef>,
+    // Every frontmatter key, for queries; empty without a valid block
+    pub frontmatter: Mapping,
+    // Local times from `remind:` frontmatter and `@remind(...)` tokens
+    pub reminders: Vec<NaiveDateTime>,
+    // Checkbox items, with due dates where the note gives one
+    pub tasks: Vec<markdown::Task>,
+    // Term frequencies over the note body (code blocks excluded)
+    pub terms: HashMap<String, u32>,
+}
+
+impl NoteRecord {
+    fn read(path: &Path) -> Option<NoteRecord> {
+        let metadata = fs::metadata(path).ok()?;
+        let content = fs::read_to_string(path).ok()?;
+        let modified = metadata.modified().ok();
+
+        // Encrypted notes are indexed by title only; their ciphertext isn't searchable text
+        let content = if encryption::is_encrypted(&content) {
+            String::new()
+        } else {
+            content
+        };
+
+        let mut terms = HashMap::new();
+        for term in markdown::tokenize(&markdown::strip_code_blocks(&content)) {
+            *terms.entry(term).or_insert(0) += 1;
+        }
+
+        Some(NoteRecord {
+            path: path.to_path_buf(),
+            title: markdown::note_title(path),
+            size: metadata.len(),
+            words: markdown::count_words(&content),
+            // Not every filesystem records birth time; fall back to mtime
+            created: metadata.created().ok().or(modified),
+            modified,
+            tags: markdown::extract_tags(&content),
+            links: markdown::extract_wiki_links(&content),
+            aliases: markdown::extract_aliases(&content),
+            headings: markdown::extract_headings(&content),
+            blocks: markdown::extract_blocks(&content),
+            block_refs: markdown::extract_block_refs(&content),
+            frontmatter: markdown::parse_frontmatter(&content).unwrap_or_default(),
+            reminders: markdown::extract_reminders(&content),
+            tasks: markdown::extract_tasks(&content),
+            terms,
+        })
+    }
+
+    // Whether a `[[link]]` key points at this note, by title or by alias
+    pub fn answers_to(&self, key: &str) -> bool {
+        markdown::link_key(&self.title) == key
+            || self
+                .aliases
+                .iter()
+                .any(|alias| markdown::link_key(alias) == key)
+    }
+}
+
+pub struct WorkspaceIndex {
+    filter: NoteFilter,
+    // Only changed through `insert` and `remove`, which keep `postings` in step
+    pub notes: HashMap<PathBuf, NoteRecord>,
+    // Term -> notes using it, sorted so a prefix is one range scan
+    pub postings: BTreeMap<String, HashSet<PathBuf>>,
+    // Moved to a fresh value on every change so derived caches know when to recompute
+    pub generation: u64,
+}
+
+impl WorkspaceIndex {
+    pub fn build(root: &Path, settings: &Settings) -> WorkspaceIndex {
+        let filter = NoteFilter::for_workspace(root, settings);
+        let mut files = Vec::new();
+        collect_markdown_files(root, &filter, &mut files);
+
+        let mut index = WorkspaceIndex {
+            filter,
+            notes: HashMap::new(),
+            postings: BTreeMap::new(),
+            generation: next_generation(),
+        };
+        for path in files {
+            if let Some(record) = NoteRecord::read(&path) {
+                index.insert(record);
+            }
+        }
+        index
+    }
+
+    fn insert(&mut self, record: NoteRecord) {
+        self.remove(&record.path.clone());
+        for term in record.terms.keys() {
+            self.postings
+                .entry(term.clone())
+                .or_default()
+                .insert(record.path.clone());
+        }
+        self.notes.insert(record.path.clone(), record);
+    }
+
+    fn remove(&mut self, path: &Path) {
+        let Some(record) = self.notes.remove(path) else {
+            return;
+        };
+        for term in record.terms.keys() {
+            if let Some(paths) = self.postings.get_mut(term) {
+                paths.remove(path);
+                if paths.is_empty() {
+                    self.postings.remove(term);
+                }
+            }
+        }
+    }
+
+    // Notes using `term`, or any term starting with it when `prefix` is set
+    pub fn notes_with(&self, term: &str, prefix: bool) -> HashSet<&PathBuf> {
+        if !prefix {
+            return self
+                .postings
+                .get(term)
+                .map(|paths| paths.iter().collect())
+                .unwrap_or_default();
+        }
+        self.postings
+            .range(term.to_string()..)
+            .take_while(|(key, _)| key.starts_with(term))
+            .flat_map(|(_, paths)| paths.iter())
+            .collect()
+    }
+
+    // Titles win over aliases so a new note can't be shadowed by an old alias
+    pub fn resolve_link(&self, target: &str) -> Option<&NoteRecord> {
+        let key = markdown::link_key(target);
+        if key.is_empty() {
+            return None;
+        }
+
+        self.notes
+            .values()
+            .find(|note| markdown::link_key(&note.title) == key)
+            .or_else(|| self.notes.values().find(|note| note.answers_to(&key)))
+    }
+
+    fn refresh_path(&mut self, path: &Path) {
+        if self.filter.is_ignored(path) {
+            return;
+        }
+
+        if path.is_file() {
+            if !self.filter.includes_note(path) {
+                return;
+            }
+            match NoteRecord::read(path) {
+                Some(record) => self.insert(record),
+                None => self.remove(path),
+            }
+        } else if path.is_dir() {
+            // A folder moved or copied in: pick up everything beneath it
+            let mut files = Vec::new();
+            collect_markdown_files(path, &self.filter, &mut files);
+            for file in files {
+                if let Some(record) = NoteRecord::read(&file) {
+                    self.insert(record);
+                }
+            }
+        } else {
+            // Removed file or folder: drop it and anything that lived under it
+            let removed: Vec<PathBuf> = self
+                .notes
+                .keys()
+                .filter(|note_path| note_path.starts_with(path))
+                .cloned()
+                .collect();
+            for note_path in removed {
+                self.remove(&note_path);
+            }
+        }
+
+        self.generation = next_generation();
+    }
+}
+
+pub struct IndexState {
+    workspaces: Mutex<HashMap<PathBuf, WorkspaceIndex>>,
+    settings: Mutex<Settings>,
+}
+
+impl Default for IndexState {
+    fn default() -> Self {
+        IndexState {
+            workspaces: Mutex::new(HashMap::new()),
+            settings: Mutex::new(Settings::default()),
+        }
+    }
+}
+
+impl IndexState {
+    fn settings(&self) -> Settings {
+        self.settings
+            .lock()
+            .map(|settings| settings.clone())
+            .unwrap_or_default()
+    }
+
+    // Extensions and ignore globs decide what counts as a note, so a change there
+    // drops every index and lets them rebuild lazily
+    pub fn set_settings(&self, settings: Settings) {
+        let changed = match self.settings.lock() {
+            Ok(mut current) => {
+                let changed = current.watched_extensions != settings.watched_extensions
+                    || current.ignore_globs != settings.ignore_globs;
+                *current = settings;
+                changed
+            }
+            Err(_) => false,
+        };
+
+        if changed {
+            if let Ok(mut workspaces) = self.workspaces.lock() {
+                workspaces.clear();
+            }
+        }
+    }
+
+    // Forces the next access to rebuild, e.g. after `.marky/config.json` changed
+    pub fn invalidate(&self, root: &Path) {
+        if let Ok(mut workspaces) = self.workspaces.lock() {
+            workspaces.remove(root);
+        }
+    }
+
+    // Runs `f` against the index for `root`, building it on first use.
+    pub fn with_workspace<R>(
+        &self,
+        root: &Path,
+        f: impl FnOnce(&mut WorkspaceIndex) -> R,
+    ) -> Result<R, String> {
+        let settings = self.settings();
+        let mut guard = self
+            .workspaces
+            .lock()
+            .map_err(|e| format!("Failed to lock index state: {}", e))?;
+        let index = guard
+            .entry(root.to_path_buf())
+            .or_insert_with(|| WorkspaceIndex::build(root, &settings));
+        Ok(f(index))
+    }
+
+    // Brings an already-built index back in line with the disk after the watcher
+    // may have dropped events: notes whose size or mtime changed are re-read and
+    // missing ones dropped. The walk and reads happen outside the lock so queries
+    // keep working meanwhile. Returns how many notes changed.
+    pub fn rescan(&self, root: &Path) -> Result<usize, String> {
+        let known: HashMap<PathBuf, (Option<SystemTime>, u64)> = {
+            let guard = self
+                .workspaces
+                .lock()
+                .map_err(|e| format!("Failed to lock index state: {}", e))?;
+            let Some(index) = guard.get(root) else {
+                return Ok(0);
+            };
+            index
+                .notes
+                .iter()
+                .map(|(path, note)| (path.clone(), (note.modified, note.size)))
+                .collect()
+        };
+
+        let filter = NoteFilter::for_workspace(root, &self.settings());
+        let mut files = Vec::new();
+        collect_markdown_files(root, &filter, &mut files);
+
+        let mut changed = Vec::new();
+        for path in &files {
+            let current = fs::metadata(path)
+                .ok()
+                .map(|metadata| (metadata.modified().ok(), metadata.len()));
+            if current.is_none() || current != known.get(path).copied() {
+                changed.push((path.clone(), NoteRecord::read(path)));
+            }
+        }
+        let present: HashSet<&PathBuf> = files.iter().collect();
+        let removed: Vec<&PathBuf> = known
+            .keys()
+            .filter(|path| !present.contains(path))
+            .collect();
+
+        let mut guard = self
+            .workspaces
+            .lock()
+            .map_err(|e| format!("Failed to lock index state: {}", e))?;
+        let Some(index) = guard.get_mut(root) else {
+            return Ok(0);
+        };
+        let count = changed.len() + removed.len();
+        for path in removed {
+            index.remove(path);
+        }
+        for (path, record) in changed {
+            match record {
+                Some(record) => index.insert(record),
+                None => index.remove(&path),
+            }
+        }
+        if count > 0 {
+            index.generation = next_generation();
+        }
+        Ok(count)
+    }
+
+    // Longest indexed root containing `path`, for commands that only receive a note path
+    pub fn root_for(&self, path: &Path) -> Option<PathBuf> {
+        let guard = self.workspaces.lock().ok()?;
+        guard
+            .keys()
+            .filter(|root| path.starts_with(root))
+            .max_by_key(|root| root.components().count())
+            .cloned()
+    }
+
+    // Applies a watcher event to an already-built index; unindexed roots are ignored.
+    pub fn apply_change(&self, root: &Path, path: &Path) {
+        let Ok(mut guard) = self.workspaces.lock() else {
+            return;
+        };
+        if let Some(index) = guard.get_mut(root) {
+            index.refresh_path(path);
+        }
+    }
+}
+
+// Walks `dir` the same way `scan_folder_for_markdown` does, honoring the workspace filter
+pub fn collect_markdown_files(dir: &Path, filter: &NoteFilter, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if filter.is_ignored(&path) {
+            continue;
+        }
+
+        if path.is_dir() {
+            collect_markdown_files(&path, filter, files);
+        } else if path.is_file() && filter.includes_note(&path) {
+            files.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebuilt_index_gets_a_fresh_generation() {
+        let root = std::env::temp_dir().join(format!("marky-index-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("a.md"), "# A").unwrap();
+
+        let state = IndexState::default();
+        let before = state
+            .with_workspace(&root, |index| index.generation)
+            .unwrap();
+        state.invalidate(&root);
+        let after = state
+            .with_workspace(&root, |index| index.generation)
+            .unwrap();
+        assert_ne!(before, after);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::index::collect_markdown_files;
+use crate::settings::SettingsState;
+use crate::storage;
+use crate::vault;
+use crate::workspace_config::NoteFilter;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tauri::{AppHandle, State};
+
+const MANIFEST_FILE: &str = "integrity.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ManifestEntry {
+    size: u64,
+    modified_ms: u64,
+    sha256: String,
+}
+
+// Checksums from the last verification, keyed by path relative to the workspace
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    verified_at: Option<String>,
+    files: BTreeMap<String, ManifestEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum IssueKind {
+    // Contents changed while size and modification time stayed the same
+    Corrupted,
+    // Shrank without being modified, or ends in a run of NUL bytes
+    Truncated,
+    InvalidUtf8,
+    Empty,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IntegrityIssue {
+    path: String,
+    kind: IssueKind,
+    detail: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IntegrityReport {
+    checked: usize,
+    // Notes the manifest hadn't seen, and ones edited since the last check
+    added: usize,
+    changed: usize,
+    removed: usize,
+    previous_check: Option<String>,
+    issues: Vec<IntegrityIssue>,
+}
+
+fn manifest_path(app: &AppHandle, root: &Path) -> Result<PathBuf, String> {
+    Ok(storage::workspace_data_dir(app, root)?.join(MANIFEST_FILE))
+}
+
+fn modified_ms(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |since| since.as_millis() as u64)
+}
+
+// Sync clients that lose a transfer tend to leave the file padded with zeros
+fn nul_padding(bytes: &[u8]) -> usize {
+    bytes.iter().rev().take_while(|byte| **byte == 0).count()
+}
+
+fn issue(path: &Path, kind: IssueKind, detail: String) -> IntegrityIssue {
+    IntegrityIssue {
+        path: path.to_string_lossy().to_string(),
+        kind,
+        detail,
+    }
+}
+
+// Hashes every note and compares it with the manifest from the last run. A
+// note whose bytes changed without its size or modification time changing was
+// damaged on disk or in transit; a real edit always bumps the timestamp.
+// Damaged notes keep their old manifest entry, so they're reported again until
+// they're restored or edited.
+#[tauri::command]
+pub async fn verify_workspace(
+    folder_path: String,
+    app: AppHandle,
+    settings_state: State<'_, SettingsState>,
+) -> Result<IntegrityReport, String> {
+    let root = PathBuf::from(&folder_path);
+    storage::ensure_accessible(&app, &root)?;
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+
+    let filter = NoteFilter::for_workspace(&root, &settings_state.current());
+    let mut paths = Vec::new();
+    collect_markdown_files(&root, &filter, &mut paths);
+    paths.sort();
+
+    // Vault files are ciphertext, so only their checksums mean anything
+    let is_vault = vault::is_vault_root(&root);
+    let manifest_path = manifest_path(&app, &root)?;
+    let previous: Manifest = storage::read_json(&manifest_path);
+    let mut manifest = Manifest {
+        verified_at: Some(chrono::Local::now().to_rfc3339()),
+        files: BTreeMap::new(),
+    };
+    let mut report = IntegrityReport {
+        checked: 0,
+        added: 0,
+        changed: 0,
+        removed: 0,
+        previous_check: previous.verified_at.clone(),
+        issues: Vec::new(),
+    };
+
+    for path in &paths {
+        let key = path
+            .strip_prefix(&root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let (metadata, bytes) = match fs::metadata(path).and_then(|m| Ok((m, fs::read(path)?))) {
+            Ok(read) => read,
+            Err(e) => {
+                report.issues.push(issue(
+                    path,
+                    IssueKind::Corrupted,
+                    format!("Couldn't be read: {}", e),
+                ));
+                continue;
+            }
+        };
+        report.checked += 1;
+
+        let entry = ManifestEntry {
+            size: bytes.len() as u64,
+            modified_ms: modified_ms(&metadata),
+            sha256: format!("{:x}", Sha256::digest(&bytes)),
+        };
+        let known = previous.files.get(&key);
+        let unmodified = known.is_some_and(|known| known.modified_ms == entry.modified_ms);
+        let mut damaged = false;
+
+        if bytes.is_empty() {
+            report.issues.push(issue(
+                path,
+                IssueKind::Empty,
+                match known {
+                    Some(known) if known.size > 0 => {
+                        format!("Was {} bytes at the last check", known.size)
+                    }
+                    _ => "The note has no content".to_string(),
+                },
+            ));
+            damaged = unmodified;
+        } else if let Some(known) = known.filter(|_| unmodified) {
+            if entry.size < known.size {
+                report.issues.push(issue(
+                    path,
+                    IssueKind::Truncated,
+                    format!(
+                        "Shrank from {} to {} bytes without being modified",
+                        known.size, entry.size
+                    ),
+                ));
+                damaged = true;
+            } else if entry.sha256 != known.sha256 {
+                report.issues.push(issue(
+                    path,
+                    IssueKind::Corrupted,
+                    "Contents changed without being modified".to_string(),
+                ));
+                damaged = true;
+            }
+        }
+
+        let padding = nul_padding(&bytes);
+        if !bytes.is_empty() && padding > 0 {
+            report.issues.push(issue(
+                path,
+                IssueKind::Truncated,
+                format!("Ends in {} NUL bytes", padding),
+            ));
+        } else if !is_vault && !bytes.is_empty() {
+            if let Err(e) = std::str::from_utf8(&bytes) {
+                report.issues.push(issue(
+                    path,
+                    IssueKind::InvalidUtf8,
+                    format!("Invalid UTF-8 at byte {}", e.valid_up_to()),
+                ));
+            }
+        }
+
+        match known {
+            None => report.added += 1,
+            Some(known) if !unmodified && known.sha256 != entry.sha256 => report.changed += 1,
+            _ => {}
+        }
+        let kept = match known {
+            Some(known) if damaged => known.clone(),
+            _ => entry,
+        };
+        manifest.files.insert(key, kept);
+    }
+
+    report.removed = previous
+        .files
+        .keys()
+        .filter(|key| !manifest.files.contains_key(*key))
+        .count();
+    storage::write_json(&manifest_path, &manifest)?;
+    if !report.issues.is_empty() {
+        tracing::warn!(
+            "Integrity check found {} issue(s) in {}",
+            report.issues.len(),
+            root.display()
+        );
+    }
+    Ok(report)
+}
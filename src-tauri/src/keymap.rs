@@ -0,0 +1,458 @@
+#[cfg(desktop)]
+use crate::menu;
+use crate::storage;
+use serde::Serialize;
+use std::collections::BTreeMap;
+#[cfg(desktop)]
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, State};
+
+const KEYMAP_FILE: &str = "keymap.json";
+
+// An action the user can bind, with its default accelerator and the menu
+// item (if any) that shows it
+struct Action {
+    id: &'static str,
+    label: &'static str,
+    accelerator: &'static str,
+    menu_id: Option<&'static str>,
+}
+
+const fn action(
+    id: &'static str,
+    label: &'static str,
+    accelerator: &'static str,
+    menu_id: Option<&'static str>,
+) -> Action {
+    Action {
+        id,
+        label,
+        accelerator,
+        menu_id,
+    }
+}
+
+const ACTIONS: &[Action] = &[
+    action(
+        "newNote",
+        "New Note",
+        "CmdOrCtrl+N",
+        Some("menu://new-note"),
+    ),
+    action(
+        "newFolder",
+        "New Folder",
+        "CmdOrCtrl+Shift+N",
+        Some("menu://new-folder"),
+    ),
+    action(
+        "openFile",
+        "Open File",
+        "CmdOrCtrl+O",
+        Some("menu://open-file"),
+    ),
+    action(
+        "openFolder",
+        "Open Folder",
+        "CmdOrCtrl+Shift+O",
+        Some("menu://open-folder"),
+    ),
+    action("save", "Save", "CmdOrCtrl+S", Some("menu://save-note")),
+    action("closeTab", "Close Tab", "CmdOrCtrl+W", None),
+    action(
+        "commandPalette",
+        "Command Palette",
+        "CmdOrCtrl+K",
+        Some("menu://command-palette"),
+    ),
+    action(
+        "search",
+        "Search All Notes",
+        "CmdOrCtrl+Shift+F",
+        Some("menu://search"),
+    ),
+    action("editorSearch", "Find in Editor", "CmdOrCtrl+F", None),
+    action(
+        "toggleSidebar",
+        "Toggle Sidebar",
+        "CmdOrCtrl+B",
+        Some("menu://toggle-sidebar"),
+    ),
+    action(
+        "showShortcuts",
+        "Keyboard Shortcuts",
+        "CmdOrCtrl+/",
+        Some("menu://show-shortcuts"),
+    ),
+    action(
+        "openSettings",
+        "Settings",
+        "CmdOrCtrl+,",
+        Some("menu://open-settings"),
+    ),
+    action(
+        "viewEditor",
+        "Editor Only",
+        "CmdOrCtrl+1",
+        Some("menu://view-editor"),
+    ),
+    action(
+        "viewSplit",
+        "Split View",
+        "CmdOrCtrl+2",
+        Some("menu://view-split"),
+    ),
+    action(
+        "viewPreview",
+        "Preview Only",
+        "CmdOrCtrl+3",
+        Some("menu://view-preview"),
+    ),
+    action(
+        "toggleFocusMode",
+        "Focus Mode",
+        "CmdOrCtrl+Alt+F",
+        Some("menu://focus-mode"),
+    ),
+    action("bold", "Bold", "CmdOrCtrl+Shift+B", None),
+    action("italic", "Italic", "CmdOrCtrl+I", None),
+    action("link", "Insert Link", "CmdOrCtrl+Shift+K", None),
+    action("codeBlock", "Insert Code Block", "CmdOrCtrl+Shift+C", None),
+    action("list", "Insert List", "CmdOrCtrl+Shift+L", None),
+];
+
+// The default menu's own items, which keep their accelerators. Close
+// Window's CmdOrCtrl+W is left out: Close Tab has always shared it.
+const RESERVED: &[(&str, &str)] = &[
+    ("CmdOrCtrl+Z", "Undo"),
+    ("CmdOrCtrl+Shift+Z", "Redo"),
+    ("CmdOrCtrl+X", "Cut"),
+    ("CmdOrCtrl+C", "Copy"),
+    ("CmdOrCtrl+V", "Paste"),
+    ("CmdOrCtrl+A", "Select All"),
+    ("CmdOrCtrl+M", "Minimize"),
+    ("CmdOrCtrl+H", "Hide"),
+    ("CmdOrCtrl+Q", "Quit"),
+];
+
+// Unshifted keys menus can show; `Shift+/` rather than `?`
+const PUNCTUATION: &str = "`-=[]\\;',./";
+
+const NAMED_KEYS: &[(&str, &str)] = &[
+    ("enter", "Enter"),
+    ("return", "Enter"),
+    ("tab", "Tab"),
+    ("space", "Space"),
+    ("backspace", "Backspace"),
+    ("delete", "Delete"),
+    ("escape", "Escape"),
+    ("esc", "Escape"),
+    ("up", "Up"),
+    ("arrowup", "Up"),
+    ("down", "Down"),
+    ("arrowdown", "Down"),
+    ("left", "Left"),
+    ("arrowleft", "Left"),
+    ("right", "Right"),
+    ("arrowright", "Right"),
+    ("home", "Home"),
+    ("end", "End"),
+    ("pageup", "PageUp"),
+    ("pagedown", "PageDown"),
+    ("insert", "Insert"),
+];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct Shortcut {
+    action: String,
+    // None when the user has unassigned it
+    accelerator: Option<String>,
+    default_accelerator: String,
+    // Shown on a native menu item, which is rebuilt when it changes
+    menu: bool,
+    customized: bool,
+}
+
+fn function_key(key: &str) -> Option<String> {
+    let number: u8 = key.strip_prefix(['f', 'F'])?.parse().ok()?;
+    (1..=24).contains(&number).then(|| format!("F{}", number))
+}
+
+// `accelerator` in the one spelling used for storage and comparison, e.g.
+// `CmdOrCtrl+Shift+N`. Ctrl, Cmd and Mod all mean CmdOrCtrl, since the
+// editor makes no distinction.
+fn normalize(accelerator: &str) -> Result<String, String> {
+    let (mut command, mut shift, mut alt) = (false, false, false);
+    let mut key: Option<String> = None;
+    for part in accelerator.split('+').map(str::trim) {
+        match part.to_lowercase().as_str() {
+            "cmdorctrl" | "commandorcontrol" | "cmd" | "command" | "ctrl" | "control" | "mod"
+            | "meta" | "super" => command = true,
+            "shift" => shift = true,
+            "alt" | "option" => alt = true,
+            "" => return Err(format!("\"{}\" isn't a shortcut", accelerator)),
+            lower => {
+                if key.is_some() {
+                    return Err("A shortcut can only have one key besides modifiers".to_string());
+                }
+                let mut chars = part.chars();
+                let single = match (chars.next(), chars.next()) {
+                    (Some(c), None) if c.is_ascii_alphanumeric() || PUNCTUATION.contains(c) => {
+                        Some(c.to_ascii_uppercase())
+                    }
+                    _ => None,
+                };
+                key = Some(
+                    single
+                        .map(String::from)
+                        .or_else(|| function_key(part))
+                        .or_else(|| {
+                            NAMED_KEYS
+                                .iter()
+                                .find(|(name, _)| *name == lower)
+                                .map(|(_, canonical)| canonical.to_string())
+                        })
+                        .ok_or_else(|| format!("Unknown key: {}", part))?,
+                );
+            }
+        }
+    }
+    let key = key.ok_or_else(|| "A shortcut needs a key besides modifiers".to_string())?;
+    if !command && !alt && function_key(&key).is_none() {
+        return Err("Shortcuts need Cmd/Ctrl or Alt, unless they're function keys".to_string());
+    }
+
+    let mut parts = Vec::new();
+    if command {
+        parts.push("CmdOrCtrl".to_string());
+    }
+    if shift {
+        parts.push("Shift".to_string());
+    }
+    if alt {
+        parts.push("Alt".to_string());
+    }
+    parts.push(key);
+    Ok(parts.join("+"))
+}
+
+fn find_action(id: &str) -> Result<&'static Action, String> {
+    ACTIONS
+        .iter()
+        .find(|action| action.id == id)
+        .ok_or_else(|| format!("Unknown shortcut action: {}", id))
+}
+
+// Custom accelerators by action; an empty string means unassigned
+pub struct KeymapState {
+    overrides: Mutex<BTreeMap<String, String>>,
+}
+
+fn keymap_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(storage::app_config_dir(app)?.join(KEYMAP_FILE))
+}
+
+fn effective<'a>(
+    overrides: &'a BTreeMap<String, String>,
+    action: &'static Action,
+) -> Option<&'a str> {
+    match overrides.get(action.id) {
+        Some(custom) if custom.is_empty() => None,
+        Some(custom) => Some(custom),
+        None => Some(action.accelerator),
+    }
+}
+
+fn shortcuts(overrides: &BTreeMap<String, String>) -> Vec<Shortcut> {
+    ACTIONS
+        .iter()
+        .map(|action| Shortcut {
+            action: action.id.to_string(),
+            accelerator: effective(overrides, action).map(str::to_string),
+            default_accelerator: action.accelerator.to_string(),
+            menu: action.menu_id.is_some(),
+            customized: overrides.contains_key(action.id),
+        })
+        .collect()
+}
+
+// Refuses `accelerator` for `target` when another action or a default menu
+// item already answers to it
+fn check_conflicts(
+    overrides: &BTreeMap<String, String>,
+    target: &Action,
+    accelerator: &str,
+) -> Result<(), String> {
+    if let Some((_, item)) = RESERVED
+        .iter()
+        .find(|(reserved, _)| *reserved == accelerator)
+    {
+        return Err(format!(
+            "{} is the menu's {} shortcut and can't be reassigned",
+            accelerator, item
+        ));
+    }
+    if let Some(other) = ACTIONS
+        .iter()
+        .filter(|action| action.id != target.id)
+        .find(|action| effective(overrides, action) == Some(accelerator))
+    {
+        return Err(format!(
+            "{} is already used by {}; change that shortcut first",
+            accelerator, other.label
+        ));
+    }
+    Ok(())
+}
+
+// Entries for actions that no longer exist, or that don't parse, are dropped
+fn sanitized(stored: BTreeMap<String, String>) -> BTreeMap<String, String> {
+    stored
+        .into_iter()
+        .filter(|(id, _)| find_action(id).is_ok())
+        .filter_map(|(id, accelerator)| {
+            if accelerator.trim().is_empty() {
+                return Some((id, String::new()));
+            }
+            normalize(&accelerator)
+                .ok()
+                .map(|accelerator| (id, accelerator))
+        })
+        .collect()
+}
+
+impl KeymapState {
+    pub fn load(app: &AppHandle) -> KeymapState {
+        let stored: BTreeMap<String, String> = keymap_path(app)
+            .map(|path| storage::read_json(&path))
+            .unwrap_or_default();
+        KeymapState {
+            overrides: Mutex::new(sanitized(stored)),
+        }
+    }
+
+    pub fn overrides(&self) -> Result<BTreeMap<String, String>, String> {
+        self.overrides
+            .lock()
+            .map(|overrides| overrides.clone())
+            .map_err(|e| format!("Failed to lock keymap: {}", e))
+    }
+
+    // Swaps in another set of overrides, such as one from an imported
+    // profile. Bindings that collide with another action's, or with a
+    // reserved menu shortcut, are left at their defaults.
+    pub fn replace(
+        &self,
+        app: &AppHandle,
+        overrides: BTreeMap<String, String>,
+    ) -> Result<Vec<Shortcut>, String> {
+        let imported = sanitized(overrides);
+        let accepted: BTreeMap<String, String> = imported
+            .iter()
+            .filter(|(id, accelerator)| {
+                accelerator.is_empty()
+                    || find_action(id)
+                        .and_then(|target| check_conflicts(&imported, target, accelerator))
+                        .is_ok()
+            })
+            .map(|(id, accelerator)| (id.clone(), accelerator.clone()))
+            .collect();
+        self.update(app, |current| {
+            *current = accepted;
+            Ok(())
+        })
+    }
+
+    // The accelerator to show on each menu item, by menu id
+    #[cfg(desktop)]
+    pub fn menu_accelerators(&self) -> HashMap<&'static str, String> {
+        let Ok(overrides) = self.overrides.lock() else {
+            return HashMap::new();
+        };
+        ACTIONS
+            .iter()
+            .filter_map(|action| {
+                let accelerator = effective(&overrides, action)?;
+                Some((action.menu_id?, accelerator.to_string()))
+            })
+            .collect()
+    }
+
+    fn update(
+        &self,
+        app: &AppHandle,
+        edit: impl FnOnce(&mut BTreeMap<String, String>) -> Result<(), String>,
+    ) -> Result<Vec<Shortcut>, String> {
+        let mut guard = self
+            .overrides
+            .lock()
+            .map_err(|e| format!("Failed to lock keymap: {}", e))?;
+        let mut next = guard.clone();
+        edit(&mut next)?;
+        storage::write_json(&keymap_path(app)?, &next)?;
+        *guard = next;
+        let keymap = shortcuts(&guard);
+        drop(guard);
+
+        #[cfg(desktop)]
+        menu::rebuild(app);
+        app.emit("keymap-changed", &keymap)
+            .map_err(|e| format!("Failed to emit event: {}", e))?;
+        Ok(keymap)
+    }
+}
+
+#[tauri::command]
+pub fn get_keymap(keymap_state: State<KeymapState>) -> Result<Vec<Shortcut>, String> {
+    let overrides = keymap_state
+        .overrides
+        .lock()
+        .map_err(|e| format!("Failed to lock keymap: {}", e))?;
+    Ok(shortcuts(&overrides))
+}
+
+// Binds `action` to `accelerator`; an empty one unassigns it and None puts
+// the default back
+#[tauri::command]
+pub fn set_shortcut(
+    action: String,
+    accelerator: Option<String>,
+    app: AppHandle,
+    keymap_state: State<KeymapState>,
+) -> Result<Vec<Shortcut>, String> {
+    let target = find_action(&action)?;
+    let accelerator = match accelerator.as_deref().map(str::trim) {
+        None => Some(target.accelerator.to_string()),
+        Some("") => None,
+        Some(accelerator) => Some(normalize(accelerator)?),
+    };
+    keymap_state.update(&app, |overrides| {
+        match accelerator {
+            Some(accelerator) => {
+                check_conflicts(overrides, target, &accelerator)?;
+                if accelerator == target.accelerator {
+                    overrides.remove(target.id);
+                } else {
+                    overrides.insert(target.id.to_string(), accelerator);
+                }
+            }
+            None => {
+                overrides.insert(target.id.to_string(), String::new());
+            }
+        }
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn reset_keymap(
+    app: AppHandle,
+    keymap_state: State<KeymapState>,
+) -> Result<Vec<Shortcut>, String> {
+    keymap_state.update(&app, |overrides| {
+        overrides.clear();
+        Ok(())
+    })
+}
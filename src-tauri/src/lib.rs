@@ -1 +1,1288 @@
-// Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
+mod activity;
+mod api;
+mod archive;
+mod automations;
+mod badge;
+mod blocks;
+mod calendar;
+mod clipboard;
+mod clipper;
+mod conflicts;
+mod custom_menu;
+mod delete_guard;
+mod diagnostics;
+mod diff;
+mod disk_space;
+mod documents;
+mod drag;
+mod duplicates;
+mod edit_locks;
+mod email;
+mod embeds;
+mod encryption;
+mod export;
+mod feeds;
+mod git;
+mod graph;
+mod http;
+mod i18n;
+mod ics;
+mod index;
+mod integrity;
+mod keymap;
+mod link_previews;
+mod link_titles;
+mod links;
+mod locks;
+mod logging;
+mod markdown;
+mod mcp;
+mod mentions;
+#[cfg(desktop)]
+mod menu;
+mod merge;
+mod network;
+mod note_ids;
+mod notifications;
+mod pins;
+mod plugins;
+mod profile;
+mod profiling;
+mod properties;
+mod query;
+mod related;
+mod reminders;
+mod rtf;
+mod safe_mode;
+mod sample_workspace;
+mod schema;
+mod search;
+mod secrets;
+mod secure_delete;
+mod session;
+mod settings;
+mod share;
+mod smart_folders;
+mod snippets;
+#[cfg(target_os = "macos")]
+mod spotlight;
+mod stats;
+mod storage;
+mod tags;
+mod task_sync;
+mod telemetry;
+#[cfg(desktop)]
+mod updater;
+mod vault;
+mod watcher;
+mod window_state;
+mod windows;
+mod workspace_config;
+mod workspaces;
+mod writing;
+
+use index::IndexState;
+use serde::{Deserialize, Serialize};
+use settings::SettingsState;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tauri::{Emitter, Manager};
+use watcher::WatcherState;
+use workspace_config::NoteFilter;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MarkdownFile {
+    name: String,
+    path: String,
+    is_dir: bool,
+    #[serde(default)]
+    archived: bool,
+    #[serde(default)]
+    locked: bool,
+}
+
+fn ensure_valid_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err(i18n::t("error.name_empty"));
+    }
+
+    if name == "." || name == ".." {
+        return Err(i18n::t("error.name_invalid_characters"));
+    }
+
+    if name.ends_with(' ') || name.ends_with('.') {
+        return Err(i18n::t("error.name_trailing"));
+    }
+
+    if name.contains(['/', '\\']) {
+        return Err(i18n::t("error.name_separators"));
+    }
+
+    if name.chars().any(|c| c.is_control()) {
+        return Err(i18n::t("error.name_invalid_characters"));
+    }
+
+    if name
+        .chars()
+        .any(|c| matches!(c, '<' | '>' | ':' | '"' | '|' | '?' | '*'))
+    {
+        return Err(i18n::t("error.name_invalid_characters"));
+    }
+
+    let uppercase = name.to_uppercase();
+    let stem = uppercase.split('.').next().unwrap_or(&uppercase);
+    const RESERVED_NAMES: [&str; 22] = [
+        "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+        "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+    ];
+    if RESERVED_NAMES.contains(&stem) {
+        return Err(i18n::t("error.name_reserved"));
+    }
+
+    Ok(())
+}
+
+fn split_name_and_extension(name: &str) -> (String, Option<String>) {
+    if let Some(idx) = name.rfind('.') {
+        if idx == 0 {
+            return (name.to_string(), None);
+        }
+
+        let stem = name[..idx].to_string();
+        let ext = name[idx + 1..].to_string();
+        return (stem, Some(ext));
+    }
+
+    (name.to_string(), None)
+}
+
+fn resolve_unique_path(
+    parent: &Path,
+    desired_name: &str,
+    is_dir: bool,
+) -> Result<(PathBuf, String), String> {
+    resolve_unique_path_with(parent, desired_name, is_dir, Path::exists)
+}
+
+// Vaults can't check their plaintext names on disk, so existence is pluggable
+fn resolve_unique_path_with(
+    parent: &Path,
+    desired_name: &str,
+    is_dir: bool,
+    exists: impl Fn(&Path) -> bool,
+) -> Result<(PathBuf, String), String> {
+    const MAX_ATTEMPTS: usize = 500;
+
+    let (base_stem, base_ext) = if is_dir {
+        (desired_name.to_string(), None)
+    } else {
+        split_name_and_extension(desired_name)
+    };
+
+    for counter in 0..=MAX_ATTEMPTS {
+        let candidate_name = if counter == 0 {
+            desired_name.to_string()
+        } else if let Some(ref ext) = base_ext {
+            format!("{} {}.{}", base_stem, counter, ext)
+        } else {
+            format!("{} {}", base_stem, counter)
+        };
+
+        let candidate_path = parent.join(&candidate_name);
+
+        if !exists(&candidate_path) {
+            return Ok((candidate_path, candidate_name));
+        }
+    }
+
+    Err(i18n::t("error.no_available_name"))
+}
+
+#[tauri::command]
+fn create_folder(
+    parent_folder_path: String,
+    folder_name: String,
+    app: tauri::AppHandle,
+) -> Result<String, String> {
+    let parent = PathBuf::from(&parent_folder_path);
+    network::guard(&app, &parent, move |app| {
+        create_folder_in(app, &parent_folder_path, &folder_name)
+    })?
+}
+
+fn create_folder_in(
+    app: &tauri::AppHandle,
+    parent_folder_path: &str,
+    folder_name: &str,
+) -> Result<String, String> {
+    ensure_valid_name(folder_name)?;
+
+    let parent = PathBuf::from(parent_folder_path);
+    storage::ensure_accessible(app, &parent)?;
+    if vault::vault_root(&parent).is_some() {
+        vault::ensure_unlocked(app, &parent)?;
+        if !vault::is_dir(app, &parent) {
+            return Err(i18n::t("error.parent_missing"));
+        }
+        let (target, _) =
+            resolve_unique_path_with(&parent, folder_name, true, |p| vault::exists(app, p))?;
+        vault::create_dir(app, &target)?;
+        return Ok(target.to_string_lossy().to_string());
+    }
+
+    if !parent.exists() || !parent.is_dir() {
+        return Err(i18n::t("error.parent_missing"));
+    }
+
+    let (target, _) = resolve_unique_path(&parent, folder_name, true)?;
+
+    fs::create_dir(&target).map_err(|e| i18n::tf("error.create_folder", &[&e]))?;
+
+    Ok(target.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+fn create_markdown_file(
+    parent_folder_path: String,
+    file_name: String,
+    content: Option<String>,
+    app: tauri::AppHandle,
+) -> Result<String, String> {
+    let parent = PathBuf::from(&parent_folder_path);
+    network::guard(&app, &parent, move |app| {
+        create_file_in(app, &parent_folder_path, &file_name, content)
+    })?
+}
+
+fn create_file_in(
+    app: &tauri::AppHandle,
+    parent_folder_path: &str,
+    file_name: &str,
+    content: Option<String>,
+) -> Result<String, String> {
+    ensure_valid_name(file_name)?;
+
+    let parent = PathBuf::from(parent_folder_path);
+    storage::ensure_accessible(app, &parent)?;
+    // Vault activity isn't recorded, since the log would keep plaintext names
+    if vault::vault_root(&parent).is_some() {
+        vault::ensure_unlocked(app, &parent)?;
+        if !vault::is_dir(app, &parent) {
+            return Err(i18n::t("error.parent_missing"));
+        }
+        let (target, _) =
+            resolve_unique_path_with(&parent, file_name, false, |p| vault::exists(app, p))?;
+        vault::write_file(app, &target, &content.unwrap_or_default())?;
+        return Ok(target.to_string_lossy().to_string());
+    }
+
+    if !parent.exists() || !parent.is_dir() {
+        return Err(i18n::t("error.parent_missing"));
+    }
+
+    let (target, _) = resolve_unique_path(&parent, file_name, false)?;
+
+    fs::write(&target, content.unwrap_or_default())
+        .map_err(|e| i18n::tf("error.create_file", &[&e]))?;
+
+    if let Some(root) = app.state::<WatcherState>().root_for(&target) {
+        if let Err(e) = activity::record_activity(
+            app,
+            &app.state::<activity::ActivityState>(),
+            &root,
+            &target,
+            activity::ActivityKind::Create,
+        ) {
+            tracing::error!("Failed to record activity: {}", e);
+        }
+        #[cfg(target_os = "macos")]
+        spotlight::index_note(app, &root, &target);
+        automations::run(app, &root, "create", &target);
+    }
+
+    Ok(target.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+fn save_note(
+    file_path: String,
+    content: String,
+    app: tauri::AppHandle,
+    window: tauri::Window,
+) -> Result<(), locks::NoteWriteError> {
+    let origin = window.label().to_string();
+    let started = Instant::now();
+    network::guard(&app, &PathBuf::from(&file_path), move |app| {
+        write_note(
+            app,
+            &origin,
+            &file_path,
+            content,
+            &app.state::<WatcherState>(),
+            &app.state::<writing::WritingState>(),
+            &app.state::<activity::ActivityState>(),
+        )
+    })
+    .map_err(locks::NoteWriteError::io)??;
+    telemetry::record_timing(&app, "note_save", started.elapsed());
+    Ok(())
+}
+
+// Saves from outside a window (the local API) pass an `origin` no window has,
+// so every window showing the note reloads it
+pub fn write_note(
+    app: &tauri::AppHandle,
+    origin: &str,
+    file_path: &str,
+    content: String,
+    watcher_state: &WatcherState,
+    writing_state: &writing::WritingState,
+    activity_state: &activity::ActivityState,
+) -> Result<(), locks::NoteWriteError> {
+    let path = PathBuf::from(file_path);
+    storage::ensure_accessible(app, &path).map_err(locks::NoteWriteError::io)?;
+    if vault::vault_root(&path).is_some() {
+        vault::write_file(app, &path, &content).map_err(locks::NoteWriteError::io)?;
+        windows::emit_note_saved(app, origin, &path);
+        return Ok(());
+    }
+
+    let root = watcher_state.root_for(&path);
+
+    if root
+        .as_deref()
+        .is_some_and(|root| locks::is_locked(app, root, &path))
+    {
+        return Err(locks::NoteWriteError::locked(&path));
+    }
+
+    let previous = fs::read_to_string(&path).ok();
+
+    // Plaintext must never overwrite an encrypted note; those go through save_encrypted_note
+    if previous.as_deref().is_some_and(encryption::is_encrypted)
+        && !encryption::is_encrypted(&content)
+    {
+        return Err(locks::NoteWriteError::encrypted(&path));
+    }
+
+    // Overwriting truncates first, so only the growth needs free space
+    let growth = content
+        .len()
+        .saturating_sub(previous.as_ref().map_or(0, String::len));
+    disk_space::ensure_space(&path, growth as u64)
+        .map_err(locks::NoteWriteError::insufficient_space)?;
+    fs::write(&path, &content)
+        .map_err(|e| locks::NoteWriteError::io(i18n::tf("error.save_note", &[&e])))?;
+
+    if let Some(root) = root {
+        let previous_words = previous.as_deref().map_or(0, markdown::count_words);
+        let delta = markdown::count_words(&content) as i64 - previous_words as i64;
+        if let Err(e) = writing::record_word_delta(app, writing_state, &root, delta) {
+            tracing::error!("Failed to record writing progress: {}", e);
+        }
+
+        let kind = if previous.is_some() {
+            activity::ActivityKind::Edit
+        } else {
+            activity::ActivityKind::Create
+        };
+        if let Err(e) = activity::record_activity(app, activity_state, &root, &path, kind) {
+            tracing::error!("Failed to record activity: {}", e);
+        }
+        #[cfg(target_os = "macos")]
+        spotlight::index_note(app, &root, &path);
+        automations::run(app, &root, "save", &path);
+    }
+
+    windows::emit_note_saved(app, origin, &path);
+
+    Ok(())
+}
+
+// Adds `text` on its own lines after whatever the note already holds
+pub fn append_to_note(
+    app: &tauri::AppHandle,
+    origin: &str,
+    path: &Path,
+    text: &str,
+) -> Result<(), locks::NoteWriteError> {
+    let mut content = fs::read_to_string(path)
+        .map_err(|e| locks::NoteWriteError::io(i18n::tf("error.read_note", &[&e])))?;
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(text);
+    if !content.ends_with('\n') {
+        content.push('\n');
+    }
+    write_note(
+        app,
+        origin,
+        &path.to_string_lossy(),
+        content,
+        &app.state::<WatcherState>(),
+        &app.state::<writing::WritingState>(),
+        &app.state::<activity::ActivityState>(),
+    )
+}
+
+#[tauri::command]
+fn read_note(file_path: String, app: tauri::AppHandle) -> Result<String, String> {
+    network::guard_read(&app, &PathBuf::from(&file_path), move |app| {
+        let path = PathBuf::from(&file_path);
+        storage::ensure_accessible(app, &path)?;
+        if vault::vault_root(&path).is_some() {
+            return vault::read_file(app, &path);
+        }
+        fs::read_to_string(&path).map_err(|e| i18n::tf("error.read_note", &[&e]))
+    })?
+}
+
+fn update_metadata_after_move(
+    app: &tauri::AppHandle,
+    watcher_state: &WatcherState,
+    source: &Path,
+    target: &Path,
+) {
+    if let Some(root) = watcher_state.root_for(source) {
+        if let Err(e) = pins::rename_path(app, &root, source, target) {
+            tracing::error!("Failed to update pins: {}", e);
+        }
+        if let Err(e) = locks::rename_path(app, &root, source, target) {
+            tracing::error!("Failed to update note locks: {}", e);
+        }
+        pins::refresh_menu(app, &root);
+    }
+}
+
+#[tauri::command]
+fn rename_entry(
+    source_path: String,
+    new_name: String,
+    app: tauri::AppHandle,
+) -> Result<String, String> {
+    let source = PathBuf::from(&source_path);
+    network::guard(&app, &source, move |app| {
+        rename_entry_in(app, &source_path, &new_name)
+    })?
+}
+
+fn rename_entry_in(
+    app: &tauri::AppHandle,
+    source_path: &str,
+    new_name: &str,
+) -> Result<String, String> {
+    ensure_valid_name(new_name)?;
+
+    let source = PathBuf::from(source_path);
+    storage::ensure_accessible(app, &source)?;
+    let in_vault = vault::vault_root(&source).is_some();
+    if in_vault {
+        vault::ensure_unlocked(app, &source)?;
+    }
+    let exists = |p: &Path| {
+        if in_vault {
+            vault::exists(app, p)
+        } else {
+            p.exists()
+        }
+    };
+    if !exists(&source) {
+        return Err(i18n::t("error.source_missing"));
+    }
+
+    let current_name = source
+        .file_name()
+        .ok_or("Invalid source name")?
+        .to_string_lossy()
+        .to_string();
+
+    if current_name == new_name {
+        return Ok(source.to_string_lossy().to_string());
+    }
+
+    let parent = source.parent().ok_or("Cannot determine parent directory")?;
+    let is_dir = if in_vault {
+        vault::is_dir(app, &source)
+    } else {
+        source.is_dir()
+    };
+    let (target, _) = resolve_unique_path_with(parent, new_name, is_dir, exists)?;
+
+    if target == source {
+        return Ok(source.to_string_lossy().to_string());
+    }
+
+    if in_vault {
+        vault::rename(app, &source, &target)?;
+        return Ok(target.to_string_lossy().to_string());
+    }
+
+    fs::rename(&source, &target).map_err(|e| i18n::tf("error.rename_entry", &[&e]))?;
+    update_metadata_after_move(app, &app.state::<WatcherState>(), &source, &target);
+
+    Ok(target.to_string_lossy().to_string())
+}
+
+// Workspaces and folders over the delete confirmation limits need the token
+// from `delete_guard::preview_delete`
+#[tauri::command]
+fn delete_entry(
+    target_path: String,
+    confirm_token: Option<String>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    let path = PathBuf::from(&target_path);
+    network::guard(&app, &path, move |app| {
+        delete_entry_in(app, &target_path, confirm_token.as_deref())
+    })?
+}
+
+fn delete_entry_in(
+    app: &tauri::AppHandle,
+    target_path: &str,
+    confirm_token: Option<&str>,
+) -> Result<(), String> {
+    let path = PathBuf::from(target_path);
+    storage::ensure_accessible(app, &path)?;
+
+    if vault::vault_root(&path).is_some() {
+        vault::ensure_unlocked(app, &path)?;
+        if !vault::exists(app, &path) {
+            return Err(i18n::t("error.path_missing"));
+        }
+        let physical = vault::physical_location(app, &path)?;
+        delete_guard::check(app, &path, &physical, confirm_token)?;
+        return vault::remove(app, &path);
+    }
+
+    if !path.exists() {
+        return Err(i18n::t("error.path_missing"));
+    }
+    delete_guard::check(app, &path, &path, confirm_token)?;
+
+    secure_delete::remove(&path, app.state::<SettingsState>().current().secure_delete)?;
+
+    // Refreshing prunes any pin that pointed at the deleted entry
+    if let Some(root) = app.state::<WatcherState>().root_for(&path) {
+        pins::refresh_menu(app, &root);
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+fn move_entry(
+    source_path: String,
+    dest_folder_path: String,
+    app: tauri::AppHandle,
+) -> Result<String, String> {
+    let source = PathBuf::from(&source_path);
+    network::guard(&app, &source, move |app| {
+        move_entry_in(app, &source_path, &dest_folder_path)
+    })?
+}
+
+fn move_entry_in(
+    app: &tauri::AppHandle,
+    source_path: &str,
+    dest_folder_path: &str,
+) -> Result<String, String> {
+    let source = PathBuf::from(source_path);
+    let dest_folder = PathBuf::from(dest_folder_path);
+    storage::ensure_accessible(app, &source)?;
+    storage::ensure_accessible(app, &dest_folder)?;
+
+    let source_vault = vault::vault_root(&source);
+    if source_vault != vault::vault_root(&dest_folder) {
+        return Err(i18n::t("error.vault_move"));
+    }
+    if source_vault.is_some() {
+        return move_vault_entry(app, &source, &dest_folder);
+    }
+
+    if !source.exists() {
+        return Err(i18n::t("error.source_missing"));
+    }
+
+    if !dest_folder.exists() || !dest_folder.is_dir() {
+        return Err(i18n::t("error.destination_missing"));
+    }
+
+    if let Some(current_parent) = source.parent() {
+        if current_parent == dest_folder {
+            return Ok(source.to_string_lossy().to_string());
+        }
+    }
+
+    if source.is_dir() && dest_folder.starts_with(&source) {
+        return Err(i18n::t("error.move_into_itself"));
+    }
+
+    let file_name = source
+        .file_name()
+        .ok_or("Invalid source name")?
+        .to_string_lossy()
+        .to_string();
+    let is_dir = source.is_dir();
+    let (target, _) = resolve_unique_path(&dest_folder, &file_name, is_dir)?;
+
+    if source.is_dir() && target.starts_with(&source) {
+        return Err(i18n::t("error.move_into_itself"));
+    }
+
+    fs::rename(&source, &target).map_err(|e| i18n::tf("error.move_entry", &[&e]))?;
+    update_metadata_after_move(app, &app.state::<WatcherState>(), &source, &target);
+
+    Ok(target.to_string_lossy().to_string())
+}
+
+fn move_vault_entry(
+    app: &tauri::AppHandle,
+    source: &Path,
+    dest_folder: &Path,
+) -> Result<String, String> {
+    vault::ensure_unlocked(app, source)?;
+    if !vault::exists(app, source) {
+        return Err(i18n::t("error.source_missing"));
+    }
+    if !vault::is_dir(app, dest_folder) {
+        return Err(i18n::t("error.destination_missing"));
+    }
+    if source.parent() == Some(dest_folder) {
+        return Ok(source.to_string_lossy().to_string());
+    }
+    let is_dir = vault::is_dir(app, source);
+    if is_dir && dest_folder.starts_with(source) {
+        return Err(i18n::t("error.move_into_itself"));
+    }
+
+    let file_name = source
+        .file_name()
+        .ok_or("Invalid source name")?
+        .to_string_lossy()
+        .to_string();
+    let (target, _) =
+        resolve_unique_path_with(dest_folder, &file_name, is_dir, |p| vault::exists(app, p))?;
+    vault::rename(app, source, &target)?;
+
+    Ok(target.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+fn copy_entries_to_folder(
+    source_paths: Vec<String>,
+    dest_folder_path: String,
+    app: tauri::AppHandle,
+) -> Result<Vec<String>, disk_space::SpaceError> {
+    let dest_folder = PathBuf::from(&dest_folder_path);
+    // Copies grow with the data, so they get the bulk timeout
+    network::guard_bulk(&app, &dest_folder, move |app| {
+        copy_entries_in(app, source_paths, &dest_folder_path)
+    })?
+}
+
+fn copy_entries_in(
+    app: &tauri::AppHandle,
+    source_paths: Vec<String>,
+    dest_folder_path: &str,
+) -> Result<Vec<String>, disk_space::SpaceError> {
+    let dest_folder = PathBuf::from(dest_folder_path);
+    storage::ensure_accessible(app, &dest_folder)?;
+    for source in &source_paths {
+        storage::ensure_accessible(app, Path::new(source))?;
+    }
+
+    if vault::vault_root(&dest_folder).is_some()
+        || source_paths
+            .iter()
+            .any(|source| vault::vault_root(Path::new(source)).is_some())
+    {
+        return Err(i18n::t("error.vault_copy").into());
+    }
+
+    if !dest_folder.exists() || !dest_folder.is_dir() {
+        return Err(i18n::t("error.destination_missing").into());
+    }
+
+    // Checked up front so a copy never stops halfway with partial files
+    let required = source_paths
+        .iter()
+        .map(|source| disk_space::size_on_disk(Path::new(source)))
+        .sum();
+    disk_space::ensure_space(&dest_folder, required)?;
+
+    let mut new_paths = Vec::new();
+
+    for source_path in source_paths {
+        let source = PathBuf::from(&source_path);
+
+        if !source.exists() {
+            continue; // Skip non-existent sources
+        }
+
+        let file_name = source
+            .file_name()
+            .ok_or("Invalid source name")?
+            .to_string_lossy()
+            .to_string();
+
+        let is_dir = source.is_dir();
+        let (target, _) = resolve_unique_path(&dest_folder, &file_name, is_dir)?;
+
+        // Copy directory or file
+        if is_dir {
+            copy_dir_all(&source, &target).map_err(|e| i18n::tf("error.copy_directory", &[&e]))?;
+        } else {
+            fs::copy(&source, &target).map_err(|e| i18n::tf("error.copy_file", &[&e]))?;
+        }
+
+        new_paths.push(target.to_string_lossy().to_string());
+    }
+
+    Ok(new_paths)
+}
+
+// Helper function to recursively copy directories
+fn copy_dir_all(src: &Path, dst: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dst)?;
+
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+
+        if file_type.is_dir() {
+            copy_dir_all(&src_path, &dst_path)?;
+        } else {
+            fs::copy(&src_path, &dst_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+fn scan_folder_for_markdown(
+    folder_path: String,
+    include_archived: Option<bool>,
+    app: tauri::AppHandle,
+) -> Result<Vec<MarkdownFile>, String> {
+    let path = PathBuf::from(&folder_path);
+    let started = Instant::now();
+    let files = network::guard_read(&app, &path, move |app| {
+        scan_folder(
+            &folder_path,
+            include_archived,
+            app,
+            &app.state::<SettingsState>(),
+        )
+    })??;
+    telemetry::record_timing(&app, "workspace_scan", started.elapsed());
+    Ok(files)
+}
+
+fn scan_folder(
+    folder_path: &str,
+    include_archived: Option<bool>,
+    app: &tauri::AppHandle,
+    settings_state: &SettingsState,
+) -> Result<Vec<MarkdownFile>, String> {
+    let path = PathBuf::from(folder_path);
+    storage::ensure_accessible(app, &path)?;
+
+    if !path.exists() {
+        return Err(i18n::t("error.folder_missing"));
+    }
+
+    if !path.is_dir() {
+        return Err(i18n::t("error.not_a_directory"));
+    }
+
+    let mut markdown_files = Vec::new();
+
+    fn scan_directory(
+        dir: &PathBuf,
+        filter: &NoteFilter,
+        locked: &HashSet<PathBuf>,
+        files: &mut Vec<MarkdownFile>,
+    ) -> Result<(), String> {
+        let entries = fs::read_dir(dir).map_err(|e| i18n::tf("error.read_directory", &[&e]))?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| i18n::tf("error.read_entry", &[&e]))?;
+            let path = entry.path();
+            let file_name = entry.file_name().to_string_lossy().to_string();
+
+            if filter.is_ignored(&path) {
+                continue;
+            }
+
+            if path.is_dir() {
+                files.push(MarkdownFile {
+                    name: file_name,
+                    path: path.to_string_lossy().to_string(),
+                    is_dir: true,
+                    archived: filter.is_archived(&path),
+                    locked: false,
+                });
+
+                scan_directory(&path, filter, locked, files)?;
+            } else if path.is_file() && filter.includes_note(&path) {
+                files.push(MarkdownFile {
+                    name: file_name,
+                    path: path.to_string_lossy().to_string(),
+                    is_dir: false,
+                    archived: filter.is_archived(&path),
+                    locked: locked.contains(&path),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    let filter = NoteFilter::for_workspace(&path, &settings_state.current())
+        .including_archived(include_archived.unwrap_or(false));
+
+    // Vault entries are listed by their decrypted names; matching still runs on those
+    if vault::is_vault_root(&path) {
+        for (entry, is_dir) in vault::list(app, &path)? {
+            if filter.is_ignored(&entry) || (!is_dir && !filter.includes_note(&entry)) {
+                continue;
+            }
+            markdown_files.push(MarkdownFile {
+                name: entry
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                path: entry.to_string_lossy().to_string(),
+                is_dir,
+                archived: filter.is_archived(&entry),
+                locked: false,
+            });
+        }
+        return Ok(markdown_files);
+    }
+
+    let locked = locks::locked_paths(app, &path);
+    scan_directory(&path, &filter, &locked, &mut markdown_files)?;
+
+    Ok(markdown_files)
+}
+
+// NEW: Command to show the main window
+#[tauri::command]
+async fn show_main_window(window: tauri::Window) {
+    let _ = window.show();
+}
+
+#[derive(Debug, Deserialize)]
+struct RecentNoteInfo {
+    _name: String,
+    _path: String,
+}
+
+#[tauri::command]
+async fn update_dock_menu(
+    _app: tauri::AppHandle,
+    _recent_notes: Vec<RecentNoteInfo>,
+) -> Result<(), String> {
+    // Note: Tauri v2 doesn't have direct dock menu support yet
+    // This is a placeholder for future implementation or use of native APIs
+    // For now, we'll just log the recent notes
+
+    // You could integrate with macOS native APIs here using objc crate if needed
+    // For this MVP, we'll rely on the sidebar UI for recent notes
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn open_recent_note(
+    path: String,
+    app: tauri::AppHandle,
+    window: tauri::Window,
+) -> Result<(), String> {
+    app.emit_to(window.label(), "open-recent-note", path)
+        .map_err(|e| i18n::tf("error.emit_event", &[&e]))?;
+    Ok(())
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    let safe_mode = safe_mode::requested();
+
+    let builder = tauri::Builder::default();
+
+    // Mobile apps update through their store and have no menu bar
+    #[cfg(desktop)]
+    let builder = builder
+        .plugin(tauri_plugin_updater::Builder::new().build())
+        .manage(updater::UpdaterState::default())
+        .on_menu_event(|app, event| {
+            let event_id = event.id().as_ref();
+
+            // recent note clicks (future use)
+            if event_id.starts_with("recent://") {
+                let path = event_id.strip_prefix("recent://").unwrap_or("");
+                windows::emit_to_focused(app, "open-recent-note", path.to_string());
+                return;
+            }
+
+            if custom_menu::handle(app, event_id) {
+                return;
+            }
+
+            // `plugin://<plugin>/<command>` items run a command in the plugin's worker,
+            // which lives in the main window whichever window is focused
+            if let Some((plugin, command)) = event_id
+                .strip_prefix("plugin://")
+                .and_then(|rest| rest.split_once('/'))
+            {
+                let _ = app.emit_to(
+                    windows::MAIN_WINDOW,
+                    "plugin-command",
+                    serde_json::json!({ "plugin": plugin, "command": command }),
+                );
+                return;
+            }
+
+            match event_id {
+                "menu://new-note" => {
+                    windows::emit_to_focused(app, "menu://new-note", ());
+                }
+                "menu://new-folder" => {
+                    windows::emit_to_focused(app, "menu://new-folder", ());
+                }
+                "menu://open-file" => {
+                    windows::emit_to_focused(app, "menu://open-file", ());
+                }
+                "menu://open-folder" => {
+                    windows::emit_to_focused(app, "menu://open-folder", ());
+                }
+                "menu://save-note" => {
+                    windows::emit_to_focused(app, "menu://save-note", ());
+                }
+                "menu://close-note" => {
+                    windows::emit_to_focused(app, "menu://close-note", ());
+                }
+                "menu://export-note" => {
+                    windows::emit_to_focused(app, "menu://export-note", ());
+                }
+                "menu://backup-workspace" => {
+                    windows::emit_to_focused(app, "menu://backup-workspace", ());
+                }
+                "menu://search" => {
+                    windows::emit_to_focused(app, "menu://search", ());
+                }
+                "menu://command-palette" => {
+                    windows::emit_to_focused(app, "menu://command-palette", ());
+                }
+                "menu://toggle-sidebar" => {
+                    windows::emit_to_focused(app, "menu://toggle-sidebar", ());
+                }
+                "menu://view-editor" => {
+                    windows::emit_to_focused(app, "menu://view-editor", ());
+                }
+                "menu://view-split" => {
+                    windows::emit_to_focused(app, "menu://view-split", ());
+                }
+                "menu://view-preview" => {
+                    windows::emit_to_focused(app, "menu://view-preview", ());
+                }
+                "menu://focus-mode" => {
+                    windows::emit_to_focused(app, "menu://focus-mode", ());
+                }
+                "menu://open-graph" => {
+                    windows::emit_to_focused(app, "menu://open-graph", ());
+                }
+                "menu://open-settings" => {
+                    windows::emit_to_focused(app, "menu://open-settings", ());
+                }
+                "menu://show-shortcuts" => {
+                    windows::emit_to_focused(app, "menu://show-shortcuts", ());
+                }
+                _ => {}
+            }
+        });
+
+    builder
+        .manage(WatcherState::default())
+        .manage(delete_guard::DeleteGuardState::default())
+        .manage(export::ExportState::default())
+        .manage(IndexState::default())
+        .manage(stats::StatsCache::default())
+        .manage(writing::WritingState::default())
+        .manage(activity::ActivityState::default())
+        .manage(windows::WindowRegistry::default())
+        .manage(pins::PinsState::default())
+        .manage(locks::LocksState::default())
+        .manage(edit_locks::EditLockState::default())
+        .manage(vault::VaultState::default())
+        .manage(safe_mode::SafeModeState::new(safe_mode))
+        .manage(notifications::NotificationState::default())
+        .manage(ics::IcsState::default())
+        .manage(plugins::PluginsState::default())
+        .manage(automations::AutomationsState::default())
+        .manage(badge::BadgeState::default())
+        .manage(feeds::FeedsState::default())
+        .manage(task_sync::TaskSyncState::default())
+        .manage(api::ApiState::default())
+        .manage(network::NetworkState::default())
+        .manage(note_ids::NoteIdState::default())
+        .manage(smart_folders::SmartFolderState::default())
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_notification::init())
+        .register_uri_scheme_protocol(export::SCHEME, export::serve)
+        .on_window_event(|window, event| {
+            window_state::handle_window_event(window, event);
+            windows::handle_window_event(window, event);
+        })
+        .invoke_handler(tauri::generate_handler![
+            scan_folder_for_markdown,
+            create_folder,
+            create_markdown_file,
+            save_note,
+            read_note,
+            rename_entry,
+            delete_entry,
+            delete_guard::preview_delete,
+            move_entry,
+            copy_entries_to_folder,
+            disk_space::check_disk_space,
+            documents::import_documents,
+            archive::archive_note,
+            archive::unarchive_note,
+            locks::set_note_locked,
+            encryption::encrypt_note,
+            encryption::decrypt_note,
+            encryption::save_encrypted_note,
+            encryption::remove_note_encryption,
+            vault::create_vault,
+            vault::unlock_vault,
+            vault::lock_vault,
+            vault::get_vault_status,
+            watcher::watch_folder,
+            watcher::stop_watching,
+            watcher::get_watcher_status,
+            diagnostics::generate_diagnostics,
+            profiling::profile_workspace,
+            logging::get_recent_logs,
+            logging::set_log_level,
+            notifications::send_notification,
+            safe_mode::get_safe_mode,
+            safe_mode::leave_safe_mode,
+            safe_mode::reset_app_state,
+            #[cfg(desktop)]
+            updater::check_for_updates,
+            #[cfg(desktop)]
+            updater::install_update,
+            show_main_window,
+            update_dock_menu,
+            open_recent_note,
+            stats::get_workspace_stats,
+            writing::get_writing_streak,
+            writing::set_daily_goal,
+            activity::get_activity_heatmap,
+            calendar::get_notes_by_date,
+            calendar::get_adjacent_daily_note,
+            ics::list_todays_events,
+            ics::create_note_from_event,
+            ics::export_tasks_ics,
+            email::email_note,
+            embeds::compose_note,
+            graph::export_graph,
+            export::export_formats,
+            export::export_note,
+            export::export_folder,
+            share::share_note,
+            drag::drag_note,
+            clipboard::copy_as_rich_text,
+            link_titles::fetch_url_title,
+            link_previews::fetch_link_preview,
+            link_previews::clear_link_previews,
+            diff::diff_text,
+            diff::diff_notes,
+            diff::diff_against_head,
+            git::get_git_statuses,
+            search::search_workspace,
+            merge::merge_texts,
+            profile::export_profile,
+            profile::import_profile,
+            sample_workspace::create_sample_workspace,
+            telemetry::record_feature_use,
+            telemetry::record_feature_timing,
+            telemetry::get_telemetry_preview,
+            telemetry::clear_telemetry,
+            duplicates::find_duplicate_notes,
+            duplicates::find_duplicate_files,
+            duplicates::merge_duplicate_files,
+            integrity::verify_workspace,
+            related::get_related_notes,
+            links::resolve_note_link,
+            links::get_quick_switcher_index,
+            links::get_link_candidates,
+            mentions::get_unlinked_mentions,
+            mentions::link_unlinked_mentions,
+            blocks::ensure_block_id,
+            blocks::get_block_references,
+            reminders::list_reminders,
+            badge::set_badge,
+            secrets::store_secret,
+            secrets::get_secret,
+            secrets::delete_secret,
+            session::save_session,
+            session::load_session,
+            settings::get_settings,
+            settings::set_setting,
+            snippets::list_snippets,
+            snippets::expand_snippet,
+            keymap::get_keymap,
+            keymap::set_shortcut,
+            keymap::reset_keymap,
+            pins::pin_entry,
+            pins::list_pinned,
+            plugins::list_plugins,
+            plugins::get_plugins_folder,
+            plugins::enable_plugin,
+            plugins::load_plugin,
+            plugins::plugin_request,
+            workspace_config::get_workspace_config,
+            workspace_config::set_workspace_config,
+            workspace_config::get_spellcheck_ignore,
+            workspace_config::add_spellcheck_ignore,
+            workspace_config::remove_spellcheck_ignore,
+            automations::get_automations,
+            automations::set_automations,
+            automations::dry_run_automations,
+            automations::run_automation,
+            automations::get_automation_log,
+            automations::clear_automation_log,
+            conflicts::save_conflict_copy,
+            edit_locks::acquire_note_lock,
+            edit_locks::release_note_lock,
+            network::get_offline_workspaces,
+            feeds::get_feeds,
+            feeds::set_feeds,
+            feeds::get_feed_status,
+            feeds::refresh_feeds,
+            feeds::mark_feed_read,
+            tags::add_tag_to_notes,
+            tags::remove_tag_from_notes,
+            tags::rename_tag,
+            tags::get_tag_tree,
+            tags::get_notes_by_tag,
+            schema::validate_frontmatter,
+            query::query_notes,
+            properties::get_note_properties,
+            properties::set_note_property,
+            properties::remove_note_property,
+            note_ids::ensure_note_id,
+            note_ids::resolve_note_id,
+            smart_folders::list_smart_folders,
+            smart_folders::get_smart_folder_contents,
+            smart_folders::save_smart_folder,
+            smart_folders::delete_smart_folder,
+            task_sync::get_task_sync,
+            task_sync::set_task_sync,
+            task_sync::get_task_sync_status,
+            task_sync::sync_tasks,
+            api::get_api_status,
+            api::get_api_token,
+            api::regenerate_api_token,
+            api::get_clipper_token,
+            api::regenerate_clipper_token,
+            api::get_tasks_feed_url,
+            api::regenerate_tasks_feed_token,
+            workspaces::list_workspaces,
+            workspaces::add_workspace,
+            workspaces::open_workspace,
+            workspaces::get_recent_workspaces,
+            workspaces::webview_ready,
+            workspaces::remove_workspace,
+            windows::open_note_in_new_window,
+            windows::open_workspace_window
+        ])
+        .setup(move |app| {
+            diagnostics::install_panic_hook(app.handle());
+            let settings_state = SettingsState::load(app.handle());
+            if let Err(e) = logging::init(app.handle(), &settings_state.current().log_level) {
+                // Nothing else can report this, so it goes straight to stderr
+                eprintln!("Failed to initialize logging: {}", e);
+            }
+            i18n::set_locale(&settings_state.current().locale);
+            app.manage(keymap::KeymapState::load(app.handle()));
+            app.manage(telemetry::TelemetryState::load(app.handle()));
+            #[cfg(desktop)]
+            app.set_menu(menu::build(app.handle())?)?;
+            app.state::<IndexState>()
+                .set_settings(settings_state.current());
+            app.manage(settings_state);
+            app.manage(workspaces::WorkspaceRegistryState::load(app.handle()));
+
+            // Safe mode starts at the default size in case the saved geometry is the problem
+            let window_store = window_state::WindowStateStore::load(app.handle());
+            if let Some(window) = app.get_webview_window("main") {
+                if safe_mode {
+                    tracing::warn!("Starting in safe mode");
+                } else {
+                    window_state::restore(&window, &window_store);
+                }
+            }
+            app.manage(window_store);
+            if !safe_mode {
+                api::apply(app.handle(), &app.state::<SettingsState>().current().api);
+            }
+            vault::spawn_auto_lock(app.handle().clone());
+            edit_locks::spawn_heartbeat(app.handle().clone());
+            reminders::spawn_scheduler(app.handle().clone());
+            badge::spawn_scheduler(app.handle().clone());
+            #[cfg(target_os = "macos")]
+            {
+                spotlight::handle_activities(app.handle());
+                spotlight::index_registered(app.handle());
+            }
+            feeds::spawn_scheduler(app.handle().clone());
+            task_sync::spawn_scheduler(app.handle().clone());
+            email::clear_drafts(app.handle());
+            share::clear_exports(app.handle());
+
+            #[cfg(all(desktop, not(target_os = "macos")))]
+            {
+                let window = app.get_webview_window("main").unwrap();
+                let _ = window.set_decorations(false);
+            }
+
+            #[cfg(debug_assertions)]
+            {
+                let window = app.get_webview_window("main").unwrap();
+                window.open_devtools();
+            }
+            Ok(())
+        })
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // `marky://open?path=...` links from other apps
+            #[cfg(target_os = "macos")]
+            if let tauri::RunEvent::Opened { urls } = &event {
+                for url in urls.iter().filter(|url| url.scheme() == "marky") {
+                    spotlight::open_link(app, url.as_str());
+                }
+            }
+            if let tauri::RunEvent::Exit = event {
+                edit_locks::release_all(app);
+                if !app.state::<safe_mode::SafeModeState>().should_persist() {
+                    return;
+                }
+                if let Err(e) = app.state::<window_state::WindowStateStore>().save(app) {
+                    tracing::error!("Failed to save window state: {}", e);
+                }
+            }
+        });
+}
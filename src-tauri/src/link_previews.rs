@@ -0,0 +1,386 @@
+use crate::http;
+use crate::link_titles;
+use crate::settings::{LinkPreviewSettings, SettingsState};
+use crate::storage;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, Url};
+
+const CACHE_DIR: &str = "link_previews";
+// Cards are refreshed about weekly; stale ones are still shown while offline
+const MAX_AGE_MS: i64 = 7 * 24 * 60 * 60 * 1000;
+const IMAGE_TIMEOUT: Duration = Duration::from_secs(10);
+// Cards show a thumbnail, so anything larger is left out rather than shrunk
+const MAX_IMAGE_BYTES: usize = 2 * 1024 * 1024;
+const MAX_TITLE_CHARS: usize = 200;
+const MAX_DESCRIPTION_CHARS: usize = 300;
+
+// What a card or hover preview shows; `image` is a data URL
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LinkPreview {
+    pub url: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub site_name: Option<String>,
+    pub image: Option<String>,
+}
+
+// One cache entry; the image sits next to it under `image_file`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct CachedPreview {
+    // Unix milliseconds; 0 for an unreadable entry
+    fetched_at: i64,
+    url: String,
+    title: Option<String>,
+    description: Option<String>,
+    site_name: Option<String>,
+    image_file: Option<String>,
+    image_type: Option<String>,
+}
+
+fn cache_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = storage::app_data_dir(app)?.join(CACHE_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create link preview cache: {}", e))?;
+    Ok(dir)
+}
+
+fn cache_key(url: &str) -> String {
+    format!("{:x}", Sha256::digest(url.as_bytes()))
+        .chars()
+        .take(32)
+        .collect()
+}
+
+fn allowed(settings: &LinkPreviewSettings, url: &Url) -> bool {
+    if settings.allowlist.is_empty() {
+        return true;
+    }
+    let Some(host) = url.host_str() else {
+        return false;
+    };
+    let host = host.to_lowercase();
+    settings.allowlist.iter().any(|allowed| {
+        host == *allowed
+            || host
+                .strip_suffix(allowed.as_str())
+                .is_some_and(|rest| rest.ends_with('.'))
+    })
+}
+
+fn is_fresh(entry: &CachedPreview) -> bool {
+    Utc::now().timestamp_millis() - entry.fetched_at < MAX_AGE_MS
+}
+
+// The first of `selectors` with a non-empty `content`
+fn meta(document: &dom_query::Document, selectors: &[&str], max: usize) -> Option<String> {
+    selectors.iter().find_map(|selector| {
+        document
+            .select(selector)
+            .attr("content")
+            .and_then(|content| link_titles::clean(&content, max))
+    })
+}
+
+// Open Graph first, then Twitter cards, then the plain HTML equivalents
+fn parse(html: &str, page_url: &Url) -> (CachedPreview, Option<Url>) {
+    let document = dom_query::Document::from(html);
+    let title = meta(
+        &document,
+        &["meta[property='og:title']", "meta[name='twitter:title']"],
+        MAX_TITLE_CHARS,
+    )
+    .or_else(|| link_titles::clean(&document.select("title").first().text(), MAX_TITLE_CHARS));
+    let description = meta(
+        &document,
+        &[
+            "meta[property='og:description']",
+            "meta[name='twitter:description']",
+            "meta[name='description']",
+        ],
+        MAX_DESCRIPTION_CHARS,
+    );
+    let site_name = meta(
+        &document,
+        &["meta[property='og:site_name']"],
+        MAX_TITLE_CHARS,
+    );
+    // Images are often given relative to the page
+    let image = meta(
+        &document,
+        &[
+            "meta[property='og:image:secure_url']",
+            "meta[property='og:image']",
+            "meta[name='twitter:image']",
+            "meta[name='twitter:image:src']",
+        ],
+        usize::MAX,
+    )
+    .and_then(|image| page_url.join(&image).ok())
+    .filter(|image| matches!(image.scheme(), "http" | "https"));
+
+    let preview = CachedPreview {
+        fetched_at: Utc::now().timestamp_millis(),
+        url: page_url.to_string(),
+        title,
+        description,
+        site_name,
+        image_file: None,
+        image_type: None,
+    };
+    (preview, image)
+}
+
+// Raster images only; SVGs can carry scripts and rarely make good thumbnails
+async fn fetch_image(
+    settings: &LinkPreviewSettings,
+    url: &Url,
+) -> Result<(String, Vec<u8>), String> {
+    let prefix = http::get_prefix(
+        url.as_str(),
+        MAX_IMAGE_BYTES + 1,
+        IMAGE_TIMEOUT,
+        |url| allowed(settings, url),
+        |_| false,
+    )
+    .await?;
+    let kind = prefix
+        .content_type
+        .as_deref()
+        .and_then(|kind| kind.split(';').next())
+        .map(|kind| kind.trim().to_lowercase())
+        .unwrap_or_default();
+    if !kind.starts_with("image/") || kind.contains("svg") {
+        return Err(format!("{} isn't a raster image", url));
+    }
+    if prefix.bytes.len() > MAX_IMAGE_BYTES {
+        return Err(format!("{} is too large for a preview", url));
+    }
+    Ok((kind, prefix.bytes))
+}
+
+async fn fetch(
+    settings: &LinkPreviewSettings,
+    dir: &Path,
+    key: &str,
+    url: &str,
+) -> Result<CachedPreview, String> {
+    let head = link_titles::get_head(
+        url,
+        |url| allowed(settings, url),
+        |bytes| link_titles::contains_ignore_case(bytes, b"</head"),
+    )
+    .await?;
+    let page_url = Url::parse(&head.url).map_err(|e| format!("Invalid URL {}: {}", head.url, e))?;
+    let (mut entry, image) = parse(&head.text, &page_url);
+
+    // A card without its image is still worth showing
+    if let Some(image) = image {
+        match fetch_image(settings, &image).await {
+            Ok((kind, bytes)) => {
+                let file = format!("{}.img", key);
+                match fs::write(dir.join(&file), bytes) {
+                    Ok(()) => {
+                        entry.image_file = Some(file);
+                        entry.image_type = Some(kind);
+                    }
+                    Err(e) => tracing::warn!("Failed to cache preview image for {}: {}", url, e),
+                }
+            }
+            Err(e) => tracing::debug!("No preview image for {}: {}", url, e),
+        }
+    }
+
+    storage::write_json(&dir.join(format!("{}.json", key)), &entry)?;
+    Ok(entry)
+}
+
+fn to_preview(dir: &Path, entry: CachedPreview) -> LinkPreview {
+    let image = entry.image_file.as_ref().and_then(|file| {
+        let bytes = fs::read(dir.join(file)).ok()?;
+        Some(format!(
+            "data:{};base64,{}",
+            entry.image_type.as_deref().unwrap_or("image/png"),
+            BASE64.encode(bytes)
+        ))
+    });
+    LinkPreview {
+        url: entry.url,
+        title: entry.title,
+        description: entry.description,
+        site_name: entry.site_name,
+        image,
+    }
+}
+
+// Title, description, site name and image for the web page at `url`, from
+// its Open Graph or Twitter card tags, for link cards and hover previews.
+// Results are cached on disk; with previews set to offline only the cache is
+// read, and hosts outside a non-empty allowlist are never contacted, whether
+// a page redirects to them or names them for its image.
+#[tauri::command]
+pub async fn fetch_link_preview(url: String, app: AppHandle) -> Result<LinkPreview, String> {
+    let settings = app.state::<SettingsState>().current().link_previews;
+    if !settings.enabled {
+        return Err("Link previews are turned off".to_string());
+    }
+    let url = url.trim();
+    if !link_titles::is_web_url(url) {
+        return Err("Only http and https links have previews".to_string());
+    }
+    let parsed = Url::parse(url).map_err(|e| format!("Invalid URL {}: {}", url, e))?;
+    if !allowed(&settings, &parsed) {
+        return Err(format!(
+            "{} isn't on the link preview allowlist",
+            parsed.host_str().unwrap_or(url)
+        ));
+    }
+
+    let dir = cache_dir(&app)?;
+    let key = cache_key(url);
+    let cached = dir.join(format!("{}.json", key));
+    let entry: Option<CachedPreview> = cached
+        .exists()
+        .then(|| storage::read_json(&cached))
+        .filter(|entry: &CachedPreview| entry.fetched_at > 0);
+
+    let entry = match entry {
+        Some(entry) if settings.offline || is_fresh(&entry) => entry,
+        _ if settings.offline => {
+            return Err(format!("No cached preview for {} while offline", url))
+        }
+        // A stale card beats none when the site can't be reached
+        Some(entry) => match fetch(&settings, &dir, &key, url).await {
+            Ok(fresh) => fresh,
+            Err(e) => {
+                tracing::debug!("Failed to refresh preview for {}: {}", url, e);
+                entry
+            }
+        },
+        None => fetch(&settings, &dir, &key, url).await?,
+    };
+    Ok(to_preview(&dir, entry))
+}
+
+// Drops every cached preview and image
+#[tauri::command]
+pub fn clear_link_previews(app: AppHandle) -> Result<(), String> {
+    let dir = storage::app_data_dir(&app)?.join(CACHE_DIR);
+    if dir.exists() {
+        fs::remove_dir_all(&dir).map_err(|e| format!("Failed to clear link previews: {}", e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    // Serves a page on 127.0.0.1 whose image, like the page `/redirect`
+    // sends to, is on `localhost`: the same server under a host the
+    // allowlist doesn't name. Returns the port and the paths requested.
+    fn serve() -> (u16, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let requested = Arc::new(Mutex::new(Vec::new()));
+        let log = requested.clone();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut request = Vec::new();
+                let mut buffer = [0; 1024];
+                while !request.windows(4).any(|end| end == b"\r\n\r\n") {
+                    match stream.read(&mut buffer) {
+                        Ok(0) | Err(_) => break,
+                        Ok(read) => request.extend_from_slice(&buffer[..read]),
+                    }
+                }
+                let request = String::from_utf8_lossy(&request);
+                let path = request.split(' ').nth(1).unwrap_or("").to_string();
+                log.lock().unwrap().push(path.clone());
+                let (status, headers, body) = match path.as_str() {
+                    "/redirect" => (
+                        "302 Found",
+                        format!("Location: http://localhost:{}/page\r\n", port),
+                        String::new(),
+                    ),
+                    "/page" => (
+                        "200 OK",
+                        "Content-Type: text/html\r\n".to_string(),
+                        format!(
+                            "<html><head><title>Page</title><meta property='og:image' \
+                             content='http://localhost:{}/image.png'></head></html>",
+                            port
+                        ),
+                    ),
+                    _ => (
+                        "200 OK",
+                        "Content-Type: image/png\r\n".to_string(),
+                        "png".to_string(),
+                    ),
+                };
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    headers,
+                    body.len(),
+                    body
+                );
+            }
+        });
+        (port, requested)
+    }
+
+    fn local_only() -> LinkPreviewSettings {
+        LinkPreviewSettings {
+            allowlist: vec!["127.0.0.1".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn cache(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "marky-link-previews-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn redirects_off_the_allowlist_are_not_followed() {
+        let (port, requested) = serve();
+        let dir = cache("redirect");
+        let url = format!("http://127.0.0.1:{}/redirect", port);
+
+        let result = tauri::async_runtime::block_on(fetch(&local_only(), &dir, "redirect", &url));
+        assert!(result.is_err());
+        assert_eq!(*requested.lock().unwrap(), vec!["/redirect".to_string()]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn images_off_the_allowlist_are_not_fetched() {
+        let (port, requested) = serve();
+        let dir = cache("image");
+        let url = format!("http://127.0.0.1:{}/page", port);
+
+        let entry =
+            tauri::async_runtime::block_on(fetch(&local_only(), &dir, "image", &url)).unwrap();
+        assert_eq!(entry.title.as_deref(), Some("Page"));
+        assert_eq!(entry.image_file, None);
+        assert_eq!(*requested.lock().unwrap(), vec!["/page".to_string()]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
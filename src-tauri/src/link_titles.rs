@@ -0,0 +1,170 @@
+use crate::http;
+use std::time::Duration;
+use tauri::Url;
+
+// Pasting waits on this, so slow sites give up well before the usual timeout
+const TIMEOUT: Duration = Duration::from_secs(8);
+// Titles sit in the head, which is rarely anywhere near this large
+const MAX_HEAD_BYTES: usize = 256 * 1024;
+const MAX_TITLE_CHARS: usize = 200;
+
+// What windows-1252 puts at 0x80..=0x9F, where ISO-8859-1 has control
+// characters; pages labelled Latin-1 are decoded as windows-1252, as browsers do
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20ac}', '\u{81}', '\u{201a}', '\u{192}', '\u{201e}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2c6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8d}', '\u{17d}', '\u{8f}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201c}', '\u{201d}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2dc}', '\u{2122}', '\u{161}', '\u{203a}', '\u{153}', '\u{9d}', '\u{17e}', '\u{178}',
+];
+
+pub fn contains_ignore_case(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window.eq_ignore_ascii_case(needle))
+}
+
+// `charset=...` from a Content-Type header or a `<meta>` tag
+fn charset_param(text: &str) -> Option<String> {
+    let lower = text.to_lowercase();
+    let start = lower.find("charset=")? + "charset=".len();
+    let charset: String = lower[start..]
+        .trim_start_matches(['"', '\''])
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'))
+        .collect();
+    (!charset.is_empty()).then_some(charset)
+}
+
+// Pages that don't say in their headers usually do in a `<meta>` near the top
+fn meta_charset(bytes: &[u8]) -> Option<String> {
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(4096)]).to_lowercase();
+    head.match_indices("<meta")
+        .filter_map(|(start, _)| {
+            let tag = &head[start..];
+            charset_param(&tag[..tag.find('>').unwrap_or(tag.len())])
+        })
+        .next()
+}
+
+fn decode_utf16(bytes: &[u8], little_endian: bool) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| {
+            if little_endian {
+                u16::from_le_bytes([pair[0], pair[1]])
+            } else {
+                u16::from_be_bytes([pair[0], pair[1]])
+            }
+        })
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+fn decode(bytes: &[u8], content_type: Option<&str>) -> Result<String, String> {
+    // A byte-order mark outranks whatever the page claims
+    if let Some(rest) = bytes.strip_prefix(&[0xef, 0xbb, 0xbf]) {
+        return Ok(String::from_utf8_lossy(rest).to_string());
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xff, 0xfe]) {
+        return Ok(decode_utf16(rest, true));
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xfe, 0xff]) {
+        return Ok(decode_utf16(rest, false));
+    }
+
+    let charset = content_type
+        .and_then(charset_param)
+        .or_else(|| meta_charset(bytes))
+        .unwrap_or_else(|| "utf-8".to_string());
+    match charset.as_str() {
+        "utf-8" | "utf8" | "unicode-1-1-utf-8" => Ok(String::from_utf8_lossy(bytes).to_string()),
+        "utf-16" | "utf-16le" => Ok(decode_utf16(bytes, true)),
+        "utf-16be" => Ok(decode_utf16(bytes, false)),
+        "iso-8859-1" | "latin1" | "l1" | "iso_8859-1" | "windows-1252" | "cp1252" | "us-ascii"
+        | "ascii" => Ok(bytes
+            .iter()
+            .map(|&byte| match byte {
+                0x80..=0x9f => WINDOWS_1252_HIGH[(byte - 0x80) as usize],
+                byte => byte as char,
+            })
+            .collect()),
+        // Anything else only works out if it happens to be UTF-8 anyway
+        other => String::from_utf8(bytes.to_vec())
+            .map_err(|_| format!("Unsupported page encoding: {}", other)),
+    }
+}
+
+// The document's `<title>`, or its Open Graph title when that's empty
+fn page_title(html: &str) -> Option<String> {
+    let document = dom_query::Document::from(html);
+    clean(&document.select("title").first().text(), MAX_TITLE_CHARS).or_else(|| {
+        document
+            .select("meta[property='og:title']")
+            .attr("content")
+            .and_then(|title| clean(&title, MAX_TITLE_CHARS))
+    })
+}
+
+pub fn is_web_url(url: &str) -> bool {
+    let url = url.to_lowercase();
+    ["http://", "https://"]
+        .iter()
+        .any(|scheme| url.starts_with(scheme))
+}
+
+// A page's head as text, and the URL it ended up at
+pub struct Head {
+    pub url: String,
+    pub text: String,
+}
+
+// Fetches the start of the page at `url` until `done`, decoded with whatever
+// charset it declares. Only URLs `allow` accepts are fetched, redirects included.
+pub async fn get_head(
+    url: &str,
+    allow: impl Fn(&Url) -> bool,
+    done: impl Fn(&[u8]) -> bool,
+) -> Result<Head, String> {
+    let prefix = http::get_prefix(url, MAX_HEAD_BYTES, TIMEOUT, allow, done).await?;
+    if prefix.content_type.as_deref().is_some_and(|kind| {
+        let kind = kind.to_lowercase();
+        !kind.contains("html") && !kind.starts_with("text/")
+    }) {
+        return Err(format!("{} isn't a web page", url));
+    }
+    Ok(Head {
+        text: decode(&prefix.bytes, prefix.content_type.as_deref())?,
+        url: prefix.url,
+    })
+}
+
+// Whitespace-collapsed, shortened to `max` characters, or None when empty
+pub fn clean(text: &str, max: usize) -> Option<String> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        return None;
+    }
+    if text.chars().count() <= max {
+        return Some(text);
+    }
+    let cut: String = text.chars().take(max - 1).collect();
+    Some(format!("{}\u{2026}", cut.trim_end()))
+}
+
+// The title of the page at `url`, so a pasted link can become
+// `[Page Title](url)`. Redirects are followed; only the page's head is read.
+#[tauri::command]
+pub async fn fetch_url_title(url: String) -> Result<String, String> {
+    let url = url.trim();
+    if !is_web_url(url) {
+        return Err("Only http and https links have page titles".to_string());
+    }
+
+    let html = get_head(
+        url,
+        |_| true,
+        |bytes| contains_ignore_case(bytes, b"</title") || contains_ignore_case(bytes, b"</head"),
+    )
+    .await?;
+    page_title(&html.text).ok_or_else(|| format!("{} has no title", url))
+}
@@ -0,0 +1,362 @@
+use crate::index::{IndexState, NoteRecord, WorkspaceIndex};
+use crate::markdown;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+const DEFAULT_CANDIDATES: usize = 20;
+
+#[derive(Debug, Serialize)]
+pub struct SwitcherEntry {
+    path: String,
+    title: String,
+    aliases: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HeadingLocation {
+    // The heading as written in the note, which may differ from the link
+    text: String,
+    level: usize,
+    // 1-based line and byte offset, for scrolling the editor to it
+    line: usize,
+    offset: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BlockRange {
+    id: String,
+    // 1-based lines and byte offsets spanning the block
+    start_line: usize,
+    end_line: usize,
+    start: usize,
+    end: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LinkTarget {
+    path: String,
+    // What followed `#` in the link, if anything
+    heading: Option<String>,
+    // Where that heading is; None when the note has no such heading
+    found: Option<HeadingLocation>,
+    // The id from `#^id`, and the block carrying it if there is one
+    block: Option<String>,
+    block_range: Option<BlockRange>,
+}
+
+// `note.md`, `folder/note` or `../note.md%20x`: found relative to the linking
+// note's folder, then to the workspace root
+fn resolve_path<'a>(
+    index: &'a WorkspaceIndex,
+    root: &Path,
+    from: Option<&Path>,
+    target: &str,
+) -> Option<&'a NoteRecord> {
+    let decoded = markdown::percent_decode(target);
+    let canonical_root = root.canonicalize().ok()?;
+    let bases = from
+        .and_then(Path::parent)
+        .into_iter()
+        .chain(std::iter::once(root));
+    let mut tried = Vec::new();
+    for base in bases {
+        let candidate = base.join(&decoded);
+        tried.push(candidate.clone());
+        if markdown::strip_note_extension(&decoded) == decoded {
+            tried.push(base.join(format!("{}.md", decoded)));
+        }
+    }
+    tried.into_iter().find_map(|candidate| {
+        let canonical = candidate.canonicalize().ok()?;
+        let relative = canonical.strip_prefix(&canonical_root).ok()?;
+        index.notes.get(&root.join(relative))
+    })
+}
+
+// Resolves `[[Note]]`, `[[Note#Heading]]`, `[[#Heading]]`, `[[Note#^block-id]]`
+// and `note.md#heading` targets. Notes are matched by title first, then by alias; headings loosely,
+// by slug, so `#getting-started` finds "Getting Started". `from` is the note
+// holding the link, for same-note and relative targets.
+#[tauri::command]
+pub async fn resolve_note_link(
+    folder_path: String,
+    target: String,
+    from: Option<String>,
+    index_state: State<'_, IndexState>,
+) -> Result<Option<LinkTarget>, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+    let from = from.map(PathBuf::from);
+    let target = target.split('|').next().unwrap_or("").trim();
+    let (name, heading) = match target.split_once('#') {
+        Some((name, heading)) => (name.trim(), Some(heading.trim())),
+        None => (target, None),
+    };
+
+    index_state.with_workspace(&root, |index| {
+        let note = if name.is_empty() {
+            from.as_deref().and_then(|from| index.notes.get(from))
+        } else if name.contains(['/', '\\']) || markdown::strip_note_extension(name) != name {
+            resolve_path(index, &root, from.as_deref(), name).or_else(|| {
+                let file_name = name.rsplit(['/', '\\']).next().unwrap_or(name);
+                index.resolve_link(&markdown::percent_decode(file_name))
+            })
+        } else {
+            index.resolve_link(name)
+        }?;
+        let heading = heading
+            .filter(|heading| !heading.is_empty())
+            .map(markdown::percent_decode);
+        let block = heading
+            .as_deref()
+            .and_then(|heading| heading.strip_prefix('^'))
+            .map(str::to_string);
+        if let Some(id) = block {
+            let block_range = note
+                .blocks
+                .iter()
+                .find(|block| {
+                    block
+                        .id
+                        .as_deref()
+                        .is_some_and(|own| own.eq_ignore_ascii_case(&id))
+                })
+                .map(|block| BlockRange {
+                    id: block.id.clone().unwrap_or_default(),
+                    start_line: block.start_line,
+                    end_line: block.end_line,
+                    start: block.start,
+                    end: block.end,
+                });
+            return Some(LinkTarget {
+                path: note.path.to_string_lossy().to_string(),
+                heading: None,
+                found: None,
+                block: Some(id),
+                block_range,
+            });
+        }
+        let found = heading
+            .as_deref()
+            .and_then(|heading| markdown::find_heading(&note.headings, heading))
+            .map(|heading| HeadingLocation {
+                text: heading.text.clone(),
+                level: heading.level,
+                line: heading.line,
+                offset: heading.offset,
+            });
+        Some(LinkTarget {
+            path: note.path.to_string_lossy().to_string(),
+            heading,
+            found,
+            block: None,
+            block_range: None,
+        })
+    })
+}
+
+// Titles and aliases for every note, for the quick switcher to match against
+#[tauri::command]
+pub async fn get_quick_switcher_index(
+    folder_path: String,
+    index_state: State<'_, IndexState>,
+) -> Result<Vec<SwitcherEntry>, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+
+    index_state.with_workspace(&root, |index| {
+        let mut entries: Vec<SwitcherEntry> = index
+            .notes
+            .values()
+            .map(|note| SwitcherEntry {
+                path: note.path.to_string_lossy().to_string(),
+                title: note.title.clone(),
+                aliases: note.aliases.clone(),
+            })
+            .collect();
+        entries.sort_by_cached_key(|entry| entry.title.to_lowercase());
+        entries
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum CandidateKind {
+    Title,
+    Alias,
+    Heading,
+    Block,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LinkCandidate {
+    kind: CandidateKind,
+    // What goes between the brackets: `Title` or `Title#Heading`
+    target: String,
+    // The title, alias or heading that matched
+    label: String,
+    path: String,
+}
+
+// How well `text` matches what was typed (already lowercased); lower is better
+fn match_rank(text: &str, typed: &str) -> Option<u8> {
+    if typed.is_empty() {
+        return Some(0);
+    }
+    let text = text.to_lowercase();
+    if text == typed {
+        Some(0)
+    } else if text.starts_with(typed) {
+        Some(1)
+    } else if text
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| word.starts_with(typed))
+    {
+        Some(2)
+    } else if text.contains(typed) {
+        Some(3)
+    } else {
+        // Letters in order, e.g. `mtg` for "Meeting notes"
+        let mut chars = text.chars();
+        typed
+            .chars()
+            .all(|wanted| chars.any(|c| c == wanted))
+            .then_some(4)
+    }
+}
+
+struct Ranked<'a> {
+    rank: u8,
+    kind: CandidateKind,
+    note: &'a NoteRecord,
+    label: &'a str,
+}
+
+impl Ranked<'_> {
+    fn into_candidate(self) -> LinkCandidate {
+        let target = match self.kind {
+            CandidateKind::Heading => format!("{}#{}", self.note.title, self.label),
+            CandidateKind::Block => format!("{}#^{}", self.note.title, self.label),
+            _ => self.note.title.clone(),
+        };
+        LinkCandidate {
+            kind: self.kind,
+            target,
+            label: self.label.to_string(),
+            path: self.note.path.to_string_lossy().to_string(),
+        }
+    }
+}
+
+// Completions for what's been typed after `[[`: note titles and aliases, or
+// with `Note#` the headings of that note and with `Note#^` its block ids (`#`
+// alone means the note being edited, `path`). Reads only the in-memory index,
+// so it's cheap per keystroke.
+#[tauri::command]
+pub async fn get_link_candidates(
+    folder_path: String,
+    prefix: String,
+    path: Option<String>,
+    limit: Option<usize>,
+    index_state: State<'_, IndexState>,
+) -> Result<Vec<LinkCandidate>, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+    let limit = limit.unwrap_or(DEFAULT_CANDIDATES);
+    // Past an alias separator the user is writing display text
+    if prefix.contains(['|', ']']) {
+        return Ok(Vec::new());
+    }
+
+    index_state.with_workspace(&root, |index| {
+        let mut ranked = Vec::new();
+        match prefix.split_once('#') {
+            Some((note, heading)) => {
+                let note = if note.trim().is_empty() {
+                    path.as_deref()
+                        .and_then(|path| index.notes.get(Path::new(path)))
+                } else {
+                    index.resolve_link(note)
+                };
+                let typed = heading.trim().to_lowercase();
+                // `Note#^` lists the note's block ids instead
+                if let (Some(note), Some(typed)) = (note, typed.strip_prefix('^')) {
+                    for id in note.blocks.iter().filter_map(|block| block.id.as_deref()) {
+                        if let Some(rank) = match_rank(id, typed) {
+                            ranked.push(Ranked {
+                                rank,
+                                kind: CandidateKind::Block,
+                                note,
+                                label: id,
+                            });
+                        }
+                    }
+                } else if let Some(note) = note {
+                    for heading in &note.headings {
+                        if let Some(rank) = match_rank(&heading.text, &typed) {
+                            ranked.push(Ranked {
+                                rank,
+                                kind: CandidateKind::Heading,
+                                note,
+                                label: &heading.text,
+                            });
+                        }
+                    }
+                }
+            }
+            None => {
+                let typed = markdown::link_key(&prefix);
+                for note in index.notes.values() {
+                    if let Some(rank) = match_rank(&note.title, &typed) {
+                        ranked.push(Ranked {
+                            rank,
+                            kind: CandidateKind::Title,
+                            note,
+                            label: &note.title,
+                        });
+                    }
+                    // With nothing typed yet, recent notes by title are enough
+                    if typed.is_empty() {
+                        continue;
+                    }
+                    let best_alias = note
+                        .aliases
+                        .iter()
+                        .filter_map(|alias| Some((match_rank(alias, &typed)?, alias)))
+                        .min_by_key(|(rank, _)| *rank);
+                    if let Some((rank, alias)) = best_alias {
+                        ranked.push(Ranked {
+                            rank,
+                            kind: CandidateKind::Alias,
+                            note,
+                            label: alias,
+                        });
+                    }
+                }
+            }
+        }
+
+        // Headings and blocks keep their order in the note; notes go most recent first
+        ranked.sort_by_key(|candidate| match candidate.kind {
+            CandidateKind::Heading | CandidateKind::Block => {
+                (candidate.rank, candidate.kind, Reverse(None), 0)
+            }
+            _ => (
+                candidate.rank,
+                candidate.kind,
+                Reverse(candidate.note.modified),
+                candidate.label.len(),
+            ),
+        });
+        ranked.truncate(limit);
+        ranked.into_iter().map(Ranked::into_candidate).collect()
+    })
+}
@@ -0,0 +1,206 @@
+use crate::disk_space;
+use crate::network;
+use crate::storage;
+use crate::watcher::WatcherState;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+const LOCKS_FILE: &str = "locks.json";
+
+// Serialized as an object so the frontend can tell a locked note from an I/O failure;
+// `message` keeps the existing string-based error handling working
+#[derive(Debug, Serialize)]
+pub struct NoteWriteError {
+    code: &'static str,
+    message: String,
+    path: Option<String>,
+    // Set for `insufficient_space`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    required: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    available: Option<u64>,
+}
+
+impl NoteWriteError {
+    pub fn locked(path: &Path) -> NoteWriteError {
+        NoteWriteError {
+            code: "note_locked",
+            message: "Note is locked".to_string(),
+            path: Some(path.to_string_lossy().to_string()),
+            required: None,
+            available: None,
+        }
+    }
+
+    pub fn encrypted(path: &Path) -> NoteWriteError {
+        NoteWriteError {
+            code: "note_encrypted",
+            message: "Note is encrypted".to_string(),
+            path: Some(path.to_string_lossy().to_string()),
+            required: None,
+            available: None,
+        }
+    }
+
+    pub fn io(message: String) -> NoteWriteError {
+        NoteWriteError {
+            code: "io_error",
+            message,
+            path: None,
+            required: None,
+            available: None,
+        }
+    }
+
+    pub fn insufficient_space(error: disk_space::InsufficientSpace) -> NoteWriteError {
+        NoteWriteError {
+            code: "insufficient_space",
+            message: error.message,
+            path: Some(error.path),
+            required: Some(error.required),
+            available: Some(error.available),
+        }
+    }
+
+    // For callers that report errors as plain strings
+    pub fn into_message(self) -> String {
+        self.message
+    }
+}
+
+// Serializes read-modify-write cycles on the locks file
+#[derive(Default)]
+pub struct LocksState {
+    lock: Mutex<()>,
+}
+
+fn locks_path(app: &AppHandle, root: &Path) -> Result<PathBuf, String> {
+    Ok(storage::workspace_data_dir(app, root)?.join(LOCKS_FILE))
+}
+
+// Paths are stored relative to the workspace root, like pins
+fn read_locks(app: &AppHandle, root: &Path) -> Vec<String> {
+    locks_path(app, root)
+        .map(|path| storage::read_json(&path))
+        .unwrap_or_default()
+}
+
+pub fn locked_paths(app: &AppHandle, root: &Path) -> HashSet<PathBuf> {
+    read_locks(app, root)
+        .iter()
+        .map(|relative| root.join(relative))
+        .collect()
+}
+
+pub fn is_locked(app: &AppHandle, root: &Path, path: &Path) -> bool {
+    path.strip_prefix(root)
+        .map(|relative| {
+            let relative = relative.to_string_lossy();
+            read_locks(app, root).iter().any(|entry| *entry == relative)
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(unix)]
+fn set_writable(path: &Path, writable: bool) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut permissions = fs::metadata(path)
+        .map_err(|e| format!("Failed to read permissions: {}", e))?
+        .permissions();
+    // Only the owner gets write access back, instead of whatever `set_readonly(false)` would grant
+    let mode = if writable {
+        permissions.mode() | 0o200
+    } else {
+        permissions.mode() & !0o222
+    };
+    permissions.set_mode(mode);
+    fs::set_permissions(path, permissions).map_err(|e| format!("Failed to set permissions: {}", e))
+}
+
+#[cfg(not(unix))]
+#[allow(clippy::permissions_set_readonly_false)]
+fn set_writable(path: &Path, writable: bool) -> Result<(), String> {
+    let mut permissions = fs::metadata(path)
+        .map_err(|e| format!("Failed to read permissions: {}", e))?
+        .permissions();
+    permissions.set_readonly(!writable);
+    fs::set_permissions(path, permissions).map_err(|e| format!("Failed to set permissions: {}", e))
+}
+
+// Keeps locks attached to notes renamed or moved through the app
+pub fn rename_path(app: &AppHandle, root: &Path, from: &Path, to: &Path) -> Result<(), String> {
+    let (Ok(from_key), Ok(to_key)) = (from.strip_prefix(root), to.strip_prefix(root)) else {
+        return Ok(());
+    };
+
+    let locks_state = app.state::<LocksState>();
+    let _guard = locks_state
+        .lock
+        .lock()
+        .map_err(|e| format!("Failed to lock note locks: {}", e))?;
+
+    let path = locks_path(app, root)?;
+    let mut locks: Vec<String> = storage::read_json(&path);
+    let mut changed = false;
+
+    for entry in locks.iter_mut() {
+        if let Ok(rest) = Path::new(entry.as_str()).strip_prefix(from_key) {
+            *entry = to_key.join(rest).to_string_lossy().to_string();
+            changed = true;
+        }
+    }
+
+    if changed {
+        storage::write_json(&path, &locks)?;
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_note_locked(path: String, locked: bool, app: AppHandle) -> Result<bool, String> {
+    let target = PathBuf::from(path);
+    storage::ensure_accessible(&app, &target)?;
+    network::guard(&app, &target.clone(), move |app| {
+        set_locked(app, &target, locked)
+    })?
+}
+
+fn set_locked(app: &AppHandle, target: &Path, locked: bool) -> Result<bool, String> {
+    if !target.is_file() {
+        return Err("Note does not exist".to_string());
+    }
+
+    let root = app
+        .state::<WatcherState>()
+        .root_for(target)
+        .ok_or("Path is not inside an open workspace")?;
+    let key = target
+        .strip_prefix(&root)
+        .map(|relative| relative.to_string_lossy().to_string())
+        .map_err(|_| "Path is not inside an open workspace")?;
+
+    let locks_state = app.state::<LocksState>();
+    let _guard = locks_state
+        .lock
+        .lock()
+        .map_err(|e| format!("Failed to lock note locks: {}", e))?;
+
+    set_writable(target, !locked)?;
+
+    let file = locks_path(app, &root)?;
+    let mut locks: Vec<String> = storage::read_json(&file);
+    // Locks on notes deleted outside the app are dropped whenever the file is rewritten
+    locks.retain(|entry| *entry != key && root.join(entry).is_file());
+    if locked {
+        locks.push(key);
+    }
+    storage::write_json(&file, &locks)?;
+
+    Ok(locked)
+}
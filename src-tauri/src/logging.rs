@@ -0,0 +1,241 @@
+use crate::settings::{self, SettingsState};
+use serde::Serialize;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{Builder as RollingBuilder, Rotation};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, Layer, Registry};
+
+const LOG_PREFIX: &str = "marky";
+const LOG_SUFFIX: &str = "log";
+const MAX_LOG_FILES: usize = 7;
+const MAX_RECENT_ERRORS: usize = 100;
+const DEFAULT_LINES: usize = 200;
+const MAX_LINES: usize = 5000;
+
+pub const LEVELS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+
+#[derive(Debug, Serialize, Clone)]
+struct BackendErrorEvent {
+    message: String,
+    target: String,
+}
+
+pub struct LoggingState {
+    dir: PathBuf,
+    level: reload::Handle<LevelFilter, Registry>,
+    recent_errors: Mutex<VecDeque<String>>,
+    // Dropping the guard stops the background writer, so it lives as long as the app
+    _guard: WorkerGuard,
+}
+
+impl LoggingState {
+    pub fn recent_errors(&self) -> Vec<String> {
+        self.recent_errors
+            .lock()
+            .map(|errors| errors.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.message, " {}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.message, " {}={}", field.name(), value);
+        }
+    }
+}
+
+thread_local! {
+    // Emitting can log in turn; never report errors raised while reporting one
+    static REPORTING: Cell<bool> = const { Cell::new(false) };
+}
+
+// Keeps recent errors for diagnostics and forwards them to the frontend as toasts
+struct ErrorReporter {
+    app: AppHandle,
+}
+
+impl<S: Subscriber> Layer<S> for ErrorReporter {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if *event.metadata().level() != Level::ERROR || REPORTING.with(Cell::get) {
+            return;
+        }
+        REPORTING.with(|reporting| reporting.set(true));
+
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        if let Some(state) = self.app.try_state::<LoggingState>() {
+            if let Ok(mut errors) = state.recent_errors.lock() {
+                if errors.len() == MAX_RECENT_ERRORS {
+                    errors.pop_front();
+                }
+                errors.push_back(format!(
+                    "{} {}",
+                    chrono::Local::now().to_rfc3339(),
+                    visitor.message
+                ));
+            }
+        }
+        let _ = self.app.emit(
+            "backend-error",
+            BackendErrorEvent {
+                message: visitor.message,
+                target: event.metadata().target().to_string(),
+            },
+        );
+
+        REPORTING.with(|reporting| reporting.set(false));
+    }
+}
+
+fn parse_level(level: &str) -> Result<LevelFilter, String> {
+    LevelFilter::from_str(level.trim()).map_err(|_| format!("Unknown log level: {}", level))
+}
+
+// Daily files in the app log dir, the last week kept; stderr keeps its output for development
+pub fn init(app: &AppHandle, level: &str) -> Result<(), String> {
+    let dir = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| format!("Failed to resolve log directory: {}", e))?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create log directory: {}", e))?;
+
+    let appender = RollingBuilder::new()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_PREFIX)
+        .filename_suffix(LOG_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(&dir)
+        .map_err(|e| format!("Failed to open log file: {}", e))?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+
+    let (filter, handle) = reload::Layer::new(parse_level(level).unwrap_or(LevelFilter::INFO));
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer().with_writer(writer).with_ansi(false))
+        .with(fmt::layer().with_writer(std::io::stderr))
+        .with(ErrorReporter { app: app.clone() })
+        .try_init()
+        .map_err(|e| format!("Failed to initialize logging: {}", e))?;
+
+    app.manage(LoggingState {
+        dir,
+        level: handle,
+        recent_errors: Mutex::new(VecDeque::new()),
+        _guard: guard,
+    });
+    Ok(())
+}
+
+pub fn apply_level(app: &AppHandle, level: &str) {
+    let (Some(state), Ok(filter)) = (app.try_state::<LoggingState>(), parse_level(level)) else {
+        return;
+    };
+    if let Err(e) = state.level.modify(|current| *current = filter) {
+        tracing::warn!("Failed to change log level: {}", e);
+    }
+}
+
+// Lines look like `2026-01-02T03:04:05.678Z  WARN marky::watcher: message`
+fn line_level(line: &str) -> Option<Level> {
+    line.split_whitespace()
+        .nth(1)
+        .and_then(|token| Level::from_str(token).ok())
+}
+
+#[tauri::command]
+pub fn get_recent_logs(
+    level: Option<String>,
+    lines: Option<usize>,
+    logging_state: State<LoggingState>,
+) -> Result<Vec<String>, String> {
+    let filter = match level {
+        Some(level) => parse_level(&level)?,
+        None => LevelFilter::TRACE,
+    };
+    let wanted = lines.unwrap_or(DEFAULT_LINES).min(MAX_LINES);
+
+    let mut files: Vec<PathBuf> = fs::read_dir(&logging_state.dir)
+        .map_err(|e| format!("Failed to read logs: {}", e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with(LOG_PREFIX))
+        })
+        .collect();
+    // File names end in the date, so the newest sorts last
+    files.sort();
+
+    let mut collected: Vec<String> = Vec::new();
+    for file in files.iter().rev() {
+        let Ok(content) = fs::read_to_string(file) else {
+            continue;
+        };
+        // Continuation lines of a multi-line message follow their entry's level
+        let mut entries: Vec<String> = Vec::new();
+        let mut keep = false;
+        for line in content.lines() {
+            if let Some(line_level) = line_level(line) {
+                keep = filter >= line_level;
+            }
+            if keep {
+                entries.push(line.to_string());
+            }
+        }
+        entries.extend(collected);
+        collected = entries;
+        if collected.len() >= wanted {
+            break;
+        }
+    }
+
+    let skip = collected.len().saturating_sub(wanted);
+    Ok(collected.split_off(skip))
+}
+
+#[tauri::command]
+pub fn set_log_level(
+    level: String,
+    app: AppHandle,
+    settings_state: State<SettingsState>,
+) -> Result<String, String> {
+    let level = level.trim().to_lowercase();
+    parse_level(&level)?;
+    // Persisted like any other setting; set_setting applies it to the live filter
+    settings::set_setting(
+        "log_level".to_string(),
+        serde_json::Value::String(level.clone()),
+        app,
+        settings_state,
+    )?;
+    Ok(level)
+}
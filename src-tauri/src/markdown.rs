@@ -0,0 +1,978 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use serde_yaml::{Mapping, Value};
+use std::ops::Range;
+use std::path::Path;
+
+pub const DEFAULT_EXTENSIONS: [&str; 3] = ["md", "markdown", "txt"];
+
+// `extensions` comes from the watched-extensions setting, lowercased without dots
+pub fn has_note_extension(path: &Path, extensions: &[String]) -> bool {
+    path.extension().is_some_and(|ext| {
+        let ext = ext.to_string_lossy().to_lowercase();
+        extensions.contains(&ext)
+    })
+}
+
+pub fn note_title(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+// Mirrors `stripCodeBlocks` in the frontend store: drop fenced blocks, then inline code
+pub fn strip_code_blocks(content: &str) -> String {
+    let mut without_fences = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(start) = rest.find("```") {
+        match rest[start + 3..].find("```") {
+            Some(end) => {
+                without_fences.push_str(&rest[..start]);
+                rest = &rest[start + 3 + end + 3..];
+            }
+            None => break,
+        }
+    }
+    without_fences.push_str(rest);
+
+    let mut stripped = String::with_capacity(without_fences.len());
+    for line in without_fences.split_inclusive('\n') {
+        let mut remaining = line;
+        while let Some(start) = remaining.find('`') {
+            let after = &remaining[start + 1..];
+            match after.find('`') {
+                Some(end) if end > 0 && !after[..end].contains('\n') => {
+                    stripped.push_str(&remaining[..start]);
+                    remaining = &after[end + 1..];
+                }
+                _ => {
+                    stripped.push_str(&remaining[..start + 1]);
+                    remaining = after;
+                }
+            }
+        }
+        stripped.push_str(remaining);
+    }
+
+    stripped
+}
+
+// `/` nests tags: `#project/client/acme` sits under `project` and `project/client`
+fn is_tag_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '/'
+}
+
+fn is_tag_path(tag: &str) -> bool {
+    !tag.is_empty() && !tag.starts_with('/') && !tag.ends_with('/') && !tag.contains("//")
+}
+
+// `project`, `project/client`, `project/client/acme` for `project/client/acme`
+pub fn tag_ancestors(tag: &str) -> impl Iterator<Item = &str> {
+    tag.match_indices('/')
+        .map(move |(idx, _)| &tag[..idx])
+        .chain(std::iter::once(tag))
+}
+
+fn is_tag_terminator(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '.' | ',' | ';' | '!' | '?' | ')')
+}
+
+// Same rules as `extractTags` in the frontend: `#tag` preceded by whitespace or
+// start of text and followed by whitespace, punctuation, or end of text.
+pub fn extract_tags(content: &str) -> Vec<String> {
+    let clean = strip_code_blocks(content);
+    let mut tags = Vec::new();
+    let mut previous: Option<char> = None;
+    let mut chars = clean.char_indices().peekable();
+
+    while let Some((idx, c)) = chars.next() {
+        let at_boundary = previous.is_none_or(char::is_whitespace);
+        previous = Some(c);
+
+        if c != '#' || !at_boundary {
+            continue;
+        }
+
+        let body_start = idx + 1;
+        let mut body_end = body_start;
+        while let Some(&(next_idx, next)) = chars.peek() {
+            if !is_tag_char(next) {
+                break;
+            }
+            body_end = next_idx + next.len_utf8();
+            previous = Some(next);
+            chars.next();
+        }
+
+        if body_end == body_start {
+            continue;
+        }
+
+        let terminated = clean[body_end..]
+            .chars()
+            .next()
+            .is_none_or(is_tag_terminator);
+        // A trailing slash is punctuation, as in "filed under #project/"
+        let tag = clean[body_start..body_end].trim_end_matches('/');
+        if terminated && is_tag_path(tag) {
+            tags.push(tag.to_lowercase());
+        }
+    }
+
+    tags.extend(frontmatter_tags(content));
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+// Byte ranges of fenced blocks and inline code: what `strip_code_blocks` drops
+pub fn code_ranges(content: &str) -> Vec<Range<usize>> {
+    let mut fences = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = content[offset..].find("```") {
+        let start = offset + start;
+        match content[start + 3..].find("```") {
+            Some(end) => {
+                let end = start + 3 + end + 3;
+                fences.push(start..end);
+                offset = end;
+            }
+            None => break,
+        }
+    }
+
+    let mut ranges = Vec::new();
+    let mut segment_start = 0;
+    for fence in fences
+        .iter()
+        .cloned()
+        .chain(std::iter::once(content.len()..content.len()))
+    {
+        let mut line_start = segment_start;
+        for line in content[segment_start..fence.start].split_inclusive('\n') {
+            let mut cursor = 0;
+            while let Some(open) = line[cursor..].find('`') {
+                let open = cursor + open;
+                match line[open + 1..].find('`') {
+                    Some(close) if close > 0 => {
+                        let close = open + 1 + close;
+                        ranges.push(line_start + open..line_start + close + 1);
+                        cursor = close + 1;
+                    }
+                    _ => cursor = open + 1,
+                }
+            }
+            line_start += line.len();
+        }
+        if fence.start < fence.end {
+            ranges.push(fence.clone());
+        }
+        segment_start = fence.end;
+    }
+    ranges.sort_by_key(|range| range.start);
+    ranges
+}
+
+// Every inline `#tag` outside code, by the rules of `extract_tags`, as the
+// byte range of the whole `#tag` and the lowercased tag
+pub fn inline_tag_spans(content: &str) -> Vec<(Range<usize>, String)> {
+    let code = code_ranges(content);
+    let in_code = |idx: usize| code.iter().any(|range| range.contains(&idx));
+    let mut spans = Vec::new();
+    let mut previous: Option<char> = None;
+    let mut chars = content.char_indices().peekable();
+
+    while let Some((idx, c)) = chars.next() {
+        let at_boundary = previous.is_none_or(char::is_whitespace);
+        previous = Some(c);
+        if c != '#' || !at_boundary || in_code(idx) {
+            continue;
+        }
+
+        let body_start = idx + 1;
+        let mut body_end = body_start;
+        while let Some(&(next_idx, next)) = chars.peek() {
+            if !is_tag_char(next) {
+                break;
+            }
+            body_end = next_idx + next.len_utf8();
+            previous = Some(next);
+            chars.next();
+        }
+        let terminated = content[body_end..]
+            .chars()
+            .next()
+            .is_none_or(is_tag_terminator);
+        let tag = content[body_start..body_end].trim_end_matches('/');
+        if terminated && is_tag_path(tag) {
+            spans.push((idx..body_start + tag.len(), tag.to_lowercase()));
+        }
+    }
+    spans
+}
+
+// A `tags:` / `tag:` frontmatter entry as a tag, or None when it isn't one
+pub fn frontmatter_tag_value(value: &str) -> Option<String> {
+    let tag = value
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'')
+        .trim_start_matches('#')
+        .to_lowercase();
+    (is_tag_path(&tag) && tag.chars().all(is_tag_char)).then_some(tag)
+}
+
+// Tags listed in `tags:` (or `tag:`) frontmatter, as a list, a block list, or
+// a comma- or space-separated string
+pub fn frontmatter_tags(content: &str) -> Vec<String> {
+    let Some(mapping) = parse_frontmatter(content) else {
+        return Vec::new();
+    };
+    let mut tags: Vec<String> = Vec::new();
+    for key in ["tags", "tag"] {
+        for entry in frontmatter_list(&mapping, key) {
+            for value in entry.split(|c: char| c == ',' || c.is_whitespace()) {
+                if let Some(tag) = frontmatter_tag_value(value) {
+                    if !tags.contains(&tag) {
+                        tags.push(tag);
+                    }
+                }
+            }
+        }
+    }
+    tags
+}
+
+pub fn strip_note_extension(target: &str) -> &str {
+    if let Some(idx) = target.rfind('.') {
+        let ext = &target[idx + 1..];
+        if idx > 0
+            && DEFAULT_EXTENSIONS
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        {
+            return &target[..idx];
+        }
+    }
+    target
+}
+
+// Lowercased `[[target]]` keys, deduplicated, matching `buildNoteLinkKey`
+pub fn extract_wiki_links(content: &str) -> Vec<String> {
+    let clean = strip_code_blocks(content);
+    let mut links: Vec<String> = Vec::new();
+    let mut rest = clean.as_str();
+
+    while let Some(start) = rest.find("[[") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("]]") else {
+            break;
+        };
+
+        let inner = after[..end].trim();
+        let target = inner.split('|').next().unwrap_or("");
+        // `[[Note#Heading]]` links to the note; `[[#Heading]]` stays within this one
+        let key = link_key(target.split('#').next().unwrap_or(""));
+        if !key.is_empty() && !links.contains(&key) {
+            links.push(key);
+        }
+
+        rest = &after[end + 2..];
+    }
+
+    links
+}
+
+fn is_external_target(target: &str) -> bool {
+    target.is_empty()
+        || target.starts_with('#')
+        || target.contains("://")
+        || target.to_lowercase().starts_with("mailto:")
+}
+
+// Targets of `[text](target)` and `![alt](target)` that point at local files,
+// plus `![[file.png]]` embeds of non-note files, in order and deduplicated.
+// Link titles and `<...>` wrappers are dropped; `%20` style escapes are kept.
+pub fn extract_local_links(content: &str) -> Vec<String> {
+    let clean = strip_code_blocks(content);
+    let mut links: Vec<String> = Vec::new();
+    let mut push = |target: &str| {
+        if !is_external_target(target) && !links.iter().any(|link| link == target) {
+            links.push(target.to_string());
+        }
+    };
+
+    let mut rest = clean.as_str();
+    while let Some(start) = rest.find("](") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find(')') else {
+            break;
+        };
+        let inner = after[..end].trim();
+        let target = match inner.strip_prefix('<') {
+            Some(wrapped) => wrapped.split('>').next().unwrap_or(""),
+            None => inner.split_whitespace().next().unwrap_or(""),
+        };
+        push(target.split('#').next().unwrap_or(""));
+        rest = &after[end + 1..];
+    }
+
+    let mut rest = clean.as_str();
+    while let Some(start) = rest.find("![[") {
+        let after = &rest[start + 3..];
+        let Some(end) = after.find("]]") else {
+            break;
+        };
+        let target = after[..end].split('|').next().unwrap_or("").trim();
+        if strip_note_extension(target) == target && target.contains('.') {
+            push(target);
+        }
+        rest = &after[end + 2..];
+    }
+
+    links
+}
+
+// The YAML between a leading `---` line and the next `---` (or `...`) line
+pub fn frontmatter(content: &str) -> Option<&str> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let rest = content
+        .strip_prefix("---\r\n")
+        .or_else(|| content.strip_prefix("---\n"))?;
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if trimmed == "---" || trimmed == "..." {
+            return Some(&rest[..offset]);
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+// Invalid YAML is treated as no frontmatter rather than an error
+pub fn parse_frontmatter(content: &str) -> Option<Mapping> {
+    match serde_yaml::from_str::<Value>(frontmatter(content)?) {
+        Ok(Value::Mapping(mapping)) => Some(mapping),
+        _ => None,
+    }
+}
+
+// A frontmatter value as a list of strings: `key: a`, `key: [a, b]`, or a block list
+pub fn frontmatter_list(mapping: &Mapping, key: &str) -> Vec<String> {
+    let values = match mapping.get(key) {
+        Some(Value::Sequence(items)) => items.iter().collect(),
+        Some(value) => vec![value],
+        None => Vec::new(),
+    };
+
+    let mut list: Vec<String> = Vec::new();
+    for value in values {
+        let text = match value {
+            Value::String(text) => text.trim().to_string(),
+            Value::Number(number) => number.to_string(),
+            Value::Bool(flag) => flag.to_string(),
+            _ => continue,
+        };
+        if !text.is_empty() && !list.contains(&text) {
+            list.push(text);
+        }
+    }
+    list
+}
+
+// `aliases:` (or the singular `alias:`) names a note also answers to in links and search
+pub fn extract_aliases(content: &str) -> Vec<String> {
+    let Some(mapping) = parse_frontmatter(content) else {
+        return Vec::new();
+    };
+
+    let mut aliases = frontmatter_list(&mapping, "aliases");
+    for alias in frontmatter_list(&mapping, "alias") {
+        if !aliases.contains(&alias) {
+            aliases.push(alias);
+        }
+    }
+    aliases
+}
+
+// Reminders without a time of day go off in the morning
+const DEFAULT_REMINDER_TIME: (u32, u32) = (9, 0);
+const INLINE_REMINDER: &str = "@remind(";
+
+// `2024-06-01 09:00`, `2024-06-01T09:00[:00]`, or a bare date, in local time
+pub fn parse_reminder_time(text: &str) -> Option<NaiveDateTime> {
+    let text = text.trim();
+    for format in [
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+    ] {
+        if let Ok(at) = NaiveDateTime::parse_from_str(text, format) {
+            return Some(at);
+        }
+    }
+
+    let date = NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()?;
+    let (hour, minute) = DEFAULT_REMINDER_TIME;
+    Some(date.and_time(NaiveTime::from_hms_opt(hour, minute, 0)?))
+}
+
+// From a `remind:` frontmatter value (one or a list) and inline `@remind(...)`
+// tokens outside code; unparseable times are skipped
+pub fn extract_reminders(content: &str) -> Vec<NaiveDateTime> {
+    let mut reminders: Vec<NaiveDateTime> = parse_frontmatter(content)
+        .map(|mapping| frontmatter_list(&mapping, "remind"))
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|text| parse_reminder_time(text))
+        .collect();
+
+    let clean = strip_code_blocks(content);
+    let mut rest = clean.as_str();
+    while let Some(start) = rest.find(INLINE_REMINDER) {
+        rest = &rest[start + INLINE_REMINDER.len()..];
+        let Some(end) = rest.find(')') else {
+            break;
+        };
+        if let Some(at) = parse_reminder_time(&rest[..end]) {
+            reminders.push(at);
+        }
+        rest = &rest[end + 1..];
+    }
+
+    reminders.sort();
+    reminders.dedup();
+    reminders
+}
+
+#[derive(Debug, Clone)]
+pub struct Task {
+    pub text: String,
+    pub done: bool,
+    pub due: Option<NaiveDate>,
+    // 1-based, for jumping to the task
+    pub line: usize,
+}
+
+const DUE_MARKERS: [&str; 3] = ["\u{1f4c5}", "due:", "@due("];
+
+fn due_date(text: &str) -> Option<NaiveDate> {
+    DUE_MARKERS.iter().find_map(|marker| {
+        let start = text.find(marker)? + marker.len();
+        let date = text[start..].trim_start().get(..10)?;
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+    })
+}
+
+// Task text without its due-date marker, for places that show the date separately
+pub fn strip_due_date(text: &str) -> String {
+    let mut text = text.to_string();
+    for marker in DUE_MARKERS {
+        let Some(start) = text.find(marker) else {
+            continue;
+        };
+        let after = &text[start + marker.len()..];
+        let date_start = after.len() - after.trim_start().len();
+        let mut end = start + marker.len() + date_start + 10;
+        if end > text.len() || !text.is_char_boundary(end) {
+            continue;
+        }
+        if marker == "@due(" && text[end..].starts_with(')') {
+            end += 1;
+        }
+        text.replace_range(start..end, "");
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// The text after a `- [ ]`, `* [x]`, or `1. [ ]` marker, and whether it's checked
+fn parse_task_line(line: &str) -> Option<(bool, &str)> {
+    let line = line.trim_start();
+    let rest = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))
+        .or_else(|| {
+            let digits = line.find(|c: char| !c.is_ascii_digit())?;
+            if digits == 0 {
+                return None;
+            }
+            line[digits..]
+                .strip_prefix(". ")
+                .or_else(|| line[digits..].strip_prefix(") "))
+        })?;
+
+    let (done, text) = if let Some(text) = rest.strip_prefix("[ ]") {
+        (false, text)
+    } else if let Some(text) = rest
+        .strip_prefix("[x]")
+        .or_else(|| rest.strip_prefix("[X]"))
+    {
+        (true, text)
+    } else {
+        return None;
+    };
+
+    // `- [ ]` must be followed by whitespace or the end of the line
+    if !text.is_empty() && !text.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some((done, text.trim()))
+}
+
+// The line with its open checkbox ticked, or `None` when it isn't an open task
+pub fn complete_task_line(line: &str) -> Option<String> {
+    match parse_task_line(line) {
+        // Only indentation and a list marker come before the box
+        Some((false, _)) => {
+            let at = line.find("[ ]")?;
+            Some(format!("{}[x]{}", &line[..at], &line[at + 3..]))
+        }
+        _ => None,
+    }
+}
+
+// Checkbox list items outside fenced code. A due date comes from
+// `📅 2024-06-01`, `due:2024-06-01`, or `@due(2024-06-01)`.
+pub fn extract_tasks(content: &str) -> Vec<Task> {
+    let mut tasks = Vec::new();
+    let mut in_fence = false;
+
+    for (idx, line) in content.lines().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        if let Some((done, text)) = parse_task_line(line) {
+            tasks.push(Task {
+                text: text.to_string(),
+                done,
+                due: due_date(text),
+                line: idx + 1,
+            });
+        }
+    }
+
+    tasks
+}
+
+#[derive(Debug, Clone)]
+pub struct Heading {
+    pub text: String,
+    pub level: usize,
+    // 1-based line and byte offset of the heading line within the note
+    pub line: usize,
+    pub offset: usize,
+}
+
+// The `#` count of an ATX heading line, already trimmed at the start
+fn heading_level(trimmed: &str) -> Option<usize> {
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    let rest = &trimmed[level..];
+    ((1..=6).contains(&level) && (rest.is_empty() || rest.starts_with([' ', '\t'])))
+        .then_some(level)
+}
+
+// ATX headings in order, skipping the frontmatter and fenced code
+pub fn extract_headings(content: &str) -> Vec<Heading> {
+    let body = body(content);
+    let mut offset = content.len() - body.len();
+    let first_line = content[..offset].matches('\n').count() + 1;
+    let mut headings = Vec::new();
+    let mut in_fence = false;
+    for (idx, raw) in body.split_inclusive('\n').enumerate() {
+        let start = offset;
+        offset += raw.len();
+        let line = raw.trim_end_matches(['\n', '\r']);
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence || line.len() - trimmed.len() > 3 {
+            continue;
+        }
+        let Some(level) = heading_level(trimmed) else {
+            continue;
+        };
+        let rest = &trimmed[level..];
+        // A closing run of `#`s after a space isn't part of the text
+        let text = rest.trim();
+        let unclosed = text.trim_end_matches('#');
+        let text = if unclosed.is_empty() || unclosed.ends_with([' ', '\t']) {
+            unclosed.trim_end()
+        } else {
+            text
+        };
+        if !text.is_empty() {
+            headings.push(Heading {
+                text: text.to_string(),
+                level,
+                line: first_line + idx,
+                offset: start,
+            });
+        }
+    }
+    headings
+}
+
+// The anchor a heading is reached by: lowercased words joined with `-`,
+// punctuation dropped, like the preview's heading ids
+pub fn heading_slug(text: &str) -> String {
+    let mut slug = String::new();
+    for word in text.split_whitespace() {
+        let word: String = word
+            .chars()
+            .filter(|c| c.is_alphanumeric() || matches!(c, '-' | '_'))
+            .flat_map(char::to_lowercase)
+            .collect();
+        if word.is_empty() {
+            continue;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(&word);
+    }
+    slug
+}
+
+// The heading `[[Note#wanted]]` means: the exact text (ignoring case), then
+// the same slug, then the slug without separators, then the first heading
+// whose slug starts with the wanted one
+pub fn find_heading<'a>(headings: &'a [Heading], wanted: &str) -> Option<&'a Heading> {
+    let wanted = wanted.trim().to_lowercase();
+    if let Some(heading) = headings
+        .iter()
+        .find(|heading| heading.text.to_lowercase() == wanted)
+    {
+        return Some(heading);
+    }
+    let slug = heading_slug(&wanted);
+    if slug.is_empty() {
+        return None;
+    }
+    let compact = |slug: &str| slug.replace(['-', '_'], "");
+    let slugs: Vec<String> = headings
+        .iter()
+        .map(|heading| heading_slug(&heading.text))
+        .collect();
+    slugs
+        .iter()
+        .position(|candidate| *candidate == slug)
+        .or_else(|| {
+            slugs
+                .iter()
+                .position(|candidate| compact(candidate) == compact(&slug))
+        })
+        .or_else(|| {
+            slugs
+                .iter()
+                .position(|candidate| candidate.starts_with(&slug))
+        })
+        .map(|index| &headings[index])
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockKind {
+    Paragraph,
+    ListItem,
+    Heading,
+    Code,
+    Quote,
+    Table,
+}
+
+// A paragraph, list item, heading, code block, quote or table: what a
+// `[[Note#^id]]` reference can point at
+#[derive(Debug, Clone)]
+pub struct Block {
+    pub kind: BlockKind,
+    // From a trailing `^id`, or a `^id` line right after the block
+    pub id: Option<String>,
+    // 1-based lines and byte offsets; `end` is before the last line's break
+    pub start_line: usize,
+    pub end_line: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Block {
+    // Code, quotes and tables take their id on a line of its own after them;
+    // a trailing `^id` would become part of their content
+    pub fn id_on_own_line(&self) -> bool {
+        matches!(
+            self.kind,
+            BlockKind::Code | BlockKind::Quote | BlockKind::Table
+        )
+    }
+}
+
+// Where a ` ^id` marker starts in `line`, with the id
+fn block_id_marker(line: &str) -> Option<(usize, &str)> {
+    let line = line.trim_end();
+    let caret = line.rfind('^')?;
+    let id = &line[caret + 1..];
+    let separated = caret == 0 || line[..caret].ends_with([' ', '\t']);
+    let valid = !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    (separated && valid).then_some((caret, id))
+}
+
+fn is_list_item(trimmed: &str) -> bool {
+    if ["- ", "* ", "+ "]
+        .iter()
+        .any(|bullet| trimmed.starts_with(bullet))
+    {
+        return true;
+    }
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    digits > 0 && (trimmed[digits..].starts_with(". ") || trimmed[digits..].starts_with(") "))
+}
+
+// Every block in the note body, with or without an id. Blank lines end a
+// block; headings, list items and fences start a new one.
+pub fn extract_block_spans(content: &str) -> Vec<Block> {
+    let body = body(content);
+    let mut offset = content.len() - body.len();
+    let first_line = content[..offset].matches('\n').count() + 1;
+    let mut blocks: Vec<Block> = Vec::new();
+    // Whether the last block still takes the next line
+    let mut open = false;
+    let mut in_fence = false;
+    for (idx, raw) in body.split_inclusive('\n').enumerate() {
+        let start = offset;
+        offset += raw.len();
+        let line_number = first_line + idx;
+        let line = raw.trim_end_matches(['\n', '\r']);
+        let end = start + line.len();
+        let trimmed = line.trim_start();
+        let fence = trimmed.starts_with("```") || trimmed.starts_with("~~~");
+
+        if in_fence {
+            if let Some(block) = blocks.last_mut() {
+                block.end_line = line_number;
+                block.end = end;
+            }
+            if fence {
+                in_fence = false;
+                open = false;
+            }
+            continue;
+        }
+        if trimmed.is_empty() {
+            open = false;
+            continue;
+        }
+
+        let marker = block_id_marker(line);
+        // A line holding only `^id` names the block before it
+        if let Some((caret, id)) = marker {
+            if caret == line.len() - trimmed.len() {
+                if let Some(block) = blocks.last_mut().filter(|block| block.id.is_none()) {
+                    block.id = Some(id.to_string());
+                }
+                open = false;
+                continue;
+            }
+        }
+
+        let heading = line.len() - trimmed.len() <= 3 && heading_level(trimmed).is_some();
+        if open && !fence && !heading && !is_list_item(trimmed) {
+            if let Some(block) = blocks.last_mut() {
+                block.end_line = line_number;
+                block.end = end;
+            }
+        } else {
+            let kind = if fence {
+                BlockKind::Code
+            } else if heading {
+                BlockKind::Heading
+            } else if is_list_item(trimmed) {
+                BlockKind::ListItem
+            } else if trimmed.starts_with('>') {
+                BlockKind::Quote
+            } else if trimmed.starts_with('|') {
+                BlockKind::Table
+            } else {
+                BlockKind::Paragraph
+            };
+            blocks.push(Block {
+                kind,
+                id: None,
+                start_line: line_number,
+                end_line: line_number,
+                start,
+                end,
+            });
+        }
+        open = !heading;
+        if fence {
+            in_fence = true;
+            continue;
+        }
+        if let Some((_, id)) = marker {
+            if let Some(block) = blocks.last_mut() {
+                block.id.get_or_insert_with(|| id.to_string());
+            }
+            open = false;
+        }
+    }
+    blocks
+}
+
+// Blocks that carry a `^id`
+pub fn extract_blocks(content: &str) -> Vec<Block> {
+    extract_block_spans(content)
+        .into_iter()
+        .filter(|block| block.id.is_some())
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockRef {
+    // Link key of the note, as in `extract_wiki_links`; empty for `[[#^id]]`
+    pub key: String,
+    pub id: String,
+}
+
+// `[[Note#^id]]` and `![[Note#^id]]` references, deduplicated
+pub fn extract_block_refs(content: &str) -> Vec<BlockRef> {
+    let clean = strip_code_blocks(content);
+    let mut refs: Vec<BlockRef> = Vec::new();
+    let mut rest = clean.as_str();
+
+    while let Some(start) = rest.find("[[") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("]]") else {
+            break;
+        };
+        let target = after[..end].split('|').next().unwrap_or("");
+        if let Some((note, subpath)) = target.split_once('#') {
+            let id = subpath.trim().strip_prefix('^').unwrap_or("");
+            if !id.is_empty() {
+                let found = BlockRef {
+                    key: link_key(note),
+                    id: id.to_string(),
+                };
+                if !refs.contains(&found) {
+                    refs.push(found);
+                }
+            }
+        }
+        rest = &after[end + 2..];
+    }
+
+    refs
+}
+
+// Lowercased link key for a title or alias, matching `buildNoteLinkKey`
+pub fn link_key(name: &str) -> String {
+    strip_note_extension(name.trim()).trim().to_lowercase()
+}
+
+pub fn count_words(content: &str) -> usize {
+    content.split_whitespace().count()
+}
+
+// Lowercased word tokens used for term statistics; single characters carry no signal
+pub fn tokenize(content: &str) -> impl Iterator<Item = String> + '_ {
+    content
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() > 1)
+        .map(|word| word.to_lowercase())
+}
+
+// `%20`-style escapes in link targets and URLs; malformed escapes are kept as-is
+pub fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let Some(byte) = text
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+// Everything after the frontmatter block, or the whole note when there is none
+pub fn body(content: &str) -> &str {
+    let trimmed = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let Some(yaml) = frontmatter(trimmed) else {
+        return content;
+    };
+    let opening = if trimmed.starts_with("---\r\n") { 5 } else { 4 };
+    // `rest` starts at the closing `---` line
+    let rest = &trimmed[opening + yaml.len()..];
+    match rest.find('\n') {
+        Some(end) => &rest[end + 1..],
+        None => "",
+    }
+}
+
+// The note body as an HTML fragment, with the GitHub-flavored extensions the
+// preview renders
+pub fn to_html(content: &str) -> String {
+    use pulldown_cmark::{html, Options, Parser};
+
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS;
+    let mut output = String::new();
+    html::push_html(&mut output, Parser::new_ext(body(content), options));
+    output
+}
+
+// Pasted or clipped HTML as markdown. Relative links and images are resolved
+// against `base` so they still work once the markdown leaves the page
+pub fn from_html(html: &str, base: Option<&tauri::Url>) -> String {
+    let document = dom_query::Document::from(html);
+    if let Some(base) = base {
+        for (selector, attr) in [("a[href]", "href"), ("img[src]", "src")] {
+            for node in document.select(selector).nodes() {
+                let Some(value) = node.attr(attr) else {
+                    continue;
+                };
+                if let Ok(resolved) = base.join(&value) {
+                    node.set_attr(attr, resolved.as_str());
+                }
+            }
+        }
+    }
+    let markdown = document.md(None).to_string();
+
+    // The serializer leaves runs of blank lines where block elements were empty
+    let mut output = String::new();
+    let mut blank = 0;
+    for line in markdown.trim().lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            blank += 1;
+            if blank > 1 {
+                continue;
+            }
+        } else {
+            blank = 0;
+        }
+        output.push_str(line);
+        output.push('\n');
+    }
+    output
+}
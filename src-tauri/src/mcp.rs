@@ -0,0 +1,247 @@
+use crate::api;
+use crate::encryption;
+use crate::index::IndexState;
+use crate::markdown;
+use crate::settings::SettingsState;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+// Newest first; clients asking for anything else get the newest
+const PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+// Appends from assistants aren't tied to a window, so every window reloads the note
+const ORIGIN: &str = "mcp";
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+const MAX_SEARCH_LIMIT: usize = 100;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+fn reply(id: &Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn error(id: &Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+pub fn parse_error(message: &str) -> Value {
+    error(&Value::Null, PARSE_ERROR, message)
+}
+
+// Tool failures are results the assistant can read, not protocol errors
+fn tool_text(text: impl Into<String>, is_error: bool) -> Value {
+    json!({
+        "content": [{ "type": "text", "text": text.into() }],
+        "isError": is_error,
+    })
+}
+
+fn tools(allow_append: bool) -> Vec<Value> {
+    let mut tools = vec![
+        json!({
+            "name": "search_notes",
+            "description": "Search the open Marky workspace for notes containing every word of the query. Returns matching note paths and titles, best matches first.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Words to search for" },
+                    "limit": { "type": "integer", "description": "Maximum results (default 20)" }
+                },
+                "required": ["query"]
+            }
+        }),
+        json!({
+            "name": "list_notes",
+            "description": "List note paths in the open Marky workspace, optionally only those under a folder.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "folder": { "type": "string", "description": "Workspace-relative folder, e.g. `Projects`" }
+                }
+            }
+        }),
+        json!({
+            "name": "read_note",
+            "description": "Read a note's markdown by its workspace-relative path, as returned by search_notes or list_notes.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "Workspace-relative note path" }
+                },
+                "required": ["path"]
+            }
+        }),
+    ];
+    if allow_append {
+        tools.push(json!({
+            "name": "append_to_note",
+            "description": "Append markdown to the end of an existing note. Existing content is never changed.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "Workspace-relative note path" },
+                    "text": { "type": "string", "description": "Markdown to append" }
+                },
+                "required": ["path", "text"]
+            }
+        }));
+    }
+    tools
+}
+
+fn string_arg<'a>(args: &'a Value, key: &str) -> Result<&'a str, String> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("Missing argument: {}", key))
+}
+
+// Only existing notes; the config folder and attachments stay out of reach
+fn note_file(app: &AppHandle, root: &Path, args: &Value) -> Result<PathBuf, String> {
+    let path = api::note_path(root, string_arg(args, "path")?)?;
+    let extensions = app.state::<SettingsState>().current().watched_extensions;
+    if !markdown::has_note_extension(&path, &extensions) || !path.is_file() {
+        return Err("Note not found".to_string());
+    }
+    Ok(path)
+}
+
+fn read_text(path: &Path) -> Result<String, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read note: {}", e))?;
+    // Ciphertext is useless to an assistant, and the passphrase never leaves the app
+    if encryption::is_encrypted(&content) {
+        return Err("This note is encrypted".to_string());
+    }
+    Ok(content)
+}
+
+fn call_tool(app: &AppHandle, root: &Path, name: &str, args: &Value) -> Result<String, String> {
+    match name {
+        "search_notes" => {
+            let limit = args
+                .get("limit")
+                .and_then(Value::as_u64)
+                .map_or(DEFAULT_SEARCH_LIMIT, |limit| limit as usize)
+                .clamp(1, MAX_SEARCH_LIMIT);
+            let results = api::search_notes(app, root, string_arg(args, "query")?, limit)?;
+            if results.is_empty() {
+                return Ok("No notes matched.".to_string());
+            }
+            Ok(results
+                .iter()
+                .map(|result| {
+                    let path = result["path"].as_str().unwrap_or_default();
+                    let title = result["title"].as_str().unwrap_or_default();
+                    format!("{} — {}", path, title)
+                })
+                .collect::<Vec<_>>()
+                .join("\n"))
+        }
+        "list_notes" => {
+            let folder = match args.get("folder").and_then(Value::as_str) {
+                Some(folder) if !folder.trim().is_empty() => api::note_path(root, folder)?,
+                _ => root.to_path_buf(),
+            };
+            let mut paths = app.state::<IndexState>().with_workspace(root, |index| {
+                index
+                    .notes
+                    .keys()
+                    .filter(|path| path.starts_with(&folder))
+                    .map(|path| api::relative_path(root, path))
+                    .collect::<Vec<_>>()
+            })?;
+            paths.sort();
+            Ok(if paths.is_empty() {
+                "No notes.".to_string()
+            } else {
+                paths.join("\n")
+            })
+        }
+        "read_note" => read_text(&note_file(app, root, args)?),
+        "append_to_note" => {
+            if !app.state::<SettingsState>().current().api.mcp_allow_append {
+                return Err("Appending to notes is turned off in Marky's settings".to_string());
+            }
+            let path = note_file(app, root, args)?;
+            let text = string_arg(args, "text")?;
+            // Encrypted notes would otherwise fail deep in the write with a vaguer error
+            read_text(&path)?;
+            crate::append_to_note(app, ORIGIN, &path, text).map_err(|e| e.into_message())?;
+            Ok(format!("Appended to {}", api::relative_path(root, &path)))
+        }
+        _ => Err(format!("Unknown tool: {}", name)),
+    }
+}
+
+fn handle_request(app: &AppHandle, root: Option<&Path>, message: &Value) -> Option<Value> {
+    if message.get("jsonrpc").and_then(Value::as_str) != Some("2.0") {
+        return Some(error(
+            &Value::Null,
+            INVALID_REQUEST,
+            "Not a JSON-RPC 2.0 message",
+        ));
+    }
+    // Without an id it's a notification (or a response to us), which gets no reply
+    let id = message.get("id")?;
+    let method = message.get("method").and_then(Value::as_str)?;
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = match method {
+        "initialize" => {
+            let requested = params.get("protocolVersion").and_then(Value::as_str);
+            let version = requested
+                .filter(|version| PROTOCOL_VERSIONS.contains(version))
+                .unwrap_or(PROTOCOL_VERSIONS[0]);
+            json!({
+                "protocolVersion": version,
+                "capabilities": { "tools": {} },
+                "serverInfo": {
+                    "name": "marky",
+                    "version": app.package_info().version.to_string(),
+                },
+                "instructions": "Tools for the notes workspace open in Marky. Paths are relative to the workspace root.",
+            })
+        }
+        "ping" => json!({}),
+        "tools/list" => {
+            let allow_append = app.state::<SettingsState>().current().api.mcp_allow_append;
+            json!({ "tools": tools(allow_append) })
+        }
+        "tools/call" => {
+            let Some(name) = params.get("name").and_then(Value::as_str) else {
+                return Some(error(id, INVALID_PARAMS, "Missing tool name"));
+            };
+            let args = params.get("arguments").cloned().unwrap_or(json!({}));
+            let outcome = match root {
+                Some(root) => call_tool(app, root, name, &args),
+                None => Err("No workspace is open in Marky".to_string()),
+            };
+            match outcome {
+                Ok(text) => tool_text(text, false),
+                Err(e) => {
+                    tracing::debug!("MCP tool {} failed: {}", name, e);
+                    tool_text(e, true)
+                }
+            }
+        }
+        _ => return Some(error(id, METHOD_NOT_FOUND, "Method not found")),
+    };
+    Some(reply(id, result))
+}
+
+// A single message or a batch of them; `None` when nothing needs a reply
+pub fn handle(app: &AppHandle, root: Option<&Path>, message: Value) -> Option<Value> {
+    match message {
+        Value::Array(batch) => {
+            let replies: Vec<Value> = batch
+                .iter()
+                .filter_map(|message| handle_request(app, root, message))
+                .collect();
+            (!replies.is_empty()).then_some(Value::Array(replies))
+        }
+        message => handle_request(app, root, &message),
+    }
+}
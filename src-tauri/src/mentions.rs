@@ -0,0 +1,260 @@
+use crate::encryption;
+use crate::index::IndexState;
+use crate::markdown;
+use crate::network;
+use crate::properties;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, State, Window};
+
+// Characters of context kept on each side of a mention
+const CONTEXT_CHARS: usize = 60;
+
+#[derive(Debug, Serialize)]
+pub struct UnlinkedMention {
+    // The note the mention is in
+    path: String,
+    title: String,
+    // The mention as written, and its byte range in that note
+    text: String,
+    start: usize,
+    end: usize,
+    line: usize,
+    // The rest of the line on either side, shortened
+    before: String,
+    after: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MentionRange {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// Byte ranges a mention can't be turned into a link inside: the frontmatter,
+// code, existing `[[links]]` and `[markdown](links)`, and bare URLs
+fn excluded_ranges(content: &str) -> Vec<Range<usize>> {
+    let mut ranges = markdown::code_ranges(content);
+    ranges.push(0..content.len() - markdown::body(content).len());
+
+    let mut offset = 0;
+    while let Some(open) = content[offset..].find("[[") {
+        let open = offset + open;
+        let Some(close) = content[open..].find("]]") else {
+            break;
+        };
+        ranges.push(open..open + close + 2);
+        offset = open + close + 2;
+    }
+
+    let mut offset = 0;
+    while let Some(open) = content[offset..].find('[') {
+        let open = offset + open;
+        offset = open + 1;
+        let Some(label_end) = content[open..].find("](").map(|idx| open + idx) else {
+            break;
+        };
+        if content[open..label_end].contains('\n') {
+            continue;
+        }
+        if let Some(close) = content[label_end..].find(')') {
+            ranges.push(open..label_end + close + 1);
+            offset = label_end + close + 1;
+        }
+    }
+
+    for scheme in ["http://", "https://"] {
+        let mut offset = 0;
+        while let Some(start) = content[offset..].find(scheme) {
+            let start = offset + start;
+            let end = content[start..]
+                .find(char::is_whitespace)
+                .map_or(content.len(), |idx| start + idx);
+            ranges.push(start..end);
+            offset = end;
+        }
+    }
+    ranges
+}
+
+// Case-insensitive, whole-word occurrences of any of `names` outside links
+// and code. Longer names win where they overlap a shorter one.
+fn find_mentions(content: &str, names: &[String]) -> Vec<Range<usize>> {
+    let excluded = excluded_ranges(content);
+    let overlaps = |ranges: &[Range<usize>], start: usize, end: usize| {
+        ranges
+            .iter()
+            .any(|range| range.start < end && start < range.end)
+    };
+
+    let mut found: Vec<Range<usize>> = Vec::new();
+    for name in names {
+        for (start, _) in content.char_indices() {
+            let end = start + name.len();
+            let Some(candidate) = content.get(start..end) else {
+                continue;
+            };
+            if !candidate.eq_ignore_ascii_case(name) {
+                continue;
+            }
+            let bounded = !content[..start]
+                .chars()
+                .next_back()
+                .is_some_and(is_word_char)
+                && !content[end..].chars().next().is_some_and(is_word_char);
+            if bounded && !overlaps(&excluded, start, end) && !overlaps(&found, start, end) {
+                found.push(start..end);
+            }
+        }
+    }
+    found.sort_by_key(|range| range.start);
+    found
+}
+
+fn shorten_start(text: &str) -> String {
+    let count = text.chars().count();
+    if count <= CONTEXT_CHARS {
+        return text.trim_start().to_string();
+    }
+    let kept: String = text.chars().skip(count - CONTEXT_CHARS).collect();
+    format!("…{}", kept.trim_start())
+}
+
+fn shorten_end(text: &str) -> String {
+    if text.chars().count() <= CONTEXT_CHARS {
+        return text.trim_end().to_string();
+    }
+    let kept: String = text.chars().take(CONTEXT_CHARS).collect();
+    format!("{}…", kept.trim_end())
+}
+
+fn mention(path: &Path, title: &str, content: &str, range: Range<usize>) -> UnlinkedMention {
+    let line_start = content[..range.start].rfind('\n').map_or(0, |idx| idx + 1);
+    let line_end = content[range.end..]
+        .find('\n')
+        .map_or(content.len(), |idx| range.end + idx);
+    UnlinkedMention {
+        path: path.to_string_lossy().to_string(),
+        title: title.to_string(),
+        text: content[range.clone()].to_string(),
+        start: range.start,
+        end: range.end,
+        line: content[..range.start].matches('\n').count() + 1,
+        before: shorten_start(&content[line_start..range.start]),
+        after: shorten_end(content[range.end..line_end].trim_end_matches('\r')),
+    }
+}
+
+// Plain-text mentions of the note's title and aliases in other notes of its
+// workspace. Only notes whose indexed terms include every word of a name are
+// read; names without a word of two letters or more are too short to look for.
+#[tauri::command]
+pub async fn get_unlinked_mentions(
+    path: String,
+    index_state: State<'_, IndexState>,
+) -> Result<Vec<UnlinkedMention>, String> {
+    let target = PathBuf::from(&path);
+    let root = index_state
+        .root_for(&target)
+        .ok_or_else(|| "Note isn't in an open workspace".to_string())?;
+
+    let (names, candidates) = index_state.with_workspace(&root, |index| {
+        let note = index
+            .notes
+            .get(&target)
+            .ok_or_else(|| "Note isn't indexed".to_string())?;
+        let mut names: Vec<String> = std::iter::once(&note.title)
+            .chain(&note.aliases)
+            .map(|name| name.trim().to_string())
+            .filter(|name| markdown::tokenize(name).next().is_some())
+            .collect();
+        names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+        names.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+
+        let words: Vec<Vec<String>> = names
+            .iter()
+            .map(|name| markdown::tokenize(name).collect())
+            .collect();
+        let mut candidates: Vec<(PathBuf, String)> = index
+            .notes
+            .values()
+            .filter(|other| other.path != target)
+            .filter(|other| {
+                words
+                    .iter()
+                    .any(|words| words.iter().all(|word| other.terms.contains_key(word)))
+            })
+            .map(|other| (other.path.clone(), other.title.clone()))
+            .collect();
+        candidates.sort_by_cached_key(|(_, title)| title.to_lowercase());
+        Ok::<_, String>((names, candidates))
+    })??;
+
+    let mut mentions = Vec::new();
+    for (path, title) in candidates {
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if encryption::is_encrypted(&content) {
+            continue;
+        }
+        for range in find_mentions(&content, &names) {
+            mentions.push(mention(&path, &title, &content, range));
+        }
+    }
+    Ok(mentions)
+}
+
+// Turns mentions in the note at `path` into links to `target`: `[[Title]]`
+// when the text is the title as written, `[[Title|text]]` otherwise. Every
+// range must still hold its text, so a note edited since the mentions were
+// found isn't touched. Returns the note's new content.
+#[tauri::command]
+pub fn link_unlinked_mentions(
+    path: String,
+    target: String,
+    mentions: Vec<MentionRange>,
+    app: AppHandle,
+    window: Window,
+) -> Result<String, String> {
+    let title = markdown::note_title(Path::new(&target));
+    if title.is_empty() {
+        return Err("Target note is required".to_string());
+    }
+    let origin = window.label().to_string();
+    let note = PathBuf::from(&path);
+    network::guard(&app, &note, move |app| {
+        let mut content = properties::read_note(app, &PathBuf::from(&path))?;
+        let mut mentions = mentions;
+        mentions.sort_by_key(|mention| std::cmp::Reverse(mention.start));
+        let mut previous_start = content.len();
+        for mention in &mentions {
+            if mention.end > previous_start
+                || content.get(mention.start..mention.end) != Some(mention.text.as_str())
+            {
+                return Err(
+                    "The note changed since its mentions were found; refresh and try again"
+                        .to_string(),
+                );
+            }
+            previous_start = mention.start;
+            let link = if mention.text == title {
+                format!("[[{}]]", title)
+            } else {
+                format!("[[{}|{}]]", title, mention.text)
+            };
+            content.replace_range(mention.start..mention.end, &link);
+        }
+        if !mentions.is_empty() {
+            properties::save_content(app, &origin, &path, content.clone())?;
+        }
+        Ok(content)
+    })?
+}
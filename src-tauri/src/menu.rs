@@ -0,0 +1,288 @@
+use crate::custom_menu;
+use crate::i18n;
+use crate::keymap::KeymapState;
+use crate::pins;
+use crate::plugins;
+use crate::watcher::WatcherState;
+use crate::windows;
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::{AppHandle, Manager, Wry};
+
+// The app menu, labelled in the current locale
+pub fn build(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let menu = Menu::default(app)?;
+    // Marky's accelerators come from the keymap, so customized shortcuts show here
+    let accelerators = app.state::<KeymapState>().menu_accelerators();
+    let accelerator = |id: &str| accelerators.get(id).cloned();
+
+    // Build all items upfront so they can be injected into the
+    // existing default submenus (File / Edit / View / Window).
+
+    // File items — prepended before the default "Close Window"
+    let new_note = MenuItem::with_id(
+        app,
+        "menu://new-note",
+        i18n::t("menu.new_note"),
+        true,
+        accelerator("menu://new-note"),
+    )?;
+    let new_folder = MenuItem::with_id(
+        app,
+        "menu://new-folder",
+        i18n::t("menu.new_folder"),
+        true,
+        accelerator("menu://new-folder"),
+    )?;
+    let sep_f1 = PredefinedMenuItem::separator(app)?;
+    let open_file = MenuItem::with_id(
+        app,
+        "menu://open-file",
+        i18n::t("menu.open_file"),
+        true,
+        accelerator("menu://open-file"),
+    )?;
+    let open_folder = MenuItem::with_id(
+        app,
+        "menu://open-folder",
+        i18n::t("menu.open_folder"),
+        true,
+        accelerator("menu://open-folder"),
+    )?;
+    let pinned_notes = Submenu::with_id(
+        app,
+        pins::PINNED_MENU_ID,
+        i18n::t("menu.pinned_notes"),
+        true,
+    )?;
+    let sep_f2 = PredefinedMenuItem::separator(app)?;
+    let save_note = MenuItem::with_id(
+        app,
+        "menu://save-note",
+        i18n::t("menu.save"),
+        true,
+        accelerator("menu://save-note"),
+    )?;
+    let sep_f3 = PredefinedMenuItem::separator(app)?;
+    let export_note = MenuItem::with_id(
+        app,
+        "menu://export-note",
+        i18n::t("menu.export_note"),
+        true,
+        None::<&str>,
+    )?;
+    let backup_ws = MenuItem::with_id(
+        app,
+        "menu://backup-workspace",
+        i18n::t("menu.backup_workspace"),
+        true,
+        None::<&str>,
+    )?;
+    let sep_f4 = PredefinedMenuItem::separator(app)?;
+    // (Close Window stays as the last item from the default menu)
+
+    // Edit extras — appended after the default Undo/Redo/Cut/Copy/Paste
+    let sep_e1 = PredefinedMenuItem::separator(app)?;
+    let find_in_notes = MenuItem::with_id(
+        app,
+        "menu://search",
+        i18n::t("menu.find_in_notes"),
+        true,
+        accelerator("menu://search"),
+    )?;
+    let cmd_palette = MenuItem::with_id(
+        app,
+        "menu://command-palette",
+        i18n::t("menu.command_palette"),
+        true,
+        accelerator("menu://command-palette"),
+    )?;
+
+    // View items — prepended before the default "Enter Full Screen"
+    let toggle_sidebar = MenuItem::with_id(
+        app,
+        "menu://toggle-sidebar",
+        i18n::t("menu.toggle_sidebar"),
+        true,
+        accelerator("menu://toggle-sidebar"),
+    )?;
+    let sep_v1 = PredefinedMenuItem::separator(app)?;
+    let view_editor = MenuItem::with_id(
+        app,
+        "menu://view-editor",
+        i18n::t("menu.editor_only"),
+        true,
+        accelerator("menu://view-editor"),
+    )?;
+    let view_split = MenuItem::with_id(
+        app,
+        "menu://view-split",
+        i18n::t("menu.split_view"),
+        true,
+        accelerator("menu://view-split"),
+    )?;
+    let view_preview = MenuItem::with_id(
+        app,
+        "menu://view-preview",
+        i18n::t("menu.preview_only"),
+        true,
+        accelerator("menu://view-preview"),
+    )?;
+    let sep_v2 = PredefinedMenuItem::separator(app)?;
+    let focus_mode = MenuItem::with_id(
+        app,
+        "menu://focus-mode",
+        i18n::t("menu.focus_mode"),
+        true,
+        accelerator("menu://focus-mode"),
+    )?;
+    let open_graph = MenuItem::with_id(
+        app,
+        "menu://open-graph",
+        i18n::t("menu.graph_view"),
+        true,
+        None::<&str>,
+    )?;
+    let sep_v3 = PredefinedMenuItem::separator(app)?;
+    // (Enter Full Screen stays as the last item from the default menu)
+
+    // Window extras — appended after default Minimize/Maximize/Close
+    let sep_w1 = PredefinedMenuItem::separator(app)?;
+    let open_settings = MenuItem::with_id(
+        app,
+        "menu://open-settings",
+        i18n::t("menu.preferences"),
+        true,
+        accelerator("menu://open-settings"),
+    )?;
+    let show_shortcuts = MenuItem::with_id(
+        app,
+        "menu://show-shortcuts",
+        i18n::t("menu.keyboard_shortcuts"),
+        true,
+        accelerator("menu://show-shortcuts"),
+    )?;
+
+    // Commands contributed by enabled plugins, routed back to the plugin that owns them
+    let plugin_items = plugins::enabled_manifests(app)
+        .into_iter()
+        .flat_map(|manifest| {
+            manifest.menu.into_iter().map(move |item| {
+                (
+                    format!("plugin://{}/{}", manifest.id, item.command),
+                    item.label,
+                )
+            })
+        })
+        .map(|(id, label)| MenuItem::with_id(app, id, label, true, None::<&str>))
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let mut plugins_position = None;
+
+    // Inject into every existing default submenu by title.
+    for (position, item) in menu.items()?.iter().enumerate() {
+        if let Some(sub) = item.as_submenu() {
+            // Default submenus are matched by their English titles, then renamed
+            match sub.text().as_deref().unwrap_or("") {
+                "File" => {
+                    sub.set_text(i18n::t("menu.file"))?;
+                    // Prepend Marky items so they sit above "Close Window"
+                    sub.prepend_items(&[
+                        &new_note,
+                        &new_folder,
+                        &sep_f1,
+                        &open_file,
+                        &open_folder,
+                        &pinned_notes,
+                        &sep_f2,
+                        &save_note,
+                        &sep_f3,
+                        &export_note,
+                        &backup_ws,
+                        &sep_f4,
+                    ])?;
+                }
+                "Edit" => {
+                    sub.set_text(i18n::t("menu.edit"))?;
+                    sub.append_items(&[
+                        &sep_e1 as &dyn tauri::menu::IsMenuItem<_>,
+                        &find_in_notes,
+                        &cmd_palette,
+                    ])?;
+                }
+                "View" => {
+                    sub.set_text(i18n::t("menu.view"))?;
+                    // Prepend Marky items so "Enter Full Screen" stays last
+                    sub.prepend_items(&[
+                        &toggle_sidebar,
+                        &sep_v1,
+                        &view_editor,
+                        &view_split,
+                        &view_preview,
+                        &sep_v2,
+                        &focus_mode,
+                        &open_graph,
+                        &sep_v3,
+                    ])?;
+                }
+                "Window" => {
+                    plugins_position = Some(position);
+                    sub.set_text(i18n::t("menu.window"))?;
+                    sub.append_items(&[
+                        &sep_w1 as &dyn tauri::menu::IsMenuItem<_>,
+                        &open_settings,
+                        &show_shortcuts,
+                    ])?;
+                }
+                "Help" => {
+                    sub.set_text(i18n::t("menu.help"))?;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if !plugin_items.is_empty() {
+        let items: Vec<&dyn tauri::menu::IsMenuItem<_>> = plugin_items
+            .iter()
+            .map(|item| item as &dyn tauri::menu::IsMenuItem<_>)
+            .collect();
+        let plugins_menu = Submenu::with_items(app, i18n::t("menu.plugins"), true, &items)?;
+        // Before Window, where the platform puts its own menus last
+        match plugins_position {
+            Some(position) => {
+                menu.insert(&plugins_menu, position)?;
+                plugins_position = Some(position + 1);
+            }
+            None => menu.append(&plugins_menu)?,
+        }
+    }
+
+    // Entries users defined in settings, in the order they listed them
+    let custom_items = custom_menu::items(app)?;
+    if !custom_items.is_empty() {
+        let items: Vec<&dyn tauri::menu::IsMenuItem<_>> = custom_items
+            .iter()
+            .map(|item| item as &dyn tauri::menu::IsMenuItem<_>)
+            .collect();
+        let custom = Submenu::with_items(app, i18n::t("menu.custom"), true, &items)?;
+        match plugins_position {
+            Some(position) => menu.insert(&custom, position)?,
+            None => menu.append(&custom)?,
+        }
+    }
+
+    Ok(menu)
+}
+
+// Swaps in a freshly labelled menu after the locale changes
+pub fn rebuild(app: &AppHandle) {
+    if let Err(e) = build(app).and_then(|menu| app.set_menu(menu)) {
+        tracing::error!("Failed to rebuild menu: {}", e);
+        return;
+    }
+
+    // The new Pinned Notes submenu starts empty; refill it for the focused workspace
+    let label = windows::focused_label(app);
+    if let Some(root) = app.state::<WatcherState>().active_root(&label) {
+        pins::refresh_menu(app, &root);
+    }
+}
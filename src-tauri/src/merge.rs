@@ -0,0 +1,175 @@
+use serde::Serialize;
+use similar::{capture_diff_slices_deadline, Algorithm, DiffOp};
+use std::time::{Duration, Instant};
+
+// Past this the line matching settles for a coarser result, which only
+// makes conflicts larger
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+// Either text both sides agree on, or a region where they made different
+// changes to the same lines
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MergeChunk {
+    Resolved {
+        text: String,
+    },
+    Conflict {
+        base: String,
+        ours: String,
+        theirs: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Merge {
+    pub chunks: Vec<MergeChunk>,
+    // The whole text, with git-style markers around each conflict
+    pub merged: String,
+    pub conflicts: usize,
+}
+
+// Lines keep their endings, so joining them gives back the exact text
+fn lines(text: &str) -> Vec<&str> {
+    text.split_inclusive('\n').collect()
+}
+
+// For each base line, where it sits in `other` if that side left it alone
+fn kept_lines(base: &[&str], other: &[&str], deadline: Instant) -> Vec<Option<usize>> {
+    let mut kept = vec![None; base.len()];
+    for op in capture_diff_slices_deadline(Algorithm::Myers, base, other, Some(deadline)) {
+        if let DiffOp::Equal {
+            old_index,
+            new_index,
+            len,
+        } = op
+        {
+            for offset in 0..len {
+                kept[old_index + offset] = Some(new_index + offset);
+            }
+        }
+    }
+    kept
+}
+
+fn push_resolved(chunks: &mut Vec<MergeChunk>, lines: &[&str]) {
+    if lines.is_empty() {
+        return;
+    }
+    let text = lines.concat();
+    match chunks.last_mut() {
+        Some(MergeChunk::Resolved { text: last }) => last.push_str(&text),
+        _ => chunks.push(MergeChunk::Resolved { text }),
+    }
+}
+
+// One stretch between lines all three versions share
+fn merge_region(chunks: &mut Vec<MergeChunk>, base: &[&str], ours: &[&str], theirs: &[&str]) {
+    if ours == theirs || theirs == base {
+        return push_resolved(chunks, ours);
+    }
+    if ours == base {
+        return push_resolved(chunks, theirs);
+    }
+    // Lines both sides added at the edges of the conflict aren't part of it
+    let prefix = ours
+        .iter()
+        .zip(theirs)
+        .take_while(|(ours, theirs)| ours == theirs)
+        .count();
+    let suffix = ours[prefix..]
+        .iter()
+        .rev()
+        .zip(theirs[prefix..].iter().rev())
+        .take_while(|(ours, theirs)| ours == theirs)
+        .count();
+    push_resolved(chunks, &ours[..prefix]);
+    chunks.push(MergeChunk::Conflict {
+        base: base.concat(),
+        ours: ours[prefix..ours.len() - suffix].concat(),
+        theirs: theirs[prefix..theirs.len() - suffix].concat(),
+    });
+    push_resolved(chunks, &ours[ours.len() - suffix..]);
+}
+
+fn with_newline(text: &str) -> String {
+    if text.is_empty() || text.ends_with('\n') {
+        text.to_string()
+    } else {
+        format!("{}\n", text)
+    }
+}
+
+fn render(chunks: &[MergeChunk]) -> String {
+    let mut merged = String::new();
+    for chunk in chunks {
+        match chunk {
+            MergeChunk::Resolved { text } => merged.push_str(text),
+            MergeChunk::Conflict { base, ours, theirs } => {
+                merged = with_newline(&merged);
+                merged.push_str("<<<<<<< ours\n");
+                merged.push_str(&with_newline(ours));
+                merged.push_str("||||||| base\n");
+                merged.push_str(&with_newline(base));
+                merged.push_str("=======\n");
+                merged.push_str(&with_newline(theirs));
+                merged.push_str(">>>>>>> theirs\n");
+            }
+        }
+    }
+    merged
+}
+
+// Three-way merge by lines: changes only one side made are taken as is, and
+// overlapping changes become conflicts. `ours` and `theirs` only name the
+// two sides; neither is preferred.
+pub fn merge(base: &str, ours: &str, theirs: &str) -> Merge {
+    let (base, ours, theirs) = (lines(base), lines(ours), lines(theirs));
+    let deadline = Instant::now() + TIMEOUT;
+    let kept_ours = kept_lines(&base, &ours, deadline);
+    let kept_theirs = kept_lines(&base, &theirs, deadline);
+
+    let mut chunks = Vec::new();
+    let (mut i, mut a, mut b) = (0, 0, 0);
+    while i < base.len() || a < ours.len() || b < theirs.len() {
+        if i < base.len() && kept_ours[i] == Some(a) && kept_theirs[i] == Some(b) {
+            push_resolved(&mut chunks, &base[i..=i]);
+            i += 1;
+            a += 1;
+            b += 1;
+            continue;
+        }
+        // Up to the next base line neither side touched, or the end
+        let (next, next_a, next_b) = (i..base.len())
+            .find_map(|k| Some((k, kept_ours[k]?, kept_theirs[k]?)))
+            .unwrap_or((base.len(), ours.len(), theirs.len()));
+        merge_region(
+            &mut chunks,
+            &base[i..next],
+            &ours[a..next_a],
+            &theirs[b..next_b],
+        );
+        (i, a, b) = (next, next_a, next_b);
+    }
+
+    let conflicts = chunks
+        .iter()
+        .filter(|chunk| matches!(chunk, MergeChunk::Conflict { .. }))
+        .count();
+    Merge {
+        merged: render(&chunks),
+        chunks,
+        conflicts,
+    }
+}
+
+// Merges two edits of the same note made from a common version, such as a
+// draft and a change synced in from another machine. The result can be
+// saved as is when `conflicts` is 0; otherwise the UI picks a side per
+// conflict from `chunks`.
+#[tauri::command]
+pub async fn merge_texts(base: String, ours: String, theirs: String) -> Result<Merge, String> {
+    tauri::async_runtime::spawn_blocking(move || merge(&base, &ours, &theirs))
+        .await
+        .map_err(|e| format!("Merge failed: {}", e))
+}
@@ -0,0 +1,325 @@
+use crate::i18n;
+use crate::watcher::WatcherState;
+use crate::windows;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+// How long a command on a network share may take before the share counts as dropped
+const OPERATION_TIMEOUT: Duration = Duration::from_secs(5);
+// Copies and exports grow with the amount of data, so they get longer
+const BULK_OPERATION_TIMEOUT: Duration = Duration::from_secs(120);
+// Reads that timed out on a share that still answers are tried again this often
+const READ_RETRIES: usize = 1;
+// A slow share gets a couple of quick checks before it's declared offline
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+const PROBE_RETRIES: usize = 2;
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize)]
+struct WorkspaceAvailability {
+    root: String,
+}
+
+#[derive(Default)]
+pub struct NetworkState {
+    // Whether each workspace lives on a network share, checked once per root
+    remote: Mutex<HashMap<PathBuf, bool>>,
+    // Shares that stopped answering; commands under them fail fast until they return
+    offline: Mutex<HashSet<PathBuf>>,
+}
+
+#[cfg(target_os = "macos")]
+fn detect_remote(path: &Path) -> bool {
+    use std::ffi::{CStr, CString};
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(c_path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    // SAFETY: statfs only writes into the zeroed struct we hand it
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return false;
+    }
+    let fs_type = unsafe { CStr::from_ptr(stat.f_fstypename.as_ptr()) };
+    matches!(
+        fs_type.to_bytes(),
+        b"smbfs" | b"nfs" | b"afpfs" | b"webdav" | b"cifs"
+    )
+}
+
+#[cfg(target_os = "linux")]
+fn detect_remote(path: &Path) -> bool {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    const NFS_SUPER_MAGIC: i64 = 0x6969;
+    const SMB_SUPER_MAGIC: i64 = 0x517B;
+    const CIFS_SUPER_MAGIC: i64 = 0xFF53_4D42;
+    const SMB2_SUPER_MAGIC: i64 = 0xFE53_4D42;
+
+    let Ok(c_path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    // SAFETY: statfs only writes into the zeroed struct we hand it
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return false;
+    }
+    #[allow(clippy::unnecessary_cast)]
+    let magic = stat.f_type as i64;
+    matches!(
+        magic,
+        NFS_SUPER_MAGIC | SMB_SUPER_MAGIC | CIFS_SUPER_MAGIC | SMB2_SUPER_MAGIC
+    )
+}
+
+#[cfg(windows)]
+fn detect_remote(path: &Path) -> bool {
+    use std::path::{Component, Prefix};
+    use windows::core::PCWSTR;
+    use windows::Win32::Storage::FileSystem::GetDriveTypeW;
+
+    const DRIVE_REMOTE: u32 = 4;
+
+    match path.components().next() {
+        Some(Component::Prefix(prefix)) => match prefix.kind() {
+            Prefix::UNC(..) | Prefix::VerbatimUNC(..) => true,
+            Prefix::Disk(letter) | Prefix::VerbatimDisk(letter) => {
+                let drive: Vec<u16> = format!("{}:\\", letter as char)
+                    .encode_utf16()
+                    .chain(std::iter::once(0))
+                    .collect();
+                // SAFETY: `drive` is a NUL-terminated UTF-16 string that outlives the call
+                unsafe { GetDriveTypeW(PCWSTR(drive.as_ptr())) == DRIVE_REMOTE }
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+fn detect_remote(_path: &Path) -> bool {
+    false
+}
+
+// The workspace a path belongs to, or the path itself outside watched workspaces
+fn root_of(app: &AppHandle, path: &Path) -> PathBuf {
+    app.state::<WatcherState>()
+        .root_for(path)
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+fn is_remote(app: &AppHandle, root: &Path) -> bool {
+    let state = app.state::<NetworkState>();
+    if let Some(remote) = state
+        .remote
+        .lock()
+        .ok()
+        .and_then(|remote| remote.get(root).copied())
+    {
+        return remote;
+    }
+    let remote = detect_remote(root);
+    if remote {
+        tracing::info!("{} is on a network share", root.display());
+    }
+    if let Ok(mut cache) = state.remote.lock() {
+        cache.insert(root.to_path_buf(), remote);
+    }
+    remote
+}
+
+// Runs `work` on a helper thread, giving up after `timeout`. A stuck thread is
+// left behind; the kernel releases it when the share answers or the mount dies.
+fn run_with_timeout<T: Send + 'static>(
+    timeout: Duration,
+    work: impl FnOnce() -> T + Send + 'static,
+) -> Option<T> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(work());
+    });
+    receiver.recv_timeout(timeout).ok()
+}
+
+fn responds(root: &Path) -> bool {
+    let root = root.to_path_buf();
+    run_with_timeout(PROBE_TIMEOUT, move || root.metadata().is_ok()).unwrap_or(false)
+}
+
+fn broadcast(app: &AppHandle, event: &str, root: &Path) {
+    let payload = WorkspaceAvailability {
+        root: root.to_string_lossy().to_string(),
+    };
+    windows::emit_to_workspace(app, root, event, payload);
+}
+
+// Polls a dropped share until it answers again, then lets commands through
+fn spawn_reconnect(app: AppHandle, root: PathBuf) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(RECONNECT_INTERVAL);
+        if !responds(&root) {
+            continue;
+        }
+        if let Ok(mut offline) = app.state::<NetworkState>().offline.lock() {
+            offline.remove(&root);
+        }
+        tracing::info!("{} is reachable again", root.display());
+        broadcast(&app, "workspace-online", &root);
+        break;
+    });
+}
+
+fn mark_offline(app: &AppHandle, root: &Path) {
+    let newly_offline = app
+        .state::<NetworkState>()
+        .offline
+        .lock()
+        .map(|mut offline| offline.insert(root.to_path_buf()))
+        .unwrap_or(false);
+    if newly_offline {
+        tracing::warn!("{} stopped responding", root.display());
+        broadcast(app, "workspace-offline", root);
+        spawn_reconnect(app.clone(), root.to_path_buf());
+    }
+}
+
+fn offline_error(root: &Path) -> String {
+    i18n::tf("error.share_offline", &[&root.display()])
+}
+
+// Fails fast for paths on a share that already stopped answering
+pub fn ensure_online(app: &AppHandle, path: &Path) -> Result<(), String> {
+    let Some(state) = app.try_state::<NetworkState>() else {
+        return Ok(());
+    };
+    let Ok(offline) = state.offline.lock() else {
+        return Ok(());
+    };
+    match offline.iter().find(|root| path.starts_with(root)) {
+        Some(root) => Err(offline_error(root)),
+        None => Ok(()),
+    }
+}
+
+enum Outcome<T> {
+    Done(T),
+    // Timed out, but the share still answers probes
+    Slow,
+    Offline,
+}
+
+fn attempt<T: Send + 'static>(
+    app: &AppHandle,
+    root: &Path,
+    timeout: Duration,
+    work: impl FnOnce(&AppHandle) -> T + Send + 'static,
+) -> Outcome<T> {
+    let worker = app.clone();
+    if let Some(result) = run_with_timeout(timeout, move || work(&worker)) {
+        return Outcome::Done(result);
+    }
+    // Only slow, or really gone?
+    if (0..PROBE_RETRIES).any(|_| responds(root)) {
+        Outcome::Slow
+    } else {
+        Outcome::Offline
+    }
+}
+
+fn slow_error(root: &Path) -> String {
+    i18n::tf("error.share_slow", &[&root.display()])
+}
+
+fn run_guarded<T: Send + 'static>(
+    app: &AppHandle,
+    path: &Path,
+    timeout: Duration,
+    work: impl FnOnce(&AppHandle) -> T + Send + 'static,
+) -> Result<T, String> {
+    ensure_online(app, path)?;
+    let root = root_of(app, path);
+    if !is_remote(app, &root) {
+        return Ok(work(app));
+    }
+
+    match attempt(app, &root, timeout, work) {
+        Outcome::Done(result) => Ok(result),
+        Outcome::Slow => Err(slow_error(&root)),
+        Outcome::Offline => {
+            mark_offline(app, &root);
+            Err(offline_error(&root))
+        }
+    }
+}
+
+// Filesystem work for `path`. On local disks it simply runs; on network shares
+// it runs with a timeout so a dropped connection can't freeze the window, and
+// a share that stays silent is reported with `workspace-offline`. Writes are
+// never retried, since the timed-out attempt may still land.
+pub fn guard<T: Send + 'static>(
+    app: &AppHandle,
+    path: &Path,
+    work: impl FnOnce(&AppHandle) -> T + Send + 'static,
+) -> Result<T, String> {
+    run_guarded(app, path, OPERATION_TIMEOUT, work)
+}
+
+// `guard` with a longer timeout, for copies and exports
+pub fn guard_bulk<T: Send + 'static>(
+    app: &AppHandle,
+    path: &Path,
+    work: impl FnOnce(&AppHandle) -> T + Send + 'static,
+) -> Result<T, String> {
+    run_guarded(app, path, BULK_OPERATION_TIMEOUT, work)
+}
+
+// `guard` for work that only reads, so it's safe to run again: a timeout on a
+// share that still answers is retried before giving up
+pub fn guard_read<T: Send + 'static>(
+    app: &AppHandle,
+    path: &Path,
+    work: impl Fn(&AppHandle) -> T + Send + Sync + 'static,
+) -> Result<T, String> {
+    ensure_online(app, path)?;
+    let root = root_of(app, path);
+    if !is_remote(app, &root) {
+        return Ok(work(app));
+    }
+
+    let work = Arc::new(work);
+    for _ in 0..=READ_RETRIES {
+        let work = work.clone();
+        match attempt(app, &root, OPERATION_TIMEOUT, move |app| work(app)) {
+            Outcome::Done(result) => return Ok(result),
+            Outcome::Slow => tracing::info!("Retrying a slow read on {}", root.display()),
+            Outcome::Offline => {
+                mark_offline(app, &root);
+                return Err(offline_error(&root));
+            }
+        }
+    }
+    Err(slow_error(&root))
+}
+
+// Network shares whose connection dropped, for windows opened while offline
+#[tauri::command]
+pub fn get_offline_workspaces(app: AppHandle) -> Vec<String> {
+    app.state::<NetworkState>()
+        .offline
+        .lock()
+        .map(|offline| {
+            offline
+                .iter()
+                .map(|root| root.to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
@@ -0,0 +1,211 @@
+use crate::index::IndexState;
+use crate::markdown;
+use crate::network;
+use crate::properties::{self, PropertyValue};
+use crate::storage;
+use crate::watcher::WatcherState;
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use serde_yaml::{Mapping, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State, Window};
+
+const REGISTRY_FILE: &str = "note-ids.json";
+// The frontmatter key holding a note's id
+const ID_KEY: &str = "id";
+
+// Note id -> where the note was last seen, relative to the workspace root
+type Registry = BTreeMap<String, String>;
+
+#[derive(Default)]
+pub struct NoteIdState {
+    // Serializes read-modify-write cycles on the registry
+    lock: Mutex<()>,
+}
+
+// What the index knows: every id with the notes carrying it, and every note
+// it could read (vault and encrypted notes aren't among them)
+struct Indexed {
+    ids: BTreeMap<String, Vec<PathBuf>>,
+    paths: HashSet<PathBuf>,
+}
+
+fn registry_path(app: &AppHandle, root: &Path) -> Result<PathBuf, String> {
+    Ok(storage::workspace_data_dir(app, root)?.join(REGISTRY_FILE))
+}
+
+pub fn frontmatter_id(frontmatter: &Mapping) -> Option<String> {
+    let id = match frontmatter.get(ID_KEY)? {
+        Value::String(text) => text.trim().to_string(),
+        Value::Number(number) => number.to_string(),
+        _ => return None,
+    };
+    (!id.is_empty()).then_some(id)
+}
+
+fn note_id(content: &str) -> Option<String> {
+    markdown::parse_frontmatter(content)
+        .as_ref()
+        .and_then(frontmatter_id)
+}
+
+fn new_id() -> String {
+    let mut bytes = [0u8; 8];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn relative_path(root: &Path, path: &Path) -> Option<String> {
+    path.strip_prefix(root)
+        .ok()
+        .map(|relative| relative.to_string_lossy().to_string())
+}
+
+fn indexed(app: &AppHandle, root: &Path) -> Result<Indexed, String> {
+    app.state::<IndexState>().with_workspace(root, |index| {
+        let mut ids: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+        for note in index.notes.values() {
+            if let Some(id) = frontmatter_id(&note.frontmatter) {
+                ids.entry(id).or_default().push(note.path.clone());
+            }
+        }
+        for paths in ids.values_mut() {
+            paths.sort();
+        }
+        Indexed {
+            ids,
+            paths: index.notes.keys().cloned().collect(),
+        }
+    })
+}
+
+// Brings the registry up to date with the index: a moved note's id points at
+// its new path, and ids whose note is gone are dropped. When two notes carry
+// the same id (a copied file), the registered one keeps it. Returns whether
+// anything changed.
+fn sync(registry: &mut Registry, root: &Path, indexed: &Indexed) -> bool {
+    let before = registry.clone();
+    registry.retain(|id, relative| {
+        let path = root.join(relative.as_str());
+        indexed.ids.contains_key(id) || (path.exists() && !indexed.paths.contains(&path))
+    });
+    for (id, paths) in &indexed.ids {
+        let registered = registry.get(id).map(|relative| root.join(relative));
+        if registered.is_some_and(|path| paths.contains(&path)) {
+            continue;
+        }
+        if let Some(relative) = paths.first().and_then(|path| relative_path(root, path)) {
+            registry.insert(id.clone(), relative);
+        }
+    }
+    *registry != before
+}
+
+fn update(app: &AppHandle, root: &Path) -> Result<Registry, String> {
+    let state = app.state::<NoteIdState>();
+    let _guard = state
+        .lock
+        .lock()
+        .map_err(|e| format!("Failed to lock note ids: {}", e))?;
+    let path = registry_path(app, root)?;
+    let mut registry: Registry = storage::read_json(&path);
+    let indexed = indexed(app, root)?;
+    if sync(&mut registry, root, &indexed) {
+        storage::write_json(&path, &registry)?;
+    }
+    Ok(registry)
+}
+
+// Follows renames and moves the watcher saw; called after each batch. Until
+// an id has been registered there's nothing to keep up to date.
+pub fn refresh(app: &AppHandle, root: &Path) {
+    if !registry_path(app, root).is_ok_and(|path| path.exists()) {
+        return;
+    }
+    if let Err(e) = update(app, root) {
+        tracing::warn!("Failed to update note ids for {}: {}", root.display(), e);
+    }
+}
+
+// The note's `id:`, written into its frontmatter first if it has none. A note
+// that shares its id with another (a copy) is given a new one.
+#[tauri::command]
+pub fn ensure_note_id(
+    path: String,
+    app: AppHandle,
+    window: Window,
+    watcher_state: State<WatcherState>,
+    index_state: State<IndexState>,
+) -> Result<String, String> {
+    let note = PathBuf::from(&path);
+    let root = watcher_state
+        .root_for(&note)
+        .or_else(|| index_state.root_for(&note))
+        .ok_or_else(|| "Note isn't in an open workspace".to_string())?;
+    let origin = window.label().to_string();
+    let target = note.clone();
+
+    network::guard(&app, &target, move |app| {
+        let content = properties::read_note(app, &note)?;
+        let state = app.state::<NoteIdState>();
+        let _guard = state
+            .lock
+            .lock()
+            .map_err(|e| format!("Failed to lock note ids: {}", e))?;
+        let registry_file = registry_path(app, &root)?;
+        let mut registry: Registry = storage::read_json(&registry_file);
+
+        let taken = |id: &str| {
+            registry.get(id).is_some_and(|relative| {
+                let other = root.join(relative);
+                other != note
+                    && properties::read_note(app, &other)
+                        .ok()
+                        .and_then(|content| note_id(&content))
+                        .as_deref()
+                        == Some(id)
+            })
+        };
+        let id = match note_id(&content) {
+            Some(id) if !taken(&id) => id,
+            _ => {
+                let id = new_id();
+                let next = properties::with_property(
+                    &content,
+                    ID_KEY,
+                    Some(&PropertyValue::Text(id.clone())),
+                )?;
+                properties::save_content(app, &origin, &path, next)?;
+                app.state::<IndexState>().apply_change(&root, &note);
+                id
+            }
+        };
+
+        let relative =
+            relative_path(&root, &note).ok_or_else(|| "Note isn't in the workspace".to_string())?;
+        if registry.get(&id) != Some(&relative) {
+            registry.insert(id.clone(), relative);
+            storage::write_json(&registry_file, &registry)?;
+        }
+        Ok(id)
+    })?
+}
+
+// Where the note with `id` is now, or None when no note in the workspace has it
+#[tauri::command]
+pub async fn resolve_note_id(
+    folder_path: String,
+    id: String,
+    app: AppHandle,
+) -> Result<Option<String>, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+    let registry = update(&app, &root)?;
+    Ok(registry
+        .get(id.trim())
+        .map(|relative| root.join(relative).to_string_lossy().to_string()))
+}
@@ -0,0 +1,123 @@
+use crate::i18n;
+use crate::markdown;
+use crate::settings::SettingsState;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+// Saves made through the app come back as watcher events a moment later
+const OWN_WRITE_GRACE: Duration = Duration::from_secs(5);
+// However often another tool rewrites a note, it's announced once a minute at most
+const NOTE_COOLDOWN: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    ExternalChanges,
+    Operations,
+    SyncConflicts,
+    Reminders,
+}
+
+#[derive(Default)]
+pub struct NotificationState {
+    own_writes: Mutex<HashMap<PathBuf, Instant>>,
+    notified: Mutex<HashMap<PathBuf, Instant>>,
+}
+
+fn is_enabled(app: &AppHandle, category: Category) -> bool {
+    let settings = app.state::<SettingsState>().current().notifications;
+    match category {
+        Category::ExternalChanges => settings.external_changes,
+        Category::Operations => settings.operations,
+        Category::SyncConflicts => settings.sync_conflicts,
+        Category::Reminders => settings.reminders,
+    }
+}
+
+// While a Marky window has focus the frontend's toasts cover it
+fn should_notify(app: &AppHandle, category: Category) -> bool {
+    is_enabled(app, category)
+        && !app
+            .webview_windows()
+            .values()
+            .any(|window| window.is_focused().unwrap_or(false))
+}
+
+pub fn notify(app: &AppHandle, category: Category, title: &str, body: &str) {
+    if !should_notify(app, category) {
+        return;
+    }
+
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        tracing::warn!("Failed to show notification: {}", e);
+    }
+}
+
+// Marks a note as just written by the app so its watcher echo stays quiet
+pub fn record_write(app: &AppHandle, path: &Path) {
+    let state = app.state::<NotificationState>();
+    let Ok(mut writes) = state.own_writes.lock() else {
+        return;
+    };
+    let now = Instant::now();
+    writes.retain(|_, at| now.duration_since(*at) < OWN_WRITE_GRACE);
+    writes.insert(path.to_path_buf(), now);
+}
+
+// Called by the watcher when a note's contents change on disk
+pub fn note_changed(app: &AppHandle, path: &Path) {
+    if !should_notify(app, Category::ExternalChanges) {
+        return;
+    }
+
+    let state = app.state::<NotificationState>();
+    let now = Instant::now();
+
+    let written_by_app = state
+        .own_writes
+        .lock()
+        .map(|writes| {
+            writes
+                .get(path)
+                .is_some_and(|at| now.duration_since(*at) < OWN_WRITE_GRACE)
+        })
+        .unwrap_or(false);
+    if written_by_app {
+        return;
+    }
+
+    {
+        let Ok(mut notified) = state.notified.lock() else {
+            return;
+        };
+        if notified
+            .get(path)
+            .is_some_and(|at| now.duration_since(*at) < NOTE_COOLDOWN)
+        {
+            return;
+        }
+        notified.retain(|_, at| now.duration_since(*at) < NOTE_COOLDOWN);
+        notified.insert(path.to_path_buf(), now);
+    }
+
+    notify(
+        app,
+        Category::ExternalChanges,
+        &i18n::t("notification.note_changed_title"),
+        &i18n::tf(
+            "notification.note_changed_body",
+            &[&markdown::note_title(path)],
+        ),
+    );
+}
+
+// For work that finishes in the frontend, like backups and exports
+#[tauri::command]
+pub fn send_notification(category: Category, title: String, body: String, app: AppHandle) {
+    notify(&app, category, &title, &body);
+}
@@ -0,0 +1,258 @@
+#[cfg(desktop)]
+use crate::i18n;
+use crate::markdown;
+use crate::storage;
+use crate::vault;
+use crate::watcher::WatcherState;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+#[cfg(desktop)]
+use tauri::menu::MenuItem;
+use tauri::{AppHandle, Manager, State};
+
+const PINS_FILE: &str = "pins.json";
+#[cfg(desktop)]
+pub const PINNED_MENU_ID: &str = "menu://pinned";
+#[cfg(desktop)]
+const MAX_MENU_ITEMS: usize = 20;
+
+// Paths are stored relative to the workspace root so moving the vault keeps its pins
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredPin {
+    path: String,
+    is_dir: bool,
+    pinned_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PinnedEntry {
+    path: String,
+    name: String,
+    is_dir: bool,
+    pinned_at: i64,
+}
+
+// Serializes read-modify-write cycles on the pins file
+#[derive(Default)]
+pub struct PinsState {
+    lock: Mutex<()>,
+}
+
+fn pins_path(app: &AppHandle, root: &Path) -> Result<PathBuf, String> {
+    Ok(storage::workspace_data_dir(app, root)?.join(PINS_FILE))
+}
+
+fn relative_key(root: &Path, path: &Path) -> Option<String> {
+    path.strip_prefix(root)
+        .ok()
+        .map(|relative| relative.to_string_lossy().to_string())
+}
+
+// Drops pins whose files were deleted outside the app; returns whether anything changed
+fn prune_missing(root: &Path, pins: &mut Vec<StoredPin>) -> bool {
+    let before = pins.len();
+    pins.retain(|pin| {
+        let path = root.join(&pin.path);
+        if pin.is_dir {
+            path.is_dir()
+        } else {
+            path.is_file()
+        }
+    });
+    pins.len() != before
+}
+
+fn to_entries(root: &Path, pins: &[StoredPin]) -> Vec<PinnedEntry> {
+    pins.iter()
+        .map(|pin| {
+            let path = root.join(&pin.path);
+            let name = if pin.is_dir {
+                path.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            } else {
+                markdown::note_title(&path)
+            };
+            PinnedEntry {
+                path: path.to_string_lossy().to_string(),
+                name,
+                is_dir: pin.is_dir,
+                pinned_at: pin.pinned_at,
+            }
+        })
+        .collect()
+}
+
+fn load_pins(app: &AppHandle, root: &Path) -> Result<Vec<PinnedEntry>, String> {
+    let path = pins_path(app, root)?;
+    let mut pins: Vec<StoredPin> = storage::read_json(&path);
+    if prune_missing(root, &mut pins) {
+        storage::write_json(&path, &pins)?;
+    }
+    Ok(to_entries(root, &pins))
+}
+
+// Rebuilds the "Pinned Notes" submenu from the given workspace's pins
+#[cfg(desktop)]
+pub fn refresh_menu(app: &AppHandle, root: &Path) {
+    let pins = {
+        let pins_state = app.state::<PinsState>();
+        let Ok(_guard) = pins_state.lock.lock() else {
+            return;
+        };
+        match load_pins(app, root) {
+            Ok(pins) => pins,
+            Err(e) => {
+                tracing::error!("Failed to load pins: {}", e);
+                return;
+            }
+        }
+    };
+
+    let Some(submenu) = app
+        .menu()
+        .and_then(|menu| menu.get(PINNED_MENU_ID))
+        .and_then(|item| item.as_submenu().cloned())
+    else {
+        return;
+    };
+
+    if let Ok(items) = submenu.items() {
+        for item in items {
+            let _ = submenu.remove(&item);
+        }
+    }
+
+    let notes: Vec<&PinnedEntry> = pins.iter().filter(|pin| !pin.is_dir).collect();
+    if notes.is_empty() {
+        if let Ok(empty) = MenuItem::new(app, i18n::t("menu.no_pinned_notes"), false, None::<&str>)
+        {
+            let _ = submenu.append(&empty);
+        }
+        return;
+    }
+
+    // `recent://` ids are routed to the focused window by the menu handler
+    for pin in notes.into_iter().take(MAX_MENU_ITEMS) {
+        let id = format!("recent://{}", pin.path);
+        if let Ok(item) = MenuItem::with_id(app, id, &pin.name, true, None::<&str>) {
+            let _ = submenu.append(&item);
+        }
+    }
+}
+
+// Mobile apps have no menu bar to keep in sync
+#[cfg(mobile)]
+pub fn refresh_menu(_app: &AppHandle, _root: &Path) {}
+
+// Keeps pins attached to entries renamed or moved through the app
+pub fn rename_path(app: &AppHandle, root: &Path, from: &Path, to: &Path) -> Result<(), String> {
+    let (Some(from_key), Some(to_key)) = (relative_key(root, from), relative_key(root, to)) else {
+        return Ok(());
+    };
+
+    let pins_state = app.state::<PinsState>();
+    let _guard = pins_state
+        .lock
+        .lock()
+        .map_err(|e| format!("Failed to lock pins: {}", e))?;
+
+    let path = pins_path(app, root)?;
+    let mut pins: Vec<StoredPin> = storage::read_json(&path);
+    let mut changed = false;
+
+    for pin in pins.iter_mut() {
+        let pin_path = Path::new(&pin.path);
+        if let Ok(rest) = pin_path.strip_prefix(&from_key) {
+            pin.path = Path::new(&to_key).join(rest).to_string_lossy().to_string();
+            changed = true;
+        }
+    }
+
+    if changed {
+        storage::write_json(&path, &pins)?;
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn pin_entry(
+    path: String,
+    pinned: Option<bool>,
+    folder_path: Option<String>,
+    app: AppHandle,
+    watcher_state: State<WatcherState>,
+    pins_state: State<PinsState>,
+) -> Result<Vec<PinnedEntry>, String> {
+    let target = PathBuf::from(&path);
+    if vault::vault_root(&target).is_some() {
+        return Err("Pinning is not available in encrypted vaults".to_string());
+    }
+    if !target.exists() {
+        return Err("Path does not exist".to_string());
+    }
+
+    // The frontend may pin before its watcher is up, so it can name the workspace itself
+    let root = folder_path
+        .map(PathBuf::from)
+        .filter(|root| target.starts_with(root))
+        .or_else(|| watcher_state.root_for(&target))
+        .ok_or("Path is not inside an open workspace")?;
+    let key = relative_key(&root, &target).ok_or("Path is not inside an open workspace")?;
+
+    let entries = {
+        let _guard = pins_state
+            .lock
+            .lock()
+            .map_err(|e| format!("Failed to lock pins: {}", e))?;
+
+        let file = pins_path(&app, &root)?;
+        let mut pins: Vec<StoredPin> = storage::read_json(&file);
+        prune_missing(&root, &mut pins);
+
+        let position = pins.iter().position(|pin| pin.path == key);
+        // Without an explicit value this toggles
+        let pin = pinned.unwrap_or(position.is_none());
+
+        match (pin, position) {
+            (true, None) => pins.push(StoredPin {
+                path: key,
+                is_dir: target.is_dir(),
+                pinned_at: Utc::now().timestamp_millis(),
+            }),
+            (false, Some(position)) => {
+                pins.remove(position);
+            }
+            _ => {}
+        }
+
+        storage::write_json(&file, &pins)?;
+        to_entries(&root, &pins)
+    };
+
+    refresh_menu(&app, &root);
+
+    Ok(entries)
+}
+
+#[tauri::command]
+pub fn list_pinned(
+    folder_path: String,
+    app: AppHandle,
+    pins_state: State<PinsState>,
+) -> Result<Vec<PinnedEntry>, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+
+    let _guard = pins_state
+        .lock
+        .lock()
+        .map_err(|e| format!("Failed to lock pins: {}", e))?;
+
+    load_pins(&app, &root)
+}
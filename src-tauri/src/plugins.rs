@@ -0,0 +1,413 @@
+use crate::index::IndexState;
+use crate::markdown;
+use crate::safe_mode::SafeModeState;
+use crate::settings::SettingsState;
+use crate::storage;
+use crate::vault;
+use crate::watcher::WatcherState;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State, Window};
+
+const PLUGINS_DIR: &str = "plugins";
+const MANIFEST_FILE: &str = "manifest.json";
+const ENABLED_FILE: &str = "plugins.json";
+// Plugin entry points are single scripts, not bundles of assets
+const MAX_SOURCE_BYTES: u64 = 5 * 1024 * 1024;
+
+// What a plugin may ask of the host. Registering commands, menu items, and
+// notifications needs no permission; everything that touches notes does.
+pub const PERMISSIONS: [&str; 4] = ["notes:read", "notes:write", "events", "network"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginMenuItem {
+    // Command id the plugin registers at runtime
+    pub command: String,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginManifest {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub author: String,
+    // Script run in the plugin's worker, relative to its folder
+    #[serde(default = "default_main")]
+    pub main: String,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    // Shown under the Plugins menu while the plugin is enabled
+    #[serde(default)]
+    pub menu: Vec<PluginMenuItem>,
+}
+
+fn default_main() -> String {
+    "main.js".to_string()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginInfo {
+    pub id: String,
+    pub enabled: bool,
+    pub path: String,
+    pub manifest: Option<PluginManifest>,
+    // Why the plugin can't be enabled, e.g. a broken manifest
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct PluginsChangedEvent {
+    id: String,
+    enabled: bool,
+}
+
+// Serializes read-modify-write cycles on the enabled-plugins file
+#[derive(Default)]
+pub struct PluginsState {
+    lock: Mutex<()>,
+}
+
+pub fn plugins_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = storage::app_config_dir(app)?.join(PLUGINS_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create plugins folder: {}", e))?;
+    Ok(dir)
+}
+
+fn enabled_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(storage::app_config_dir(app)?.join(ENABLED_FILE))
+}
+
+fn read_enabled(app: &AppHandle) -> HashSet<String> {
+    enabled_path(app)
+        .map(|path| storage::read_json(&path))
+        .unwrap_or_default()
+}
+
+fn valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '.')
+        && !id.starts_with('.')
+}
+
+// The entry point has to stay inside the plugin's own folder
+fn is_contained(relative: &str) -> bool {
+    let path = Path::new(relative);
+    !relative.is_empty()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+fn read_manifest(dir: &Path) -> Result<PluginManifest, String> {
+    let raw = fs::read_to_string(dir.join(MANIFEST_FILE))
+        .map_err(|e| format!("Failed to read {}: {}", MANIFEST_FILE, e))?;
+    let manifest: PluginManifest =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid {}: {}", MANIFEST_FILE, e))?;
+
+    let folder = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if manifest.id != folder {
+        return Err(format!(
+            "Plugin id \"{}\" doesn't match its folder \"{}\"",
+            manifest.id, folder
+        ));
+    }
+    if !valid_id(&manifest.id) {
+        return Err("Plugin ids may only use lowercase letters, digits, '-' and '.'".to_string());
+    }
+    if !is_contained(&manifest.main) {
+        return Err(format!(
+            "Entry point {} is outside the plugin",
+            manifest.main
+        ));
+    }
+    if let Some(unknown) = manifest
+        .permissions
+        .iter()
+        .find(|permission| !PERMISSIONS.contains(&permission.as_str()))
+    {
+        return Err(format!("Unknown permission: {}", unknown));
+    }
+    Ok(manifest)
+}
+
+fn plugin_info(dir: &Path, enabled: &HashSet<String>) -> PluginInfo {
+    let id = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let (manifest, error) = match read_manifest(dir) {
+        Ok(manifest) => (Some(manifest), None),
+        Err(e) => (None, Some(e)),
+    };
+    PluginInfo {
+        enabled: enabled.contains(&id) && manifest.is_some(),
+        id,
+        path: dir.to_string_lossy().to_string(),
+        manifest,
+        error,
+    }
+}
+
+pub fn list(app: &AppHandle) -> Result<Vec<PluginInfo>, String> {
+    let dir = plugins_dir(app)?;
+    let enabled = read_enabled(app);
+    let entries =
+        fs::read_dir(&dir).map_err(|e| format!("Failed to read plugins folder: {}", e))?;
+
+    let mut plugins: Vec<PluginInfo> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_dir()
+                && !path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+        })
+        .map(|path| plugin_info(&path, &enabled))
+        .collect();
+    plugins.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(plugins)
+}
+
+// Manifests of enabled plugins, for the Plugins menu
+#[cfg(desktop)]
+pub fn enabled_manifests(app: &AppHandle) -> Vec<PluginManifest> {
+    list(app)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|plugin| plugin.enabled)
+        .filter_map(|plugin| plugin.manifest)
+        .collect()
+}
+
+fn enabled_manifest(app: &AppHandle, id: &str) -> Result<PluginManifest, String> {
+    if !valid_id(id) {
+        return Err("Plugin not found".to_string());
+    }
+    if !read_enabled(app).contains(id) {
+        return Err(format!("Plugin {} is not enabled", id));
+    }
+    read_manifest(&plugins_dir(app)?.join(id))
+}
+
+fn require(manifest: &PluginManifest, permission: &str) -> Result<(), String> {
+    if manifest.permissions.iter().any(|p| p == permission) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Plugin {} doesn't have the {} permission",
+            manifest.id, permission
+        ))
+    }
+}
+
+fn string_param(params: &Value, key: &str) -> Result<String, String> {
+    params
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| format!("Missing string parameter: {}", key))
+}
+
+// Plugins only reach notes of the workspace the calling window shows, and
+// never the inside of an encrypted vault
+fn workspace_path(root: &Path, raw: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(raw);
+    let path = if path.is_absolute() {
+        path
+    } else {
+        root.join(path)
+    };
+    if path
+        .components()
+        .any(|component| matches!(component, Component::ParentDir))
+        || !path.starts_with(root)
+    {
+        return Err("Path is outside the workspace".to_string());
+    }
+    if vault::vault_root(&path).is_some() {
+        return Err("Plugins can't access encrypted vaults".to_string());
+    }
+    Ok(path)
+}
+
+// Writes stay within note files, so a plugin can't drop scripts into the workspace
+fn ensure_note(app: &AppHandle, path: &Path) -> Result<(), String> {
+    let extensions = app.state::<SettingsState>().current().watched_extensions;
+    if markdown::has_note_extension(path, &extensions) {
+        Ok(())
+    } else {
+        Err("Plugins can only write notes".to_string())
+    }
+}
+
+#[tauri::command]
+pub fn list_plugins(app: AppHandle) -> Result<Vec<PluginInfo>, String> {
+    list(&app)
+}
+
+#[tauri::command]
+pub fn get_plugins_folder(app: AppHandle) -> Result<String, String> {
+    Ok(plugins_dir(&app)?.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub fn enable_plugin(
+    id: String,
+    enabled: bool,
+    app: AppHandle,
+    plugins_state: State<PluginsState>,
+) -> Result<PluginInfo, String> {
+    if !valid_id(&id) {
+        return Err("Plugin not found".to_string());
+    }
+    let dir = plugins_dir(&app)?.join(&id);
+    if !dir.is_dir() {
+        return Err("Plugin not found".to_string());
+    }
+    if enabled {
+        read_manifest(&dir)?;
+    }
+
+    let info = {
+        let _guard = plugins_state
+            .lock
+            .lock()
+            .map_err(|e| format!("Failed to lock plugins: {}", e))?;
+        let mut ids = read_enabled(&app);
+        if enabled {
+            ids.insert(id.clone());
+        } else {
+            ids.remove(&id);
+        }
+        storage::write_json(&enabled_path(&app)?, &ids)?;
+        plugin_info(&dir, &ids)
+    };
+
+    #[cfg(desktop)]
+    crate::menu::rebuild(&app);
+    app.emit("plugins-changed", PluginsChangedEvent { id, enabled })
+        .map_err(|e| format!("Failed to emit event: {}", e))?;
+
+    Ok(info)
+}
+
+// Source of an enabled plugin's entry point, for the frontend to run in a worker
+#[tauri::command]
+pub fn load_plugin(
+    id: String,
+    app: AppHandle,
+    safe_mode: State<SafeModeState>,
+) -> Result<String, String> {
+    if safe_mode.is_active() {
+        return Err("Plugins are disabled in safe mode".to_string());
+    }
+    let manifest = enabled_manifest(&app, &id)?;
+    let path = plugins_dir(&app)?.join(&id).join(&manifest.main);
+    let size = fs::metadata(&path)
+        .map_err(|e| format!("Failed to read plugin {}: {}", id, e))?
+        .len();
+    if size > MAX_SOURCE_BYTES {
+        return Err(format!("Plugin {} is too large to load", id));
+    }
+    fs::read_to_string(&path).map_err(|e| format!("Failed to read plugin {}: {}", id, e))
+}
+
+// The capability API: every note operation a plugin makes goes through here,
+// checked against its manifest and the calling window's workspace
+#[tauri::command]
+pub fn plugin_request(
+    plugin_id: String,
+    method: String,
+    params: Value,
+    app: AppHandle,
+    window: Window,
+    watcher_state: State<WatcherState>,
+    index_state: State<IndexState>,
+) -> Result<Value, String> {
+    // A worker started before safe mode was turned on mustn't keep working
+    if app.state::<SafeModeState>().is_active() {
+        return Err("Plugins are disabled in safe mode".to_string());
+    }
+    let manifest = enabled_manifest(&app, &plugin_id)?;
+    let root = watcher_state
+        .active_root(window.label())
+        .ok_or("No workspace is open")?;
+
+    match method.as_str() {
+        "notes.list" => {
+            require(&manifest, "notes:read")?;
+            if vault::is_vault_root(&root) {
+                return Ok(json!([]));
+            }
+            let mut notes = index_state.with_workspace(&root, |index| {
+                index
+                    .notes
+                    .values()
+                    .map(|note| {
+                        json!({
+                            "path": note.path.to_string_lossy(),
+                            "title": note.title,
+                            "tags": note.tags,
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })?;
+            notes.sort_by(|a, b| a["path"].as_str().cmp(&b["path"].as_str()));
+            Ok(Value::Array(notes))
+        }
+        "notes.read" => {
+            require(&manifest, "notes:read")?;
+            let path = workspace_path(&root, &string_param(&params, "path")?)?;
+            let content =
+                fs::read_to_string(&path).map_err(|e| format!("Failed to read note: {}", e))?;
+            Ok(json!({ "path": path.to_string_lossy(), "content": content }))
+        }
+        "notes.write" => {
+            require(&manifest, "notes:write")?;
+            let path = workspace_path(&root, &string_param(&params, "path")?)?;
+            ensure_note(&app, &path)?;
+            crate::save_note(
+                path.to_string_lossy().to_string(),
+                string_param(&params, "content")?,
+                app.clone(),
+                window,
+            )
+            .map_err(|e| e.into_message())?;
+            Ok(json!({ "path": path.to_string_lossy() }))
+        }
+        "notes.create" => {
+            require(&manifest, "notes:write")?;
+            let folder = match params.get("folder").and_then(Value::as_str) {
+                Some(folder) => workspace_path(&root, folder)?,
+                None => root.clone(),
+            };
+            let name = string_param(&params, "name")?;
+            ensure_note(&app, Path::new(&name))?;
+            let path = crate::create_markdown_file(
+                folder.to_string_lossy().to_string(),
+                name,
+                params
+                    .get("content")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                app.clone(),
+            )?;
+            Ok(json!({ "path": path }))
+        }
+        _ => Err(format!("Unknown plugin method: {}", method)),
+    }
+}
@@ -0,0 +1,115 @@
+use crate::keymap::{KeymapState, Shortcut};
+use crate::settings::{self, Settings, SettingsState};
+use crate::storage;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use tauri::{AppHandle, State};
+
+const FORMAT: &str = "marky-profile";
+const VERSION: u32 = 1;
+
+// Settings tied to this machine or person, which a shared profile shouldn't
+// carry: the local API's port, mail delivery, calendar URLs (private feeds
+// keep their access token in the URL), and the telemetry opt-in, which only
+// the user can give. Importing leaves them as they are.
+const LOCAL_SETTINGS: &[&str] = &["api", "email", "calendar_subscriptions", "telemetry"];
+
+// A setup to move to another machine or hand to a team. `frontend` is what
+// the frontend keeps itself (appearance, custom templates) and is passed
+// through untouched.
+#[derive(Debug, Serialize, Deserialize)]
+struct Profile {
+    format: String,
+    version: u32,
+    exported_at: i64,
+    settings: Map<String, Value>,
+    keymap: BTreeMap<String, String>,
+    #[serde(default)]
+    frontend: Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportedProfile {
+    pub settings: Settings,
+    pub keymap: Vec<Shortcut>,
+    pub frontend: Value,
+}
+
+// Writes the global settings (snippets and custom menu included), custom
+// shortcuts, and `frontend` to `path`
+#[tauri::command]
+pub fn export_profile(
+    path: String,
+    frontend: Value,
+    app: AppHandle,
+    settings_state: State<SettingsState>,
+    keymap_state: State<KeymapState>,
+) -> Result<(), String> {
+    let path = Path::new(&path);
+    storage::ensure_accessible(&app, path)?;
+
+    let mut settings = match serde_json::to_value(settings_state.current()) {
+        Ok(Value::Object(settings)) => settings,
+        Ok(_) => Map::new(),
+        Err(e) => return Err(format!("Failed to serialize settings: {}", e)),
+    };
+    settings.retain(|key, _| !LOCAL_SETTINGS.contains(&key.as_str()));
+
+    let profile = Profile {
+        format: FORMAT.to_string(),
+        version: VERSION,
+        exported_at: Utc::now().timestamp_millis(),
+        settings,
+        keymap: keymap_state.overrides()?,
+        frontend,
+    };
+    let raw = serde_json::to_string_pretty(&profile)
+        .map_err(|e| format!("Failed to serialize profile: {}", e))?;
+    fs::write(path, raw).map_err(|e| format!("Failed to write profile: {}", e))
+}
+
+// Applies a profile from `export_profile`, replacing the settings it has and
+// the custom shortcuts. Settings it doesn't know (from a newer Marky) are
+// ignored; the frontend applies `frontend` from the result.
+#[tauri::command]
+pub fn import_profile(
+    path: String,
+    app: AppHandle,
+    settings_state: State<SettingsState>,
+    keymap_state: State<KeymapState>,
+) -> Result<ImportedProfile, String> {
+    let path = Path::new(&path);
+    storage::ensure_accessible(&app, path)?;
+    let raw = fs::read_to_string(path).map_err(|e| format!("Failed to read profile: {}", e))?;
+    let profile: Profile =
+        serde_json::from_str(&raw).map_err(|_| "Not a Marky profile".to_string())?;
+    if profile.format != FORMAT {
+        return Err("Not a Marky profile".to_string());
+    }
+    if profile.version > VERSION {
+        return Err("This profile is from a newer version of Marky".to_string());
+    }
+
+    let settings = settings::update(&app, &settings_state, "profile".to_string(), |raw| {
+        let Some(current) = raw.as_object_mut() else {
+            return Ok(());
+        };
+        for (key, value) in profile.settings {
+            if current.contains_key(&key) && !LOCAL_SETTINGS.contains(&key.as_str()) {
+                current.insert(key, value);
+            }
+        }
+        Ok(())
+    })?;
+    let keymap = keymap_state.replace(&app, profile.keymap)?;
+
+    Ok(ImportedProfile {
+        settings,
+        keymap,
+        frontend: profile.frontend,
+    })
+}
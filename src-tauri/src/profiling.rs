@@ -0,0 +1,226 @@
+use crate::index::{collect_markdown_files, WorkspaceIndex};
+use crate::settings::SettingsState;
+use crate::workspace_config::NoteFilter;
+use notify_debouncer_full::notify::{self, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::State;
+
+const MAX_HOTSPOTS: usize = 15;
+// Folders smaller than this are never worth calling out
+const MIN_HOTSPOT_ENTRIES: usize = 1000;
+
+#[derive(Debug, Serialize)]
+pub struct PhaseTiming {
+    ms: f64,
+    // Notes for scan and index, directories for the watcher, entries for the walk
+    count: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DirectoryHotspot {
+    path: String,
+    files: usize,
+    directories: usize,
+    notes: usize,
+    walk_ms: f64,
+    // Already excluded from scans and the index, though the watcher still sees it
+    ignored: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkspaceProfile {
+    folder: String,
+    total_ms: f64,
+    scan: PhaseTiming,
+    index: PhaseTiming,
+    watcher: PhaseTiming,
+    walk: PhaseTiming,
+    hotspots: Vec<DirectoryHotspot>,
+}
+
+// Totals for a directory and everything beneath it
+struct DirectoryStats {
+    path: PathBuf,
+    files: usize,
+    directories: usize,
+    notes: usize,
+    elapsed: Duration,
+    children: Vec<usize>,
+}
+
+impl DirectoryStats {
+    fn entries(&self) -> usize {
+        self.files + self.directories
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 10_000.0).round() / 10.0
+}
+
+// Ignored folders are walked too: the recursive watcher pays for them either way.
+// Symlinks are not followed.
+fn walk(dir: &Path, filter: &NoteFilter, dirs: &mut Vec<DirectoryStats>) -> usize {
+    let started = Instant::now();
+    let id = dirs.len();
+    dirs.push(DirectoryStats {
+        path: dir.to_path_buf(),
+        files: 0,
+        directories: 0,
+        notes: 0,
+        elapsed: Duration::ZERO,
+        children: Vec::new(),
+    });
+
+    let (mut files, mut directories, mut notes) = (0, 0, 0);
+    let mut children = Vec::new();
+
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+
+            if file_type.is_dir() {
+                let child = walk(&path, filter, dirs);
+                files += dirs[child].files;
+                directories += dirs[child].directories + 1;
+                notes += dirs[child].notes;
+                children.push(child);
+            } else {
+                files += 1;
+                if filter.includes_note(&path) {
+                    notes += 1;
+                }
+            }
+        }
+    }
+
+    let stats = &mut dirs[id];
+    stats.files = files;
+    stats.directories = directories;
+    stats.notes = notes;
+    stats.children = children;
+    stats.elapsed = started.elapsed();
+    id
+}
+
+// A folder is a hotspot when it holds a big share of the tree and no single
+// subfolder accounts for most of it, so `project/node_modules` gets named
+// rather than `project`
+fn hotspots(root: &Path, dirs: &[DirectoryStats], filter: &NoteFilter) -> Vec<DirectoryHotspot> {
+    let Some(root_stats) = dirs.first() else {
+        return Vec::new();
+    };
+    let threshold = MIN_HOTSPOT_ENTRIES.max(root_stats.entries() / 20);
+
+    let mut found: Vec<&DirectoryStats> = dirs
+        .iter()
+        .skip(1)
+        .filter(|stats| stats.entries() >= threshold)
+        .filter(|stats| {
+            !stats
+                .children
+                .iter()
+                .any(|&child| dirs[child].entries() * 5 >= stats.entries() * 4)
+        })
+        .collect();
+    found.sort_by_key(|stats| std::cmp::Reverse(stats.entries()));
+    found.truncate(MAX_HOTSPOTS);
+
+    found
+        .into_iter()
+        .map(|stats| DirectoryHotspot {
+            path: stats
+                .path
+                .strip_prefix(root)
+                .unwrap_or(&stats.path)
+                .to_string_lossy()
+                .to_string(),
+            files: stats.files,
+            directories: stats.directories,
+            notes: stats.notes,
+            walk_ms: millis(stats.elapsed),
+            ignored: filter.is_ignored(&stats.path),
+        })
+        .collect()
+}
+
+// Times each startup phase against a fresh copy of the workspace, leaving the
+// live index and watchers alone. The full walk runs last so its cache warming
+// doesn't flatter the scan.
+#[tauri::command]
+pub async fn profile_workspace(
+    folder_path: String,
+    settings_state: State<'_, SettingsState>,
+) -> Result<WorkspaceProfile, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+
+    let settings = settings_state.current();
+    let filter = NoteFilter::for_workspace(&root, &settings);
+    let total_started = Instant::now();
+
+    let started = Instant::now();
+    let mut files = Vec::new();
+    collect_markdown_files(&root, &filter, &mut files);
+    let scan = PhaseTiming {
+        ms: millis(started.elapsed()),
+        count: files.len(),
+    };
+
+    let started = Instant::now();
+    let index = WorkspaceIndex::build(&root, &settings);
+    let index = PhaseTiming {
+        ms: millis(started.elapsed()),
+        count: index.notes.len(),
+    };
+
+    let started = Instant::now();
+    let mut watcher = notify::recommended_watcher(|_: notify::Result<notify::Event>| {})
+        .map_err(|e| format!("Failed to create watcher: {}", e))?;
+    watcher
+        .watch(&root, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch folder: {}", e))?;
+    let watcher_ms = millis(started.elapsed());
+    drop(watcher);
+
+    let started = Instant::now();
+    let mut dirs = Vec::new();
+    walk(&root, &filter, &mut dirs);
+    let walk_ms = millis(started.elapsed());
+
+    let root_entries = dirs.first().map(DirectoryStats::entries).unwrap_or(0);
+    let profile = WorkspaceProfile {
+        folder: folder_path,
+        total_ms: millis(total_started.elapsed()),
+        scan,
+        index,
+        watcher: PhaseTiming {
+            ms: watcher_ms,
+            count: dirs.len(),
+        },
+        walk: PhaseTiming {
+            ms: walk_ms,
+            count: root_entries,
+        },
+        hotspots: hotspots(&root, &dirs, &filter),
+    };
+
+    tracing::info!(
+        "Profiled workspace: scan {}ms, index {}ms, watcher {}ms, {} entries, {} hotspots",
+        profile.scan.ms,
+        profile.index.ms,
+        profile.watcher.ms,
+        root_entries,
+        profile.hotspots.len()
+    );
+
+    Ok(profile)
+}
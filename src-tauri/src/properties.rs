@@ -0,0 +1,394 @@
+use crate::activity::ActivityState;
+use crate::encryption;
+use crate::markdown;
+use crate::network;
+use crate::query;
+use crate::storage;
+use crate::vault;
+use crate::watcher::WatcherState;
+use crate::writing::WritingState;
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, Window};
+
+// A frontmatter value as the property editor shows it. Values it can't edit
+// in place (nested maps, lists of lists) come back as `yaml` and are read-only.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum PropertyValue {
+    // `key:` with nothing after it
+    Empty,
+    Text(String),
+    Number(f64),
+    Boolean(bool),
+    // `2024-05-01`, or a date and time
+    Date(String),
+    List(Vec<String>),
+    // `"[[Note]]"`, without the brackets
+    Link(String),
+    Yaml(String),
+}
+
+#[derive(Debug, Serialize)]
+pub struct NoteProperty {
+    key: String,
+    #[serde(flatten)]
+    value: PropertyValue,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PropertyEdit {
+    content: String,
+    properties: Vec<NoteProperty>,
+}
+
+fn link_target(text: &str) -> Option<&str> {
+    let inner = text.trim().strip_prefix("[[")?.strip_suffix("]]")?.trim();
+    (!inner.is_empty() && !inner.contains("[[") && !inner.contains("]]")).then_some(inner)
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+fn raw_yaml(value: &Value) -> String {
+    serde_yaml::to_string(value)
+        .map(|yaml| yaml.trim_end().to_string())
+        .unwrap_or_default()
+}
+
+fn typed(value: &Value) -> PropertyValue {
+    match value {
+        Value::Null => PropertyValue::Empty,
+        Value::Bool(flag) => PropertyValue::Boolean(*flag),
+        Value::Number(number) => number.as_f64().map_or_else(
+            || PropertyValue::Yaml(number.to_string()),
+            PropertyValue::Number,
+        ),
+        Value::String(text) => match link_target(text) {
+            Some(target) => PropertyValue::Link(target.to_string()),
+            None if query::parse_date(text).is_some() => {
+                PropertyValue::Date(text.trim().to_string())
+            }
+            None => PropertyValue::Text(text.clone()),
+        },
+        Value::Sequence(items) => items
+            .iter()
+            .map(scalar_text)
+            .collect::<Option<Vec<_>>>()
+            .map_or_else(|| PropertyValue::Yaml(raw_yaml(value)), PropertyValue::List),
+        _ => PropertyValue::Yaml(raw_yaml(value)),
+    }
+}
+
+// Refuses frontmatter that doesn't parse, so an edit never builds on YAML
+// the user would have to untangle by hand
+fn frontmatter_mapping(content: &str) -> Result<Mapping, String> {
+    match markdown::parse_frontmatter(content) {
+        Some(mapping) => Ok(mapping),
+        // An empty block parses as null rather than a mapping
+        None if markdown::frontmatter(content).is_some_and(|block| !block.trim().is_empty()) => {
+            Err("Frontmatter isn't valid YAML; fix it in the editor first".to_string())
+        }
+        None => Ok(Mapping::new()),
+    }
+}
+
+fn read_properties(content: &str) -> Result<Vec<NoteProperty>, String> {
+    Ok(frontmatter_mapping(content)?
+        .iter()
+        .filter_map(|(key, value)| {
+            Some(NoteProperty {
+                key: scalar_text(key)?,
+                value: typed(value),
+            })
+        })
+        .collect())
+}
+
+fn property_key(key: &str) -> Result<String, String> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err("Property name is required".to_string());
+    }
+    let valid = key
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ' '))
+        && !key.starts_with('-');
+    if !valid {
+        return Err(format!(
+            "\"{}\" can't be a property name; use letters, numbers, spaces, '-', '_' and '.'",
+            key
+        ));
+    }
+    Ok(key.to_string())
+}
+
+fn single_line<'a>(text: &'a str, what: &str) -> Result<&'a str, String> {
+    let text = text.trim();
+    if text.contains(['\n', '\r']) {
+        return Err(format!("{} must fit on one line", what));
+    }
+    Ok(text)
+}
+
+// Quoted whenever YAML would otherwise read the text as something else
+fn yaml_scalar(text: &str) -> String {
+    serde_yaml::to_string(&Value::String(text.to_string()))
+        .map(|yaml| yaml.trim_end().to_string())
+        .unwrap_or_else(|_| format!("'{}'", text.replace('\'', "''")))
+}
+
+fn yaml_number(number: f64) -> String {
+    if number.fract() == 0.0 && number.abs() < 1e15 {
+        format!("{}", number as i64)
+    } else {
+        number.to_string()
+    }
+}
+
+// The frontmatter lines for `key: value`, after checking the value is what
+// its type says it is
+fn render_entry(key: &str, value: &PropertyValue, line_ending: &str) -> Result<String, String> {
+    let inline = match value {
+        PropertyValue::Empty => return Ok(format!("{}:{}", key, line_ending)),
+        PropertyValue::Text(text) => yaml_scalar(single_line(text, "Text")?),
+        PropertyValue::Number(number) => {
+            if !number.is_finite() {
+                return Err("Numbers must be finite".to_string());
+            }
+            yaml_number(*number)
+        }
+        PropertyValue::Boolean(flag) => flag.to_string(),
+        PropertyValue::Date(date) => {
+            let date = single_line(date, "A date")?;
+            if query::parse_date(date).is_none() {
+                return Err(format!(
+                    "\"{}\" isn't a date; use YYYY-MM-DD or YYYY-MM-DDTHH:MM",
+                    date
+                ));
+            }
+            yaml_scalar(date)
+        }
+        PropertyValue::Link(target) => {
+            let target = single_line(target, "A link")?;
+            let target = link_target(target).unwrap_or(target);
+            if target.is_empty() || target.contains("[[") || target.contains("]]") {
+                return Err("A link needs a note name, like [[Note]]".to_string());
+            }
+            yaml_scalar(&format!("[[{}]]", target))
+        }
+        PropertyValue::List(items) => {
+            let mut lines = String::new();
+            for item in items {
+                let item = single_line(item, "List items")?;
+                if !item.is_empty() {
+                    lines.push_str(&format!("  - {}{}", yaml_scalar(item), line_ending));
+                }
+            }
+            if lines.is_empty() {
+                "[]".to_string()
+            } else {
+                return Ok(format!("{}:{}{}", key, line_ending, lines));
+            }
+        }
+        PropertyValue::Yaml(_) => {
+            return Err("This property can only be edited in the note's frontmatter".to_string())
+        }
+    };
+    Ok(format!("{}: {}{}", key, inline, line_ending))
+}
+
+// Byte range of `key`'s entry within the note: its line plus the indented or
+// `- ` lines that continue it
+fn find_entry(content: &str, key: &str) -> Option<Range<usize>> {
+    let frontmatter = markdown::frontmatter(content)?;
+    let base = frontmatter.as_ptr() as usize - content.as_ptr() as usize;
+
+    let mut found: Option<Range<usize>> = None;
+    let mut offset = base;
+    for line in frontmatter.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let continues = line.starts_with([' ', '\t']) || line.starts_with('-');
+        if let Some(range) = found.as_mut() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if !continues {
+                break;
+            }
+            range.end = offset;
+            continue;
+        }
+        if continues || line.starts_with('#') {
+            continue;
+        }
+        let Some((name, _)) = line.split_once(':') else {
+            continue;
+        };
+        let name = name.trim();
+        let name = ['"', '\'']
+            .iter()
+            .find_map(|quote| name.strip_prefix(*quote)?.strip_suffix(*quote))
+            .unwrap_or(name);
+        if name == key {
+            found = Some(start..offset);
+        }
+    }
+    found
+}
+
+fn line_ending(content: &str) -> &'static str {
+    if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+// Replaces `key`'s entry with `entry`, adds it at the end of the frontmatter
+// (creating the block if needed), or removes it when `entry` is None
+fn apply_entry(content: &str, key: &str, entry: Option<&str>) -> String {
+    let line_ending = line_ending(content);
+    let replacement = entry.unwrap_or_default();
+    if let Some(range) = find_entry(content, key) {
+        let mut next = content.to_string();
+        next.replace_range(range, replacement);
+        return next;
+    }
+    if entry.is_none() {
+        return content.to_string();
+    }
+    if let Some(frontmatter) = markdown::frontmatter(content) {
+        let end = frontmatter.as_ptr() as usize - content.as_ptr() as usize + frontmatter.len();
+        return format!("{}{}{}", &content[..end], replacement, &content[end..]);
+    }
+    let body = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let bom = &content[..content.len() - body.len()];
+    format!(
+        "{}---{}{}---{}{}",
+        bom, line_ending, replacement, line_ending, body
+    )
+}
+
+// A note's text for an edit made outside the editor; encrypted notes can't be
+pub fn read_note(app: &AppHandle, path: &Path) -> Result<String, String> {
+    storage::ensure_accessible(app, path)?;
+    let content = if vault::vault_root(path).is_some() {
+        vault::read_file(app, path)?
+    } else {
+        fs::read_to_string(path).map_err(|e| format!("Failed to read note: {}", e))?
+    };
+    if encryption::is_encrypted(&content) {
+        return Err("Note is encrypted; decrypt it first".to_string());
+    }
+    Ok(content)
+}
+
+// `content` with `key` set to `value` (or removed, for None). Only that
+// entry's lines change, and an edit that would leave the frontmatter
+// unreadable is refused.
+pub fn with_property(
+    content: &str,
+    key: &str,
+    value: Option<&PropertyValue>,
+) -> Result<String, String> {
+    frontmatter_mapping(content)?;
+    let line_ending = line_ending(content);
+    let entry = value
+        .map(|value| render_entry(key, value, line_ending))
+        .transpose()?;
+    let next = apply_entry(content, key, entry.as_deref());
+
+    let present = frontmatter_mapping(&next).ok().map(|mapping| {
+        mapping
+            .keys()
+            .any(|name| scalar_text(name).as_deref() == Some(key))
+    });
+    if present != Some(value.is_some()) {
+        return Err(format!(
+            "Couldn't update {} without breaking the frontmatter",
+            key
+        ));
+    }
+    Ok(next)
+}
+
+// Saves through the regular note write, so locks, vaults and other windows
+// showing the note are handled as for a save from the editor
+pub fn save_content(
+    app: &AppHandle,
+    origin: &str,
+    path: &str,
+    content: String,
+) -> Result<(), String> {
+    crate::write_note(
+        app,
+        origin,
+        path,
+        content,
+        &app.state::<WatcherState>(),
+        &app.state::<WritingState>(),
+        &app.state::<ActivityState>(),
+    )
+    .map_err(|e| e.into_message())
+}
+
+fn edit_property(
+    app: AppHandle,
+    window: Window,
+    path: String,
+    key: String,
+    value: Option<PropertyValue>,
+) -> Result<PropertyEdit, String> {
+    let key = property_key(&key)?;
+    let origin = window.label().to_string();
+    let target = PathBuf::from(&path);
+    network::guard(&app, &target, move |app| {
+        let content = read_note(app, &PathBuf::from(&path))?;
+        let next = with_property(&content, &key, value.as_ref())?;
+        let properties = read_properties(&next)?;
+        if next != content {
+            save_content(app, &origin, &path, next.clone())?;
+        }
+        Ok(PropertyEdit {
+            content: next,
+            properties,
+        })
+    })?
+}
+
+#[tauri::command]
+pub fn get_note_properties(path: String, app: AppHandle) -> Result<Vec<NoteProperty>, String> {
+    read_properties(&read_note(&app, &PathBuf::from(path))?)
+}
+
+#[tauri::command]
+pub fn set_note_property(
+    path: String,
+    key: String,
+    value: PropertyValue,
+    app: AppHandle,
+    window: Window,
+) -> Result<PropertyEdit, String> {
+    edit_property(app, window, path, key, Some(value))
+}
+
+#[tauri::command]
+pub fn remove_note_property(
+    path: String,
+    key: String,
+    app: AppHandle,
+    window: Window,
+) -> Result<PropertyEdit, String> {
+    edit_property(app, window, path, key, None)
+}
@@ -0,0 +1,798 @@
+use crate::index::{IndexState, NoteRecord, WorkspaceIndex};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::Serialize;
+use serde_yaml::Value as Yaml;
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+// Queries read like
+//   TABLE status, due FROM "Projects" WHERE status = "active" AND due < today SORT due DESC LIMIT 20
+// where every clause is optional and WHERE may be left out before the condition.
+// Bare names are frontmatter keys, `tags` is every tag on the note, and
+// `file.name`, `file.path`, `file.folder`, `file.created`, `file.modified`,
+// `file.size` and `file.words` come from the file itself.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Number(f64),
+    Date(NaiveDateTime),
+    Tag(String),
+    Op(CmpOp),
+    LParen,
+    RParen,
+    Comma,
+}
+
+#[derive(Debug, Clone)]
+enum QValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    Text(String),
+    Date(NaiveDateTime),
+    List(Vec<QValue>),
+}
+
+#[derive(Debug)]
+enum Operand {
+    Field(String),
+    Literal(QValue),
+    // `#project` on its own: the note has the tag, or one nested under it
+    Tag(String),
+}
+
+#[derive(Debug)]
+enum Expr {
+    Or(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare(Operand, CmpOp, Operand),
+    Truthy(Operand),
+}
+
+#[derive(Debug)]
+enum Source {
+    Folder(String),
+    Tag(String),
+}
+
+#[derive(Debug, Default)]
+struct Query {
+    columns: Vec<String>,
+    source: Option<Source>,
+    filter: Option<Expr>,
+    // Field and whether it sorts descending
+    sort: Vec<(String, bool)>,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QueryRow {
+    pub path: String,
+    title: String,
+    // One per column, in order
+    values: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QueryResult {
+    columns: Vec<String>,
+    pub rows: Vec<QueryRow>,
+    // Matches before LIMIT applied
+    pub total: usize,
+}
+
+// Dates as frontmatter usually holds them: `2024-05-01`, with or without a time
+pub fn parse_date(text: &str) -> Option<NaiveDateTime> {
+    let text = text.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        return Some(date.and_time(NaiveTime::MIN));
+    }
+    if let Ok(date) = DateTime::parse_from_rfc3339(text) {
+        return Some(date.with_timezone(&Local).naive_local());
+    }
+    ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.')
+}
+
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let take_while = |chars: &mut std::iter::Peekable<std::str::CharIndices>,
+                          keep: fn(char) -> bool| {
+            let mut end = start;
+            while let Some(&(idx, next)) = chars.peek() {
+                if !keep(next) {
+                    break;
+                }
+                end = idx + next.len_utf8();
+                chars.next();
+            }
+            &source[start..end]
+        };
+
+        let token = match c {
+            '(' | ')' | ',' => {
+                chars.next();
+                match c {
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    _ => Token::Comma,
+                }
+            }
+            '=' | '!' | '<' | '>' => {
+                chars.next();
+                let followed_by_eq = chars.peek().is_some_and(|&(_, next)| next == '=');
+                if followed_by_eq {
+                    chars.next();
+                }
+                Token::Op(match (c, followed_by_eq) {
+                    ('=', _) => CmpOp::Eq,
+                    ('!', true) => CmpOp::Ne,
+                    ('<', false) => CmpOp::Lt,
+                    ('<', true) => CmpOp::Le,
+                    ('>', false) => CmpOp::Gt,
+                    ('>', true) => CmpOp::Ge,
+                    _ => return Err(format!("Unexpected `!` at position {}", start + 1)),
+                })
+            }
+            '"' | '\'' => {
+                chars.next();
+                let mut text = String::new();
+                let mut closed = false;
+                while let Some((_, next)) = chars.next() {
+                    match next {
+                        '\\' => {
+                            if let Some((_, escaped)) = chars.next() {
+                                text.push(escaped);
+                            }
+                        }
+                        quote if quote == c => {
+                            closed = true;
+                            break;
+                        }
+                        other => text.push(other),
+                    }
+                }
+                if !closed {
+                    return Err(format!("Unclosed string at position {}", start + 1));
+                }
+                Token::Str(text)
+            }
+            '#' => {
+                chars.next();
+                let tag = take_while(&mut chars, |c| {
+                    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '/')
+                });
+                let tag = tag.trim_start_matches('#').trim_end_matches('/');
+                if tag.is_empty() {
+                    return Err(format!(
+                        "Expected a tag after `#` at position {}",
+                        start + 1
+                    ));
+                }
+                Token::Tag(tag.to_lowercase())
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let literal = take_while(&mut chars, |c| {
+                    c.is_ascii_digit() || matches!(c, '-' | ':' | '.' | 'T')
+                });
+                if let Some(date) = parse_date(literal) {
+                    Token::Date(date)
+                } else if let Ok(number) = literal.parse::<f64>() {
+                    Token::Number(number)
+                } else {
+                    return Err(format!(
+                        "`{}` isn't a number or date at position {}",
+                        literal,
+                        start + 1
+                    ));
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                Token::Ident(take_while(&mut chars, is_ident_char).to_string())
+            }
+            other => {
+                return Err(format!("Unexpected `{}` at position {}", other, start + 1));
+            }
+        };
+        tokens.push((start, token));
+    }
+    Ok(tokens)
+}
+
+const KEYWORDS: [&str; 11] = [
+    "and", "or", "not", "contains", "table", "from", "where", "sort", "limit", "asc", "desc",
+];
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, token)| token)
+    }
+
+    fn describe_position(&self) -> String {
+        match self.tokens.get(self.pos) {
+            Some((offset, _)) => format!("at position {}", offset + 1),
+            None => "at the end".to_string(),
+        }
+    }
+
+    fn error<T>(&self, message: &str) -> Result<T, String> {
+        Err(format!("{} {}", message, self.describe_position()))
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let found = self.peek() == Some(token);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn field(&mut self) -> Result<String, String> {
+        match self.peek() {
+            Some(Token::Ident(name))
+                if !KEYWORDS.iter().any(|kw| name.eq_ignore_ascii_case(kw)) =>
+            {
+                let name = name.clone();
+                self.pos += 1;
+                Ok(name)
+            }
+            _ => self.error("Expected a field name"),
+        }
+    }
+
+    fn field_list(&mut self) -> Result<Vec<String>, String> {
+        let mut fields = vec![self.field()?];
+        while self.eat(&Token::Comma) {
+            fields.push(self.field()?);
+        }
+        Ok(fields)
+    }
+
+    fn query(&mut self) -> Result<Query, String> {
+        let mut query = Query::default();
+        if self.eat_keyword("table") {
+            query.columns = self.field_list()?;
+        }
+        if self.eat_keyword("from") {
+            query.source = Some(match self.peek().cloned() {
+                Some(Token::Str(folder)) => Source::Folder(folder),
+                Some(Token::Tag(tag)) => Source::Tag(tag),
+                _ => return self.error("Expected a \"folder\" or #tag after FROM"),
+            });
+            self.pos += 1;
+        }
+        let explicit_where = self.eat_keyword("where");
+        if explicit_where
+            || !(self.peek().is_none() || self.is_keyword("sort") || self.is_keyword("limit"))
+        {
+            query.filter = Some(self.or()?);
+        }
+        if self.eat_keyword("sort") {
+            loop {
+                let field = self.field()?;
+                let descending = if self.eat_keyword("desc") {
+                    true
+                } else {
+                    self.eat_keyword("asc");
+                    false
+                };
+                query.sort.push((field, descending));
+                if !self.eat(&Token::Comma) {
+                    break;
+                }
+            }
+        }
+        if self.eat_keyword("limit") {
+            match self.peek() {
+                Some(Token::Number(limit)) if *limit >= 0.0 && limit.fract() == 0.0 => {
+                    query.limit = Some(*limit as usize);
+                    self.pos += 1;
+                }
+                _ => return self.error("Expected a whole number after LIMIT"),
+            }
+        }
+        if self.peek().is_some() {
+            return self.error("Unexpected input");
+        }
+        Ok(query)
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.eat_keyword("or") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.not()?;
+        while self.eat_keyword("and") {
+            expr = Expr::And(Box::new(expr), Box::new(self.not()?));
+        }
+        Ok(expr)
+    }
+
+    fn not(&mut self) -> Result<Expr, String> {
+        if self.eat_keyword("not") {
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+        if self.eat(&Token::LParen) {
+            let expr = self.or()?;
+            if !self.eat(&Token::RParen) {
+                return self.error("Expected `)`");
+            }
+            return Ok(expr);
+        }
+        let left = self.operand()?;
+        let op = match self.peek() {
+            Some(Token::Op(op)) => Some(*op),
+            _ if self.is_keyword("contains") => Some(CmpOp::Contains),
+            _ => None,
+        };
+        let Some(op) = op else {
+            return Ok(Expr::Truthy(left));
+        };
+        self.pos += 1;
+        Ok(Expr::Compare(left, op, self.operand()?))
+    }
+
+    fn operand(&mut self) -> Result<Operand, String> {
+        let operand = match self.peek().cloned() {
+            Some(Token::Str(text)) => Operand::Literal(QValue::Text(text)),
+            Some(Token::Number(number)) => Operand::Literal(QValue::Number(number)),
+            Some(Token::Date(date)) => Operand::Literal(QValue::Date(date)),
+            Some(Token::Tag(tag)) => Operand::Tag(tag),
+            Some(Token::Ident(word)) => {
+                let today = Local::now().date_naive().and_time(NaiveTime::MIN);
+                match word.to_lowercase().as_str() {
+                    "true" => Operand::Literal(QValue::Bool(true)),
+                    "false" => Operand::Literal(QValue::Bool(false)),
+                    "null" => Operand::Literal(QValue::Null),
+                    "today" => Operand::Literal(QValue::Date(today)),
+                    "yesterday" => Operand::Literal(QValue::Date(today - Duration::days(1))),
+                    "tomorrow" => Operand::Literal(QValue::Date(today + Duration::days(1))),
+                    "now" => Operand::Literal(QValue::Date(Local::now().naive_local())),
+                    _ => return self.field().map(Operand::Field),
+                }
+            }
+            _ => return self.error("Expected a field or value"),
+        };
+        self.pos += 1;
+        Ok(operand)
+    }
+}
+
+fn parse(source: &str) -> Result<Query, String> {
+    let tokens = tokenize(source)?;
+    if tokens.is_empty() {
+        return Err("Query is empty".to_string());
+    }
+    Parser { tokens, pos: 0 }.query()
+}
+
+fn from_yaml(value: &Yaml) -> QValue {
+    match value {
+        Yaml::Bool(flag) => QValue::Bool(*flag),
+        Yaml::Number(number) => number.as_f64().map_or(QValue::Null, QValue::Number),
+        Yaml::String(text) => QValue::Text(text.clone()),
+        Yaml::Sequence(items) => QValue::List(items.iter().map(from_yaml).collect()),
+        _ => QValue::Null,
+    }
+}
+
+fn from_time(time: Option<std::time::SystemTime>) -> QValue {
+    time.map_or(QValue::Null, |time| {
+        QValue::Date(DateTime::<Local>::from(time).naive_local())
+    })
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn has_tag(note: &NoteRecord, tag: &str) -> bool {
+    let nested = format!("{}/", tag);
+    note.tags
+        .iter()
+        .any(|candidate| candidate == tag || candidate.starts_with(&nested))
+}
+
+fn resolve(root: &Path, note: &NoteRecord, field: &str) -> QValue {
+    match field {
+        "tags" => QValue::List(note.tags.iter().cloned().map(QValue::Text).collect()),
+        "file.name" => QValue::Text(note.title.clone()),
+        "file.path" => QValue::Text(relative_path(root, &note.path)),
+        "file.folder" => QValue::Text(
+            note.path
+                .parent()
+                .map(|parent| relative_path(root, parent))
+                .unwrap_or_default(),
+        ),
+        "file.created" => from_time(note.created),
+        "file.modified" => from_time(note.modified),
+        "file.size" => QValue::Number(note.size as f64),
+        "file.words" => QValue::Number(note.words as f64),
+        _ => note
+            .frontmatter
+            .get(field)
+            .or_else(|| {
+                note.frontmatter
+                    .iter()
+                    .find(|(key, _)| {
+                        key.as_str()
+                            .is_some_and(|key| key.eq_ignore_ascii_case(field))
+                    })
+                    .map(|(_, value)| value)
+            })
+            .map_or(QValue::Null, from_yaml),
+    }
+}
+
+fn as_number(value: &QValue) -> Option<f64> {
+    match value {
+        QValue::Number(number) => Some(*number),
+        QValue::Text(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn as_date(value: &QValue) -> Option<NaiveDateTime> {
+    match value {
+        QValue::Date(date) => Some(*date),
+        QValue::Text(text) => parse_date(text),
+        _ => None,
+    }
+}
+
+// Text against a date or number is read as one; nulls and lists don't order
+fn compare(left: &QValue, right: &QValue) -> Option<Ordering> {
+    match (left, right) {
+        (QValue::Null, _) | (_, QValue::Null) | (QValue::List(_), _) | (_, QValue::List(_)) => None,
+        (QValue::Date(_), _) | (_, QValue::Date(_)) => as_date(left)?.partial_cmp(&as_date(right)?),
+        (QValue::Number(_), _) | (_, QValue::Number(_)) => {
+            as_number(left)?.partial_cmp(&as_number(right)?)
+        }
+        (QValue::Bool(a), QValue::Bool(b)) => Some(a.cmp(b)),
+        (QValue::Text(a), QValue::Text(b)) => Some(a.to_lowercase().cmp(&b.to_lowercase())),
+        _ => None,
+    }
+}
+
+fn equals(left: &QValue, right: &QValue) -> bool {
+    match (left, right) {
+        (QValue::Null, QValue::Null) => true,
+        (QValue::List(items), QValue::Null) | (QValue::Null, QValue::List(items)) => {
+            items.is_empty()
+        }
+        // `tags = "work"` asks whether the list holds it
+        (QValue::List(items), other) | (other, QValue::List(items)) => {
+            items.iter().any(|item| equals(item, other))
+        }
+        _ => compare(left, right) == Some(Ordering::Equal),
+    }
+}
+
+fn truthy(value: &QValue) -> bool {
+    match value {
+        QValue::Null => false,
+        QValue::Bool(flag) => *flag,
+        QValue::Number(number) => *number != 0.0,
+        QValue::Text(text) => !text.is_empty(),
+        QValue::Date(_) => true,
+        QValue::List(items) => !items.is_empty(),
+    }
+}
+
+fn operand_value(root: &Path, note: &NoteRecord, operand: &Operand) -> QValue {
+    match operand {
+        Operand::Field(field) => resolve(root, note, field),
+        Operand::Literal(value) => value.clone(),
+        Operand::Tag(tag) => QValue::Bool(has_tag(note, tag)),
+    }
+}
+
+fn matches(root: &Path, note: &NoteRecord, expr: &Expr) -> bool {
+    match expr {
+        Expr::Or(left, right) => matches(root, note, left) || matches(root, note, right),
+        Expr::And(left, right) => matches(root, note, left) && matches(root, note, right),
+        Expr::Not(inner) => !matches(root, note, inner),
+        Expr::Truthy(operand) => truthy(&operand_value(root, note, operand)),
+        Expr::Compare(left, op, right) => {
+            let left = operand_value(root, note, left);
+            let right = operand_value(root, note, right);
+            match op {
+                CmpOp::Eq => equals(&left, &right),
+                CmpOp::Ne => !equals(&left, &right),
+                CmpOp::Contains => match (&left, &right) {
+                    (QValue::List(items), _) => items.iter().any(|item| equals(item, &right)),
+                    (QValue::Text(text), QValue::Text(needle)) => {
+                        text.to_lowercase().contains(&needle.to_lowercase())
+                    }
+                    _ => false,
+                },
+                CmpOp::Lt => compare(&left, &right) == Some(Ordering::Less),
+                CmpOp::Le => matches!(
+                    compare(&left, &right),
+                    Some(Ordering::Less | Ordering::Equal)
+                ),
+                CmpOp::Gt => compare(&left, &right) == Some(Ordering::Greater),
+                CmpOp::Ge => matches!(
+                    compare(&left, &right),
+                    Some(Ordering::Greater | Ordering::Equal)
+                ),
+            }
+        }
+    }
+}
+
+fn in_source(root: &Path, note: &NoteRecord, source: &Source) -> bool {
+    match source {
+        Source::Folder(folder) => {
+            let folder = folder.trim_matches('/').replace('\\', "/");
+            folder.is_empty()
+                || relative_path(root, &note.path).starts_with(&format!("{}/", folder))
+        }
+        Source::Tag(tag) => has_tag(note, tag),
+    }
+}
+
+fn to_json(value: &QValue) -> serde_json::Value {
+    match value {
+        QValue::Null => serde_json::Value::Null,
+        QValue::Bool(flag) => serde_json::Value::Bool(*flag),
+        // `prio: 2` comes back as 2, not 2.0
+        QValue::Number(number) if number.fract() == 0.0 && number.abs() < i64::MAX as f64 => {
+            serde_json::Value::from(*number as i64)
+        }
+        QValue::Number(number) => serde_json::Number::from_f64(*number)
+            .map_or(serde_json::Value::Null, serde_json::Value::Number),
+        QValue::Text(text) => serde_json::Value::String(text.clone()),
+        QValue::Date(date) if date.time() == NaiveTime::MIN => {
+            serde_json::Value::String(date.format("%Y-%m-%d").to_string())
+        }
+        QValue::Date(date) => serde_json::Value::String(date.format("%Y-%m-%d %H:%M").to_string()),
+        QValue::List(items) => serde_json::Value::Array(items.iter().map(to_json).collect()),
+    }
+}
+
+fn collect_fields<'a>(expr: &'a Expr, fields: &mut Vec<&'a str>) {
+    let mut push = |operand: &'a Operand| {
+        if let Operand::Field(field) = operand {
+            fields.push(field);
+        }
+    };
+    match expr {
+        Expr::Or(left, right) | Expr::And(left, right) => {
+            collect_fields(left, fields);
+            collect_fields(right, fields);
+        }
+        Expr::Not(inner) => collect_fields(inner, fields),
+        Expr::Truthy(operand) => push(operand),
+        Expr::Compare(left, _, right) => {
+            push(left);
+            push(right);
+        }
+    }
+}
+
+// Without TABLE, the columns are whatever the condition and sort mention
+fn columns(query: &Query) -> Vec<String> {
+    if !query.columns.is_empty() {
+        return query.columns.clone();
+    }
+    let mut fields = Vec::new();
+    if let Some(filter) = &query.filter {
+        collect_fields(filter, &mut fields);
+    }
+    fields.extend(query.sort.iter().map(|(field, _)| field.as_str()));
+
+    let mut columns: Vec<String> = Vec::new();
+    for field in fields {
+        if field != "file.name" && !columns.iter().any(|column| column == field) {
+            columns.push(field.to_string());
+        }
+    }
+    columns
+}
+
+// Text that reads as a date or a number sorts with those, as `compare` reads it
+fn sort_value(value: &QValue) -> QValue {
+    match value {
+        QValue::Text(text) => parse_date(text)
+            .map(QValue::Date)
+            .or_else(|| text.trim().parse().ok().map(QValue::Number))
+            .unwrap_or_else(|| value.clone()),
+        QValue::List(items) => QValue::List(items.iter().map(sort_value).collect()),
+        _ => value.clone(),
+    }
+}
+
+fn kind_rank(value: &QValue) -> u8 {
+    match value {
+        QValue::Null => 0,
+        QValue::Bool(_) => 1,
+        QValue::Number(_) => 2,
+        QValue::Date(_) => 3,
+        QValue::Text(_) => 4,
+        QValue::List(_) => 5,
+    }
+}
+
+// A total order for sorting, which `compare` is not: values of different
+// kinds rank null < bool < number < date < text < list, then compare within
+// their kind
+fn sort_compare(left: &QValue, right: &QValue) -> Ordering {
+    match (left, right) {
+        (QValue::Bool(a), QValue::Bool(b)) => a.cmp(b),
+        (QValue::Number(a), QValue::Number(b)) => a.total_cmp(b),
+        (QValue::Date(a), QValue::Date(b)) => a.cmp(b),
+        (QValue::Text(a), QValue::Text(b)) => a.to_lowercase().cmp(&b.to_lowercase()),
+        (QValue::List(a), QValue::List(b)) => a
+            .iter()
+            .zip(b)
+            .map(|(a, b)| sort_compare(a, b))
+            .find(|order| *order != Ordering::Equal)
+            .unwrap_or_else(|| a.len().cmp(&b.len())),
+        _ => kind_rank(left).cmp(&kind_rank(right)),
+    }
+}
+
+// Notes without a value sort last either way; ties fall back to the title
+fn sort_order(root: &Path, a: &NoteRecord, b: &NoteRecord, sort: &[(String, bool)]) -> Ordering {
+    for (field, descending) in sort {
+        let left = resolve(root, a, field);
+        let right = resolve(root, b, field);
+        let order = match (&left, &right) {
+            (QValue::Null, QValue::Null) => Ordering::Equal,
+            (QValue::Null, _) => return Ordering::Greater,
+            (_, QValue::Null) => return Ordering::Less,
+            _ => sort_compare(&sort_value(&left), &sort_value(&right)),
+        };
+        let order = if *descending { order.reverse() } else { order };
+        if order != Ordering::Equal {
+            return order;
+        }
+    }
+    a.title.to_lowercase().cmp(&b.title.to_lowercase())
+}
+
+// Checks that `source` parses, for queries saved to run later
+pub fn validate(source: &str) -> Result<(), String> {
+    parse(source).map(|_| ())
+}
+
+pub fn evaluate(root: &Path, index: &WorkspaceIndex, source: &str) -> Result<QueryResult, String> {
+    let query = parse(source)?;
+    let columns = columns(&query);
+    let mut notes: Vec<&NoteRecord> = index
+        .notes
+        .values()
+        .filter(|note| {
+            query
+                .source
+                .as_ref()
+                .is_none_or(|source| in_source(root, note, source))
+        })
+        .filter(|note| {
+            query
+                .filter
+                .as_ref()
+                .is_none_or(|filter| matches(root, note, filter))
+        })
+        .collect();
+    notes.sort_by(|a, b| sort_order(root, a, b, &query.sort));
+
+    let total = notes.len();
+    let rows = notes
+        .into_iter()
+        .take(query.limit.unwrap_or(usize::MAX))
+        .map(|note| QueryRow {
+            path: note.path.to_string_lossy().to_string(),
+            title: note.title.clone(),
+            values: columns
+                .iter()
+                .map(|column| to_json(&resolve(root, note, column)))
+                .collect(),
+        })
+        .collect();
+    Ok(QueryResult {
+        columns,
+        rows,
+        total,
+    })
+}
+
+// Runs a query against the workspace's metadata index. Notes are read as last
+// saved, so unsaved edits show up once they're written.
+#[tauri::command]
+pub async fn query_notes(
+    folder_path: String,
+    query: String,
+    index_state: State<'_, IndexState>,
+) -> Result<QueryResult, String> {
+    validate(&query)?;
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+    index_state.with_workspace(&root, |index| evaluate(&root, index, &query))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mixed_kinds_sort_without_panicking() {
+        let kinds = [
+            QValue::Text("2024-03-01".to_string()),
+            QValue::Text("next week".to_string()),
+            QValue::Number(3.0),
+            QValue::Text("12".to_string()),
+            QValue::Bool(true),
+            QValue::Text("2023-13-45".to_string()),
+            QValue::List(vec![QValue::Text("a".to_string())]),
+            QValue::Number(f64::NAN),
+        ];
+        // Deterministic shuffle so the sort sees many interleavings
+        let mut values: Vec<QValue> = (0..2000usize)
+            .map(|i| sort_value(&kinds[(i * 7919 + i / 3) % kinds.len()]))
+            .collect();
+        values.sort_by(sort_compare);
+
+        let ranks: Vec<u8> = values.iter().map(kind_rank).collect();
+        assert!(ranks.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(values
+            .windows(2)
+            .all(|pair| sort_compare(&pair[0], &pair[1]) != Ordering::Greater));
+    }
+}
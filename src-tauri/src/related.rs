@@ -0,0 +1,130 @@
+use crate::index::{IndexState, NoteRecord};
+use crate::watcher::WatcherState;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::State;
+
+const DEFAULT_LIMIT: usize = 10;
+const DIRECT_LINK_WEIGHT: f64 = 3.0;
+const SHARED_LINK_WEIGHT: f64 = 1.0;
+const SHARED_TAG_WEIGHT: f64 = 2.0;
+const SIMILARITY_WEIGHT: f64 = 5.0;
+
+#[derive(Debug, Serialize)]
+pub struct RelatedNote {
+    name: String,
+    path: String,
+    score: f64,
+    links_to: bool,
+    linked_from: bool,
+    shared_links: Vec<String>,
+    shared_tags: Vec<String>,
+    similarity: f64,
+}
+
+fn tf_idf(note: &NoteRecord, idf: &HashMap<&str, f64>) -> HashMap<String, f64> {
+    note.terms
+        .iter()
+        .map(|(term, count)| {
+            let weight = (1.0 + f64::from(*count).ln()) * idf.get(term.as_str()).unwrap_or(&0.0);
+            (term.clone(), weight)
+        })
+        .collect()
+}
+
+fn cosine(a: &HashMap<String, f64>, b: &HashMap<String, f64>) -> f64 {
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    let dot: f64 = small
+        .iter()
+        .filter_map(|(term, weight)| large.get(term).map(|other| weight * other))
+        .sum();
+    let norm_a = a.values().map(|w| w * w).sum::<f64>().sqrt();
+    let norm_b = b.values().map(|w| w * w).sum::<f64>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+#[tauri::command]
+pub async fn get_related_notes(
+    file_path: String,
+    k: Option<usize>,
+    index_state: State<'_, IndexState>,
+    watcher_state: State<'_, WatcherState>,
+) -> Result<Vec<RelatedNote>, String> {
+    let path = PathBuf::from(&file_path);
+    let root = index_state
+        .root_for(&path)
+        .or_else(|| watcher_state.root_for(&path))
+        .filter(|root| path.starts_with(root))
+        .ok_or("Note is not inside an open workspace")?;
+    let limit = k.unwrap_or(DEFAULT_LIMIT);
+
+    index_state.with_workspace(&root, |index| -> Result<Vec<RelatedNote>, String> {
+        let target = index.notes.get(&path).ok_or("Note is not indexed")?;
+
+        let doc_count = index.notes.len() as f64;
+        let mut doc_freq: HashMap<&str, usize> = HashMap::new();
+        for note in index.notes.values() {
+            for term in note.terms.keys() {
+                *doc_freq.entry(term.as_str()).or_insert(0) += 1;
+            }
+        }
+        let idf: HashMap<&str, f64> = doc_freq
+            .into_iter()
+            .map(|(term, df)| (term, (doc_count / df as f64).ln()))
+            .collect();
+
+        let target_vector = tf_idf(target, &idf);
+
+        let mut related: Vec<RelatedNote> = index
+            .notes
+            .values()
+            .filter(|note| note.path != target.path)
+            .filter_map(|note| {
+                let links_to = target.links.iter().any(|link| note.answers_to(link));
+                let linked_from = note.links.iter().any(|link| target.answers_to(link));
+                let shared_links: Vec<String> = target
+                    .links
+                    .iter()
+                    .filter(|link| note.links.contains(link))
+                    .cloned()
+                    .collect();
+                let shared_tags: Vec<String> = target
+                    .tags
+                    .iter()
+                    .filter(|tag| note.tags.contains(tag))
+                    .cloned()
+                    .collect();
+                let similarity = cosine(&target_vector, &tf_idf(note, &idf));
+
+                let score = DIRECT_LINK_WEIGHT * (f64::from(links_to as u8 + linked_from as u8))
+                    + SHARED_LINK_WEIGHT * shared_links.len() as f64
+                    + SHARED_TAG_WEIGHT * shared_tags.len() as f64
+                    + SIMILARITY_WEIGHT * similarity;
+
+                (score > 0.0).then(|| RelatedNote {
+                    name: note.title.clone(),
+                    path: note.path.to_string_lossy().to_string(),
+                    score,
+                    links_to,
+                    linked_from,
+                    shared_links,
+                    shared_tags,
+                    similarity,
+                })
+            })
+            .collect();
+
+        related.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        related.truncate(limit);
+        Ok(related)
+    })?
+}
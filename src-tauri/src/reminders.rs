@@ -0,0 +1,156 @@
+use crate::i18n;
+use crate::index::IndexState;
+use crate::markdown;
+use crate::notifications::{self, Category};
+use crate::safe_mode::SafeModeState;
+use crate::storage;
+use crate::watcher::WatcherState;
+use chrono::{Duration as ChronoDuration, Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+const FIRED_FILE: &str = "reminders.json";
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+// Reminders that came due while Marky was closed still fire if they're this recent
+const MISSED_WINDOW_HOURS: i64 = 24;
+const TIME_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Reminder {
+    path: String,
+    title: String,
+    // Local time, `YYYY-MM-DDTHH:MM`
+    at: String,
+}
+
+#[derive(Debug, Clone)]
+struct Due {
+    path: PathBuf,
+    at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct FiredReminder {
+    path: String,
+    at: String,
+}
+
+// Per workspace, so a reminder fires once even across restarts
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct FiredLog {
+    fired: Vec<FiredReminder>,
+}
+
+fn workspace_reminders(index_state: &IndexState, root: &Path) -> Result<Vec<Due>, String> {
+    index_state.with_workspace(root, |index| {
+        index
+            .notes
+            .iter()
+            .flat_map(|(path, note)| {
+                note.reminders.iter().map(|at| Due {
+                    path: path.clone(),
+                    at: *at,
+                })
+            })
+            .collect()
+    })
+}
+
+fn to_reminder(reminder: &Due) -> Reminder {
+    Reminder {
+        path: reminder.path.to_string_lossy().to_string(),
+        title: markdown::note_title(&reminder.path),
+        at: reminder.at.format(TIME_FORMAT).to_string(),
+    }
+}
+
+fn fire(app: &AppHandle, root: &Path, payload: &Reminder) {
+    notifications::notify(
+        app,
+        Category::Reminders,
+        &i18n::t("notification.reminder_title"),
+        &payload.title,
+    );
+
+    // Windows showing the workspace offer a toast that opens the note
+    for label in app.state::<WatcherState>().windows_for(root) {
+        let _ = app.emit_to(label.as_str(), "reminder-due", payload.clone());
+    }
+}
+
+fn check_workspace(app: &AppHandle, root: &Path) -> Result<(), String> {
+    let now = Local::now().naive_local();
+    let oldest = now - ChronoDuration::hours(MISSED_WINDOW_HOURS);
+    let due: Vec<Due> = workspace_reminders(&app.state::<IndexState>(), root)?
+        .into_iter()
+        .filter(|reminder| reminder.at <= now && reminder.at >= oldest)
+        .collect();
+    if due.is_empty() {
+        return Ok(());
+    }
+
+    let log_path = storage::workspace_data_dir(app, root)?.join(FIRED_FILE);
+    let mut log: FiredLog = storage::read_json(&log_path);
+    log.fired.retain(|fired| {
+        NaiveDateTime::parse_from_str(&fired.at, TIME_FORMAT).is_ok_and(|at| at >= oldest)
+    });
+
+    let mut changed = false;
+    for reminder in due.iter().map(to_reminder) {
+        let fired = FiredReminder {
+            path: reminder.path.clone(),
+            at: reminder.at.clone(),
+        };
+        if log.fired.contains(&fired) {
+            continue;
+        }
+        fire(app, root, &reminder);
+        log.fired.push(fired);
+        changed = true;
+    }
+
+    if changed {
+        storage::write_json(&log_path, &log)?;
+    }
+    Ok(())
+}
+
+// Checks every watched workspace on a timer; the index already tracks edits
+pub fn spawn_scheduler(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(CHECK_INTERVAL);
+        if app.state::<SafeModeState>().is_active() {
+            continue;
+        }
+
+        for root in app.state::<WatcherState>().watched_roots() {
+            if let Err(e) = check_workspace(&app, &root) {
+                tracing::error!("Failed to check reminders: {}", e);
+            }
+        }
+    });
+}
+
+// Upcoming reminders plus any from the last day, oldest first
+#[tauri::command]
+pub fn list_reminders(
+    folder_path: String,
+    index_state: State<IndexState>,
+) -> Result<Vec<Reminder>, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err(i18n::t("error.folder_missing"));
+    }
+
+    let oldest = Local::now().naive_local() - ChronoDuration::hours(MISSED_WINDOW_HOURS);
+    let mut reminders: Vec<Due> = workspace_reminders(&index_state, &root)?
+        .into_iter()
+        .filter(|reminder| reminder.at >= oldest)
+        .collect();
+    reminders.sort_by(|a, b| a.at.cmp(&b.at).then_with(|| a.path.cmp(&b.path)));
+
+    Ok(reminders.iter().map(to_reminder).collect())
+}
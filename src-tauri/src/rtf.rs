@@ -0,0 +1,389 @@
+use crate::markdown;
+use pulldown_cmark::{Alignment, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+// Sizes are in half-points and widths in twips, as RTF counts them
+const BODY_SIZE: u32 = 24;
+const CODE_SIZE: u32 = 20;
+const LIST_INDENT: u32 = 360;
+const QUOTE_INDENT: u32 = 720;
+const PARAGRAPH_SPACING: u32 = 120;
+const HEADING_SPACING: u32 = 240;
+// Six and a half inches, the text width of a Letter or A4 page in Word
+const TABLE_WIDTH: u32 = 9360;
+const CELL_PADDING: u32 = 108;
+
+// Word and Outlook pick the first font of each family that's installed
+const FONT_TABLE: &str =
+    "{\\fonttbl{\\f0\\fswiss\\fcharset0 Calibri;}{\\f1\\fmodern\\fcharset0 Consolas;}}";
+// 1: text, 2: code and table header shading, 3: quotes, 4: links
+const COLOR_TABLE: &str = "{\\colortbl;\\red0\\green0\\blue0;\\red242\\green242\\blue242;\\red102\\green102\\blue102;\\red5\\green99\\blue193;}";
+const CODE_SHADE: u32 = 2;
+const QUOTE_COLOR: u32 = 3;
+const LINK_COLOR: u32 = 4;
+
+fn heading_size(level: HeadingLevel) -> u32 {
+    match level {
+        HeadingLevel::H1 => 36,
+        HeadingLevel::H2 => 32,
+        HeadingLevel::H3 => 28,
+        HeadingLevel::H4 => 26,
+        _ => BODY_SIZE,
+    }
+}
+
+// Bullets change with depth, as in Word's default list style
+fn bullet(depth: usize) -> &'static str {
+    match depth % 3 {
+        1 => "\\bullet",
+        2 => "\\u9702?",
+        _ => "\\u9642?",
+    }
+}
+
+fn alignment(align: Alignment) -> &'static str {
+    match align {
+        Alignment::Center => "\\qc",
+        Alignment::Right => "\\qr",
+        Alignment::Left | Alignment::None => "\\ql",
+    }
+}
+
+// RTF is 7-bit: control characters are escaped and everything past ASCII
+// goes out as signed 16-bit `\u` escapes, with `?` for readers that can't
+fn push_escaped(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '\\' | '{' | '}' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push(' '),
+            '\t' => out.push_str("\\tab "),
+            c if c.is_ascii() => out.push(c),
+            c => {
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    out.push_str(&format!("\\u{}?", *unit as i16));
+                }
+            }
+        }
+    }
+}
+
+// The table being written: its column alignments and where the next cell goes
+struct Table {
+    alignments: Vec<Alignment>,
+    cell: usize,
+    head: bool,
+}
+
+// Ordered lists remember the next number to show
+struct Writer {
+    out: String,
+    lists: Vec<Option<u64>>,
+    quotes: u32,
+    table: Option<Table>,
+    // Nothing written since the last `\par`, so a paragraph has to be opened
+    line_start: bool,
+    in_code_block: bool,
+}
+
+impl Writer {
+    fn indent(&self) -> u32 {
+        LIST_INDENT * self.lists.len() as u32 + QUOTE_INDENT * self.quotes
+    }
+
+    // `\plain` drops the previous paragraph's character formatting along with `\pard`
+    fn paragraph(&mut self, controls: &str) {
+        self.out.push_str(&format!(
+            "\\pard\\plain\\li{}\\sa{}\\fs{}",
+            self.indent(),
+            PARAGRAPH_SPACING,
+            BODY_SIZE
+        ));
+        if self.in_code_block {
+            self.out
+                .push_str(&format!("\\f1\\fs{}\\cbpat{}\\sa0", CODE_SIZE, CODE_SHADE));
+        }
+        if self.quotes > 0 {
+            self.out.push_str(&format!(
+                "\\brdrl\\brdrs\\brdrw30\\brsp180\\cf{}",
+                QUOTE_COLOR
+            ));
+        }
+        self.out.push_str(controls);
+        self.out.push(' ');
+        self.line_start = false;
+    }
+
+    fn open_paragraph(&mut self) {
+        if self.line_start {
+            self.paragraph("");
+        }
+    }
+
+    fn end_paragraph(&mut self) {
+        if !self.line_start {
+            self.out.push_str("\\par\n");
+            self.line_start = true;
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.in_code_block {
+            // Code paragraphs have no spacing of their own, so a block reads
+            // as one shaded run with lines kept as they are
+            for (idx, line) in text.split('\n').enumerate() {
+                // Blank lines still get a paragraph of their own
+                if idx > 0 {
+                    self.open_paragraph();
+                    self.end_paragraph();
+                }
+                if !line.is_empty() {
+                    self.open_paragraph();
+                    push_escaped(&mut self.out, line);
+                }
+            }
+        } else {
+            self.open_paragraph();
+            push_escaped(&mut self.out, text);
+        }
+    }
+
+    fn start_item(&mut self) {
+        self.end_paragraph();
+        let depth = self.lists.len();
+        let marker = match self.lists.last_mut() {
+            Some(Some(number)) => {
+                *number += 1;
+                format!("{}.", *number - 1)
+            }
+            _ => bullet(depth).to_string(),
+        };
+        let indent = self.indent();
+        self.paragraph(&format!(
+            "\\fi-{}\\tx{}\\sa{}",
+            LIST_INDENT,
+            indent,
+            PARAGRAPH_SPACING / 2
+        ));
+        self.out.push_str(&marker);
+        self.out.push_str("\\tab ");
+    }
+
+    // Word needs every row to define its cells: borders, shading and where
+    // each one ends
+    fn start_row(&mut self, head: bool) {
+        self.end_paragraph();
+        let indent = self.indent();
+        let Some(table) = self.table.as_mut() else {
+            return;
+        };
+        table.cell = 0;
+        table.head = head;
+        let columns = table.alignments.len().max(1) as u32;
+        let width = (TABLE_WIDTH - indent.min(TABLE_WIDTH / 2)) / columns;
+        self.out.push_str(&format!(
+            "\\trowd\\trgaph{}\\trleft{}",
+            CELL_PADDING, indent
+        ));
+        if head {
+            self.out.push_str("\\trhdr");
+        }
+        for column in 1..=columns {
+            self.out
+                .push_str("\\clbrdrt\\brdrs\\brdrw10\\clbrdrl\\brdrs\\brdrw10");
+            self.out
+                .push_str("\\clbrdrb\\brdrs\\brdrw10\\clbrdrr\\brdrs\\brdrw10");
+            if head {
+                self.out.push_str(&format!("\\clcbpat{}", CODE_SHADE));
+            }
+            self.out
+                .push_str(&format!("\\cellx{}", indent + width * column));
+        }
+        self.out.push('\n');
+    }
+
+    fn start_cell(&mut self) {
+        let Some(table) = self.table.as_mut() else {
+            return;
+        };
+        let align = table
+            .alignments
+            .get(table.cell)
+            .copied()
+            .unwrap_or(Alignment::None);
+        let head = table.head;
+        table.cell += 1;
+        self.out.push_str(&format!(
+            "\\pard\\plain\\intbl{}\\fs{}",
+            alignment(align),
+            BODY_SIZE
+        ));
+        self.out.push_str(if head { "\\b " } else { " " });
+        self.line_start = false;
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            // Inside a list the text follows its item's marker directly
+            Tag::Paragraph if self.lists.is_empty() => self.end_paragraph(),
+            Tag::CodeBlock(_) => {
+                self.end_paragraph();
+                self.in_code_block = true;
+            }
+            Tag::Heading { level, .. } => {
+                self.end_paragraph();
+                self.paragraph(&format!(
+                    "\\sb{}\\keepn\\b\\fs{}",
+                    HEADING_SPACING,
+                    heading_size(level)
+                ));
+            }
+            Tag::BlockQuote(_) => {
+                self.end_paragraph();
+                self.quotes += 1;
+            }
+            Tag::List(start) => {
+                self.end_paragraph();
+                self.lists.push(start);
+            }
+            Tag::Item => self.start_item(),
+            Tag::Table(alignments) => {
+                self.end_paragraph();
+                self.table = Some(Table {
+                    alignments,
+                    cell: 0,
+                    head: false,
+                });
+            }
+            Tag::TableHead => self.start_row(true),
+            Tag::TableRow => self.start_row(false),
+            Tag::TableCell => self.start_cell(),
+            Tag::Emphasis => self.open_group("\\i"),
+            Tag::Strong => self.open_group("\\b"),
+            Tag::Strikethrough => self.open_group("\\strike"),
+            Tag::Link { dest_url, .. } => {
+                self.open_paragraph();
+                self.out.push_str("{\\field{\\*\\fldinst{HYPERLINK \"");
+                push_escaped(&mut self.out, &dest_url.replace('"', "%22"));
+                self.out
+                    .push_str(&format!("\"}}}}{{\\fldrslt{{\\ul\\cf{} ", LINK_COLOR));
+            }
+            // Pasted documents can't reach the note's images, so the alt text stands in
+            Tag::Image { .. } => self.open_group("\\i"),
+            Tag::FootnoteDefinition(label) => {
+                self.end_paragraph();
+                self.open_paragraph();
+                self.out.push_str("{\\super ");
+                push_escaped(&mut self.out, &label);
+                self.out.push_str("} ");
+            }
+            _ => {}
+        }
+    }
+
+    fn open_group(&mut self, control: &str) {
+        self.open_paragraph();
+        self.out.push('{');
+        self.out.push_str(control);
+        self.out.push(' ');
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::Item | TagEnd::FootnoteDefinition => {
+                self.end_paragraph()
+            }
+            TagEnd::CodeBlock => {
+                self.end_paragraph();
+                self.in_code_block = false;
+                // Spacing after the block, which its own lines don't have
+                self.paragraph("\\sa0\\fs4");
+                self.end_paragraph();
+            }
+            TagEnd::BlockQuote(_) => {
+                self.end_paragraph();
+                self.quotes = self.quotes.saturating_sub(1);
+            }
+            TagEnd::List(_) => {
+                self.end_paragraph();
+                self.lists.pop();
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough | TagEnd::Image => {
+                self.out.push('}')
+            }
+            TagEnd::Link => self.out.push_str("}}}"),
+            TagEnd::TableCell => self.out.push_str("\\cell "),
+            TagEnd::TableHead | TagEnd::TableRow => {
+                self.out.push_str("\\row\n");
+                self.line_start = true;
+            }
+            TagEnd::Table => {
+                self.table = None;
+                // `\pard` on its own ends the table, so what follows isn't a row
+                self.out.push_str("\\pard\\plain\\sa0\\fs4 \\par\n");
+                self.line_start = true;
+            }
+            _ => {}
+        }
+    }
+
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => self.text(&text),
+            Event::Code(code) => {
+                self.open_paragraph();
+                self.out
+                    .push_str(&format!("{{\\f1\\fs{}\\chcbpat{} ", CODE_SIZE, CODE_SHADE));
+                push_escaped(&mut self.out, &code);
+                self.out.push('}');
+            }
+            Event::SoftBreak => self.text(" "),
+            Event::HardBreak => {
+                self.open_paragraph();
+                self.out.push_str("\\line ");
+            }
+            Event::Rule => {
+                self.end_paragraph();
+                self.paragraph("\\brdrb\\brdrs\\brdrw10\\brsp20");
+                self.end_paragraph();
+            }
+            Event::TaskListMarker(done) => self.text(if done { "\u{2611} " } else { "\u{2610} " }),
+            Event::FootnoteReference(label) => {
+                self.open_paragraph();
+                self.out.push_str("{\\super ");
+                push_escaped(&mut self.out, &label);
+                self.out.push('}');
+            }
+            // Raw HTML has no RTF equivalent
+            _ => {}
+        }
+    }
+}
+
+// The note body as an RTF document, for Word, Outlook and other apps that
+// paste rich text from that flavor rather than HTML. Headings, lists, tables
+// and code keep their structure; code is set in a shaded monospace font.
+pub fn from_markdown(content: &str) -> String {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS;
+    let mut writer = Writer {
+        out: format!("{{\\rtf1\\ansi\\deff0\\uc1{}{}\n", FONT_TABLE, COLOR_TABLE),
+        lists: Vec::new(),
+        quotes: 0,
+        table: None,
+        line_start: true,
+        in_code_block: false,
+    };
+    for event in Parser::new_ext(markdown::body(content), options) {
+        writer.event(event);
+    }
+    writer.end_paragraph();
+    writer.out.push('}');
+    writer.out
+}
@@ -0,0 +1,120 @@
+use crate::settings;
+use crate::storage;
+use crate::window_state;
+use crate::workspaces;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, State};
+
+pub const SAFE_MODE_FLAG: &str = "--safe";
+
+// Safe mode opens a bare window with no workspace, watchers, or index so a
+// corrupted state file can't keep the app from starting
+#[derive(Default)]
+pub struct SafeModeState {
+    active: AtomicBool,
+    // Set once state has been wiped so exit handlers don't write it back
+    reset: AtomicBool,
+}
+
+impl SafeModeState {
+    pub fn new(active: bool) -> SafeModeState {
+        SafeModeState {
+            active: AtomicBool::new(active),
+            reset: AtomicBool::new(false),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    // Window geometry from a safe-mode session is not worth keeping
+    pub fn should_persist(&self) -> bool {
+        !self.is_active() && !self.reset.load(Ordering::SeqCst)
+    }
+}
+
+// `--safe` on the command line, or Shift held while the app launches
+pub fn requested() -> bool {
+    std::env::args().any(|arg| arg == SAFE_MODE_FLAG) || modifier_held()
+}
+
+#[cfg(target_os = "macos")]
+fn modifier_held() -> bool {
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGEventSourceFlagsState(state_id: i32) -> u64;
+    }
+    const COMBINED_SESSION_STATE: i32 = 0;
+    const SHIFT_MASK: u64 = 0x0002_0000;
+
+    // SAFETY: reads global keyboard flags; no pointers involved
+    unsafe { CGEventSourceFlagsState(COMBINED_SESSION_STATE) & SHIFT_MASK != 0 }
+}
+
+#[cfg(target_os = "windows")]
+fn modifier_held() -> bool {
+    #[link(name = "user32")]
+    extern "system" {
+        fn GetAsyncKeyState(key: i32) -> i16;
+    }
+    const VK_SHIFT: i32 = 0x10;
+
+    // SAFETY: takes a virtual key code by value; the high bit means "down"
+    unsafe { GetAsyncKeyState(VK_SHIFT) < 0 }
+}
+
+// No display-server independent way to read the keyboard; use `--safe`
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn modifier_held() -> bool {
+    false
+}
+
+fn remove_path(path: &Path) -> Result<(), String> {
+    let result = if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else if path.exists() {
+        fs::remove_file(path)
+    } else {
+        return Ok(());
+    };
+
+    result.map_err(|e| format!("Failed to remove {}: {}", path.display(), e))
+}
+
+#[tauri::command]
+pub fn get_safe_mode(state: State<SafeModeState>) -> bool {
+    state.is_active()
+}
+
+// The frontend mounts the full app afterwards; watchers start as workspaces open
+#[tauri::command]
+pub fn leave_safe_mode(state: State<SafeModeState>) {
+    state.active.store(false, Ordering::SeqCst);
+}
+
+// Wipes settings, window geometry, the workspace registry, and per-workspace
+// data (sessions, pins, locks, journals), then relaunches normally. Notes,
+// logs, crash reports, and keychain secrets are left alone.
+#[tauri::command]
+pub fn reset_app_state(app: AppHandle, state: State<SafeModeState>) -> Result<(), String> {
+    state.reset.store(true, Ordering::SeqCst);
+
+    remove_path(&settings::settings_path(&app)?)?;
+    remove_path(&window_state::state_path(&app)?)?;
+    remove_path(&workspaces::registry_path(&app)?)?;
+    remove_path(&storage::app_data_dir(&app)?.join(storage::WORKSPACES_DIR))?;
+
+    let exe = std::env::current_exe()
+        .map_err(|e| format!("Failed to locate the app executable: {}", e))?;
+    Command::new(exe)
+        .args(std::env::args().skip(1).filter(|arg| arg != SAFE_MODE_FLAG))
+        .spawn()
+        .map_err(|e| format!("Failed to relaunch Marky: {}", e))?;
+
+    app.exit(0);
+    Ok(())
+}
@@ -0,0 +1,265 @@
+use crate::settings::SettingsState;
+use crate::storage;
+use crate::workspace_config;
+use chrono::{Duration, Local, NaiveDate};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, State};
+
+const FOLDER_NAME: &str = "Marky Sample";
+const TEMPLATES_FOLDER: &str = "Templates";
+
+const WELCOME: &str = "# Welcome
+
+This is a sample workspace to look around in. Everything here is a plain
+markdown file in a folder on your disk, so delete it whenever you like.
+
+## Links
+
+Notes link to each other with double brackets: open [[Project Plan]] or
+[[Reading List]], then look at their backlinks to find your way back here.
+Open the graph to see how the notes connect.
+
+## Tags
+
+Tags like #getting-started and #marky group notes across folders. Click one
+in the sidebar to see every note that has it.
+
+## Daily notes
+
+Today's note is in Daily. Open the calendar to jump between days.
+
+## Tasks
+
+Lines starting with `- [ ]` are tasks. Give them a due date and they show up
+in the task view:
+
+- [x] Open the sample workspace
+- [ ] Follow a link to [[Project Plan]]
+- [ ] Open the command palette and look around
+
+## Templates
+
+New notes can start from the files in Templates, like the
+[[Meeting Notes]] template.
+
+## Attachments
+
+Images and other files dropped into a note are copied into {attachments}:
+
+![Marky diagram]({attachments}/diagram.svg)
+";
+
+const READING_LIST: &str = "# Reading List
+
+#reading
+
+Notes worth coming back to, linked from [[Welcome]].
+
+- [ ] *How to Take Smart Notes* by Sönke Ahrens
+- [ ] *The Pragmatic Programmer* by David Thomas and Andrew Hunt
+- [x] *Deep Work* by Cal Newport
+";
+
+const MEETING_TEMPLATE: &str = "# {{title}}
+
+**Date:** {{date}}
+**Time:** {{time}}
+**Attendees:**
+
+## Agenda
+
+-
+
+## Notes
+
+## Action items
+
+- [ ]
+";
+
+const DAILY_TEMPLATE: &str = "# {{title}}
+
+## Today
+
+- [ ]
+
+## Notes
+";
+
+const DIAGRAM: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="360" height="140" viewBox="0 0 360 140">
+  <rect width="360" height="140" rx="12" fill="#1e1e2e"/>
+  <g font-family="sans-serif" font-size="14" text-anchor="middle">
+    <rect x="20" y="45" width="90" height="50" rx="8" fill="#89b4fa"/>
+    <text x="65" y="75" fill="#1e1e2e">Welcome</text>
+    <rect x="135" y="45" width="90" height="50" rx="8" fill="#a6e3a1"/>
+    <text x="180" y="75" fill="#1e1e2e">Project</text>
+    <rect x="250" y="45" width="90" height="50" rx="8" fill="#f9e2af"/>
+    <text x="295" y="75" fill="#1e1e2e">Reading</text>
+  </g>
+  <g stroke="#cdd6f4" stroke-width="2">
+    <line x1="110" y1="70" x2="135" y2="70"/>
+    <line x1="225" y1="70" x2="250" y2="70"/>
+  </g>
+</svg>
+"##;
+
+fn project_plan(today: NaiveDate) -> String {
+    let due = |days: i64| (today + Duration::days(days)).format("%Y-%m-%d");
+    format!(
+        "# Project Plan
+
+#project #getting-started
+
+A made-up project to show tasks with due dates. Back to [[Welcome]].
+
+## Milestones
+
+- [x] Write the brief due:{}
+- [ ] Review the draft with the team due:{}
+- [ ] Ship the first version due:{}
+- [ ] Collect feedback 📅 {}
+
+## Links
+
+- Kickoff: [[Kickoff Meeting]]
+- Background reading: [[Reading List]]
+",
+        due(-3),
+        due(1),
+        due(7),
+        due(14)
+    )
+}
+
+fn kickoff_meeting(today: NaiveDate) -> String {
+    format!(
+        "# Kickoff Meeting
+
+#meeting #project
+
+**Date:** {}
+**Attendees:** Alex, Sam
+
+Made from the [[Meeting Notes]] template for [[Project Plan]].
+
+## Notes
+
+- Agreed on the scope of the first version
+- Sam owns the review
+
+## Action items
+
+- [ ] Share the brief with everyone due:{}
+",
+        today.format("%Y-%m-%d"),
+        (today + Duration::days(2)).format("%Y-%m-%d")
+    )
+}
+
+fn daily_note(title: &str) -> String {
+    format!(
+        "# {}
+
+#daily
+
+## Today
+
+- [x] Open the sample workspace
+- [ ] Read through [[Welcome]]
+- [ ] Write a first note of my own
+
+## Notes
+
+Daily notes are named by date, so the calendar can find them.
+",
+        title
+    )
+}
+
+// `Marky Sample` inside `parent`, or `Marky Sample 2`, 3, … when taken, so an
+// earlier sample (and whatever the user wrote in it) is never touched
+fn unused_folder(parent: &Path) -> PathBuf {
+    let mut folder = parent.join(FOLDER_NAME);
+    let mut n = 2;
+    while folder.exists() {
+        folder = parent.join(format!("{} {}", FOLDER_NAME, n));
+        n += 1;
+    }
+    folder
+}
+
+fn write(root: &Path, relative: &str, content: &str) -> Result<(), String> {
+    let path = root.join(relative);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create folder: {}", e))?;
+    }
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", relative, e))
+}
+
+// Creates a small demo workspace in a new folder inside `dest` and returns
+// its path. Due dates and the daily note are relative to today, so the task
+// and calendar views have something in them.
+#[tauri::command]
+pub fn create_sample_workspace(
+    dest: String,
+    app: AppHandle,
+    settings_state: State<SettingsState>,
+) -> Result<String, String> {
+    let parent = Path::new(&dest);
+    storage::ensure_accessible(&app, parent)?;
+    if !parent.is_dir() {
+        return Err("Invalid folder path".to_string());
+    }
+
+    let root = unused_folder(parent);
+    fs::create_dir_all(&root).map_err(|e| format!("Failed to create folder: {}", e))?;
+
+    let config_path = workspace_config::config_path(&root);
+    if let Some(dir) = config_path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create folder: {}", e))?;
+    }
+    let config = serde_json::json!({ "templates_folder": TEMPLATES_FOLDER });
+    storage::write_json(&config_path, &config)?;
+
+    let effective = workspace_config::effective(&root, &settings_state.current());
+    let today = Local::now().date_naive();
+    let daily_title = Local::now()
+        .format(&effective.daily_note_format)
+        .to_string();
+
+    write(
+        &root,
+        "Welcome.md",
+        &WELCOME.replace("{attachments}", &effective.attachment_folder),
+    )?;
+    write(&root, "Reading List.md", READING_LIST)?;
+    write(&root, "Projects/Project Plan.md", &project_plan(today))?;
+    write(
+        &root,
+        "Projects/Kickoff Meeting.md",
+        &kickoff_meeting(today),
+    )?;
+    write(
+        &root,
+        &format!("Daily/{}.md", daily_title),
+        &daily_note(&daily_title),
+    )?;
+    write(
+        &root,
+        &format!("{}/Meeting Notes.md", TEMPLATES_FOLDER),
+        MEETING_TEMPLATE,
+    )?;
+    write(
+        &root,
+        &format!("{}/Daily Note.md", TEMPLATES_FOLDER),
+        DAILY_TEMPLATE,
+    )?;
+    write(
+        &root,
+        &format!("{}/diagram.svg", effective.attachment_folder),
+        DIAGRAM,
+    )?;
+
+    Ok(root.to_string_lossy().to_string())
+}
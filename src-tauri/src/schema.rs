@@ -0,0 +1,259 @@
+use crate::encryption;
+use crate::index::collect_markdown_files;
+use crate::markdown;
+use crate::query;
+use crate::settings::SettingsState;
+use crate::storage;
+use crate::vault;
+use crate::workspace_config::{NoteFilter, CONFIG_DIR};
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, State};
+
+const SCHEMA_FILE: &str = "schema.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum FieldType {
+    #[default]
+    Any,
+    String,
+    Number,
+    Boolean,
+    // `2024-05-01`, or a date and time
+    Date,
+    List,
+}
+
+impl FieldType {
+    fn describe(self) -> &'static str {
+        match self {
+            FieldType::Any => "any value",
+            FieldType::String => "text",
+            FieldType::Number => "a number",
+            FieldType::Boolean => "true or false",
+            FieldType::Date => "a date",
+            FieldType::List => "a list",
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct FieldRule {
+    #[serde(rename = "type")]
+    kind: FieldType,
+    required: bool,
+    // For lists, every item must be one of these
+    allowed: Vec<String>,
+}
+
+// `.marky/schema.json`, e.g.
+// {"fields": {"status": {"type": "string", "required": true, "allowed": ["draft", "done"]}}}
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct Schema {
+    fields: BTreeMap<String, FieldRule>,
+    // With false, keys the schema doesn't list are violations too
+    allow_unknown: bool,
+}
+
+impl Default for Schema {
+    fn default() -> Self {
+        Schema {
+            fields: BTreeMap::new(),
+            allow_unknown: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ViolationKind {
+    Missing,
+    WrongType,
+    NotAllowed,
+    UnknownField,
+    InvalidFrontmatter,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Violation {
+    path: String,
+    field: Option<String>,
+    kind: ViolationKind,
+    message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidationReport {
+    schema_path: String,
+    checked: usize,
+    violations: Vec<Violation>,
+}
+
+// The nearest `.marky/schema.json` at or above `path`: the workspace's own
+fn find_schema(path: &Path) -> Option<(PathBuf, PathBuf)> {
+    path.ancestors().find_map(|dir| {
+        let schema = dir.join(CONFIG_DIR).join(SCHEMA_FILE);
+        schema.is_file().then(|| (dir.to_path_buf(), schema))
+    })
+}
+
+fn load_schema(path: &Path) -> Result<Schema, String> {
+    let raw = fs::read_to_string(path).map_err(|e| format!("Failed to read schema: {}", e))?;
+    serde_json::from_str(&raw).map_err(|e| format!("Invalid {}: {}", SCHEMA_FILE, e))
+}
+
+fn matches_type(value: &Value, kind: FieldType) -> bool {
+    match kind {
+        FieldType::Any => true,
+        FieldType::String => value.is_string(),
+        FieldType::Number => value.is_number(),
+        FieldType::Boolean => value.is_bool(),
+        FieldType::Date => value.as_str().and_then(query::parse_date).is_some(),
+        FieldType::List => value.is_sequence(),
+    }
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.trim().to_string()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+fn check_note(path: &Path, content: &str, schema: &Schema, violations: &mut Vec<Violation>) {
+    let violation = |field: Option<&str>, kind, message: String| Violation {
+        path: path.to_string_lossy().to_string(),
+        field: field.map(str::to_string),
+        kind,
+        message,
+    };
+
+    let block = markdown::frontmatter(content);
+    let mapping = match markdown::parse_frontmatter(content) {
+        Some(mapping) => mapping,
+        // An empty block parses as null rather than a mapping
+        None if block.is_some_and(|block| !block.trim().is_empty()) => {
+            violations.push(violation(
+                None,
+                ViolationKind::InvalidFrontmatter,
+                "Frontmatter isn't valid YAML".to_string(),
+            ));
+            return;
+        }
+        None => Mapping::new(),
+    };
+
+    for (field, rule) in &schema.fields {
+        let value = mapping.get(field.as_str()).filter(|value| !value.is_null());
+        let Some(value) = value else {
+            if rule.required {
+                violations.push(violation(
+                    Some(field),
+                    ViolationKind::Missing,
+                    format!("`{}` is required", field),
+                ));
+            }
+            continue;
+        };
+
+        if !matches_type(value, rule.kind) {
+            violations.push(violation(
+                Some(field),
+                ViolationKind::WrongType,
+                format!("`{}` should be {}", field, rule.kind.describe()),
+            ));
+            continue;
+        }
+
+        if rule.allowed.is_empty() {
+            continue;
+        }
+        let items: Vec<&Value> = match value {
+            Value::Sequence(items) => items.iter().collect(),
+            value => vec![value],
+        };
+        for item in items {
+            let text = scalar_text(item).unwrap_or_default();
+            if !rule.allowed.contains(&text) {
+                violations.push(violation(
+                    Some(field),
+                    ViolationKind::NotAllowed,
+                    format!(
+                        "`{}` can't be \"{}\"; expected one of {}",
+                        field,
+                        text,
+                        rule.allowed.join(", ")
+                    ),
+                ));
+            }
+        }
+    }
+
+    if !schema.allow_unknown {
+        for key in mapping.keys().filter_map(Value::as_str) {
+            if !schema.fields.contains_key(key) {
+                violations.push(violation(
+                    Some(key),
+                    ViolationKind::UnknownField,
+                    format!("`{}` isn't in the schema", key),
+                ));
+            }
+        }
+    }
+}
+
+// Checks one note, or every note under a folder, against the workspace's
+// `.marky/schema.json`. Encrypted and vault notes are skipped; their
+// frontmatter isn't readable here.
+#[tauri::command]
+pub async fn validate_frontmatter(
+    path: String,
+    app: AppHandle,
+    settings_state: State<'_, SettingsState>,
+) -> Result<ValidationReport, String> {
+    let target = PathBuf::from(&path);
+    storage::ensure_accessible(&app, &target)?;
+    if !target.exists() {
+        return Err("Path does not exist".to_string());
+    }
+    let (root, schema_path) = find_schema(&target)
+        .ok_or_else(|| format!("No {}/{} in this workspace", CONFIG_DIR, SCHEMA_FILE))?;
+    let schema = load_schema(&schema_path)?;
+
+    let mut paths = Vec::new();
+    if target.is_dir() {
+        let filter = NoteFilter::for_workspace(&root, &settings_state.current());
+        collect_markdown_files(&target, &filter, &mut paths);
+        paths.sort();
+    } else {
+        paths.push(target);
+    }
+
+    let mut report = ValidationReport {
+        schema_path: schema_path.to_string_lossy().to_string(),
+        checked: 0,
+        violations: Vec::new(),
+    };
+    for path in paths {
+        if vault::vault_root(&path).is_some() {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if encryption::is_encrypted(&content) {
+            continue;
+        }
+        report.checked += 1;
+        check_note(&path, &content, &schema, &mut report.violations);
+    }
+    Ok(report)
+}
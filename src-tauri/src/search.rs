@@ -0,0 +1,283 @@
+use crate::encryption;
+use crate::index::{IndexState, WorkspaceIndex};
+use crate::markdown;
+use crate::network;
+use crate::storage;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LINES_PER_NOTE: usize = 3;
+const SNIPPET_CHARS: usize = 140;
+// How much of the line before the first hit a long snippet keeps
+const SNIPPET_LEAD: usize = 40;
+// A word in the title counts as much as this many extra body hits
+const TITLE_BOOST: f64 = 3.0;
+// Standard BM25 tuning
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+
+#[derive(Debug, Serialize)]
+pub struct LineMatch {
+    // 1-based
+    line: usize,
+    snippet: String,
+    // Highlights as [start, end) offsets into `snippet`, counted in UTF-16
+    // code units so the frontend can slice the string directly
+    ranges: Vec<[usize; 2]>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResult {
+    path: String,
+    title: String,
+    score: f64,
+    title_match: bool,
+    matches: Vec<LineMatch>,
+}
+
+struct QueryWord {
+    text: String,
+    // The word still being typed matches any term it starts
+    prefix: bool,
+}
+
+fn parse_query(query: &str) -> Vec<QueryWord> {
+    let mut words: Vec<QueryWord> = markdown::tokenize(query)
+        .map(|text| QueryWord {
+            text,
+            prefix: false,
+        })
+        .collect();
+    let still_typing = query.chars().last().is_some_and(char::is_alphanumeric);
+    if let Some(last) = words.last_mut() {
+        last.prefix = still_typing;
+    }
+    words
+}
+
+fn idf(total: usize, df: usize) -> f64 {
+    let (total, df) = (total as f64, df as f64);
+    (1.0 + (total - df + 0.5) / (df + 0.5)).ln()
+}
+
+// Every word has to be in the note's text or title; the rest is BM25 over the
+// indexed term counts with a bonus for title hits
+fn rank(index: &WorkspaceIndex, words: &[QueryWord]) -> Vec<(PathBuf, String, f64, bool)> {
+    let total = index.notes.len();
+    if total == 0 {
+        return Vec::new();
+    }
+    let average_words =
+        (index.notes.values().map(|note| note.words).sum::<usize>() as f64 / total as f64).max(1.0);
+
+    let mut candidates: Option<HashSet<&PathBuf>> = None;
+    let mut postings = Vec::with_capacity(words.len());
+    for word in words {
+        let in_text = index.notes_with(&word.text, word.prefix);
+        let in_title = index
+            .notes
+            .values()
+            .filter(|note| note.title.to_lowercase().contains(&word.text))
+            .map(|note| &note.path);
+        let matching: HashSet<&PathBuf> = in_text.iter().copied().chain(in_title).collect();
+        candidates = Some(match candidates {
+            Some(previous) => previous.intersection(&matching).copied().collect(),
+            None => matching,
+        });
+        postings.push(in_text.len());
+    }
+
+    let mut ranked: Vec<(PathBuf, String, f64, bool)> = candidates
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|path| index.notes.get(path))
+        .map(|note| {
+            let title = note.title.to_lowercase();
+            let length = note.words.max(1) as f64;
+            let mut score = 0.0;
+            let mut title_match = false;
+            for (word, df) in words.iter().zip(&postings) {
+                let tf = if word.prefix {
+                    note.terms
+                        .iter()
+                        .filter(|(term, _)| term.starts_with(&word.text))
+                        .map(|(_, count)| *count)
+                        .sum::<u32>()
+                } else {
+                    note.terms.get(&word.text).copied().unwrap_or(0)
+                } as f64;
+                let weight = idf(total, *df);
+                score +=
+                    weight * tf * (K1 + 1.0) / (tf + K1 * (1.0 - B + B * length / average_words));
+                if title.contains(&word.text) {
+                    title_match = true;
+                    score += weight * TITLE_BOOST;
+                }
+            }
+            (note.path.clone(), note.title.clone(), score, title_match)
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.2.partial_cmp(&a.2)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.1.cmp(&b.1))
+    });
+    ranked
+}
+
+// Where each word occurs in `chars` as a whole word (or word start, for the
+// prefix word), as [start, end) char offsets, in order and without overlaps
+fn find_words(chars: &[char], words: &[Vec<char>], prefixes: &[bool]) -> Vec<[usize; 2]> {
+    let lowered: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+    let boundary = |i: usize| i == 0 || !lowered[i - 1].is_alphanumeric();
+
+    let mut ranges = Vec::new();
+    for (word, prefix) in words.iter().zip(prefixes) {
+        if word.is_empty() || word.len() > lowered.len() {
+            continue;
+        }
+        for start in 0..=lowered.len() - word.len() {
+            let end = start + word.len();
+            if boundary(start)
+                && lowered[start..end] == word[..]
+                && (*prefix || end == lowered.len() || !lowered[end].is_alphanumeric())
+            {
+                ranges.push([start, end]);
+            }
+        }
+    }
+    ranges.sort();
+    let mut merged: Vec<[usize; 2]> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range[0] < last[1] => last[1] = last[1].max(range[1]),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+// Up to a few lines with hits, each trimmed around its first hit
+fn line_matches(content: &str, words: &[QueryWord]) -> Vec<LineMatch> {
+    let texts: Vec<Vec<char>> = words
+        .iter()
+        .map(|word| word.text.chars().collect())
+        .collect();
+    let prefixes: Vec<bool> = words.iter().map(|word| word.prefix).collect();
+
+    let mut matches = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        let chars: Vec<char> = line.trim_end().chars().collect();
+        let ranges = find_words(&chars, &texts, &prefixes);
+        let Some(first) = ranges.first() else {
+            continue;
+        };
+
+        let (start, end) = if chars.len() <= SNIPPET_CHARS {
+            (0, chars.len())
+        } else {
+            let start = first[0]
+                .saturating_sub(SNIPPET_LEAD)
+                .min(chars.len() - SNIPPET_CHARS);
+            (start, start + SNIPPET_CHARS)
+        };
+        let lead = if start > 0 { "…" } else { "" };
+        let utf16 = |at: usize| -> usize {
+            lead.encode_utf16().count()
+                + chars[start..at]
+                    .iter()
+                    .map(|c| c.len_utf16())
+                    .sum::<usize>()
+        };
+        let snippet = format!(
+            "{}{}{}",
+            lead,
+            chars[start..end].iter().collect::<String>(),
+            if end < chars.len() { "…" } else { "" }
+        );
+        let ranges = ranges
+            .iter()
+            .filter(|range| range[0] >= start && range[1] <= end)
+            .map(|range| [utf16(range[0]), utf16(range[1])])
+            .collect();
+
+        matches.push(LineMatch {
+            line: number + 1,
+            snippet,
+            ranges,
+        });
+        if matches.len() == MAX_LINES_PER_NOTE {
+            break;
+        }
+    }
+    matches
+}
+
+fn search(
+    app: &AppHandle,
+    root: &Path,
+    words: &[QueryWord],
+    limit: usize,
+) -> Result<Vec<SearchResult>, String> {
+    let ranked = app
+        .state::<IndexState>()
+        .with_workspace(root, |index| rank(index, words))?;
+
+    Ok(ranked
+        .into_iter()
+        .take(limit)
+        .map(|(path, title, score, title_match)| {
+            // The index only keeps term counts, so lines come from the file;
+            // encrypted notes match by title alone
+            let matches = fs::read_to_string(&path)
+                .ok()
+                .filter(|content| !encryption::is_encrypted(content))
+                .map(|content| line_matches(&content, words))
+                .unwrap_or_default();
+            SearchResult {
+                path: path.to_string_lossy().to_string(),
+                title,
+                score,
+                title_match,
+                matches,
+            }
+        })
+        .collect())
+}
+
+// Ranked full-text search over the workspace index, which the watcher keeps
+// current; each result carries the lines that matched for highlighting
+#[tauri::command]
+pub async fn search_workspace(
+    folder_path: String,
+    query: String,
+    limit: Option<usize>,
+    app: AppHandle,
+) -> Result<Vec<SearchResult>, String> {
+    let root = PathBuf::from(&folder_path);
+    storage::ensure_accessible(&app, &root)?;
+    let words = parse_query(&query);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        network::guard_read(&app, &root.clone(), move |app| {
+            if !root.is_dir() {
+                return Err("Folder does not exist".to_string());
+            }
+            if words.is_empty() {
+                return Ok(Vec::new());
+            }
+            search(app, &root, &words, limit)
+        })?
+    })
+    .await
+    .map_err(|e| format!("Failed to search workspace: {}", e))?
+}
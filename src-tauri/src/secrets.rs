@@ -0,0 +1,60 @@
+use tauri::AppHandle;
+
+const MAX_KEY_LEN: usize = 128;
+
+// Secrets live in the platform credential store (Keychain, Credential Manager, or the
+// Secret Service), one entry per key under the app's identifier. Keys are namespaced by
+// their owner, e.g. `sync.token` or `ai.openai.api_key`; values never touch settings.json.
+fn entry(app: &AppHandle, key: &str) -> Result<keyring::Entry, String> {
+    validate_key(key)?;
+    keyring::Entry::new(&app.config().identifier, key)
+        .map_err(|e| format!("Failed to open credential store: {}", e))
+}
+
+fn validate_key(key: &str) -> Result<(), String> {
+    if key.is_empty() || key.len() > MAX_KEY_LEN {
+        return Err("Secret key must be between 1 and 128 characters".to_string());
+    }
+    if !key
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+    {
+        return Err("Secret key contains invalid characters".to_string());
+    }
+    Ok(())
+}
+
+pub fn get(app: &AppHandle, key: &str) -> Result<Option<String>, String> {
+    match entry(app, key)?.get_password() {
+        Ok(value) => Ok(Some(value)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read secret: {}", e)),
+    }
+}
+
+pub fn set(app: &AppHandle, key: &str, value: &str) -> Result<(), String> {
+    if value.is_empty() {
+        return Err("Secret cannot be empty".to_string());
+    }
+    entry(app, key)?
+        .set_password(value)
+        .map_err(|e| format!("Failed to store secret: {}", e))
+}
+
+#[tauri::command]
+pub fn store_secret(key: String, value: String, app: AppHandle) -> Result<(), String> {
+    set(&app, &key, &value)
+}
+
+#[tauri::command]
+pub fn get_secret(key: String, app: AppHandle) -> Result<Option<String>, String> {
+    get(&app, &key)
+}
+
+#[tauri::command]
+pub fn delete_secret(key: String, app: AppHandle) -> Result<(), String> {
+    match entry(&app, &key)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Failed to delete secret: {}", e)),
+    }
+}
@@ -0,0 +1,166 @@
+use crate::i18n;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+const CHUNK: usize = 64 * 1024;
+
+// Copy-on-write filesystems write the zeros to fresh blocks and leave the old contents
+// where they were, so overwriting there only costs time. Flash wear leveling can do the
+// same on any filesystem; this is best effort, not a guarantee.
+#[cfg(target_os = "macos")]
+fn overwrite_reaches_disk(path: &Path) -> bool {
+    use std::ffi::{CStr, CString};
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(c_path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    // SAFETY: statfs only writes into the zeroed struct we hand it
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return false;
+    }
+    let fs_type = unsafe { CStr::from_ptr(stat.f_fstypename.as_ptr()) };
+    !matches!(fs_type.to_bytes(), b"apfs" | b"zfs")
+}
+
+#[cfg(target_os = "linux")]
+fn overwrite_reaches_disk(path: &Path) -> bool {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    const BTRFS_SUPER_MAGIC: i64 = 0x9123_683E;
+    const ZFS_SUPER_MAGIC: i64 = 0x2FC1_2FC1;
+    const BCACHEFS_SUPER_MAGIC: i64 = 0xCA45_1A4E;
+
+    let Ok(c_path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    // SAFETY: statfs only writes into the zeroed struct we hand it
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return false;
+    }
+    #[allow(clippy::unnecessary_cast)]
+    let magic = stat.f_type as i64;
+    !matches!(
+        magic,
+        BTRFS_SUPER_MAGIC | ZFS_SUPER_MAGIC | BCACHEFS_SUPER_MAGIC
+    )
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn overwrite_reaches_disk(_path: &Path) -> bool {
+    true
+}
+
+fn overwrite(path: &Path) -> io::Result<()> {
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    let mut remaining = file.metadata()?.len();
+    let zeros = vec![0u8; CHUNK];
+    while remaining > 0 {
+        let len = remaining.min(CHUNK as u64) as usize;
+        file.write_all(&zeros[..len])?;
+        remaining -= len as u64;
+    }
+    file.sync_all()
+}
+
+fn shred_file(path: &Path) -> io::Result<()> {
+    // Locked notes are read-only; they're being deleted anyway
+    let mut permissions = fs::metadata(path)?.permissions();
+    if permissions.readonly() {
+        #[allow(clippy::permissions_set_readonly_false)]
+        permissions.set_readonly(false);
+        fs::set_permissions(path, permissions)?;
+    }
+    overwrite(path)?;
+    fs::remove_file(path)
+}
+
+fn shred_dir(dir: &Path) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        // Symlinks are removed, never followed
+        if entry.file_type()?.is_dir() {
+            shred_dir(&path)?;
+        } else if entry.file_type()?.is_file() {
+            shred_file(&path)?;
+        } else {
+            fs::remove_file(&path)?;
+        }
+    }
+    fs::remove_dir(dir)
+}
+
+// Deletes a file or folder; in secure mode file contents are overwritten first wherever
+// that means anything
+pub fn remove(path: &Path, secure: bool) -> Result<(), String> {
+    let file_type = fs::symlink_metadata(path)
+        .map_err(|e| i18n::tf("error.delete_file", &[&e]))?
+        .file_type();
+    let is_dir = file_type.is_dir();
+    // A top-level symlink is unlinked like a nested one; its target may sit outside the
+    // workspace and must not be overwritten
+    let shred = secure && !file_type.is_symlink() && overwrite_reaches_disk(path);
+    let result = match (is_dir, shred) {
+        (true, true) => shred_dir(path),
+        (true, false) => fs::remove_dir_all(path),
+        (false, true) if file_type.is_file() => shred_file(path),
+        (false, _) => fs::remove_file(path),
+    };
+
+    result.map_err(|e| {
+        if is_dir {
+            i18n::tf("error.delete_folder", &[&e])
+        } else {
+            i18n::tf("error.delete_file", &[&e])
+        }
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+    use std::path::PathBuf;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "marky-secure-delete-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn symlink_target_survives_secure_delete() {
+        let outside = scratch_dir("outside");
+        let workspace = scratch_dir("workspace");
+        let target = outside.join("secret.md");
+        fs::write(&target, "keep me").unwrap();
+        let link = workspace.join("link.md");
+        symlink(&target, &link).unwrap();
+
+        remove(&link, true).unwrap();
+
+        assert!(fs::symlink_metadata(&link).is_err());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "keep me");
+
+        let dir_link = workspace.join("linked-folder");
+        symlink(&outside, &dir_link).unwrap();
+
+        remove(&dir_link, true).unwrap();
+
+        assert!(fs::symlink_metadata(&dir_link).is_err());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "keep me");
+
+        let _ = fs::remove_dir_all(&outside);
+        let _ = fs::remove_dir_all(&workspace);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::storage;
+use crate::vault;
+use crate::watcher::WatcherState;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, State, Window};
+
+const SESSION_FILE: &str = "session.json";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CursorPosition {
+    pub line: u32,
+    pub column: u32,
+    pub scroll_top: f64,
+}
+
+// Paths are absolute on the wire and stored relative to the workspace root
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub open_notes: Vec<String>,
+    pub active_note: Option<String>,
+    pub cursors: HashMap<String, CursorPosition>,
+    pub expanded_folders: Vec<String>,
+    pub saved_at: Option<i64>,
+}
+
+fn to_relative(root: &Path, path: &str) -> String {
+    Path::new(path)
+        .strip_prefix(root)
+        .map(|relative| relative.to_string_lossy().to_string())
+        .unwrap_or_else(|_| path.to_string())
+}
+
+fn to_absolute(root: &Path, path: &str) -> PathBuf {
+    root.join(path)
+}
+
+fn resolve_root(
+    folder_path: Option<String>,
+    window: &Window,
+    watcher_state: &WatcherState,
+) -> Result<PathBuf, String> {
+    match folder_path {
+        Some(folder_path) => Ok(PathBuf::from(folder_path)),
+        None => watcher_state
+            .active_root(window.label())
+            .ok_or_else(|| "No workspace is open".to_string()),
+    }
+}
+
+// `folder_path` pins the workspace so a save racing a workspace switch can't land in the wrong one
+#[tauri::command]
+pub fn save_session(
+    state: Session,
+    folder_path: Option<String>,
+    app: AppHandle,
+    window: Window,
+    watcher_state: State<WatcherState>,
+) -> Result<(), String> {
+    let root = resolve_root(folder_path, &window, &watcher_state)?;
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+    // A saved session would spell out a vault's note names in plaintext
+    if vault::is_vault_root(&root) {
+        return Ok(());
+    }
+
+    let session = Session {
+        open_notes: state
+            .open_notes
+            .iter()
+            .map(|path| to_relative(&root, path))
+            .collect(),
+        active_note: state.active_note.map(|path| to_relative(&root, &path)),
+        cursors: state
+            .cursors
+            .into_iter()
+            .map(|(path, cursor)| (to_relative(&root, &path), cursor))
+            .collect(),
+        expanded_folders: state
+            .expanded_folders
+            .iter()
+            .map(|path| to_relative(&root, path))
+            .collect(),
+        saved_at: Some(Utc::now().timestamp_millis()),
+    };
+
+    storage::write_json(
+        &storage::workspace_data_dir(&app, &root)?.join(SESSION_FILE),
+        &session,
+    )
+}
+
+// Notes and folders that no longer exist are dropped so the frontend never opens dead tabs
+#[tauri::command]
+pub fn load_session(
+    folder_path: Option<String>,
+    app: AppHandle,
+    window: Window,
+    watcher_state: State<WatcherState>,
+) -> Result<Session, String> {
+    let root = resolve_root(folder_path, &window, &watcher_state)?;
+    let stored: Session =
+        storage::read_json(&storage::workspace_data_dir(&app, &root)?.join(SESSION_FILE));
+
+    let existing_file = |path: &String| {
+        let absolute = to_absolute(&root, path);
+        absolute
+            .is_file()
+            .then(|| absolute.to_string_lossy().to_string())
+    };
+
+    let open_notes: Vec<String> = stored.open_notes.iter().filter_map(existing_file).collect();
+    let active_note = stored
+        .active_note
+        .as_ref()
+        .and_then(existing_file)
+        .filter(|path| open_notes.contains(path))
+        .or_else(|| open_notes.last().cloned());
+
+    Ok(Session {
+        cursors: stored
+            .cursors
+            .into_iter()
+            .filter_map(|(path, cursor)| existing_file(&path).map(|path| (path, cursor)))
+            .collect(),
+        expanded_folders: stored
+            .expanded_folders
+            .iter()
+            .map(|path| to_absolute(&root, path))
+            .filter(|path| path.is_dir())
+            .map(|path| path.to_string_lossy().to_string())
+            .collect(),
+        open_notes,
+        active_note,
+        saved_at: stored.saved_at,
+    })
+}
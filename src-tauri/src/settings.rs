@@ -0,0 +1,528 @@
+use crate::api;
+use crate::badge;
+use crate::custom_menu;
+use crate::email;
+use crate::i18n;
+use crate::index::IndexState;
+use crate::logging;
+use crate::markdown;
+#[cfg(desktop)]
+use crate::menu;
+use crate::safe_mode::SafeModeState;
+#[cfg(target_os = "macos")]
+use crate::spotlight;
+use crate::storage;
+use crate::telemetry;
+#[cfg(desktop)]
+use crate::updater;
+use crate::workspace_config;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+const SETTINGS_FILE: &str = "settings.json";
+const MIN_DEBOUNCE_MS: u64 = 50;
+const MAX_DEBOUNCE_MS: u64 = 10_000;
+// Ports below this need elevated privileges on most systems
+const MIN_API_PORT: u16 = 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub watched_extensions: Vec<String>,
+    // Off, workspaces are only rescanned when the app comes back to the foreground
+    pub watch_files: bool,
+    pub watch_debounce_ms: u64,
+    pub theme_id: String,
+    pub templates_folder: Option<String>,
+    // chrono strftime pattern, relative to the workspace root, without extension
+    pub daily_note_format: String,
+    pub attachment_folder: String,
+    pub archive_folder: String,
+    pub ignore_globs: Vec<String>,
+    pub reopen_last_workspace: bool,
+    // Idle minutes before an unlocked vault locks itself; 0 keeps it unlocked
+    pub vault_auto_lock_minutes: u64,
+    // Overwrite file contents before deleting them
+    pub secure_delete: bool,
+    pub delete_confirmation: DeleteConfirmationSettings,
+    // Index notes of registered workspaces in Spotlight (macOS)
+    pub spotlight: bool,
+    // "stable" or "beta"
+    pub update_channel: String,
+    // One of logging::LEVELS
+    pub log_level: String,
+    // Language for menus and backend messages; one of i18n::LOCALES
+    pub locale: String,
+    pub notifications: NotificationSettings,
+    // ICS files or http(s)/webcal URLs read for today's meetings
+    pub calendar_subscriptions: Vec<String>,
+    pub email: EmailSettings,
+    pub api: ApiSettings,
+    // Abbreviations the editor expands on Tab
+    pub snippets: Vec<Snippet>,
+    // Entries for the Custom menu on desktop
+    pub custom_menu: Vec<CustomMenuEntry>,
+    pub link_previews: LinkPreviewSettings,
+    // Opt-in usage counts and timings; see telemetry::TelemetryReport
+    pub telemetry: bool,
+}
+
+// `key` is what's typed, like `;sig`; `text` may use the placeholders
+// snippets::render understands
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Snippet {
+    pub key: String,
+    pub text: String,
+    pub description: String,
+}
+
+// `action` is one of custom_menu::ACTIONS; `target` is the note (a
+// workspace-relative path or a note name), the automation's name or id, or
+// the URL
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomMenuEntry {
+    pub label: String,
+    pub action: String,
+    pub target: String,
+}
+
+// How `email_note` delivers; the SMTP password lives in the credential store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmailSettings {
+    // One of email::METHODS
+    pub method: String,
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    // One of email::SECURITY
+    pub smtp_security: String,
+    pub smtp_username: String,
+    pub from_address: String,
+}
+
+impl Default for EmailSettings {
+    fn default() -> Self {
+        EmailSettings {
+            method: "client".to_string(),
+            smtp_host: String::new(),
+            smtp_port: 587,
+            smtp_security: "starttls".to_string(),
+            smtp_username: String::new(),
+            from_address: String::new(),
+        }
+    }
+}
+
+// The local HTTP API; its token lives in the credential store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiSettings {
+    pub enabled: bool,
+    pub port: u16,
+    // The MCP endpoint for AI assistants, on the same server and token
+    pub mcp_enabled: bool,
+    pub mcp_allow_append: bool,
+    // `POST /clip` for the browser clipper, with its own token
+    pub clipper_enabled: bool,
+    // Workspace-relative folder new clips are saved in
+    pub clipper_folder: String,
+    // `GET /tasks.ics` for calendar apps, with its own token
+    pub tasks_feed_enabled: bool,
+}
+
+impl Default for ApiSettings {
+    fn default() -> Self {
+        ApiSettings {
+            enabled: false,
+            port: 27124,
+            mcp_enabled: false,
+            mcp_allow_append: false,
+            clipper_enabled: false,
+            clipper_folder: "Clippings".to_string(),
+            tasks_feed_enabled: false,
+        }
+    }
+}
+
+// Folders bigger than either limit are only deleted with a token from
+// `preview_delete`; 0 turns a limit off. Workspace roots always need one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeleteConfirmationSettings {
+    pub files: u64,
+    pub megabytes: u64,
+}
+
+impl Default for DeleteConfirmationSettings {
+    fn default() -> Self {
+        DeleteConfirmationSettings {
+            files: 100,
+            megabytes: 100,
+        }
+    }
+}
+
+// Which native notifications may be shown while Marky is in the background
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub external_changes: bool,
+    pub operations: bool,
+    pub sync_conflicts: bool,
+    pub reminders: bool,
+    // The dock badge or taskbar overlay for due tasks and unsaved notes
+    pub badge: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        NotificationSettings {
+            external_changes: true,
+            operations: true,
+            sync_conflicts: true,
+            reminders: true,
+            badge: true,
+        }
+    }
+}
+
+// Cards and hover previews for web links in the preview
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LinkPreviewSettings {
+    pub enabled: bool,
+    // Only show what's already cached, never fetching
+    pub offline: bool,
+    // Hosts previews may be fetched from, subdomains included; empty allows any
+    pub allowlist: Vec<String>,
+}
+
+impl Default for LinkPreviewSettings {
+    fn default() -> Self {
+        LinkPreviewSettings {
+            enabled: true,
+            offline: false,
+            allowlist: Vec::new(),
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            watched_extensions: markdown::DEFAULT_EXTENSIONS
+                .iter()
+                .map(|ext| ext.to_string())
+                .collect(),
+            // Mobile OSes suspend background threads and don't report changes
+            // made through document providers, so there's little to watch
+            watch_files: cfg!(desktop),
+            watch_debounce_ms: 500,
+            theme_id: "midnight".to_string(),
+            templates_folder: None,
+            daily_note_format: "%Y-%m-%d".to_string(),
+            attachment_folder: "attachments".to_string(),
+            archive_folder: "Archive".to_string(),
+            ignore_globs: Vec::new(),
+            reopen_last_workspace: true,
+            vault_auto_lock_minutes: 15,
+            secure_delete: false,
+            delete_confirmation: DeleteConfirmationSettings::default(),
+            spotlight: true,
+            update_channel: "stable".to_string(),
+            log_level: "info".to_string(),
+            locale: "system".to_string(),
+            notifications: NotificationSettings::default(),
+            calendar_subscriptions: Vec::new(),
+            email: EmailSettings::default(),
+            api: ApiSettings::default(),
+            snippets: Vec::new(),
+            custom_menu: Vec::new(),
+            link_previews: LinkPreviewSettings::default(),
+            telemetry: false,
+        }
+    }
+}
+
+impl Settings {
+    // Keeps hand-edited or partially invalid files usable instead of rejecting them
+    fn normalized(mut self) -> Settings {
+        let mut extensions: Vec<String> = Vec::new();
+        for ext in &self.watched_extensions {
+            let ext = ext.trim().trim_start_matches('.').to_lowercase();
+            if !ext.is_empty() && !extensions.contains(&ext) {
+                extensions.push(ext);
+            }
+        }
+        self.watched_extensions = if extensions.is_empty() {
+            Settings::default().watched_extensions
+        } else {
+            extensions
+        };
+
+        self.watch_debounce_ms = self
+            .watch_debounce_ms
+            .clamp(MIN_DEBOUNCE_MS, MAX_DEBOUNCE_MS);
+
+        self.templates_folder = self
+            .templates_folder
+            .map(|folder| folder.trim().to_string())
+            .filter(|folder| !folder.is_empty());
+
+        let defaults = Settings::default();
+        if self.daily_note_format.trim().is_empty()
+            || !workspace_config::is_valid_date_format(&self.daily_note_format)
+        {
+            self.daily_note_format = defaults.daily_note_format;
+        }
+        if self.attachment_folder.trim().is_empty() {
+            self.attachment_folder = defaults.attachment_folder;
+        }
+        if self.archive_folder.trim().is_empty() {
+            self.archive_folder = defaults.archive_folder;
+        }
+        // Mobile builds update through the app stores, so the channel is never read there
+        #[cfg(desktop)]
+        {
+            self.update_channel = self.update_channel.trim().to_lowercase();
+            if !updater::CHANNELS.contains(&self.update_channel.as_str()) {
+                self.update_channel = defaults.update_channel;
+            }
+        }
+        self.log_level = self.log_level.trim().to_lowercase();
+        if !logging::LEVELS.contains(&self.log_level.as_str()) {
+            self.log_level = defaults.log_level;
+        }
+        self.locale = self.locale.trim().to_lowercase();
+        if !i18n::LOCALES.contains(&self.locale.as_str()) {
+            self.locale = defaults.locale;
+        }
+        self.ignore_globs
+            .retain(|pattern| !pattern.trim().is_empty());
+        let mut subscriptions: Vec<String> = Vec::new();
+        for source in &self.calendar_subscriptions {
+            let source = source.trim().to_string();
+            if !source.is_empty() && !subscriptions.contains(&source) {
+                subscriptions.push(source);
+            }
+        }
+        self.calendar_subscriptions = subscriptions;
+        // Keys are matched against the text before the cursor, so they can't hold spaces
+        let mut snippets: Vec<Snippet> = Vec::new();
+        for mut snippet in self.snippets {
+            snippet.key = snippet.key.trim().to_string();
+            if !snippet.key.is_empty()
+                && !snippet.key.contains(char::is_whitespace)
+                && !snippets.iter().any(|existing| existing.key == snippet.key)
+            {
+                snippets.push(snippet);
+            }
+        }
+        self.snippets = snippets;
+        for entry in &mut self.custom_menu {
+            entry.label = entry.label.trim().to_string();
+            entry.action = entry.action.trim().to_lowercase();
+            entry.target = entry.target.trim().to_string();
+        }
+        self.custom_menu.retain(|entry| {
+            !entry.label.is_empty()
+                && custom_menu::ACTIONS.contains(&entry.action.as_str())
+                && !entry.target.is_empty()
+                && (entry.action != "open_url" || custom_menu::valid_url(&entry.target))
+        });
+        // Hosts, however they were pasted in
+        let mut hosts: Vec<String> = Vec::new();
+        for entry in &self.link_previews.allowlist {
+            let entry = entry.trim().to_lowercase();
+            let entry = entry
+                .split_once("://")
+                .map_or(entry.as_str(), |(_, rest)| rest);
+            let host = entry.split(['/', '?', '#']).next().unwrap_or_default();
+            let host = host
+                .trim_start_matches("www.")
+                .trim_matches('.')
+                .to_string();
+            if !host.is_empty() && !hosts.contains(&host) {
+                hosts.push(host);
+            }
+        }
+        self.link_previews.allowlist = hosts;
+
+        let email_defaults = EmailSettings::default();
+        self.email.method = self.email.method.trim().to_lowercase();
+        if !email::METHODS.contains(&self.email.method.as_str()) {
+            self.email.method = email_defaults.method;
+        }
+        self.email.smtp_security = self.email.smtp_security.trim().to_lowercase();
+        if !email::SECURITY.contains(&self.email.smtp_security.as_str()) {
+            self.email.smtp_security = email_defaults.smtp_security;
+        }
+        if self.email.smtp_port == 0 {
+            self.email.smtp_port = email_defaults.smtp_port;
+        }
+        if self.api.port < MIN_API_PORT {
+            self.api.port = ApiSettings::default().port;
+        }
+        self.api.clipper_folder = self
+            .api
+            .clipper_folder
+            .trim()
+            .trim_matches(['/', '\\'])
+            .to_string();
+        if self.api.clipper_folder.is_empty() {
+            self.api.clipper_folder = ApiSettings::default().clipper_folder;
+        }
+
+        self
+    }
+}
+
+pub struct SettingsState {
+    settings: Mutex<Settings>,
+}
+
+impl SettingsState {
+    pub fn load(app: &AppHandle) -> SettingsState {
+        let settings: Settings = settings_path(app)
+            .map(|path| storage::read_json(&path))
+            .unwrap_or_default();
+
+        SettingsState {
+            settings: Mutex::new(settings.normalized()),
+        }
+    }
+
+    pub fn current(&self) -> Settings {
+        self.settings
+            .lock()
+            .map(|settings| settings.clone())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct SettingsChangedEvent {
+    key: String,
+    settings: Settings,
+}
+
+pub fn settings_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(storage::app_config_dir(app)?.join(SETTINGS_FILE))
+}
+
+// Dotted keys address nested sections, e.g. `notifications.mute_backup`
+fn set_value_at(target: &mut Value, key: &str, value: Value) -> Result<(), String> {
+    let unknown = || format!("Unknown setting: {}", key);
+    let mut current = target;
+    let mut parts = key.split('.').peekable();
+
+    while let Some(part) = parts.next() {
+        let object = current.as_object_mut().ok_or_else(unknown)?;
+        if parts.peek().is_none() {
+            if !object.contains_key(part) {
+                return Err(unknown());
+            }
+            object.insert(part.to_string(), value);
+            return Ok(());
+        }
+        current = object.get_mut(part).ok_or_else(unknown)?;
+    }
+
+    Err(unknown())
+}
+
+#[tauri::command]
+pub fn get_settings(settings_state: State<SettingsState>) -> Settings {
+    settings_state.current()
+}
+
+// Applies `edit` to the settings as JSON, saves the result, and lets the rest
+// of the app know; `key` names what changed in the `settings-changed` event
+pub fn update(
+    app: &AppHandle,
+    settings_state: &SettingsState,
+    key: String,
+    edit: impl FnOnce(&mut Value) -> Result<(), String>,
+) -> Result<Settings, String> {
+    let mut guard = settings_state
+        .settings
+        .lock()
+        .map_err(|e| format!("Failed to lock settings: {}", e))?;
+
+    let mut raw = serde_json::to_value(&*guard)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    edit(&mut raw)?;
+
+    let updated: Settings = serde_json::from_value(raw)
+        .map_err(|e| format!("Invalid value for setting {}: {}", key, e))?;
+    let updated = updated.normalized();
+    let locale_changed = updated.locale != guard.locale;
+    let badge_changed = updated.notifications.badge != guard.notifications.badge;
+    let telemetry_stopped = guard.telemetry && !updated.telemetry;
+    #[cfg(target_os = "macos")]
+    let spotlight_changed = updated.spotlight != guard.spotlight;
+    #[cfg(desktop)]
+    let custom_menu_changed = updated.custom_menu != guard.custom_menu;
+
+    storage::write_json(&settings_path(app)?, &updated)?;
+
+    *guard = updated.clone();
+    drop(guard);
+
+    app.state::<IndexState>().set_settings(updated.clone());
+    logging::apply_level(app, &updated.log_level);
+    // Safe mode keeps the local API off no matter what gets saved
+    if !app.state::<SafeModeState>().is_active() {
+        api::apply(app, &updated.api);
+    }
+    if locale_changed {
+        i18n::set_locale(&updated.locale);
+    }
+    if badge_changed {
+        badge::refresh(app);
+    }
+    if telemetry_stopped {
+        telemetry::clear(app)?;
+    }
+    #[cfg(target_os = "macos")]
+    if spotlight_changed {
+        if updated.spotlight {
+            spotlight::index_registered(app);
+        } else {
+            spotlight::clear();
+        }
+    }
+    #[cfg(desktop)]
+    if locale_changed || custom_menu_changed {
+        menu::rebuild(app);
+    }
+
+    app.emit(
+        "settings-changed",
+        SettingsChangedEvent {
+            key,
+            settings: updated.clone(),
+        },
+    )
+    .map_err(|e| format!("Failed to emit event: {}", e))?;
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub fn set_setting(
+    key: String,
+    value: Value,
+    app: AppHandle,
+    settings_state: State<SettingsState>,
+) -> Result<Settings, String> {
+    let path = key.clone();
+    update(&app, &settings_state, key, |raw| {
+        set_value_at(raw, &path, value)
+    })
+}
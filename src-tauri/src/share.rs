@@ -0,0 +1,209 @@
+use crate::markdown;
+use crate::storage;
+use crate::vault;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, WebviewWindow};
+
+const SHARE_DIR: &str = "share";
+const FORMATS: [&str; 3] = ["markdown", "html", "pdf"];
+
+// File names can't hold path separators and the like, which note titles may
+pub fn export_name(title: &str, ext: &str) -> String {
+    let title: String = title
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    let title = title.trim();
+    let title = if title.is_empty() { "Note" } else { title };
+    format!("{}.{}", title, ext)
+}
+
+// Each share gets its own folder so the receiving app sees the note's own name
+fn write_export(
+    app: &AppHandle,
+    note: &Path,
+    ext: &str,
+    contents: &[u8],
+) -> Result<PathBuf, String> {
+    let dir = storage::app_data_dir(app)?
+        .join(SHARE_DIR)
+        .join(storage::path_key(note));
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create share folder: {}", e))?;
+    let path = dir.join(export_name(&markdown::note_title(note), ext));
+    fs::write(&path, contents).map_err(|e| format!("Failed to write shared file: {}", e))?;
+    Ok(path)
+}
+
+// Exports only need to outlive the share or drag itself
+pub fn clear_exports(app: &AppHandle) {
+    let Ok(dir) = storage::app_data_dir(app).map(|dir| dir.join(SHARE_DIR)) else {
+        return;
+    };
+    if dir.exists() {
+        if let Err(e) = fs::remove_dir_all(&dir) {
+            tracing::warn!("Failed to clear shared exports: {}", e);
+        }
+    }
+}
+
+// The file handed to another app: the note itself (`markdown`) or an export
+// the frontend rendered (`html` text or `pdf` bytes), written under app data
+pub fn export_file(
+    app: &AppHandle,
+    path: &str,
+    format: &str,
+    contents: Option<Vec<u8>>,
+) -> Result<PathBuf, String> {
+    let format = format.trim().to_lowercase();
+    if !FORMATS.contains(&format.as_str()) {
+        return Err(format!("Unknown export format: {}", format));
+    }
+    let note = PathBuf::from(path);
+    // The receiving app would get a plaintext copy from outside the vault
+    if vault::vault_root(&note).is_some() {
+        return Err("Notes in an encrypted vault can't leave it as plain files".to_string());
+    }
+    if !note.is_file() {
+        return Err("Note does not exist".to_string());
+    }
+
+    match (format.as_str(), contents) {
+        ("markdown", _) => Ok(note),
+        (ext, Some(contents)) => write_export(app, &note, ext, &contents),
+        (_, None) => Err("Nothing to export".to_string()),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn present(app: &AppHandle, window: &WebviewWindow, file: PathBuf) -> Result<(), String> {
+    use objc2::rc::Retained;
+    use objc2::runtime::AnyObject;
+    use objc2::AllocAnyThread;
+    use objc2_app_kit::{NSSharingServicePicker, NSWindow};
+    use objc2_foundation::{NSArray, NSPoint, NSRect, NSRectEdge, NSSize, NSString, NSURL};
+    use std::cell::RefCell;
+
+    thread_local! {
+        // AppKit doesn't keep the picker alive while it's on screen
+        static PICKER: RefCell<Option<Retained<NSSharingServicePicker>>> = const { RefCell::new(None) };
+    }
+
+    // The raw pointer isn't Send, so it's looked up again on the main thread
+    let window = window.clone();
+    app.run_on_main_thread(move || {
+        let Ok(ns_window) = window.ns_window() else {
+            tracing::error!("Failed to share note: window has no native handle");
+            return;
+        };
+        // SAFETY: Tauri hands out a valid NSWindow pointer for as long as the window is open
+        let ns_window: &NSWindow = unsafe { &*ns_window.cast() };
+        let Some(view) = ns_window.contentView() else {
+            return;
+        };
+
+        let url = NSURL::fileURLWithPath(&NSString::from_str(&file.to_string_lossy()));
+        let item: Retained<AnyObject> = Retained::into_super(Retained::into_super(url));
+        let items = NSArray::from_retained_slice(&[item]);
+        // SAFETY: file URLs conform to NSPasteboardWriting, as the picker requires
+        let picker = unsafe {
+            NSSharingServicePicker::initWithItems(NSSharingServicePicker::alloc(), &items)
+        };
+
+        // Anchored under the title bar, centered, where the toolbar would be
+        let bounds = view.bounds();
+        let anchor = NSRect::new(
+            NSPoint::new(bounds.size.width / 2.0, bounds.size.height - 8.0),
+            NSSize::new(1.0, 1.0),
+        );
+        picker.showRelativeToRect_ofView_preferredEdge(anchor, &view, NSRectEdge::MinY);
+        PICKER.with(|current| *current.borrow_mut() = Some(picker));
+    })
+    .map_err(|e| format!("Failed to open the share sheet: {}", e))
+}
+
+#[cfg(target_os = "windows")]
+fn present(app: &AppHandle, window: &WebviewWindow, file: PathBuf) -> Result<(), String> {
+    use std::sync::Mutex;
+    use windows::core::{factory, AgileReference, Interface, HSTRING};
+    use windows::ApplicationModel::DataTransfer::{DataRequestedEventArgs, DataTransferManager};
+    use windows::Foundation::TypedEventHandler;
+    use windows::Storage::{IStorageItem, StorageFile};
+    use windows::Win32::UI::Shell::IDataTransferManagerInterop;
+    use windows_collections::IIterable;
+
+    // A window keeps one data-requested handler; the previous share's is replaced
+    static HANDLER_TOKEN: Mutex<Option<i64>> = Mutex::new(None);
+
+    let show = move |window: &WebviewWindow| -> windows::core::Result<()> {
+        let hwnd = window.hwnd().map_err(|_| windows::core::Error::empty())?;
+        let item: IStorageItem =
+            StorageFile::GetFileFromPathAsync(&HSTRING::from(file.as_os_str()))?
+                .join()?
+                .cast()?;
+        // The handler may run on another thread than the one that fetched the file
+        let item = AgileReference::new(&item)?;
+        let title = HSTRING::from(
+            file.file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        );
+
+        let interop = factory::<DataTransferManager, IDataTransferManagerInterop>()?;
+        // SAFETY: `hwnd` belongs to a live top-level window owned by this process
+        let manager: DataTransferManager = unsafe { interop.GetForWindow(hwnd) }?;
+
+        let handler = TypedEventHandler::<DataTransferManager, DataRequestedEventArgs>::new(
+            move |_, args| {
+                if let Some(args) = args.as_ref() {
+                    let data = args.Request()?.Data()?;
+                    data.Properties()?.SetTitle(&title)?;
+                    data.SetStorageItemsReadOnly(&IIterable::<IStorageItem>::from(vec![Some(
+                        item.resolve()?,
+                    )]))?;
+                }
+                Ok(())
+            },
+        );
+        let token = manager.DataRequested(&handler)?;
+        if let Ok(mut previous) = HANDLER_TOKEN.lock() {
+            if let Some(previous) = previous.replace(token) {
+                let _ = manager.RemoveDataRequested(previous);
+            }
+        }
+
+        // SAFETY: as above
+        unsafe { interop.ShowShareUIForWindow(hwnd) }
+    };
+
+    let window = window.clone();
+    app.run_on_main_thread(move || {
+        if let Err(e) = show(&window) {
+            tracing::error!("Failed to open the share sheet: {}", e);
+        }
+    })
+    .map_err(|e| format!("Failed to open the share sheet: {}", e))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn present(_app: &AppHandle, _window: &WebviewWindow, _file: PathBuf) -> Result<(), String> {
+    Err("Sharing isn't available on this platform; export the note instead".to_string())
+}
+
+// Shares the note itself (`markdown`) or an export the frontend rendered
+// (`html` text or `pdf` bytes) through the system share sheet
+#[tauri::command]
+pub fn share_note(
+    path: String,
+    format: String,
+    contents: Option<Vec<u8>>,
+    app: AppHandle,
+    window: WebviewWindow,
+) -> Result<(), String> {
+    let file = export_file(&app, &path, &format, contents)?;
+    present(&app, &window, file)
+}
@@ -0,0 +1,255 @@
+use crate::index::IndexState;
+use crate::query::{self, QueryResult};
+use crate::storage;
+use crate::watcher::WatcherState;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+const SMART_FOLDERS_FILE: &str = "smart-folders.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmartFolder {
+    id: String,
+    name: String,
+    query: String,
+    created_at: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SmartFolderEntry {
+    #[serde(flatten)]
+    folder: SmartFolder,
+    // None when the saved query no longer parses
+    count: Option<usize>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SmartFoldersChanged {
+    workspace: String,
+    ids: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct SmartFolderState {
+    // Serializes read-modify-write cycles on the smart folders file
+    lock: Mutex<()>,
+    // Match count and listed note paths of each folder when last read, so
+    // watcher batches only announce folders whose contents actually changed
+    members: Mutex<HashMap<PathBuf, HashMap<String, Members>>>,
+}
+
+impl SmartFolderState {
+    fn remember(&self, root: &Path, id: &str, result: &QueryResult) {
+        if let Ok(mut members) = self.members.lock() {
+            members
+                .entry(root.to_path_buf())
+                .or_default()
+                .insert(id.to_string(), members_of(result));
+        }
+    }
+}
+
+type Members = (usize, Vec<String>);
+
+fn members_of(result: &QueryResult) -> Members {
+    (
+        result.total,
+        result.rows.iter().map(|row| row.path.clone()).collect(),
+    )
+}
+
+fn folders_path(app: &AppHandle, root: &Path) -> Result<PathBuf, String> {
+    Ok(storage::workspace_data_dir(app, root)?.join(SMART_FOLDERS_FILE))
+}
+
+fn load(app: &AppHandle, root: &Path) -> Result<Vec<SmartFolder>, String> {
+    Ok(storage::read_json(&folders_path(app, root)?))
+}
+
+fn workspace_root(folder_path: &str) -> Result<PathBuf, String> {
+    let root = PathBuf::from(folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+    Ok(root)
+}
+
+// Re-runs every smart folder after the watcher updated the index and tells the
+// workspace's windows which ones gained or lost notes
+pub fn refresh(app: &AppHandle, root: &Path) {
+    let Ok(folders) = load(app, root) else {
+        return;
+    };
+    if folders.is_empty() {
+        return;
+    }
+
+    let state = app.state::<SmartFolderState>();
+    let results = app.state::<IndexState>().with_workspace(root, |index| {
+        folders
+            .iter()
+            .filter_map(|folder| {
+                let result = query::evaluate(root, index, &folder.query).ok()?;
+                Some((folder.id.clone(), members_of(&result)))
+            })
+            .collect::<Vec<_>>()
+    });
+    let Ok(results) = results else {
+        return;
+    };
+
+    let changed: Vec<String> = {
+        let Ok(mut members) = state.members.lock() else {
+            return;
+        };
+        let known = members.entry(root.to_path_buf()).or_default();
+        results
+            .into_iter()
+            .filter_map(|(id, members)| {
+                let same = known.get(&id) == Some(&members);
+                known.insert(id.clone(), members);
+                (!same).then_some(id)
+            })
+            .collect()
+    };
+    if changed.is_empty() {
+        return;
+    }
+
+    let payload = SmartFoldersChanged {
+        workspace: root.to_string_lossy().to_string(),
+        ids: changed,
+    };
+    for label in app.state::<WatcherState>().windows_for(root) {
+        let _ = app.emit_to(label.as_str(), "smart-folders-changed", payload.clone());
+    }
+}
+
+#[tauri::command]
+pub async fn list_smart_folders(
+    folder_path: String,
+    app: AppHandle,
+    index_state: State<'_, IndexState>,
+    smart_folder_state: State<'_, SmartFolderState>,
+) -> Result<Vec<SmartFolderEntry>, String> {
+    let root = workspace_root(&folder_path)?;
+    let folders = load(&app, &root)?;
+    index_state.with_workspace(&root, |index| {
+        folders
+            .into_iter()
+            .map(
+                |folder| match query::evaluate(&root, index, &folder.query) {
+                    Ok(result) => {
+                        smart_folder_state.remember(&root, &folder.id, &result);
+                        SmartFolderEntry {
+                            count: Some(result.total),
+                            error: None,
+                            folder,
+                        }
+                    }
+                    Err(e) => SmartFolderEntry {
+                        count: None,
+                        error: Some(e),
+                        folder,
+                    },
+                },
+            )
+            .collect()
+    })
+}
+
+#[tauri::command]
+pub async fn get_smart_folder_contents(
+    folder_path: String,
+    id: String,
+    app: AppHandle,
+    index_state: State<'_, IndexState>,
+    smart_folder_state: State<'_, SmartFolderState>,
+) -> Result<QueryResult, String> {
+    let root = workspace_root(&folder_path)?;
+    let folder = load(&app, &root)?
+        .into_iter()
+        .find(|folder| folder.id == id)
+        .ok_or_else(|| "Smart folder not found".to_string())?;
+    let result = index_state
+        .with_workspace(&root, |index| query::evaluate(&root, index, &folder.query))??;
+    smart_folder_state.remember(&root, &folder.id, &result);
+    Ok(result)
+}
+
+// Creates a smart folder, or updates the one with `id`
+#[tauri::command]
+pub fn save_smart_folder(
+    folder_path: String,
+    id: Option<String>,
+    name: String,
+    query: String,
+    app: AppHandle,
+    smart_folder_state: State<SmartFolderState>,
+) -> Result<SmartFolder, String> {
+    let root = workspace_root(&folder_path)?;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Smart folder name is required".to_string());
+    }
+    let query = query.trim().to_string();
+    query::validate(&query)?;
+
+    let _guard = smart_folder_state
+        .lock
+        .lock()
+        .map_err(|e| format!("Failed to lock smart folders: {}", e))?;
+    let path = folders_path(&app, &root)?;
+    let mut folders: Vec<SmartFolder> = storage::read_json(&path);
+
+    let saved = match id.and_then(|id| folders.iter_mut().find(|folder| folder.id == id)) {
+        Some(existing) => {
+            existing.name = name;
+            existing.query = query;
+            existing.clone()
+        }
+        None => {
+            let created_at = Utc::now().timestamp_millis();
+            let folder = SmartFolder {
+                id: format!("smart-{}", created_at),
+                name,
+                query,
+                created_at,
+            };
+            folders.push(folder.clone());
+            folder
+        }
+    };
+    storage::write_json(&path, &folders)?;
+    Ok(saved)
+}
+
+#[tauri::command]
+pub fn delete_smart_folder(
+    folder_path: String,
+    id: String,
+    app: AppHandle,
+    smart_folder_state: State<SmartFolderState>,
+) -> Result<(), String> {
+    let root = workspace_root(&folder_path)?;
+    let _guard = smart_folder_state
+        .lock
+        .lock()
+        .map_err(|e| format!("Failed to lock smart folders: {}", e))?;
+    let path = folders_path(&app, &root)?;
+    let mut folders: Vec<SmartFolder> = storage::read_json(&path);
+    folders.retain(|folder| folder.id != id);
+    storage::write_json(&path, &folders)?;
+
+    if let Ok(mut members) = smart_folder_state.members.lock() {
+        if let Some(known) = members.get_mut(&root) {
+            known.remove(&id);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use crate::markdown;
+use crate::settings::{SettingsState, Snippet};
+use crate::workspace_config;
+use chrono::Local;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::State;
+
+const CURSOR: &str = "cursor";
+
+#[derive(Debug, Serialize)]
+pub struct SnippetExpansion {
+    text: String,
+    // Where `{{cursor}}` was, in UTF-16 units as the editor counts them
+    cursor: Option<usize>,
+}
+
+// The value of one `{{placeholder}}`; None leaves it as written
+fn placeholder(
+    name: &str,
+    path: Option<&Path>,
+    variables: &HashMap<String, String>,
+) -> Option<String> {
+    let now = Local::now();
+    if let Some(format) = name.strip_prefix("date:") {
+        return workspace_config::is_valid_date_format(format)
+            .then(|| now.format(format).to_string());
+    }
+    match name {
+        "date" => Some(now.format("%Y-%m-%d").to_string()),
+        "time" => Some(now.format("%H:%M").to_string()),
+        "datetime" => Some(now.format("%Y-%m-%d %H:%M").to_string()),
+        "weekday" => Some(now.format("%A").to_string()),
+        "title" => Some(path.map(markdown::note_title).unwrap_or_default()),
+        _ => variables.get(name).cloned(),
+    }
+}
+
+// Fills placeholders in one pass, so a value that itself contains `{{...}}`
+// (a selection, say) is inserted as is. Besides the dates and `{{title}}`,
+// `{{name}}` takes `variables[name]`, and `{{cursor}}` marks where the
+// cursor goes.
+fn render(
+    template: &str,
+    path: Option<&Path>,
+    variables: &HashMap<String, String>,
+) -> SnippetExpansion {
+    let mut text = String::with_capacity(template.len());
+    let mut cursor = None;
+    let mut rest = template;
+    while let Some(open) = rest.find("{{") {
+        let Some(close) = rest[open + 2..].find("}}").map(|idx| open + 2 + idx) else {
+            break;
+        };
+        text.push_str(&rest[..open]);
+        let name = rest[open + 2..close].trim();
+        if name == CURSOR {
+            cursor.get_or_insert(text.encode_utf16().count());
+        } else {
+            match placeholder(name, path, variables) {
+                Some(value) => text.push_str(&value),
+                None => text.push_str(&rest[open..close + 2]),
+            }
+        }
+        rest = &rest[close + 2..];
+    }
+    text.push_str(rest);
+    SnippetExpansion { text, cursor }
+}
+
+#[tauri::command]
+pub fn list_snippets(settings_state: State<SettingsState>) -> Vec<Snippet> {
+    settings_state.current().snippets
+}
+
+// The text `key` expands to in the note at `path` (for `{{title}}`), with
+// editor-supplied `variables` such as the current selection
+#[tauri::command]
+pub fn expand_snippet(
+    key: String,
+    path: Option<String>,
+    variables: Option<HashMap<String, String>>,
+    settings_state: State<SettingsState>,
+) -> Result<SnippetExpansion, String> {
+    let snippet = settings_state
+        .current()
+        .snippets
+        .into_iter()
+        .find(|snippet| snippet.key == key)
+        .ok_or_else(|| format!("Unknown snippet: {}", key))?;
+    Ok(render(
+        &snippet.text,
+        path.as_deref().map(Path::new),
+        &variables.unwrap_or_default(),
+    ))
+}
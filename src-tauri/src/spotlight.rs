@@ -0,0 +1,319 @@
+use crate::encryption;
+use crate::index;
+use crate::markdown;
+use crate::settings::SettingsState;
+use crate::storage;
+use crate::vault;
+use crate::windows;
+use crate::workspace_config::NoteFilter;
+use crate::workspaces;
+use block2::RcBlock;
+use objc2::rc::{Allocated, Retained};
+use objc2::runtime::{AnyClass, AnyObject, Bool, Imp, Sel};
+use objc2::{msg_send, sel};
+use objc2_foundation::{NSArray, NSError, NSString};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tauri::{AppHandle, Manager};
+
+// What Spotlight hands back when a result is picked, and what `marky://`
+// links opened from other apps look like
+const LINK_PREFIX: &str = "marky://open?path=";
+// Spotlight only needs enough of a note to match and preview it
+const MAX_TEXT_BYTES: usize = 20_000;
+// The Markdown UTI, so results are labelled as documents of that kind
+const CONTENT_TYPE: &str = "net.daringfireball.markdown";
+// The user-activity key CoreSpotlight puts a picked item's identifier under
+const ACTIVITY_IDENTIFIER_KEY: &str = "kCSSearchableItemActivityIdentifier";
+const ACTIVITY_TYPE: &str = "com.apple.corespotlightitem";
+
+#[link(name = "CoreSpotlight", kind = "framework")]
+extern "C" {}
+
+// One note as Spotlight sees it. Items are grouped by workspace (the domain),
+// so a workspace can be dropped or rebuilt in one call.
+struct SpotlightNote {
+    link: String,
+    title: String,
+    tags: Vec<String>,
+    text: String,
+}
+
+fn encode_path(path: &str) -> String {
+    path.bytes().fold(String::new(), |mut encoded, byte| {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+        encoded
+    })
+}
+
+fn note_link(path: &Path) -> String {
+    format!("{}{}", LINK_PREFIX, encode_path(&path.to_string_lossy()))
+}
+
+fn link_path(link: &str) -> Option<PathBuf> {
+    let encoded = link.strip_prefix(LINK_PREFIX)?.split('&').next()?;
+    Some(PathBuf::from(markdown::percent_decode(encoded)))
+}
+
+// Encrypted notes stay out of the system index
+fn read_note(path: &Path) -> Option<SpotlightNote> {
+    let content = fs::read_to_string(path).ok()?;
+    if encryption::is_encrypted(&content) {
+        return None;
+    }
+    let body = markdown::body(&content).trim();
+    let mut end = body.len().min(MAX_TEXT_BYTES);
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    Some(SpotlightNote {
+        link: note_link(path),
+        title: markdown::note_title(path),
+        tags: markdown::extract_tags(&content),
+        text: body[..end].to_string(),
+    })
+}
+
+fn enabled(app: &AppHandle) -> bool {
+    app.state::<SettingsState>().current().spotlight
+}
+
+fn strings(values: &[String]) -> Retained<NSArray<NSString>> {
+    let values: Vec<Retained<NSString>> = values.iter().map(|v| NSString::from_str(v)).collect();
+    NSArray::from_retained_slice(&values)
+}
+
+fn searchable_item(domain: &str, note: &SpotlightNote) -> Option<Retained<AnyObject>> {
+    let attributes_class = AnyClass::get(c"CSSearchableItemAttributeSet")?;
+    let item_class = AnyClass::get(c"CSSearchableItem")?;
+    let title = NSString::from_str(&note.title);
+    // SAFETY: these are CoreSpotlight's documented initializers and setters,
+    // called with the argument types their headers declare
+    unsafe {
+        let attributes: Allocated<AnyObject> = msg_send![attributes_class, alloc];
+        let attributes: Retained<AnyObject> = msg_send![
+            attributes,
+            initWithItemContentType: &*NSString::from_str(CONTENT_TYPE)
+        ];
+        let _: () = msg_send![&attributes, setTitle: &*title];
+        let _: () = msg_send![&attributes, setDisplayName: &*title];
+        let _: () = msg_send![&attributes, setTextContent: &*NSString::from_str(&note.text)];
+        let _: () = msg_send![&attributes, setKeywords: &*strings(&note.tags)];
+        let item: Allocated<AnyObject> = msg_send![item_class, alloc];
+        let item: Retained<AnyObject> = msg_send![
+            item,
+            initWithUniqueIdentifier: &*NSString::from_str(&note.link),
+            domainIdentifier: &*NSString::from_str(domain),
+            attributeSet: &*attributes
+        ];
+        Some(item)
+    }
+}
+
+fn default_index() -> Option<Retained<AnyObject>> {
+    let index_class = AnyClass::get(c"CSSearchableIndex")?;
+    // SAFETY: `defaultSearchableIndex` takes no arguments and returns the shared index
+    unsafe { msg_send![index_class, defaultSearchableIndex] }
+}
+
+fn log_error(action: &'static str) -> RcBlock<dyn Fn(*mut NSError)> {
+    RcBlock::new(move |error: *mut NSError| {
+        // SAFETY: CoreSpotlight passes either nil or a valid NSError
+        if let Some(error) = unsafe { error.as_ref() } {
+            tracing::warn!(
+                "Failed to {} Spotlight items: {}",
+                action,
+                error.localizedDescription()
+            );
+        }
+    })
+}
+
+fn submit(domain: &str, notes: &[SpotlightNote]) {
+    let Some(index) = default_index() else {
+        return;
+    };
+    let items: Vec<Retained<AnyObject>> = notes
+        .iter()
+        .filter_map(|note| searchable_item(domain, note))
+        .collect();
+    if items.is_empty() {
+        return;
+    }
+    let items = NSArray::from_retained_slice(&items);
+    let done = log_error("index");
+    // SAFETY: the array holds CSSearchableItems and the handler matches the
+    // declared `void (^)(NSError *)` signature
+    unsafe {
+        let _: () = msg_send![&index, indexSearchableItems: &*items, completionHandler: &*done];
+    }
+}
+
+// Drops a workspace's items, then runs `then` once Spotlight has done so
+fn delete_domain(domain: &str, then: impl Fn() + 'static) {
+    let Some(index) = default_index() else {
+        return;
+    };
+    let domains = strings(&[domain.to_string()]);
+    let done = RcBlock::new(move |error: *mut NSError| {
+        // SAFETY: CoreSpotlight passes either nil or a valid NSError
+        match unsafe { error.as_ref() } {
+            Some(error) => tracing::warn!(
+                "Failed to remove Spotlight items: {}",
+                error.localizedDescription()
+            ),
+            None => then(),
+        }
+    });
+    // SAFETY: the array holds NSStrings and the handler matches the declared signature
+    unsafe {
+        let _: () = msg_send![
+            &index,
+            deleteSearchableItemsWithDomainIdentifiers: &*domains,
+            completionHandler: &*done
+        ];
+    }
+}
+
+// Rebuilds a workspace's items from disk, so notes deleted or renamed while
+// Marky wasn't looking drop out of search. Runs on a background thread.
+pub fn index_workspace(app: &AppHandle, root: &Path) {
+    if !enabled(app) || vault::is_vault_root(root) {
+        return;
+    }
+    let app = app.clone();
+    let root = root.to_path_buf();
+    std::thread::spawn(move || {
+        let filter = NoteFilter::for_workspace(&root, &app.state::<SettingsState>().current());
+        let mut files = Vec::new();
+        index::collect_markdown_files(&root, &filter, &mut files);
+        let notes: Vec<SpotlightNote> = files.iter().filter_map(|path| read_note(path)).collect();
+
+        let domain = storage::workspace_key(&root);
+        delete_domain(&domain.clone(), move || submit(&domain, &notes));
+    });
+}
+
+// Every registered workspace, at launch or when indexing is turned back on
+pub fn index_registered(app: &AppHandle) {
+    for root in workspaces::registered_roots(app) {
+        if root.is_dir() {
+            index_workspace(app, &root);
+        }
+    }
+}
+
+// Re-indexes one note after it's saved or created
+pub fn index_note(app: &AppHandle, root: &Path, path: &Path) {
+    if !enabled(app) || vault::is_vault_root(root) {
+        return;
+    }
+    if let Some(note) = read_note(path) {
+        submit(&storage::workspace_key(root), &[note]);
+    }
+}
+
+// When a workspace is removed from Marky
+pub fn remove_workspace(root: &Path) {
+    delete_domain(&storage::workspace_key(root), || {});
+}
+
+// When indexing is turned off
+pub fn clear() {
+    let Some(index) = default_index() else {
+        return;
+    };
+    let done = log_error("remove");
+    // SAFETY: the handler matches the declared `void (^)(NSError *)` signature
+    unsafe {
+        let _: () = msg_send![&index, deleteAllSearchableItemsWithCompletionHandler: &*done];
+    }
+}
+
+// Opens the note a `marky://open?path=...` link points at
+pub fn open_link(app: &AppHandle, link: &str) {
+    let Some(path) = link_path(link) else {
+        tracing::warn!("Unrecognized Marky link: {}", link);
+        return;
+    };
+    if let Err(e) = windows::show_note(app, &path) {
+        tracing::warn!("Failed to open {}: {}", path.display(), e);
+    }
+}
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+static ORIGINAL_CONTINUE: OnceLock<Imp> = OnceLock::new();
+
+type ContinueActivity =
+    unsafe extern "C-unwind" fn(&AnyObject, Sel, &AnyObject, &AnyObject, &AnyObject) -> Bool;
+
+// `application:continueUserActivity:restorationHandler:`. Picking a Spotlight
+// result comes in as a user activity carrying the item's identifier, which
+// is its `marky://` link; anything else goes to the original handler.
+unsafe extern "C-unwind" fn continue_user_activity(
+    delegate: &AnyObject,
+    cmd: Sel,
+    application: &AnyObject,
+    activity: &AnyObject,
+    restoration: &AnyObject,
+) -> Bool {
+    let activity_type: Option<Retained<NSString>> = msg_send![activity, activityType];
+    if activity_type.is_some_and(|kind| kind.to_string() == ACTIVITY_TYPE) {
+        let user_info: Option<Retained<AnyObject>> = msg_send![activity, userInfo];
+        let link: Option<Retained<NSString>> = user_info.and_then(
+            |info| msg_send![&info, objectForKey: &*NSString::from_str(ACTIVITY_IDENTIFIER_KEY)],
+        );
+        if let (Some(link), Some(app)) = (link, APP.get()) {
+            open_link(app, &link.to_string());
+            return Bool::YES;
+        }
+    }
+    match ORIGINAL_CONTINUE.get() {
+        Some(original) => {
+            let original: ContinueActivity = std::mem::transmute(*original);
+            original(delegate, cmd, application, activity, restoration)
+        }
+        None => Bool::NO,
+    }
+}
+
+// Tauri's app delegate only handles links from browsing activities, so its
+// handler is wrapped to also take Spotlight's. Call on the main thread once
+// the app is running.
+pub fn handle_activities(app: &AppHandle) {
+    if APP.set(app.clone()).is_err() {
+        return;
+    }
+    let Some(app_class) = AnyClass::get(c"NSApplication") else {
+        return;
+    };
+    // SAFETY: `sharedApplication` and `delegate` take no arguments and return objects
+    let delegate: Option<Retained<AnyObject>> = unsafe {
+        let shared: Retained<AnyObject> = msg_send![app_class, sharedApplication];
+        msg_send![&shared, delegate]
+    };
+    let Some(delegate) = delegate else {
+        return;
+    };
+    let Some(method) = delegate
+        .class()
+        .instance_method(sel!(application:continueUserActivity:restorationHandler:))
+    else {
+        tracing::warn!(
+            "App delegate doesn't handle user activities; Spotlight results won't open notes"
+        );
+        return;
+    };
+    let wrapper: ContinueActivity = continue_user_activity;
+    // SAFETY: the wrapper has the same signature as the method it replaces and
+    // forwards to the original for everything it doesn't handle
+    let original =
+        unsafe { method.set_implementation(std::mem::transmute::<ContinueActivity, Imp>(wrapper)) };
+    let _ = ORIGINAL_CONTINUE.set(original);
+}
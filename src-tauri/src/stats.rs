@@ -0,0 +1,187 @@
+use crate::index::{IndexState, WorkspaceIndex};
+use crate::network;
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tauri::{AppHandle, Manager};
+
+const LARGEST_NOTES_LIMIT: usize = 10;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DayCount {
+    date: String,
+    count: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct NoteSize {
+    name: String,
+    path: String,
+    words: usize,
+    size: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TagCount {
+    tag: String,
+    count: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct WorkspaceStats {
+    note_count: usize,
+    total_words: usize,
+    total_bytes: u64,
+    total_links: usize,
+    created_per_day: Vec<DayCount>,
+    modified_per_day: Vec<DayCount>,
+    largest_notes: Vec<NoteSize>,
+    tag_distribution: Vec<TagCount>,
+}
+
+// Computed stats keyed by workspace root, tagged with the index generation they came from
+#[derive(Default)]
+pub struct StatsCache {
+    entries: Mutex<HashMap<PathBuf, (u64, WorkspaceStats)>>,
+}
+
+pub fn local_day(time: SystemTime) -> String {
+    DateTime::<Local>::from(time).format("%Y-%m-%d").to_string()
+}
+
+fn per_day(times: impl Iterator<Item = SystemTime>) -> Vec<DayCount> {
+    let mut buckets: BTreeMap<String, usize> = BTreeMap::new();
+    for time in times {
+        *buckets.entry(local_day(time)).or_insert(0) += 1;
+    }
+
+    buckets
+        .into_iter()
+        .map(|(date, count)| DayCount { date, count })
+        .collect()
+}
+
+fn compute_stats(index: &WorkspaceIndex) -> WorkspaceStats {
+    let notes: Vec<_> = index.notes.values().collect();
+
+    let mut tag_counts: HashMap<&str, usize> = HashMap::new();
+    for note in &notes {
+        for tag in &note.tags {
+            *tag_counts.entry(tag.as_str()).or_insert(0) += 1;
+        }
+    }
+    let mut tag_distribution: Vec<TagCount> = tag_counts
+        .into_iter()
+        .map(|(tag, count)| TagCount {
+            tag: tag.to_string(),
+            count,
+        })
+        .collect();
+    tag_distribution.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+
+    let mut by_size = notes.clone();
+    by_size.sort_by(|a, b| b.words.cmp(&a.words).then_with(|| b.size.cmp(&a.size)));
+    let largest_notes = by_size
+        .into_iter()
+        .take(LARGEST_NOTES_LIMIT)
+        .map(|note| NoteSize {
+            name: note.title.clone(),
+            path: note.path.to_string_lossy().to_string(),
+            words: note.words,
+            size: note.size,
+        })
+        .collect();
+
+    WorkspaceStats {
+        note_count: notes.len(),
+        total_words: notes.iter().map(|note| note.words).sum(),
+        total_bytes: notes.iter().map(|note| note.size).sum(),
+        total_links: notes.iter().map(|note| note.links.len()).sum(),
+        created_per_day: per_day(notes.iter().filter_map(|note| note.created)),
+        modified_per_day: per_day(notes.iter().filter_map(|note| note.modified)),
+        largest_notes,
+        tag_distribution,
+    }
+}
+
+#[tauri::command]
+pub async fn get_workspace_stats(
+    folder_path: String,
+    app: AppHandle,
+) -> Result<WorkspaceStats, String> {
+    let root = PathBuf::from(&folder_path);
+    network::guard_read(&app, &root.clone(), move |app| {
+        if !root.is_dir() {
+            return Err("Folder does not exist".to_string());
+        }
+        cached_stats(
+            &app.state::<StatsCache>(),
+            &app.state::<IndexState>(),
+            &root,
+        )
+    })?
+}
+
+fn cached_stats(
+    stats_cache: &StatsCache,
+    index_state: &IndexState,
+    root: &Path,
+) -> Result<WorkspaceStats, String> {
+    let mut cache = stats_cache
+        .entries
+        .lock()
+        .map_err(|e| format!("Failed to lock stats cache: {}", e))?;
+
+    index_state.with_workspace(root, |index| {
+        if let Some((generation, stats)) = cache.get(root) {
+            if *generation == index.generation {
+                return stats.clone();
+            }
+        }
+
+        let stats = compute_stats(index);
+        cache.insert(root.to_path_buf(), (index.generation, stats.clone()));
+        stats
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::Settings;
+    use std::fs;
+
+    #[test]
+    fn settings_change_recomputes_cached_stats() {
+        let root = std::env::temp_dir().join(format!("marky-stats-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("drafts")).unwrap();
+        fs::write(root.join("a.md"), "# A\n\none two").unwrap();
+        fs::write(root.join("drafts").join("b.md"), "# B\n\nthree").unwrap();
+
+        let index_state = IndexState::default();
+        let cache = StatsCache::default();
+        assert_eq!(
+            cached_stats(&cache, &index_state, &root)
+                .unwrap()
+                .note_count,
+            2
+        );
+
+        index_state.set_settings(Settings {
+            ignore_globs: vec!["drafts".to_string()],
+            ..Settings::default()
+        });
+        assert_eq!(
+            cached_stats(&cache, &index_state, &root)
+                .unwrap()
+                .note_count,
+            1
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,131 @@
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+pub const WORKSPACES_DIR: &str = "workspaces";
+#[cfg(mobile)]
+const DEFAULT_VAULT_NAME: &str = "Marky";
+
+pub fn app_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {}", e))?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    Ok(dir)
+}
+
+pub fn app_config_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to resolve app config directory: {}", e))?;
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create app config directory: {}", e))?;
+    Ok(dir)
+}
+
+// Mobile builds keep notes in the app's documents folder, which iOS shows in the
+// Files app; desktop users always pick their own folders
+#[cfg(mobile)]
+pub fn default_vault_dir(app: &AppHandle) -> Option<PathBuf> {
+    let dir = match app.path().document_dir() {
+        Ok(dir) => dir.join(DEFAULT_VAULT_NAME),
+        Err(e) => {
+            tracing::error!("Failed to resolve documents directory: {}", e);
+            return None;
+        }
+    };
+    if let Err(e) = fs::create_dir_all(&dir) {
+        tracing::error!("Failed to create notes folder: {}", e);
+        return None;
+    }
+    Some(dir)
+}
+
+#[cfg(desktop)]
+pub fn default_vault_dir(_app: &AppHandle) -> Option<PathBuf> {
+    None
+}
+
+// Mobile sandboxes only let the app reach its own directories and documents the
+// system picker granted, so anything else is refused before the OS does it less
+// helpfully. Desktop builds already work on folders the user picked.
+#[cfg(mobile)]
+pub fn ensure_accessible(app: &AppHandle, path: &Path) -> Result<(), String> {
+    use std::path::Component;
+    use tauri_plugin_fs::FsExt;
+
+    if path
+        .components()
+        .any(|component| matches!(component, Component::ParentDir))
+    {
+        return Err(crate::i18n::t("error.outside_storage"));
+    }
+
+    let resolver = app.path();
+    let sandbox = [
+        resolver.app_data_dir(),
+        resolver.app_config_dir(),
+        resolver.document_dir(),
+    ];
+    let in_sandbox = sandbox
+        .into_iter()
+        .flatten()
+        .any(|dir| path.starts_with(dir));
+    if in_sandbox || app.fs_scope().is_allowed(path) {
+        Ok(())
+    } else {
+        Err(crate::i18n::t("error.outside_storage"))
+    }
+}
+
+// Desktop builds only refuse paths on a network share that stopped answering,
+// so commands fail fast instead of waiting on it
+#[cfg(desktop)]
+pub fn ensure_accessible(app: &AppHandle, path: &Path) -> Result<(), String> {
+    crate::network::ensure_online(app, path)
+}
+
+// Stable short id for a path, safe to use in file names and window labels
+pub fn path_key(path: &Path) -> String {
+    let digest = Sha256::digest(path.to_string_lossy().as_bytes());
+    format!("{:x}", digest).chars().take(16).collect()
+}
+
+// Stable short id for a workspace root, shared by the registry and data dirs
+pub fn workspace_key(root: &Path) -> String {
+    path_key(root)
+}
+
+// Per-workspace state lives under the app data dir, keyed by `workspace_key`
+pub fn workspace_data_dir(app: &AppHandle, root: &Path) -> Result<PathBuf, String> {
+    let dir = app_data_dir(app)?
+        .join(WORKSPACES_DIR)
+        .join(workspace_key(root));
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create workspace data directory: {}", e))?;
+    Ok(dir)
+}
+
+// Missing or unreadable files fall back to the default value
+pub fn read_json<T: DeserializeOwned + Default>(path: &Path) -> T {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+pub fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let raw = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize data: {}", e))?;
+
+    // Write to a sibling first so a crash never leaves a truncated file behind
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, raw).map_err(|e| format!("Failed to write data: {}", e))?;
+    fs::rename(&tmp, path).map_err(|e| format!("Failed to write data: {}", e))?;
+
+    Ok(())
+}
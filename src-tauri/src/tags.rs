@@ -0,0 +1,747 @@
+use crate::encryption;
+use crate::index::IndexState;
+use crate::locks;
+use crate::markdown;
+use crate::notifications;
+use crate::storage;
+use crate::vault;
+use crate::watcher::WatcherState;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, State};
+
+#[derive(Debug, Serialize)]
+pub struct SkippedNote {
+    path: String,
+    reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TagEditReport {
+    tag: String,
+    changed: Vec<String>,
+    // Notes that already had (or already lacked) the tag
+    unchanged: usize,
+    skipped: Vec<SkippedNote>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TagNode {
+    // Last segment, `acme` for `project/client/acme`
+    name: String,
+    tag: String,
+    // Notes tagged with this tag or anything under it, each counted once
+    count: usize,
+    // Notes tagged with exactly this tag
+    direct: usize,
+    children: Vec<TagNode>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TaggedNote {
+    path: String,
+    title: String,
+    tags: Vec<String>,
+}
+
+// Same rules as `normalizeTagValue` in the frontend
+pub fn normalize_tag(value: &str) -> String {
+    let lowered = value.trim().trim_start_matches('#').to_lowercase();
+    lowered
+        .split('/')
+        .map(normalize_segment)
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn normalize_segment(value: &str) -> String {
+    let mut segment = String::with_capacity(value.len());
+    for c in value.chars() {
+        let c = if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+            c
+        } else {
+            '-'
+        };
+        if !(c == '-' && segment.ends_with('-')) {
+            segment.push(c);
+        }
+    }
+    segment.trim_matches(|c| c == '-' || c == '_').to_string()
+}
+
+// How the tags are written, kept when the field is rewritten
+enum Layout {
+    // `tags: [a, "b c"]`
+    Flow,
+    // `tags: a b` or `tags: "a, b"`: one string, `quote` wrapping all of it
+    Scalar {
+        separator: &'static str,
+        quote: Option<char>,
+    },
+    // `- a` lines under the key, at this indent
+    Block(String),
+}
+
+// The `tags:` / `tag:` entry of a note's frontmatter, located by line
+struct TagsField {
+    // Byte range of the key line plus any block-list items under it
+    start: usize,
+    end: usize,
+    key: String,
+    // As written, quotes included, so untouched items are left as they were
+    items: Vec<String>,
+    layout: Layout,
+    line_ending: &'static str,
+}
+
+// The value of a YAML scalar, without its quotes
+fn unquote(item: &str) -> String {
+    let item = item.trim();
+    for quote in ['"', '\''] {
+        if item.len() >= 2 && item.starts_with(quote) && item.ends_with(quote) {
+            let inner = &item[1..item.len() - 1];
+            return if quote == '"' {
+                inner.replace("\\\"", "\"")
+            } else {
+                inner.replace("''", "'")
+            };
+        }
+    }
+    item.to_string()
+}
+
+fn item_tag(item: &str) -> Option<String> {
+    markdown::frontmatter_tag_value(&unquote(item))
+}
+
+// Splits a flow list on the commas outside quoted items
+fn split_flow(list: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut escaped = false;
+    for c in list.chars() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == ',' => {
+                items.push(std::mem::take(&mut current));
+                continue;
+            }
+            None => {}
+        }
+        current.push(c);
+    }
+    items.push(current);
+    items
+        .into_iter()
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+// A plain string value, split the way `markdown::frontmatter_tags` reads it
+fn scalar_field(value: &str) -> (Vec<String>, Layout) {
+    let quote = ['"', '\'']
+        .into_iter()
+        .find(|quote| value.len() >= 2 && value.starts_with(*quote) && value.ends_with(*quote));
+    let text = match quote {
+        Some(_) => unquote(value),
+        None => value.to_string(),
+    };
+    let separator = if text.contains(',') { ", " } else { " " };
+    let items = text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect();
+    (items, Layout::Scalar { separator, quote })
+}
+
+impl TagsField {
+    fn find(content: &str) -> Option<TagsField> {
+        let frontmatter = markdown::frontmatter(content)?;
+        let base = frontmatter.as_ptr() as usize - content.as_ptr() as usize;
+        let lines: Vec<(usize, &str)> = frontmatter
+            .split_inclusive('\n')
+            .scan(base, |offset, line| {
+                let start = *offset;
+                *offset += line.len();
+                Some((start, line))
+            })
+            .collect();
+
+        for (index, (start, line)) in lines.iter().enumerate() {
+            let text = line.trim_end();
+            let Some((key, value)) = text.split_once(':') else {
+                continue;
+            };
+            if key != "tags" && key != "tag" {
+                continue;
+            }
+            let line_ending = if line.ends_with("\r\n") { "\r\n" } else { "\n" };
+            let value = value.trim();
+            if !value.is_empty() {
+                let (items, layout) = match value
+                    .strip_prefix('[')
+                    .and_then(|list| list.strip_suffix(']'))
+                {
+                    Some(list) => (split_flow(list), Layout::Flow),
+                    None => scalar_field(value),
+                };
+                return Some(TagsField {
+                    start: *start,
+                    end: start + line.len(),
+                    key: key.to_string(),
+                    items,
+                    layout,
+                    line_ending,
+                });
+            }
+
+            let mut end = start + line.len();
+            let mut items = Vec::new();
+            let mut block_indent = None;
+            for (item_start, item_line) in &lines[index + 1..] {
+                let trimmed = item_line.trim_start();
+                let Some(item) = trimmed.strip_prefix("- ") else {
+                    break;
+                };
+                block_indent.get_or_insert_with(|| {
+                    item_line[..item_line.len() - trimmed.len()].to_string()
+                });
+                items.push(item.trim().to_string());
+                end = item_start + item_line.len();
+            }
+            return Some(TagsField {
+                start: *start,
+                end,
+                key: key.to_string(),
+                items,
+                layout: Layout::Block(block_indent.unwrap_or_else(|| "  ".to_string())),
+                line_ending,
+            });
+        }
+        None
+    }
+
+    fn has(&self, tag: &str) -> bool {
+        self.items
+            .iter()
+            .any(|item| item_tag(item).as_deref() == Some(tag))
+    }
+
+    fn render(&self) -> String {
+        match &self.layout {
+            Layout::Block(indent) if !self.items.is_empty() => {
+                let mut text = format!("{}:{}", self.key, self.line_ending);
+                for item in &self.items {
+                    text.push_str(&format!("{}- {}{}", indent, item, self.line_ending));
+                }
+                text
+            }
+            Layout::Scalar { separator, quote } if !self.items.is_empty() => {
+                let quote = quote.map(String::from).unwrap_or_default();
+                format!(
+                    "{}: {}{}{}{}",
+                    self.key,
+                    quote,
+                    self.items.join(separator),
+                    quote,
+                    self.line_ending
+                )
+            }
+            _ => format!(
+                "{}: [{}]{}",
+                self.key,
+                self.items.join(", "),
+                self.line_ending
+            ),
+        }
+    }
+
+    fn replace_in(&self, content: &str) -> String {
+        format!(
+            "{}{}{}",
+            &content[..self.start],
+            self.render(),
+            &content[self.end..]
+        )
+    }
+}
+
+// Adds `tag` to the frontmatter list when the note keeps its tags there,
+// otherwise as an inline `#tag` on the note's last line of tags (or a new one)
+pub fn add_tag(content: &str, tag: &str) -> Option<String> {
+    if markdown::extract_tags(content).iter().any(|t| t == tag) {
+        return None;
+    }
+    if let Some(mut field) = TagsField::find(content) {
+        field.items.push(tag.to_string());
+        return Some(field.replace_in(content));
+    }
+
+    let trimmed = content.trim_end();
+    let trailing = &content[trimmed.len()..];
+    let last_line_start = trimmed.rfind('\n').map_or(0, |idx| idx + 1);
+    let last_line = &trimmed[last_line_start..];
+    let spans = markdown::inline_tag_spans(trimmed);
+    let tag_line = !last_line.trim().is_empty()
+        && last_line.split_whitespace().count()
+            == spans
+                .iter()
+                .filter(|(range, _)| range.start >= last_line_start)
+                .count();
+
+    let ending = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let trailing = if trailing.is_empty() {
+        ending
+    } else {
+        trailing
+    };
+    Some(if trimmed.is_empty() {
+        format!("#{}{}", tag, ending)
+    } else if tag_line {
+        format!("{} #{}{}", trimmed, tag, trailing)
+    } else {
+        format!("{}{}{}#{}{}", trimmed, ending, ending, tag, trailing)
+    })
+}
+
+// Drops inline `#tag`s (with the space before them) and the frontmatter entry
+pub fn remove_tag(content: &str, tag: &str) -> Option<String> {
+    let mut next = content.to_string();
+    if let Some(mut field) = TagsField::find(&next) {
+        if field.has(tag) {
+            field
+                .items
+                .retain(|item| item_tag(item).as_deref() != Some(tag));
+            next = field.replace_in(&next);
+        }
+    }
+
+    let spans: Vec<_> = markdown::inline_tag_spans(&next)
+        .into_iter()
+        .filter(|(_, found)| found == tag)
+        .collect();
+    for (range, _) in spans.into_iter().rev() {
+        let before = next[..range.start].trim_end_matches([' ', '\t']);
+        let start = if before.len() < range.start && !before.ends_with('\n') && !before.is_empty() {
+            before.len()
+        } else {
+            range.start
+        };
+        let mut end = range.end;
+        if start == range.start {
+            end += next[end..].len() - next[end..].trim_start_matches([' ', '\t']).len();
+        }
+        next.replace_range(start..end, "");
+    }
+
+    (next != content).then_some(next)
+}
+
+// `tag` with `from` swapped for `to`, when it is `from` or nested under it
+fn renamed_tag(tag: &str, from: &str, to: &str) -> Option<String> {
+    if tag == from {
+        return Some(to.to_string());
+    }
+    tag.strip_prefix(from)
+        .filter(|rest| rest.starts_with('/'))
+        .map(|rest| format!("{}{}", to, rest))
+}
+
+// Rewrites `#from` to `#to` and the matching frontmatter entry, along with
+// tags nested under it (`#from/x` becomes `#to/x`); a note that already had
+// the new tag in its frontmatter keeps a single entry
+pub fn replace_tag(content: &str, from: &str, to: &str) -> Option<String> {
+    let mut next = content.to_string();
+    if let Some(mut field) = TagsField::find(&next) {
+        let mut kept: HashSet<String> = field
+            .items
+            .iter()
+            .filter_map(|item| item_tag(item))
+            .filter(|tag| renamed_tag(tag, from, to).is_none())
+            .collect();
+        let mut changed = false;
+        field.items.retain_mut(|item| {
+            let Some(renamed) = item_tag(item).and_then(|tag| renamed_tag(&tag, from, to)) else {
+                return true;
+            };
+            changed = true;
+            if !kept.insert(renamed.clone()) {
+                return false;
+            }
+            *item = renamed;
+            true
+        });
+        if changed {
+            next = field.replace_in(&next);
+        }
+    }
+
+    let spans: Vec<_> = markdown::inline_tag_spans(&next)
+        .into_iter()
+        .filter_map(|(range, found)| Some((range, renamed_tag(&found, from, to)?)))
+        .collect();
+    for (range, renamed) in spans.into_iter().rev() {
+        next.replace_range(range, &format!("#{}", renamed));
+    }
+
+    (next != content).then_some(next)
+}
+
+struct PendingWrite {
+    path: PathBuf,
+    original: String,
+    content: String,
+    temp: PathBuf,
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.marky-tmp", name))
+}
+
+// Writes every edit or none: all new contents go to temporary files first, and
+// if moving one into place fails the notes already replaced are put back
+fn commit(pending: &[PendingWrite]) -> Result<(), String> {
+    for (index, write) in pending.iter().enumerate() {
+        if let Err(e) = fs::write(&write.temp, &write.content) {
+            for written in &pending[..=index] {
+                let _ = fs::remove_file(&written.temp);
+            }
+            return Err(format!("Failed to write {}: {}", write.path.display(), e));
+        }
+    }
+    for (index, write) in pending.iter().enumerate() {
+        if let Err(e) = fs::rename(&write.temp, &write.path) {
+            for restored in &pending[..index] {
+                if let Err(e) = fs::write(&restored.path, &restored.original) {
+                    tracing::error!("Failed to restore {}: {}", restored.path.display(), e);
+                }
+            }
+            for unmoved in &pending[index..] {
+                let _ = fs::remove_file(&unmoved.temp);
+            }
+            return Err(format!("Failed to update {}: {}", write.path.display(), e));
+        }
+    }
+    Ok(())
+}
+
+// Applies `edit` to each note and writes the results together. Notes that can't
+// be edited safely (missing, locked, encrypted, or in a vault) are reported
+// instead; `dry_run` only reports what would change.
+pub fn edit_notes(
+    app: &AppHandle,
+    tag: &str,
+    paths: &[PathBuf],
+    dry_run: bool,
+    edit: impl Fn(&str) -> Option<String>,
+) -> Result<TagEditReport, String> {
+    let watcher_state = app.state::<WatcherState>();
+    let mut report = TagEditReport {
+        tag: tag.to_string(),
+        changed: Vec::new(),
+        unchanged: 0,
+        skipped: Vec::new(),
+    };
+    let mut skip = |path: &Path, reason: &str| {
+        report.skipped.push(SkippedNote {
+            path: path.to_string_lossy().to_string(),
+            reason: reason.to_string(),
+        })
+    };
+
+    let mut pending = Vec::new();
+    for path in paths {
+        storage::ensure_accessible(app, path)?;
+        if vault::vault_root(path).is_some() {
+            skip(path, "Notes in a vault are retagged one at a time");
+            continue;
+        }
+        let root = watcher_state.root_for(path);
+        if root
+            .as_deref()
+            .is_some_and(|root| locks::is_locked(app, root, path))
+        {
+            skip(path, "Note is locked");
+            continue;
+        }
+        let Ok(original) = fs::read_to_string(path) else {
+            skip(path, "Note could not be read");
+            continue;
+        };
+        if encryption::is_encrypted(&original) {
+            skip(path, "Note is encrypted");
+            continue;
+        }
+        match edit(&original) {
+            Some(content) => pending.push(PendingWrite {
+                temp: temp_path(path),
+                path: path.clone(),
+                original,
+                content,
+            }),
+            None => report.unchanged += 1,
+        }
+    }
+
+    if !dry_run {
+        commit(&pending)?;
+    }
+    let index_state = app.state::<IndexState>();
+    for write in &pending {
+        if !dry_run {
+            notifications::record_write(app, &write.path);
+            if let Some(root) = index_state.root_for(&write.path) {
+                index_state.apply_change(&root, &write.path);
+            }
+        }
+        report
+            .changed
+            .push(write.path.to_string_lossy().to_string());
+    }
+    Ok(report)
+}
+
+fn tag_argument(tag: &str) -> Result<String, String> {
+    let tag = normalize_tag(tag);
+    if tag.is_empty() {
+        return Err("Tag is required".to_string());
+    }
+    Ok(tag)
+}
+
+#[tauri::command]
+pub async fn add_tag_to_notes(
+    paths: Vec<String>,
+    tag: String,
+    app: AppHandle,
+) -> Result<TagEditReport, String> {
+    let tag = tag_argument(&tag)?;
+    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+    edit_notes(&app, &tag, &paths, false, |content| add_tag(content, &tag))
+}
+
+#[tauri::command]
+pub async fn remove_tag_from_notes(
+    paths: Vec<String>,
+    tag: String,
+    app: AppHandle,
+) -> Result<TagEditReport, String> {
+    let tag = tag_argument(&tag)?;
+    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+    edit_notes(&app, &tag, &paths, false, |content| {
+        remove_tag(content, &tag)
+    })
+}
+
+// Renames a tag and the tags nested under it across the workspace, or with
+// `dry_run` only reports which notes would change. Merging into an existing
+// tag is a rename onto it.
+#[tauri::command]
+pub async fn rename_tag(
+    folder_path: String,
+    old_tag: String,
+    new_tag: String,
+    dry_run: Option<bool>,
+    app: AppHandle,
+    index_state: State<'_, IndexState>,
+) -> Result<TagEditReport, String> {
+    let from = tag_argument(&old_tag)?;
+    let to = tag_argument(&new_tag)?;
+    if from == to {
+        return Err("Source and target tag are the same".to_string());
+    }
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+
+    let mut paths: Vec<PathBuf> = index_state.with_workspace(&root, |index| {
+        index
+            .notes
+            .values()
+            .filter(|note| {
+                note.tags
+                    .iter()
+                    .any(|tag| renamed_tag(tag, &from, &to).is_some())
+            })
+            .map(|note| note.path.clone())
+            .collect()
+    })?;
+    paths.sort();
+    edit_notes(&app, &to, &paths, dry_run.unwrap_or(false), |content| {
+        replace_tag(content, &from, &to)
+    })
+}
+
+fn build_tree(
+    tag: &str,
+    counts: &BTreeMap<String, (usize, usize)>,
+    children: &HashMap<&str, Vec<&str>>,
+) -> TagNode {
+    let (count, direct) = counts.get(tag).copied().unwrap_or_default();
+    TagNode {
+        name: tag.rsplit('/').next().unwrap_or(tag).to_string(),
+        tag: tag.to_string(),
+        count,
+        direct,
+        children: children
+            .get(tag)
+            .map(|nested| {
+                nested
+                    .iter()
+                    .map(|child| build_tree(child, counts, children))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+// Tags as a tree split on `/`. A parent's count covers its descendants, so a
+// note tagged `#project/acme` also counts toward `project` even when nothing
+// is tagged `#project` itself.
+#[tauri::command]
+pub async fn get_tag_tree(
+    folder_path: String,
+    index_state: State<'_, IndexState>,
+) -> Result<Vec<TagNode>, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+
+    let counts = index_state.with_workspace(&root, |index| {
+        let mut counts: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+        for note in index.notes.values() {
+            let covered: HashSet<&str> = note
+                .tags
+                .iter()
+                .flat_map(|tag| markdown::tag_ancestors(tag))
+                .collect();
+            for tag in covered {
+                counts.entry(tag.to_string()).or_default().0 += 1;
+            }
+            for tag in &note.tags {
+                counts.entry(tag.clone()).or_default().1 += 1;
+            }
+        }
+        counts
+    })?;
+
+    // BTreeMap order keeps siblings sorted by name
+    let mut roots = Vec::new();
+    let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+    for tag in counts.keys() {
+        match tag.rsplit_once('/') {
+            Some((parent, _)) => children.entry(parent).or_default().push(tag),
+            None => roots.push(tag.as_str()),
+        }
+    }
+    Ok(roots
+        .into_iter()
+        .map(|tag| build_tree(tag, &counts, &children))
+        .collect())
+}
+
+// Notes tagged `prefix` or anything nested under it
+#[tauri::command]
+pub async fn get_notes_by_tag(
+    folder_path: String,
+    prefix: String,
+    index_state: State<'_, IndexState>,
+) -> Result<Vec<TaggedNote>, String> {
+    let prefix = tag_argument(&prefix)?;
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+
+    let nested = format!("{}/", prefix);
+    let mut notes: Vec<TaggedNote> = index_state.with_workspace(&root, |index| {
+        index
+            .notes
+            .values()
+            .filter(|note| {
+                note.tags
+                    .iter()
+                    .any(|tag| *tag == prefix || tag.starts_with(&nested))
+            })
+            .map(|note| TaggedNote {
+                path: note.path.to_string_lossy().to_string(),
+                title: note.title.clone(),
+                tags: note.tags.clone(),
+            })
+            .collect()
+    })?;
+    notes.sort_by_key(|note| note.title.to_lowercase());
+    Ok(notes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_flow_items_survive_a_rename() {
+        let content = "---\ntags: [\"project x\", \"a\", 'b, c']\n---\nBody\n";
+        assert_eq!(
+            replace_tag(content, "a", "d").unwrap(),
+            "---\ntags: [\"project x\", d, 'b, c']\n---\nBody\n"
+        );
+        assert_eq!(
+            remove_tag(content, "a").unwrap(),
+            "---\ntags: [\"project x\", 'b, c']\n---\nBody\n"
+        );
+    }
+
+    #[test]
+    fn scalar_tags_stay_scalar() {
+        let content = "---\ntags: a b\n---\nBody\n";
+        assert_eq!(
+            replace_tag(content, "b", "c").unwrap(),
+            "---\ntags: a c\n---\nBody\n"
+        );
+        assert_eq!(
+            add_tag(content, "d").unwrap(),
+            "---\ntags: a b d\n---\nBody\n"
+        );
+
+        let quoted = "---\ntags: \"a, b\"\n---\nBody\n";
+        assert_eq!(
+            remove_tag(quoted, "a").unwrap(),
+            "---\ntags: \"b\"\n---\nBody\n"
+        );
+    }
+
+    #[test]
+    fn renaming_a_parent_moves_its_children() {
+        let content = "---\ntags: [a, a/b, ab]\n---\nSee #a/b/c and #ab\n";
+        assert_eq!(
+            replace_tag(content, "a", "z").unwrap(),
+            "---\ntags: [z, z/b, ab]\n---\nSee #z/b/c and #ab\n"
+        );
+
+        let merged = "---\ntags: [a/b, z/b]\n---\nBody\n";
+        assert_eq!(
+            replace_tag(merged, "a", "z").unwrap(),
+            "---\ntags: [z/b]\n---\nBody\n"
+        );
+    }
+}
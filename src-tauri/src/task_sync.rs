@@ -0,0 +1,579 @@
+use crate::activity::ActivityState;
+use crate::encryption;
+use crate::ics;
+use crate::index::IndexState;
+use crate::markdown;
+use crate::safe_mode::SafeModeState;
+use crate::storage;
+use crate::vault;
+use crate::watcher::WatcherState;
+use crate::workspace_config::CONFIG_DIR;
+use crate::writing::WritingState;
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const CONFIG_FILE: &str = "task-sync.json";
+const MAPPING_FILE: &str = "task-sync.json";
+// Ticks made from another app aren't tied to a window, so every window reloads the note
+const ORIGIN: &str = "task-sync";
+const SYNC_INTERVAL: Duration = Duration::from_secs(5 * 60);
+// todo.txt `key:value` tag naming the markdown task a line came from
+const ID_TAG: &str = "marky:";
+const MAX_LIST_CHARS: usize = 100;
+
+// Lists the list's reminders as `[{id, completed}]`
+const REMINDERS_PULL: &str = r#"
+function run(argv) {
+  const input = JSON.parse(argv[0]);
+  const app = Application('Reminders');
+  const lists = app.lists.whose({ name: input.list })();
+  if (lists.length === 0) return JSON.stringify([]);
+  const ids = lists[0].reminders.id();
+  const completed = lists[0].reminders.completed();
+  return JSON.stringify(ids.map((id, i) => ({ id, completed: completed[i] })));
+}
+"#;
+
+// Creates, completes, and removes reminders; answers `{task id: reminder id}` for the created ones
+const REMINDERS_PUSH: &str = r#"
+function run(argv) {
+  const input = JSON.parse(argv[0]);
+  const app = Application('Reminders');
+  let lists = app.lists.whose({ name: input.list })();
+  if (lists.length === 0) {
+    app.lists.push(app.List({ name: input.list }));
+    lists = app.lists.whose({ name: input.list })();
+  }
+  const list = lists[0];
+  const created = {};
+  for (const task of input.create) {
+    const properties = { name: task.name, body: task.body };
+    if (task.due) {
+      const [year, month, day] = task.due.split('-').map(Number);
+      properties.alldayDueDate = new Date(year, month - 1, day);
+    }
+    const reminder = app.Reminder(properties);
+    list.reminders.push(reminder);
+    created[task.id] = reminder.id();
+  }
+  for (const id of input.complete) {
+    const reminder = list.reminders.byId(id);
+    if (reminder.exists()) reminder.completed = true;
+  }
+  for (const id of input.remove) {
+    const reminder = list.reminders.byId(id);
+    if (reminder.exists()) app.delete(reminder);
+  }
+  return JSON.stringify(created);
+}
+"#;
+
+// Where the workspace's tasks are mirrored. Stored in the workspace so the
+// setup travels with the notes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskSyncConfig {
+    // todo.txt file; a relative path starts at the workspace root
+    pub todo_txt: Option<String>,
+    // Apple Reminders list, created on the first sync (macOS only)
+    pub reminders_list: Option<String>,
+    // Every open task, not only those with a due date
+    pub include_undated: bool,
+}
+
+// A task that was exported, so a completion elsewhere can find its way back
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct MappedTask {
+    // Workspace-relative
+    path: String,
+    text: String,
+    reminder: Option<String>,
+}
+
+// The mapping file, kept with the app's data for the workspace. Keys are the
+// ids from `ics::task_id`, which todo.txt lines carry as `marky:<id>`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct SyncLog {
+    tasks: HashMap<String, MappedTask>,
+    last_synced: Option<String>,
+    last_error: Option<String>,
+    last_completed: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskSyncStatus {
+    last_synced: Option<String>,
+    last_error: Option<String>,
+    // Markdown tasks ticked by the last sync because they were done elsewhere
+    last_completed: usize,
+    exported: usize,
+    reminders_available: bool,
+}
+
+// Syncs of one workspace run one at a time, so no reminder is created twice
+#[derive(Default)]
+pub struct TaskSyncState {
+    syncing: Mutex<()>,
+}
+
+struct OpenTask {
+    id: String,
+    path: PathBuf,
+    text: String,
+    note_title: String,
+    due: Option<NaiveDate>,
+}
+
+#[derive(Deserialize)]
+struct Reminder {
+    id: String,
+    completed: bool,
+}
+
+fn config_path(root: &Path) -> PathBuf {
+    root.join(CONFIG_DIR).join(CONFIG_FILE)
+}
+
+fn load(root: &Path) -> TaskSyncConfig {
+    storage::read_json(&config_path(root))
+}
+
+fn log_path(app: &AppHandle, root: &Path) -> Result<PathBuf, String> {
+    Ok(storage::workspace_data_dir(app, root)?.join(MAPPING_FILE))
+}
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn todo_txt_path(root: &Path, config: &TaskSyncConfig) -> Option<PathBuf> {
+    let path = PathBuf::from(non_empty(&config.todo_txt)?);
+    Some(if path.is_absolute() {
+        path
+    } else {
+        root.join(path)
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn run_jxa(script: &str, input: &Value) -> Result<Value, String> {
+    let output = std::process::Command::new("osascript")
+        .args(["-l", "JavaScript", "-e", script])
+        .arg(input.to_string())
+        .output()
+        .map_err(|e| format!("Failed to reach Reminders: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Reminders refused the request: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Unexpected answer from Reminders: {}", e))
+}
+
+#[cfg(not(target_os = "macos"))]
+fn run_jxa(_script: &str, _input: &Value) -> Result<Value, String> {
+    Err("Apple Reminders is only available on macOS".to_string())
+}
+
+fn pull_reminders(list: &str) -> Result<Vec<Reminder>, String> {
+    let answer = run_jxa(REMINDERS_PULL, &json!({ "list": list }))?;
+    serde_json::from_value(answer).map_err(|e| format!("Unexpected answer from Reminders: {}", e))
+}
+
+fn marky_id(line: &str) -> Option<&str> {
+    line.split_whitespace()
+        .find_map(|token| token.strip_prefix(ID_TAG))
+        .filter(|id| !id.is_empty())
+}
+
+// Open tasks to export, and the ids of tasks already ticked in markdown
+fn collect_tasks(
+    index_state: &IndexState,
+    root: &Path,
+    include_undated: bool,
+) -> Result<(Vec<OpenTask>, HashSet<String>), String> {
+    index_state.with_workspace(root, |index| {
+        let mut notes: Vec<_> = index.notes.values().collect();
+        notes.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut open = Vec::new();
+        let mut done = HashSet::new();
+        for note in notes {
+            let mut occurrences: HashMap<&str, usize> = HashMap::new();
+            for task in &note.tasks {
+                let occurrence = occurrences.entry(task.text.as_str()).or_default();
+                *occurrence += 1;
+                let id = ics::task_id(root, &note.path, &task.text, *occurrence);
+                if task.done {
+                    done.insert(id);
+                } else if task.due.is_some() || include_undated {
+                    open.push(OpenTask {
+                        id,
+                        path: note.path.clone(),
+                        text: task.text.clone(),
+                        note_title: note.title.clone(),
+                        due: task.due,
+                    });
+                }
+            }
+        }
+        (open, done)
+    })
+}
+
+// Ticks the markdown checkboxes of tasks finished in another app; answers how many
+fn complete_in_notes(
+    app: &AppHandle,
+    root: &Path,
+    log: &SyncLog,
+    completed: &HashSet<String>,
+) -> Result<usize, String> {
+    let mut by_note: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for id in completed {
+        if let Some(mapped) = log.tasks.get(id) {
+            by_note
+                .entry(mapped.path.as_str())
+                .or_default()
+                .insert(id.as_str());
+        }
+    }
+
+    let mut ticked = 0;
+    for (relative, ids) in by_note {
+        let path = root.join(relative);
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if encryption::is_encrypted(&content) {
+            continue;
+        }
+
+        // Same numbering as `collect_tasks`, so ids match lines
+        let mut targets = HashSet::new();
+        let mut occurrences: HashMap<&str, usize> = HashMap::new();
+        let tasks = markdown::extract_tasks(&content);
+        for task in &tasks {
+            let occurrence = occurrences.entry(task.text.as_str()).or_default();
+            *occurrence += 1;
+            let id = ics::task_id(root, &path, &task.text, *occurrence);
+            if !task.done && ids.contains(id.as_str()) {
+                targets.insert(task.line);
+            }
+        }
+        if targets.is_empty() {
+            continue;
+        }
+
+        let mut updated = String::with_capacity(content.len());
+        for (idx, line) in content.split_inclusive('\n').enumerate() {
+            let body = line.trim_end_matches(['\r', '\n']);
+            match markdown::complete_task_line(body).filter(|_| targets.contains(&(idx + 1))) {
+                Some(ticked_line) => {
+                    updated.push_str(&ticked_line);
+                    updated.push_str(&line[body.len()..]);
+                }
+                None => updated.push_str(line),
+            }
+        }
+        crate::write_note(
+            app,
+            ORIGIN,
+            &path.to_string_lossy(),
+            updated,
+            &app.state::<WatcherState>(),
+            &app.state::<WritingState>(),
+            &app.state::<ActivityState>(),
+        )
+        .map_err(|e| e.into_message())?;
+        ticked += targets.len();
+    }
+    Ok(ticked)
+}
+
+// `Send invoice due:2026-03-01 +Projects marky:<id>`
+fn todo_txt_line(task: &OpenTask) -> String {
+    let mut parts = vec![markdown::strip_due_date(&task.text)];
+    if let Some(due) = task.due {
+        parts.push(format!("due:{}", due.format("%Y-%m-%d")));
+    }
+    let project = task
+        .note_title
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-");
+    if !project.is_empty() {
+        parts.push(format!("+{}", project));
+    }
+    parts.push(format!("{}{}", ID_TAG, task.id));
+    parts.join(" ")
+}
+
+// Replaces the lines Marky wrote earlier and keeps everything else in the file
+fn write_todo_txt(path: &Path, existing: &str, open: &[OpenTask]) -> Result<(), String> {
+    let mut lines: Vec<String> = existing
+        .lines()
+        .filter(|line| marky_id(line).is_none())
+        .map(str::to_string)
+        .collect();
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    lines.extend(open.iter().map(todo_txt_line));
+
+    let mut output = lines.join("\n");
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    if output == existing {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create folder: {}", e))?;
+    }
+    fs::write(path, output).map_err(|e| format!("Failed to write todo.txt: {}", e))
+}
+
+fn run_sync(
+    app: &AppHandle,
+    root: &Path,
+    config: &TaskSyncConfig,
+    log: &mut SyncLog,
+) -> Result<(), String> {
+    let todo_path = todo_txt_path(root, config);
+    let todo_text = todo_path
+        .as_deref()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .unwrap_or_default();
+    let list = non_empty(&config.reminders_list);
+    let reminders = match &list {
+        Some(list) => pull_reminders(list)?,
+        None => Vec::new(),
+    };
+
+    // Completions made elsewhere come back into the notes first
+    let mut completed: HashSet<String> = todo_text
+        .lines()
+        .filter(|line| line.starts_with("x "))
+        .filter_map(marky_id)
+        .map(str::to_string)
+        .collect();
+    let done_reminders: HashSet<&str> = reminders
+        .iter()
+        .filter(|reminder| reminder.completed)
+        .map(|reminder| reminder.id.as_str())
+        .collect();
+    completed.extend(
+        log.tasks
+            .iter()
+            .filter(|(_, mapped)| {
+                mapped
+                    .reminder
+                    .as_deref()
+                    .is_some_and(|id| done_reminders.contains(id))
+            })
+            .map(|(id, _)| id.clone()),
+    );
+    log.last_completed = complete_in_notes(app, root, log, &completed)?;
+
+    // The index catches up with those ticks later, so they're left out by hand
+    let (mut open, mut done) =
+        collect_tasks(&app.state::<IndexState>(), root, config.include_undated)?;
+    open.retain(|task| !completed.contains(&task.id));
+    done.extend(completed);
+
+    if let Some(path) = &todo_path {
+        write_todo_txt(path, &todo_text, &open)?;
+    }
+
+    let mut reminder_ids: HashMap<String, String> = log
+        .tasks
+        .iter()
+        .filter_map(|(id, mapped)| Some((id.clone(), mapped.reminder.clone()?)))
+        .collect();
+    if let Some(list) = &list {
+        let open_ids: HashSet<&str> = open.iter().map(|task| task.id.as_str()).collect();
+        let create: Vec<Value> = open
+            .iter()
+            .filter(|task| !reminder_ids.contains_key(&task.id))
+            .map(|task| {
+                json!({
+                    "id": task.id,
+                    "name": markdown::strip_due_date(&task.text),
+                    "body": task.note_title,
+                    "due": task.due.map(|due| due.format("%Y-%m-%d").to_string()),
+                })
+            })
+            .collect();
+        // Ticked in markdown: completed. Edited or deleted: removed, and the
+        // edited version is created fresh.
+        let (complete, remove): (Vec<_>, Vec<_>) = reminder_ids
+            .iter()
+            .filter(|(id, _)| !open_ids.contains(id.as_str()))
+            .partition(|(id, _)| done.contains(id.as_str()));
+        let complete: Vec<&String> = complete
+            .into_iter()
+            .filter(|(_, reminder)| !done_reminders.contains(reminder.as_str()))
+            .map(|(_, reminder)| reminder)
+            .collect();
+        let remove: Vec<&String> = remove.into_iter().map(|(_, reminder)| reminder).collect();
+
+        if !create.is_empty() || !complete.is_empty() || !remove.is_empty() {
+            let answer = run_jxa(
+                REMINDERS_PUSH,
+                &json!({ "list": list, "create": create, "complete": complete, "remove": remove }),
+            )?;
+            let created: HashMap<String, String> = serde_json::from_value(answer)
+                .map_err(|e| format!("Unexpected answer from Reminders: {}", e))?;
+            reminder_ids.extend(created);
+        }
+    }
+
+    log.tasks = open
+        .iter()
+        .map(|task| {
+            let mapped = MappedTask {
+                path: task
+                    .path
+                    .strip_prefix(root)
+                    .unwrap_or(&task.path)
+                    .to_string_lossy()
+                    .to_string(),
+                text: task.text.clone(),
+                reminder: reminder_ids.get(&task.id).cloned(),
+            };
+            (task.id.clone(), mapped)
+        })
+        .collect();
+    Ok(())
+}
+
+fn status(log: &SyncLog) -> TaskSyncStatus {
+    TaskSyncStatus {
+        last_synced: log.last_synced.clone(),
+        last_error: log.last_error.clone(),
+        last_completed: log.last_completed,
+        exported: log.tasks.len(),
+        reminders_available: cfg!(target_os = "macos"),
+    }
+}
+
+fn sync_workspace(app: &AppHandle, root: &Path) -> Result<TaskSyncStatus, String> {
+    let state = app.state::<TaskSyncState>();
+    let _syncing = state
+        .syncing
+        .lock()
+        .map_err(|_| "Task sync is unavailable".to_string())?;
+
+    let config = load(root);
+    let path = log_path(app, root)?;
+    let mut log: SyncLog = storage::read_json(&path);
+    log.last_error = match run_sync(app, root, &config, &mut log) {
+        Ok(()) => None,
+        Err(e) => {
+            tracing::warn!("Task sync failed: {}", e);
+            Some(e)
+        }
+    };
+    log.last_synced = Some(Local::now().to_rfc3339());
+    storage::write_json(&path, &log)?;
+    Ok(status(&log))
+}
+
+fn is_configured(config: &TaskSyncConfig) -> bool {
+    non_empty(&config.todo_txt).is_some() || non_empty(&config.reminders_list).is_some()
+}
+
+// Syncs every watched workspace that has somewhere to send its tasks
+pub fn spawn_scheduler(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(SYNC_INTERVAL);
+        if app.state::<SafeModeState>().is_active() {
+            continue;
+        }
+
+        for root in app.state::<WatcherState>().watched_roots() {
+            // Task text would leave the vault in plaintext
+            if vault::is_vault_root(&root) || !is_configured(&load(&root)) {
+                continue;
+            }
+            if let Err(e) = sync_workspace(&app, &root) {
+                tracing::error!("Failed to sync tasks: {}", e);
+            }
+        }
+    });
+}
+
+fn workspace_root(folder_path: &str) -> Result<PathBuf, String> {
+    let root = PathBuf::from(folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+    if vault::is_vault_root(&root) {
+        return Err("Tasks in encrypted vaults can't be exported".to_string());
+    }
+    Ok(root)
+}
+
+#[tauri::command]
+pub fn get_task_sync(folder_path: String) -> Result<TaskSyncConfig, String> {
+    Ok(load(&workspace_root(&folder_path)?))
+}
+
+#[tauri::command]
+pub fn set_task_sync(
+    folder_path: String,
+    config: TaskSyncConfig,
+) -> Result<TaskSyncConfig, String> {
+    let root = workspace_root(&folder_path)?;
+    let config = TaskSyncConfig {
+        todo_txt: non_empty(&config.todo_txt),
+        reminders_list: non_empty(&config.reminders_list),
+        include_undated: config.include_undated,
+    };
+    if config
+        .reminders_list
+        .as_ref()
+        .is_some_and(|list| list.chars().count() > MAX_LIST_CHARS)
+    {
+        return Err("Reminders list name is too long".to_string());
+    }
+    if let Some(path) = todo_txt_path(&root, &config) {
+        if path.is_dir() {
+            return Err("todo.txt location is a folder".to_string());
+        }
+    }
+
+    fs::create_dir_all(root.join(CONFIG_DIR))
+        .map_err(|e| format!("Failed to create workspace config folder: {}", e))?;
+    storage::write_json(&config_path(&root), &config)?;
+    Ok(load(&root))
+}
+
+#[tauri::command]
+pub fn get_task_sync_status(folder_path: String, app: AppHandle) -> Result<TaskSyncStatus, String> {
+    let root = workspace_root(&folder_path)?;
+    Ok(status(&storage::read_json(&log_path(&app, &root)?)))
+}
+
+#[tauri::command]
+pub async fn sync_tasks(folder_path: String, app: AppHandle) -> Result<TaskSyncStatus, String> {
+    let root = workspace_root(&folder_path)?;
+    tauri::async_runtime::spawn_blocking(move || sync_workspace(&app, &root))
+        .await
+        .map_err(|e| format!("Task sync failed: {}", e))?
+}
@@ -0,0 +1,208 @@
+use crate::settings::SettingsState;
+use crate::storage;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+const TELEMETRY_FILE: &str = "telemetry.json";
+
+// Everything that can be counted or timed. Events are fixed names rather than
+// free text, so nothing from a note (its title, path, or a search) can end
+// up in the report.
+pub const FEATURES: &[&str] = &[
+    "command_palette",
+    "compare_notes",
+    "conflict_merge",
+    "export_note",
+    "graph",
+    "profile_export",
+    "profile_import",
+    "review_changes",
+    "search",
+    "snippet_expansion",
+];
+pub const TIMINGS: &[&str] = &["note_save", "search", "workspace_scan"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Timing {
+    count: u64,
+    total_ms: f64,
+    max_ms: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Counters {
+    // When counting started, in ms since the epoch; 0 before the first event
+    since: i64,
+    features: BTreeMap<String, u64>,
+    timings: BTreeMap<String, Timing>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TimingSummary {
+    count: u64,
+    mean_ms: f64,
+    max_ms: f64,
+}
+
+// Exactly what would be sent, and nothing else. There's no collection
+// endpoint yet, so a report only leaves the machine when the user copies it.
+#[derive(Debug, Serialize)]
+pub struct TelemetryReport {
+    app_version: String,
+    os: String,
+    arch: String,
+    since: Option<i64>,
+    features: BTreeMap<String, u64>,
+    timings: BTreeMap<String, TimingSummary>,
+}
+
+// Usage counts since the user opted in; nothing is recorded while they're out
+pub struct TelemetryState {
+    counters: Mutex<Counters>,
+}
+
+fn telemetry_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(storage::app_data_dir(app)?.join(TELEMETRY_FILE))
+}
+
+fn enabled(app: &AppHandle) -> bool {
+    app.try_state::<SettingsState>()
+        .is_some_and(|settings| settings.current().telemetry)
+}
+
+fn round(ms: f64) -> f64 {
+    (ms * 10.0).round() / 10.0
+}
+
+impl TelemetryState {
+    pub fn load(app: &AppHandle) -> TelemetryState {
+        let counters = telemetry_path(app)
+            .map(|path| storage::read_json(&path))
+            .unwrap_or_default();
+        TelemetryState {
+            counters: Mutex::new(counters),
+        }
+    }
+
+    fn update(&self, app: &AppHandle, edit: impl FnOnce(&mut Counters)) {
+        if !enabled(app) {
+            return;
+        }
+        let Ok(mut counters) = self.counters.lock() else {
+            return;
+        };
+        if counters.since == 0 {
+            counters.since = Utc::now().timestamp_millis();
+        }
+        edit(&mut counters);
+        if let Err(e) = telemetry_path(app).and_then(|path| storage::write_json(&path, &*counters))
+        {
+            tracing::warn!("Failed to save usage statistics: {}", e);
+        }
+    }
+
+    fn report(&self, app: &AppHandle) -> TelemetryReport {
+        let counters = self
+            .counters
+            .lock()
+            .map(|counters| counters.clone())
+            .unwrap_or_default();
+        TelemetryReport {
+            app_version: app.package_info().version.to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            since: (counters.since > 0).then_some(counters.since),
+            features: counters.features,
+            timings: counters
+                .timings
+                .into_iter()
+                .map(|(name, timing)| {
+                    let summary = TimingSummary {
+                        count: timing.count,
+                        mean_ms: round(timing.total_ms / timing.count.max(1) as f64),
+                        max_ms: round(timing.max_ms),
+                    };
+                    (name, summary)
+                })
+                .collect(),
+        }
+    }
+}
+
+// Counts one use of `feature`, one of FEATURES
+pub fn record_feature(app: &AppHandle, feature: &str) {
+    let Some(state) = app.try_state::<TelemetryState>() else {
+        return;
+    };
+    state.update(app, |counters| {
+        *counters.features.entry(feature.to_string()).or_default() += 1;
+    });
+}
+
+// Adds one measurement of `name`, one of TIMINGS
+pub fn record_timing(app: &AppHandle, name: &str, elapsed: Duration) {
+    let Some(state) = app.try_state::<TelemetryState>() else {
+        return;
+    };
+    let ms = elapsed.as_secs_f64() * 1000.0;
+    state.update(app, |counters| {
+        let timing = counters.timings.entry(name.to_string()).or_default();
+        timing.count += 1;
+        timing.total_ms += ms;
+        timing.max_ms = timing.max_ms.max(ms);
+    });
+}
+
+// Forgets everything recorded so far, as turning telemetry off does
+pub fn clear(app: &AppHandle) -> Result<(), String> {
+    if let Some(state) = app.try_state::<TelemetryState>() {
+        if let Ok(mut counters) = state.counters.lock() {
+            *counters = Counters::default();
+        }
+    }
+    let path = telemetry_path(app)?;
+    if path.exists() {
+        std::fs::remove_file(&path)
+            .map_err(|e| format!("Failed to clear usage statistics: {}", e))?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn record_feature_use(feature: String, app: AppHandle) -> Result<(), String> {
+    if !FEATURES.contains(&feature.as_str()) {
+        return Err(format!("Unknown feature: {}", feature));
+    }
+    record_feature(&app, &feature);
+    Ok(())
+}
+
+// For timings only the frontend can take, like a search over loaded notes
+#[tauri::command]
+pub fn record_feature_timing(name: String, ms: f64, app: AppHandle) -> Result<(), String> {
+    if !TIMINGS.contains(&name.as_str()) {
+        return Err(format!("Unknown timing: {}", name));
+    }
+    // Negative, NaN, and absurd values aren't measurements
+    if let Ok(elapsed) = Duration::try_from_secs_f64(ms / 1000.0) {
+        record_timing(&app, &name, elapsed);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_telemetry_preview(app: AppHandle, state: State<TelemetryState>) -> TelemetryReport {
+    state.report(&app)
+}
+
+#[tauri::command]
+pub fn clear_telemetry(app: AppHandle) -> Result<(), String> {
+    clear(&app)
+}
@@ -0,0 +1,115 @@
+use crate::settings::SettingsState;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, State, Url};
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+const STABLE_ENDPOINT: &str =
+    "https://github.com/amiralibg/marky/releases/latest/download/latest.json";
+// Pre-releases publish their manifest to a rolling `beta` tag
+const BETA_ENDPOINT: &str = "https://github.com/amiralibg/marky/releases/download/beta/latest.json";
+
+pub const CHANNELS: [&str; 2] = ["stable", "beta"];
+
+#[derive(Debug, Serialize)]
+pub struct UpdateInfo {
+    version: String,
+    current_version: String,
+    channel: String,
+    notes: Option<String>,
+    date: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct DownloadProgress {
+    downloaded: u64,
+    content_length: Option<u64>,
+}
+
+// The last update found by check_for_updates, kept so install_update applies exactly that one
+#[derive(Default)]
+pub struct UpdaterState {
+    pending: Mutex<Option<Update>>,
+}
+
+fn endpoint(channel: &str) -> &'static str {
+    match channel {
+        "beta" => BETA_ENDPOINT,
+        _ => STABLE_ENDPOINT,
+    }
+}
+
+#[tauri::command]
+pub async fn check_for_updates(
+    app: AppHandle,
+    settings_state: State<'_, SettingsState>,
+    updater_state: State<'_, UpdaterState>,
+) -> Result<Option<UpdateInfo>, String> {
+    let channel = settings_state.current().update_channel;
+    let url =
+        Url::parse(endpoint(&channel)).map_err(|e| format!("Invalid update endpoint: {}", e))?;
+
+    let update = app
+        .updater_builder()
+        .endpoints(vec![url])
+        .and_then(|builder| builder.build())
+        .map_err(|e| format!("Failed to configure updater: {}", e))?
+        .check()
+        .await
+        .map_err(|e| format!("Failed to check for updates: {}", e))?;
+
+    let info = update.as_ref().map(|update| UpdateInfo {
+        version: update.version.clone(),
+        current_version: update.current_version.clone(),
+        channel: channel.clone(),
+        notes: update.body.clone(),
+        date: update.date.map(|date| date.to_string()),
+    });
+
+    *updater_state
+        .pending
+        .lock()
+        .map_err(|e| format!("Failed to lock updater state: {}", e))? = update;
+
+    Ok(info)
+}
+
+// Progress goes out as `update-download-progress` events; relaunching is left to the user
+#[tauri::command]
+pub async fn install_update(
+    app: AppHandle,
+    updater_state: State<'_, UpdaterState>,
+) -> Result<(), String> {
+    let update = updater_state
+        .pending
+        .lock()
+        .map_err(|e| format!("Failed to lock updater state: {}", e))?
+        .clone()
+        .ok_or("No update is ready to install")?;
+
+    let mut downloaded: u64 = 0;
+    update
+        .download_and_install(
+            |chunk_length, content_length| {
+                downloaded += chunk_length as u64;
+                let _ = app.emit(
+                    "update-download-progress",
+                    DownloadProgress {
+                        downloaded,
+                        content_length,
+                    },
+                );
+            },
+            || {
+                let _ = app.emit("update-downloaded", ());
+            },
+        )
+        .await
+        .map_err(|e| format!("Failed to install update: {}", e))?;
+
+    if let Ok(mut pending) = updater_state.pending.lock() {
+        *pending = None;
+    }
+
+    Ok(())
+}
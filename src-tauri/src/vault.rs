@@ -0,0 +1,622 @@
+use crate::i18n;
+use crate::secure_delete;
+use crate::settings::SettingsState;
+use crate::watcher::WatcherState;
+use crate::workspace_config::CONFIG_DIR;
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::Argon2;
+use base64::engine::general_purpose::{STANDARD as BASE64, URL_SAFE_NO_PAD};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+const VAULT_FILE: &str = "vault.json";
+const VAULT_EXTENSION: &str = "mvault";
+const VAULT_VERSION: u32 = 1;
+const CONTENT_MAGIC: &[u8] = b"MKV1";
+const VERIFIER: &[u8] = b"marky-vault";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const AUTO_LOCK_CHECK: Duration = Duration::from_secs(30);
+
+// Only the salt and a verifier live in the workspace; the key never touches disk
+#[derive(Debug, Serialize, Deserialize)]
+struct VaultHeader {
+    version: u32,
+    kdf: String,
+    salt: String,
+    verifier: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VaultStatus {
+    is_vault: bool,
+    unlocked: bool,
+    auto_lock_minutes: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct VaultLockedEvent {
+    workspace: String,
+}
+
+// Separate keys for contents, names, and the name nonces, all derived from the master key
+struct VaultKeys {
+    content: Key<Aes256Gcm>,
+    names: Key<Aes256Gcm>,
+    name_nonces: [u8; 32],
+}
+
+struct UnlockedVault {
+    keys: VaultKeys,
+    last_used: Instant,
+}
+
+#[derive(Default)]
+pub struct VaultState {
+    vaults: Mutex<HashMap<PathBuf, UnlockedVault>>,
+}
+
+impl VaultState {
+    // Runs `f` with the vault's keys, counting the access as activity for auto-lock
+    fn with_keys<R>(
+        &self,
+        root: &Path,
+        f: impl FnOnce(&VaultKeys) -> Result<R, String>,
+    ) -> Result<R, String> {
+        let mut vaults = self
+            .vaults
+            .lock()
+            .map_err(|e| i18n::tf("error.vault_state_lock", &[&e]))?;
+        let vault = vaults
+            .get_mut(root)
+            .ok_or_else(|| i18n::t("error.vault_locked"))?;
+        vault.last_used = Instant::now();
+        f(&vault.keys)
+    }
+
+    fn is_unlocked(&self, root: &Path) -> bool {
+        self.vaults
+            .lock()
+            .map(|vaults| vaults.contains_key(root))
+            .unwrap_or(false)
+    }
+
+    fn lock(&self, root: &Path) -> bool {
+        self.vaults
+            .lock()
+            .map(|mut vaults| vaults.remove(root).is_some())
+            .unwrap_or(false)
+    }
+
+    fn lock_idle(&self, timeout: Duration) -> Vec<PathBuf> {
+        let Ok(mut vaults) = self.vaults.lock() else {
+            return Vec::new();
+        };
+        let idle: Vec<PathBuf> = vaults
+            .iter()
+            .filter(|(_, vault)| vault.last_used.elapsed() >= timeout)
+            .map(|(root, _)| root.clone())
+            .collect();
+        for root in &idle {
+            vaults.remove(root);
+        }
+        idle
+    }
+}
+
+fn header_path(root: &Path) -> PathBuf {
+    root.join(CONFIG_DIR).join(VAULT_FILE)
+}
+
+pub fn is_vault_root(root: &Path) -> bool {
+    header_path(root).is_file()
+}
+
+// The vault a path lives in, whether or not it's unlocked
+pub fn vault_root(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|ancestor| is_vault_root(ancestor))
+        .map(Path::to_path_buf)
+}
+
+fn sha256(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+fn derive_keys(passphrase: &str, salt: &[u8]) -> Result<VaultKeys, String> {
+    let mut master = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut master)
+        .map_err(|e| i18n::tf("error.vault_derive_key", &[&e]))?;
+
+    Ok(VaultKeys {
+        content: Key::<Aes256Gcm>::from(sha256(&[b"marky-vault-content", &master])),
+        names: Key::<Aes256Gcm>::from(sha256(&[b"marky-vault-names", &master])),
+        name_nonces: sha256(&[b"marky-vault-name-nonces", &master]),
+    })
+}
+
+fn random_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; len];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+fn seal(key: &Key<Aes256Gcm>, nonce: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    Aes256Gcm::new(key)
+        .encrypt(Nonce::from_slice(nonce), plaintext)
+        .map_err(|_| i18n::t("error.vault_encrypt"))
+}
+
+fn unseal(key: &Key<Aes256Gcm>, sealed: &[u8]) -> Option<Vec<u8>> {
+    if sealed.len() < NONCE_LEN {
+        return None;
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    Aes256Gcm::new(key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .ok()
+}
+
+// Names need a deterministic encoding so a plaintext path maps to exactly one file.
+// The nonce is derived from the name itself (the SIV idea), so equal names encrypt
+// equally but no two different names ever share a nonce. Names don't depend on their
+// folder, which keeps renaming a folder to a single rename on disk.
+fn encrypt_name(keys: &VaultKeys, name: &str, is_file: bool) -> Result<String, String> {
+    let nonce = &sha256(&[&keys.name_nonces, name.as_bytes()])[..NONCE_LEN];
+    let mut sealed = nonce.to_vec();
+    sealed.extend(seal(&keys.names, nonce, name.as_bytes())?);
+
+    let encoded = URL_SAFE_NO_PAD.encode(sealed);
+    let encoded = if is_file {
+        format!("{}.{}", encoded, VAULT_EXTENSION)
+    } else {
+        encoded
+    };
+    // Most filesystems cap a single name at 255 bytes
+    if encoded.len() > 255 {
+        return Err(i18n::t("error.vault_name_too_long"));
+    }
+    Ok(encoded)
+}
+
+fn decrypt_name(keys: &VaultKeys, encoded: &str) -> Option<(String, bool)> {
+    let (encoded, is_file) = match encoded.strip_suffix(&format!(".{}", VAULT_EXTENSION)) {
+        Some(stem) => (stem, true),
+        None => (encoded, false),
+    };
+    let sealed = URL_SAFE_NO_PAD.decode(encoded).ok()?;
+    let name = String::from_utf8(unseal(&keys.names, &sealed)?).ok()?;
+    Some((name, is_file))
+}
+
+fn encrypt_content(keys: &VaultKeys, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let nonce = random_bytes(NONCE_LEN);
+    let mut data = CONTENT_MAGIC.to_vec();
+    data.extend(&nonce);
+    data.extend(seal(&keys.content, &nonce, plaintext)?);
+    Ok(data)
+}
+
+fn decrypt_content(keys: &VaultKeys, data: &[u8]) -> Result<Vec<u8>, String> {
+    data.strip_prefix(CONTENT_MAGIC)
+        .and_then(|sealed| unseal(&keys.content, sealed))
+        .ok_or_else(|| i18n::t("error.vault_decrypt"))
+}
+
+fn relative_names(root: &Path, path: &Path) -> Result<Vec<String>, String> {
+    let relative = path
+        .strip_prefix(root)
+        .map_err(|_| i18n::t("error.vault_outside"))?;
+    relative
+        .components()
+        .map(|part| match part {
+            Component::Normal(name) => Ok(name.to_string_lossy().to_string()),
+            _ => Err(i18n::t("error.vault_invalid_path")),
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    File,
+    Dir,
+    // Whichever exists, preferring a file
+    Existing,
+}
+
+fn physical_path(
+    keys: &VaultKeys,
+    root: &Path,
+    path: &Path,
+    kind: Kind,
+) -> Result<PathBuf, String> {
+    let names = relative_names(root, path)?;
+    let Some((last, parents)) = names.split_last() else {
+        return Ok(root.to_path_buf());
+    };
+
+    let mut physical = root.to_path_buf();
+    for name in parents {
+        physical.push(encrypt_name(keys, name, false)?);
+    }
+
+    let as_file = physical.join(encrypt_name(keys, last, true)?);
+    let as_dir = physical.join(encrypt_name(keys, last, false)?);
+    Ok(match kind {
+        Kind::File => as_file,
+        Kind::Dir => as_dir,
+        Kind::Existing if as_file.is_file() || !as_dir.exists() => as_file,
+        Kind::Existing => as_dir,
+    })
+}
+
+fn resolve(app: &AppHandle, path: &Path, kind: Kind) -> Result<PathBuf, String> {
+    let Some(root) = vault_root(path) else {
+        return Ok(path.to_path_buf());
+    };
+    app.state::<VaultState>()
+        .with_keys(&root, |keys| physical_path(keys, &root, path, kind))
+}
+
+// Maps a file inside an unlocked vault back to the path the frontend knows it by.
+// Works for deleted files too, since the names alone are enough.
+pub fn to_virtual(app: &AppHandle, root: &Path, physical: &Path) -> Option<PathBuf> {
+    let relative = physical.strip_prefix(root).ok()?;
+    app.state::<VaultState>()
+        .with_keys(root, |keys| {
+            let mut path = root.to_path_buf();
+            for part in relative.components() {
+                let Component::Normal(name) = part else {
+                    return Err(String::new());
+                };
+                let (name, _) =
+                    decrypt_name(keys, &name.to_string_lossy()).ok_or_else(String::new)?;
+                path.push(name);
+            }
+            Ok(path)
+        })
+        .ok()
+}
+
+// Where an entry's ciphertext lives on disk; plain paths come back unchanged
+pub fn physical_location(app: &AppHandle, path: &Path) -> Result<PathBuf, String> {
+    resolve(app, path, Kind::Existing)
+}
+
+pub fn ensure_unlocked(app: &AppHandle, path: &Path) -> Result<(), String> {
+    resolve(app, path, Kind::Existing).map(|_| ())
+}
+
+pub fn exists(app: &AppHandle, path: &Path) -> bool {
+    resolve(app, path, Kind::Existing)
+        .map(|physical| physical.exists())
+        .unwrap_or(false)
+}
+
+pub fn is_dir(app: &AppHandle, path: &Path) -> bool {
+    resolve(app, path, Kind::Existing)
+        .map(|physical| physical.is_dir())
+        .unwrap_or(false)
+}
+
+pub fn read_file(app: &AppHandle, path: &Path) -> Result<String, String> {
+    let root = vault_root(path).ok_or_else(|| i18n::t("error.vault_outside"))?;
+    app.state::<VaultState>().with_keys(&root, |keys| {
+        let physical = physical_path(keys, &root, path, Kind::File)?;
+        let data = fs::read(&physical).map_err(|e| i18n::tf("error.read_note", &[&e]))?;
+        String::from_utf8(decrypt_content(keys, &data)?).map_err(|_| i18n::t("error.note_not_text"))
+    })
+}
+
+pub fn write_file(app: &AppHandle, path: &Path, content: &str) -> Result<(), String> {
+    let root = vault_root(path).ok_or_else(|| i18n::t("error.vault_outside"))?;
+    app.state::<VaultState>().with_keys(&root, |keys| {
+        let physical = physical_path(keys, &root, path, Kind::File)?;
+        let data = encrypt_content(keys, content.as_bytes())?;
+        // Replace atomically so a crash can't leave a truncated ciphertext behind
+        let tmp = physical.with_extension("tmp");
+        fs::write(&tmp, data).map_err(|e| i18n::tf("error.save_note", &[&e]))?;
+        fs::rename(&tmp, &physical).map_err(|e| i18n::tf("error.save_note", &[&e]))
+    })
+}
+
+pub fn create_dir(app: &AppHandle, path: &Path) -> Result<(), String> {
+    let physical = resolve(app, path, Kind::Dir)?;
+    fs::create_dir(&physical).map_err(|e| i18n::tf("error.create_folder", &[&e]))
+}
+
+pub fn rename(app: &AppHandle, from: &Path, to: &Path) -> Result<(), String> {
+    let source = resolve(app, from, Kind::Existing)?;
+    let kind = if source.is_dir() {
+        Kind::Dir
+    } else {
+        Kind::File
+    };
+    let target = resolve(app, to, kind)?;
+    fs::rename(&source, &target).map_err(|e| i18n::tf("error.move_entry", &[&e]))
+}
+
+pub fn remove(app: &AppHandle, path: &Path) -> Result<(), String> {
+    let physical = resolve(app, path, Kind::Existing)?;
+    let secure = app.state::<SettingsState>().current().secure_delete;
+    secure_delete::remove(&physical, secure)
+}
+
+// Every decryptable entry as (plaintext path, is_dir); anything else in the folder is skipped
+pub fn list(app: &AppHandle, root: &Path) -> Result<Vec<(PathBuf, bool)>, String> {
+    fn walk(
+        keys: &VaultKeys,
+        physical: &Path,
+        virtual_dir: &Path,
+        entries: &mut Vec<(PathBuf, bool)>,
+    ) -> Result<(), String> {
+        let dir = fs::read_dir(physical).map_err(|e| i18n::tf("error.read_directory", &[&e]))?;
+        for entry in dir.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') {
+                continue;
+            }
+            let Some((plain, is_file)) = decrypt_name(keys, &name) else {
+                continue;
+            };
+
+            let path = entry.path();
+            let virtual_path = virtual_dir.join(plain);
+            if !is_file && path.is_dir() {
+                entries.push((virtual_path.clone(), true));
+                walk(keys, &path, &virtual_path, entries)?;
+            } else if is_file && path.is_file() {
+                entries.push((virtual_path, false));
+            }
+        }
+        Ok(())
+    }
+
+    app.state::<VaultState>().with_keys(root, |keys| {
+        let mut entries = Vec::new();
+        walk(keys, root, root, &mut entries)?;
+        Ok(entries)
+    })
+}
+
+fn read_header(root: &Path) -> Result<VaultHeader, String> {
+    let raw =
+        fs::read_to_string(header_path(root)).map_err(|e| i18n::tf("error.vault_read", &[&e]))?;
+    let header: VaultHeader =
+        serde_json::from_str(&raw).map_err(|_| i18n::t("error.vault_header"))?;
+    if header.version != VAULT_VERSION {
+        return Err(i18n::tf("error.vault_version", &[&header.version]));
+    }
+    Ok(header)
+}
+
+fn unlock_keys(root: &Path, passphrase: &str) -> Result<VaultKeys, String> {
+    let header = read_header(root)?;
+    let salt = BASE64
+        .decode(&header.salt)
+        .map_err(|_| i18n::t("error.vault_header"))?;
+    let verifier = BASE64
+        .decode(&header.verifier)
+        .map_err(|_| i18n::t("error.vault_header"))?;
+
+    let keys = derive_keys(passphrase, &salt)?;
+    match unseal(&keys.content, &verifier) {
+        Some(plain) if plain == VERIFIER => Ok(keys),
+        _ => Err(i18n::t("error.vault_passphrase")),
+    }
+}
+
+fn remove_empty_dirs(dir: &Path) {
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() && !entry.file_name().to_string_lossy().starts_with('.') {
+                remove_empty_dirs(&path);
+                let _ = fs::remove_dir(&path);
+            }
+        }
+    }
+}
+
+// Collects every regular file outside hidden folders, attachments included
+fn collect_plain_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries = fs::read_dir(dir).map_err(|e| i18n::tf("error.read_directory", &[&e]))?;
+    for entry in entries.flatten() {
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        if path.is_dir() {
+            collect_plain_files(&path, files)?;
+        } else if path.is_file() {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+// Idle vaults lock themselves; the frontend drops its plaintext when told
+pub fn spawn_auto_lock(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(AUTO_LOCK_CHECK);
+        let minutes = app
+            .state::<SettingsState>()
+            .current()
+            .vault_auto_lock_minutes;
+        if minutes == 0 {
+            continue;
+        }
+        for root in app
+            .state::<VaultState>()
+            .lock_idle(Duration::from_secs(minutes * 60))
+        {
+            emit_locked(&app, &root);
+        }
+    });
+}
+
+fn emit_locked(app: &AppHandle, root: &Path) {
+    let payload = VaultLockedEvent {
+        workspace: root.to_string_lossy().to_string(),
+    };
+    for label in app.state::<WatcherState>().windows_for(root) {
+        let _ = app.emit_to(label.as_str(), "vault-locked", payload.clone());
+    }
+}
+
+// Converts a workspace into a vault: everything is encrypted alongside the plaintext
+// first, the header is written, and only then are the plaintext files removed, so an
+// interruption never leaves notes that can't be opened
+#[tauri::command]
+pub fn create_vault(
+    folder_path: String,
+    passphrase: String,
+    app: AppHandle,
+    vault_state: State<VaultState>,
+) -> Result<VaultStatus, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err(i18n::t("error.folder_missing"));
+    }
+    if vault_root(&root).is_some() {
+        return Err(i18n::t("error.vault_nested"));
+    }
+    if passphrase.is_empty() {
+        return Err(i18n::t("error.passphrase_empty"));
+    }
+
+    let salt = random_bytes(SALT_LEN);
+    let keys = derive_keys(&passphrase, &salt)?;
+
+    let mut files = Vec::new();
+    collect_plain_files(&root, &mut files)?;
+
+    for file in &files {
+        let physical = physical_path(&keys, &root, file, Kind::File)?;
+        if let Some(parent) = physical.parent() {
+            fs::create_dir_all(parent).map_err(|e| i18n::tf("error.vault_create_folder", &[&e]))?;
+        }
+        let data = fs::read(file).map_err(|e| i18n::tf("error.read_file", &[&e]))?;
+        fs::write(&physical, encrypt_content(&keys, &data)?)
+            .map_err(|e| i18n::tf("error.vault_write_file", &[&e]))?;
+    }
+
+    let verifier_nonce = random_bytes(NONCE_LEN);
+    let mut verifier = verifier_nonce.clone();
+    verifier.extend(seal(&keys.content, &verifier_nonce, VERIFIER)?);
+    let header = VaultHeader {
+        version: VAULT_VERSION,
+        kdf: "argon2id".to_string(),
+        salt: BASE64.encode(&salt),
+        verifier: BASE64.encode(&verifier),
+    };
+    fs::create_dir_all(root.join(CONFIG_DIR))
+        .map_err(|e| i18n::tf("error.config_folder", &[&e]))?;
+    let raw = serde_json::to_string_pretty(&header)
+        .map_err(|e| i18n::tf("error.vault_serialize", &[&e]))?;
+    fs::write(header_path(&root), raw).map_err(|e| i18n::tf("error.vault_write", &[&e]))?;
+
+    let secure = app.state::<SettingsState>().current().secure_delete;
+    for file in &files {
+        if let Err(e) = secure_delete::remove(file, secure) {
+            tracing::error!("Failed to remove a plaintext file: {}", e);
+        }
+    }
+    // Plaintext folders are empty now; encrypted ones still hold their files
+    remove_empty_dirs(&root);
+
+    if let Ok(mut vaults) = vault_state.vaults.lock() {
+        vaults.insert(
+            root.clone(),
+            UnlockedVault {
+                keys,
+                last_used: Instant::now(),
+            },
+        );
+    }
+
+    Ok(VaultStatus {
+        is_vault: true,
+        unlocked: true,
+        auto_lock_minutes: app
+            .state::<SettingsState>()
+            .current()
+            .vault_auto_lock_minutes,
+    })
+}
+
+#[tauri::command]
+pub fn unlock_vault(
+    folder_path: String,
+    passphrase: String,
+    app: AppHandle,
+    vault_state: State<VaultState>,
+) -> Result<VaultStatus, String> {
+    let root = PathBuf::from(&folder_path);
+    if !is_vault_root(&root) {
+        return Err(i18n::t("error.not_a_vault"));
+    }
+
+    let keys = unlock_keys(&root, &passphrase)?;
+    vault_state
+        .vaults
+        .lock()
+        .map_err(|e| i18n::tf("error.vault_state_lock", &[&e]))?
+        .insert(
+            root,
+            UnlockedVault {
+                keys,
+                last_used: Instant::now(),
+            },
+        );
+
+    Ok(VaultStatus {
+        is_vault: true,
+        unlocked: true,
+        auto_lock_minutes: app
+            .state::<SettingsState>()
+            .current()
+            .vault_auto_lock_minutes,
+    })
+}
+
+#[tauri::command]
+pub fn lock_vault(
+    folder_path: String,
+    app: AppHandle,
+    vault_state: State<VaultState>,
+) -> Result<(), String> {
+    let root = PathBuf::from(&folder_path);
+    if vault_state.lock(&root) {
+        emit_locked(&app, &root);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_vault_status(
+    folder_path: String,
+    vault_state: State<VaultState>,
+    settings_state: State<SettingsState>,
+) -> VaultStatus {
+    let root = PathBuf::from(&folder_path);
+    VaultStatus {
+        is_vault: is_vault_root(&root),
+        unlocked: vault_state.is_unlocked(&root),
+        auto_lock_minutes: settings_state.current().vault_auto_lock_minutes,
+    }
+}
@@ -0,0 +1,490 @@
+use crate::index::IndexState;
+use crate::note_ids;
+use crate::notifications;
+use crate::pins;
+use crate::safe_mode::SafeModeState;
+use crate::settings::SettingsState;
+use crate::smart_folders;
+use crate::storage;
+use crate::vault;
+use crate::windows;
+use crate::workspace_config::{self, NoteFilter};
+use crate::workspaces;
+use chrono::Utc;
+use notify_debouncer_full::{
+    new_debouncer,
+    notify::{event::ModifyKind, RecursiveMode, Watcher},
+    DebounceEventResult, Debouncer, FileIdMap,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State, Window};
+
+type FolderWatcher = Debouncer<notify::RecommendedWatcher, FileIdMap>;
+
+#[derive(Debug, Serialize, Clone)]
+struct FileChangeEvent {
+    event_type: String,
+    path: String,
+    workspace: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct WatchOverflowEvent {
+    workspace: String,
+    reason: String,
+}
+
+// What went wrong with a workspace's watcher since it started
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct WatchHealth {
+    // Times the OS dropped events and asked for a rescan
+    overflows: u32,
+    last_overflow: Option<i64>,
+    // The last watcher error, until events arrive again
+    last_error: Option<String>,
+    rescanning: bool,
+    last_rescan: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WatcherStatus {
+    workspace: Option<String>,
+    // Changes are reported live, rather than picked up on focus
+    live: bool,
+    healthy: bool,
+    #[serde(flatten)]
+    health: WatchHealth,
+}
+
+#[derive(Default)]
+pub struct WatcherState {
+    // One watcher per open workspace so every index stays current; `None` when
+    // file watching is turned off and the workspace is only tracked
+    watchers: Mutex<HashMap<PathBuf, Option<FolderWatcher>>>,
+    // Window label -> the workspace that window is showing
+    active: Mutex<HashMap<String, PathBuf>>,
+    health: Mutex<HashMap<PathBuf, WatchHealth>>,
+}
+
+impl WatcherState {
+    // The workspace a window is showing; changes are only emitted to windows showing them
+    pub fn active_root(&self, label: &str) -> Option<PathBuf> {
+        self.active
+            .lock()
+            .ok()
+            .and_then(|active| active.get(label).cloned())
+    }
+
+    pub fn set_active_root(&self, label: &str, root: Option<PathBuf>) {
+        if let Ok(mut active) = self.active.lock() {
+            match root {
+                Some(root) => active.insert(label.to_string(), root),
+                None => active.remove(label),
+            };
+        }
+    }
+
+    pub fn windows_for(&self, root: &Path) -> Vec<String> {
+        self.active
+            .lock()
+            .map(|active| {
+                active
+                    .iter()
+                    .filter(|(_, active_root)| active_root.as_path() == root)
+                    .map(|(label, _)| label.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn is_active(&self, root: &Path) -> bool {
+        !self.windows_for(root).is_empty()
+    }
+
+    pub fn is_watching(&self, root: &Path) -> bool {
+        self.watchers
+            .lock()
+            .map(|watchers| watchers.contains_key(root))
+            .unwrap_or(false)
+    }
+
+    fn is_live(&self, root: &Path) -> bool {
+        self.watchers
+            .lock()
+            .map(|watchers| matches!(watchers.get(root), Some(Some(_))))
+            .unwrap_or(false)
+    }
+
+    pub fn watched_roots(&self) -> Vec<PathBuf> {
+        self.watchers
+            .lock()
+            .map(|watchers| watchers.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn health(&self, root: &Path) -> WatchHealth {
+        self.health
+            .lock()
+            .ok()
+            .and_then(|health| health.get(root).cloned())
+            .unwrap_or_default()
+    }
+
+    fn update_health(&self, root: &Path, f: impl FnOnce(&mut WatchHealth)) {
+        if let Ok(mut health) = self.health.lock() {
+            f(health.entry(root.to_path_buf()).or_default());
+        }
+    }
+
+    // Records the overflow or error and claims the rescan; false when one is
+    // already running, since it will pick up whatever was missed this time too
+    fn begin_rescan(&self, root: &Path, error: Option<String>) -> bool {
+        let mut started = false;
+        self.update_health(root, |health| {
+            match error {
+                Some(error) => health.last_error = Some(error),
+                None => {
+                    health.overflows += 1;
+                    health.last_overflow = Some(Utc::now().timestamp_millis());
+                }
+            }
+            if !health.rescanning {
+                health.rescanning = true;
+                started = true;
+            }
+        });
+        started
+    }
+
+    // Left alone if the workspace was closed while the rescan ran
+    fn finish_rescan(&self, root: &Path) {
+        if let Some(health) = self
+            .health
+            .lock()
+            .ok()
+            .as_mut()
+            .and_then(|health| health.get_mut(root))
+        {
+            health.rescanning = false;
+            health.last_rescan = Some(Utc::now().timestamp_millis());
+        }
+    }
+
+    // Deepest watched workspace containing `path`, independent of which window asks
+    pub fn root_for(&self, path: &Path) -> Option<PathBuf> {
+        self.watchers
+            .lock()
+            .ok()?
+            .keys()
+            .filter(|root| path.starts_with(root))
+            .max_by_key(|root| root.components().count())
+            .cloned()
+    }
+}
+
+fn create_watcher(app: &AppHandle, root: &Path) -> Result<FolderWatcher, String> {
+    let debounce_ms = app.state::<SettingsState>().current().watch_debounce_ms;
+    let app_clone = app.clone();
+    let root_clone = root.to_path_buf();
+
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(debounce_ms),
+        None,
+        move |result: DebounceEventResult| match result {
+            Ok(events) => {
+                let root = &root_clone;
+                let index_state = app_clone.state::<IndexState>();
+                let settings = app_clone.state::<SettingsState>().current();
+                let emit_changes = app_clone.state::<WatcherState>().is_active(root);
+                // Built per batch so settings and .marky/config.json edits apply without re-watching
+                let filter = NoteFilter::for_workspace(root, &settings);
+                let is_vault = vault::is_vault_root(root);
+
+                let watcher_state = app_clone.state::<WatcherState>();
+                watcher_state.update_health(root, |health| health.last_error = None);
+                // The OS queue overflowed (inotify) or coalesced a whole subtree
+                // (FSEvents), so the events below are incomplete
+                if events.iter().any(|event| event.need_rescan()) {
+                    tracing::warn!("Watcher dropped events for a workspace; rescanning");
+                    recover(&app_clone, root, "overflow", None);
+                }
+
+                for event in events {
+                    let event_type = match event.kind {
+                        notify::EventKind::Create(_) => "create",
+                        notify::EventKind::Modify(_) => "modify",
+                        notify::EventKind::Remove(_) => "remove",
+                        _ => "other",
+                    };
+
+                    for path in &event.paths {
+                        if workspace_config::is_config_path(root, path) {
+                            index_state.invalidate(root);
+                            if emit_changes {
+                                let config = workspace_config::effective(root, &settings);
+                                for label in app_clone.state::<WatcherState>().windows_for(root) {
+                                    let _ = app_clone.emit_to(
+                                        label.as_str(),
+                                        "workspace-config-changed",
+                                        config.clone(),
+                                    );
+                                }
+                            }
+                            continue;
+                        }
+
+                        if let Some(file_name) = path.file_name() {
+                            let name = file_name.to_string_lossy();
+                            if name.starts_with('.') || filter.is_ignored(path) {
+                                continue;
+                            }
+
+                            // Vault files are opaque on disk, so they're reported by their
+                            // decrypted paths and never indexed; a locked vault stays silent
+                            if is_vault {
+                                let Some(virtual_path) = vault::to_virtual(&app_clone, root, path)
+                                else {
+                                    continue;
+                                };
+                                if emit_changes
+                                    && !filter.is_ignored(&virtual_path)
+                                    && (path.is_dir() || filter.includes_note(&virtual_path))
+                                {
+                                    let change_event = FileChangeEvent {
+                                        event_type: event_type.to_string(),
+                                        path: virtual_path.to_string_lossy().to_string(),
+                                        workspace: root.to_string_lossy().to_string(),
+                                    };
+                                    windows::emit_file_change(
+                                        &app_clone,
+                                        root,
+                                        &virtual_path,
+                                        change_event,
+                                    );
+                                }
+                                continue;
+                            }
+
+                            index_state.apply_change(root, path);
+
+                            if matches!(
+                                event.kind,
+                                notify::EventKind::Modify(ModifyKind::Data(_) | ModifyKind::Any)
+                            ) && filter.includes_note(path)
+                            {
+                                notifications::note_changed(&app_clone, path);
+                            }
+
+                            if emit_changes && (path.is_dir() || filter.includes_note(path)) {
+                                let change_event = FileChangeEvent {
+                                    event_type: event_type.to_string(),
+                                    path: path.to_string_lossy().to_string(),
+                                    workspace: root.to_string_lossy().to_string(),
+                                };
+
+                                windows::emit_file_change(&app_clone, root, path, change_event);
+                            }
+                        }
+                    }
+                }
+
+                if emit_changes && !is_vault {
+                    note_ids::refresh(&app_clone, root);
+                    smart_folders::refresh(&app_clone, root);
+                }
+            }
+            Err(errors) => {
+                tracing::error!("Watch error: {:?}", errors);
+                let message = errors
+                    .iter()
+                    .map(|error| error.to_string())
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                // Events may have been lost along with the error
+                recover(&app_clone, &root_clone, "error", Some(message));
+            }
+        },
+    )
+    .map_err(|e| format!("Failed to create watcher: {}", e))?;
+
+    debouncer
+        .watcher()
+        .watch(root, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch folder: {}", e))?;
+
+    Ok(debouncer)
+}
+
+// Tells the workspace's windows to reload their file tree and brings the index
+// back in line with the disk in the background
+fn recover(app: &AppHandle, root: &Path, reason: &str, error: Option<String>) {
+    let watcher_state = app.state::<WatcherState>();
+    if !watcher_state.begin_rescan(root, error) {
+        return;
+    }
+
+    let payload = WatchOverflowEvent {
+        workspace: root.to_string_lossy().to_string(),
+        reason: reason.to_string(),
+    };
+    let emit_changes = watcher_state.is_active(root);
+    for label in watcher_state.windows_for(root) {
+        let _ = app.emit_to(label.as_str(), "watch-overflow", payload.clone());
+    }
+
+    let app = app.clone();
+    let root = root.to_path_buf();
+    std::thread::spawn(move || {
+        // Vault notes aren't indexed; their windows rescan from the event alone
+        if !vault::is_vault_root(&root) {
+            match app.state::<IndexState>().rescan(&root) {
+                Ok(changed) => {
+                    tracing::info!("Rescan after watcher overflow updated {} notes", changed)
+                }
+                Err(e) => tracing::warn!("Rescan after watcher overflow failed: {}", e),
+            }
+            if emit_changes {
+                note_ids::refresh(&app, &root);
+                smart_folders::refresh(&app, &root);
+            }
+        }
+        app.state::<WatcherState>().finish_rescan(&root);
+    });
+}
+
+// Starts (or reuses) the watcher for `root` and makes it the window's active workspace.
+// Returns whether changes are reported live; if not, the frontend rescans on focus.
+pub fn start_watching(app: &AppHandle, label: &str, root: &Path) -> Result<bool, String> {
+    let watcher_state = app.state::<WatcherState>();
+    let live = app.state::<SettingsState>().current().watch_files;
+
+    // Reopening after the setting changed swaps the watcher in or out
+    if !watcher_state.is_watching(root) || watcher_state.is_live(root) != live {
+        let debouncer = if live {
+            Some(create_watcher(app, root)?)
+        } else {
+            None
+        };
+        let mut watchers = watcher_state
+            .watchers
+            .lock()
+            .map_err(|e| format!("Failed to lock watcher state: {}", e))?;
+        watchers.insert(root.to_path_buf(), debouncer);
+        drop(watchers);
+        if let Ok(mut health) = watcher_state.health.lock() {
+            health.remove(root);
+        }
+    }
+
+    watcher_state.set_active_root(label, Some(root.to_path_buf()));
+
+    pins::refresh_menu(app, root);
+
+    // Load the workspace config and index up front so the first query is fast
+    let app_handle = app.clone();
+    let root = root.to_path_buf();
+    std::thread::spawn(move || {
+        let _ = app_handle
+            .state::<IndexState>()
+            .with_workspace(&root, |_| ());
+    });
+
+    Ok(live)
+}
+
+// Drops the watcher and detaches every window from the workspace
+pub fn stop_watching_root(watcher_state: &WatcherState, root: &Path) -> Result<(), String> {
+    let mut watchers = watcher_state
+        .watchers
+        .lock()
+        .map_err(|e| format!("Failed to lock watcher state: {}", e))?;
+    watchers.remove(root);
+    drop(watchers);
+    if let Ok(mut health) = watcher_state.health.lock() {
+        health.remove(root);
+    }
+
+    for label in watcher_state.windows_for(root) {
+        watcher_state.set_active_root(&label, None);
+    }
+
+    Ok(())
+}
+
+// Detaches one window; the watcher only goes away once no window shows the workspace
+pub fn release_window(watcher_state: &WatcherState, label: &str) -> Result<(), String> {
+    let Some(root) = watcher_state.active_root(label) else {
+        return Ok(());
+    };
+
+    watcher_state.set_active_root(label, None);
+    if watcher_state.is_active(&root) {
+        return Ok(());
+    }
+
+    stop_watching_root(watcher_state, &root)
+}
+
+#[tauri::command]
+pub fn watch_folder(folder_path: String, app: AppHandle, window: Window) -> Result<bool, String> {
+    if app.state::<SafeModeState>().is_active() {
+        return Err("Folder watching is disabled in safe mode".to_string());
+    }
+
+    let path = PathBuf::from(&folder_path);
+    storage::ensure_accessible(&app, &path)?;
+
+    if !path.exists() || !path.is_dir() {
+        return Err("Invalid folder path".to_string());
+    }
+
+    let live = start_watching(&app, window.label(), &path)?;
+    workspaces::record_opened(&app, &path)?;
+
+    Ok(live)
+}
+
+// Without a folder this stops the calling window's workspace
+#[tauri::command]
+pub fn stop_watching(
+    folder_path: Option<String>,
+    window: Window,
+    watcher_state: State<WatcherState>,
+) -> Result<(), String> {
+    // A stale stop for a folder the window already left must not touch its new workspace
+    if let Some(folder_path) = folder_path {
+        let root = PathBuf::from(folder_path);
+        if watcher_state.active_root(window.label()).as_ref() != Some(&root) {
+            if !watcher_state.is_active(&root) {
+                stop_watching_root(&watcher_state, &root)?;
+            }
+            return Ok(());
+        }
+    }
+
+    release_window(&watcher_state, window.label())
+}
+
+// Whether the calling window's workspace is watched live and keeping up
+#[tauri::command]
+pub fn get_watcher_status(window: Window, watcher_state: State<WatcherState>) -> WatcherStatus {
+    let root = watcher_state.active_root(window.label());
+    let live = root
+        .as_deref()
+        .is_some_and(|root| watcher_state.is_live(root));
+    let health = root
+        .as_deref()
+        .map(|root| watcher_state.health(root))
+        .unwrap_or_default();
+
+    WatcherStatus {
+        workspace: root.map(|root| root.to_string_lossy().to_string()),
+        live,
+        healthy: live && health.last_error.is_none() && !health.rescanning,
+        health,
+    }
+}
@@ -0,0 +1,190 @@
+use crate::export;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, WebviewWindow, Window, WindowEvent};
+#[cfg(desktop)]
+use tauri::{Monitor, PhysicalPosition, PhysicalSize};
+
+const WINDOW_STATE_FILE: &str = "window-state.json";
+// How much of the title bar has to land on a monitor for a saved position to count
+#[cfg(desktop)]
+const MIN_VISIBLE_WIDTH: i64 = 100;
+#[cfg(desktop)]
+const MIN_VISIBLE_HEIGHT: i64 = 40;
+
+// Physical pixels, as reported by the window that was closed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+    pub monitor: Option<String>,
+}
+
+// Keyed by window label, kept in memory while running and written on exit
+pub struct WindowStateStore {
+    windows: Mutex<HashMap<String, WindowGeometry>>,
+}
+
+impl WindowStateStore {
+    pub fn load(app: &AppHandle) -> WindowStateStore {
+        let windows = state_path(app)
+            .map(|path| storage::read_json(&path))
+            .unwrap_or_default();
+
+        WindowStateStore {
+            windows: Mutex::new(windows),
+        }
+    }
+
+    #[cfg(desktop)]
+    pub fn get(&self, label: &str) -> Option<WindowGeometry> {
+        self.windows
+            .lock()
+            .ok()
+            .and_then(|windows| windows.get(label).cloned())
+    }
+
+    fn capture(&self, window: &Window) {
+        // Minimized windows report bogus positions on some platforms
+        if window.is_minimized().unwrap_or(false) {
+            return;
+        }
+
+        let Ok(mut windows) = self.windows.lock() else {
+            return;
+        };
+        let maximized = window.is_maximized().unwrap_or(false);
+        let monitor = window
+            .current_monitor()
+            .ok()
+            .flatten()
+            .and_then(|monitor| monitor.name().cloned());
+
+        // While maximized keep the last normal bounds so un-maximizing after a restore works
+        if maximized {
+            if let Some(geometry) = windows.get_mut(window.label()) {
+                geometry.maximized = true;
+                geometry.monitor = monitor;
+                return;
+            }
+        }
+
+        let (Ok(position), Ok(size)) = (window.outer_position(), window.outer_size()) else {
+            return;
+        };
+
+        windows.insert(
+            window.label().to_string(),
+            WindowGeometry {
+                x: position.x,
+                y: position.y,
+                width: size.width,
+                height: size.height,
+                maximized,
+                monitor,
+            },
+        );
+    }
+
+    pub fn save(&self, app: &AppHandle) -> Result<(), String> {
+        let windows = self
+            .windows
+            .lock()
+            .map_err(|e| format!("Failed to lock window state: {}", e))?;
+        storage::write_json(&state_path(app)?, &*windows)
+    }
+}
+
+pub fn state_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(storage::app_data_dir(app)?.join(WINDOW_STATE_FILE))
+}
+
+#[cfg(desktop)]
+fn visible_on(geometry: &WindowGeometry, monitor: &Monitor) -> bool {
+    let origin = monitor.position();
+    let size = monitor.size();
+
+    let left = i64::from(geometry.x).max(i64::from(origin.x));
+    let right = (i64::from(geometry.x) + i64::from(geometry.width))
+        .min(i64::from(origin.x) + i64::from(size.width));
+    let top = i64::from(geometry.y).max(i64::from(origin.y));
+    let bottom = (i64::from(geometry.y) + MIN_VISIBLE_HEIGHT)
+        .min(i64::from(origin.y) + i64::from(size.height));
+
+    right - left >= MIN_VISIBLE_WIDTH && bottom - top >= MIN_VISIBLE_HEIGHT
+}
+
+// Applies the saved geometry, falling back to a centered window when the saved
+// monitor is gone or the position would land off-screen
+#[cfg(desktop)]
+pub fn restore(window: &WebviewWindow, store: &WindowStateStore) {
+    let Some(geometry) = store.get(window.label()) else {
+        return;
+    };
+    let monitors = window.available_monitors().unwrap_or_default();
+
+    let target = monitors
+        .iter()
+        .find(|monitor| {
+            geometry.monitor.is_some()
+                && monitor.name() == geometry.monitor.as_ref()
+                && visible_on(&geometry, monitor)
+        })
+        .or_else(|| {
+            monitors
+                .iter()
+                .find(|monitor| visible_on(&geometry, monitor))
+        });
+
+    match target {
+        Some(monitor) => {
+            let bounds = monitor.size();
+            let _ = window.set_size(PhysicalSize::new(
+                geometry.width.min(bounds.width),
+                geometry.height.min(bounds.height),
+            ));
+            let _ = window.set_position(PhysicalPosition::new(geometry.x, geometry.y));
+        }
+        None => {
+            let (width, height) = match window.primary_monitor().ok().flatten() {
+                Some(primary) => (
+                    geometry.width.min(primary.size().width),
+                    geometry.height.min(primary.size().height),
+                ),
+                None => (geometry.width, geometry.height),
+            };
+            let _ = window.set_size(PhysicalSize::new(width, height));
+            let _ = window.center();
+        }
+    }
+
+    if geometry.maximized {
+        let _ = window.maximize();
+    }
+}
+
+// Mobile windows always fill the screen
+#[cfg(mobile)]
+pub fn restore(_window: &WebviewWindow, _store: &WindowStateStore) {}
+
+pub fn handle_window_event(window: &Window, event: &WindowEvent) {
+    // Hidden PDF renderers have no geometry worth restoring
+    if window.label().starts_with(export::WINDOW_PREFIX) {
+        return;
+    }
+    match event {
+        WindowEvent::Moved(_) | WindowEvent::Resized(_) | WindowEvent::CloseRequested { .. } => {
+            // Events can arrive before setup has managed the store
+            if let Some(store) = window.try_state::<WindowStateStore>() {
+                store.capture(window);
+            }
+        }
+        _ => {}
+    }
+}
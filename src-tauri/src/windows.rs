@@ -0,0 +1,345 @@
+use crate::badge::{self, BadgeState};
+use crate::edit_locks::{self, EditLockState};
+use crate::markdown;
+use crate::notifications;
+use crate::storage;
+use crate::watcher::{self, WatcherState};
+use crate::window_state::{self, WindowStateStore};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{
+    AppHandle, Emitter, Manager, State, WebviewUrl, WebviewWindowBuilder, Window, WindowEvent,
+};
+
+pub const MAIN_WINDOW: &str = "main";
+const NOTE_WINDOW_PREFIX: &str = "note-";
+const WORKSPACE_WINDOW_PREFIX: &str = "workspace-";
+
+#[derive(Debug, Serialize, Clone)]
+struct NoteSavedEvent {
+    path: String,
+    window: String,
+}
+
+// Tracks what each secondary window is showing so events can be routed to it
+#[derive(Default)]
+pub struct WindowRegistry {
+    notes: Mutex<HashMap<String, PathBuf>>,
+    // Workspace windows waiting for their webview before the folder is opened
+    pending_workspaces: Mutex<HashMap<String, PathBuf>>,
+}
+
+impl WindowRegistry {
+    fn register(&self, label: &str, path: &Path) {
+        if let Ok(mut notes) = self.notes.lock() {
+            notes.insert(label.to_string(), path.to_path_buf());
+        }
+    }
+
+    fn unregister(&self, label: &str) {
+        if let Ok(mut notes) = self.notes.lock() {
+            notes.remove(label);
+        }
+        if let Ok(mut pending) = self.pending_workspaces.lock() {
+            pending.remove(label);
+        }
+    }
+
+    fn note_for(&self, label: &str) -> Option<PathBuf> {
+        self.notes
+            .lock()
+            .ok()
+            .and_then(|notes| notes.get(label).cloned())
+    }
+
+    fn windows_showing(&self, path: &Path) -> Vec<String> {
+        self.notes
+            .lock()
+            .map(|notes| {
+                notes
+                    .iter()
+                    .filter(|(_, note)| note.as_path() == path)
+                    .map(|(label, _)| label.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn take_pending_workspace(&self, label: &str) -> Option<PathBuf> {
+        self.pending_workspaces
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.remove(label))
+    }
+}
+
+fn note_label(path: &Path) -> String {
+    format!("{}{}", NOTE_WINDOW_PREFIX, storage::path_key(path))
+}
+
+fn workspace_label(root: &Path) -> String {
+    format!("{}{}", WORKSPACE_WINDOW_PREFIX, storage::path_key(root))
+}
+
+// Windows showing the workspace see every change in it; note windows only their own note
+fn windows_interested(app: &AppHandle, root: Option<&Path>, path: &Path) -> Vec<String> {
+    let registry = app.state::<WindowRegistry>();
+    let mut labels: Vec<String> = root
+        .map(|root| app.state::<WatcherState>().windows_for(root))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|label| {
+            registry
+                .note_for(label)
+                .is_none_or(|note| note.as_path() == path)
+        })
+        .collect();
+
+    for label in registry.windows_showing(path) {
+        if !labels.contains(&label) {
+            labels.push(label);
+        }
+    }
+
+    labels
+}
+
+pub fn emit_file_change<S: Serialize + Clone>(
+    app: &AppHandle,
+    root: &Path,
+    path: &Path,
+    payload: S,
+) {
+    for label in windows_interested(app, Some(root), path) {
+        let _ = app.emit_to(label.as_str(), "file-change", payload.clone());
+    }
+}
+
+// Workspace-wide events go only to the windows that have `root` open
+pub fn emit_to_workspace<S: Serialize + Clone>(
+    app: &AppHandle,
+    root: &Path,
+    event: &str,
+    payload: S,
+) {
+    for label in app.state::<WatcherState>().windows_for(root) {
+        let _ = app.emit_to(label.as_str(), event, payload.clone());
+    }
+}
+
+// Lets every other window showing the note pick up the new content
+pub fn emit_note_saved(app: &AppHandle, origin: &str, path: &Path) {
+    notifications::record_write(app, path);
+
+    let payload = NoteSavedEvent {
+        path: path.to_string_lossy().to_string(),
+        window: origin.to_string(),
+    };
+    let root = app.state::<WatcherState>().root_for(path);
+
+    for label in windows_interested(app, root.as_deref(), path) {
+        if label != origin {
+            let _ = app.emit_to(label.as_str(), "note-saved", payload.clone());
+        }
+    }
+    // Plugins run in the main window and hear about every save, its own included
+    let _ = app.emit_to(MAIN_WINDOW, "plugins://note-saved", payload);
+}
+
+// Menu actions belong to whichever window the user is working in
+// Falls back to the main window when nothing is focused
+#[cfg(desktop)]
+pub fn focused_label(app: &AppHandle) -> String {
+    app.webview_windows()
+        .into_iter()
+        .find(|(_, window)| window.is_focused().unwrap_or(false))
+        .map(|(label, _)| label)
+        .unwrap_or_else(|| MAIN_WINDOW.to_string())
+}
+
+#[cfg(desktop)]
+pub fn emit_to_focused<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: S) {
+    let _ = app.emit_to(focused_label(app).as_str(), event, payload);
+}
+
+pub fn handle_window_event(window: &Window, event: &WindowEvent) {
+    if let WindowEvent::Destroyed = event {
+        if let Some(registry) = window.try_state::<WindowRegistry>() {
+            registry.unregister(window.label());
+        }
+        if let Some(badge_state) = window.try_state::<BadgeState>() {
+            badge_state.forget_window(window.label());
+            badge::refresh(window.app_handle());
+        }
+        if window.try_state::<EditLockState>().is_some() {
+            edit_locks::release_window(window.app_handle(), window.label());
+        }
+        if let Some(watcher_state) = window.try_state::<WatcherState>() {
+            if let Err(e) = watcher::release_window(&watcher_state, window.label()) {
+                tracing::error!("Failed to release window workspace: {}", e);
+            }
+        }
+    }
+}
+
+fn build_window(
+    app: &AppHandle,
+    label: &str,
+    title: &str,
+    init_script: &str,
+) -> Result<tauri::WebviewWindow, String> {
+    let builder = WebviewWindowBuilder::new(app, label, WebviewUrl::App("index.html".into()))
+        .title(title)
+        .inner_size(900.0, 800.0)
+        .min_inner_size(480.0, 400.0)
+        .initialization_script(init_script);
+
+    #[cfg(target_os = "macos")]
+    let builder = builder
+        .title_bar_style(tauri::TitleBarStyle::Overlay)
+        .hidden_title(true);
+
+    #[cfg(all(desktop, not(target_os = "macos")))]
+    let builder = builder.decorations(false);
+
+    let window = builder
+        .build()
+        .map_err(|e| format!("Failed to open window: {}", e))?;
+    window_state::restore(&window, &app.state::<WindowStateStore>());
+
+    Ok(window)
+}
+
+fn focus_existing(app: &AppHandle, label: &str) -> Result<bool, String> {
+    let Some(existing) = app.get_webview_window(label) else {
+        return Ok(false);
+    };
+
+    #[cfg(desktop)]
+    let _ = existing.unminimize();
+    existing
+        .set_focus()
+        .map_err(|e| format!("Failed to focus window: {}", e))?;
+    Ok(true)
+}
+
+fn script_value(value: &str) -> Result<String, String> {
+    serde_json::to_string(value).map_err(|e| format!("Failed to serialize window data: {}", e))
+}
+
+// Async so window creation doesn't block the main thread on Windows
+#[tauri::command]
+pub async fn open_note_in_new_window(
+    file_path: String,
+    app: AppHandle,
+    window: Window,
+    registry: State<'_, WindowRegistry>,
+    watcher_state: State<'_, WatcherState>,
+) -> Result<String, String> {
+    let path = PathBuf::from(&file_path);
+    if !path.is_file() {
+        return Err("Note does not exist".to_string());
+    }
+
+    open_note_window(&app, &path, window.label(), &registry, &watcher_state)
+}
+
+fn open_note_window(
+    app: &AppHandle,
+    path: &Path,
+    parent: &str,
+    registry: &WindowRegistry,
+    watcher_state: &WatcherState,
+) -> Result<String, String> {
+    let label = note_label(path);
+    if focus_existing(app, &label)? {
+        return Ok(label);
+    }
+
+    // The frontend reads these before mounting to render a single-note layout
+    // hydrated from the window that opened it
+    let init_script = format!(
+        "window.__MARKY_NOTE_WINDOW__ = {}; window.__MARKY_PARENT_WINDOW__ = {};",
+        script_value(&path.to_string_lossy())?,
+        script_value(parent)?
+    );
+    build_window(app, &label, &markdown::note_title(path), &init_script)?;
+
+    registry.register(&label, path);
+    let root = watcher_state
+        .active_root(parent)
+        .filter(|root| path.starts_with(root))
+        .or_else(|| watcher_state.root_for(path));
+    watcher_state.set_active_root(&label, root);
+
+    Ok(label)
+}
+
+// Opens a note from outside the app, such as a `marky://` link: in a window
+// already showing its workspace, or else in a note window of its own
+#[cfg(target_os = "macos")]
+pub fn show_note(app: &AppHandle, path: &Path) -> Result<(), String> {
+    if !path.is_file() {
+        return Err("Note does not exist".to_string());
+    }
+    let watcher_state = app.state::<WatcherState>();
+    let workspace_window = watcher_state.root_for(path).and_then(|root| {
+        let labels = watcher_state.windows_for(&root);
+        labels
+            .iter()
+            .find(|label| label.as_str() == MAIN_WINDOW)
+            .or_else(|| {
+                labels
+                    .iter()
+                    .find(|label| label.starts_with(WORKSPACE_WINDOW_PREFIX))
+            })
+            .cloned()
+    });
+
+    if let Some(label) = workspace_window {
+        focus_existing(app, &label)?;
+        return app
+            .emit_to(label.as_str(), "open-recent-note", path.to_string_lossy())
+            .map_err(|e| format!("Failed to emit event: {}", e));
+    }
+    open_note_window(
+        app,
+        path,
+        MAIN_WINDOW,
+        &app.state::<WindowRegistry>(),
+        &watcher_state,
+    )
+    .map(|_| ())
+}
+
+// Opens a separate window with its own workspace, tabs, and recent notes
+#[tauri::command]
+pub async fn open_workspace_window(
+    folder_path: String,
+    app: AppHandle,
+    registry: State<'_, WindowRegistry>,
+) -> Result<String, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+
+    let label = workspace_label(&root);
+    if focus_existing(&app, &label)? {
+        return Ok(label);
+    }
+
+    if let Ok(mut pending) = registry.pending_workspaces.lock() {
+        pending.insert(label.clone(), root.clone());
+    }
+
+    let title = root
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| folder_path.clone());
+    build_window(&app, &label, &title, "")?;
+
+    Ok(label)
+}
@@ -0,0 +1,301 @@
+use crate::markdown;
+use crate::settings::{Settings, SettingsState};
+use crate::storage;
+use chrono::format::{Item, StrftimeItems};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+pub const CONFIG_DIR: &str = ".marky";
+const CONFIG_FILE: &str = "config.json";
+
+// Everything is optional so a vault only overrides what it cares about
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceConfig {
+    pub daily_note_format: Option<String>,
+    pub templates_folder: Option<String>,
+    pub ignore: Vec<String>,
+    pub attachment_folder: Option<String>,
+    pub archive_folder: Option<String>,
+    // Words and phrases spell checking should accept in this workspace
+    pub spellcheck_ignore: Vec<String>,
+    // Keys written by newer versions (or other tools) survive a round trip
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EffectiveConfig {
+    pub daily_note_format: String,
+    pub templates_folder: Option<String>,
+    pub ignore: Vec<String>,
+    pub attachment_folder: String,
+    pub archive_folder: String,
+    pub spellcheck_ignore: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkspaceConfigInfo {
+    config_path: String,
+    workspace: WorkspaceConfig,
+    effective: EffectiveConfig,
+}
+
+// chrono panics when formatting with an invalid specifier, so check up front
+pub fn is_valid_date_format(format: &str) -> bool {
+    !StrftimeItems::new(format).any(|item| matches!(item, Item::Error))
+}
+
+pub fn config_path(root: &Path) -> PathBuf {
+    root.join(CONFIG_DIR).join(CONFIG_FILE)
+}
+
+pub fn is_config_path(root: &Path, path: &Path) -> bool {
+    path == config_path(root)
+}
+
+pub fn load(root: &Path) -> WorkspaceConfig {
+    storage::read_json(&config_path(root))
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+// Workspace values win over global settings; ignore globs accumulate
+pub fn effective(root: &Path, settings: &Settings) -> EffectiveConfig {
+    let config = load(root);
+
+    let daily_note_format = non_empty(config.daily_note_format)
+        .filter(|format| is_valid_date_format(format))
+        .unwrap_or_else(|| settings.daily_note_format.clone());
+
+    let mut ignore = settings.ignore_globs.clone();
+    for pattern in config.ignore {
+        if !ignore.contains(&pattern) {
+            ignore.push(pattern);
+        }
+    }
+
+    EffectiveConfig {
+        daily_note_format,
+        templates_folder: non_empty(config.templates_folder)
+            .or_else(|| settings.templates_folder.clone()),
+        ignore,
+        attachment_folder: non_empty(config.attachment_folder)
+            .unwrap_or_else(|| settings.attachment_folder.clone()),
+        archive_folder: non_empty(config.archive_folder)
+            .unwrap_or_else(|| settings.archive_folder.clone()),
+        spellcheck_ignore: config.spellcheck_ignore,
+    }
+}
+
+// Decides which paths under a workspace count as notes
+pub struct NoteFilter {
+    root: PathBuf,
+    extensions: Vec<String>,
+    ignore: GlobSet,
+    archive: PathBuf,
+    include_archived: bool,
+}
+
+impl NoteFilter {
+    pub fn for_workspace(root: &Path, settings: &Settings) -> NoteFilter {
+        let config = effective(root, settings);
+        let mut builder = GlobSetBuilder::new();
+
+        for pattern in &config.ignore {
+            let pattern = pattern.trim().trim_end_matches('/');
+            if pattern.is_empty() {
+                continue;
+            }
+
+            // Bare names like `node_modules` match at any depth, as in .gitignore
+            let pattern = if pattern.contains('/') {
+                pattern.trim_start_matches('/').to_string()
+            } else {
+                format!("**/{}", pattern)
+            };
+
+            match Glob::new(&pattern) {
+                Ok(glob) => {
+                    builder.add(glob);
+                }
+                Err(e) => {
+                    tracing::warn!("Invalid ignore pattern {}: {}", pattern, e)
+                }
+            }
+        }
+
+        NoteFilter {
+            root: root.to_path_buf(),
+            extensions: settings.watched_extensions.clone(),
+            ignore: builder.build().unwrap_or_else(|_| GlobSet::empty()),
+            archive: root.join(&config.archive_folder),
+            include_archived: false,
+        }
+    }
+
+    // Archived notes stay out of scans and the index unless asked for
+    pub fn including_archived(mut self, include: bool) -> NoteFilter {
+        self.include_archived = include;
+        self
+    }
+
+    pub fn is_archived(&self, path: &Path) -> bool {
+        path.starts_with(&self.archive)
+    }
+
+    // Hidden entries and anything under an ignored folder are out of the workspace
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+
+        if relative
+            .components()
+            .any(|part| part.as_os_str().to_string_lossy().starts_with('.'))
+        {
+            return true;
+        }
+
+        if !self.include_archived && self.is_archived(path) {
+            return true;
+        }
+
+        relative
+            .ancestors()
+            .filter(|ancestor| !ancestor.as_os_str().is_empty())
+            .any(|ancestor| self.ignore.is_match(ancestor))
+    }
+
+    pub fn includes_note(&self, path: &Path) -> bool {
+        markdown::has_note_extension(path, &self.extensions) && !self.is_ignored(path)
+    }
+}
+
+fn save(root: &Path, config: &WorkspaceConfig) -> Result<(), String> {
+    fs::create_dir_all(root.join(CONFIG_DIR))
+        .map_err(|e| format!("Failed to create workspace config folder: {}", e))?;
+    storage::write_json(&config_path(root), config)
+}
+
+// One entry per word regardless of case, sorted so the shared config diffs
+// cleanly when teammates add words
+fn normalize_spellcheck_ignore(words: &mut Vec<String>) {
+    let mut seen = Vec::new();
+    for word in words.iter_mut() {
+        *word = word.trim().to_string();
+    }
+    words.retain(|word| {
+        let key = word.to_lowercase();
+        let fresh = !word.is_empty() && !seen.contains(&key);
+        seen.push(key);
+        fresh
+    });
+    words.sort_by_cached_key(|word| word.to_lowercase());
+}
+
+fn edit_spellcheck_ignore(folder_path: &str, word: &str, add: bool) -> Result<Vec<String>, String> {
+    let root = PathBuf::from(folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+    let word = word.trim();
+    if word.is_empty() {
+        return Err("Word is required".to_string());
+    }
+    if word.contains(['\n', '\r']) {
+        return Err("Ignored words must fit on one line".to_string());
+    }
+
+    let mut config = load(&root);
+    let before = config.spellcheck_ignore.clone();
+    let key = word.to_lowercase();
+    config
+        .spellcheck_ignore
+        .retain(|existing| existing.trim().to_lowercase() != key);
+    if add {
+        config.spellcheck_ignore.push(word.to_string());
+    }
+    normalize_spellcheck_ignore(&mut config.spellcheck_ignore);
+    if config.spellcheck_ignore != before {
+        save(&root, &config)?;
+    }
+    Ok(config.spellcheck_ignore)
+}
+
+#[tauri::command]
+pub fn get_workspace_config(
+    folder_path: String,
+    settings_state: State<SettingsState>,
+) -> Result<WorkspaceConfigInfo, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+
+    Ok(WorkspaceConfigInfo {
+        config_path: config_path(&root).to_string_lossy().to_string(),
+        workspace: load(&root),
+        effective: effective(&root, &settings_state.current()),
+    })
+}
+
+#[tauri::command]
+pub fn set_workspace_config(
+    folder_path: String,
+    config: WorkspaceConfig,
+    settings_state: State<SettingsState>,
+) -> Result<WorkspaceConfigInfo, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+
+    if let Some(format) = non_empty(config.daily_note_format.clone()) {
+        if !is_valid_date_format(&format) {
+            return Err(format!("Invalid daily note format: {}", format));
+        }
+    }
+
+    let mut config = config;
+    normalize_spellcheck_ignore(&mut config.spellcheck_ignore);
+    save(&root, &config)?;
+
+    // The watcher picks up the write and refreshes the index and frontend
+    Ok(WorkspaceConfigInfo {
+        config_path: config_path(&root).to_string_lossy().to_string(),
+        workspace: config,
+        effective: effective(&root, &settings_state.current()),
+    })
+}
+
+// The workspace's spellcheck ignore list, from `.marky/config.json` so it's
+// shared with everyone who opens the folder
+#[tauri::command]
+pub fn get_spellcheck_ignore(folder_path: String) -> Result<Vec<String>, String> {
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+    let mut words = load(&root).spellcheck_ignore;
+    normalize_spellcheck_ignore(&mut words);
+    Ok(words)
+}
+
+#[tauri::command]
+pub fn add_spellcheck_ignore(folder_path: String, word: String) -> Result<Vec<String>, String> {
+    edit_spellcheck_ignore(&folder_path, &word, true)
+}
+
+#[tauri::command]
+pub fn remove_spellcheck_ignore(folder_path: String, word: String) -> Result<Vec<String>, String> {
+    edit_spellcheck_ignore(&folder_path, &word, false)
+}
@@ -0,0 +1,345 @@
+use crate::index::IndexState;
+use crate::settings::SettingsState;
+#[cfg(target_os = "macos")]
+use crate::spotlight;
+use crate::storage;
+use crate::watcher::{self, WatcherState};
+use crate::windows::{self, WindowRegistry};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State, Window};
+
+const REGISTRY_FILE: &str = "workspaces.json";
+const DEFAULT_RECENT_LIMIT: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceEntry {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    pub added_at: i64,
+    pub last_opened_at: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkspaceInfo {
+    #[serde(flatten)]
+    entry: WorkspaceEntry,
+    exists: bool,
+    active: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct WorkspaceRegistry {
+    workspaces: Vec<WorkspaceEntry>,
+}
+
+pub struct WorkspaceRegistryState {
+    registry: Mutex<WorkspaceRegistry>,
+    // Set once the webview has asked for its startup workspace
+    startup_handled: AtomicBool,
+}
+
+impl WorkspaceRegistryState {
+    pub fn load(app: &AppHandle) -> WorkspaceRegistryState {
+        let registry = registry_path(app)
+            .map(|path| storage::read_json(&path))
+            .unwrap_or_default();
+
+        WorkspaceRegistryState {
+            registry: Mutex::new(registry),
+            startup_handled: AtomicBool::new(false),
+        }
+    }
+}
+
+pub fn registry_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(storage::app_data_dir(app)?.join(REGISTRY_FILE))
+}
+
+// Every workspace Marky knows about, whether or not it's open
+pub fn registered_roots(app: &AppHandle) -> Vec<PathBuf> {
+    app.state::<WorkspaceRegistryState>()
+        .registry
+        .lock()
+        .map(|registry| {
+            registry
+                .workspaces
+                .iter()
+                .map(|entry| PathBuf::from(&entry.path))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn folder_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+fn save_registry(app: &AppHandle, registry: &WorkspaceRegistry) -> Result<(), String> {
+    storage::write_json(&registry_path(app)?, registry)
+}
+
+fn new_entry(path: &Path, name: Option<String>) -> WorkspaceEntry {
+    WorkspaceEntry {
+        id: storage::workspace_key(path),
+        name: name
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| folder_name(path)),
+        path: path.to_string_lossy().to_string(),
+        added_at: Utc::now().timestamp_millis(),
+        last_opened_at: None,
+    }
+}
+
+// Folders opened directly (not through the vault manager) are registered on first open
+pub fn record_opened(app: &AppHandle, root: &Path) -> Result<WorkspaceEntry, String> {
+    let registry_state = app.state::<WorkspaceRegistryState>();
+    let mut registry = registry_state
+        .registry
+        .lock()
+        .map_err(|e| format!("Failed to lock workspace registry: {}", e))?;
+
+    let position = match registry
+        .workspaces
+        .iter()
+        .position(|entry| Path::new(&entry.path) == root)
+    {
+        Some(position) => position,
+        None => {
+            registry.workspaces.push(new_entry(root, None));
+            registry.workspaces.len() - 1
+        }
+    };
+
+    let entry = &mut registry.workspaces[position];
+    entry.last_opened_at = Some(Utc::now().timestamp_millis());
+    let entry = entry.clone();
+    save_registry(app, &registry)?;
+    drop(registry);
+
+    #[cfg(target_os = "macos")]
+    spotlight::index_workspace(app, root);
+
+    Ok(entry)
+}
+
+fn recent_entries(registry: &WorkspaceRegistry) -> Vec<WorkspaceEntry> {
+    let mut recent: Vec<WorkspaceEntry> = registry
+        .workspaces
+        .iter()
+        .filter(|entry| entry.last_opened_at.is_some())
+        .cloned()
+        .collect();
+    recent.sort_by_key(|entry| std::cmp::Reverse(entry.last_opened_at));
+    recent
+}
+
+fn to_info(entry: &WorkspaceEntry, active_root: Option<&Path>) -> WorkspaceInfo {
+    let path = PathBuf::from(&entry.path);
+    WorkspaceInfo {
+        entry: entry.clone(),
+        exists: path.is_dir(),
+        active: active_root == Some(path.as_path()),
+    }
+}
+
+#[tauri::command]
+pub fn list_workspaces(
+    window: Window,
+    registry_state: State<WorkspaceRegistryState>,
+    watcher_state: State<WatcherState>,
+) -> Result<Vec<WorkspaceInfo>, String> {
+    let registry = registry_state
+        .registry
+        .lock()
+        .map_err(|e| format!("Failed to lock workspace registry: {}", e))?;
+    let active_root = watcher_state.active_root(window.label());
+
+    Ok(registry
+        .workspaces
+        .iter()
+        .map(|entry| to_info(entry, active_root.as_deref()))
+        .collect())
+}
+
+#[tauri::command]
+pub fn add_workspace(
+    folder_path: String,
+    name: Option<String>,
+    app: AppHandle,
+    registry_state: State<WorkspaceRegistryState>,
+) -> Result<WorkspaceEntry, String> {
+    let path = PathBuf::from(&folder_path);
+    if !path.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+
+    let mut registry = registry_state
+        .registry
+        .lock()
+        .map_err(|e| format!("Failed to lock workspace registry: {}", e))?;
+
+    // Registering the same folder twice just hands back the existing entry
+    if let Some(existing) = registry
+        .workspaces
+        .iter()
+        .find(|entry| Path::new(&entry.path) == path)
+    {
+        return Ok(existing.clone());
+    }
+
+    let entry = new_entry(&path, name);
+    registry.workspaces.push(entry.clone());
+    save_registry(&app, &registry)?;
+
+    Ok(entry)
+}
+
+#[tauri::command]
+pub fn open_workspace(
+    id: String,
+    app: AppHandle,
+    window: Window,
+    registry_state: State<WorkspaceRegistryState>,
+) -> Result<WorkspaceEntry, String> {
+    let path = {
+        let registry = registry_state
+            .registry
+            .lock()
+            .map_err(|e| format!("Failed to lock workspace registry: {}", e))?;
+
+        registry
+            .workspaces
+            .iter()
+            .find(|entry| entry.id == id)
+            .map(|entry| PathBuf::from(&entry.path))
+            .ok_or("Workspace not found")?
+    };
+
+    if !path.is_dir() {
+        return Err("Workspace folder does not exist".to_string());
+    }
+
+    watcher::start_watching(&app, window.label(), &path)?;
+    let entry = record_opened(&app, &path)?;
+
+    app.emit_to(window.label(), "workspace-opened", entry.clone())
+        .map_err(|e| format!("Failed to emit event: {}", e))?;
+
+    Ok(entry)
+}
+
+#[tauri::command]
+pub fn get_recent_workspaces(
+    limit: Option<usize>,
+    window: Window,
+    registry_state: State<WorkspaceRegistryState>,
+    watcher_state: State<WatcherState>,
+) -> Result<Vec<WorkspaceInfo>, String> {
+    let registry = registry_state
+        .registry
+        .lock()
+        .map_err(|e| format!("Failed to lock workspace registry: {}", e))?;
+    let active_root = watcher_state.active_root(window.label());
+
+    Ok(recent_entries(&registry)
+        .iter()
+        .take(limit.unwrap_or(DEFAULT_RECENT_LIMIT))
+        .map(|entry| to_info(entry, active_root.as_deref()))
+        .collect())
+}
+
+// Called by each window once its listeners are attached. Workspace windows get the
+// folder they were opened for; the main window, on the first call of a launch, gets the
+// last workspace (if enabled and still on disk). `legacy_folder_path` is the root
+// remembered by older frontends, used when the registry has no history yet.
+#[tauri::command]
+pub fn webview_ready(
+    legacy_folder_path: Option<String>,
+    app: AppHandle,
+    window: Window,
+    registry_state: State<WorkspaceRegistryState>,
+    settings_state: State<SettingsState>,
+    window_registry: State<WindowRegistry>,
+) -> Result<Option<WorkspaceEntry>, String> {
+    let path = if window.label() == windows::MAIN_WINDOW {
+        if registry_state.startup_handled.swap(true, Ordering::SeqCst)
+            || !settings_state.current().reopen_last_workspace
+        {
+            return Ok(None);
+        }
+
+        let last_path = {
+            let registry = registry_state
+                .registry
+                .lock()
+                .map_err(|e| format!("Failed to lock workspace registry: {}", e))?;
+
+            recent_entries(&registry)
+                .into_iter()
+                .map(|entry| PathBuf::from(entry.path))
+                .find(|path| path.is_dir())
+        };
+
+        last_path
+            .or_else(|| {
+                legacy_folder_path
+                    .map(PathBuf::from)
+                    .filter(|path| path.is_dir())
+            })
+            // A fresh mobile install opens straight into its notes folder
+            .or_else(|| storage::default_vault_dir(&app))
+    } else {
+        window_registry.take_pending_workspace(window.label())
+    };
+
+    let Some(path) = path else {
+        return Ok(None);
+    };
+
+    watcher::start_watching(&app, window.label(), &path)?;
+    let entry = record_opened(&app, &path)?;
+
+    app.emit_to(window.label(), "open-folder", entry.clone())
+        .map_err(|e| format!("Failed to emit event: {}", e))?;
+
+    Ok(Some(entry))
+}
+
+// Only forgets the vault; nothing on disk is touched
+#[tauri::command]
+pub fn remove_workspace(
+    id: String,
+    app: AppHandle,
+    registry_state: State<WorkspaceRegistryState>,
+    watcher_state: State<WatcherState>,
+    index_state: State<IndexState>,
+) -> Result<(), String> {
+    let mut registry = registry_state
+        .registry
+        .lock()
+        .map_err(|e| format!("Failed to lock workspace registry: {}", e))?;
+
+    let position = registry
+        .workspaces
+        .iter()
+        .position(|entry| entry.id == id)
+        .ok_or("Workspace not found")?;
+    let entry = registry.workspaces.remove(position);
+    save_registry(&app, &registry)?;
+
+    let path = PathBuf::from(&entry.path);
+    watcher::stop_watching_root(&watcher_state, &path)?;
+    index_state.invalidate(&path);
+    #[cfg(target_os = "macos")]
+    spotlight::remove_workspace(&path);
+
+    Ok(())
+}
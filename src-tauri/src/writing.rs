@@ -0,0 +1,192 @@
+use crate::i18n;
+use crate::storage;
+use crate::watcher::WatcherState;
+use chrono::{Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, State, Window};
+
+const JOURNAL_FILE: &str = "writing.json";
+const RECENT_DAYS: i64 = 30;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct WritingJournal {
+    daily_goal: Option<u32>,
+    // Net words added per local day, keyed by YYYY-MM-DD
+    days: BTreeMap<String, i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DayWords {
+    date: String,
+    words: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WritingStreak {
+    daily_goal: Option<u32>,
+    today_words: i64,
+    goal_met: bool,
+    current_streak: u32,
+    longest_streak: u32,
+    recent_days: Vec<DayWords>,
+}
+
+// Serializes read-modify-write cycles on the journal file
+#[derive(Default)]
+pub struct WritingState {
+    lock: Mutex<()>,
+}
+
+fn day_key(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+impl WritingJournal {
+    fn words_on(&self, date: NaiveDate) -> i64 {
+        self.days.get(&day_key(date)).copied().unwrap_or(0)
+    }
+
+    // Without a goal any net progress keeps the streak alive
+    fn day_counts(&self, words: i64) -> bool {
+        match self.daily_goal {
+            Some(goal) if goal > 0 => words >= i64::from(goal),
+            _ => words > 0,
+        }
+    }
+
+    fn current_streak(&self, today: NaiveDate) -> u32 {
+        // Today still in progress shouldn't break a streak built up to yesterday
+        let mut day = if self.day_counts(self.words_on(today)) {
+            today
+        } else {
+            today - Duration::days(1)
+        };
+
+        let mut streak = 0;
+        while self.day_counts(self.words_on(day)) {
+            streak += 1;
+            day -= Duration::days(1);
+        }
+        streak
+    }
+
+    fn longest_streak(&self) -> u32 {
+        let mut longest = 0;
+        let mut run = 0;
+        let mut previous: Option<NaiveDate> = None;
+
+        for (key, words) in &self.days {
+            let Ok(date) = NaiveDate::parse_from_str(key, "%Y-%m-%d") else {
+                continue;
+            };
+
+            if !self.day_counts(*words) {
+                run = 0;
+                previous = None;
+                continue;
+            }
+
+            run = match previous {
+                Some(prev) if date - prev == Duration::days(1) => run + 1,
+                _ => 1,
+            };
+            longest = longest.max(run);
+            previous = Some(date);
+        }
+
+        longest
+    }
+}
+
+pub fn record_word_delta(
+    app: &AppHandle,
+    writing_state: &WritingState,
+    root: &Path,
+    delta: i64,
+) -> Result<(), String> {
+    if delta == 0 {
+        return Ok(());
+    }
+
+    let _guard = writing_state
+        .lock
+        .lock()
+        .map_err(|e| i18n::tf("error.writing_journal_lock", &[&e]))?;
+
+    let path = storage::workspace_data_dir(app, root)?.join(JOURNAL_FILE);
+    let mut journal: WritingJournal = storage::read_json(&path);
+    *journal
+        .days
+        .entry(day_key(Local::now().date_naive()))
+        .or_insert(0) += delta;
+
+    storage::write_json(&path, &journal)
+}
+
+#[tauri::command]
+pub fn get_writing_streak(
+    app: AppHandle,
+    window: Window,
+    watcher_state: State<WatcherState>,
+    writing_state: State<WritingState>,
+) -> Result<WritingStreak, String> {
+    let root = watcher_state
+        .active_root(window.label())
+        .ok_or_else(|| i18n::t("error.no_workspace"))?;
+
+    let _guard = writing_state
+        .lock
+        .lock()
+        .map_err(|e| i18n::tf("error.writing_journal_lock", &[&e]))?;
+    let journal: WritingJournal =
+        storage::read_json(&storage::workspace_data_dir(&app, &root)?.join(JOURNAL_FILE));
+
+    let today = Local::now().date_naive();
+    let today_words = journal.words_on(today);
+    let recent_days = (0..RECENT_DAYS)
+        .rev()
+        .map(|offset| {
+            let date = today - Duration::days(offset);
+            DayWords {
+                date: day_key(date),
+                words: journal.words_on(date),
+            }
+        })
+        .collect();
+
+    Ok(WritingStreak {
+        daily_goal: journal.daily_goal,
+        today_words,
+        goal_met: journal.daily_goal.is_some() && journal.day_counts(today_words),
+        current_streak: journal.current_streak(today),
+        longest_streak: journal.longest_streak(),
+        recent_days,
+    })
+}
+
+#[tauri::command]
+pub fn set_daily_goal(
+    words: Option<u32>,
+    app: AppHandle,
+    window: Window,
+    watcher_state: State<WatcherState>,
+    writing_state: State<WritingState>,
+) -> Result<(), String> {
+    let root = watcher_state
+        .active_root(window.label())
+        .ok_or_else(|| i18n::t("error.no_workspace"))?;
+
+    let _guard = writing_state
+        .lock
+        .lock()
+        .map_err(|e| i18n::tf("error.writing_journal_lock", &[&e]))?;
+
+    let path = storage::workspace_data_dir(&app, &root)?.join(JOURNAL_FILE);
+    let mut journal: WritingJournal = storage::read_json(&path);
+    journal.daily_goal = words.filter(|goal| *goal > 0);
+
+    storage::write_json(&path, &journal)
+}
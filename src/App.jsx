@@ -6,14 +6,25 @@ import WorkspaceRequiredModal from "./components/modals/WorkspaceRequiredModal";
 import NotificationToast from "./components/layout/NotificationToast";
 import TitleBar from "./components/layout/TitleBar";
 import ConfirmDialog from "./components/modals/ConfirmDialog";
+import PassphraseModal from "./components/modals/PassphraseModal";
 import useNotesStore, { SETTINGS_TAB_ID } from "./store/notesStore";
 import useSettingsStore, { matchesKeymap } from "./store/settingsStore";
 import useUIStore from "./store/uiStore";
 import { exportWorkspaceAsZip } from "./utils/backup";
+import { saveSession, readMarkdownFile, importDocuments } from "./utils/fileSystem";
+import { isNoteWindow, noteWindowPath } from "./utils/windowContext";
 import { checkForAppUpdate } from "./utils/appUpdater";
+import { exportTasksCalendar, getAdjacentDailyNote } from "./utils/calendar";
+import { shareNote } from "./utils/share";
+import { copyAsRichText } from "./utils/clipboard";
+import { setBadge } from "./utils/notifications";
+import { recordFeatureUse } from "./utils/telemetry";
 import { listen } from "@tauri-apps/api/event";
 import { writeText } from "@tauri-apps/plugin-clipboard-manager";
 import { useFileWatcher } from "./hooks/useFileWatcher";
+import { usePlugins } from "./hooks/usePlugins";
+import { useGitStatus } from "./hooks/useGitStatus";
+import { runPluginCommand } from "./utils/pluginHost";
 
 const TemplateModal = lazy(() => import("./components/modals/TemplateModal"));
 const ScheduleNoteModal = lazy(() => import("./components/modals/ScheduleNoteModal"));
@@ -21,11 +32,16 @@ const GraphModal = lazy(() => import("./components/modals/GraphModal"));
 const SearchModal = lazy(() => import("./components/modals/SearchModal"));
 const CommandPalette = lazy(() => import("./components/modals/CommandPalette"));
 const KeymapsModal = lazy(() => import("./components/modals/KeymapsModal"));
+const EmailNoteModal = lazy(() => import("./components/modals/EmailNoteModal"));
+const CompareNotesModal = lazy(() => import("./components/modals/CompareNotesModal"));
+const MeetingNoteModal = lazy(() => import("./components/modals/MeetingNoteModal"));
 
 const stripMarkdownExtension = (name = "") => name.replace(/\.(md|markdown|txt)$/i, "");
 
 function App() {
   useFileWatcher();
+  usePlugins();
+  useGitStatus();
 
   const items = useNotesStore((state) => state.items);
   const {
@@ -37,10 +53,20 @@ function App() {
     closeNote,
     currentNoteId,
     rootFolderPath,
+    vaultLocked,
+    unlockVaultWorkspace,
   } = useNotesStore();
-  const { keymaps, initializeSettings, isRecordingKeymap } = useSettingsStore();
-  const { focusMode, toggleFocusMode, showWorkspaceModal, setShowWorkspaceModal, addNotification } =
-    useUIStore();
+  const { keymaps, initializeSettings, loadKeymap, applyShortcuts, isRecordingKeymap } =
+    useSettingsStore();
+  const {
+    focusMode,
+    toggleFocusMode,
+    showWorkspaceModal,
+    setShowWorkspaceModal,
+    addNotification,
+    comparingNotes,
+    setComparingNotes,
+  } = useUIStore();
   const [isResizingSidebar, setIsResizingSidebar] = useState(false);
 
   // Initialize settings (apply accent color) on mount
@@ -48,6 +74,16 @@ function App() {
     initializeSettings();
   }, [initializeSettings]);
 
+  // Shortcuts live in the backend, which also puts them on the menu; other
+  // windows' changes arrive as `keymap-changed`
+  useEffect(() => {
+    loadKeymap().catch((error) => console.error("Failed to load keymap:", error));
+    const unlisten = listen("keymap-changed", (event) => applyShortcuts(event.payload));
+    return () => {
+      unlisten.then((fn) => fn()).catch(() => {});
+    };
+  }, [loadKeymap, applyShortcuts]);
+
   useEffect(() => {
     if (!import.meta.env.PROD) return;
 
@@ -67,6 +103,88 @@ function App() {
     }
   }, [rootFolderPath]);
 
+  // Note windows show just their note, refreshed from disk since local storage may be stale
+  useEffect(() => {
+    if (!isNoteWindow) return;
+
+    const openWindowNote = async () => {
+      const { refreshRootFromDisk, rootFolderPath, loadNoteFromFile } = useNotesStore.getState();
+      if (rootFolderPath) {
+        await refreshRootFromDisk().catch((error) =>
+          console.error("Failed to refresh note window:", error)
+        );
+      }
+
+      const state = useNotesStore.getState();
+      const note = state.items.find(
+        (item) => item.type === "note" && item.filePath === noteWindowPath
+      );
+      if (note) {
+        useNotesStore.setState({ openNoteIds: [note.id] });
+        state.selectNote(note.id);
+        return;
+      }
+
+      try {
+        const content = await readMarkdownFile(noteWindowPath);
+        const noteId = loadNoteFromFile({
+          content,
+          path: noteWindowPath,
+          name: noteWindowPath.split(/[\\/]/).pop(),
+        });
+        useNotesStore.setState({ openNoteIds: [noteId] });
+      } catch (error) {
+        addNotification("Failed to open note: " + error.message, "error");
+      }
+    };
+
+    openWindowNote();
+  }, [addNotification]);
+
+  // Save tabs, cursors, and expanded folders so relaunching restores the session
+  useEffect(() => {
+    if (isNoteWindow) return undefined;
+
+    let timeoutId = null;
+    const unsubscribe = useNotesStore.subscribe((state, previous) => {
+      if (
+        !state.rootFolderPath ||
+        state.isLoading ||
+        state.rootFolderPath !== previous.rootFolderPath ||
+        (state.openNoteIds === previous.openNoteIds &&
+          state.currentNoteId === previous.currentNoteId &&
+          state.expandedFolders === previous.expandedFolders &&
+          state.cursorPositions === previous.cursorPositions)
+      ) {
+        return;
+      }
+
+      const folderPath = state.rootFolderPath;
+      window.clearTimeout(timeoutId);
+      timeoutId = window.setTimeout(() => {
+        const current = useNotesStore.getState();
+        if (current.rootFolderPath !== folderPath) return;
+        saveSession(folderPath, current.getSessionSnapshot());
+      }, 1000);
+    });
+
+    return () => {
+      window.clearTimeout(timeoutId);
+      unsubscribe();
+    };
+  }, []);
+
+  // Unsaved notes feed the dock badge, next to the tasks due today
+  useEffect(() => {
+    setBadge(useNotesStore.getState().dirtyNoteIds.length);
+    const unsubscribe = useNotesStore.subscribe((state, previous) => {
+      if (state.dirtyNoteIds.length !== previous.dirtyNoteIds.length) {
+        setBadge(state.dirtyNoteIds.length);
+      }
+    });
+    return unsubscribe;
+  }, []);
+
   const startResizingSidebar = useCallback((e) => {
     e.preventDefault();
     setIsResizingSidebar(true);
@@ -104,7 +222,7 @@ function App() {
 
   const processDueSchedules = useNotesStore((state) => state.processDueSchedules);
   const [showKeymapsModal, setShowKeymapsModal] = useState(false);
-  const [showSidebar, setShowSidebar] = useState(true);
+  const [showSidebar, setShowSidebar] = useState(!isNoteWindow);
   const [closeConfirmation, setCloseConfirmation] = useState(null); // { noteId, noteName }
 
   // Modal states
@@ -116,6 +234,9 @@ function App() {
   const [scheduleTemplate, setScheduleTemplate] = useState(null);
   const [templateParentId, setTemplateParentId] = useState(null);
   const [renamingItem, setRenamingItem] = useState(null);
+  const [showCreateVaultModal, setShowCreateVaultModal] = useState(false);
+  const [showMeetingNoteModal, setShowMeetingNoteModal] = useState(false);
+  const [emailingNote, setEmailingNote] = useState(null);
 
   const sidebarRef = useRef(null);
   const editorRef = useRef(null);
@@ -193,6 +314,7 @@ function App() {
   const handleCommandExecute = useCallback(
     (command) => {
       const { action, payload } = command;
+      recordFeatureUse("command_palette");
 
       switch (action) {
         case "selectNote":
@@ -220,6 +342,29 @@ function App() {
         case "openFolder":
           sidebarRef.current?.handleOpenFolder?.();
           break;
+        case "importDocuments": {
+          const { rootFolderPath, items, getCurrentNote, refreshRootFromDisk } =
+            useNotesStore.getState();
+          if (!rootFolderPath) {
+            addNotification("No workspace folder is open", "warning");
+            break;
+          }
+          // Next to the open note, like new notes
+          const parentId = getCurrentNote()?.parentId;
+          const folderPath =
+            items.find((item) => item.id === parentId)?.filePath || rootFolderPath;
+          importDocuments(folderPath)
+            .then(async (paths) => {
+              if (paths.length === 0) return;
+              await refreshRootFromDisk();
+              addNotification(
+                `Imported ${paths.length} document${paths.length !== 1 ? "s" : ""}`,
+                "success"
+              );
+            })
+            .catch((error) => addNotification(error.message || String(error), "error"));
+          break;
+        }
         case "save":
           // Trigger save in editor
           editorRef.current?.handleSave?.();
@@ -245,6 +390,44 @@ function App() {
         case "exportNote":
           editorRef.current?.handleExport?.();
           break;
+        case "emailNote": {
+          const currentNote = useNotesStore.getState().getCurrentNote();
+          if (!currentNote?.filePath) {
+            addNotification("Save the note before emailing it", "info");
+          } else {
+            setEmailingNote(currentNote);
+          }
+          break;
+        }
+        case "shareNotePdf":
+        case "shareNoteHtml": {
+          const currentNote = useNotesStore.getState().getCurrentNote();
+          if (!currentNote?.filePath) {
+            addNotification("Save the note before sharing it", "info");
+            break;
+          }
+          const format = action === "shareNotePdf" ? "pdf" : "html";
+          shareNote(currentNote.filePath, currentNote.name, currentNote.content, format).catch(
+            (error) => addNotification("Failed to share note: " + error, "error")
+          );
+          break;
+        }
+        case "copyAsRichText": {
+          const currentNote = useNotesStore.getState().getCurrentNote();
+          if (!currentNote) {
+            addNotification("No note is selected", "info");
+            break;
+          }
+          // The selection, or the editor's text so unsaved edits come along
+          const selection = editorRef.current?.getSelectedText?.() || currentNote.content;
+          copyAsRichText(currentNote.filePath || null, selection)
+            .then(() => addNotification("Copied as rich text", "success"))
+            .catch((error) => addNotification("Failed to copy as rich text: " + error, "error"));
+          break;
+        }
+        case "pluginCommand":
+          runPluginCommand(payload.pluginId, payload.commandId);
+          break;
         case "renameCurrentNote": {
           const currentNote = useNotesStore.getState().getCurrentNote();
           if (currentNote) {
@@ -279,6 +462,20 @@ function App() {
             );
           break;
         }
+        case "copyBlockLink": {
+          const link = editorRef.current?.getBlockLink?.();
+          if (!link) {
+            addNotification("Open a note to link one of its blocks", "info");
+            break;
+          }
+          link
+            .then((text) => writeText(text))
+            .then(() => addNotification("Block link copied", "success", 1800))
+            .catch((error) =>
+              addNotification("Failed to copy block link: " + (error?.message || error), "error")
+            );
+          break;
+        }
         case "toggleCurrentNotePin": {
           const state = useNotesStore.getState();
           const currentNote = state.getCurrentNote();
@@ -303,6 +500,87 @@ function App() {
         case "toggleFocusMode":
           toggleFocusMode();
           break;
+        case "createVault": {
+          const { rootFolderPath: workspacePath, isVaultWorkspace } = useNotesStore.getState();
+          if (!workspacePath) {
+            addNotification("No workspace folder is open", "warning");
+          } else if (isVaultWorkspace) {
+            addNotification("This workspace is already an encrypted vault", "info");
+          } else {
+            setShowCreateVaultModal(true);
+          }
+          break;
+        }
+        case "lockVault": {
+          const { isVaultWorkspace, lockVaultWorkspace } = useNotesStore.getState();
+          if (!isVaultWorkspace) {
+            addNotification("This workspace isn’t an encrypted vault", "info");
+            break;
+          }
+          lockVaultWorkspace().catch((error) =>
+            addNotification("Failed to lock vault: " + error.message, "error")
+          );
+          break;
+        }
+        case "newMeetingNote":
+          if (!useNotesStore.getState().rootFolderPath) {
+            addNotification("No workspace folder is open", "warning");
+          } else if (useSettingsStore.getState().calendarSubscriptions.length === 0) {
+            addNotification("Add a calendar subscription in Settings first", "info");
+            selectNote(SETTINGS_TAB_ID);
+          } else {
+            setShowMeetingNoteModal(true);
+          }
+          break;
+        case "previousDailyNote":
+        case "nextDailyNote": {
+          const { rootFolderPath: workspacePath, getCurrentNote } = useNotesStore.getState();
+          if (!workspacePath) {
+            addNotification("No workspace folder is open", "warning");
+            break;
+          }
+          const earlier = action === "previousDailyNote";
+          // Step from the open note when it's a daily note, otherwise from today
+          const now = new Date();
+          const today = [
+            now.getFullYear(),
+            String(now.getMonth() + 1).padStart(2, "0"),
+            String(now.getDate()).padStart(2, "0"),
+          ].join("-");
+          const currentPath = getCurrentNote()?.filePath;
+          const lookup = (from) =>
+            getAdjacentDailyNote(workspacePath, from, earlier ? "previous" : "next");
+          (currentPath ? lookup(currentPath).catch(() => lookup(today)) : lookup(today))
+            .then((dailyNote) => {
+              const target =
+                dailyNote &&
+                useNotesStore
+                  .getState()
+                  .items.find((item) => item.type === "note" && item.filePath === dailyNote.path);
+              if (!target) {
+                addNotification(earlier ? "No earlier daily note" : "No later daily note", "info");
+                return;
+              }
+              selectNote(target.id);
+            })
+            .catch((error) =>
+              addNotification("Failed to find daily note: " + error.message, "error")
+            );
+          break;
+        }
+        case "toggleShowArchived": {
+          const { showArchived, setShowArchived } = useNotesStore.getState();
+          setShowArchived(!showArchived)
+            .then(() =>
+              addNotification(
+                showArchived ? "Archived notes hidden" : "Showing archived notes",
+                "success",
+                1800
+              )
+            )
+            .catch((error) => addNotification("Failed to refresh: " + error.message, "error"));
+          break;
+        }
         case "backupWorkspace": {
           const { rootFolderPath, items: storeItems } = useNotesStore.getState();
           const settings = useSettingsStore.getState();
@@ -325,6 +603,24 @@ function App() {
             });
           break;
         }
+        case "exportTasksCalendar": {
+          const { rootFolderPath } = useNotesStore.getState();
+          const { addNotification } = useUIStore.getState();
+          if (!rootFolderPath) {
+            addNotification("No workspace folder is open", "warning");
+            break;
+          }
+          exportTasksCalendar(rootFolderPath)
+            .then((count) => {
+              if (count === null) return;
+              addNotification(
+                count === 1 ? "Exported 1 task" : `Exported ${count} tasks`,
+                "success"
+              );
+            })
+            .catch((err) => addNotification("Failed to export tasks: " + err, "error"));
+          break;
+        }
         default:
           console.warn(`Unknown command action: ${action}`);
       }
@@ -333,7 +629,7 @@ function App() {
   );
 
   const [onboardingDismissed, setOnboardingDismissed] = useState(false);
-  const showOnboarding = items.length === 0 && !onboardingDismissed;
+  const showOnboarding = items.length === 0 && !onboardingDismissed && !isNoteWindow;
 
   // Global keyboard shortcut listener using configurable keymaps
   useEffect(() => {
@@ -424,6 +720,13 @@ function App() {
         return;
       }
 
+      // Open file
+      if (matchesKeymap(e, keymaps.openFile)) {
+        e.preventDefault();
+        sidebarRef.current?.handleOpenFile?.();
+        return;
+      }
+
       // Open folder
       if (matchesKeymap(e, keymaps.openFolder)) {
         e.preventDefault();
@@ -431,6 +734,13 @@ function App() {
         return;
       }
 
+      // Settings
+      if (matchesKeymap(e, keymaps.openSettings)) {
+        e.preventDefault();
+        selectNote(SETTINGS_TAB_ID);
+        return;
+      }
+
       // View modes
       if (matchesKeymap(e, keymaps.viewEditor)) {
         e.preventDefault();
@@ -460,7 +770,15 @@ function App() {
 
     window.addEventListener("keydown", handleKeyDown);
     return () => window.removeEventListener("keydown", handleKeyDown);
-  }, [keymaps, isRecordingKeymap, currentNoteId, handleCloseTab, focusMode, toggleFocusMode]);
+  }, [
+    keymaps,
+    isRecordingKeymap,
+    currentNoteId,
+    handleCloseTab,
+    focusMode,
+    toggleFocusMode,
+    selectNote,
+  ]);
 
   // Native macOS menu-bar event listeners
   useEffect(() => {
@@ -469,8 +787,8 @@ function App() {
 
     const attach = async (eventName, handler) => {
       try {
-        const unlisten = await listen(eventName, () => {
-          if (isMounted) handler();
+        const unlisten = await listen(eventName, (event) => {
+          if (isMounted) handler(event);
         });
         if (isMounted) {
           unlisteners.push(unlisten);
@@ -494,6 +812,18 @@ function App() {
       await attach("menu://open-settings", () => selectNote(SETTINGS_TAB_ID));
       await attach("menu://show-shortcuts", () => setShowKeymapsModal(true));
       await attach("menu://export-note", () => editorRef.current?.handleExport?.());
+      // Custom menu entries that run an automation on the open note
+      await attach("menu://run-automation", (event) => {
+        const { currentNoteId, runAutomationOnNote } = useNotesStore.getState();
+        runAutomationOnNote(currentNoteId, event.payload)
+          .then((entry) => {
+            const types = { done: "success", skipped: "info", failed: "error" };
+            addNotification(`${entry.rule_name}: ${entry.message}`, types[entry.outcome]);
+          })
+          .catch((err) => {
+            addNotification("Automation failed: " + (err?.message || err), "error");
+          });
+      });
       await attach("menu://backup-workspace", () => {
         const { rootFolderPath, items: storeItems } = useNotesStore.getState();
         const settings = useSettingsStore.getState();
@@ -538,6 +868,63 @@ function App() {
     setShowKeymapsModal,
   ]);
 
+  // Backend errors that never reach a command's caller still deserve a toast
+  useEffect(() => {
+    let isMounted = true;
+    let unlisten = null;
+
+    listen("backend-error", (event) => {
+      const { message } = event.payload || {};
+      if (isMounted && message) {
+        addNotification(message, "error", 5000);
+      }
+    })
+      .then((fn) => {
+        if (isMounted) {
+          unlisten = fn;
+        } else {
+          fn();
+        }
+      })
+      .catch((error) => console.error("Failed to listen for backend errors:", error));
+
+    return () => {
+      isMounted = false;
+      if (unlisten) unlisten();
+    };
+  }, [addNotification]);
+
+  // The backend locks idle vaults on its own; forget the decrypted tree when it does
+  useEffect(() => {
+    if (isNoteWindow) return undefined;
+
+    let isMounted = true;
+    let unlisten = null;
+
+    listen("vault-locked", (event) => {
+      if (!isMounted) return;
+      const { workspace } = event.payload || {};
+      const { vaultLocked: alreadyLocked, handleVaultLocked } = useNotesStore.getState();
+      if (!workspace || alreadyLocked) return;
+      if (handleVaultLocked(workspace)) {
+        addNotification("Vault locked", "info");
+      }
+    })
+      .then((fn) => {
+        if (isMounted) {
+          unlisten = fn;
+        } else {
+          fn();
+        }
+      })
+      .catch((error) => console.error("Failed to listen for vault locks:", error));
+
+    return () => {
+      isMounted = false;
+      if (unlisten) unlisten();
+    };
+  }, [addNotification]);
+
   useEffect(() => {
     if (typeof processDueSchedules !== "function") {
       return undefined;
@@ -639,11 +1026,53 @@ function App() {
         </div>
       </div>
       {showOnboarding && <OnboardingModal onSkip={() => setOnboardingDismissed(true)} />}
-      {showWorkspaceModal && <WorkspaceRequiredModal />}
+      {showWorkspaceModal && !isNoteWindow && <WorkspaceRequiredModal />}
+      {vaultLocked && rootFolderPath && !isNoteWindow && (
+        <PassphraseModal
+          isOpen
+          title="Unlock Vault"
+          message={`${rootFolderPath.split("/").pop()} is an encrypted vault. Enter its passphrase to open it.`}
+          confirmLabel="Unlock"
+          onSubmit={unlockVaultWorkspace}
+          onCancel={() =>
+            useNotesStore.setState({
+              rootFolderPath: null,
+              isVaultWorkspace: false,
+              vaultLocked: false,
+            })
+          }
+        />
+      )}
+      <PassphraseModal
+        isOpen={showCreateVaultModal}
+        title="Encrypt Workspace as Vault"
+        message="Every note, attachment, and file name in this workspace will be encrypted on disk. There's no way to recover the notes without this passphrase."
+        confirmLabel="Encrypt Workspace"
+        requireConfirmation
+        onSubmit={async (passphrase) => {
+          await useNotesStore.getState().createVaultFromWorkspace(passphrase);
+          setShowCreateVaultModal(false);
+          addNotification("Workspace is now an encrypted vault", "success");
+        }}
+        onCancel={() => setShowCreateVaultModal(false)}
+      />
       <Suspense fallback={null}>
         {showKeymapsModal && (
           <KeymapsModal isOpen={showKeymapsModal} onClose={() => setShowKeymapsModal(false)} />
         )}
+        {emailingNote && (
+          <EmailNoteModal
+            isOpen={Boolean(emailingNote)}
+            note={emailingNote}
+            onClose={() => setEmailingNote(null)}
+          />
+        )}
+        {showMeetingNoteModal && (
+          <MeetingNoteModal
+            isOpen={showMeetingNoteModal}
+            onClose={() => setShowMeetingNoteModal(false)}
+          />
+        )}
         {showTemplateModal && (
           <TemplateModal
             isOpen={showTemplateModal}
@@ -663,6 +1092,14 @@ function App() {
             }}
           />
         )}
+        {comparingNotes && (
+          <CompareNotesModal
+            isOpen={Boolean(comparingNotes)}
+            oldNote={comparingNotes.old}
+            newNote={comparingNotes.new}
+            onClose={() => setComparingNotes(null)}
+          />
+        )}
         {showGraphModal && (
           <GraphModal isOpen={showGraphModal} onClose={() => setShowGraphModal(false)} />
         )}
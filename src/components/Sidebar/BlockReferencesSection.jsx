@@ -0,0 +1,116 @@
+import { useEffect, useState } from "react";
+import useNotesStore from "../../store/notesStore";
+import { getBlockReferences } from "../../utils/blocks";
+
+const normalizePath = (value) => (value ? value.replace(/\\/g, "/") : "");
+
+// Blocks of the current note that other notes link to with `[[Note#^id]]`.
+// Hidden until there's at least one.
+const BlockReferencesSection = ({ note }) => {
+  const items = useNotesStore((state) => state.items);
+  const selectNote = useNotesStore((state) => state.selectNote);
+
+  const [showSection, setShowSection] = useState(true);
+  const [blocks, setBlocks] = useState([]);
+  const filePath = note?.filePath;
+  const content = note?.content;
+
+  useEffect(() => {
+    if (!filePath) {
+      setBlocks([]);
+      return;
+    }
+    let cancelled = false;
+    const timer = setTimeout(() => {
+      getBlockReferences(filePath)
+        .then((result) => {
+          if (!cancelled) setBlocks(result);
+        })
+        .catch(() => {
+          if (!cancelled) setBlocks([]);
+        });
+    }, 400);
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [filePath, content]);
+
+  if (!filePath || blocks.length === 0) return null;
+
+  const openNote = (path) => {
+    const target = normalizePath(path);
+    const match = items.find(
+      (item) => item.type === "note" && normalizePath(item.filePath) === target
+    );
+    if (match) selectNote(match.id);
+  };
+
+  return (
+    <div className="mb-0.5">
+      <button
+        onClick={() => setShowSection(!showSection)}
+        className="w-full px-2 py-1.5 flex items-center justify-between text-[11px] font-bold text-text-muted hover:text-text-secondary uppercase tracking-wider transition-colors rounded hover:bg-overlay-subtle group"
+        aria-expanded={showSection}
+      >
+        <div className="flex items-center gap-1.5">
+          <svg
+            className="w-3 h-3 opacity-50 group-hover:opacity-100"
+            fill="none"
+            stroke="currentColor"
+            viewBox="0 0 24 24"
+          >
+            <path
+              strokeLinecap="round"
+              strokeLinejoin="round"
+              strokeWidth={2}
+              d="M4 6h16M4 12h10M4 18h16"
+            />
+          </svg>
+          <span>Block References</span>
+          <span className="ml-1 px-1 py-px bg-accent/20 text-accent text-[9px] rounded-full">
+            {blocks.length}
+          </span>
+        </div>
+        <svg
+          className={`w-3 h-3 transition-transform opacity-50 group-hover:opacity-100 ${showSection ? "rotate-180" : ""}`}
+          fill="none"
+          stroke="currentColor"
+          viewBox="0 0 24 24"
+        >
+          <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M19 9l-7 7-7-7" />
+        </svg>
+      </button>
+
+      {showSection && (
+        <div className="space-y-1 mt-0.5">
+          {blocks.map((block) => (
+            <div key={block.id} className="px-1">
+              <div
+                className="px-2 py-1 text-xs"
+                title={block.line ? `Line ${block.line}` : "This note no longer has this block"}
+              >
+                <span className="font-mono text-accent">^{block.id}</span>
+                <span className={block.line ? "ml-1.5 text-text-muted" : "ml-1.5 text-red-400"}>
+                  {block.line ? block.text : "missing"}
+                </span>
+              </div>
+              {block.references.map((reference) => (
+                <button
+                  key={reference.path}
+                  onClick={() => openNote(reference.path)}
+                  className="w-full pl-5 pr-2 py-0.5 text-left text-sm text-text-secondary hover:text-text-primary hover:bg-overlay-subtle rounded-md truncate"
+                  title={reference.path}
+                >
+                  {reference.title}
+                </button>
+              ))}
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+};
+
+export default BlockReferencesSection;
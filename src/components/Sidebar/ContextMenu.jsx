@@ -1,7 +1,15 @@
-import { useState } from "react";
+import { useEffect, useState } from "react";
 import useNotesStore from "../../store/notesStore";
 import useUIStore from "../../store/uiStore";
 import ConfirmDialog from "../modals/ConfirmDialog";
+import PassphraseModal from "../modals/PassphraseModal";
+import { writeText } from "@tauri-apps/plugin-clipboard-manager";
+import { openNoteInNewWindow } from "../../utils/fileSystem";
+import { exportFolder, getExportFormats } from "../../utils/noteExport";
+import { describeDeletePreview, useDeletePreview } from "../../hooks/useDeletePreview";
+
+// Actions that open a dialog and close the menu once it's dismissed
+const DIALOG_ACTIONS = ["delete", "encrypt", "decrypt"];
 
 // Count all descendant notes inside a folder recursively
 const getDescendantCount = (folderId) => {
@@ -23,10 +31,60 @@ const getDescendantCount = (folderId) => {
 };
 
 const ContextMenu = ({ x, y, item, onClose, onRename, onShowTemplate }) => {
-  const { createFolder, deleteItem, undoLastDelete, togglePinNote, isPinned } = useNotesStore();
-  const { addNotification } = useUIStore();
+  const {
+    createFolder,
+    deleteItem,
+    undoLastDelete,
+    togglePinNote,
+    isPinned,
+    archiveItem,
+    unarchiveItem,
+    toggleNoteLock,
+    encryptNoteWithPassphrase,
+    decryptNoteToPlaintext,
+    lockEncryptedNote,
+    getStableNoteId,
+    rootFolderId,
+  } = useNotesStore();
+  const { addNotification, setComparingNotes } = useUIStore();
+  const currentNote = useNotesStore((state) => state.getCurrentNote());
+  const canCompare =
+    item.type === "note" &&
+    item.filePath &&
+    !item.encrypted &&
+    currentNote?.filePath &&
+    currentNote.filePath !== item.filePath;
   const isNotePinned = item.type === "note" && isPinned(item.id);
   const [showDeleteConfirm, setShowDeleteConfirm] = useState(false);
+  const [passphraseAction, setPassphraseAction] = useState(null); // 'encrypt' | 'decrypt'
+  const deletePreview = useDeletePreview(showDeleteConfirm ? item : null);
+  const [exportFormats, setExportFormats] = useState([]);
+  const canExportFolder = item.type === "folder" && item.filePath;
+
+  useEffect(() => {
+    if (!canExportFolder) return;
+    getExportFormats()
+      .then(setExportFormats)
+      .catch(() => setExportFormats([]));
+  }, [canExportFolder]);
+
+  // The menu closes first; a large folder can take a while to render
+  const handleExportFolder = async (format) => {
+    onClose();
+    try {
+      const result = await exportFolder(item.filePath, format);
+      if (!result) return;
+      const count = `${result.exported} note${result.exported !== 1 ? "s" : ""}`;
+      const skipped = result.skipped.length
+        ? ` (${result.skipped.length} skipped, such as encrypted notes)`
+        : "";
+      addNotification(`Exported ${count} to ${result.destination}${skipped}`, "success", 5000);
+    } catch (error) {
+      console.error("Folder export failed:", error);
+      addNotification("Export failed: " + (error?.message || error), "error");
+    }
+  };
+
 
   const handleAction = async (action) => {
     try {
@@ -39,6 +97,32 @@ const ContextMenu = ({ x, y, item, onClose, onRename, onShowTemplate }) => {
       } else if (action === "rename") {
         onRename(item);
         return;
+      } else if (action === "newWindow") {
+        await openNoteInNewWindow(item.filePath);
+      } else if (action === "compare") {
+        setComparingNotes({
+          old: { path: currentNote.filePath, name: currentNote.name },
+          new: { path: item.filePath, name: item.name },
+        });
+      } else if (action === "copyId") {
+        await writeText(await getStableNoteId(item.id));
+        addNotification("Note ID copied", "success", 1800);
+      } else if (action === "archive") {
+        if (item.archived) {
+          await unarchiveItem(item.id);
+          addNotification(`${item.type === "note" ? "Note" : "Folder"} restored`, "success", 1800);
+        } else {
+          await archiveItem(item.id);
+          addNotification(`${item.type === "note" ? "Note" : "Folder"} archived`, "success", 1800);
+        }
+      } else if (action === "lock") {
+        const locked = await toggleNoteLock(item.id);
+        addNotification(locked ? "Note locked" : "Note unlocked", "success", 1800);
+      } else if (action === "encrypt" || action === "decrypt") {
+        setPassphraseAction(action);
+        return;
+      } else if (action === "relock") {
+        lockEncryptedNote(item.id);
       } else if (action === "pin") {
         togglePinNote(item.id);
         onClose();
@@ -51,7 +135,7 @@ const ContextMenu = ({ x, y, item, onClose, onRename, onShowTemplate }) => {
       console.error("Context menu action failed:", error);
       addNotification("Action failed: " + error.message, "error");
     } finally {
-      if (action !== "delete") {
+      if (!DIALOG_ACTIONS.includes(action)) {
         onClose();
       }
     }
@@ -60,7 +144,7 @@ const ContextMenu = ({ x, y, item, onClose, onRename, onShowTemplate }) => {
   const handleConfirmDelete = async () => {
     try {
       const hasFilePath = !!item.filePath;
-      await deleteItem(item.id);
+      await deleteItem(item.id, { confirmToken: deletePreview?.token });
 
       if (hasFilePath) {
         addNotification(`${item.type === "note" ? "Note" : "Folder"} deleted`, "success", 5000, {
@@ -86,6 +170,23 @@ const ContextMenu = ({ x, y, item, onClose, onRename, onShowTemplate }) => {
     }
   };
 
+  const handlePassphraseSubmit = async (passphrase) => {
+    if (passphraseAction === "encrypt") {
+      await encryptNoteWithPassphrase(item.id, passphrase);
+      addNotification("Note encrypted", "success", 1800);
+    } else {
+      await decryptNoteToPlaintext(item.id, passphrase);
+      addNotification("Encryption removed", "success", 1800);
+    }
+    setPassphraseAction(null);
+    onClose();
+  };
+
+  const handlePassphraseCancel = () => {
+    setPassphraseAction(null);
+    onClose();
+  };
+
   const handleCancelDelete = () => {
     setShowDeleteConfirm(false);
     onClose();
@@ -143,6 +244,56 @@ const ContextMenu = ({ x, y, item, onClose, onRename, onShowTemplate }) => {
               </svg>
               Rename
             </button>
+            {item.type === "note" && item.filePath && (
+              <button
+                className="w-full px-3 py-2 text-left text-sm text-text-primary hover:bg-overlay-light flex items-center gap-2 transition-colors"
+                onClick={() => handleAction("newWindow")}
+              >
+                <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M10 6H6a2 2 0 00-2 2v10a2 2 0 002 2h10a2 2 0 002-2v-4M14 4h6m0 0v6m0-6L10 14"
+                  />
+                </svg>
+                Open in New Window
+              </button>
+            )}
+            {canCompare && (
+              <button
+                className="w-full px-3 py-2 text-left text-sm text-text-primary hover:bg-overlay-light flex items-center gap-2 transition-colors"
+                onClick={() => handleAction("compare")}
+                title={`Compare with ${currentNote.name}`}
+              >
+                <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M9 17V7m0 10a2 2 0 01-2 2H5a2 2 0 01-2-2V7a2 2 0 012-2h2a2 2 0 012 2m0 10a2 2 0 002 2h2a2 2 0 002-2M9 7a2 2 0 012-2h2a2 2 0 012 2m0 10V7m0 10a2 2 0 002 2h2a2 2 0 002-2V7a2 2 0 00-2-2h-2a2 2 0 00-2 2"
+                  />
+                </svg>
+                Compare with Current Note
+              </button>
+            )}
+            {item.type === "note" && item.filePath && !item.encrypted && (
+              <button
+                className="w-full px-3 py-2 text-left text-sm text-text-primary hover:bg-overlay-light flex items-center gap-2 transition-colors"
+                onClick={() => handleAction("copyId")}
+                title="Copy an id that keeps pointing at this note after renames and moves"
+              >
+                <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M7 20l4-16m2 16l4-16M6 9h14M4 15h14"
+                  />
+                </svg>
+                Copy Note ID
+              </button>
+            )}
             {item.type === "note" && (
               <button
                 className="w-full px-3 py-2 text-left text-sm text-text-primary hover:bg-overlay-light flex items-center gap-2 transition-colors"
@@ -164,6 +315,108 @@ const ContextMenu = ({ x, y, item, onClose, onRename, onShowTemplate }) => {
                 {isNotePinned ? "Unpin" : "Pin to top"}
               </button>
             )}
+            {item.type === "note" && item.filePath && (
+              <button
+                className="w-full px-3 py-2 text-left text-sm text-text-primary hover:bg-overlay-light flex items-center gap-2 transition-colors"
+                onClick={() => handleAction("lock")}
+              >
+                <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d={
+                      item.locked
+                        ? "M8 11V7a4 4 0 118 0m-4 8v2m-6 4h12a2 2 0 002-2v-6a2 2 0 00-2-2H6a2 2 0 00-2 2v6a2 2 0 002 2z"
+                        : "M12 15v2m-6 4h12a2 2 0 002-2v-6a2 2 0 00-2-2H6a2 2 0 00-2 2v6a2 2 0 002 2zm10-10V7a4 4 0 00-8 0v4h8z"
+                    }
+                  />
+                </svg>
+                {item.locked ? "Unlock" : "Lock"}
+              </button>
+            )}
+            {item.type === "note" && item.filePath && (
+              <button
+                className="w-full px-3 py-2 text-left text-sm text-text-primary hover:bg-overlay-light flex items-center gap-2 transition-colors"
+                onClick={() => handleAction(item.encrypted ? "decrypt" : "encrypt")}
+              >
+                <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M15 7a2 2 0 012 2m4 0a6 6 0 01-7.743 5.743L11 17H9v2H7v2H4a1 1 0 01-1-1v-2.586a1 1 0 01.293-.707l5.964-5.964A6 6 0 1121 9z"
+                  />
+                </svg>
+                {item.encrypted ? "Remove Encryption…" : "Encrypt…"}
+              </button>
+            )}
+            {item.decrypted && (
+              <button
+                className="w-full px-3 py-2 text-left text-sm text-text-primary hover:bg-overlay-light flex items-center gap-2 transition-colors"
+                onClick={() => handleAction("relock")}
+              >
+                <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M12 15v2m-6 4h12a2 2 0 002-2v-6a2 2 0 00-2-2H6a2 2 0 00-2 2v6a2 2 0 002 2zm10-10V7a4 4 0 00-8 0v4h8z"
+                  />
+                </svg>
+                Lock Encrypted Note
+              </button>
+            )}
+            {canExportFolder && exportFormats.includes("html") && (
+              <button
+                className="w-full px-3 py-2 text-left text-sm text-text-primary hover:bg-overlay-light flex items-center gap-2 transition-colors"
+                onClick={() => handleExportFolder("html")}
+                title="Export every note in this folder as linked HTML pages"
+              >
+                <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M4 16v1a3 3 0 003 3h10a3 3 0 003-3v-1m-4-8l-4-4m0 0L8 8m4-4v12"
+                  />
+                </svg>
+                Export as HTML…
+              </button>
+            )}
+            {canExportFolder && exportFormats.includes("pdf") && (
+              <button
+                className="w-full px-3 py-2 text-left text-sm text-text-primary hover:bg-overlay-light flex items-center gap-2 transition-colors"
+                onClick={() => handleExportFolder("pdf")}
+                title="Export every note in this folder as a PDF"
+              >
+                <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M4 16v1a3 3 0 003 3h10a3 3 0 003-3v-1m-4-8l-4-4m0 0L8 8m4-4v12"
+                  />
+                </svg>
+                Export as PDF…
+              </button>
+            )}
+            {item.filePath && item.id !== rootFolderId && (
+              <button
+                className="w-full px-3 py-2 text-left text-sm text-text-primary hover:bg-overlay-light flex items-center gap-2 transition-colors"
+                onClick={() => handleAction("archive")}
+              >
+                <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M5 8h14M5 8a2 2 0 110-4h14a2 2 0 110 4M5 8v10a2 2 0 002 2h10a2 2 0 002-2V8m-9 4h4"
+                  />
+                </svg>
+                {item.archived ? "Unarchive" : "Archive"}
+              </button>
+            )}
             <div className="my-1 border-t border-glass-border" />
             <button
               className="w-full px-3 py-2 text-left text-sm text-red-400 hover:bg-red-500/10 flex items-center gap-2 transition-colors"
@@ -187,6 +440,8 @@ const ContextMenu = ({ x, y, item, onClose, onRename, onShowTemplate }) => {
         isOpen={showDeleteConfirm}
         title={`Delete ${item.type === "folder" ? "Folder" : "Note"}`}
         message={(() => {
+          const warning = describeDeletePreview(deletePreview);
+          const suffix = warning ? ` ${warning}` : "";
           if (item.type === "folder") {
             const { noteCount, folderCount } = getDescendantCount(item.id);
             const parts = [];
@@ -194,10 +449,10 @@ const ContextMenu = ({ x, y, item, onClose, onRename, onShowTemplate }) => {
             if (folderCount > 0)
               parts.push(`${folderCount} subfolder${folderCount !== 1 ? "s" : ""}`);
             if (parts.length > 0) {
-              return `Are you sure you want to delete "${item.name}" and its ${parts.join(" and ")}? This action cannot be undone.`;
+              return `Are you sure you want to delete "${item.name}" and its ${parts.join(" and ")}? This action cannot be undone.${suffix}`;
             }
           }
-          return `Are you sure you want to delete "${item.name}"? This action cannot be undone.`;
+          return `Are you sure you want to delete "${item.name}"? This action cannot be undone.${suffix}`;
         })()}
         confirmLabel="Delete"
         cancelLabel="Cancel"
@@ -205,6 +460,20 @@ const ContextMenu = ({ x, y, item, onClose, onRename, onShowTemplate }) => {
         onConfirm={handleConfirmDelete}
         onCancel={handleCancelDelete}
       />
+
+      <PassphraseModal
+        isOpen={Boolean(passphraseAction)}
+        title={passphraseAction === "encrypt" ? "Encrypt Note" : "Remove Encryption"}
+        message={
+          passphraseAction === "encrypt"
+            ? `"${item.name}" will be unreadable without this passphrase. It can't be recovered if you forget it.`
+            : `"${item.name}" will be saved as plain text again.`
+        }
+        confirmLabel={passphraseAction === "encrypt" ? "Encrypt" : "Remove Encryption"}
+        requireConfirmation={passphraseAction === "encrypt"}
+        onSubmit={handlePassphraseSubmit}
+        onCancel={handlePassphraseCancel}
+      />
     </>
   );
 };
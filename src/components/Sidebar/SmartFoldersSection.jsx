@@ -0,0 +1,276 @@
+import { useCallback, useEffect, useRef, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import useNotesStore from "../../store/notesStore";
+import useUIStore from "../../store/uiStore";
+import ConfirmDialog from "../modals/ConfirmDialog";
+import SmartFolderModal from "../modals/SmartFolderModal";
+import {
+  deleteSmartFolder,
+  getSmartFolderContents,
+  listSmartFolders,
+} from "../../utils/smartFolders";
+
+const normalizePath = (value) => (value ? value.replace(/\\/g, "/") : "");
+
+const SmartFoldersSection = () => {
+  const rootFolderPath = useNotesStore((state) => state.rootFolderPath);
+  const items = useNotesStore((state) => state.items);
+  const selectNote = useNotesStore((state) => state.selectNote);
+  const addNotification = useUIStore((state) => state.addNotification);
+
+  const [showSection, setShowSection] = useState(false);
+  const [folders, setFolders] = useState([]);
+  const [expanded, setExpanded] = useState(() => new Set());
+  const [contents, setContents] = useState({});
+  const [editing, setEditing] = useState(null); // null | { folder }
+  const [pendingDelete, setPendingDelete] = useState(null);
+  const expandedRef = useRef(expanded);
+  expandedRef.current = expanded;
+
+  const loadFolders = useCallback(async () => {
+    if (!rootFolderPath) {
+      setFolders([]);
+      return;
+    }
+    try {
+      setFolders(await listSmartFolders(rootFolderPath));
+    } catch (error) {
+      console.error("Failed to load smart folders:", error);
+    }
+  }, [rootFolderPath]);
+
+  const loadContents = useCallback(
+    async (id) => {
+      if (!rootFolderPath) return;
+      try {
+        const result = await getSmartFolderContents(rootFolderPath, id);
+        setContents((current) => ({ ...current, [id]: result }));
+      } catch (error) {
+        setContents((current) => ({ ...current, [id]: { error: String(error?.message || error) } }));
+      }
+    },
+    [rootFolderPath]
+  );
+
+  useEffect(() => {
+    setExpanded(new Set());
+    setContents({});
+    loadFolders();
+  }, [loadFolders]);
+
+  // The watcher re-runs saved queries and names the folders whose notes changed
+  useEffect(() => {
+    if (!rootFolderPath) return;
+    let unlisten = null;
+    let cancelled = false;
+    listen("smart-folders-changed", (event) => {
+      if (normalizePath(event.payload?.workspace) !== normalizePath(rootFolderPath)) return;
+      loadFolders();
+      (event.payload?.ids || [])
+        .filter((id) => expandedRef.current.has(id))
+        .forEach((id) => loadContents(id));
+    }).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [rootFolderPath, loadFolders, loadContents]);
+
+  if (!rootFolderPath) return null;
+
+  const toggleFolder = (id) => {
+    const next = new Set(expanded);
+    if (next.has(id)) {
+      next.delete(id);
+    } else {
+      next.add(id);
+      loadContents(id);
+    }
+    setExpanded(next);
+  };
+
+  const openNote = (path) => {
+    const target = normalizePath(path);
+    const note = items.find(
+      (item) => item.type === "note" && normalizePath(item.filePath) === target
+    );
+    if (note) selectNote(note.id);
+  };
+
+  const handleSaved = (saved) => {
+    loadFolders();
+    if (expanded.has(saved.id)) loadContents(saved.id);
+  };
+
+  const handleDelete = async () => {
+    const folder = pendingDelete;
+    setPendingDelete(null);
+    if (!folder) return;
+    try {
+      await deleteSmartFolder(rootFolderPath, folder.id);
+      loadFolders();
+    } catch (error) {
+      addNotification("Failed to delete smart folder: " + (error?.message || error), "error");
+    }
+  };
+
+  return (
+    <div className="mb-0.5">
+      <div className="flex items-center group">
+        <button
+          onClick={() => setShowSection(!showSection)}
+          className="flex-1 px-2 py-1.5 flex items-center justify-between text-[11px] font-bold text-text-muted hover:text-text-secondary uppercase tracking-wider transition-colors rounded hover:bg-overlay-subtle"
+          aria-expanded={showSection}
+        >
+          <div className="flex items-center gap-1.5">
+            <svg
+              className="w-3 h-3 opacity-50 group-hover:opacity-100"
+              fill="none"
+              stroke="currentColor"
+              viewBox="0 0 24 24"
+            >
+              <path
+                strokeLinecap="round"
+                strokeLinejoin="round"
+                strokeWidth={2}
+                d="M3 7v10a2 2 0 002 2h14a2 2 0 002-2V9a2 2 0 00-2-2h-6l-2-2H5a2 2 0 00-2 2z"
+              />
+            </svg>
+            <span>Smart Folders</span>
+          </div>
+          <svg
+            className={`w-3 h-3 transition-transform opacity-50 group-hover:opacity-100 ${showSection ? "rotate-180" : ""}`}
+            fill="none"
+            stroke="currentColor"
+            viewBox="0 0 24 24"
+          >
+            <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M19 9l-7 7-7-7" />
+          </svg>
+        </button>
+        <button
+          onClick={() => {
+            setShowSection(true);
+            setEditing({ folder: null });
+          }}
+          className="ml-0.5 p-1 rounded text-text-muted hover:text-text-secondary hover:bg-overlay-subtle opacity-0 group-hover:opacity-100 focus:opacity-100 transition-opacity"
+          title="New smart folder"
+          aria-label="New smart folder"
+        >
+          <svg className="w-3 h-3" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+            <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M12 4v16m8-8H4" />
+          </svg>
+        </button>
+      </div>
+
+      {showSection && (
+        <div className="space-y-0.5 mt-0.5">
+          {folders.length === 0 && (
+            <p className="px-3 py-1.5 text-xs text-text-muted">
+              Save a query to list the notes that match it.
+            </p>
+          )}
+          {folders.map((folder) => {
+            const isExpanded = expanded.has(folder.id);
+            const result = contents[folder.id];
+            return (
+              <div key={folder.id}>
+                <div className="flex items-center group/folder rounded-md hover:bg-overlay-subtle">
+                  <button
+                    onClick={() => toggleFolder(folder.id)}
+                    className="flex-1 min-w-0 px-3 py-1.5 text-left text-sm text-text-secondary hover:text-text-primary flex items-center gap-2"
+                    aria-expanded={isExpanded}
+                    title={folder.error || folder.query}
+                  >
+                    <svg
+                      className={`w-2.5 h-2.5 shrink-0 transition-transform ${isExpanded ? "rotate-90" : ""}`}
+                      fill="none"
+                      stroke="currentColor"
+                      viewBox="0 0 24 24"
+                    >
+                      <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M9 5l7 7-7 7" />
+                    </svg>
+                    <span className="truncate">{folder.name}</span>
+                    <span
+                      className={`ml-auto text-[10px] ${folder.error ? "text-red-400" : "text-text-muted"}`}
+                    >
+                      {folder.error ? "!" : folder.count}
+                    </span>
+                  </button>
+                  <button
+                    onClick={() => setEditing({ folder })}
+                    className="p-1 text-text-muted hover:text-text-secondary opacity-0 group-hover/folder:opacity-100 focus:opacity-100"
+                    title="Edit smart folder"
+                    aria-label={`Edit ${folder.name}`}
+                  >
+                    <svg className="w-3 h-3" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                      <path
+                        strokeLinecap="round"
+                        strokeLinejoin="round"
+                        strokeWidth={2}
+                        d="M15.232 5.232l3.536 3.536M9 13l6.586-6.586a2 2 0 112.828 2.828L11.828 15.828A2 2 0 0110.414 16.414L7 17l.586-3.414A2 2 0 019 13z"
+                      />
+                    </svg>
+                  </button>
+                  <button
+                    onClick={() => setPendingDelete(folder)}
+                    className="p-1 mr-1 text-text-muted hover:text-red-400 opacity-0 group-hover/folder:opacity-100 focus:opacity-100"
+                    title="Delete smart folder"
+                    aria-label={`Delete ${folder.name}`}
+                  >
+                    <svg className="w-3 h-3" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                      <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M6 18L18 6M6 6l12 12" />
+                    </svg>
+                  </button>
+                </div>
+                {isExpanded && (
+                  <div className="ml-5 space-y-0.5">
+                    {result?.error && <p className="px-3 py-1 text-xs text-red-400">{result.error}</p>}
+                    {result?.rows?.length === 0 && (
+                      <p className="px-3 py-1 text-xs text-text-muted">No matching notes.</p>
+                    )}
+                    {result?.rows?.map((row) => (
+                      <button
+                        key={row.path}
+                        onClick={() => openNote(row.path)}
+                        className="w-full px-3 py-1 text-left text-sm text-text-secondary hover:text-text-primary hover:bg-overlay-subtle rounded-md transition-colors"
+                      >
+                        <span className="truncate block" title={row.path}>
+                          {row.title}
+                        </span>
+                      </button>
+                    ))}
+                    {result?.total > (result?.rows?.length || 0) && (
+                      <p className="px-3 py-1 text-[10px] text-text-muted">
+                        Showing {result.rows.length} of {result.total}
+                      </p>
+                    )}
+                  </div>
+                )}
+              </div>
+            );
+          })}
+        </div>
+      )}
+
+      <SmartFolderModal
+        isOpen={Boolean(editing)}
+        folder={editing?.folder || null}
+        rootFolderPath={rootFolderPath}
+        onClose={() => setEditing(null)}
+        onSaved={handleSaved}
+      />
+      <ConfirmDialog
+        isOpen={Boolean(pendingDelete)}
+        title="Delete Smart Folder"
+        message={`Delete "${pendingDelete?.name || ""}"? The notes it lists are not affected.`}
+        onConfirm={handleDelete}
+        onCancel={() => setPendingDelete(null)}
+      />
+    </div>
+  );
+};
+
+export default SmartFoldersSection;
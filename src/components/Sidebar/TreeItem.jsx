@@ -2,6 +2,15 @@ import { useState, useRef, useEffect } from "react";
 import useNotesStore from "../../store/notesStore";
 import useSettingsStore from "../../store/settingsStore";
 import useUIStore from "../../store/uiStore";
+import useGitStore from "../../store/gitStore";
+
+// Letters and colors as code editors show them
+const GIT_BADGES = {
+  modified: { letter: "M", className: "text-amber-400", label: "Modified" },
+  added: { letter: "A", className: "text-green-400", label: "Added" },
+  untracked: { letter: "U", className: "text-green-400", label: "Untracked" },
+  conflicted: { letter: "!", className: "text-red-400", label: "Conflicted" },
+};
 
 const TreeItem = ({
   item,
@@ -36,6 +45,13 @@ const TreeItem = ({
   const sidebarDensity = useSettingsStore((state) => state.sidebarDensity);
   const showSidebarMetadata = useSettingsStore((state) => state.showSidebarMetadata);
   const { addNotification } = useUIStore();
+  const gitStatus = useGitStore((state) => (item.filePath ? state.statusFor(item.filePath) : null));
+  const gitFolderChanged = useGitStore(
+    (state) =>
+      item.type === "folder" && Boolean(item.filePath) && state.hasChangesIn(item.filePath)
+  );
+  const gitBadge = GIT_BADGES[gitStatus];
+  const isGitIgnored = gitStatus === "ignored";
 
   const [isRenaming, setIsRenaming] = useState(false);
   const [renamingValue, setRenamingValue] = useState(item.name);
@@ -402,6 +418,7 @@ const TreeItem = ({
     if (children.length > 0) {
       metadataParts.push(`${children.length} item${children.length !== 1 ? "s" : ""}`);
     }
+    if (gitFolderChanged) metadataParts.push("has git changes");
   } else {
     if (isSelected) metadataParts.push("selected");
     if (showSidebarMetadata && isPinned(item.id)) metadataParts.push("pinned");
@@ -411,7 +428,9 @@ const TreeItem = ({
     if (noteTags.length > 0) {
       metadataParts.push(`tags ${noteTags.join(", ")}`);
     }
+    if (gitBadge) metadataParts.push(`git ${gitBadge.label.toLowerCase()}`);
   }
+  if (isGitIgnored) metadataParts.push("ignored by git");
   const treeItemLabel = `${isFolder ? "Folder" : "Note"} ${item.name}${
     metadataParts.length ? `, ${metadataParts.join(", ")}` : ""
   }`;
@@ -545,7 +564,7 @@ const TreeItem = ({
         ) : (
           <>
             <span
-              className={`flex-1 ${nameDensityClass} truncate`}
+              className={`flex-1 ${nameDensityClass} truncate ${isGitIgnored ? "opacity-50" : ""}`}
               title={item.filePath || item.name}
             >
               {item.name}
@@ -594,6 +613,23 @@ const TreeItem = ({
                 )}
               </div>
             )}
+
+            {/* Git status */}
+            {gitBadge && (
+              <span
+                className={`ml-2 shrink-0 text-[10px] font-semibold ${gitBadge.className}`}
+                title={gitBadge.label}
+              >
+                {gitBadge.letter}
+              </span>
+            )}
+            {gitFolderChanged && !isExpanded && (
+              <span
+                className="ml-2 shrink-0 w-1.5 h-1.5 rounded-full bg-amber-400"
+                title="Contains changes"
+                aria-hidden="true"
+              />
+            )}
           </>
         )}
 
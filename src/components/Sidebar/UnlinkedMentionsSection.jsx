@@ -0,0 +1,172 @@
+import { useCallback, useEffect, useMemo, useState } from "react";
+import useNotesStore from "../../store/notesStore";
+import useUIStore from "../../store/uiStore";
+import { getUnlinkedMentions } from "../../utils/mentions";
+
+const normalizePath = (value) => (value ? value.replace(/\\/g, "/") : "");
+
+// Other notes that name the current one in plain text, each mention one click
+// away from becoming a link
+const UnlinkedMentionsSection = ({ note }) => {
+  const items = useNotesStore((state) => state.items);
+  const selectNote = useNotesStore((state) => state.selectNote);
+  const linkMentions = useNotesStore((state) => state.linkMentions);
+  const addNotification = useUIStore((state) => state.addNotification);
+
+  const [showSection, setShowSection] = useState(false);
+  const [mentions, setMentions] = useState([]);
+  const [isLoading, setIsLoading] = useState(false);
+  const [error, setError] = useState(null);
+  const filePath = note?.filePath;
+
+  const loadMentions = useCallback(async () => {
+    if (!filePath) return;
+    setIsLoading(true);
+    try {
+      setMentions(await getUnlinkedMentions(filePath));
+      setError(null);
+    } catch (loadError) {
+      setMentions([]);
+      setError(String(loadError?.message || loadError));
+    } finally {
+      setIsLoading(false);
+    }
+  }, [filePath]);
+
+  useEffect(() => {
+    setMentions([]);
+    setError(null);
+    if (showSection) loadMentions();
+  }, [showSection, loadMentions]);
+
+  const groups = useMemo(() => {
+    const byPath = new Map();
+    for (const mention of mentions) {
+      if (!byPath.has(mention.path)) {
+        byPath.set(mention.path, { path: mention.path, title: mention.title, mentions: [] });
+      }
+      byPath.get(mention.path).mentions.push(mention);
+    }
+    return [...byPath.values()];
+  }, [mentions]);
+
+  if (!filePath) return null;
+
+  const openNote = (path) => {
+    const target = normalizePath(path);
+    const match = items.find(
+      (item) => item.type === "note" && normalizePath(item.filePath) === target
+    );
+    if (match) selectNote(match.id);
+  };
+
+  const handleLink = async (path, selected) => {
+    try {
+      await linkMentions(path, filePath, selected);
+      addNotification(
+        selected.length === 1 ? "Mention linked" : `${selected.length} mentions linked`,
+        "success",
+        1800
+      );
+    } catch (linkError) {
+      addNotification("Failed to link mention: " + (linkError?.message || linkError), "error");
+    }
+    loadMentions();
+  };
+
+  return (
+    <div className="mb-0.5">
+      <button
+        onClick={() => setShowSection(!showSection)}
+        className="w-full px-2 py-1.5 flex items-center justify-between text-[11px] font-bold text-text-muted hover:text-text-secondary uppercase tracking-wider transition-colors rounded hover:bg-overlay-subtle group"
+        aria-expanded={showSection}
+      >
+        <div className="flex items-center gap-1.5">
+          <svg
+            className="w-3 h-3 opacity-50 group-hover:opacity-100"
+            fill="none"
+            stroke="currentColor"
+            viewBox="0 0 24 24"
+          >
+            <path
+              strokeLinecap="round"
+              strokeLinejoin="round"
+              strokeWidth={2}
+              d="M8 12h.01M12 12h.01M16 12h.01M21 12c0 4.418-4.03 8-9 8a9.863 9.863 0 01-4.255-.949L3 20l1.395-3.72C3.512 15.042 3 13.574 3 12c0-4.418 4.03-8 9-8s9 3.582 9 8z"
+            />
+          </svg>
+          <span>Unlinked Mentions</span>
+          {showSection && mentions.length > 0 && (
+            <span className="ml-1 px-1 py-px bg-accent/20 text-accent text-[9px] rounded-full">
+              {mentions.length}
+            </span>
+          )}
+        </div>
+        <svg
+          className={`w-3 h-3 transition-transform opacity-50 group-hover:opacity-100 ${showSection ? "rotate-180" : ""}`}
+          fill="none"
+          stroke="currentColor"
+          viewBox="0 0 24 24"
+        >
+          <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M19 9l-7 7-7-7" />
+        </svg>
+      </button>
+
+      {showSection && (
+        <div className="space-y-1 mt-0.5">
+          {isLoading && mentions.length === 0 && (
+            <p className="px-3 py-1.5 text-xs text-text-muted">Looking for mentions…</p>
+          )}
+          {error && <p className="px-3 py-1.5 text-xs text-red-400">{error}</p>}
+          {!isLoading && !error && groups.length === 0 && (
+            <p className="px-3 py-1.5 text-xs text-text-muted">
+              No other note mentions this one without linking it.
+            </p>
+          )}
+          {groups.map((group) => (
+            <div key={group.path} className="px-1">
+              <div className="flex items-center group/mention">
+                <button
+                  onClick={() => openNote(group.path)}
+                  className="flex-1 min-w-0 px-2 py-1 text-left text-sm font-medium text-text-secondary hover:text-text-primary truncate"
+                  title={group.path}
+                >
+                  {group.title}
+                </button>
+                {group.mentions.length > 1 && (
+                  <button
+                    onClick={() => handleLink(group.path, group.mentions)}
+                    className="px-1.5 py-0.5 mr-1 text-[10px] rounded text-accent hover:bg-accent/10 opacity-0 group-hover/mention:opacity-100 focus:opacity-100"
+                  >
+                    Link all
+                  </button>
+                )}
+              </div>
+              {group.mentions.map((mention) => (
+                <div
+                  key={mention.start}
+                  className="flex items-start gap-2 pl-4 pr-1 py-1 rounded-md hover:bg-overlay-subtle group/item"
+                >
+                  <p className="flex-1 min-w-0 text-xs text-text-muted line-clamp-2 break-words">
+                    {mention.before}
+                    <mark className="bg-accent/20 text-text-primary rounded px-0.5">{mention.text}</mark>
+                    {mention.after}
+                  </p>
+                  <button
+                    onClick={() => handleLink(group.path, [mention])}
+                    className="shrink-0 px-1.5 py-0.5 text-[10px] rounded text-accent hover:bg-accent/10 opacity-0 group-hover/item:opacity-100 focus:opacity-100"
+                    title={`Line ${mention.line}`}
+                  >
+                    Link
+                  </button>
+                </div>
+              ))}
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+};
+
+export default UnlinkedMentionsSection;
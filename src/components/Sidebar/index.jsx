@@ -9,22 +9,32 @@ import {
 } from "react";
 import { listen } from "@tauri-apps/api/event";
 import { getCurrentWebview } from "@tauri-apps/api/webview";
-import useNotesStore from "../../store/notesStore";
+import useNotesStore, { tagMatches } from "../../store/notesStore";
 import useSettingsStore from "../../store/settingsStore";
 import useUIStore from "../../store/uiStore";
 import { checkForAppUpdate, installAppUpdate } from "../../utils/appUpdater";
+import { isNoteWindow } from "../../utils/windowContext";
 
 import {
   openMarkdownFile,
   saveMarkdownFile,
   openFolder,
+  getVaultStatus,
+  scanFolder,
   copyEntriesToFolder,
+  notifyWebviewReady,
+  openWorkspaceWindow,
 } from "../../utils/fileSystem";
+import { dragNoteOut } from "../../utils/share";
 
 import TreeItem from "./TreeItem";
 import ContextMenu from "./ContextMenu";
 import BacklinkItem from "./BacklinkItem";
+import UnlinkedMentionsSection from "./UnlinkedMentionsSection";
+import BlockReferencesSection from "./BlockReferencesSection";
+import SmartFoldersSection from "./SmartFoldersSection";
 import ConfirmDialog from "../modals/ConfirmDialog";
+import { describeDeletePreview, useDeletePreview } from "../../hooks/useDeletePreview";
 import { UpdateIcon } from "../icons/AppUpdateIcon";
 
 const VIRTUAL_TREE_THRESHOLD = 250;
@@ -129,7 +139,8 @@ const Sidebar = forwardRef(
     const [showTags, setShowTags] = useState(false);
     const [showBrokenLinks, setShowBrokenLinks] = useState(true);
     const [showBacklinks, setShowBacklinks] = useState(true);
-    const [tagSortMode, setTagSortMode] = useState("frequency"); // 'frequency' | 'alpha' | 'recent'
+    const [tagSortMode, setTagSortMode] = useState("frequency"); // 'frequency' | 'alpha' | 'recent' | 'tree'
+    const [expandedTags, setExpandedTags] = useState(() => new Set());
     const [sortBy, setSortBy] = useState("name-asc"); // 'name-asc', 'name-desc', 'date-desc', 'date-asc'
     const [showSortMenu, setShowSortMenu] = useState(false);
     const [showWorkspaceSwitcher, setShowWorkspaceSwitcher] = useState(false);
@@ -137,6 +148,7 @@ const Sidebar = forwardRef(
     const [isExternalDragging, setIsExternalDragging] = useState(false);
     const [isRootDropActive, setIsRootDropActive] = useState(false);
     const [pendingDeleteItem, setPendingDeleteItem] = useState(null);
+    const deletePreview = useDeletePreview(pendingDeleteItem);
     const [treeScrollTop, setTreeScrollTop] = useState(0);
     const [treeViewportHeight, setTreeViewportHeight] = useState(0);
     const dropHandledRef = useRef(false);
@@ -152,12 +164,9 @@ const Sidebar = forwardRef(
     // Compute all tags from items - this will re-compute when items change
     const allTagsArray = useMemo(() => {
       const tagCounts = items
-        .filter((item) => item.type === "note" && item.content)
+        .filter((item) => item.type === "note" && item.tags)
         .reduce((acc, note) => {
-          const tags = (note.content.match(/(?:^|[\s])#([a-zA-Z0-9_-]+)/g) || []).map((t) =>
-            t.trim().substring(1).toLowerCase()
-          );
-          tags.forEach((tag) => {
+          note.tags.forEach((tag) => {
             acc[tag] = (acc[tag] || 0) + 1;
           });
           return acc;
@@ -179,6 +188,48 @@ const Sidebar = forwardRef(
       return arr.sort((a, b) => b.count - a.count);
     }, [items, tagSortMode]);
 
+    // Nested tags (`#project/client`) as a tree; a parent counts every note
+    // under it once, like `get_tag_tree` in the backend
+    const tagTree = useMemo(() => {
+      const nodes = new Map();
+      const nodeFor = (tag) => {
+        if (!nodes.has(tag)) {
+          nodes.set(tag, {
+            tag,
+            name: tag.slice(tag.lastIndexOf("/") + 1),
+            count: 0,
+            direct: 0,
+            children: [],
+          });
+        }
+        return nodes.get(tag);
+      };
+      items
+        .filter((item) => item.type === "note" && item.tags)
+        .forEach((note) => {
+          const covered = new Set();
+          note.tags.forEach((tag) => {
+            nodeFor(tag).direct += 1;
+            tag.split("/").forEach((_, index, segments) => {
+              covered.add(segments.slice(0, index + 1).join("/"));
+            });
+          });
+          covered.forEach((tag) => {
+            nodeFor(tag).count += 1;
+          });
+        });
+
+      const roots = [];
+      [...nodes.keys()]
+        .sort((a, b) => a.localeCompare(b))
+        .forEach((tag) => {
+          const slash = tag.lastIndexOf("/");
+          if (slash === -1) roots.push(nodes.get(tag));
+          else nodeFor(tag.slice(0, slash)).children.push(nodes.get(tag));
+        });
+      return roots;
+    }, [items]);
+
     // Search filtering function
     const filterItemsBySearch = useCallback((items, query) => {
       if (!query.trim()) return items;
@@ -216,12 +267,12 @@ const Sidebar = forwardRef(
       if (selectedTags.length > 0) {
         const tagFilteredNotes = new Set();
         items.forEach((item) => {
-          if (item.type === "note" && item.content) {
-            const noteTags =
-              item.content
-                .match(/(?:^|[\s])#([a-zA-Z0-9_-]+)/g)
-                ?.map((t) => t.trim().substring(1).toLowerCase()) || [];
-            if (selectedTags.every((tag) => noteTags.includes(tag))) {
+          if (item.type === "note" && item.tags) {
+            // Filtering by a parent tag includes everything nested under it
+            const matches = selectedTags.every((tag) =>
+              item.tags.some((noteTag) => tagMatches(noteTag, tag))
+            );
+            if (matches) {
               tagFilteredNotes.add(item.id);
               let current = item;
               while (current.parentId) {
@@ -428,15 +479,28 @@ const Sidebar = forwardRef(
       }
     }, [addNotification, loadFolderFromSystem]);
 
+    const handleOpenFolderInNewWindow = useCallback(async () => {
+      try {
+        const folderData = await openFolder();
+        if (folderData) {
+          await openWorkspaceWindow(folderData.folderPath);
+        }
+      } catch (error) {
+        console.error("Failed to open folder window:", error);
+        addNotification("Failed to open folder in a new window: " + error.message, "error");
+      }
+    }, [addNotification]);
+
     // Expose methods to parent via ref
     useImperativeHandle(
       ref,
       () => ({
         handleNewNote,
         handleNewFolder,
+        handleOpenFile,
         handleOpenFolder,
       }),
-      [handleNewNote, handleNewFolder, handleOpenFolder]
+      [handleNewNote, handleNewFolder, handleOpenFile, handleOpenFolder]
     );
 
     const handleSave = useCallback(async () => {
@@ -600,7 +664,9 @@ const Sidebar = forwardRef(
         try {
           const { undoLastDelete } = useNotesStore.getState();
           const hasFilePath = Boolean(item.filePath);
-          await useNotesStore.getState().deleteItem(item.id);
+          await useNotesStore
+            .getState()
+            .deleteItem(item.id, { confirmToken: deletePreview?.token });
 
           addNotification(
             `${item.type === "note" ? "Note" : "Folder"} deleted`,
@@ -626,7 +692,7 @@ const Sidebar = forwardRef(
           setPendingDeleteItem(null);
         }
       },
-      [addNotification]
+      [addNotification, deletePreview]
     );
 
     const handleDropToRoot = async (event) => {
@@ -672,9 +738,23 @@ const Sidebar = forwardRef(
         setDragPosition({ x: e.clientX, y: e.clientY });
       };
 
+      // Carrying a note past the window's edge hands it to the system as a
+      // real file, for dropping into Finder, Explorer or a mail message
+      const handleLeaveWindow = (e) => {
+        if (draggedItem.type !== "note" || !draggedItem.filePath || !(e.buttons & 1)) return;
+        setDraggedItem(null);
+        dragNoteOut(draggedItem.filePath, draggedItem.name, null, "markdown").catch((error) => {
+          addNotification("Couldn't drag the note out: " + (error?.message || error), "error");
+        });
+      };
+
       document.addEventListener("mousemove", handleMouseMove);
-      return () => document.removeEventListener("mousemove", handleMouseMove);
-    }, [draggedItem]);
+      document.documentElement.addEventListener("mouseleave", handleLeaveWindow);
+      return () => {
+        document.removeEventListener("mousemove", handleMouseMove);
+        document.documentElement.removeEventListener("mouseleave", handleLeaveWindow);
+      };
+    }, [draggedItem, addNotification]);
 
     const handleTreeMouseMove = useCallback(
       (event) => {
@@ -812,6 +892,50 @@ const Sidebar = forwardRef(
       handleCloseNote,
     ]);
 
+    // Reopen the last workspace once the backend knows we're listening
+    useEffect(() => {
+      if (typeof window === "undefined" || !window.__TAURI__ || isNoteWindow) {
+        return undefined;
+      }
+
+      let isMounted = true;
+      let unlisten = null;
+
+      const restoreWorkspace = async () => {
+        try {
+          unlisten = await listen("open-folder", async (event) => {
+            const { path, name } = event.payload || {};
+            if (!isMounted || !path) return;
+            try {
+              const vaultStatus = await getVaultStatus(path);
+              const files =
+                vaultStatus.is_vault && !vaultStatus.unlocked
+                  ? []
+                  : await scanFolder(path, useNotesStore.getState().showArchived);
+              await loadFolderFromSystem({ folderPath: path, folderName: name, files });
+            } catch (error) {
+              console.error("Failed to reopen workspace:", error);
+              addNotification("Could not reopen workspace: " + error.message, "error");
+            }
+          });
+          if (!isMounted) {
+            unlisten();
+            return;
+          }
+          await notifyWebviewReady(useNotesStore.getState().rootFolderPath);
+        } catch (error) {
+          console.error("Failed to register open-folder listener:", error);
+        }
+      };
+
+      restoreWorkspace();
+
+      return () => {
+        isMounted = false;
+        if (unlisten) unlisten();
+      };
+    }, [addNotification, loadFolderFromSystem]);
+
     // Prevent browser default drag-drop behavior (opening files)
     useEffect(() => {
       const preventDefaults = (e) => {
@@ -978,6 +1102,72 @@ const Sidebar = forwardRef(
       };
     }, [rootFolderPath, refreshRootFromDisk, addNotification]);
 
+    const toggleTagExpanded = (tag) => {
+      setExpandedTags((current) => {
+        const next = new Set(current);
+        if (next.has(tag)) next.delete(tag);
+        else next.add(tag);
+        return next;
+      });
+    };
+
+    const renderTagNode = (node, depth) => {
+      const isSelected = selectedTags.includes(node.tag);
+      const hasChildren = node.children.length > 0;
+      const isExpanded = expandedTags.has(node.tag);
+      const noteLabel = `${node.count} note${node.count !== 1 ? "s" : ""}`;
+      return (
+        <div
+          key={node.tag}
+          role="treeitem"
+          aria-expanded={hasChildren ? isExpanded : undefined}
+          aria-selected={isSelected}
+        >
+          <div className="flex items-center gap-0.5" style={{ paddingLeft: depth * 12 }}>
+            {hasChildren ? (
+              <button
+                onClick={() => toggleTagExpanded(node.tag)}
+                className="w-4 h-4 flex items-center justify-center text-text-muted hover:text-text-secondary"
+                aria-label={`${isExpanded ? "Collapse" : "Expand"} #${node.tag}`}
+              >
+                <svg
+                  className={`w-2.5 h-2.5 transition-transform ${isExpanded ? "rotate-90" : ""}`}
+                  fill="none"
+                  stroke="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M9 5l7 7-7 7" />
+                </svg>
+              </button>
+            ) : (
+              <span className="w-4 h-4" />
+            )}
+            <button
+              onClick={() => toggleTagFilter(node.tag)}
+              title={
+                node.direct !== node.count
+                  ? `${noteLabel}, ${node.direct} tagged #${node.tag} directly`
+                  : noteLabel
+              }
+              aria-pressed={isSelected}
+              aria-label={`${isSelected ? "Remove" : "Add"} tag filter ${node.tag}, ${noteLabel}`}
+              className={`flex-1 min-w-0 flex items-center justify-between px-1.5 py-0.5 text-[11px] rounded transition-colors ${
+                isSelected
+                  ? "bg-accent/10 text-accent"
+                  : "text-text-muted hover:text-text-secondary hover:bg-overlay-subtle"
+              }`}
+            >
+              <span className="truncate">#{node.name}</span>
+              <span className="ml-2 text-[10px] opacity-60">{node.count}</span>
+            </button>
+          </div>
+          {hasChildren && isExpanded && (
+            <div role="group">{node.children.map((child) => renderTagNode(child, depth + 1))}</div>
+          )}
+        </div>
+      );
+    };
+
     return (
       <div className="w-full bg-sidebar-bg flex flex-col h-full bg-linear-to-b from-sidebar-bg to-bg-base/50">
         {/* Search Bar */}
@@ -1157,6 +1347,28 @@ const Sidebar = forwardRef(
                         </svg>
                         Open another folder…
                       </button>
+                      <button
+                        onClick={() => {
+                          setShowWorkspaceSwitcher(false);
+                          handleOpenFolderInNewWindow();
+                        }}
+                        className="w-full flex items-center gap-2 px-3 py-2 text-left text-xs text-text-secondary hover:bg-overlay-light hover:text-text-primary transition-colors"
+                      >
+                        <svg
+                          className="w-3.5 h-3.5 shrink-0"
+                          fill="none"
+                          stroke="currentColor"
+                          viewBox="0 0 24 24"
+                        >
+                          <path
+                            strokeLinecap="round"
+                            strokeLinejoin="round"
+                            strokeWidth={2}
+                            d="M10 6H6a2 2 0 00-2 2v10a2 2 0 002 2h10a2 2 0 002-2v-4M14 4h6m0 0v6m0-6L10 14"
+                          />
+                        </svg>
+                        Open folder in new window…
+                      </button>
                     </div>
                   </>
                 )}
@@ -1302,6 +1514,8 @@ const Sidebar = forwardRef(
             </div>
           )}
 
+          {!searchQuery && <SmartFoldersSection />}
+
           {/* Recent Section */}
           {!searchQuery && getRecentNotes().length > 0 && (
             <div className="mb-0.5">
@@ -1412,6 +1626,7 @@ const Sidebar = forwardRef(
                       { id: "frequency", label: "#" },
                       { id: "alpha", label: "A–Z" },
                       { id: "recent", label: "Recent" },
+                      { id: "tree", label: "Tree" },
                     ].map(({ id, label }) => (
                       <button
                         key={id}
@@ -1427,7 +1642,9 @@ const Sidebar = forwardRef(
                             ? "Sort by frequency"
                             : id === "alpha"
                               ? "Sort A–Z"
-                              : "Sort by recent use"
+                              : id === "recent"
+                                ? "Sort by recent use"
+                                : "Show nested tags as a tree"
                         }
                       >
                         {label}
@@ -1456,30 +1673,36 @@ const Sidebar = forwardRef(
                       </button>
                     )}
                   </div>
-                  <div className="flex flex-wrap gap-1.5">
-                    {allTagsArray.map(({ tag, count }) => {
-                      const isSelected = selectedTags.includes(tag);
-                      return (
-                        <button
-                          key={tag}
-                          onClick={() => toggleTagFilter(tag)}
-                          title={`${count} note${count !== 1 ? "s" : ""}`}
-                          aria-pressed={isSelected}
-                          aria-label={`${isSelected ? "Remove" : "Add"} tag filter ${tag}, ${count} note${count !== 1 ? "s" : ""}`}
-                          className={`
-                          px-2 py-0.5 text-[10px] rounded-full border transition-all
-                          ${
-                            isSelected
-                              ? "bg-accent/10 border-accent/20 text-accent"
-                              : "bg-overlay-subtle border-overlay-subtle text-text-muted hover:text-text-secondary hover:border-overlay-light"
-                          }
-                        `}
-                        >
-                          #{tag}
-                        </button>
-                      );
-                    })}
-                  </div>
+                  {tagSortMode === "tree" ? (
+                    <div role="tree" aria-label="Tags">
+                      {tagTree.map((node) => renderTagNode(node, 0))}
+                    </div>
+                  ) : (
+                    <div className="flex flex-wrap gap-1.5">
+                      {allTagsArray.map(({ tag, count }) => {
+                        const isSelected = selectedTags.includes(tag);
+                        return (
+                          <button
+                            key={tag}
+                            onClick={() => toggleTagFilter(tag)}
+                            title={`${count} note${count !== 1 ? "s" : ""}`}
+                            aria-pressed={isSelected}
+                            aria-label={`${isSelected ? "Remove" : "Add"} tag filter ${tag}, ${count} note${count !== 1 ? "s" : ""}`}
+                            className={`
+                            px-2 py-0.5 text-[10px] rounded-full border transition-all
+                            ${
+                              isSelected
+                                ? "bg-accent/10 border-accent/20 text-accent"
+                                : "bg-overlay-subtle border-overlay-subtle text-text-muted hover:text-text-secondary hover:border-overlay-light"
+                            }
+                          `}
+                          >
+                            #{tag}
+                          </button>
+                        );
+                      })}
+                    </div>
+                  )}
                 </div>
               )}
             </div>
@@ -1629,6 +1852,9 @@ const Sidebar = forwardRef(
             </div>
           )}
 
+          {!searchQuery && currentNote && <BlockReferencesSection note={currentNote} />}
+          {!searchQuery && currentNote && <UnlinkedMentionsSection note={currentNote} />}
+
           {/* Navigation Buttons Row */}
           {!searchQuery && (
             <div className="grid grid-cols-2 gap-1 px-1 mb-3 pt-2">
@@ -2009,7 +2235,9 @@ const Sidebar = forwardRef(
         <ConfirmDialog
           isOpen={Boolean(pendingDeleteItem)}
           title={`Delete ${pendingDeleteItem?.type === "folder" ? "Folder" : "Note"}`}
-          message={getDeleteMessage(pendingDeleteItem) || ""}
+          message={[getDeleteMessage(pendingDeleteItem), describeDeletePreview(deletePreview)]
+            .filter(Boolean)
+            .join(" ")}
           confirmLabel="Delete"
           cancelLabel="Cancel"
           variant="danger"
@@ -20,6 +20,7 @@ const CodeMirrorEditor = forwardRef(
       formattingKeymaps = {},
       getNotes = () => [],
       getTags = () => [],
+      getNotePath = () => null,
       ariaLabel = "Markdown editor",
     },
     ref
@@ -30,6 +31,7 @@ const CodeMirrorEditor = forwardRef(
     const vimModeChangeListenerRef = useRef(null);
     const getNotesRef = useRef(null);
     const getTagsRef = useRef(null);
+    const getNotePathRef = useRef(null);
     const suppressOnChangeRef = useRef(false);
     const vimStatusSyncFrameRef = useRef(null);
 
@@ -50,6 +52,10 @@ const CodeMirrorEditor = forwardRef(
       getTagsRef.current = getTags;
     }, [getTags]);
 
+    useEffect(() => {
+      getNotePathRef.current = getNotePath;
+    }, [getNotePath]);
+
     const emitVimModeStatus = () => {
       if (!enableVimMode || !viewRef.current || !vimModeChangeListenerRef.current) return;
 
@@ -99,6 +105,7 @@ const CodeMirrorEditor = forwardRef(
         formattingKeymaps,
         getNotes: () => (getNotesRef.current ? getNotesRef.current() : []),
         getTags: () => (getTagsRef.current ? getTagsRef.current() : []),
+        getNotePath: () => (getNotePathRef.current ? getNotePathRef.current() : null),
       });
 
       const state = EditorState.create({
@@ -0,0 +1,68 @@
+import { useState } from "react";
+import useNotesStore from "../../store/notesStore";
+
+// Shown in place of the editor until the note is unlocked; the plaintext stays in memory
+const EncryptedNotePanel = ({ note }) => {
+  const { unlockEncryptedNote } = useNotesStore();
+  const [passphrase, setPassphrase] = useState("");
+  const [error, setError] = useState("");
+  const [isUnlocking, setIsUnlocking] = useState(false);
+
+  const handleSubmit = async (e) => {
+    e.preventDefault();
+    if (!passphrase) return;
+
+    setIsUnlocking(true);
+    setError("");
+    try {
+      await unlockEncryptedNote(note.id, passphrase);
+    } catch (unlockError) {
+      setError(unlockError.message || "Could not unlock this note");
+      setIsUnlocking(false);
+    }
+  };
+
+  return (
+    <div className="h-full flex items-center justify-center bg-editor-bg p-6">
+      <form onSubmit={handleSubmit} className="w-full max-w-sm text-center space-y-4">
+        <svg
+          className="w-10 h-10 mx-auto text-text-muted"
+          fill="none"
+          stroke="currentColor"
+          viewBox="0 0 24 24"
+        >
+          <path
+            strokeLinecap="round"
+            strokeLinejoin="round"
+            strokeWidth={2}
+            d="M12 15v2m-6 4h12a2 2 0 002-2v-6a2 2 0 00-2-2H6a2 2 0 00-2 2v6a2 2 0 002 2zm10-10V7a4 4 0 00-8 0v4h8z"
+          />
+        </svg>
+        <div>
+          <h2 className="text-base font-semibold text-text-primary">{note.name} is encrypted</h2>
+          <p className="mt-1 text-sm text-text-secondary">
+            Enter its passphrase to read and edit it. Nothing is decrypted to disk.
+          </p>
+        </div>
+        <input
+          type="password"
+          className="w-full px-3 py-2 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent"
+          placeholder="Passphrase"
+          value={passphrase}
+          onChange={(e) => setPassphrase(e.target.value)}
+          autoFocus
+        />
+        {error && <p className="text-xs text-red-400">{error}</p>}
+        <button
+          type="submit"
+          disabled={isUnlocking || !passphrase}
+          className="w-full px-4 py-2 text-sm font-medium rounded-lg bg-accent hover:bg-accent-hover text-white transition-colors disabled:opacity-60"
+        >
+          {isUnlocking ? "Unlocking..." : "Unlock"}
+        </button>
+      </form>
+    </div>
+  );
+};
+
+export default EncryptedNotePanel;
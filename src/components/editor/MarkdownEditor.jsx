@@ -17,19 +17,35 @@ import "katex/dist/katex.min.css";
 import Toolbar from "../layout/Toolbar";
 import CreateNoteModal from "../modals/CreateNoteModal";
 import CodeMirrorEditor from "./CodeMirrorEditor";
+import EncryptedNotePanel from "./EncryptedNotePanel";
 import useNotesStore, { SETTINGS_TAB_ID } from "../../store/notesStore";
 import useUIStore from "../../store/uiStore";
 import useSettingsStore from "../../store/settingsStore";
 import { slugify } from "../../utils/slugify";
+import { acquireNoteLock, releaseNoteLock } from "../../utils/noteLocks";
+import { queryNotes } from "../../utils/query";
+import { resolveNoteLink } from "../../utils/links";
+import { recordFeatureUse } from "../../utils/telemetry";
+import { attachLinkHoverPreviews, renderLinkCards } from "./linkPreviewCards";
 import "./MarkdownPreview.css";
 
 const ExportModal = lazy(() => import("../modals/ExportModal"));
 const TableOfContents = lazy(() => import("./TableOfContents"));
+const PropertiesPanel = lazy(() => import("./PropertiesPanel"));
 const SettingsPage = lazy(() => import("../settings/SettingsPage"));
 const NoteHistoryModal = lazy(() => import("../modals/NoteHistoryModal"));
 const ConflictCompareModal = lazy(() => import("../modals/ConflictCompareModal"));
+const ReviewChangesModal = lazy(() => import("../modals/ReviewChangesModal"));
 const WorkspaceDashboard = lazy(() => import("../dashboard/WorkspaceDashboard"));
 
+const describeLockHolder = (holder) => {
+  const where = [holder.host && `on ${holder.host}`, holder.user && `by ${holder.user}`]
+    .filter(Boolean)
+    .join(" ");
+  const since = holder.since ? ` since ${new Date(holder.since).toLocaleString()}` : "";
+  return `This note is open in ${holder.app}${where ? ` ${where}` : ""}${since}.`;
+};
+
 // Lazy-load mermaid only when needed (large dependency ~1.5MB)
 let mermaidPromise = null;
 const getMermaid = () => {
@@ -66,6 +82,15 @@ const escapeHtml = (value = "") =>
     }
   });
 
+// A trailing `^block-id` is hidden and becomes the element's id, so
+// `[[Note#^id]]` links can scroll the preview to it
+const BLOCK_ID_PATTERN = /(?:^|\s)\^([A-Za-z0-9-]+)\s*$/;
+const splitBlockId = (html) => {
+  const match = html.match(BLOCK_ID_PATTERN);
+  if (!match) return { html, idAttr: "" };
+  return { html: html.slice(0, match.index).trimEnd(), idAttr: ` id="^${escapeHtml(match[1])}"` };
+};
+
 const wikiLinkExtension = {
   name: "wikilink",
   level: "inline",
@@ -121,7 +146,8 @@ if (!extensionsRegistered) {
       if (token.type === "wikilink") {
         const state = useNotesStore.getState();
         const note = state.findNoteByLinkTarget?.(token.target);
-        token.exists = Boolean(note);
+        // `[[#Heading]]` points into the note being previewed
+        token.exists = token.target.startsWith("#") || Boolean(note);
       }
     },
     renderer: {
@@ -132,18 +158,20 @@ if (!extensionsRegistered) {
         return `<h${depth} id="${escapeHtml(id)}" dir="auto">${text}</h${depth}>\n`;
       },
       paragraph(token) {
-        const text = typeof token === "object" ? token.text : token;
-        return `<p dir="auto">${text}</p>\n`;
+        const { html: text, idAttr } = splitBlockId(typeof token === "object" ? token.text : token);
+        // A `^id` line of its own names the block above it
+        if (!text && idAttr) return `<span${idAttr} aria-hidden="true"></span>\n`;
+        return `<p${idAttr} dir="auto">${text}</p>\n`;
       },
       listitem(token) {
-        const text = typeof token === "object" ? token.text : token;
+        const { html: text, idAttr } = splitBlockId(typeof token === "object" ? token.text : token);
         const isTask = typeof token === "object" ? token.task : false;
         const isChecked = typeof token === "object" ? token.checked : false;
         if (isTask) {
           const checkbox = `<input type="checkbox"${isChecked ? ' checked=""' : ""} disabled="">`;
-          return `<li dir="auto">${checkbox} ${text}</li>\n`;
+          return `<li${idAttr} dir="auto">${checkbox} ${text}</li>\n`;
         }
-        return `<li dir="auto">${text}</li>\n`;
+        return `<li${idAttr} dir="auto">${text}</li>\n`;
       },
       blockquote(token) {
         const body = typeof token === "object" ? token.text : token;
@@ -166,6 +194,11 @@ if (!extensionsRegistered) {
           return `<div class="mermaid-wrapper"><div class="mermaid">${escapeHtml(text)}</div></div>`;
         }
 
+        // Metadata queries: filled in with live results after render
+        if (lang === "query") {
+          return `<div class="query-block" data-query="${escapeHtml(text)}"><p class="query-status">Running query…</p></div>`;
+        }
+
         const validLang = lang && hljs.getLanguage(lang);
         const highlighted = validLang
           ? hljs.highlight(text, { language: lang }).value
@@ -215,7 +248,7 @@ const renderMarkdownPreview = (markdown) => {
     if (token.type === "wikilink") {
       const state = useNotesStore.getState();
       const note = state.findNoteByLinkTarget?.(token.target);
-      token.exists = Boolean(note);
+      token.exists = token.target.startsWith("#") || Boolean(note);
     }
   });
 
@@ -238,10 +271,13 @@ const MarkdownEditor = forwardRef((props, ref) => {
     isNoteDirty,
     getNoteConflict,
     resolveNoteConflict,
+    applyMergedConflict,
     getRecoveredDraft,
     discardRecoveredDraft,
     getNotes,
     getAllTags,
+    editNoteProperty,
+    getBlockId,
   } = useNotesStore();
 
   const { addNotification, setShowWorkspaceModal } = useUIStore();
@@ -252,6 +288,7 @@ const MarkdownEditor = forwardRef((props, ref) => {
     autosaveDelay,
     typewriterMode: typewriterModeEnabled,
     keymaps,
+    linkPreviews,
   } = useSettingsStore();
 
   const [markdown, setMarkdown] = useState("");
@@ -259,10 +296,12 @@ const MarkdownEditor = forwardRef((props, ref) => {
   const [viewMode, setViewMode] = useState("split"); // "editor", "preview", or "split"
   const [showExportModal, setShowExportModal] = useState(false);
   const [showHistoryModal, setShowHistoryModal] = useState(false);
+  const [showReviewChanges, setShowReviewChanges] = useState(false);
   const [showConflictCompare, setShowConflictCompare] = useState(false);
   const [showCreateNoteModal, setShowCreateNoteModal] = useState(false);
   const [pendingNoteName, setPendingNoteName] = useState("");
   const [showTOC, setShowTOC] = useState(false);
+  const [showProperties, setShowProperties] = useState(false);
   const [isResizingSplit, setIsResizingSplit] = useState(false);
   const [isSaving, setIsSaving] = useState(false);
   const [showSavedIndicator, setShowSavedIndicator] = useState(false);
@@ -279,6 +318,8 @@ const MarkdownEditor = forwardRef((props, ref) => {
   const autosaveClearTimerRef = useRef(null);
   const previewTimerRef = useRef(null); // Timer for debounced preview updates
   const editorRef = useRef(null);
+  // Heading to scroll to once a note opened from a `[[Note#Heading]]` link loads
+  const pendingHeadingRef = useRef(null);
   const previewPaneRef = useRef(null);
   const highlightTimeoutRef = useRef(null);
   const ignoreNextEditorSyncScrollRef = useRef(false);
@@ -290,6 +331,31 @@ const MarkdownEditor = forwardRef((props, ref) => {
   const hasUnsavedChanges = isNoteDirty(currentNoteId);
   const noteConflict = currentNoteId ? getNoteConflict(currentNoteId) : null;
   const recoveredDraft = currentNoteId ? getRecoveredDraft(currentNoteId) : null;
+  const currentFilePath = currentNoteId ? getCurrentNote()?.filePath || null : null;
+
+  // Another Marky or editor with the note open; cleared once the user edits anyway
+  const [lockHolder, setLockHolder] = useState(null);
+
+  useEffect(() => {
+    setLockHolder(null);
+    if (!currentFilePath) return;
+    let cancelled = false;
+    acquireNoteLock(currentFilePath)
+      .then((status) => {
+        if (!cancelled && !status.acquired) setLockHolder(status.holder);
+      })
+      .catch(() => {});
+    return () => {
+      cancelled = true;
+      releaseNoteLock(currentFilePath);
+    };
+  }, [currentFilePath]);
+
+  const takeNoteLock = useCallback(async () => {
+    if (!currentFilePath) return;
+    await acquireNoteLock(currentFilePath, true);
+    setLockHolder(null);
+  }, [currentFilePath]);
 
   const startResizingSplit = useCallback((e) => {
     e.preventDefault();
@@ -516,6 +582,59 @@ const MarkdownEditor = forwardRef((props, ref) => {
     // Note: dirty state is tracked in the store, not locally
   }, [currentNoteId]); // CRITICAL: Only depend on currentNoteId, not getCurrentNote
 
+  // Remember each note's cursor and scroll position for session restore
+  useEffect(() => {
+    const filePath = getCurrentNote()?.filePath;
+    if (!filePath) return undefined;
+
+    let captureTimer = null;
+    const capture = () => {
+      const view = editorRef.current?.getView?.();
+      if (!view) return;
+      const pos = view.state.selection.main.head;
+      const line = view.state.doc.lineAt(pos);
+      useNotesStore.getState().setCursorPosition(filePath, {
+        line: line.number,
+        column: pos - line.from,
+        scroll_top: view.scrollDOM.scrollTop,
+      });
+    };
+    const scheduleCapture = () => {
+      clearTimeout(captureTimer);
+      captureTimer = setTimeout(capture, 1000);
+    };
+
+    let view = null;
+    const frame = requestAnimationFrame(() => {
+      view = editorRef.current?.getView?.();
+      if (!view) return;
+
+      const saved = useNotesStore.getState().cursorPositions[filePath];
+      if (saved) {
+        const doc = view.state.doc;
+        const line = doc.line(Math.min(Math.max(saved.line || 1, 1), doc.lines));
+        view.dispatch({ selection: { anchor: Math.min(line.from + (saved.column || 0), line.to) } });
+        view.scrollDOM.scrollTop = saved.scroll_top || 0;
+      }
+
+      view.dom.addEventListener("keyup", scheduleCapture);
+      view.dom.addEventListener("mouseup", scheduleCapture);
+      view.scrollDOM.addEventListener("scroll", scheduleCapture, { passive: true });
+    });
+
+    return () => {
+      cancelAnimationFrame(frame);
+      clearTimeout(captureTimer);
+      // Runs before the editor swaps documents, so this still reads the outgoing note
+      capture();
+      if (view) {
+        view.dom.removeEventListener("keyup", scheduleCapture);
+        view.dom.removeEventListener("mouseup", scheduleCapture);
+        view.scrollDOM.removeEventListener("scroll", scheduleCapture);
+      }
+    };
+  }, [currentNoteId]);
+
   // Cleanup on unmount
   useEffect(() => {
     return () => {
@@ -554,6 +673,19 @@ const MarkdownEditor = forwardRef((props, ref) => {
     [currentNoteId, markdown, updateNote]
   );
 
+  // `[[Note#^id]]` for the block under the cursor, giving it an id if needed
+  const getBlockLink = useCallback(async () => {
+    const note = getCurrentNote();
+    if (!note?.filePath) {
+      throw new Error("Save the note before linking to its blocks");
+    }
+    const { from } = editorRef.current?.getSelection?.() || { from: 0 };
+    const line = markdown.slice(0, from).split("\n").length;
+    flushPendingNoteUpdate(currentNoteId, markdown);
+    const id = await getBlockId(currentNoteId, line);
+    return `[[${note.name.replace(/\.(md|markdown|txt)$/i, "")}#^${id}]]`;
+  }, [currentNoteId, markdown, getCurrentNote, flushPendingNoteUpdate, getBlockId]);
+
   // Manual save function
   const handleSave = useCallback(async () => {
     if (!currentNoteId || isSaving) return;
@@ -571,6 +703,8 @@ const MarkdownEditor = forwardRef((props, ref) => {
 
     setIsSaving(true);
     try {
+      // Saving by hand after the warning means editing here wins
+      if (lockHolder) await takeNoteLock();
       flushPendingNoteUpdate(currentNoteId, markdown);
       await saveCurrentNoteToDisk();
       // Note: dirty state is cleared in the store by saveCurrentNoteToDisk
@@ -600,6 +734,8 @@ const MarkdownEditor = forwardRef((props, ref) => {
     saveCurrentNoteToDisk,
     addNotification,
     noteConflict,
+    lockHolder,
+    takeNoteLock,
   ]);
 
   // Autosave effect: schedule a disk write after typing stops when autosave is enabled
@@ -610,7 +746,7 @@ const MarkdownEditor = forwardRef((props, ref) => {
       return;
     }
     const note = getCurrentNote();
-    if (!note?.filePath || !hasUnsavedChanges || noteConflict) {
+    if (!note?.filePath || !hasUnsavedChanges || noteConflict || lockHolder) {
       return;
     }
     setAutosaveStatus("pending");
@@ -636,22 +772,48 @@ const MarkdownEditor = forwardRef((props, ref) => {
     currentNoteId,
     hasUnsavedChanges,
     noteConflict,
+    lockHolder,
     getCurrentNote,
     saveCurrentNoteToDisk,
   ]);
 
-  const handleUseDiskVersion = useCallback(() => {
+  const handleUseDiskVersion = useCallback(async () => {
     if (!currentNoteId) return;
-    const resolved = resolveNoteConflict(currentNoteId, "useDisk");
-    if (resolved) {
-      setShowConflictCompare(false);
-      addNotification("Loaded the version from disk", "info");
+    try {
+      const resolved = await resolveNoteConflict(currentNoteId, "useDisk");
+      if (resolved) {
+        setShowConflictCompare(false);
+        addNotification(
+          "Loaded the version from disk; your edits are in a conflicted copy",
+          "info"
+        );
+      }
+    } catch (error) {
+      addNotification(`Failed to keep your edits: ${error.message}`, "error");
     }
   }, [currentNoteId, resolveNoteConflict, addNotification]);
 
+  const handleUseMergedVersion = useCallback(
+    (content) => {
+      if (!currentNoteId || !applyMergedConflict(currentNoteId, content)) return;
+      setMarkdown(content);
+      setDebouncedMarkdown(content);
+      setShowConflictCompare(false);
+      recordFeatureUse("conflict_merge");
+      addNotification("Merged your draft with the disk version — save to keep it", "success");
+    },
+    [currentNoteId, applyMergedConflict, addNotification]
+  );
+
   const handleOverwriteDiskVersion = useCallback(async () => {
     if (!currentNoteId) return;
-    const resolved = resolveNoteConflict(currentNoteId, "keepLocal");
+    let resolved = false;
+    try {
+      resolved = await resolveNoteConflict(currentNoteId, "keepLocal");
+    } catch (error) {
+      addNotification(`Failed to keep the disk version: ${error.message}`, "error");
+      return;
+    }
     if (!resolved) return;
     setShowConflictCompare(false);
 
@@ -671,7 +833,7 @@ const MarkdownEditor = forwardRef((props, ref) => {
       savedIndicatorTimerRef.current = setTimeout(() => {
         setShowSavedIndicator(false);
       }, 2000);
-      addNotification("Draft saved and disk version overwritten", "success");
+      addNotification("Draft saved; the disk version is in a conflicted copy", "success");
     } catch (error) {
       console.error("Overwrite save failed:", error);
       addNotification(`Failed to overwrite disk version: ${error.message}`, "error");
@@ -720,10 +882,15 @@ const MarkdownEditor = forwardRef((props, ref) => {
       nextMatch,
       previousMatch,
       clearSearch,
+      getBlockLink,
+      getSelectedText: () => editorRef.current?.getSelectedText?.() || "",
     }),
-    [scrollToAndHighlight, handleSave, nextMatch, previousMatch, clearSearch]
+    [scrollToAndHighlight, handleSave, nextMatch, previousMatch, clearSearch, getBlockLink]
   );
 
+  // Read when a snippet expands, so it always names the note being edited
+  const getNotePath = useCallback(() => getCurrentNote()?.filePath || null, [getCurrentNote]);
+
   const handleMarkdownChange = (value) => {
     // Update local state immediately for instant typing
     setMarkdown(value);
@@ -1152,6 +1319,169 @@ const MarkdownEditor = forwardRef((props, ref) => {
       });
   }, [debouncedMarkdown, viewMode]);
 
+  // Rich cards for standalone web links, once their previews arrive
+  useEffect(() => {
+    if (viewMode === "editor") return;
+
+    const container = document.querySelector(".markdown-preview");
+    if (!container) return;
+    return renderLinkCards(container);
+  }, [debouncedMarkdown, viewMode, linkPreviews]);
+
+  useEffect(() => {
+    if (viewMode === "editor") return;
+
+    const container = document.querySelector(".markdown-preview");
+    if (!container) return;
+    return attachLinkHoverPreviews(container);
+  }, [viewMode]);
+
+  // Run ```query blocks against the workspace index and render their rows
+  useEffect(() => {
+    if (viewMode === "editor") return;
+
+    const container = document.querySelector(".markdown-preview");
+    if (!container) return;
+
+    const blocks = container.querySelectorAll(".query-block");
+    if (blocks.length === 0) return;
+
+    let cancelled = false;
+    const formatValue = (value) => {
+      if (value === null || value === undefined) return "—";
+      if (Array.isArray(value)) return value.map(formatValue).join(", ");
+      return String(value);
+    };
+
+    blocks.forEach((block) => {
+      if (!rootFolderPath) {
+        block.innerHTML = `<p class="query-status query-error">Open a workspace folder to run queries.</p>`;
+        return;
+      }
+      queryNotes(rootFolderPath, block.dataset.query || "")
+        .then((result) => {
+          if (cancelled) return;
+          if (result.rows.length === 0) {
+            block.innerHTML = `<p class="query-status">No matching notes.</p>`;
+            return;
+          }
+          const header = ["Note", ...result.columns]
+            .map((column) => `<th>${escapeHtml(column)}</th>`)
+            .join("");
+          const rows = result.rows
+            .map((row) => {
+              const link = `<a href="#" data-wikilink-target="${escapeHtml(row.title)}">${escapeHtml(row.title)}</a>`;
+              const cells = row.values
+                .map((value) => `<td>${escapeHtml(formatValue(value))}</td>`)
+                .join("");
+              return `<tr><td>${link}</td>${cells}</tr>`;
+            })
+            .join("");
+          const more =
+            result.total > result.rows.length
+              ? `<p class="query-status">Showing ${result.rows.length} of ${result.total} notes</p>`
+              : "";
+          block.innerHTML = `<table><thead><tr>${header}</tr></thead><tbody>${rows}</tbody></table>${more}`;
+        })
+        .catch((err) => {
+          if (cancelled) return;
+          block.innerHTML = `<p class="query-status query-error">${escapeHtml(String(err?.message || err))}</p>`;
+        });
+    });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [debouncedMarkdown, viewMode, rootFolderPath]);
+
+  // Handle TOC header click - scroll to line in editor and preview
+  const handleTOCHeaderClick = useCallback(
+    (header) => {
+      // Scroll editor to the header line
+      if (editorRef.current) {
+        const lines = markdown.split("\n");
+        let position = 0;
+        for (let i = 0; i < header.line - 1 && i < lines.length; i++) {
+          position += lines[i].length + 1; // +1 for newline
+        }
+        editorRef.current.scrollToPosition(position);
+      }
+
+      // Also scroll preview to the heading anchor if visible
+      if (viewMode === "split" || viewMode === "preview") {
+        const previewContainer = document.querySelector(".markdown-preview");
+        if (previewContainer && header.id) {
+          const targetElement = previewContainer.querySelector(`#${CSS.escape(header.id)}`);
+          if (targetElement) {
+            targetElement.scrollIntoView({ behavior: "smooth", block: "start" });
+          }
+        }
+      }
+    },
+    [markdown, viewMode]
+  );
+
+  // `[[Note#Heading]]`, `[[#Heading]]`, `[[Note#^block-id]]` and
+  // `note.md#heading`: opens the note and scrolls to what the backend matched
+  const openHeadingLink = useCallback(
+    async (linkTarget) => {
+      const from = getCurrentNote()?.filePath || null;
+      let resolved = null;
+      if (rootFolderPath) {
+        try {
+          resolved = await resolveNoteLink(rootFolderPath, linkTarget, from);
+        } catch {
+          resolved = null;
+        }
+      }
+
+      const { items } = useNotesStore.getState();
+      const normalize = (path) => (path ? path.replace(/\\/g, "/") : "");
+      const note =
+        (resolved &&
+          items.find(
+            (item) => item.type === "note" && normalize(item.filePath) === normalize(resolved.path)
+          )) ||
+        findNoteByLinkTarget(linkTarget);
+      if (!note) {
+        const name = linkTarget.split("#")[0].trim();
+        if (!name) return;
+        if (!rootFolderPath) {
+          addNotification("Open or create a workspace folder before creating linked notes.", "info");
+          return;
+        }
+        setPendingNoteName(name);
+        setShowCreateNoteModal(true);
+        return;
+      }
+
+      if (resolved?.block && !resolved.block_range) {
+        addNotification(`"${note.name}" has no block ^${resolved.block}`, "info");
+      } else if (resolved?.heading && !resolved.found) {
+        addNotification(`"${note.name}" has no heading "${resolved.heading}"`, "info");
+      }
+      const header = resolved?.block_range
+        ? { line: resolved.block_range.start_line, id: `^${resolved.block_range.id}` }
+        : resolved?.found
+          ? { line: resolved.found.line, id: slugify(resolved.found.text) }
+          : null;
+      if (note.id === useNotesStore.getState().currentNoteId) {
+        if (header) handleTOCHeaderClick(header);
+        return;
+      }
+      pendingHeadingRef.current = header ? { noteId: note.id, header } : null;
+      selectNote(note.id);
+    },
+    [
+      getCurrentNote,
+      rootFolderPath,
+      findNoteByLinkTarget,
+      selectNote,
+      addNotification,
+      handleTOCHeaderClick,
+    ]
+  );
+
   const handlePreviewClick = useCallback(
     (event) => {
       const target = event.target;
@@ -1173,6 +1503,20 @@ const MarkdownEditor = forwardRef((props, ref) => {
         return;
       }
 
+      // Relative links to notes, e.g. [Setup](setup.md#install)
+      const noteLink = target.closest("a[href]");
+      const href = noteLink?.getAttribute("href") || "";
+      if (
+        noteLink &&
+        !noteLink.hasAttribute("data-wikilink-target") &&
+        /^[^:?]+\.(md|markdown|txt)(#|$)/i.test(href)
+      ) {
+        event.preventDefault();
+        event.stopPropagation();
+        openHeadingLink(href);
+        return;
+      }
+
       const anchor = target.closest("a[data-wikilink-target]");
       if (!anchor) return;
 
@@ -1182,6 +1526,11 @@ const MarkdownEditor = forwardRef((props, ref) => {
       const linkTarget = anchor.getAttribute("data-wikilink-target");
       if (!linkTarget) return;
 
+      if (linkTarget.includes("#")) {
+        openHeadingLink(linkTarget);
+        return;
+      }
+
       const existing = findNoteByLinkTarget(linkTarget);
       if (existing) {
         selectNote(existing.id);
@@ -1197,7 +1546,7 @@ const MarkdownEditor = forwardRef((props, ref) => {
       setPendingNoteName(linkTarget);
       setShowCreateNoteModal(true);
     },
-    [findNoteByLinkTarget, rootFolderPath, selectNote, addNotification]
+    [findNoteByLinkTarget, rootFolderPath, selectNote, addNotification, openHeadingLink]
   );
 
   const handleCreateNoteFromLink = useCallback(
@@ -1220,32 +1569,15 @@ const MarkdownEditor = forwardRef((props, ref) => {
     [createNote, selectNote, addNotification]
   );
 
-  // Handle TOC header click - scroll to line in editor and preview
-  const handleTOCHeaderClick = useCallback(
-    (header) => {
-      // Scroll editor to the header line
-      if (editorRef.current) {
-        const lines = markdown.split("\n");
-        let position = 0;
-        for (let i = 0; i < header.line - 1 && i < lines.length; i++) {
-          position += lines[i].length + 1; // +1 for newline
-        }
-        editorRef.current.scrollToPosition(position);
-      }
-
-      // Also scroll preview to the heading anchor if visible
-      if (viewMode === "split" || viewMode === "preview") {
-        const previewContainer = document.querySelector(".markdown-preview");
-        if (previewContainer && header.id) {
-          const targetElement = previewContainer.querySelector(`#${CSS.escape(header.id)}`);
-          if (targetElement) {
-            targetElement.scrollIntoView({ behavior: "smooth", block: "start" });
-          }
-        }
-      }
-    },
-    [markdown, viewMode]
-  );
+  // Waits for the linked note's content before scrolling, since switching
+  // notes fills the editor a render later
+  useEffect(() => {
+    const pending = pendingHeadingRef.current;
+    if (!pending || pending.noteId !== currentNoteId) return;
+    if (markdown !== (getCurrentNote()?.content || "")) return;
+    pendingHeadingRef.current = null;
+    requestAnimationFrame(() => handleTOCHeaderClick(pending.header));
+  }, [currentNoteId, markdown, getCurrentNote, handleTOCHeaderClick]);
 
   const currentNote = getCurrentNote();
 
@@ -1290,6 +1622,10 @@ const MarkdownEditor = forwardRef((props, ref) => {
     );
   }
 
+  if (currentNote.encrypted && !currentNote.decrypted) {
+    return <EncryptedNotePanel note={currentNote} />;
+  }
+
   return (
     <div className="h-full flex flex-col overflow-hidden bg-editor-bg">
       {/* Title Bar - Glass effect */}
@@ -1376,6 +1712,28 @@ const MarkdownEditor = forwardRef((props, ref) => {
               <span className="hidden sm:inline">TOC</span>
             </button>
 
+            <button
+              onClick={() => setShowProperties(!showProperties)}
+              disabled={!currentNote.filePath}
+              aria-pressed={showProperties}
+              className={`px-2 py-1.5 text-xs rounded-md transition-colors flex items-center gap-1.5 disabled:opacity-50 disabled:cursor-not-allowed ${
+                showProperties
+                  ? "bg-accent/10 text-accent"
+                  : "text-text-secondary hover:text-text-primary hover:bg-overlay-subtle"
+              }`}
+              title="Toggle Properties"
+            >
+              <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                <path
+                  strokeLinecap="round"
+                  strokeLinejoin="round"
+                  strokeWidth={2}
+                  d="M7 7h.01M7 3h5a1.99 1.99 0 011.414.586l7 7a2 2 0 010 2.828l-7 7a2 2 0 01-2.828 0l-7-7A1.994 1.994 0 013 12V7a4 4 0 014-4z"
+                />
+              </svg>
+              <span className="hidden sm:inline">Properties</span>
+            </button>
+
             <button
               onClick={handleSave}
               disabled={isSaving || !currentNote.filePath}
@@ -1421,6 +1779,24 @@ const MarkdownEditor = forwardRef((props, ref) => {
               </button>
             )}
 
+            {currentNote.filePath && (
+              <button
+                onClick={() => setShowReviewChanges(true)}
+                className="px-2 py-1.5 text-xs text-text-secondary hover:text-text-primary hover:bg-overlay-subtle rounded-md transition-colors flex items-center gap-1.5"
+                title="Review changes since the last commit or save"
+              >
+                <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M9 5H7a2 2 0 00-2 2v12a2 2 0 002 2h10a2 2 0 002-2V7a2 2 0 00-2-2h-2M9 5a2 2 0 002 2h2a2 2 0 002-2M9 5a2 2 0 012-2h2a2 2 0 012 2m-6 9l2 2 4-4"
+                  />
+                </svg>
+                <span className="hidden sm:inline">Changes</span>
+              </button>
+            )}
+
             <button
               onClick={() => setShowExportModal(true)}
               className="px-2 py-1.5 text-xs text-text-secondary hover:text-text-primary hover:bg-overlay-subtle rounded-md transition-colors flex items-center gap-1.5"
@@ -1529,8 +1905,9 @@ const MarkdownEditor = forwardRef((props, ref) => {
                 This note changed on disk while you had unsaved edits.
               </p>
               <p className="text-xs text-amber-100/80 mt-1">
-                Compare both versions, then load the disk version or overwrite it with your current
-                draft.
+                {noteConflict.baseContent != null
+                  ? "Compare or merge both versions, or pick one of them."
+                  : "Compare both versions, then load the disk version or overwrite it with your current draft."}
               </p>
             </div>
             <div className="flex items-center gap-2 shrink-0">
@@ -1557,6 +1934,33 @@ const MarkdownEditor = forwardRef((props, ref) => {
         </div>
       )}
 
+      {lockHolder && !noteConflict && (
+        <div className="border-b border-amber-500/20 bg-amber-500/10 px-4 py-3 shrink-0">
+          <div className="flex flex-col gap-3 md:flex-row md:items-center md:justify-between">
+            <div className="min-w-0">
+              <p className="text-sm font-semibold text-amber-200">
+                {describeLockHolder(lockHolder)}
+              </p>
+              <p className="text-xs text-amber-100/80 mt-1">
+                Saving from both places can overwrite one side, so autosave is paused here.
+              </p>
+            </div>
+            <div className="flex items-center gap-2 shrink-0">
+              <button
+                onClick={() =>
+                  takeNoteLock().catch((error) =>
+                    addNotification(`Failed to take over the note: ${error}`, "error")
+                  )
+                }
+                className="px-3 py-1.5 text-xs rounded-md bg-amber-500 text-black hover:bg-amber-400 transition-colors font-medium"
+              >
+                Edit Anyway
+              </button>
+            </div>
+          </div>
+        </div>
+      )}
+
       {recoveredDraft && !noteConflict && (
         <div className="border-b border-blue-500/20 bg-blue-500/10 px-4 py-3 shrink-0">
           <div className="flex flex-col gap-3 md:flex-row md:items-center md:justify-between">
@@ -1598,11 +2002,28 @@ const MarkdownEditor = forwardRef((props, ref) => {
       <div
         className={`flex-1 min-h-0 overflow-hidden flex editor-container relative ${isResizingSplit ? "cursor-col-resize" : ""} ${focusMode ? "justify-center" : ""}`}
       >
-        {/* Table of Contents - Floating Panel */}
-        {showTOC && (
-          <div className="absolute top-4 right-4 z-20 w-72 max-w-[calc(100%-2rem)] animate-in slide-in-from-right-4 fade-in duration-200 shadow-2xl">
+        {/* Table of Contents and Properties - Floating Panels */}
+        {(showTOC || (showProperties && currentNote.filePath)) && (
+          <div className="absolute top-4 right-4 z-20 w-72 max-w-[calc(100%-2rem)] max-h-[calc(100%-2rem)] overflow-y-auto flex flex-col gap-3 animate-in slide-in-from-right-4 fade-in duration-200">
             <Suspense fallback={null}>
-              <TableOfContents markdown={markdown} onHeaderClick={handleTOCHeaderClick} />
+              {showProperties && currentNote.filePath && (
+                <PropertiesPanel
+                  className="shadow-2xl"
+                  filePath={currentNote.filePath}
+                  content={currentNote.content}
+                  onEdit={(key, value) => {
+                    flushPendingNoteUpdate(currentNoteId, markdown);
+                    return editNoteProperty(currentNoteId, key, value);
+                  }}
+                />
+              )}
+              {showTOC && (
+                <TableOfContents
+                  className="shadow-2xl"
+                  markdown={markdown}
+                  onHeaderClick={handleTOCHeaderClick}
+                />
+              )}
             </Suspense>
           </div>
         )}
@@ -1689,6 +2110,7 @@ const MarkdownEditor = forwardRef((props, ref) => {
               onVimModeChange={handleVimModeChange}
               placeholder="Start typing your markdown here..."
               className="w-full h-full"
+              readOnly={Boolean(currentNote?.locked)}
               enableLineNumbers={true}
               enableVimMode={vimMode}
               enableTypewriterMode={typewriterModeEnabled && focusMode}
@@ -1696,6 +2118,7 @@ const MarkdownEditor = forwardRef((props, ref) => {
               formattingKeymaps={keymaps}
               getNotes={getNotes}
               getTags={getAllTags}
+              getNotePath={getNotePath}
               ariaLabel={`Markdown editor${currentNote?.name ? ` for ${currentNote.name}` : ""}`}
             />
           </div>
@@ -1819,6 +2242,19 @@ const MarkdownEditor = forwardRef((props, ref) => {
             }}
           />
         )}
+        {showReviewChanges && (
+          <ReviewChangesModal
+            isOpen={showReviewChanges}
+            note={currentNote}
+            draft={markdown}
+            canSave={hasUnsavedChanges && !noteConflict}
+            onSave={() => {
+              setShowReviewChanges(false);
+              handleSave();
+            }}
+            onClose={() => setShowReviewChanges(false)}
+          />
+        )}
         {currentNote && noteConflict && (
           <ConflictCompareModal
             isOpen={showConflictCompare}
@@ -1826,6 +2262,8 @@ const MarkdownEditor = forwardRef((props, ref) => {
             localContent={markdown}
             diskContent={noteConflict.diskContent}
             detectedAt={noteConflict.detectedAt}
+            baseContent={noteConflict.baseContent}
+            onUseMerged={handleUseMergedVersion}
             onClose={() => setShowConflictCompare(false)}
             onUseDisk={handleUseDiskVersion}
             onKeepLocal={handleOverwriteDiskVersion}
@@ -0,0 +1,302 @@
+import { useEffect, useState } from "react";
+import { getNoteProperties } from "../../utils/properties";
+
+const TYPES = [
+  { value: "text", label: "Text" },
+  { value: "number", label: "Number" },
+  { value: "boolean", label: "Checkbox" },
+  { value: "date", label: "Date" },
+  { value: "list", label: "List" },
+  { value: "link", label: "Link" },
+];
+
+const DATE_ONLY = /^\d{4}-\d{2}-\d{2}$/;
+
+const toText = (property) => {
+  if (property.type === "empty") return "";
+  if (property.type === "list") return property.value.join(", ");
+  return String(property.value);
+};
+
+// Builds the typed value the backend expects; an empty field leaves `key:` blank
+const fromText = (type, text) => {
+  const trimmed = text.trim();
+  if (type === "boolean") return { type, value: trimmed === "true" };
+  if (type === "list") {
+    return {
+      type,
+      value: trimmed
+        .split(",")
+        .map((item) => item.trim())
+        .filter(Boolean),
+    };
+  }
+  if (!trimmed) return { type: "empty" };
+  if (type === "number") {
+    const number = Number(trimmed);
+    if (!Number.isFinite(number)) throw new Error(`"${trimmed}" isn't a number`);
+    return { type, value: number };
+  }
+  return { type, value: trimmed };
+};
+
+const inputClass =
+  "w-full min-w-0 px-2 py-1 text-xs bg-overlay-subtle border border-border rounded text-text-primary placeholder-text-muted focus:outline-none focus:border-accent";
+
+const PropertyRow = ({ property, onCommit, onRemove }) => {
+  const savedType = property.type === "empty" ? "text" : property.type;
+  const [type, setType] = useState(savedType);
+  const [text, setText] = useState(() => toText(property));
+
+  const commit = (nextType = type, nextText = text) => {
+    if (nextType === savedType && nextText === toText(property)) return;
+    onCommit(property.key, nextType, nextText);
+  };
+
+  const handleKeyDown = (event) => {
+    if (event.key === "Enter") event.currentTarget.blur();
+    if (event.key === "Escape") {
+      setText(toText(property));
+      event.currentTarget.blur();
+    }
+  };
+
+  const renderInput = () => {
+    if (property.type === "yaml") {
+      return (
+        <pre
+          className="px-2 py-1 text-[11px] bg-overlay-subtle border border-border rounded text-text-muted overflow-x-auto"
+          title="Edit this property in the note's frontmatter"
+        >
+          {property.value}
+        </pre>
+      );
+    }
+    if (type === "boolean") {
+      return (
+        <input
+          type="checkbox"
+          checked={text === "true"}
+          onChange={(event) => {
+            const next = String(event.target.checked);
+            setText(next);
+            commit(type, next);
+          }}
+          className="accent-accent"
+          aria-label={property.key}
+        />
+      );
+    }
+    return (
+      <div className="flex items-center gap-1">
+        {type === "link" && <span className="text-xs text-text-muted">[[</span>}
+        <input
+          type={
+            type === "number"
+              ? "number"
+              : type === "date" && (!text || DATE_ONLY.test(text))
+                ? "date"
+                : "text"
+          }
+          value={text}
+          onChange={(event) => setText(event.target.value)}
+          onBlur={() => commit()}
+          onKeyDown={handleKeyDown}
+          placeholder={type === "list" ? "a, b, c" : "Empty"}
+          className={inputClass}
+          aria-label={property.key}
+        />
+        {type === "link" && <span className="text-xs text-text-muted">]]</span>}
+      </div>
+    );
+  };
+
+  return (
+    <div className="group px-3 py-2 border-b border-border last:border-b-0">
+      <div className="flex items-center gap-2 mb-1">
+        <span className="flex-1 min-w-0 truncate text-xs font-medium text-text-secondary" title={property.key}>
+          {property.key}
+        </span>
+        {property.type !== "yaml" && (
+          <select
+            value={type}
+            onChange={(event) => {
+              const nextType = event.target.value;
+              const nextText =
+                nextType === "boolean" ? String(text.trim() === "true") : text;
+              setType(nextType);
+              setText(nextText);
+              commit(nextType, nextText);
+            }}
+            className="text-[10px] bg-transparent text-text-muted focus:outline-none"
+            aria-label={`Type of ${property.key}`}
+          >
+            {TYPES.map((option) => (
+              <option key={option.value} value={option.value}>
+                {option.label}
+              </option>
+            ))}
+          </select>
+        )}
+        <button
+          onClick={() => onRemove(property.key)}
+          className="p-0.5 text-text-muted hover:text-red-400 opacity-0 group-hover:opacity-100 focus:opacity-100"
+          title="Remove property"
+          aria-label={`Remove ${property.key}`}
+        >
+          <svg className="w-3 h-3" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+            <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M6 18L18 6M6 6l12 12" />
+          </svg>
+        </button>
+      </div>
+      {renderInput()}
+    </div>
+  );
+};
+
+/**
+ * Frontmatter as typed fields. Every change is written by the backend, which
+ * validates the value and rewrites only that property's lines.
+ * @param {string} filePath - The note's path
+ * @param {string} content - The note's saved content; properties reload when it changes
+ * @param {(key: string, value: object|null) => Promise<Array>} onEdit - Resolves with the new properties
+ */
+const PropertiesPanel = ({ filePath, content, onEdit, className = "" }) => {
+  const [properties, setProperties] = useState([]);
+  const [error, setError] = useState(null);
+  const [newKey, setNewKey] = useState("");
+  const [newType, setNewType] = useState("text");
+  const [isCollapsed, setIsCollapsed] = useState(false);
+
+  useEffect(() => {
+    if (!filePath) return;
+    let cancelled = false;
+    const timer = setTimeout(() => {
+      getNoteProperties(filePath)
+        .then((result) => {
+          if (cancelled) return;
+          setProperties(result);
+          setError(null);
+        })
+        .catch((loadError) => {
+          if (!cancelled) setError(String(loadError?.message || loadError));
+        });
+    }, 300);
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [filePath, content]);
+
+  const runEdit = async (key, value) => {
+    try {
+      setProperties(await onEdit(key, value));
+      setError(null);
+      return true;
+    } catch (editError) {
+      setError(String(editError?.message || editError));
+      return false;
+    }
+  };
+
+  const handleCommit = (key, type, text) => {
+    let value;
+    try {
+      value = fromText(type, text);
+    } catch (parseError) {
+      setError(parseError.message);
+      return;
+    }
+    runEdit(key, value);
+  };
+
+  const handleAdd = async (event) => {
+    event.preventDefault();
+    const key = newKey.trim();
+    if (!key) return;
+    const value = newType === "boolean" ? { type: "boolean", value: false } : fromText(newType, "");
+    if (await runEdit(key, value)) setNewKey("");
+  };
+
+  return (
+    <div className={`bg-bg-sidebar border border-border rounded-lg overflow-hidden ${className}`}>
+      <div className="px-4 py-3 border-b border-border flex items-center justify-between bg-overlay-subtle">
+        <div className="flex items-center gap-2">
+          <svg className="w-4 h-4 text-accent" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+            <path
+              strokeLinecap="round"
+              strokeLinejoin="round"
+              strokeWidth={2}
+              d="M7 7h.01M7 3h5a1.99 1.99 0 011.414.586l7 7a2 2 0 010 2.828l-7 7a2 2 0 01-2.828 0l-7-7A1.994 1.994 0 013 12V7a4 4 0 014-4z"
+            />
+          </svg>
+          <h3 className="text-sm font-semibold text-text-primary">Properties</h3>
+          <span className="text-xs text-text-muted">({properties.length})</span>
+        </div>
+        <button
+          onClick={() => setIsCollapsed(!isCollapsed)}
+          className="p-1 hover:bg-overlay-light rounded transition-colors"
+          title={isCollapsed ? "Expand" : "Collapse"}
+        >
+          <svg
+            className={`w-4 h-4 text-text-secondary transition-transform ${isCollapsed ? "-rotate-90" : ""}`}
+            fill="none"
+            stroke="currentColor"
+            viewBox="0 0 24 24"
+          >
+            <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M19 9l-7 7-7-7" />
+          </svg>
+        </button>
+      </div>
+
+      {!isCollapsed && (
+        <>
+          <div className="max-h-96 overflow-y-auto custom-scrollbar">
+            {properties.map((property) => (
+              <PropertyRow
+                key={`${property.key}:${property.type}:${JSON.stringify(property.value ?? null)}`}
+                property={property}
+                onCommit={handleCommit}
+                onRemove={(key) => runEdit(key, null)}
+              />
+            ))}
+            {properties.length === 0 && !error && (
+              <p className="px-4 py-3 text-xs text-text-muted">This note has no properties yet.</p>
+            )}
+          </div>
+          {error && <p className="px-4 py-2 text-xs text-red-400 border-t border-border">{error}</p>}
+          <form onSubmit={handleAdd} className="px-3 py-2 border-t border-border flex items-center gap-2">
+            <input
+              value={newKey}
+              onChange={(event) => setNewKey(event.target.value)}
+              placeholder="Add property"
+              className={inputClass}
+              aria-label="New property name"
+            />
+            <select
+              value={newType}
+              onChange={(event) => setNewType(event.target.value)}
+              className="text-[10px] bg-transparent text-text-muted focus:outline-none"
+              aria-label="New property type"
+            >
+              {TYPES.map((option) => (
+                <option key={option.value} value={option.value}>
+                  {option.label}
+                </option>
+              ))}
+            </select>
+            <button
+              type="submit"
+              disabled={!newKey.trim()}
+              className="px-2 py-1 text-xs rounded-md bg-accent text-white hover:bg-accent/90 disabled:opacity-50 transition-colors"
+            >
+              Add
+            </button>
+          </form>
+        </>
+      )}
+    </div>
+  );
+};
+
+export default PropertiesPanel;
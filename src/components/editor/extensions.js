@@ -23,6 +23,8 @@ import { markyTheme, markySyntaxHighlighting } from "./theme";
 import { typewriterMode } from "./typewriterMode";
 import { buildMarkyKeymaps } from "./keymaps";
 import { createWikiLinkAutocomplete } from "./wikiLinkAutocomplete";
+import { snippetExpansion } from "./snippetExpansion";
+import { linkTitlePaste } from "./linkTitlePaste";
 
 // Regex matching RTL Unicode ranges (Arabic, Hebrew, Persian, Thaana, Syriac, etc.)
 const RTL_CHAR =
@@ -96,6 +98,7 @@ export function createExtensions(options = {}) {
     formattingKeymaps = {},
     getNotes = () => [],
     getTags = () => [],
+    getNotePath = () => null,
   } = options;
 
   const filteredSearchKeymap = searchKeymap.filter((binding) => binding.key !== "Mod-f");
@@ -190,6 +193,13 @@ export function createExtensions(options = {}) {
     extensions.push(createWikiLinkAutocomplete(getNotes, getTags));
   }
 
+  // Snippet keys expand on Tab
+  if (!readOnly) {
+    extensions.push(snippetExpansion(getNotePath));
+    // Pasted URLs pick up their page titles
+    extensions.push(linkTitlePaste());
+  }
+
   // Vim mode
   if (enableVimMode) {
     extensions.push(vim());
@@ -0,0 +1,143 @@
+import useSettingsStore from '../../store/settingsStore';
+import { fetchLinkPreview } from '../../utils/linkPreviews';
+
+const HOVER_DELAY_MS = 500;
+const WEB_LINK = 'a[href^="http://"], a[href^="https://"]';
+
+const previewsEnabled = () => useSettingsStore.getState().linkPreviews?.enabled !== false;
+
+const hostOf = (url) => {
+  try {
+    return new URL(url).hostname.replace(/^www\./, '');
+  } catch {
+    return url;
+  }
+};
+
+const element = (tag, className, text) => {
+  const node = document.createElement(tag);
+  node.className = className;
+  if (text) node.textContent = text;
+  return node;
+};
+
+// Built with DOM calls rather than HTML, since everything shown comes from
+// the linked page
+const cardContents = (preview, href) => {
+  const nodes = [];
+  if (preview.image) {
+    const image = element('img', 'link-card-image');
+    image.src = preview.image;
+    image.alt = '';
+    nodes.push(image);
+  }
+  const body = element('span', 'link-card-body');
+  body.append(element('span', 'link-card-title', preview.title || href));
+  if (preview.description) {
+    body.append(element('span', 'link-card-description', preview.description));
+  }
+  body.append(element('span', 'link-card-site', preview.site_name || hostOf(preview.url || href)));
+  nodes.push(body);
+  return nodes;
+};
+
+// A bare URL on a paragraph of its own
+const isStandaloneLink = (anchor) => {
+  const paragraph = anchor.parentElement;
+  return (
+    paragraph?.tagName === 'P' &&
+    paragraph.childElementCount === 1 &&
+    paragraph.textContent.trim() === anchor.textContent.trim() &&
+    anchor.textContent.trim() === anchor.getAttribute('href')
+  );
+};
+
+/**
+ * Rich link cards: standalone bare URLs in the rendered preview turn into a
+ * card with the page's title, description and image. Links stay as they are
+ * when the preview can't be had.
+ * @param {HTMLElement} container - The `.markdown-preview` element
+ * @returns {() => void} Stops updating cards that are still loading
+ */
+export function renderLinkCards(container) {
+  if (!previewsEnabled()) return () => {};
+  let cancelled = false;
+  container.querySelectorAll(WEB_LINK).forEach((anchor) => {
+    if (!isStandaloneLink(anchor)) return;
+    const href = anchor.getAttribute('href');
+    fetchLinkPreview(href)
+      .then((preview) => {
+        if (cancelled || !anchor.isConnected) return;
+        anchor.classList.add('link-card');
+        anchor.replaceChildren(...cardContents(preview, href));
+      })
+      .catch(() => {});
+  });
+  return () => {
+    cancelled = true;
+  };
+}
+
+/**
+ * Hover previews: resting the pointer on a web link in the preview shows a
+ * small card for the page it points to.
+ * @param {HTMLElement} container - The `.markdown-preview` element
+ * @returns {() => void} Removes the listeners and any open card
+ */
+export function attachLinkHoverPreviews(container) {
+  let timer = null;
+  let popover = null;
+  let current = null;
+
+  const hide = () => {
+    clearTimeout(timer);
+    popover?.remove();
+    popover = null;
+    current = null;
+  };
+
+  const show = (anchor, preview) => {
+    popover?.remove();
+    popover = element('div', 'link-preview-popover');
+    popover.setAttribute('role', 'tooltip');
+    popover.append(...cardContents(preview, anchor.getAttribute('href')));
+    document.body.append(popover);
+
+    // Below the link, or above it near the bottom of the window
+    const rect = anchor.getBoundingClientRect();
+    const { height, width } = popover.getBoundingClientRect();
+    const top =
+      rect.bottom + 8 + height > window.innerHeight ? rect.top - height - 8 : rect.bottom + 8;
+    popover.style.top = `${Math.max(8, top)}px`;
+    popover.style.left = `${Math.max(8, Math.min(rect.left, window.innerWidth - width - 8))}px`;
+  };
+
+  const handleOver = (event) => {
+    const anchor = event.target.closest?.(WEB_LINK);
+    if (!anchor || anchor === current || anchor.classList.contains('link-card')) return;
+    if (!previewsEnabled()) return;
+    hide();
+    current = anchor;
+    timer = setTimeout(() => {
+      fetchLinkPreview(anchor.getAttribute('href'))
+        .then((preview) => {
+          if (current === anchor && anchor.isConnected) show(anchor, preview);
+        })
+        .catch(() => {});
+    }, HOVER_DELAY_MS);
+  };
+
+  const handleOut = (event) => {
+    if (current && !current.contains(event.relatedTarget)) hide();
+  };
+
+  container.addEventListener('mouseover', handleOver);
+  container.addEventListener('mouseout', handleOut);
+  window.addEventListener('scroll', hide, true);
+  return () => {
+    hide();
+    container.removeEventListener('mouseover', handleOver);
+    container.removeEventListener('mouseout', handleOut);
+    window.removeEventListener('scroll', hide, true);
+  };
+}
@@ -0,0 +1,68 @@
+import { EditorView } from '@codemirror/view';
+import useSettingsStore from '../../store/settingsStore';
+import { fetchUrlTitle } from '../../utils/linkTitles';
+
+const BARE_URL = /^https?:\/\/[^\s<>()[\]]+$/i;
+
+// Brackets and backslashes in a title would end or escape the link text
+const escapeLinkText = (text) => text.replace(/[\\[\]]/g, '\\$&');
+
+// Pasting right after `](` or `<`, or inside code, means the URL is already
+// part of some markdown and should stay as typed
+const insideMarkup = (view, pos) => {
+  const line = view.state.doc.lineAt(pos);
+  const before = line.text.slice(0, pos - line.from);
+  if (/\]\($|<$|\(\s*$/.test(before)) return true;
+  const ticks = before.match(/`/g)?.length || 0;
+  if (ticks % 2 === 1) return true;
+  const fences = view.state.sliceDoc(0, line.from).match(/^(```|~~~)/gm)?.length || 0;
+  return fences % 2 === 1;
+};
+
+/**
+ * Link titles on paste: a bare http(s) URL pasted on its own goes in as is,
+ * then becomes `[Page Title](url)` once the backend has fetched the title.
+ * Nothing changes when the fetch fails or the URL was edited meanwhile.
+ */
+export function linkTitlePaste() {
+  return EditorView.domEventHandlers({
+    paste(event, view) {
+      if (!useSettingsStore.getState().pasteLinkTitles) return false;
+      const url = event.clipboardData?.getData('text/plain')?.trim();
+      const { selection } = view.state;
+      if (!url || !BARE_URL.test(url) || selection.ranges.length > 1 || !selection.main.empty) {
+        return false;
+      }
+      const from = selection.main.from;
+      if (insideMarkup(view, from)) return false;
+
+      event.preventDefault();
+      view.dispatch({
+        changes: { from, insert: url },
+        selection: { anchor: from + url.length },
+        scrollIntoView: true,
+        userEvent: 'input.paste',
+      });
+
+      fetchUrlTitle(url)
+        .then((title) => {
+          // Typing before the link moves it; then it's only safe to replace
+          // when the URL appears just once
+          if (!view.dom.isConnected) return;
+          const doc = view.state.doc.toString();
+          let at = from;
+          if (doc.slice(from, from + url.length) !== url) {
+            at = doc.indexOf(url);
+            if (at < 0 || doc.indexOf(url, at + 1) >= 0) return;
+          }
+          if (insideMarkup(view, at)) return;
+          view.dispatch({
+            changes: { from: at, to: at + url.length, insert: `[${escapeLinkText(title)}](${url})` },
+            userEvent: 'input.complete',
+          });
+        })
+        .catch(() => {});
+      return true;
+    },
+  });
+}
@@ -0,0 +1,62 @@
+import { Prec } from '@codemirror/state';
+import { keymap } from '@codemirror/view';
+import { listen } from '@tauri-apps/api/event';
+import { expandSnippet, listSnippets } from '../../utils/snippets';
+import { recordFeatureUse } from '../../utils/telemetry';
+
+// Snippet keys from settings, shared by every editor and kept current
+// through `settings-changed`
+let snippetKeys = new Set();
+let subscribed = false;
+
+const setSnippets = (snippets = []) => {
+  snippetKeys = new Set(snippets.map((snippet) => snippet.key));
+};
+
+const ensureSnippetKeys = () => {
+  if (subscribed) return;
+  subscribed = true;
+  listSnippets()
+    .then(setSnippets)
+    .catch(() => {});
+  listen('settings-changed', (event) => {
+    setSnippets(event.payload?.settings?.snippets);
+  }).catch(() => {});
+};
+
+/**
+ * Snippet expansion: typing a snippet key (like `;sig`) and pressing Tab
+ * replaces it with the snippet's text, placing the cursor at `{{cursor}}`.
+ * Tab falls through to indentation when the text before the cursor isn't a key.
+ */
+export function snippetExpansion(getNotePath = () => null) {
+  ensureSnippetKeys();
+
+  const expand = (view) => {
+    const { selection } = view.state;
+    const { head, empty } = selection.main;
+    if (!empty || selection.ranges.length > 1 || snippetKeys.size === 0) return false;
+
+    const line = view.state.doc.lineAt(head);
+    const key = line.text.slice(0, head - line.from).match(/\S+$/)?.[0];
+    if (!key || !snippetKeys.has(key)) return false;
+
+    const from = head - key.length;
+    expandSnippet(key, getNotePath())
+      .then(({ text, cursor }) => {
+        // Typing may have carried on while the expansion was on its way
+        if (view.state.sliceDoc(from, head) !== key) return;
+        view.dispatch({
+          changes: { from, to: head, insert: text },
+          selection: { anchor: from + (cursor ?? text.length) },
+          scrollIntoView: true,
+          userEvent: 'input.complete',
+        });
+        recordFeatureUse('snippet_expansion');
+      })
+      .catch(() => {});
+    return true;
+  };
+
+  return Prec.highest(keymap.of([{ key: 'Tab', run: expand }]));
+}
@@ -1,15 +1,53 @@
 import { autocompletion } from '@codemirror/autocomplete';
+import useNotesStore from '../../store/notesStore';
+import { getLinkCandidates } from '../../utils/links';
+
+const CANDIDATE_DETAILS = {
+  title: () => '📄 Note',
+  alias: (candidate) => `Alias of ${candidate.target}`,
+  heading: (candidate) => `# in ${candidate.target.split('#')[0]}`,
+  block: (candidate) => `^ in ${candidate.target.split('#')[0]}`,
+};
+
+// Inserts `text` and closes the link unless `]]` already follows
+const applyLink = (text) => (view, completion, from, to) => {
+  const textAfter = view.state.sliceDoc(to, to + 2);
+  const insertText = text + (textAfter.startsWith(']]') ? '' : ']]');
+  view.dispatch({
+    changes: { from, to, insert: insertText },
+    selection: { anchor: from + insertText.length },
+  });
+};
 
 /**
  * Creates a wiki link autocomplete extension for CodeMirror
- * Triggers when typing [[ and shows available notes as completions
+ * Triggers when typing [[ and shows notes, aliases and headings ranked by the
+ * workspace index, falling back to the in-memory note list
  *
  * @param {Function} getNotes - Function that returns array of notes with {id, name} properties
  * @param {Function} getTags - Function that returns tags (array of strings or {tag} objects)
  * @returns {Extension} CodeMirror extension
  */
 export function createWikiLinkAutocomplete(getNotes, getTags = () => []) {
-  const wikiLinkCompletions = (context) => {
+  // Notes from the store; `unsavedOnly` keeps the new ones the index hasn't seen
+  const localOptions = (searchText, options = {}) => {
+    const notes = getNotes() || [];
+    return notes
+      .filter((note) => !options.unsavedOnly || !note.filePath)
+      .filter((note) => {
+        if (!searchText) return true;
+        return note.name.toLowerCase().includes(searchText.toLowerCase());
+      })
+      .map((note) => ({
+        label: note.name,
+        type: 'text',
+        apply: applyLink(note.name),
+        detail: note.filePath ? '📄 Note' : '✨ New',
+        info: note.filePath || 'Press Enter to insert',
+      }));
+  };
+
+  const wikiLinkCompletions = async (context) => {
     const { state, pos } = context;
     const textBefore = state.sliceDoc(Math.max(0, pos - 100), pos);
 
@@ -24,47 +62,54 @@ export function createWikiLinkAutocomplete(getNotes, getTags = () => []) {
     const searchText = openBracketMatch[1];
     const from = pos - searchText.length;
 
-    // Get all available notes
-    const notes = getNotes();
-
-    if (!notes || notes.length === 0) {
-      return null;
+    // Ranked by the backend index; the in-memory note list is the fallback
+    const { rootFolderPath, getCurrentNote } = useNotesStore.getState();
+    let options = null;
+    if (rootFolderPath) {
+      try {
+        const candidates = await getLinkCandidates(
+          rootFolderPath,
+          searchText,
+          getCurrentNote()?.filePath || null
+        );
+        if (context.aborted) return null;
+        const inCurrentNote = searchText.startsWith('#');
+        options = candidates.map((candidate) => ({
+          label: candidate.label,
+          type: candidate.kind === 'heading' || candidate.kind === 'block' ? 'property' : 'text',
+          apply: applyLink(
+            candidate.kind === 'heading' && inCurrentNote
+              ? `#${candidate.label}`
+              : candidate.kind === 'block' && inCurrentNote
+                ? `#^${candidate.label}`
+                : candidate.kind === 'alias'
+                  ? `${candidate.target}|${candidate.label}`
+                  : candidate.target
+          ),
+          detail: CANDIDATE_DETAILS[candidate.kind](candidate),
+          info: candidate.path,
+        }));
+        if (!searchText.includes('#')) {
+          options.push(...localOptions(searchText, { unsavedOnly: true }));
+        }
+      } catch {
+        options = null;
+      }
     }
-
-    // Filter and create completion options
-    const options = notes
-      .filter(note => {
-        if (!searchText) return true;
-        return note.name.toLowerCase().includes(searchText.toLowerCase());
-      })
-      .map(note => ({
-        label: note.name,
-        type: 'text',
-        apply: (view, completion, from, to) => {
-          // Check if we need to add closing brackets
-          const textAfter = view.state.sliceDoc(to, to + 2);
-          const needsClosing = !textAfter.startsWith(']]');
-
-          const insertText = note.name + (needsClosing ? ']]' : '');
-
-          view.dispatch({
-            changes: { from, to, insert: insertText },
-            selection: { anchor: from + insertText.length }
-          });
-        },
-        detail: note.filePath ? '📄 Note' : '✨ New',
-        info: note.filePath || 'Press Enter to insert',
-      }))
-      .slice(0, 20); // Limit to 20 results for performance
+    if (!options) {
+      options = localOptions(searchText);
+    }
+    options = options.slice(0, 20); // Limit to 20 results for performance
 
     if (options.length === 0) {
       return null;
     }
 
+    // Already ranked (and fuzzy), so CodeMirror shouldn't filter them again
     return {
       from,
       options,
-      validFor: /^[^\]]*$/
+      filter: false,
     };
   };
 
@@ -73,7 +118,7 @@ export function createWikiLinkAutocomplete(getNotes, getTags = () => []) {
     const textBefore = state.sliceDoc(Math.max(0, pos - 80), pos);
 
     // Trigger on hashtags but avoid markdown headings (line start # / ## ...)
-    const tagMatch = /(?:^|[\s(])#([a-zA-Z0-9_-]*)$/.exec(textBefore);
+    const tagMatch = /(?:^|[\s(])#([a-zA-Z0-9_/-]*)$/.exec(textBefore);
     if (!tagMatch) return null;
 
     const searchText = tagMatch[1] || '';
@@ -109,7 +154,7 @@ export function createWikiLinkAutocomplete(getNotes, getTags = () => []) {
     return {
       from,
       options,
-      validFor: /^[a-zA-Z0-9_-]*$/
+      validFor: /^[a-zA-Z0-9_/-]*$/
     };
   };
 
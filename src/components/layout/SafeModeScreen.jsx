@@ -0,0 +1,98 @@
+import { useState } from "react";
+import { generateDiagnosticsBundle } from "../../utils/diagnostics";
+import { leaveSafeMode, resetAppState } from "../../utils/safeMode";
+
+// Rendered instead of the app in safe mode; it touches no stores so corrupted
+// persisted state can't break it
+const SafeModeScreen = ({ onContinue }) => {
+  const [confirmingReset, setConfirmingReset] = useState(false);
+  const [isBusy, setIsBusy] = useState(false);
+  const [status, setStatus] = useState("");
+  const [error, setError] = useState("");
+
+  const run = async (action) => {
+    setIsBusy(true);
+    setError("");
+    try {
+      await action();
+    } catch (actionError) {
+      setError(actionError?.message || String(actionError));
+    } finally {
+      setIsBusy(false);
+    }
+  };
+
+  const handleContinue = () =>
+    run(async () => {
+      await leaveSafeMode();
+      onContinue();
+    });
+
+  const handleReset = () => {
+    if (!confirmingReset) {
+      setConfirmingReset(true);
+      return;
+    }
+    run(resetAppState);
+  };
+
+  const handleDiagnostics = () =>
+    run(async () => {
+      const savedPath = await generateDiagnosticsBundle();
+      if (savedPath) setStatus(`Diagnostics saved to ${savedPath}`);
+    });
+
+  const secondaryButton =
+    "w-full px-4 py-2 text-sm font-medium text-text-secondary hover:text-text-primary bg-overlay-subtle hover:bg-overlay-light rounded-lg border border-overlay-light transition-colors disabled:opacity-60";
+
+  return (
+    <div className="h-screen flex items-center justify-center bg-editor-bg p-6">
+      <div className="w-full max-w-md text-center space-y-5">
+        <div>
+          <h1 className="text-lg font-semibold text-text-primary">Marky is in safe mode</h1>
+          <p className="mt-2 text-sm text-text-secondary">
+            No workspace was opened and folder watching and indexing are off. If Marky kept
+            failing to start, resetting its state usually fixes it. Your notes are never
+            changed.
+          </p>
+        </div>
+
+        <div className="space-y-3">
+          <button
+            type="button"
+            onClick={handleContinue}
+            disabled={isBusy}
+            className="w-full px-4 py-2 text-sm font-medium rounded-lg bg-accent hover:bg-accent-hover text-white transition-colors disabled:opacity-60"
+          >
+            Continue Normally
+          </button>
+          <button
+            type="button"
+            onClick={handleReset}
+            disabled={isBusy}
+            className={
+              confirmingReset
+                ? "w-full px-4 py-2 text-sm font-medium rounded-lg bg-red-500 hover:bg-red-600 text-white transition-colors disabled:opacity-60"
+                : secondaryButton
+            }
+          >
+            {confirmingReset ? "Reset Settings, Sessions, and Recent Workspaces" : "Reset App State…"}
+          </button>
+          <button
+            type="button"
+            onClick={handleDiagnostics}
+            disabled={isBusy}
+            className={secondaryButton}
+          >
+            Save Diagnostics…
+          </button>
+        </div>
+
+        {status && <p className="text-xs text-text-muted break-all">{status}</p>}
+        {error && <p className="text-xs text-red-400">{error}</p>}
+      </div>
+    </div>
+  );
+};
+
+export default SafeModeScreen;
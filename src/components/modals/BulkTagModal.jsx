@@ -0,0 +1,177 @@
+import { useMemo, useState } from "react";
+import useNotesStore from "../../store/notesStore";
+import useUIStore from "../../store/uiStore";
+
+const BulkTagModal = ({ isOpen, onClose, initialTag = "" }) => {
+  const items = useNotesStore((state) => state.items);
+  const applyBulkTagEdit = useNotesStore((state) => state.applyBulkTagEdit);
+  const addNotification = useUIStore((state) => state.addNotification);
+
+  const [tag, setTag] = useState(initialTag);
+  const [filter, setFilter] = useState("");
+  const [selectedPaths, setSelectedPaths] = useState(new Set());
+  const [isWorking, setIsWorking] = useState(false);
+
+  const allNotes = useMemo(
+    () => items.filter((item) => item.type === "note" && item.filePath),
+    [items]
+  );
+  const visibleNotes = useMemo(() => {
+    const q = filter.trim().toLowerCase();
+    if (!q) return allNotes;
+    return allNotes.filter(
+      (note) =>
+        note.name.toLowerCase().includes(q) || note.filePath.toLowerCase().includes(q)
+    );
+  }, [allNotes, filter]);
+
+  if (!isOpen) return null;
+
+  const toggleNote = (path) => {
+    setSelectedPaths((prev) => {
+      const next = new Set(prev);
+      if (next.has(path)) {
+        next.delete(path);
+      } else {
+        next.add(path);
+      }
+      return next;
+    });
+  };
+
+  const allVisibleSelected =
+    visibleNotes.length > 0 && visibleNotes.every((note) => selectedPaths.has(note.filePath));
+
+  const toggleVisible = () => {
+    setSelectedPaths((prev) => {
+      const next = new Set(prev);
+      visibleNotes.forEach((note) =>
+        allVisibleSelected ? next.delete(note.filePath) : next.add(note.filePath)
+      );
+      return next;
+    });
+  };
+
+  const handleApply = async (action) => {
+    setIsWorking(true);
+    try {
+      const report = await applyBulkTagEdit({ action, paths: Array.from(selectedPaths), tag });
+
+      const verb = action === "add" ? "Added" : "Removed";
+      const skipped = report.skipped.length ? `, ${report.skipped.length} skipped` : "";
+      addNotification(
+        `${verb} #${report.tag} ${action === "add" ? "to" : "from"} ${report.changed.length} note${
+          report.changed.length !== 1 ? "s" : ""
+        }${skipped}`,
+        report.skipped.length ? "warning" : "success",
+        5000
+      );
+      onClose();
+    } catch (error) {
+      addNotification("Tag update failed: " + (error?.message || error), "error", 5000);
+    } finally {
+      setIsWorking(false);
+    }
+  };
+
+  const canApply = !isWorking && tag.trim() && selectedPaths.size > 0;
+
+  return (
+    <>
+      <div className="fixed inset-0 bg-black/60 backdrop-blur-sm z-50" onClick={onClose} />
+
+      <div className="fixed inset-0 z-50 flex items-center justify-center p-4 pointer-events-none">
+        <div
+          className="glass-panel border-glass-border rounded-xl shadow-2xl w-full max-w-2xl max-h-[80vh] flex flex-col pointer-events-auto"
+          onClick={(e) => e.stopPropagation()}
+        >
+          <div className="border-b border-glass-border px-6 py-4 shrink-0">
+            <h2 className="text-xl font-semibold text-text-primary">Tag Notes</h2>
+            <p className="text-sm text-text-muted mt-1">
+              Add or remove a tag across many notes in one step
+            </p>
+          </div>
+
+          <div className="px-6 pt-5 pb-3 shrink-0 space-y-3">
+            <div className="flex items-center gap-2">
+              <span className="text-text-muted text-sm">#</span>
+              <input
+                type="text"
+                value={tag}
+                onChange={(e) => setTag(e.target.value)}
+                placeholder="tag-name"
+                className="flex-1 px-3 py-2 bg-overlay-subtle border border-overlay-subtle rounded-lg text-sm text-text-primary outline-none focus:border-accent/40"
+              />
+            </div>
+            <input
+              type="text"
+              value={filter}
+              onChange={(e) => setFilter(e.target.value)}
+              placeholder="Filter notes..."
+              className="w-full px-3 py-2 bg-overlay-subtle border border-overlay-subtle rounded-lg text-sm text-text-primary outline-none focus:border-accent/40"
+            />
+          </div>
+
+          <div className="flex-1 overflow-y-auto px-6 pb-2 min-h-0 custom-scrollbar">
+            <label className="flex items-center gap-2 mb-2 py-1 border-b border-glass-border text-xs text-text-muted cursor-pointer select-none">
+              <input
+                type="checkbox"
+                checked={allVisibleSelected}
+                onChange={toggleVisible}
+                className="rounded accent-accent"
+              />
+              Select all{filter.trim() ? " matching" : ""}
+            </label>
+            <ul className="space-y-1">
+              {visibleNotes.map((note) => (
+                <li key={note.id}>
+                  <label className="flex items-center gap-2 py-1.5 px-2 rounded-lg hover:bg-overlay-light cursor-pointer transition-colors">
+                    <input
+                      type="checkbox"
+                      checked={selectedPaths.has(note.filePath)}
+                      onChange={() => toggleNote(note.filePath)}
+                      className="rounded accent-accent shrink-0"
+                    />
+                    <span className="text-sm text-text-primary truncate" title={note.filePath}>
+                      {note.name}
+                    </span>
+                  </label>
+                </li>
+              ))}
+            </ul>
+          </div>
+
+          <div className="border-t border-overlay-light px-6 py-4 flex justify-between items-center shrink-0">
+            <p className="text-xs text-text-muted">
+              {selectedPaths.size} note{selectedPaths.size !== 1 ? "s" : ""} selected
+            </p>
+            <div className="flex gap-2">
+              <button
+                onClick={onClose}
+                className="px-4 py-2 text-sm text-text-secondary hover:text-text-primary hover:bg-overlay-light rounded-lg transition-colors"
+              >
+                Cancel
+              </button>
+              <button
+                onClick={() => handleApply("remove")}
+                disabled={!canApply}
+                className="px-4 py-2 text-sm font-medium rounded-lg border border-red-500/30 bg-red-500/10 text-red-300 hover:bg-red-500/15 disabled:opacity-50"
+              >
+                Remove Tag
+              </button>
+              <button
+                onClick={() => handleApply("add")}
+                disabled={!canApply}
+                className="px-4 py-2 text-sm font-medium rounded-lg bg-accent hover:bg-accent/80 text-white disabled:opacity-50"
+              >
+                {isWorking ? "Updating..." : "Add Tag"}
+              </button>
+            </div>
+          </div>
+        </div>
+      </div>
+    </>
+  );
+};
+
+export default BulkTagModal;
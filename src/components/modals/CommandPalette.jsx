@@ -1,6 +1,7 @@
 import { useMemo, useState, useEffect, useRef } from "react";
 import Fuse from "fuse.js";
 import useNotesStore from "../../store/notesStore";
+import usePluginStore from "../../store/pluginStore";
 import useModalAccessibility from "../../hooks/useModalAccessibility";
 
 const CommandPalette = ({ isOpen, onClose, onExecuteCommand }) => {
@@ -14,6 +15,7 @@ const CommandPalette = ({ isOpen, onClose, onExecuteCommand }) => {
   const activeResultId = results.length > 0 ? `command-palette-result-${selectedIndex}` : undefined;
 
   const { items, currentNoteId, isPinned } = useNotesStore();
+  const pluginCommands = usePluginStore((state) => state.commands);
   useModalAccessibility(isOpen, dialogRef, inputRef);
   const currentNote = items.find((item) => item.id === currentNoteId && item.type === "note");
 
@@ -45,6 +47,14 @@ const CommandPalette = ({ isOpen, onClose, onExecuteCommand }) => {
         action: "openFolder",
         keywords: ["import", "load"],
       },
+      {
+        id: "import-documents",
+        name: "Import Documents",
+        category: "File Operations",
+        icon: "📥",
+        action: "importDocuments",
+        keywords: ["files app", "document picker", "copy", "add files", "mobile"],
+      },
       {
         id: "save",
         name: "Save Note",
@@ -115,6 +125,38 @@ const CommandPalette = ({ isOpen, onClose, onExecuteCommand }) => {
         action: "exportNote",
         keywords: ["download", "save as"],
       },
+      {
+        id: "email-note",
+        name: "Email Note",
+        category: "Tools",
+        icon: "✉️",
+        action: "emailNote",
+        keywords: ["mail", "send", "share", "smtp", "minutes"],
+      },
+      {
+        id: "share-note-pdf",
+        name: "Share Note as PDF",
+        category: "Tools",
+        icon: "🔗",
+        action: "shareNotePdf",
+        keywords: ["share sheet", "airdrop", "messages", "send"],
+      },
+      {
+        id: "share-note-html",
+        name: "Share Note as HTML",
+        category: "Tools",
+        icon: "🔗",
+        action: "shareNoteHtml",
+        keywords: ["share sheet", "airdrop", "messages", "send", "web page"],
+      },
+      {
+        id: "copy-rich-text",
+        name: "Copy as Rich Text",
+        category: "Tools",
+        icon: "📋",
+        action: "copyAsRichText",
+        keywords: ["clipboard", "formatted", "html", "rtf", "word", "gmail", "paste"],
+      },
       {
         id: "settings",
         name: "Open Settings",
@@ -142,6 +184,55 @@ const CommandPalette = ({ isOpen, onClose, onExecuteCommand }) => {
         keywords: ["distraction", "zen", "write", "fullscreen"],
       },
 
+      {
+        id: "toggle-archived",
+        name: "Toggle Archived Notes",
+        category: "View",
+        icon: "🗄️",
+        action: "toggleShowArchived",
+        keywords: ["archive", "hidden", "old"],
+      },
+      {
+        id: "previous-daily-note",
+        name: "Previous Daily Note",
+        category: "Navigation",
+        icon: "⬅️",
+        action: "previousDailyNote",
+        keywords: ["journal", "daily", "back", "yesterday", "earlier"],
+      },
+      {
+        id: "next-daily-note",
+        name: "Next Daily Note",
+        category: "Navigation",
+        icon: "➡️",
+        action: "nextDailyNote",
+        keywords: ["journal", "daily", "forward", "tomorrow", "later"],
+      },
+      {
+        id: "meeting-note-from-calendar",
+        name: "New Meeting Note from Calendar",
+        category: "Tools",
+        icon: "📅",
+        action: "newMeetingNote",
+        keywords: ["meeting", "calendar", "ics", "event", "today"],
+      },
+      {
+        id: "create-vault",
+        name: "Encrypt Workspace as Vault",
+        category: "Workspace",
+        icon: "🔐",
+        action: "createVault",
+        keywords: ["vault", "encrypt", "passphrase", "security"],
+      },
+      {
+        id: "lock-vault",
+        name: "Lock Vault",
+        category: "Workspace",
+        icon: "🔒",
+        action: "lockVault",
+        keywords: ["vault", "encrypt", "lock", "security"],
+      },
+
       // Backup
       {
         id: "backup",
@@ -151,9 +242,28 @@ const CommandPalette = ({ isOpen, onClose, onExecuteCommand }) => {
         action: "backupWorkspace",
         keywords: ["export", "zip", "archive", "save"],
       },
+      {
+        id: "export-tasks-calendar",
+        name: "Export Tasks to Calendar (.ics)",
+        category: "Tools",
+        icon: "🗓️",
+        action: "exportTasksCalendar",
+        keywords: ["ical", "ics", "due", "todo", "export"],
+      },
     ];
 
-    if (!currentNote) return baseCommands;
+    // Registered by running plugins
+    const pluginEntries = pluginCommands.map((cmd) => ({
+      id: `plugin-${cmd.pluginId}-${cmd.id}`,
+      name: cmd.name,
+      category: "Plugins",
+      icon: "🧩",
+      action: "pluginCommand",
+      payload: { pluginId: cmd.pluginId, commandId: cmd.id },
+      keywords: ["plugin", cmd.pluginId],
+    }));
+
+    if (!currentNote) return [...baseCommands, ...pluginEntries];
 
     const pinLabel = isPinned(currentNote.id) ? "Unpin Current Note" : "Pin Current Note";
     return [
@@ -198,6 +308,14 @@ const CommandPalette = ({ isOpen, onClose, onExecuteCommand }) => {
         action: "copyCurrentNoteWikiLink",
         keywords: ["clipboard", "markdown", "link"],
       },
+      {
+        id: "copy-block-link",
+        name: "Copy Block Link",
+        category: "Current Note",
+        icon: "🧩",
+        action: "copyBlockLink",
+        keywords: ["clipboard", "block reference", "paragraph", "^id"],
+      },
       {
         id: "toggle-current-pin",
         name: pinLabel,
@@ -206,8 +324,9 @@ const CommandPalette = ({ isOpen, onClose, onExecuteCommand }) => {
         action: "toggleCurrentNotePin",
         keywords: ["favorite", "pinned"],
       },
+      ...pluginEntries,
     ];
-  }, [currentNote, isPinned]);
+  }, [currentNote, isPinned, pluginCommands]);
 
   // Get all notes for navigation
   const notes = items.filter((item) => item.type === "note");
@@ -236,7 +355,9 @@ const CommandPalette = ({ isOpen, onClose, onExecuteCommand }) => {
       // Add notes
       ...notes.map((note) => ({
         ...note,
-        searchText: `${note.name} ${note.content || ""}`,
+        searchText: note.encrypted
+          ? note.name
+          : `${note.name} ${(note.aliases || []).join(" ")} ${note.content || ""}`,
         type: "note",
       })),
     ];
@@ -244,6 +365,7 @@ const CommandPalette = ({ isOpen, onClose, onExecuteCommand }) => {
     const fuse = new Fuse(allItems, {
       keys: [
         { name: "name", weight: 3 },
+        { name: "aliases", weight: 2 },
         { name: "searchText", weight: 1 },
       ],
       includeScore: true,
@@ -319,7 +441,7 @@ const CommandPalette = ({ isOpen, onClose, onExecuteCommand }) => {
     } else if (item.type === "command") {
       // Execute command
       if (onExecuteCommand) {
-        onExecuteCommand({ action: item.action });
+        onExecuteCommand({ action: item.action, payload: item.payload });
       }
     }
 
@@ -334,6 +456,7 @@ const CommandPalette = ({ isOpen, onClose, onExecuteCommand }) => {
       Tools: "🛠️",
       Help: "❓",
       "Current Note": "📄",
+      Plugins: "🧩",
     };
     return icons[category] || "⚡";
   };
@@ -0,0 +1,121 @@
+import { useEffect, useState } from "react";
+import { diffNotes } from "../../utils/diff";
+import { recordFeatureUse } from "../../utils/telemetry";
+import DiffHunks from "./DiffHunks";
+
+const CompareNotesModal = ({ isOpen, oldNote, newNote, onClose }) => {
+  const [sides, setSides] = useState({ old: oldNote, new: newNote });
+  const [diff, setDiff] = useState(null);
+  const [error, setError] = useState(null);
+
+  useEffect(() => {
+    setSides({ old: oldNote, new: newNote });
+  }, [oldNote, newNote]);
+
+  useEffect(() => {
+    if (isOpen) recordFeatureUse("compare_notes");
+  }, [isOpen]);
+
+  useEffect(() => {
+    if (!isOpen || !sides.old?.path || !sides.new?.path) return;
+    let cancelled = false;
+    setDiff(null);
+    setError(null);
+    diffNotes(sides.old.path, sides.new.path)
+      .then((result) => {
+        if (!cancelled) setDiff(result);
+      })
+      .catch((err) => {
+        if (!cancelled) setError(String(err?.message || err));
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [isOpen, sides]);
+
+  if (!isOpen) return null;
+
+  return (
+    <div
+      className="fixed inset-0 z-50 flex items-center justify-center p-4"
+      role="dialog"
+      aria-modal="true"
+      aria-label="Compare notes"
+      onClick={(e) => {
+        if (e.target === e.currentTarget) onClose?.();
+      }}
+    >
+      <div className="absolute inset-0 bg-black/65 backdrop-blur-sm" />
+
+      <div className="relative z-10 w-full max-w-6xl max-h-[88vh] flex flex-col bg-bg-sidebar border border-border rounded-xl shadow-2xl overflow-hidden">
+        <div className="flex items-start justify-between gap-4 px-5 py-4 border-b border-border shrink-0">
+          <div className="min-w-0">
+            <h2 className="text-base font-semibold text-text-primary">Compare Notes</h2>
+            <p className="text-xs text-text-muted mt-1">
+              Saved versions, changed words highlighted.
+              {diff && (
+                <>
+                  <span className="ml-3 text-red-300">-{diff.removed}</span>
+                  <span className="ml-2 text-green-300">+{diff.added}</span>
+                </>
+              )}
+            </p>
+          </div>
+          <div className="flex items-center gap-2 shrink-0">
+            <button
+              onClick={() => setSides((current) => ({ old: current.new, new: current.old }))}
+              className="px-3 py-1.5 text-xs rounded-md border border-overlay-light bg-overlay-subtle text-text-primary hover:bg-overlay-light transition-colors"
+              title="Swap the two sides"
+            >
+              Swap
+            </button>
+            <button
+              onClick={onClose}
+              className="p-1.5 text-text-secondary hover:text-text-primary hover:bg-overlay-subtle rounded-md transition-colors"
+              aria-label="Close comparison"
+            >
+              <svg className="w-5 h-5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                <path
+                  strokeLinecap="round"
+                  strokeLinejoin="round"
+                  strokeWidth={2}
+                  d="M6 18L18 6M6 6l12 12"
+                />
+              </svg>
+            </button>
+          </div>
+        </div>
+
+        <div className="grid grid-cols-2 border-b border-border bg-bg-base/80 shrink-0">
+          <div className="px-4 py-2 border-r border-border min-w-0">
+            <p className="text-xs font-semibold text-text-primary truncate">{sides.old?.name}</p>
+            <p className="text-[11px] text-text-muted truncate">{sides.old?.path}</p>
+          </div>
+          <div className="px-4 py-2 min-w-0">
+            <p className="text-xs font-semibold text-text-primary truncate">{sides.new?.name}</p>
+            <p className="text-[11px] text-text-muted truncate">{sides.new?.path}</p>
+          </div>
+        </div>
+
+        {error ? (
+          <div className="flex-1 flex items-center justify-center py-16 px-6 text-sm text-red-300 text-center">
+            {error}
+          </div>
+        ) : !diff ? (
+          <div className="flex-1 flex items-center justify-center py-16 text-sm text-text-muted">
+            Comparing…
+          </div>
+        ) : diff.hunks.length === 0 ? (
+          <div className="flex-1 flex flex-col items-center justify-center gap-3 py-16 text-center px-6">
+            <p className="text-sm font-medium text-text-primary">No content differences</p>
+            <p className="text-xs text-text-muted max-w-xs">Both notes have the same content.</p>
+          </div>
+        ) : (
+          <DiffHunks hunks={diff.hunks} />
+        )}
+      </div>
+    </div>
+  );
+};
+
+export default CompareNotesModal;
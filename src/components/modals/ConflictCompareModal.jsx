@@ -1,5 +1,6 @@
-import { useMemo } from "react";
+import { useEffect, useMemo, useState } from "react";
 import { computeLineDiff } from "../../utils/diff";
+import ConflictMergePanel from "./ConflictMergePanel";
 
 const buildSideBySideRows = (diffLines) => {
   let draftLine = 0;
@@ -59,7 +60,17 @@ const ConflictCompareModal = ({
   onClose,
   onUseDisk,
   onKeepLocal,
+  baseContent = null,
+  onUseMerged,
 }) => {
+  // A merge needs the version both sides started from
+  const canMerge = typeof baseContent === "string" && Boolean(onUseMerged);
+  const [mode, setMode] = useState("compare");
+
+  useEffect(() => {
+    if (!isOpen || !canMerge) setMode("compare");
+  }, [isOpen, canMerge]);
+
   const diffLines = useMemo(
     () => computeLineDiff(localContent || "", diskContent || ""),
     [localContent, diskContent]
@@ -100,105 +111,139 @@ const ConflictCompareModal = ({
               {detectedAt ? ` - detected ${new Date(detectedAt).toLocaleString()}` : ""}
             </p>
           </div>
-          <button
-            onClick={onClose}
-            className="p-1.5 text-text-secondary hover:text-text-primary hover:bg-overlay-subtle rounded-md transition-colors"
-            aria-label="Close conflict comparison"
-          >
-            <svg className="w-5 h-5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
-              <path
-                strokeLinecap="round"
-                strokeLinejoin="round"
-                strokeWidth={2}
-                d="M6 18L18 6M6 6l12 12"
-              />
-            </svg>
-          </button>
-        </div>
-
-        <div className="px-5 py-3 border-b border-border bg-overlay-subtle/40 shrink-0 flex flex-col gap-3 md:flex-row md:items-center md:justify-between">
-          <div className="text-xs text-text-secondary">
-            Review your unsaved draft beside the version currently on disk before choosing a
-            resolution.
-            <span className="ml-3 text-red-300">-{stats.deletions} draft-only</span>
-            <span className="ml-2 text-green-300">+{stats.additions} disk-only</span>
-          </div>
           <div className="flex items-center gap-2 shrink-0">
+            {canMerge && (
+              <div className="flex rounded-md border border-overlay-light overflow-hidden text-xs">
+                {[
+                  ["compare", "Side by Side"],
+                  ["merge", "Merge"],
+                ].map(([value, label]) => (
+                  <button
+                    key={value}
+                    onClick={() => setMode(value)}
+                    aria-pressed={mode === value}
+                    className={`px-3 py-1.5 transition-colors ${
+                      mode === value
+                        ? "bg-accent/20 text-text-primary"
+                        : "bg-overlay-subtle text-text-secondary hover:bg-overlay-light"
+                    }`}
+                  >
+                    {label}
+                  </button>
+                ))}
+              </div>
+            )}
             <button
-              onClick={onUseDisk}
-              className="px-3 py-1.5 text-xs rounded-md border border-overlay-light bg-overlay-subtle text-text-primary hover:bg-overlay-light transition-colors"
-            >
-              Load Disk Version
-            </button>
-            <button
-              onClick={onKeepLocal}
-              className="px-3 py-1.5 text-xs rounded-md bg-amber-500 text-black hover:bg-amber-400 transition-colors font-medium"
+              onClick={onClose}
+              className="p-1.5 text-text-secondary hover:text-text-primary hover:bg-overlay-subtle rounded-md transition-colors"
+              aria-label="Close conflict comparison"
             >
-              Overwrite Disk With Draft
+              <svg className="w-5 h-5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                <path
+                  strokeLinecap="round"
+                  strokeLinejoin="round"
+                  strokeWidth={2}
+                  d="M6 18L18 6M6 6l12 12"
+                />
+              </svg>
             </button>
           </div>
         </div>
 
-        <div className="grid grid-cols-2 border-b border-border bg-bg-base/80 shrink-0">
-          <div className="px-4 py-2 border-r border-border">
-            <p className="text-xs font-semibold text-text-primary">Current Draft</p>
-            <p className="text-[11px] text-text-muted">Unsaved content in Marky</p>
-          </div>
-          <div className="px-4 py-2">
-            <p className="text-xs font-semibold text-text-primary">Disk Version</p>
-            <p className="text-[11px] text-text-muted">External file content</p>
+        {mode === "merge" ? (
+          <ConflictMergePanel
+            baseContent={baseContent}
+            localContent={localContent || ""}
+            diskContent={diskContent || ""}
+            onUseMerged={onUseMerged}
+          />
+        ) : (
+          <>
+          <div className="px-5 py-3 border-b border-border bg-overlay-subtle/40 shrink-0 flex flex-col gap-3 md:flex-row md:items-center md:justify-between">
+            <div className="text-xs text-text-secondary">
+              Review your unsaved draft beside the version currently on disk before choosing a
+              resolution.
+              <span className="ml-3 text-red-300">-{stats.deletions} draft-only</span>
+              <span className="ml-2 text-green-300">+{stats.additions} disk-only</span>
+            </div>
+            <div className="flex items-center gap-2 shrink-0">
+              <button
+                onClick={onUseDisk}
+                className="px-3 py-1.5 text-xs rounded-md border border-overlay-light bg-overlay-subtle text-text-primary hover:bg-overlay-light transition-colors"
+              >
+                Load Disk Version
+              </button>
+              <button
+                onClick={onKeepLocal}
+                className="px-3 py-1.5 text-xs rounded-md bg-amber-500 text-black hover:bg-amber-400 transition-colors font-medium"
+              >
+                Overwrite Disk With Draft
+              </button>
+            </div>
           </div>
-        </div>
 
-        {rows.length === 0 ? (
-          <div className="flex-1 flex flex-col items-center justify-center gap-3 py-16 text-center px-6">
-            <div className="w-12 h-12 rounded-full bg-green-500/10 flex items-center justify-center">
-              <svg
-                className="w-6 h-6 text-green-400"
-                fill="none"
-                stroke="currentColor"
-                viewBox="0 0 24 24"
-              >
-                <path
-                  strokeLinecap="round"
-                  strokeLinejoin="round"
-                  strokeWidth={1.5}
-                  d="M5 13l4 4L19 7"
-                />
-              </svg>
+          <div className="grid grid-cols-2 border-b border-border bg-bg-base/80 shrink-0">
+            <div className="px-4 py-2 border-r border-border">
+              <p className="text-xs font-semibold text-text-primary">Current Draft</p>
+              <p className="text-[11px] text-text-muted">Unsaved content in Marky</p>
+            </div>
+            <div className="px-4 py-2">
+              <p className="text-xs font-semibold text-text-primary">Disk Version</p>
+              <p className="text-[11px] text-text-muted">External file content</p>
             </div>
-            <p className="text-sm font-medium text-text-primary">No content differences</p>
-            <p className="text-xs text-text-muted max-w-xs">
-              The draft and disk version currently match.
-            </p>
           </div>
-        ) : (
-          <div className="flex-1 overflow-auto custom-scrollbar">
-            <div className="min-w-[900px]">
-              {rows.map((row) => (
-                <div key={row.id} className="grid grid-cols-2 border-b border-border/40">
-                  <div
-                    className={`grid grid-cols-[3rem_1fr] border-r border-border/70 ${lineClass(row.type, "draft")}`}
-                  >
-                    <div className="px-2 py-1 text-right text-[11px] text-text-muted bg-black/10 select-none">
-                      {row.draftLine}
+
+          {rows.length === 0 ? (
+            <div className="flex-1 flex flex-col items-center justify-center gap-3 py-16 text-center px-6">
+              <div className="w-12 h-12 rounded-full bg-green-500/10 flex items-center justify-center">
+                <svg
+                  className="w-6 h-6 text-green-400"
+                  fill="none"
+                  stroke="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={1.5}
+                    d="M5 13l4 4L19 7"
+                  />
+                </svg>
+              </div>
+              <p className="text-sm font-medium text-text-primary">No content differences</p>
+              <p className="text-xs text-text-muted max-w-xs">
+                The draft and disk version currently match.
+              </p>
+            </div>
+          ) : (
+            <div className="flex-1 overflow-auto custom-scrollbar">
+              <div className="min-w-[900px]">
+                {rows.map((row) => (
+                  <div key={row.id} className="grid grid-cols-2 border-b border-border/40">
+                    <div
+                      className={`grid grid-cols-[3rem_1fr] border-r border-border/70 ${lineClass(row.type, "draft")}`}
+                    >
+                      <div className="px-2 py-1 text-right text-[11px] text-text-muted bg-black/10 select-none">
+                        {row.draftLine}
+                      </div>
+                      <pre className="px-3 py-1 text-xs font-mono whitespace-pre-wrap break-words leading-relaxed">
+                        {row.draftText || "\u00A0"}
+                      </pre>
                     </div>
-                    <pre className="px-3 py-1 text-xs font-mono whitespace-pre-wrap break-words leading-relaxed">
-                      {row.draftText || "\u00A0"}
-                    </pre>
-                  </div>
-                  <div className={`grid grid-cols-[3rem_1fr] ${lineClass(row.type, "disk")}`}>
-                    <div className="px-2 py-1 text-right text-[11px] text-text-muted bg-black/10 select-none">
-                      {row.diskLine}
+                    <div className={`grid grid-cols-[3rem_1fr] ${lineClass(row.type, "disk")}`}>
+                      <div className="px-2 py-1 text-right text-[11px] text-text-muted bg-black/10 select-none">
+                        {row.diskLine}
+                      </div>
+                      <pre className="px-3 py-1 text-xs font-mono whitespace-pre-wrap break-words leading-relaxed">
+                        {row.diskText || "\u00A0"}
+                      </pre>
                     </div>
-                    <pre className="px-3 py-1 text-xs font-mono whitespace-pre-wrap break-words leading-relaxed">
-                      {row.diskText || "\u00A0"}
-                    </pre>
                   </div>
-                </div>
-              ))}
+                ))}
+              </div>
             </div>
-          </div>
+          )}
+          </>
         )}
       </div>
     </div>
@@ -0,0 +1,142 @@
+import { useEffect, useMemo, useState } from "react";
+import { mergeTexts, resolveMerge } from "../../utils/merge";
+
+const PICKS = [
+  ["ours", "Use Draft"],
+  ["theirs", "Use Disk"],
+  ["both", "Keep Both"],
+];
+
+// Merges the draft and the disk version from the version both started from,
+// and lets each overlapping change be settled by hand
+const ConflictMergePanel = ({ baseContent, localContent, diskContent, onUseMerged }) => {
+  const [merge, setMerge] = useState(null);
+  const [error, setError] = useState(null);
+  const [picks, setPicks] = useState([]);
+
+  useEffect(() => {
+    let cancelled = false;
+    setMerge(null);
+    setError(null);
+    mergeTexts(baseContent, localContent, diskContent)
+      .then((result) => {
+        if (cancelled) return;
+        setMerge(result);
+        setPicks(new Array(result.conflicts).fill(null));
+      })
+      .catch((err) => {
+        if (!cancelled) setError(String(err?.message || err));
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [baseContent, localContent, diskContent]);
+
+  const unresolved = picks.filter((pick) => !pick).length;
+  const merged = useMemo(
+    () => (merge && unresolved === 0 ? resolveMerge(merge.chunks, picks) : null),
+    [merge, picks, unresolved]
+  );
+
+  if (error) {
+    return (
+      <div className="flex-1 flex items-center justify-center py-16 px-6 text-sm text-red-300 text-center">
+        {error}
+      </div>
+    );
+  }
+  if (!merge) {
+    return (
+      <div className="flex-1 flex items-center justify-center py-16 text-sm text-text-muted">
+        Merging…
+      </div>
+    );
+  }
+
+  let conflictIndex = -1;
+  return (
+    <>
+      <div className="px-5 py-3 border-b border-border bg-overlay-subtle/40 shrink-0 flex items-center justify-between gap-3">
+        <p className="text-xs text-text-secondary">
+          {merge.conflicts === 0
+            ? "Your edits and the disk changes don't overlap, so they merge cleanly."
+            : unresolved > 0
+              ? `${unresolved} of ${merge.conflicts} conflicting ${merge.conflicts === 1 ? "change needs" : "changes need"} a choice.`
+              : "Every conflict has a choice."}
+        </p>
+        <button
+          onClick={() => onUseMerged(merged)}
+          disabled={merged === null}
+          className="px-3 py-1.5 text-xs rounded-md bg-accent text-white hover:bg-accent-hover transition-colors font-medium disabled:opacity-50 disabled:cursor-not-allowed shrink-0"
+        >
+          Use Merged Version
+        </button>
+      </div>
+      <div className="flex-1 overflow-auto custom-scrollbar p-4 space-y-2">
+        {merge.chunks.map((chunk, index) => {
+          if (chunk.kind === "resolved") {
+            return (
+              <pre
+                key={index}
+                className="px-3 py-1 text-xs font-mono whitespace-pre-wrap break-words leading-relaxed text-text-secondary"
+              >
+                {chunk.text}
+              </pre>
+            );
+          }
+          conflictIndex += 1;
+          const current = conflictIndex;
+          const pick = picks[current];
+          return (
+            <div key={index} className="rounded-lg border border-amber-500/30 overflow-hidden">
+              <div className="grid grid-cols-2">
+                <div className="border-r border-border/70">
+                  <p className="px-3 py-1 text-[11px] font-semibold text-text-muted bg-black/10">
+                    Your Draft
+                  </p>
+                  <pre
+                    className={`px-3 py-1 text-xs font-mono whitespace-pre-wrap break-words leading-relaxed ${pick === "ours" || pick === "both" ? "bg-green-500/10 text-green-100" : "bg-red-500/10 text-red-100"}`}
+                  >
+                    {chunk.ours || "\u00A0"}
+                  </pre>
+                </div>
+                <div>
+                  <p className="px-3 py-1 text-[11px] font-semibold text-text-muted bg-black/10">
+                    Disk Version
+                  </p>
+                  <pre
+                    className={`px-3 py-1 text-xs font-mono whitespace-pre-wrap break-words leading-relaxed ${pick === "theirs" || pick === "both" ? "bg-green-500/10 text-green-100" : "bg-red-500/10 text-red-100"}`}
+                  >
+                    {chunk.theirs || "\u00A0"}
+                  </pre>
+                </div>
+              </div>
+              <div className="flex items-center justify-end gap-2 px-3 py-2 border-t border-border/40 bg-amber-500/5">
+                {PICKS.map(([value, label]) => (
+                  <button
+                    key={value}
+                    onClick={() =>
+                      setPicks((previous) =>
+                        previous.map((existing, idx) => (idx === current ? value : existing))
+                      )
+                    }
+                    aria-pressed={pick === value}
+                    className={`px-2.5 py-1 text-xs rounded-md border transition-colors ${
+                      pick === value
+                        ? "border-accent bg-accent/20 text-text-primary"
+                        : "border-overlay-light bg-overlay-subtle text-text-secondary hover:bg-overlay-light"
+                    }`}
+                  >
+                    {label}
+                  </button>
+                ))}
+              </div>
+            </div>
+          );
+        })}
+      </div>
+    </>
+  );
+};
+
+export default ConflictMergePanel;
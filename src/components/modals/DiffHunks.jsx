@@ -0,0 +1,89 @@
+import { useMemo } from "react";
+
+// Side-by-side rows for one hunk: unchanged lines sit on both sides, and each
+// run of deleted lines is paired with the inserted run that follows it
+const buildRows = (lines) => {
+  const rows = [];
+  let deleted = [];
+  let inserted = [];
+  const flush = () => {
+    for (let i = 0; i < Math.max(deleted.length, inserted.length); i++) {
+      rows.push({ type: "change", old: deleted[i] || null, new: inserted[i] || null });
+    }
+    deleted = [];
+    inserted = [];
+  };
+  lines.forEach((line) => {
+    if (line.kind === "delete") {
+      if (inserted.length > 0) flush();
+      deleted.push(line);
+    } else if (line.kind === "insert") {
+      inserted.push(line);
+    } else {
+      flush();
+      rows.push({ type: "equal", old: line, new: line });
+    }
+  });
+  flush();
+  return rows;
+};
+
+const Segments = ({ line, side }) => {
+  if (!line || line.segments.length === 0) return "\u00A0";
+  const highlight = side === "old" ? "bg-red-500/30 text-red-50" : "bg-green-500/30 text-green-50";
+  return line.segments.map((segment, index) => (
+    <span key={index} className={segment.changed ? `${highlight} rounded-sm` : undefined}>
+      {segment.text}
+    </span>
+  ));
+};
+
+const cellClass = (row, side) => {
+  if (row.type === "equal") return "bg-transparent";
+  const line = row[side];
+  if (!line) return "bg-overlay-subtle/50";
+  return side === "old" ? "bg-red-500/10 text-red-100" : "bg-green-500/10 text-green-100";
+};
+
+const Cell = ({ row, side, className = "" }) => {
+  const line = row[side];
+  const number = side === "old" ? line?.old_line : line?.new_line;
+  return (
+    <div className={`grid grid-cols-[3rem_1fr] ${cellClass(row, side)} ${className}`}>
+      <div className="px-2 py-1 text-right text-[11px] text-text-muted bg-black/10 select-none">
+        {number ?? ""}
+      </div>
+      <pre className="px-3 py-1 text-xs font-mono whitespace-pre-wrap break-words leading-relaxed">
+        <Segments line={line} side={side} />
+      </pre>
+    </div>
+  );
+};
+
+// Hunks from the backend diff, old text on the left
+const DiffHunks = ({ hunks }) => {
+  const rows = useMemo(() => hunks.map((hunk) => buildRows(hunk.lines)), [hunks]);
+
+  return (
+    <div className="flex-1 overflow-auto custom-scrollbar">
+      <div className="min-w-[900px]">
+        {hunks.map((hunk, hunkIndex) => (
+          <div key={hunkIndex}>
+            <div className="px-4 py-1 text-[11px] font-mono text-text-muted bg-overlay-subtle/60 border-b border-border/40">
+              Lines {hunk.old_start}–{hunk.old_start + Math.max(hunk.old_lines, 1) - 1} ·{" "}
+              {hunk.new_start}–{hunk.new_start + Math.max(hunk.new_lines, 1) - 1}
+            </div>
+            {rows[hunkIndex].map((row, rowIndex) => (
+              <div key={rowIndex} className="grid grid-cols-2 border-b border-border/40">
+                <Cell row={row} side="old" className="border-r border-border/70" />
+                <Cell row={row} side="new" />
+              </div>
+            ))}
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+};
+
+export default DiffHunks;
@@ -0,0 +1,169 @@
+import { useEffect, useRef, useState } from "react";
+import useSettingsStore from "../../store/settingsStore";
+import useUIStore from "../../store/uiStore";
+import { emailNote } from "../../utils/email";
+import useModalAccessibility from "../../hooks/useModalAccessibility";
+
+const FORMATS = [
+  { id: "html", label: "Formatted", description: "The rendered note as the message body" },
+  { id: "markdown", label: "Plain text", description: "The markdown source as the message body" },
+  { id: "attachment", label: "Attached", description: "The note attached as .md and .html" },
+];
+
+const EmailNoteModal = ({ isOpen, note, onClose }) => {
+  const { addNotification } = useUIStore();
+  const method = useSettingsStore((state) => state.emailSettings.method);
+  const dialogRef = useRef(null);
+  const [recipients, setRecipients] = useState("");
+  const [format, setFormat] = useState("html");
+  const [isSending, setIsSending] = useState(false);
+  useModalAccessibility(isOpen, dialogRef);
+
+  useEffect(() => {
+    const handleKeyDown = (e) => {
+      if (e.key === "Escape" && isOpen) {
+        onClose();
+      }
+    };
+
+    window.addEventListener("keydown", handleKeyDown);
+    return () => window.removeEventListener("keydown", handleKeyDown);
+  }, [isOpen, onClose]);
+
+  if (!isOpen || !note) return null;
+
+  const sendsDirectly = method === "smtp";
+  const to = recipients
+    .split(/[,;\n]/)
+    .map((address) => address.trim())
+    .filter(Boolean);
+
+  const handleSend = async () => {
+    setIsSending(true);
+    try {
+      const result = await emailNote(note.filePath, note.name, note.content, format, to);
+      const attached = result.attachments.length;
+      const suffix = attached > 0 ? ` with ${attached} attachment${attached !== 1 ? "s" : ""}` : "";
+      addNotification(
+        sendsDirectly ? `Email sent${suffix}` : `Draft opened in your mail app${suffix}`,
+        "success"
+      );
+      if (result.skipped_attachments.length > 0) {
+        addNotification(
+          `Left out ${result.skipped_attachments.join(", ")} to keep the message small enough`,
+          "warning",
+          6000
+        );
+      }
+      onClose();
+    } catch (error) {
+      addNotification("Failed to email note: " + error, "error");
+    } finally {
+      setIsSending(false);
+    }
+  };
+
+  return (
+    <>
+      {/* Backdrop */}
+      <div
+        className="fixed inset-0 bg-black/60 backdrop-blur-sm z-50"
+        onClick={onClose}
+        aria-hidden="true"
+      />
+
+      {/* Modal */}
+      <div className="fixed inset-0 z-50 flex items-center justify-center p-4 pointer-events-none">
+        <div
+          ref={dialogRef}
+          className="glass-panel border-glass-border rounded-xl shadow-2xl w-full max-w-lg flex flex-col pointer-events-auto"
+          onClick={(e) => e.stopPropagation()}
+          role="dialog"
+          aria-modal="true"
+          aria-labelledby="email-note-modal-title"
+          tabIndex={-1}
+        >
+          {/* Header */}
+          <div className="border-b border-glass-border px-6 py-4">
+            <h2
+              id="email-note-modal-title"
+              className="text-xl font-semibold text-text-primary flex items-center gap-2"
+            >
+              <svg className="w-6 h-6" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                <path
+                  strokeLinecap="round"
+                  strokeLinejoin="round"
+                  strokeWidth={2}
+                  d="M3 8l7.89 5.26a2 2 0 002.22 0L21 8M5 19h14a2 2 0 002-2V7a2 2 0 00-2-2H5a2 2 0 00-2 2v10a2 2 0 002 2z"
+                />
+              </svg>
+              Email Note
+            </h2>
+            <p className="text-sm text-text-muted mt-1 truncate">
+              {note.name}
+              {sendsDirectly ? " · sent over SMTP" : " · opens as a draft in your mail app"}
+            </p>
+          </div>
+
+          {/* Content */}
+          <div className="p-6 space-y-4">
+            <label className="block text-sm text-text-secondary space-y-1">
+              <span>To{sendsDirectly ? "" : " (optional)"}</span>
+              <input
+                type="text"
+                value={recipients}
+                onChange={(e) => setRecipients(e.target.value)}
+                placeholder="alex@example.com, sam@example.com"
+                autoFocus
+                className="w-full px-3 py-2 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent"
+              />
+            </label>
+            <div className="space-y-2">
+              {FORMATS.map((option) => (
+                <label
+                  key={option.id}
+                  className="flex items-start gap-3 px-3 py-2 rounded-lg hover:bg-overlay-subtle cursor-pointer"
+                >
+                  <input
+                    type="radio"
+                    name="email-format"
+                    checked={format === option.id}
+                    onChange={() => setFormat(option.id)}
+                    className="mt-1"
+                  />
+                  <span>
+                    <span className="block text-sm text-text-primary">{option.label}</span>
+                    <span className="block text-xs text-text-muted">{option.description}</span>
+                  </span>
+                </label>
+              ))}
+            </div>
+            <p className="text-xs text-text-muted">
+              Images and files the note links to are attached. The saved version of the note is
+              sent.
+            </p>
+          </div>
+
+          {/* Footer */}
+          <div className="border-t border-glass-border px-6 py-4 flex justify-end gap-2">
+            <button
+              onClick={onClose}
+              className="px-4 py-2 text-sm font-medium text-text-secondary hover:bg-overlay-light rounded-lg transition-colors"
+            >
+              Cancel
+            </button>
+            <button
+              onClick={handleSend}
+              disabled={isSending || (sendsDirectly && to.length === 0)}
+              className="px-4 py-2 text-sm font-medium bg-accent text-white hover:bg-accent-hover rounded-lg transition-colors shadow-lg shadow-accent/20 disabled:opacity-50"
+            >
+              {isSending ? "Sending…" : sendsDirectly ? "Send" : "Open Draft"}
+            </button>
+          </div>
+        </div>
+      </div>
+    </>
+  );
+};
+
+export default EmailNoteModal;
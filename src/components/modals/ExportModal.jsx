@@ -1,26 +1,103 @@
-import { useRef, useState } from "react";
+import { useEffect, useRef, useState } from "react";
 import { marked } from "marked";
 import useUIStore from "../../store/uiStore";
 import { writeText } from "@tauri-apps/plugin-clipboard-manager";
 import { save } from "@tauri-apps/plugin-dialog";
 import { writeTextFile } from "@tauri-apps/plugin-fs";
-import { buildStandaloneHtml, exportMarkdownToPdf } from "../../utils/noteExport";
+import {
+  buildStandaloneHtml,
+  exportMarkdownToPdf,
+  exportNote,
+  getExportFormats,
+} from "../../utils/noteExport";
+import { ensureDiskSpace } from "../../utils/fileSystem";
+import { composeNote } from "../../utils/embeds";
+import { dragNoteOut } from "../../utils/share";
+import { recordFeatureUse } from "../../utils/telemetry";
 import useModalAccessibility from "../../hooks/useModalAccessibility";
 
 const ExportModal = ({ isOpen, onClose, note }) => {
   const [exportFormat, setExportFormat] = useState("html");
   const [isExporting, setIsExporting] = useState(false);
+  const [isPreparingDrag, setIsPreparingDrag] = useState(false);
+  const mouseHeldRef = useRef(false);
+  const [nativeFormats, setNativeFormats] = useState([]);
   const { addNotification } = useUIStore();
   const dialogRef = useRef(null);
   useModalAccessibility(isOpen, dialogRef);
 
+  useEffect(() => {
+    if (!isOpen) return;
+    getExportFormats()
+      .then(setNativeFormats)
+      .catch(() => setNativeFormats([]));
+  }, [isOpen]);
+
   if (!isOpen || !note) return null;
 
+  // Embedded notes are inlined so the export reads as one document
+  const exportContent = async () => {
+    const content = note.content || "";
+    if (!note.filePath) return content;
+    try {
+      return await composeNote(note.filePath, content);
+    } catch {
+      return content;
+    }
+  };
+
+  // Saved notes render in the backend, which resolves their images and links
+  // next to the exported file; PDFs fall back to pdf-lib where the webview
+  // can't print to a file
+  const exportsNatively = (format) =>
+    Boolean(note.filePath) && nativeFormats.includes(format);
+
+  const handleNativeExport = async (format) => {
+    const result = await exportNote(note.filePath, format, note.content ?? null);
+    if (result) {
+      addNotification("Exported successfully!", "success");
+      onClose();
+    }
+  };
+
+  // File formats can be dragged straight into another app instead of saved
+  const canDragOut = Boolean(note.filePath) && exportFormat !== "copy-html";
+
+  // Rendering a PDF takes a moment; the drag only starts if the button is
+  // still held once the export is ready
+  const handleDragOut = async (e) => {
+    if (e.button !== 0 || isPreparingDrag) return;
+    e.preventDefault();
+    mouseHeldRef.current = true;
+    const release = () => {
+      mouseHeldRef.current = false;
+    };
+    document.addEventListener("mouseup", release, { once: true });
+    setIsPreparingDrag(true);
+    try {
+      const content = exportFormat === "markdown" ? null : await exportContent();
+      if (!mouseHeldRef.current) return;
+      document.removeEventListener("mouseup", release);
+      await dragNoteOut(note.filePath, note.name, content, exportFormat);
+    } catch (error) {
+      console.error("Drag out failed:", error);
+      addNotification("Drag out failed: " + (error?.message || error), "error");
+    } finally {
+      setIsPreparingDrag(false);
+    }
+  };
+
   const handleExport = async () => {
     setIsExporting(true);
+    recordFeatureUse("export_note");
     try {
+      if (exportsNatively(exportFormat)) {
+        await handleNativeExport(exportFormat);
+        return;
+      }
+      const content = await exportContent();
       if (exportFormat === "html") {
-        const html = buildStandaloneHtml(note.name, note.content || "", marked);
+        const html = buildStandaloneHtml(note.name, content, marked);
         const filePath = await save({
           defaultPath: `${note.name}.html`,
           filters: [
@@ -32,18 +109,19 @@ const ExportModal = ({ isOpen, onClose, note }) => {
         });
 
         if (filePath) {
+          await ensureDiskSpace(filePath, new TextEncoder().encode(html).byteLength);
           await writeTextFile(filePath, html);
           addNotification("Exported successfully!", "success");
           onClose();
         }
       } else if (exportFormat === "pdf") {
-        const filePath = await exportMarkdownToPdf(note.name, note.content || "");
+        const filePath = await exportMarkdownToPdf(note.name, content);
         if (filePath) {
           addNotification("PDF exported successfully!", "success");
           onClose();
         }
       } else if (exportFormat === "copy-html") {
-        const htmlContent = marked(note.content || "");
+        const htmlContent = marked(content);
         await writeText(htmlContent);
         addNotification("Formatted HTML copied to clipboard!", "success");
         onClose();
@@ -59,7 +137,8 @@ const ExportModal = ({ isOpen, onClose, note }) => {
         });
 
         if (filePath) {
-          await writeTextFile(filePath, note.content || "");
+          await ensureDiskSpace(filePath, new TextEncoder().encode(content).byteLength);
+          await writeTextFile(filePath, content);
           addNotification("Exported successfully!", "success");
           onClose();
         }
@@ -279,10 +358,33 @@ const ExportModal = ({ isOpen, onClose, note }) => {
           </div>
 
           {/* Footer */}
-          <div className="border-t border-glass-border px-6 py-4 flex justify-end gap-2">
+          <div className="border-t border-glass-border px-6 py-4 flex items-center gap-2">
+            {canDragOut && (
+              <div
+                onMouseDown={handleDragOut}
+                className={`mr-auto flex items-center gap-2 px-3 py-2 text-sm rounded-lg border border-dashed border-overlay-light select-none transition-colors ${
+                  isPreparingDrag
+                    ? "text-text-muted cursor-progress"
+                    : "text-text-secondary hover:text-text-primary hover:bg-overlay-light cursor-grab"
+                }`}
+                title="Hold and drag to drop the exported file into Finder, Explorer or a mail message"
+                role="button"
+                aria-label="Drag exported file out"
+              >
+                <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M7 11.5V14m0-2.5v-6a1.5 1.5 0 113 0m-3 6a1.5 1.5 0 00-3 0v2a7.5 7.5 0 0015 0v-5a1.5 1.5 0 00-3 0m-6-3V11m0-5.5v-1a1.5 1.5 0 013 0v1m0 0V11m0-5.5a1.5 1.5 0 013 0v3m0 0V11"
+                  />
+                </svg>
+                {isPreparingDrag ? "Preparing..." : "Drag out"}
+              </div>
+            )}
             <button
               onClick={onClose}
-              className="px-4 py-2 text-sm text-text-secondary hover:text-text-primary hover:bg-overlay-light rounded-lg transition-colors"
+              className="ml-auto px-4 py-2 text-sm text-text-secondary hover:text-text-primary hover:bg-overlay-light rounded-lg transition-colors"
             >
               Cancel
             </button>
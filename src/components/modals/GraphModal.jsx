@@ -1,7 +1,8 @@
 import { useMemo, useState, useCallback, useRef, useEffect } from "react";
 import useNotesStore from "../../store/notesStore";
 import useUIStore from "../../store/uiStore";
-import { buildGraphSvg, saveGraphPng, saveGraphSvg } from "../../utils/graphExport";
+import { buildGraphSvg, saveGraphData, saveGraphPng, saveGraphSvg } from "../../utils/graphExport";
+import { recordFeatureUse } from "../../utils/telemetry";
 import useModalAccessibility from "../../hooks/useModalAccessibility";
 
 const escapeTitle = (value = "") => value.replace(/\s+/g, " ").trim();
@@ -23,7 +24,7 @@ const extractWikiLinks = (content) => {
     if (!inner) continue;
 
     const [targetRaw] = inner.split("|");
-    const target = (targetRaw || "").trim();
+    const target = (targetRaw || "").split("#")[0].trim();
     if (!target) continue;
 
     const normalized = linkTargetKey(target);
@@ -194,6 +195,7 @@ const GraphModal = ({ isOpen, onClose }) => {
   const items = useNotesStore((state) => state.items);
   const selectNote = useNotesStore((state) => state.selectNote);
   const currentNoteId = useNotesStore((state) => state.currentNoteId);
+  const rootFolderPath = useNotesStore((state) => state.rootFolderPath);
   const addNotification = useUIStore((state) => state.addNotification);
 
   const [hoveredNode, setHoveredNode] = useState(null);
@@ -405,6 +407,7 @@ const GraphModal = ({ isOpen, onClose }) => {
       setTransform({ x: 0, y: 0, k: 1 });
       setHoveredNode(null);
       setFilter("all");
+      recordFeatureUse("graph");
     }
   }, [isOpen]);
 
@@ -455,6 +458,29 @@ const GraphModal = ({ isOpen, onClose }) => {
 
   const handleExport = useCallback(
     async (format) => {
+      // DOT and GraphML carry the whole workspace for other tools, whatever the filter
+      if (format === "dot" || format === "graphml") {
+        if (!rootFolderPath) {
+          addNotification("Open a folder to export its graph", "warning");
+          return;
+        }
+        setExportingFormat(format);
+        try {
+          const filePath = await saveGraphData(rootFolderPath, format);
+          if (filePath) {
+            addNotification(
+              `Graph exported as ${format === "dot" ? "DOT" : "GraphML"}`,
+              "success"
+            );
+          }
+        } catch (error) {
+          addNotification(`Graph export failed: ${error?.message || error}`, "error");
+        } finally {
+          setExportingFormat(null);
+        }
+        return;
+      }
+
       if (nodes.length === 0) {
         addNotification("There is no graph data to export", "warning");
         return;
@@ -488,7 +514,7 @@ const GraphModal = ({ isOpen, onClose }) => {
         setExportingFormat(null);
       }
     },
-    [addNotification, currentNoteId, edges, filter, nodes]
+    [addNotification, currentNoteId, edges, filter, nodes, rootFolderPath]
   );
 
   if (!isOpen) return null;
@@ -618,6 +644,34 @@ const GraphModal = ({ isOpen, onClose }) => {
                 >
                   {exportingFormat === "png" ? "Saving..." : "PNG"}
                 </button>
+                <button
+                  onClick={() => handleExport("dot")}
+                  disabled={exportingFormat !== null}
+                  className={`px-2.5 py-1 text-[10px] font-bold uppercase tracking-wider rounded-md transition-all ${
+                    exportingFormat === "dot"
+                      ? "bg-accent text-white"
+                      : exportingFormat
+                        ? "text-text-muted cursor-not-allowed"
+                        : "text-text-secondary hover:text-text-primary hover:bg-overlay-light"
+                  }`}
+                  title="Export the whole note graph as Graphviz DOT"
+                >
+                  {exportingFormat === "dot" ? "Saving..." : "DOT"}
+                </button>
+                <button
+                  onClick={() => handleExport("graphml")}
+                  disabled={exportingFormat !== null}
+                  className={`px-2.5 py-1 text-[10px] font-bold uppercase tracking-wider rounded-md transition-all ${
+                    exportingFormat === "graphml"
+                      ? "bg-accent text-white"
+                      : exportingFormat
+                        ? "text-text-muted cursor-not-allowed"
+                        : "text-text-secondary hover:text-text-primary hover:bg-overlay-light"
+                  }`}
+                  title="Export the whole note graph as GraphML"
+                >
+                  {exportingFormat === "graphml" ? "Saving..." : "GraphML"}
+                </button>
               </div>
 
               <button
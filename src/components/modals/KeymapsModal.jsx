@@ -1,9 +1,5 @@
 import { useEffect, useRef } from "react";
-import useSettingsStore, {
-  formatKeymap,
-  DEFAULT_KEYMAPS,
-  KEYMAP_CATEGORIES,
-} from "../../store/settingsStore";
+import useSettingsStore, { formatKeymap, KEYMAP_CATEGORIES } from "../../store/settingsStore";
 import useModalAccessibility from "../../hooks/useModalAccessibility";
 
 const KeymapsModal = ({ isOpen, onClose }) => {
@@ -24,14 +20,7 @@ const KeymapsModal = ({ isOpen, onClose }) => {
 
   if (!isOpen) return null;
 
-  const isModified = (actionId) => {
-    const current = keymaps[actionId];
-    const original = DEFAULT_KEYMAPS[actionId];
-    return (
-      current.key !== original.key ||
-      JSON.stringify(current.modifiers) !== JSON.stringify(original.modifiers)
-    );
-  };
+  const isModified = (actionId) => Boolean(keymaps[actionId]?.customized);
 
   return (
     <>
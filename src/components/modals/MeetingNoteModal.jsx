@@ -0,0 +1,176 @@
+import { useEffect, useRef, useState } from "react";
+import useNotesStore from "../../store/notesStore";
+import useUIStore from "../../store/uiStore";
+import { listTodaysEvents, createNoteFromEvent } from "../../utils/calendar";
+import useModalAccessibility from "../../hooks/useModalAccessibility";
+
+const formatEventTime = (event) => {
+  if (event.all_day) return "All day";
+  const clock = (value) => value?.split("T")[1] || "";
+  return event.end ? `${clock(event.start)} – ${clock(event.end)}` : clock(event.start);
+};
+
+const MeetingNoteModal = ({ isOpen, onClose }) => {
+  const { addNotification } = useUIStore();
+  const dialogRef = useRef(null);
+  const [events, setEvents] = useState([]);
+  const [isLoading, setIsLoading] = useState(false);
+  const [creatingId, setCreatingId] = useState(null);
+  useModalAccessibility(isOpen, dialogRef);
+
+  useEffect(() => {
+    if (!isOpen) return;
+    setIsLoading(true);
+    listTodaysEvents()
+      .then(setEvents)
+      .catch((error) => addNotification("Failed to load calendar: " + error, "error"))
+      .finally(() => setIsLoading(false));
+  }, [isOpen, addNotification]);
+
+  useEffect(() => {
+    const handleKeyDown = (e) => {
+      if (e.key === "Escape" && isOpen) {
+        onClose();
+      }
+    };
+
+    window.addEventListener("keydown", handleKeyDown);
+    return () => window.removeEventListener("keydown", handleKeyDown);
+  }, [isOpen, onClose]);
+
+  if (!isOpen) return null;
+
+  const handleCreate = async (event) => {
+    const { rootFolderPath, items, customTemplates, getCurrentNote, refreshRootFromDisk, selectNote } =
+      useNotesStore.getState();
+    if (!rootFolderPath) {
+      addNotification("No workspace folder is open", "warning");
+      return;
+    }
+
+    // Next to the open note, like other new notes
+    const parentId = getCurrentNote()?.parentId;
+    const parentFolderPath = items.find((item) => item.id === parentId)?.filePath || null;
+    const template = customTemplates.find((t) => /meeting/i.test(t.name))?.content || null;
+
+    setCreatingId(event.id);
+    try {
+      const path = await createNoteFromEvent(event.id, rootFolderPath, parentFolderPath, template);
+      await refreshRootFromDisk();
+      const note = useNotesStore
+        .getState()
+        .items.find((item) => item.type === "note" && item.filePath === path);
+      if (note) selectNote(note.id);
+      addNotification(`Created meeting note for ${event.title}`, "success");
+      onClose();
+    } catch (error) {
+      addNotification("Failed to create meeting note: " + error, "error");
+    } finally {
+      setCreatingId(null);
+    }
+  };
+
+  return (
+    <>
+      {/* Backdrop */}
+      <div
+        className="fixed inset-0 bg-black/60 backdrop-blur-sm z-50"
+        onClick={onClose}
+        aria-hidden="true"
+      />
+
+      {/* Modal */}
+      <div className="fixed inset-0 z-50 flex items-center justify-center p-4 pointer-events-none">
+        <div
+          ref={dialogRef}
+          className="glass-panel border-glass-border rounded-xl shadow-2xl w-full max-w-lg max-h-[80vh] flex flex-col pointer-events-auto"
+          onClick={(e) => e.stopPropagation()}
+          role="dialog"
+          aria-modal="true"
+          aria-labelledby="meeting-note-modal-title"
+          tabIndex={-1}
+        >
+          {/* Header */}
+          <div className="border-b border-glass-border px-6 py-4 flex items-center justify-between shrink-0">
+            <div>
+              <h2
+                id="meeting-note-modal-title"
+                className="text-xl font-semibold text-text-primary flex items-center gap-2"
+              >
+                <svg className="w-6 h-6" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M8 7V3m8 4V3m-9 8h10M5 21h14a2 2 0 002-2V7a2 2 0 00-2-2H5a2 2 0 00-2 2v12a2 2 0 002 2z"
+                  />
+                </svg>
+                Today&apos;s Meetings
+              </h2>
+              <p className="text-sm text-text-muted mt-1">
+                Pick an event to start a meeting note from your meeting template.
+              </p>
+            </div>
+            <button
+              onClick={onClose}
+              className="p-2 hover:bg-overlay-light rounded-lg transition-colors"
+              title="Close (Esc)"
+            >
+              <svg
+                className="w-5 h-5 text-text-secondary"
+                fill="none"
+                stroke="currentColor"
+                viewBox="0 0 24 24"
+              >
+                <path
+                  strokeLinecap="round"
+                  strokeLinejoin="round"
+                  strokeWidth={2}
+                  d="M6 18L18 6M6 6l12 12"
+                />
+              </svg>
+            </button>
+          </div>
+
+          {/* Content */}
+          <div className="flex-1 overflow-y-auto p-4 custom-scrollbar">
+            {isLoading ? (
+              <p className="text-sm text-text-muted text-center py-8">Loading calendar…</p>
+            ) : events.length === 0 ? (
+              <p className="text-sm text-text-muted text-center py-8">
+                No events today. Calendar subscriptions are set up in Settings → Calendar.
+              </p>
+            ) : (
+              <div className="space-y-2">
+                {events.map((event) => (
+                  <button
+                    key={event.id}
+                    onClick={() => handleCreate(event)}
+                    disabled={creatingId !== null}
+                    className="w-full text-left px-4 py-3 rounded-lg bg-overlay-subtle border border-overlay-light hover:bg-overlay-light transition-colors disabled:opacity-50"
+                  >
+                    <div className="flex items-center justify-between gap-3">
+                      <span className="text-sm font-medium text-text-primary truncate">
+                        {event.title}
+                      </span>
+                      <span className="text-xs text-text-muted shrink-0">
+                        {creatingId === event.id ? "Creating…" : formatEventTime(event)}
+                      </span>
+                    </div>
+                    {(event.location || event.attendees.length > 0) && (
+                      <p className="text-xs text-text-muted mt-1 truncate">
+                        {[event.location, event.attendees.join(", ")].filter(Boolean).join(" · ")}
+                      </p>
+                    )}
+                  </button>
+                ))}
+              </div>
+            )}
+          </div>
+        </div>
+      </div>
+    </>
+  );
+};
+
+export default MeetingNoteModal;
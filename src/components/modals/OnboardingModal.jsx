@@ -1,5 +1,5 @@
 import { useState } from "react";
-import { openFolder } from "../../utils/fileSystem";
+import { createSampleWorkspace, openFolder } from "../../utils/fileSystem";
 import useNotesStore from "../../store/notesStore";
 import useUIStore from "../../store/uiStore";
 
@@ -7,6 +7,7 @@ function OnboardingModal({ onSkip }) {
   const { loadFolderFromSystem } = useNotesStore();
   const { addNotification } = useUIStore();
   const [isLoading, setIsLoading] = useState(false);
+  const [isCreatingSample, setIsCreatingSample] = useState(false);
 
   const handleSelectWorkspace = async () => {
     try {
@@ -23,6 +24,21 @@ function OnboardingModal({ onSkip }) {
     }
   };
 
+  const handleCreateSample = async () => {
+    try {
+      setIsCreatingSample(true);
+      const folderData = await createSampleWorkspace();
+      if (folderData) {
+        await loadFolderFromSystem(folderData);
+      }
+    } catch (error) {
+      console.error("Failed to create sample workspace:", error);
+      addNotification("Failed to create sample workspace: " + error.message, "error");
+    } finally {
+      setIsCreatingSample(false);
+    }
+  };
+
   return (
     <div className="fixed inset-0 bg-black/60 backdrop-blur-sm flex items-center justify-center z-50">
       <div className="glass-panel border-glass-border rounded-lg shadow-2xl max-w-2xl w-full mx-4 p-8 animate-in zoom-in-95 duration-200">
@@ -45,7 +61,7 @@ function OnboardingModal({ onSkip }) {
 
           <button
             onClick={handleSelectWorkspace}
-            disabled={isLoading}
+            disabled={isLoading || isCreatingSample}
             className="group flex items-center gap-4 px-5 py-4 bg-bg-editor hover:bg-bg-sidebar border border-border hover:border-accent rounded-lg transition-all text-left disabled:opacity-60 w-full"
           >
             <div className="shrink-0 w-12 h-12 bg-accent/20 rounded-lg flex items-center justify-center group-hover:bg-accent/30 transition-colors">
@@ -73,6 +89,37 @@ function OnboardingModal({ onSkip }) {
               </p>
             </div>
           </button>
+
+          <button
+            onClick={handleCreateSample}
+            disabled={isLoading || isCreatingSample}
+            className="group flex items-center gap-4 px-5 py-4 mt-3 bg-bg-editor hover:bg-bg-sidebar border border-border hover:border-accent rounded-lg transition-all text-left disabled:opacity-60 w-full"
+          >
+            <div className="shrink-0 w-12 h-12 bg-accent/20 rounded-lg flex items-center justify-center group-hover:bg-accent/30 transition-colors">
+              <svg
+                className="w-6 h-6 text-accent"
+                fill="none"
+                stroke="currentColor"
+                viewBox="0 0 24 24"
+              >
+                <path
+                  strokeLinecap="round"
+                  strokeLinejoin="round"
+                  strokeWidth={2}
+                  d="M12 6.253v13m0-13C10.832 5.477 9.246 5 7.5 5S4.168 5.477 3 6.253v13C4.168 18.477 5.754 18 7.5 18s3.332.477 4.5 1.253m0-13C13.168 5.477 14.754 5 16.5 5c1.747 0 3.332.477 4.5 1.253v13C19.832 18.477 18.247 18 16.5 18c-1.746 0-3.332.477-4.5 1.253"
+                />
+              </svg>
+            </div>
+            <div className="flex-1">
+              <h3 className="text-text-primary font-semibold mb-1">
+                {isCreatingSample ? "Creating…" : "Try a Sample Workspace"}
+              </h3>
+              <p className="text-text-muted text-sm">
+                Creates a small “Marky Sample” folder with linked notes, tags, tasks, a daily note,
+                templates, and an attachment to explore first.
+              </p>
+            </div>
+          </button>
         </div>
 
         {/* Tips */}
@@ -0,0 +1,131 @@
+import { useEffect, useRef, useState } from "react";
+import useModalAccessibility from "../../hooks/useModalAccessibility";
+
+const PassphraseModal = ({
+  isOpen,
+  title,
+  message,
+  confirmLabel = "Continue",
+  requireConfirmation = false,
+  onSubmit,
+  onCancel,
+}) => {
+  const dialogRef = useRef(null);
+  const [passphrase, setPassphrase] = useState("");
+  const [confirmation, setConfirmation] = useState("");
+  const [error, setError] = useState("");
+  const [isBusy, setIsBusy] = useState(false);
+  useModalAccessibility(isOpen, dialogRef);
+
+  useEffect(() => {
+    if (!isOpen) return;
+    setPassphrase("");
+    setConfirmation("");
+    setError("");
+    setIsBusy(false);
+  }, [isOpen]);
+
+  useEffect(() => {
+    const handleKeyDown = (e) => {
+      if (isOpen && e.key === "Escape") {
+        onCancel();
+      }
+    };
+
+    window.addEventListener("keydown", handleKeyDown);
+    return () => window.removeEventListener("keydown", handleKeyDown);
+  }, [isOpen, onCancel]);
+
+  if (!isOpen) return null;
+
+  const handleSubmit = async (e) => {
+    e.preventDefault();
+    if (!passphrase) {
+      setError("Enter a passphrase");
+      return;
+    }
+    if (requireConfirmation && passphrase !== confirmation) {
+      setError("Passphrases don’t match");
+      return;
+    }
+
+    setIsBusy(true);
+    try {
+      await onSubmit(passphrase);
+    } catch (submitError) {
+      setError(submitError.message || "Something went wrong");
+      setIsBusy(false);
+    }
+  };
+
+  const inputClass =
+    "w-full px-3 py-2 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent";
+
+  return (
+    <>
+      <div
+        className="fixed inset-0 bg-black/60 backdrop-blur-sm z-100"
+        onClick={onCancel}
+        aria-hidden="true"
+      />
+
+      <div className="fixed inset-0 z-100 flex items-center justify-center p-4 pointer-events-none">
+        <form
+          ref={dialogRef}
+          onSubmit={handleSubmit}
+          className="glass-panel border-glass-border rounded-xl shadow-2xl w-full max-w-md pointer-events-auto p-6"
+          onClick={(e) => e.stopPropagation()}
+          role="dialog"
+          aria-modal="true"
+          aria-labelledby="passphrase-dialog-title"
+          tabIndex={-1}
+        >
+          <h3 id="passphrase-dialog-title" className="text-lg font-semibold text-text-primary">
+            {title}
+          </h3>
+          {message && <p className="mt-2 text-sm text-text-secondary">{message}</p>}
+
+          <div className="mt-4 space-y-3">
+            <input
+              type="password"
+              className={inputClass}
+              placeholder="Passphrase"
+              value={passphrase}
+              onChange={(e) => setPassphrase(e.target.value)}
+              autoFocus
+            />
+            {requireConfirmation && (
+              <input
+                type="password"
+                className={inputClass}
+                placeholder="Confirm passphrase"
+                value={confirmation}
+                onChange={(e) => setConfirmation(e.target.value)}
+              />
+            )}
+            {error && <p className="text-xs text-red-400">{error}</p>}
+          </div>
+
+          <div className="flex justify-end gap-3 mt-6">
+            <button
+              type="button"
+              onClick={onCancel}
+              className="px-4 py-2 text-sm font-medium text-text-secondary hover:text-text-primary bg-overlay-subtle hover:bg-overlay-light rounded-lg border border-overlay-light transition-colors"
+            >
+              Cancel
+            </button>
+            <button
+              type="submit"
+              disabled={isBusy}
+              className="px-4 py-2 text-sm font-medium rounded-lg transition-colors bg-accent hover:bg-accent-hover text-white disabled:opacity-60"
+            >
+              {confirmLabel}
+            </button>
+          </div>
+        </form>
+      </div>
+    </>
+  );
+};
+
+export default PassphraseModal;
@@ -0,0 +1,131 @@
+import { useEffect, useState } from "react";
+import { getNoteHistorySnapshots } from "../../store/notesStore";
+import { diffAgainstHead } from "../../utils/diff";
+import { recordFeatureUse } from "../../utils/telemetry";
+import DiffHunks from "./DiffHunks";
+
+// The open draft against the note's latest commit, or its last saved
+// snapshot when the note isn't in a git repository
+const ReviewChangesModal = ({ isOpen, note, draft, canSave, onSave, onClose }) => {
+  const [result, setResult] = useState(null);
+  const [error, setError] = useState(null);
+
+  useEffect(() => {
+    if (isOpen) recordFeatureUse("review_changes");
+  }, [isOpen]);
+
+  useEffect(() => {
+    if (!isOpen || !note?.filePath) return;
+    let cancelled = false;
+    setResult(null);
+    setError(null);
+    const snapshot = getNoteHistorySnapshots(note.filePath)[0]?.content ?? null;
+    diffAgainstHead(note.filePath, { content: draft, snapshot })
+      .then((next) => {
+        if (!cancelled) setResult(next);
+      })
+      .catch((err) => {
+        if (!cancelled) setError(String(err?.message || err));
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [isOpen, note?.filePath, draft]);
+
+  if (!isOpen) return null;
+
+  const base =
+    result?.source === "git" ? `commit ${result.commit}` : result ? "the last save" : null;
+
+  return (
+    <div
+      className="fixed inset-0 z-50 flex items-center justify-center p-4"
+      role="dialog"
+      aria-modal="true"
+      aria-label="Review changes"
+      onClick={(e) => {
+        if (e.target === e.currentTarget) onClose?.();
+      }}
+    >
+      <div className="absolute inset-0 bg-black/65 backdrop-blur-sm" />
+
+      <div className="relative z-10 w-full max-w-6xl max-h-[88vh] flex flex-col bg-bg-sidebar border border-border rounded-xl shadow-2xl overflow-hidden">
+        <div className="flex items-start justify-between gap-4 px-5 py-4 border-b border-border shrink-0">
+          <div className="min-w-0">
+            <h2 className="text-base font-semibold text-text-primary">Review Changes</h2>
+            <p className="text-xs text-text-muted mt-1 truncate">
+              {note?.name || "Untitled note"}
+              {base && ` — changes since ${base}`}
+              {result && (
+                <>
+                  <span className="ml-3 text-red-300">-{result.diff.removed}</span>
+                  <span className="ml-2 text-green-300">+{result.diff.added}</span>
+                </>
+              )}
+            </p>
+          </div>
+          <div className="flex items-center gap-2 shrink-0">
+            {canSave && (
+              <button
+                onClick={onSave}
+                className="px-3 py-1.5 text-xs rounded-md bg-accent text-white hover:bg-accent-hover transition-colors font-medium"
+              >
+                Save
+              </button>
+            )}
+            <button
+              onClick={onClose}
+              className="p-1.5 text-text-secondary hover:text-text-primary hover:bg-overlay-subtle rounded-md transition-colors"
+              aria-label="Close review"
+            >
+              <svg className="w-5 h-5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                <path
+                  strokeLinecap="round"
+                  strokeLinejoin="round"
+                  strokeWidth={2}
+                  d="M6 18L18 6M6 6l12 12"
+                />
+              </svg>
+            </button>
+          </div>
+        </div>
+
+        {result && (
+          <div className="grid grid-cols-2 border-b border-border bg-bg-base/80 shrink-0">
+            <div className="px-4 py-2 border-r border-border">
+              <p className="text-xs font-semibold text-text-primary">
+                {result.source === "git" ? "Last Commit" : "Last Saved"}
+              </p>
+              <p className="text-[11px] text-text-muted">
+                {result.source === "git" ? result.commit : "Local history snapshot"}
+              </p>
+            </div>
+            <div className="px-4 py-2">
+              <p className="text-xs font-semibold text-text-primary">Current Draft</p>
+              <p className="text-[11px] text-text-muted">Content in the editor</p>
+            </div>
+          </div>
+        )}
+
+        {error ? (
+          <div className="flex-1 flex items-center justify-center py-16 px-6 text-sm text-red-300 text-center">
+            {error}
+          </div>
+        ) : !result ? (
+          <div className="flex-1 flex items-center justify-center py-16 text-sm text-text-muted">
+            Comparing…
+          </div>
+        ) : result.diff.hunks.length === 0 ? (
+          <div className="flex-1 flex flex-col items-center justify-center gap-3 py-16 text-center px-6">
+            <p className="text-sm font-medium text-text-primary">No changes</p>
+            <p className="text-xs text-text-muted max-w-xs">The draft matches {base}.</p>
+          </div>
+        ) : (
+          <DiffHunks hunks={result.diff.hunks} />
+        )}
+      </div>
+    </div>
+  );
+};
+
+export default ReviewChangesModal;
@@ -2,9 +2,13 @@ import { useState, useEffect, useRef, useMemo } from "react";
 import Fuse from "fuse.js";
 import useNotesStore from "../../store/notesStore";
 import useModalAccessibility from "../../hooks/useModalAccessibility";
+import { searchWorkspace } from "../../utils/search";
+import { recordFeatureTiming, recordFeatureUse } from "../../utils/telemetry";
 
 const RECENT_SEARCHES_KEY = "marky-recent-searches";
 const MAX_RECENT_SEARCHES = 8;
+const MAX_RESULTS = 20;
+const CONTENT_SEARCH_DEBOUNCE_MS = 120;
 
 const readRecentSearches = () => {
   try {
@@ -130,6 +134,21 @@ const buildManualMatches = (note, searchQuery, options) => {
   return matchEntries;
 };
 
+const normalizeSearchPath = (path) => (path || "").replace(/\\/g, "/");
+
+// Splits a snippet at the backend's [start, end) highlight ranges
+const highlightRanges = (text, ranges = []) => {
+  const parts = [];
+  let lastIndex = 0;
+  ranges.forEach(([start, end]) => {
+    if (start > lastIndex) parts.push({ text: text.slice(lastIndex, start), highlight: false });
+    parts.push({ text: text.slice(start, end), highlight: true });
+    lastIndex = end;
+  });
+  if (lastIndex < text.length) parts.push({ text: text.slice(lastIndex), highlight: false });
+  return parts;
+};
+
 const getMatchedFieldLabels = (matches = []) => {
   const fieldNames = new Set(matches.map((m) => m.key));
   return [
@@ -182,7 +201,24 @@ const ResultRow = ({
               ))
             : result.item.name}
         </div>
-        <p className="text-xs text-text-muted line-clamp-2">{preview}</p>
+        {result.lines?.length > 0 ? (
+          <div className="space-y-0.5">
+            {result.lines.map((line) => (
+              <p key={line.line} className="text-xs text-text-muted truncate">
+                <span className="inline-block min-w-[2rem] mr-1 tabular-nums opacity-60">
+                  {line.line}
+                </span>
+                {highlightRanges(line.snippet, line.ranges).map((part, i) => (
+                  <span key={i} className={part.highlight ? "bg-accent/30 text-accent" : ""}>
+                    {part.text}
+                  </span>
+                ))}
+              </p>
+            ))}
+          </div>
+        ) : (
+          <p className="text-xs text-text-muted line-clamp-2">{preview}</p>
+        )}
         {matchedFields.length > 0 && (
           <div className="mt-2 flex flex-wrap gap-1">
             {matchedFields.map((field) => (
@@ -240,7 +276,7 @@ const SearchModal = ({ isOpen, onClose, onSelectResult }) => {
   const resultsListboxId = "search-results-listbox";
   const activeResultId = searchResults.length > 0 ? `search-result-${selectedIndex}` : undefined;
 
-  const { items, selectNote } = useNotesStore();
+  const { items, selectNote, rootFolderPath } = useNotesStore();
   useModalAccessibility(isOpen, dialogRef, searchInputRef);
 
   useEffect(() => {
@@ -267,9 +303,11 @@ const SearchModal = ({ isOpen, onClose, onSelectResult }) => {
 
     setSearchError("");
 
+    // Encrypted notes are searchable by title and path only, even while unlocked
     const normalizedNotes = notes.map((note) => ({
       ...note,
-      tagsText: Array.isArray(note.tags) ? note.tags.join(" ") : "",
+      content: note.encrypted ? "" : note.content,
+      tagsText: !note.encrypted && Array.isArray(note.tags) ? note.tags.join(" ") : "",
     }));
 
     if (searchOptions.regex || searchOptions.exact) {
@@ -291,7 +329,7 @@ const SearchModal = ({ isOpen, onClose, onSelectResult }) => {
 
             return (a.item.name || "").localeCompare(b.item.name || "");
           })
-          .slice(0, 20);
+          .slice(0, MAX_RESULTS);
 
         setSearchResults(manualResults);
         setSelectedIndex(0);
@@ -302,21 +340,79 @@ const SearchModal = ({ isOpen, onClose, onSelectResult }) => {
       return;
     }
 
-    const fuse = new Fuse(normalizedNotes, {
-      keys: enabledScopes,
-      includeScore: true,
-      includeMatches: true,
-      threshold: 0.4,
-      ignoreLocation: true,
-      minMatchCharLength: 2,
-      findAllMatches: true,
-      isCaseSensitive: searchOptions.caseSensitive,
-    });
+    const started = performance.now();
+    const runFuse = (keys) =>
+      new Fuse(normalizedNotes, {
+        keys,
+        includeScore: true,
+        includeMatches: true,
+        threshold: 0.4,
+        ignoreLocation: true,
+        minMatchCharLength: 2,
+        findAllMatches: true,
+        isCaseSensitive: searchOptions.caseSensitive,
+      })
+        .search(searchQuery)
+        .slice(0, MAX_RESULTS);
+    const showResults = (results) => {
+      recordFeatureTiming("search", performance.now() - started);
+      setSearchResults(results);
+      setSelectedIndex(0);
+    };
 
-    const results = fuse.search(searchQuery).slice(0, 20);
-    setSearchResults(results);
-    setSelectedIndex(0);
-  }, [searchQuery, items, searchOptions]);
+    // The backend's index ignores case, so case-sensitive searches stay in memory
+    if (!searchOptions.content || searchOptions.caseSensitive || !rootFolderPath) {
+      showResults(runFuse(enabledScopes));
+      return;
+    }
+
+    // Content is ranked by the workspace index with line-level matches; Fuse
+    // still covers the other scopes so a typo in a title finds its note
+    let cancelled = false;
+    const fuzzyResults = runFuse(enabledScopes.filter((scope) => scope.name !== "content"));
+    const timer = setTimeout(() => {
+      searchWorkspace(rootFolderPath, searchQuery, MAX_RESULTS)
+        .then((hits) => {
+          if (cancelled) return;
+          const notesByPath = new Map(
+            normalizedNotes.map((note) => [normalizeSearchPath(note.filePath), note])
+          );
+          const fuzzyByPath = new Map(
+            fuzzyResults.map((result) => [normalizeSearchPath(result.item.filePath), result])
+          );
+          const ranked = hits
+            .filter((hit) => searchOptions.title || hit.matches.length > 0)
+            .map((hit) => {
+              const path = normalizeSearchPath(hit.path);
+              const item = notesByPath.get(path);
+              if (!item) return null;
+              const matches = [...(fuzzyByPath.get(path)?.matches || [])];
+              if (hit.title_match && !matches.some((m) => m.key === "name")) {
+                matches.push({ key: "name" });
+              }
+              if (hit.matches.length > 0) matches.push({ key: "content" });
+              return { item, matches, score: undefined, lines: hit.matches };
+            })
+            .filter(Boolean);
+          const seen = new Set(ranked.map((result) => result.item.id));
+          showResults(
+            [...ranked, ...fuzzyResults.filter((result) => !seen.has(result.item.id))].slice(
+              0,
+              MAX_RESULTS
+            )
+          );
+        })
+        .catch(() => {
+          // Fall back to matching the loaded notes in memory
+          if (!cancelled) showResults(runFuse(enabledScopes));
+        });
+    }, CONTENT_SEARCH_DEBOUNCE_MS);
+
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [searchQuery, items, searchOptions, rootFolderPath]);
 
   useEffect(() => {
     if (isOpen && searchInputRef.current) {
@@ -389,6 +485,7 @@ const SearchModal = ({ isOpen, onClose, onSelectResult }) => {
     if (searchQuery.trim()) {
       saveRecentSearch(searchQuery);
       setRecentSearches(readRecentSearches());
+      recordFeatureUse("search");
     }
     selectNote(note.id);
     onClose();
@@ -0,0 +1,134 @@
+import { useEffect, useState } from "react";
+import { queryNotes } from "../../utils/query";
+import { saveSmartFolder } from "../../utils/smartFolders";
+
+const SmartFolderModal = ({ isOpen, onClose, onSaved, rootFolderPath, folder = null }) => {
+  const [name, setName] = useState("");
+  const [query, setQuery] = useState("");
+  const [preview, setPreview] = useState(null);
+  const [error, setError] = useState("");
+  const [isSaving, setIsSaving] = useState(false);
+
+  useEffect(() => {
+    if (!isOpen) return;
+    setName(folder?.name || "");
+    setQuery(folder?.query || "");
+    setPreview(null);
+    setError("");
+  }, [isOpen, folder]);
+
+  if (!isOpen) return null;
+
+  const handlePreview = async () => {
+    setError("");
+    try {
+      const result = await queryNotes(rootFolderPath, query);
+      setPreview(result.total);
+    } catch (err) {
+      setPreview(null);
+      setError(String(err?.message || err));
+    }
+  };
+
+  const handleSave = async () => {
+    setIsSaving(true);
+    setError("");
+    try {
+      const saved = await saveSmartFolder(rootFolderPath, { id: folder?.id, name, query });
+      onSaved?.(saved);
+      onClose();
+    } catch (err) {
+      setError(String(err?.message || err));
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const canSave = !isSaving && name.trim() && query.trim();
+
+  return (
+    <>
+      <div className="fixed inset-0 bg-black/60 backdrop-blur-sm z-50" onClick={onClose} />
+
+      <div className="fixed inset-0 z-50 flex items-center justify-center p-4 pointer-events-none">
+        <div
+          className="glass-panel border-glass-border rounded-xl shadow-2xl w-full max-w-lg flex flex-col pointer-events-auto"
+          onClick={(e) => e.stopPropagation()}
+        >
+          <div className="border-b border-glass-border px-6 py-4">
+            <h2 className="text-xl font-semibold text-text-primary">
+              {folder ? "Edit Smart Folder" : "New Smart Folder"}
+            </h2>
+            <p className="text-sm text-text-muted mt-1">
+              Lists every note matching a query, kept up to date as notes change
+            </p>
+          </div>
+
+          <div className="px-6 py-5 space-y-3">
+            <input
+              type="text"
+              value={name}
+              onChange={(e) => setName(e.target.value)}
+              placeholder="Name"
+              autoFocus
+              className="w-full px-3 py-2 bg-overlay-subtle border border-overlay-subtle rounded-lg text-sm text-text-primary outline-none focus:border-accent/40"
+            />
+            <textarea
+              value={query}
+              onChange={(e) => {
+                setQuery(e.target.value);
+                setPreview(null);
+              }}
+              placeholder={'status = "active" AND due < today SORT due'}
+              rows={3}
+              spellCheck={false}
+              className="w-full px-3 py-2 bg-overlay-subtle border border-overlay-subtle rounded-lg text-sm font-mono text-text-primary outline-none focus:border-accent/40 resize-none"
+            />
+            <p className="text-xs text-text-muted">
+              Filter on frontmatter fields, <code className="text-accent">tags</code>,{" "}
+              <code className="text-accent">#tag</code>, and{" "}
+              <code className="text-accent">file.path</code> /{" "}
+              <code className="text-accent">file.modified</code>; use{" "}
+              <code className="text-accent">FROM "Folder"</code>,{" "}
+              <code className="text-accent">SORT</code>, and{" "}
+              <code className="text-accent">LIMIT</code> to narrow it down.
+            </p>
+            {error && <p className="text-xs text-red-400">{error}</p>}
+            {preview !== null && !error && (
+              <p className="text-xs text-text-secondary">
+                Matches {preview} note{preview !== 1 ? "s" : ""}
+              </p>
+            )}
+          </div>
+
+          <div className="border-t border-overlay-light px-6 py-4 flex justify-between items-center">
+            <button
+              onClick={handlePreview}
+              disabled={!query.trim()}
+              className="px-3 py-2 text-sm text-text-secondary hover:text-text-primary hover:bg-overlay-light rounded-lg transition-colors disabled:opacity-50"
+            >
+              Preview
+            </button>
+            <div className="flex gap-2">
+              <button
+                onClick={onClose}
+                className="px-4 py-2 text-sm text-text-secondary hover:text-text-primary hover:bg-overlay-light rounded-lg transition-colors"
+              >
+                Cancel
+              </button>
+              <button
+                onClick={handleSave}
+                disabled={!canSave}
+                className="px-4 py-2 text-sm font-medium rounded-lg bg-accent hover:bg-accent/80 text-white disabled:opacity-50"
+              >
+                {isSaving ? "Saving..." : "Save"}
+              </button>
+            </div>
+          </div>
+        </div>
+      </div>
+    </>
+  );
+};
+
+export default SmartFolderModal;
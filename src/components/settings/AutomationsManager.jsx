@@ -0,0 +1,344 @@
+import { useEffect, useState } from "react";
+import useNotesStore from "../../store/notesStore";
+import useUIStore from "../../store/uiStore";
+import {
+  getAutomations,
+  setAutomations,
+  dryRunAutomations,
+  getAutomationLog,
+  clearAutomationLog,
+} from "../../utils/automations";
+
+const TRIGGER_LABELS = {
+  save: "On save",
+  create: "On create",
+};
+
+const ACTION_LABELS = {
+  export_html: "Export as HTML",
+  apply_template: "Apply template",
+  add_tag: "Add tag",
+};
+
+const OUTCOME_STYLES = {
+  done: "text-green-400",
+  skipped: "text-text-muted",
+  failed: "text-red-400",
+};
+
+const inputClassName =
+  "w-full px-3 py-2 bg-overlay-subtle border border-overlay-subtle rounded-lg text-sm text-text-primary outline-none focus:border-accent/40";
+
+const newRule = () => ({
+  id: "",
+  name: "New automation",
+  enabled: true,
+  dry_run: true,
+  trigger: "save",
+  tag: null,
+  folder: null,
+  action: "export_html",
+  template: null,
+  add_tag: null,
+  export_folder: null,
+});
+
+const actionField = (action) => {
+  switch (action) {
+    case "apply_template":
+      return { key: "template", label: "Template", placeholder: "Blog post" };
+    case "add_tag":
+      return { key: "add_tag", label: "Tag to add", placeholder: "published" };
+    default:
+      return { key: "export_folder", label: "Export folder", placeholder: "Exports" };
+  }
+};
+
+const AutomationsManager = () => {
+  const rootFolderPath = useNotesStore((state) => state.rootFolderPath);
+  const getCurrentNote = useNotesStore((state) => state.getCurrentNote);
+  const addNotification = useUIStore((state) => state.addNotification);
+
+  const [rules, setRules] = useState([]);
+  const [log, setLog] = useState([]);
+  const [isDirty, setIsDirty] = useState(false);
+  const [isSaving, setIsSaving] = useState(false);
+
+  const refreshLog = async () => {
+    if (rootFolderPath) {
+      setLog(await getAutomationLog(rootFolderPath));
+    }
+  };
+
+  useEffect(() => {
+    if (!rootFolderPath) return;
+    getAutomations(rootFolderPath)
+      .then((loaded) => {
+        setRules(loaded);
+        setIsDirty(false);
+      })
+      .catch((error) => addNotification("Failed to load automations: " + error, "error"));
+    getAutomationLog(rootFolderPath).then(setLog);
+  }, [rootFolderPath, addNotification]);
+
+  if (!rootFolderPath) {
+    return <p className="text-sm text-text-muted">Open a workspace to set up automations.</p>;
+  }
+
+  const updateRule = (index, updates) => {
+    setRules((current) =>
+      current.map((rule, i) => (i === index ? { ...rule, ...updates } : rule))
+    );
+    setIsDirty(true);
+  };
+
+  const removeRule = (index) => {
+    setRules((current) => current.filter((_, i) => i !== index));
+    setIsDirty(true);
+  };
+
+  const handleSave = async () => {
+    setIsSaving(true);
+    try {
+      setRules(await setAutomations(rootFolderPath, rules));
+      setIsDirty(false);
+      addNotification("Automations saved", "success", 1800);
+    } catch (error) {
+      addNotification(String(error), "error");
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const handleTest = async () => {
+    const note = getCurrentNote();
+    if (!note?.filePath) {
+      addNotification("Open a saved note to test automations against", "info");
+      return;
+    }
+    try {
+      const entries = await dryRunAutomations(rootFolderPath, note.filePath, "save");
+      if (entries.length === 0) {
+        addNotification("No saved automations match this note", "info");
+      }
+      await refreshLog();
+    } catch (error) {
+      addNotification("Failed to test automations: " + error, "error");
+    }
+  };
+
+  const handleClearLog = async () => {
+    try {
+      await clearAutomationLog(rootFolderPath);
+      setLog([]);
+    } catch (error) {
+      addNotification("Failed to clear log: " + error, "error");
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      {rules.length === 0 && (
+        <div className="border border-border rounded-xl bg-sidebar-bg/40 px-6 py-8 text-center text-text-muted">
+          <p className="text-lg font-semibold text-text-primary mb-2">No automations yet</p>
+          <p className="text-sm">
+            For example: on save of notes tagged <code className="text-accent">#blog</code>,
+            export them as HTML.
+          </p>
+        </div>
+      )}
+
+      {rules.map((rule, index) => {
+        const field = actionField(rule.action);
+        return (
+          <div
+            key={rule.id || `new-${index}`}
+            className="border border-overlay-subtle rounded-xl bg-sidebar-bg/40 p-4 space-y-3"
+          >
+            <div className="flex items-center gap-3">
+              <input
+                type="text"
+                value={rule.name}
+                onChange={(e) => updateRule(index, { name: e.target.value })}
+                className={inputClassName}
+                aria-label="Automation name"
+              />
+              <button
+                onClick={() => updateRule(index, { enabled: !rule.enabled })}
+                className={`relative w-14 h-7 rounded-full transition-all duration-200 shrink-0 ${
+                  rule.enabled
+                    ? "bg-accent shadow-lg shadow-accent/30"
+                    : "bg-overlay-light hover:bg-overlay-medium"
+                }`}
+                aria-checked={rule.enabled}
+                role="switch"
+                title={rule.enabled ? "Disable automation" : "Enable automation"}
+              >
+                <span
+                  className={`absolute top-1 left-1 w-5 h-5 bg-white rounded-full shadow-md transition-transform duration-200 ${
+                    rule.enabled ? "translate-x-7" : "translate-x-0"
+                  }`}
+                />
+              </button>
+              <button
+                type="button"
+                onClick={() => removeRule(index)}
+                className="px-3 py-1.5 text-xs rounded-lg border border-overlay-subtle text-text-secondary hover:text-red-400 hover:border-red-400/40 shrink-0"
+              >
+                Remove
+              </button>
+            </div>
+
+            <div className="grid grid-cols-1 md:grid-cols-3 gap-3">
+              <label className="block">
+                <span className="block text-xs font-medium text-text-muted mb-1">When</span>
+                <select
+                  value={rule.trigger}
+                  onChange={(e) => updateRule(index, { trigger: e.target.value })}
+                  className={inputClassName}
+                >
+                  {Object.entries(TRIGGER_LABELS).map(([value, label]) => (
+                    <option key={value} value={value}>
+                      {label}
+                    </option>
+                  ))}
+                </select>
+              </label>
+              <label className="block">
+                <span className="block text-xs font-medium text-text-muted mb-1">
+                  Notes tagged
+                </span>
+                <input
+                  type="text"
+                  value={rule.tag || ""}
+                  onChange={(e) => updateRule(index, { tag: e.target.value || null })}
+                  placeholder="Any tag"
+                  className={inputClassName}
+                />
+              </label>
+              <label className="block">
+                <span className="block text-xs font-medium text-text-muted mb-1">In folder</span>
+                <input
+                  type="text"
+                  value={rule.folder || ""}
+                  onChange={(e) => updateRule(index, { folder: e.target.value || null })}
+                  placeholder="Anywhere"
+                  className={inputClassName}
+                />
+              </label>
+              <label className="block">
+                <span className="block text-xs font-medium text-text-muted mb-1">Do</span>
+                <select
+                  value={rule.action}
+                  onChange={(e) => updateRule(index, { action: e.target.value })}
+                  className={inputClassName}
+                >
+                  {Object.entries(ACTION_LABELS).map(([value, label]) => (
+                    <option key={value} value={value}>
+                      {label}
+                    </option>
+                  ))}
+                </select>
+              </label>
+              <label className="block">
+                <span className="block text-xs font-medium text-text-muted mb-1">
+                  {field.label}
+                </span>
+                <input
+                  type="text"
+                  value={rule[field.key] || ""}
+                  onChange={(e) => updateRule(index, { [field.key]: e.target.value || null })}
+                  placeholder={field.placeholder}
+                  className={inputClassName}
+                />
+              </label>
+              <label className="flex items-center gap-2 md:pt-6 text-sm text-text-secondary">
+                <input
+                  type="checkbox"
+                  checked={rule.dry_run}
+                  onChange={(e) => updateRule(index, { dry_run: e.target.checked })}
+                />
+                Dry run (log only)
+              </label>
+            </div>
+          </div>
+        );
+      })}
+
+      <div className="flex flex-wrap items-center gap-2">
+        <button
+          type="button"
+          onClick={() => {
+            setRules((current) => [...current, newRule()]);
+            setIsDirty(true);
+          }}
+          className="px-3 py-1.5 text-xs rounded-lg border border-overlay-subtle text-text-secondary hover:text-text-primary hover:border-overlay-light"
+        >
+          Add Automation
+        </button>
+        <button
+          type="button"
+          onClick={handleSave}
+          disabled={!isDirty || isSaving}
+          className="px-3 py-1.5 text-xs rounded-lg bg-accent text-white hover:bg-accent/90 disabled:opacity-50"
+        >
+          {isSaving ? "Saving..." : "Save Automations"}
+        </button>
+        <button
+          type="button"
+          onClick={handleTest}
+          disabled={rules.length === 0 || isDirty}
+          title={isDirty ? "Save your changes first" : undefined}
+          className="px-3 py-1.5 text-xs rounded-lg border border-overlay-subtle text-text-secondary hover:text-text-primary hover:border-overlay-light disabled:opacity-50"
+        >
+          Dry Run on Current Note
+        </button>
+      </div>
+
+      <div className="border border-overlay-subtle rounded-xl bg-sidebar-bg/40 p-4">
+        <div className="flex items-center justify-between mb-2">
+          <p className="text-sm font-medium text-text-secondary">Recent runs</p>
+          <div className="flex items-center gap-2">
+            <button
+              type="button"
+              onClick={refreshLog}
+              className="text-xs text-text-muted hover:text-text-primary"
+            >
+              Refresh
+            </button>
+            <button
+              type="button"
+              onClick={handleClearLog}
+              disabled={log.length === 0}
+              className="text-xs text-text-muted hover:text-text-primary disabled:opacity-50"
+            >
+              Clear
+            </button>
+          </div>
+        </div>
+        {log.length === 0 ? (
+          <p className="text-xs text-text-muted py-2">Nothing has run yet.</p>
+        ) : (
+          <ul className="max-h-64 overflow-y-auto custom-scrollbar space-y-1 pr-1">
+            {log.map((entry, index) => (
+              <li key={`${entry.time}-${index}`} className="text-xs flex gap-2">
+                <span className="text-text-muted shrink-0">
+                  {new Date(entry.time).toLocaleTimeString()}
+                </span>
+                <span className={`shrink-0 ${OUTCOME_STYLES[entry.outcome] || ""}`}>
+                  {entry.outcome}
+                  {entry.dry_run && " · dry run"}
+                </span>
+                <span className="text-text-secondary truncate" title={entry.path}>
+                  {entry.rule_name}: {entry.message}
+                </span>
+              </li>
+            ))}
+          </ul>
+        )}
+      </div>
+    </div>
+  );
+};
+
+export default AutomationsManager;
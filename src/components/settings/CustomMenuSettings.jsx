@@ -0,0 +1,105 @@
+import { useEffect, useState } from "react";
+import useUIStore from "../../store/uiStore";
+import { getCustomMenu, saveCustomMenu } from "../../utils/customMenu";
+
+const inputClassName =
+  "w-full px-3 py-2 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent";
+
+const ACTIONS = [
+  { value: "open_note", label: "Open note", placeholder: "Projects/Plan.md or Plan" },
+  { value: "run_automation", label: "Run automation", placeholder: "Automation name" },
+  { value: "open_url", label: "Open URL", placeholder: "https://example.com" },
+];
+
+const isComplete = (entry) => entry.label.trim() && entry.target.trim();
+
+// Edits a local copy of the Custom menu and saves it whenever a field changes
+// or loses focus
+const CustomMenuSettings = () => {
+  const addNotification = useUIStore((state) => state.addNotification);
+  const [entries, setEntries] = useState([]);
+
+  useEffect(() => {
+    getCustomMenu()
+      .then(setEntries)
+      .catch(() => addNotification("Failed to load the custom menu", "error"));
+  }, [addNotification]);
+
+  const save = (next) =>
+    saveCustomMenu(next)
+      .then((saved) => {
+        // Rows still being filled in stay on screen
+        setEntries([...saved, ...next.filter((entry) => !isComplete(entry))]);
+        if (saved.length < next.filter(isComplete).length) {
+          addNotification("URLs must start with http://, https:// or mailto:", "warning");
+        }
+      })
+      .catch(() => addNotification("Failed to save the custom menu", "error"));
+
+  const updateField = (index, field, value) => {
+    setEntries((current) =>
+      current.map((entry, idx) => (idx === index ? { ...entry, [field]: value } : entry))
+    );
+  };
+
+  const changeAction = (index, action) =>
+    save(entries.map((entry, idx) => (idx === index ? { ...entry, action } : entry)));
+
+  const removeEntry = (index) => save(entries.filter((_, idx) => idx !== index));
+
+  return (
+    <div className="space-y-3">
+      {entries.map((entry, index) => (
+        <div key={index} className="grid grid-cols-[10rem_9rem_1fr_auto] gap-2 items-center">
+          <input
+            value={entry.label}
+            onChange={(e) => updateField(index, "label", e.target.value)}
+            onBlur={() => save(entries)}
+            placeholder="Menu label"
+            aria-label="Menu label"
+            className={inputClassName}
+          />
+          <select
+            value={entry.action}
+            onChange={(e) => changeAction(index, e.target.value)}
+            aria-label="Action"
+            className={inputClassName}
+          >
+            {ACTIONS.map((action) => (
+              <option key={action.value} value={action.value}>
+                {action.label}
+              </option>
+            ))}
+          </select>
+          <input
+            value={entry.target}
+            onChange={(e) => updateField(index, "target", e.target.value)}
+            onBlur={() => save(entries)}
+            placeholder={ACTIONS.find((action) => action.value === entry.action)?.placeholder}
+            aria-label="Target"
+            className={inputClassName}
+          />
+          <button
+            onClick={() => removeEntry(index)}
+            className="px-2 py-2 text-xs rounded-lg text-text-muted hover:text-red-400 hover:bg-overlay-light"
+            title="Remove menu entry"
+          >
+            Remove
+          </button>
+        </div>
+      ))}
+      <button
+        onClick={() => setEntries([...entries, { label: "", action: "open_note", target: "" }])}
+        className="px-3 py-1.5 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-secondary hover:text-text-primary hover:bg-overlay-light"
+      >
+        Add Menu Entry
+      </button>
+      <p className="text-xs text-text-muted">
+        Entries appear in a Custom menu in the menu bar. Notes are looked up in the open workspace,
+        and automations run on the note you have open.
+      </p>
+    </div>
+  );
+};
+
+export default CustomMenuSettings;
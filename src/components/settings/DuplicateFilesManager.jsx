@@ -0,0 +1,126 @@
+import { useState } from "react";
+import useNotesStore from "../../store/notesStore";
+import useUIStore from "../../store/uiStore";
+import { findDuplicateFiles, mergeDuplicateFiles } from "../../utils/duplicates";
+
+const formatSize = (bytes) => {
+  if (bytes < 1024) return `${bytes} B`;
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+};
+
+const relativeTo = (root, path) =>
+  path.startsWith(root) ? path.slice(root.length).replace(/^[\\/]/, "") : path;
+
+const DuplicateFilesManager = () => {
+  const rootFolderPath = useNotesStore((state) => state.rootFolderPath);
+  const refreshRootFromDisk = useNotesStore((state) => state.refreshRootFromDisk);
+  const addNotification = useUIStore((state) => state.addNotification);
+
+  const [groups, setGroups] = useState(null);
+  // Which copy to keep in each group, by hash; the first one unless changed
+  const [keep, setKeep] = useState({});
+  const [isScanning, setIsScanning] = useState(false);
+  const [mergingHash, setMergingHash] = useState(null);
+
+  if (!rootFolderPath) {
+    return <p className="text-sm text-text-muted">Open a workspace to look for duplicates.</p>;
+  }
+
+  const handleScan = async () => {
+    setIsScanning(true);
+    try {
+      setGroups(await findDuplicateFiles(rootFolderPath));
+      setKeep({});
+    } catch (error) {
+      addNotification("Failed to find duplicates: " + error, "error");
+    } finally {
+      setIsScanning(false);
+    }
+  };
+
+  const handleMerge = async (group) => {
+    const keepPath = keep[group.hash] || group.files[0].path;
+    const duplicatePaths = group.files.map((file) => file.path).filter((path) => path !== keepPath);
+    setMergingHash(group.hash);
+    try {
+      const result = await mergeDuplicateFiles(rootFolderPath, keepPath, duplicatePaths);
+      setGroups((current) => current.filter((candidate) => candidate.hash !== group.hash));
+      addNotification(
+        `Removed ${result.removed} ${result.removed === 1 ? "copy" : "copies"}, relinked ${
+          result.relinked_notes
+        } ${result.relinked_notes === 1 ? "note" : "notes"}`,
+        "success",
+        3000
+      );
+      await refreshRootFromDisk();
+    } catch (error) {
+      addNotification("Failed to merge duplicates: " + error, "error");
+    } finally {
+      setMergingHash(null);
+    }
+  };
+
+  const wasted = (groups || []).reduce(
+    (total, group) => total + group.size * (group.files.length - 1),
+    0
+  );
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center justify-between gap-4">
+        <p className="text-xs text-text-muted">
+          {groups === null
+            ? "Compares every note and attachment by content."
+            : groups.length === 0
+              ? "No duplicate files found."
+              : `${groups.length} sets of identical files · ${formatSize(wasted)} reclaimable`}
+        </p>
+        <button
+          type="button"
+          onClick={handleScan}
+          disabled={isScanning}
+          className="px-3 py-1.5 text-xs rounded-lg border border-overlay-subtle text-text-secondary hover:text-text-primary hover:border-overlay-light disabled:opacity-50 shrink-0"
+        >
+          {isScanning ? "Scanning..." : "Find Duplicates"}
+        </button>
+      </div>
+
+      {groups?.map((group) => {
+        const keepPath = keep[group.hash] || group.files[0].path;
+        return (
+          <div key={group.hash} className="rounded-lg bg-overlay-subtle p-3 space-y-2">
+            <div className="flex items-center justify-between gap-3">
+              <span className="text-xs text-text-muted">
+                {group.files.length} copies · {formatSize(group.size)} each
+              </span>
+              <button
+                type="button"
+                onClick={() => handleMerge(group)}
+                disabled={mergingHash !== null}
+                className="px-3 py-1.5 text-xs rounded-lg bg-accent text-white hover:bg-accent/90 disabled:opacity-50"
+              >
+                {mergingHash === group.hash ? "Merging..." : "Keep One, Relink Others"}
+              </button>
+            </div>
+            {group.files.map((file) => (
+              <label key={file.path} className="flex items-center gap-2 text-xs text-text-secondary">
+                <input
+                  type="radio"
+                  name={group.hash}
+                  checked={file.path === keepPath}
+                  onChange={() => setKeep((current) => ({ ...current, [group.hash]: file.path }))}
+                />
+                <span className="font-mono truncate" title={file.path}>
+                  {relativeTo(rootFolderPath, file.path)}
+                </span>
+              </label>
+            ))}
+          </div>
+        );
+      })}
+    </div>
+  );
+};
+
+export default DuplicateFilesManager;
@@ -12,6 +12,8 @@ const EditorSettings = () => {
     setAutosaveDelay,
     typewriterMode: typewriterModeEnabled,
     setTypewriterMode,
+    pasteLinkTitles,
+    setPasteLinkTitles,
     sidebarDensity,
     setSidebarDensity,
     showSidebarMetadata,
@@ -125,6 +127,31 @@ const EditorSettings = () => {
         </button>
       </div>
 
+      {/* Link Titles Toggle */}
+      <div className="flex items-start justify-between">
+        <div className="flex-1">
+          <h3 className="text-sm font-semibold text-text-primary mb-1">Link Titles on Paste</h3>
+          <p className="text-xs text-text-muted leading-relaxed">
+            Turn a pasted web address into a markdown link named after the page, like
+            [Page Title](https://…). The title is fetched in the background; the plain URL stays
+            if the page can’t be reached.
+          </p>
+        </div>
+        <button
+          onClick={() => setPasteLinkTitles(!pasteLinkTitles)}
+          className={`relative ml-4 w-14 h-7 rounded-full transition-all duration-200 shrink-0 ${
+            pasteLinkTitles
+              ? "bg-accent shadow-lg shadow-accent/30"
+              : "bg-overlay-light hover:bg-overlay-medium"
+          }`}
+          aria-label="Toggle Link Titles on Paste"
+        >
+          <span
+            className={`absolute top-1 left-1 w-5 h-5 bg-white rounded-full shadow-md transition-transform duration-200 ${pasteLinkTitles ? "translate-x-7" : "translate-x-0"}`}
+          />
+        </button>
+      </div>
+
       {/* Vim Mode Toggle */}
       {/* Sidebar Density */}
       <div className="flex items-start justify-between gap-4">
@@ -0,0 +1,265 @@
+import { useEffect, useState } from "react";
+import useNotesStore from "../../store/notesStore";
+import useUIStore from "../../store/uiStore";
+import { getFeeds, setFeeds, getFeedStatus, refreshFeeds, markFeedRead } from "../../utils/feeds";
+
+const TARGET_LABELS = {
+  note: "Its own note",
+  daily: "Today's daily note",
+};
+
+const inputClassName =
+  "w-full px-3 py-2 bg-overlay-subtle border border-overlay-subtle rounded-lg text-sm text-text-primary outline-none focus:border-accent/40";
+
+const newFeed = () => ({
+  id: "",
+  name: "New feed",
+  url: "",
+  enabled: true,
+  target: "note",
+  note: null,
+  interval_minutes: 60,
+});
+
+const describeStatus = (status) => {
+  if (!status?.last_checked) return "Not checked yet";
+  const checked = new Date(status.last_checked).toLocaleString();
+  if (status.last_error) return `Failed ${checked}: ${status.last_error}`;
+  return `Checked ${checked} · ${status.last_added} new`;
+};
+
+const FeedsManager = () => {
+  const rootFolderPath = useNotesStore((state) => state.rootFolderPath);
+  const addNotification = useUIStore((state) => state.addNotification);
+
+  const [feeds, setFeedList] = useState([]);
+  const [statuses, setStatuses] = useState([]);
+  const [isDirty, setIsDirty] = useState(false);
+  const [isSaving, setIsSaving] = useState(false);
+  const [checkingId, setCheckingId] = useState(null);
+
+  useEffect(() => {
+    if (!rootFolderPath) return;
+    getFeeds(rootFolderPath)
+      .then((loaded) => {
+        setFeedList(loaded);
+        setIsDirty(false);
+      })
+      .catch((error) => addNotification("Failed to load feeds: " + error, "error"));
+    getFeedStatus(rootFolderPath).then(setStatuses);
+  }, [rootFolderPath, addNotification]);
+
+  if (!rootFolderPath) {
+    return <p className="text-sm text-text-muted">Open a workspace to subscribe to feeds.</p>;
+  }
+
+  const updateFeed = (index, updates) => {
+    setFeedList((current) =>
+      current.map((feed, i) => (i === index ? { ...feed, ...updates } : feed))
+    );
+    setIsDirty(true);
+  };
+
+  const removeFeed = (index) => {
+    setFeedList((current) => current.filter((_, i) => i !== index));
+    setIsDirty(true);
+  };
+
+  const handleSave = async () => {
+    setIsSaving(true);
+    try {
+      setFeedList(await setFeeds(rootFolderPath, feeds));
+      setIsDirty(false);
+      addNotification("Feeds saved", "success", 1800);
+    } catch (error) {
+      addNotification(String(error), "error");
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const handleCheck = async (feed, markRead = false) => {
+    setCheckingId(feed.id);
+    try {
+      const updated = markRead
+        ? await markFeedRead(rootFolderPath, feed.id)
+        : await refreshFeeds(rootFolderPath, feed.id);
+      setStatuses(updated);
+      const status = updated.find((entry) => entry.id === feed.id);
+      if (status?.last_error) {
+        addNotification(`${feed.name}: ${status.last_error}`, "error");
+      } else if (markRead) {
+        addNotification(`Marked ${feed.name} as read`, "success", 1800);
+      }
+    } catch (error) {
+      addNotification("Failed to check feed: " + error, "error");
+    } finally {
+      setCheckingId(null);
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      {feeds.length === 0 && (
+        <div className="border border-border rounded-xl bg-sidebar-bg/40 px-6 py-8 text-center text-text-muted">
+          <p className="text-lg font-semibold text-text-primary mb-2">No feeds yet</p>
+          <p className="text-sm">
+            Add an RSS or Atom address and new posts are appended to a note as they appear.
+          </p>
+        </div>
+      )}
+
+      {feeds.map((feed, index) => {
+        const status = statuses.find((entry) => entry.id === feed.id);
+        return (
+          <div
+            key={feed.id || `new-${index}`}
+            className="border border-overlay-subtle rounded-xl bg-sidebar-bg/40 p-4 space-y-3"
+          >
+            <div className="flex items-center gap-3">
+              <input
+                type="text"
+                value={feed.name}
+                onChange={(e) => updateFeed(index, { name: e.target.value })}
+                className={inputClassName}
+                aria-label="Feed name"
+              />
+              <button
+                onClick={() => updateFeed(index, { enabled: !feed.enabled })}
+                className={`relative w-14 h-7 rounded-full transition-all duration-200 shrink-0 ${
+                  feed.enabled
+                    ? "bg-accent shadow-lg shadow-accent/30"
+                    : "bg-overlay-light hover:bg-overlay-medium"
+                }`}
+                aria-checked={feed.enabled}
+                role="switch"
+                title={feed.enabled ? "Pause feed" : "Resume feed"}
+              >
+                <span
+                  className={`absolute top-1 left-1 w-5 h-5 bg-white rounded-full shadow-md transition-transform duration-200 ${
+                    feed.enabled ? "translate-x-7" : "translate-x-0"
+                  }`}
+                />
+              </button>
+              <button
+                type="button"
+                onClick={() => removeFeed(index)}
+                className="px-3 py-1.5 text-xs rounded-lg border border-overlay-subtle text-text-secondary hover:text-red-400 hover:border-red-400/40 shrink-0"
+              >
+                Remove
+              </button>
+            </div>
+
+            <div className="grid grid-cols-1 md:grid-cols-3 gap-3">
+              <label className="block md:col-span-3">
+                <span className="block text-xs font-medium text-text-muted mb-1">Address</span>
+                <input
+                  type="url"
+                  value={feed.url}
+                  onChange={(e) => updateFeed(index, { url: e.target.value })}
+                  placeholder="https://example.com/feed.xml"
+                  className={inputClassName}
+                />
+              </label>
+              <label className="block">
+                <span className="block text-xs font-medium text-text-muted mb-1">Add posts to</span>
+                <select
+                  value={feed.target}
+                  onChange={(e) => updateFeed(index, { target: e.target.value })}
+                  className={inputClassName}
+                >
+                  {Object.entries(TARGET_LABELS).map(([value, label]) => (
+                    <option key={value} value={value}>
+                      {label}
+                    </option>
+                  ))}
+                </select>
+              </label>
+              {feed.target === "note" && (
+                <label className="block">
+                  <span className="block text-xs font-medium text-text-muted mb-1">Note</span>
+                  <input
+                    type="text"
+                    value={feed.note || ""}
+                    onChange={(e) => updateFeed(index, { note: e.target.value || null })}
+                    placeholder={`Feeds/${feed.name || "Feed"}.md`}
+                    className={inputClassName}
+                  />
+                </label>
+              )}
+              <label className="block">
+                <span className="block text-xs font-medium text-text-muted mb-1">
+                  Check every (minutes)
+                </span>
+                <input
+                  type="number"
+                  min={15}
+                  value={feed.interval_minutes}
+                  onChange={(e) =>
+                    updateFeed(index, {
+                      interval_minutes: Number.parseInt(e.target.value, 10) || 0,
+                    })
+                  }
+                  className={inputClassName}
+                />
+              </label>
+            </div>
+
+            {feed.id && (
+              <div className="flex flex-wrap items-center gap-2">
+                <span
+                  className={`text-xs flex-1 min-w-0 truncate ${
+                    status?.last_error ? "text-red-400" : "text-text-muted"
+                  }`}
+                  title={describeStatus(status)}
+                >
+                  {describeStatus(status)}
+                </span>
+                <button
+                  type="button"
+                  onClick={() => handleCheck(feed)}
+                  disabled={isDirty || checkingId !== null}
+                  title={isDirty ? "Save your changes first" : undefined}
+                  className="px-3 py-1.5 text-xs rounded-lg border border-overlay-subtle text-text-secondary hover:text-text-primary hover:border-overlay-light disabled:opacity-50"
+                >
+                  {checkingId === feed.id ? "Checking..." : "Check Now"}
+                </button>
+                <button
+                  type="button"
+                  onClick={() => handleCheck(feed, true)}
+                  disabled={isDirty || checkingId !== null}
+                  className="px-3 py-1.5 text-xs rounded-lg border border-overlay-subtle text-text-secondary hover:text-text-primary hover:border-overlay-light disabled:opacity-50"
+                >
+                  Mark All Read
+                </button>
+              </div>
+            )}
+          </div>
+        );
+      })}
+
+      <div className="flex flex-wrap items-center gap-2">
+        <button
+          type="button"
+          onClick={() => {
+            setFeedList((current) => [...current, newFeed()]);
+            setIsDirty(true);
+          }}
+          className="px-3 py-1.5 text-xs rounded-lg border border-overlay-subtle text-text-secondary hover:text-text-primary hover:border-overlay-light"
+        >
+          Add Feed
+        </button>
+        <button
+          type="button"
+          onClick={handleSave}
+          disabled={!isDirty || isSaving}
+          className="px-3 py-1.5 text-xs rounded-lg bg-accent text-white hover:bg-accent/90 disabled:opacity-50"
+        >
+          {isSaving ? "Saving..." : "Save Feeds"}
+        </button>
+      </div>
+    </div>
+  );
+};
+
+export default FeedsManager;
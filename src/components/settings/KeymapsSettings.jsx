@@ -1,13 +1,11 @@
 import { useState, useEffect, useCallback } from "react";
-import useSettingsStore, {
-  formatKeymap,
-  DEFAULT_KEYMAPS,
-  KEYMAP_CATEGORIES,
-} from "../../store/settingsStore";
+import useSettingsStore, { formatKeymap, KEYMAP_CATEGORIES } from "../../store/settingsStore";
+import useUIStore from "../../store/uiStore";
 
 const KeymapsSettings = ({ onOpenKeymapsModal }) => {
   const { keymaps, resetKeymaps, resetKeymap, updateKeymap, setIsRecordingKeymap } =
     useSettingsStore();
+  const addNotification = useUIStore((state) => state.addNotification);
   const [editingAction, setEditingAction] = useState(null);
   const [recordedKeymap, setRecordedKeymap] = useState(null);
 
@@ -29,13 +27,25 @@ const KeymapsSettings = ({ onOpenKeymapsModal }) => {
     setRecordedKeymap(null);
   }, []);
 
-  const handleConfirmKeymap = useCallback(() => {
+  // Conflicts are refused by the backend; recording stays open to try another
+  const handleConfirmKeymap = useCallback(async () => {
     if (editingAction && recordedKeymap) {
-      updateKeymap(editingAction, recordedKeymap);
+      try {
+        await updateKeymap(editingAction, recordedKeymap);
+      } catch (error) {
+        addNotification(String(error?.message || error), "error");
+        setRecordedKeymap(null);
+        return;
+      }
     }
     setEditingAction(null);
     setRecordedKeymap(null);
-  }, [editingAction, recordedKeymap, updateKeymap]);
+  }, [editingAction, recordedKeymap, updateKeymap, addNotification]);
+
+  const handleReset = (reset) =>
+    reset().catch((error) =>
+      addNotification("Failed to reset shortcut: " + (error?.message || error), "error")
+    );
 
   // Global keydown listener for recording
   useEffect(() => {
@@ -74,14 +84,7 @@ const KeymapsSettings = ({ onOpenKeymapsModal }) => {
     return () => window.removeEventListener("keydown", handleKeyDown, true);
   }, [isRecording, handleCancelRecording]);
 
-  const isModified = (actionId) => {
-    const current = keymaps[actionId];
-    const original = DEFAULT_KEYMAPS[actionId];
-    return (
-      current.key !== original.key ||
-      JSON.stringify(current.modifiers) !== JSON.stringify(original.modifiers)
-    );
-  };
+  const isModified = (actionId) => Boolean(keymaps[actionId]?.customized);
 
   const hasAnyModifications = Object.keys(keymaps).some(isModified);
 
@@ -125,7 +128,7 @@ const KeymapsSettings = ({ onOpenKeymapsModal }) => {
           )}
           {hasAnyModifications && (
             <button
-              onClick={resetKeymaps}
+              onClick={() => handleReset(resetKeymaps)}
               className="px-3 py-1.5 text-xs font-medium text-amber-400 hover:text-amber-300 bg-amber-500/10 hover:bg-amber-500/20 rounded-lg border border-amber-500/20 transition-colors"
             >
               Reset All
@@ -267,7 +270,7 @@ const KeymapsSettings = ({ onOpenKeymapsModal }) => {
                           {/* Reset button (only if modified) */}
                           {modified && (
                             <button
-                              onClick={() => resetKeymap(actionId)}
+                              onClick={() => handleReset(() => resetKeymap(actionId))}
                               className="p-1 text-text-muted hover:text-text-secondary transition-colors"
                               title="Reset to default"
                             >
@@ -0,0 +1,113 @@
+import { useState } from "react";
+import useSettingsStore from "../../store/settingsStore";
+import useUIStore from "../../store/uiStore";
+import { clearLinkPreviews } from "../../utils/linkPreviews";
+
+const Switch = ({ checked, onChange, title }) => (
+  <button
+    onClick={() => onChange(!checked)}
+    className={`relative ml-4 w-14 h-7 rounded-full transition-all duration-200 shrink-0 ${
+      checked ? "bg-accent shadow-lg shadow-accent/30" : "bg-overlay-light hover:bg-overlay-medium"
+    }`}
+    aria-checked={checked}
+    role="switch"
+    title={title}
+  >
+    <span
+      className={`absolute top-1 left-1 w-5 h-5 bg-white rounded-full shadow-md transition-transform duration-200 ${
+        checked ? "translate-x-7" : "translate-x-0"
+      }`}
+    />
+  </button>
+);
+
+const LinkPreviewSettings = () => {
+  const { linkPreviews, setLinkPreviewSetting } = useSettingsStore();
+  const addNotification = useUIStore((state) => state.addNotification);
+  const [allowlistDraft, setAllowlistDraft] = useState(null);
+  const [isClearing, setIsClearing] = useState(false);
+
+  const update = (key, value) =>
+    setLinkPreviewSetting(key, value).catch((error) => {
+      console.error("Failed to save link preview setting:", error);
+      addNotification("Failed to save link preview setting", "error");
+    });
+
+  const handleClear = async () => {
+    setIsClearing(true);
+    try {
+      await clearLinkPreviews();
+      addNotification("Link preview cache cleared", "success");
+    } catch (error) {
+      addNotification(`Failed to clear link previews: ${error?.message || error}`, "error");
+    } finally {
+      setIsClearing(false);
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center justify-between gap-4">
+        <div>
+          <p className="text-sm font-medium text-text-secondary">Show link previews</p>
+          <p className="text-xs text-text-muted mt-0.5">
+            Hovering a web link shows its page&apos;s title, description and image, and links on
+            a line of their own become cards.
+          </p>
+        </div>
+        <Switch
+          checked={linkPreviews.enabled}
+          onChange={(enabled) => update("enabled", enabled)}
+          title={linkPreviews.enabled ? "Turn off link previews" : "Turn on link previews"}
+        />
+      </div>
+
+      <div className="flex items-center justify-between gap-4 border-t border-overlay-subtle pt-4">
+        <div>
+          <p className="text-sm font-medium text-text-secondary">Offline</p>
+          <p className="text-xs text-text-muted mt-0.5">
+            Only show previews that were fetched before; no requests are made.
+          </p>
+        </div>
+        <Switch
+          checked={linkPreviews.offline}
+          onChange={(offline) => update("offline", offline)}
+          title={linkPreviews.offline ? "Fetch new previews" : "Stop fetching previews"}
+        />
+      </div>
+
+      <div className="space-y-2 border-t border-overlay-subtle pt-4">
+        <label className="block text-sm font-medium text-text-secondary">
+          Allowed sites (one host per line; empty allows all)
+        </label>
+        <textarea
+          value={allowlistDraft ?? linkPreviews.allowlist.join("\n")}
+          onChange={(e) => setAllowlistDraft(e.target.value)}
+          onBlur={() => {
+            if (allowlistDraft === null) return;
+            update("allowlist", allowlistDraft.split("\n")).finally(() => setAllowlistDraft(null));
+          }}
+          rows={3}
+          placeholder="github.com"
+          className="w-full px-3 py-2 text-sm font-mono rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent"
+        />
+        <p className="text-xs text-text-muted">Subdomains of an allowed host are allowed too.</p>
+      </div>
+
+      <div className="flex items-center justify-between gap-4 border-t border-overlay-subtle pt-4">
+        <p className="text-xs text-text-muted">
+          Previews and their images are cached for a week.
+        </p>
+        <button
+          onClick={handleClear}
+          disabled={isClearing}
+          className="px-3 py-1.5 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-secondary hover:text-text-primary hover:bg-overlay-light disabled:opacity-50 shrink-0"
+        >
+          {isClearing ? "Clearing…" : "Clear Cache"}
+        </button>
+      </div>
+    </div>
+  );
+};
+
+export default LinkPreviewSettings;
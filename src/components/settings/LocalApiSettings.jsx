@@ -0,0 +1,325 @@
+import { useCallback, useEffect, useState } from "react";
+import { writeText } from "@tauri-apps/plugin-clipboard-manager";
+import useSettingsStore from "../../store/settingsStore";
+import useUIStore from "../../store/uiStore";
+import {
+  getApiStatus,
+  getApiToken,
+  regenerateApiToken,
+  getClipperToken,
+  regenerateClipperToken,
+  getTasksFeedUrl,
+  regenerateTasksFeedToken,
+} from "../../utils/localApi";
+
+const Toggle = ({ label, description, checked, onChange }) => (
+  <div className="flex items-center justify-between">
+    <div>
+      <p className="text-sm font-medium text-text-secondary">{label}</p>
+      <p className="text-xs text-text-muted mt-0.5">{description}</p>
+    </div>
+    <button
+      onClick={() => onChange(!checked)}
+      className={`relative ml-4 w-14 h-7 rounded-full transition-all duration-200 shrink-0 ${
+        checked ? "bg-accent shadow-lg shadow-accent/30" : "bg-overlay-light hover:bg-overlay-medium"
+      }`}
+      aria-checked={checked}
+      role="switch"
+      title={label}
+    >
+      <span
+        className={`absolute top-1 left-1 w-5 h-5 bg-white rounded-full shadow-md transition-transform duration-200 ${
+          checked ? "translate-x-7" : "translate-x-0"
+        }`}
+      />
+    </button>
+  </div>
+);
+
+const LocalApiSettings = () => {
+  const apiSettings = useSettingsStore((state) => state.apiSettings);
+  const setApiSetting = useSettingsStore((state) => state.setApiSetting);
+  const addNotification = useUIStore((state) => state.addNotification);
+
+  const [status, setStatus] = useState(null);
+  const [token, setToken] = useState("");
+  const [showToken, setShowToken] = useState(false);
+  const [clipperToken, setClipperToken] = useState("");
+  const [tasksFeedUrl, setTasksFeedUrl] = useState("");
+
+  const refreshStatus = useCallback(() => {
+    getApiStatus()
+      .then(setStatus)
+      .catch((error) => console.error("Failed to get API status:", error));
+  }, []);
+
+  useEffect(() => {
+    refreshStatus();
+  }, [refreshStatus]);
+
+  useEffect(() => {
+    if (!apiSettings.enabled || token) return;
+    getApiToken()
+      .then(setToken)
+      .catch((error) => addNotification("Failed to read API token: " + error, "error"));
+  }, [apiSettings.enabled, token, addNotification]);
+
+  useEffect(() => {
+    if (!apiSettings.enabled || !apiSettings.clipper_enabled || clipperToken) return;
+    getClipperToken()
+      .then(setClipperToken)
+      .catch((error) => addNotification("Failed to read clipper token: " + error, "error"));
+  }, [apiSettings.enabled, apiSettings.clipper_enabled, clipperToken, addNotification]);
+
+  // The address embeds the port, so it's read again whenever that changes
+  useEffect(() => {
+    if (!apiSettings.enabled || !apiSettings.tasks_feed_enabled) return;
+    getTasksFeedUrl()
+      .then(setTasksFeedUrl)
+      .catch((error) => addNotification("Failed to read tasks calendar address: " + error, "error"));
+  }, [apiSettings.enabled, apiSettings.tasks_feed_enabled, apiSettings.port, addNotification]);
+
+  const baseUrl = status?.url || `http://127.0.0.1:${apiSettings.port}`;
+  const mcpUrl = `${baseUrl}/mcp`;
+
+  // The backend restarts the server before `set_setting` returns
+  const update = (key, value) =>
+    setApiSetting(key, value)
+      .then(refreshStatus)
+      .catch((error) => {
+        console.error("Failed to save API settings:", error);
+        addNotification("Failed to save API settings", "error");
+      });
+
+  const handleCopy = async (text, label) => {
+    try {
+      await writeText(text);
+      addNotification(`${label} copied`, "success", 1800);
+    } catch (error) {
+      addNotification("Failed to copy: " + error.message, "error");
+    }
+  };
+
+  const handleRegenerateClipper = async () => {
+    try {
+      setClipperToken(await regenerateClipperToken());
+      addNotification("Clipper token regenerated; pair your browser again", "success");
+    } catch (error) {
+      addNotification("Failed to regenerate token: " + error, "error");
+    }
+  };
+
+  const handleRegenerateTasksFeed = async () => {
+    try {
+      setTasksFeedUrl(await regenerateTasksFeedToken());
+      addNotification("Calendar address changed; subscribe again with the new one", "success");
+    } catch (error) {
+      addNotification("Failed to regenerate token: " + error, "error");
+    }
+  };
+
+  const handleRegenerate = async () => {
+    try {
+      setToken(await regenerateApiToken());
+      addNotification("API token regenerated; update your scripts", "success");
+    } catch (error) {
+      addNotification("Failed to regenerate token: " + error, "error");
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <Toggle
+        label="Enable local API"
+        description="Only reachable from this computer, and every request needs the token below."
+        checked={apiSettings.enabled}
+        onChange={(value) => update("enabled", value)}
+      />
+
+      <label className="block text-xs text-text-muted space-y-1">
+        <span>Port</span>
+        <input
+          type="number"
+          min={1024}
+          max={65535}
+          defaultValue={apiSettings.port}
+          onBlur={(e) => {
+            const port = Number.parseInt(e.target.value, 10);
+            if (!port || port === apiSettings.port) return;
+            update("port", port);
+          }}
+          className="w-40 px-3 py-2 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent"
+        />
+      </label>
+
+      {apiSettings.enabled && (
+        <>
+          <div className="text-xs">
+            {status?.running ? (
+              <span className="text-green-400">Listening on {status.url}</span>
+            ) : (
+              <span className="text-red-400">{status?.error || "Not running"}</span>
+            )}
+          </div>
+
+          <label className="block text-xs text-text-muted space-y-1">
+            <span>
+              Token (send as <code>Authorization: Bearer &lt;token&gt;</code>)
+            </span>
+            <div className="flex gap-2">
+              <input
+                type={showToken ? "text" : "password"}
+                value={token}
+                readOnly
+                className="flex-1 px-3 py-2 text-sm font-mono rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent"
+              />
+              <button
+                onClick={() => setShowToken((shown) => !shown)}
+                className="px-3 py-2 text-sm rounded-lg bg-overlay-light hover:bg-overlay-subtle text-text-primary transition-colors"
+              >
+                {showToken ? "Hide" : "Show"}
+              </button>
+              <button
+                onClick={() => handleCopy(token, "API token")}
+                disabled={!token}
+                className="px-3 py-2 text-sm rounded-lg bg-overlay-light hover:bg-overlay-subtle text-text-primary transition-colors disabled:opacity-50"
+              >
+                Copy
+              </button>
+              <button
+                onClick={handleRegenerate}
+                className="px-3 py-2 text-sm rounded-lg bg-overlay-light hover:bg-overlay-subtle text-text-primary transition-colors"
+              >
+                Regenerate
+              </button>
+            </div>
+          </label>
+
+          <p className="text-xs text-text-muted">
+            Endpoints: <code>GET /notes</code>, <code>GET /notes/&lt;path&gt;</code>,{" "}
+            <code>PUT /notes/&lt;path&gt;</code>, <code>POST /notes</code>,{" "}
+            <code>GET /search?q=</code>. Paths are relative to the open workspace.
+          </p>
+
+          <Toggle
+            label="MCP server"
+            description="Let AI assistants search and read notes through the Model Context Protocol."
+            checked={apiSettings.mcp_enabled}
+            onChange={(value) => update("mcp_enabled", value)}
+          />
+
+          {apiSettings.mcp_enabled && (
+            <>
+              <Toggle
+                label="Allow appending"
+                description="Assistants may add text to the end of existing notes. Nothing is ever overwritten."
+                checked={apiSettings.mcp_allow_append}
+                onChange={(value) => update("mcp_allow_append", value)}
+              />
+              <div className="flex items-center gap-2 text-xs text-text-muted">
+                <span>
+                  Add <code>{mcpUrl}</code>{" "}
+                  to your assistant as a Streamable HTTP server, with the same Authorization header.
+                </span>
+                <button
+                  onClick={() => handleCopy(mcpUrl, "MCP URL")}
+                  className="px-3 py-1.5 rounded-lg bg-overlay-light hover:bg-overlay-subtle text-text-primary transition-colors shrink-0"
+                >
+                  Copy URL
+                </button>
+              </div>
+            </>
+          )}
+
+          <Toggle
+            label="Web clipper"
+            description="Accept pages and selections from the Marky browser extension."
+            checked={apiSettings.clipper_enabled}
+            onChange={(value) => update("clipper_enabled", value)}
+          />
+
+          {apiSettings.clipper_enabled && (
+            <>
+              <label className="block text-xs text-text-muted space-y-1">
+                <span>Save clips in</span>
+                <input
+                  type="text"
+                  defaultValue={apiSettings.clipper_folder}
+                  onBlur={(e) => {
+                    const folder = e.target.value.trim();
+                    if (!folder || folder === apiSettings.clipper_folder) return;
+                    update("clipper_folder", folder);
+                  }}
+                  className="w-60 px-3 py-2 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent"
+                />
+              </label>
+
+              <label className="block text-xs text-text-muted space-y-1">
+                <span>
+                  Pairing token (paste into the extension with <code>{baseUrl}/clip</code>)
+                </span>
+                <div className="flex gap-2">
+                  <input
+                    type={showToken ? "text" : "password"}
+                    value={clipperToken}
+                    readOnly
+                    className="flex-1 px-3 py-2 text-sm font-mono rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent"
+                  />
+                  <button
+                    onClick={() => handleCopy(clipperToken, "Clipper token")}
+                    disabled={!clipperToken}
+                    className="px-3 py-2 text-sm rounded-lg bg-overlay-light hover:bg-overlay-subtle text-text-primary transition-colors disabled:opacity-50"
+                  >
+                    Copy
+                  </button>
+                  <button
+                    onClick={handleRegenerateClipper}
+                    className="px-3 py-2 text-sm rounded-lg bg-overlay-light hover:bg-overlay-subtle text-text-primary transition-colors"
+                  >
+                    Regenerate
+                  </button>
+                </div>
+              </label>
+            </>
+          )}
+
+          <Toggle
+            label="Tasks calendar"
+            description="Publish tasks with a due date as a calendar other apps can subscribe to."
+            checked={apiSettings.tasks_feed_enabled}
+            onChange={(value) => update("tasks_feed_enabled", value)}
+          />
+
+          {apiSettings.tasks_feed_enabled && (
+            <label className="block text-xs text-text-muted space-y-1">
+              <span>Subscription address (add to your calendar app; it includes its own token)</span>
+              <div className="flex gap-2">
+                <input
+                  type={showToken ? "text" : "password"}
+                  value={tasksFeedUrl}
+                  readOnly
+                  className="flex-1 px-3 py-2 text-sm font-mono rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent"
+                />
+                <button
+                  onClick={() => handleCopy(tasksFeedUrl, "Calendar address")}
+                  disabled={!tasksFeedUrl}
+                  className="px-3 py-2 text-sm rounded-lg bg-overlay-light hover:bg-overlay-subtle text-text-primary transition-colors disabled:opacity-50"
+                >
+                  Copy
+                </button>
+                <button
+                  onClick={handleRegenerateTasksFeed}
+                  className="px-3 py-2 text-sm rounded-lg bg-overlay-light hover:bg-overlay-subtle text-text-primary transition-colors"
+                >
+                  Regenerate
+                </button>
+              </div>
+            </label>
+          )}
+        </>
+      )}
+    </div>
+  );
+};
+
+export default LocalApiSettings;
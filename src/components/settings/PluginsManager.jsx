@@ -0,0 +1,152 @@
+import { useEffect, useState } from "react";
+import { writeText } from "@tauri-apps/plugin-clipboard-manager";
+import useUIStore from "../../store/uiStore";
+import { listPlugins, getPluginsFolder, enablePlugin } from "../../utils/plugins";
+
+const PERMISSION_LABELS = {
+  "notes:read": "Read notes",
+  "notes:write": "Create and edit notes",
+  events: "File change and save events",
+  network: "Network access",
+};
+
+const PluginsManager = () => {
+  const addNotification = useUIStore((state) => state.addNotification);
+  const [plugins, setPlugins] = useState([]);
+  const [pluginsFolder, setPluginsFolder] = useState("");
+  const [pendingId, setPendingId] = useState(null);
+
+  const refresh = async () => {
+    setPlugins(await listPlugins());
+  };
+
+  useEffect(() => {
+    refresh();
+    getPluginsFolder()
+      .then(setPluginsFolder)
+      .catch((error) => console.error("Failed to get plugins folder:", error));
+  }, []);
+
+  const handleToggle = async (plugin) => {
+    setPendingId(plugin.id);
+    try {
+      const updated = await enablePlugin(plugin.id, !plugin.enabled);
+      setPlugins((current) => current.map((p) => (p.id === updated.id ? updated : p)));
+    } catch (error) {
+      addNotification("Failed to update plugin: " + error, "error");
+    } finally {
+      setPendingId(null);
+    }
+  };
+
+  const handleCopyFolder = async () => {
+    try {
+      await writeText(pluginsFolder);
+      addNotification("Plugins folder path copied", "success", 1800);
+    } catch (error) {
+      addNotification("Failed to copy path: " + error.message, "error");
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center justify-between gap-4">
+        <div className="min-w-0">
+          <p className="text-sm font-medium text-text-secondary">Plugins folder</p>
+          <p className="text-xs text-text-muted mt-0.5 truncate" title={pluginsFolder}>
+            {pluginsFolder || "—"}
+          </p>
+        </div>
+        <div className="flex items-center gap-2 shrink-0">
+          <button
+            type="button"
+            onClick={handleCopyFolder}
+            disabled={!pluginsFolder}
+            className="px-3 py-1.5 text-xs rounded-lg border border-overlay-subtle text-text-secondary hover:text-text-primary hover:border-overlay-light disabled:opacity-50"
+          >
+            Copy Path
+          </button>
+          <button
+            type="button"
+            onClick={refresh}
+            className="px-3 py-1.5 text-xs rounded-lg border border-overlay-subtle text-text-secondary hover:text-text-primary hover:border-overlay-light"
+          >
+            Reload
+          </button>
+        </div>
+      </div>
+
+      {plugins.length === 0 ? (
+        <div className="border border-border rounded-xl bg-sidebar-bg/40 px-6 py-8 text-center text-text-muted">
+          <p className="text-lg font-semibold text-text-primary mb-2">No plugins installed</p>
+          <p className="text-sm">
+            Put each plugin in its own folder with a{" "}
+            <code className="text-accent">manifest.json</code> and its script.
+          </p>
+        </div>
+      ) : (
+        <div className="space-y-2">
+          {plugins.map((plugin) => {
+            const manifest = plugin.manifest;
+            return (
+              <div
+                key={plugin.id}
+                className="flex items-start justify-between gap-4 border border-overlay-subtle rounded-xl bg-sidebar-bg/40 p-4"
+              >
+                <div className="min-w-0">
+                  <p className="text-sm font-medium text-text-secondary">
+                    {manifest?.name || plugin.id}
+                    {manifest?.version && (
+                      <span className="ml-2 text-xs text-text-muted">v{manifest.version}</span>
+                    )}
+                  </p>
+                  {plugin.error ? (
+                    <p className="text-xs text-red-400 mt-0.5">{plugin.error}</p>
+                  ) : (
+                    <>
+                      {manifest.description && (
+                        <p className="text-xs text-text-muted mt-0.5">{manifest.description}</p>
+                      )}
+                      {manifest.permissions.length > 0 && (
+                        <div className="mt-2 flex flex-wrap gap-1">
+                          {manifest.permissions.map((permission) => (
+                            <span
+                              key={permission}
+                              className="text-[10px] px-1.5 py-0.5 rounded bg-overlay-subtle border border-overlay-subtle text-text-muted"
+                            >
+                              {PERMISSION_LABELS[permission] || permission}
+                            </span>
+                          ))}
+                        </div>
+                      )}
+                    </>
+                  )}
+                </div>
+                <button
+                  onClick={() => handleToggle(plugin)}
+                  disabled={!manifest || pendingId === plugin.id}
+                  className={`relative ml-4 w-14 h-7 rounded-full transition-all duration-200 shrink-0 disabled:opacity-50 ${
+                    plugin.enabled
+                      ? "bg-accent shadow-lg shadow-accent/30"
+                      : "bg-overlay-light hover:bg-overlay-medium"
+                  }`}
+                  aria-checked={plugin.enabled}
+                  role="switch"
+                  title={plugin.enabled ? "Disable plugin" : "Enable plugin"}
+                >
+                  <span
+                    className={`absolute top-1 left-1 w-5 h-5 bg-white rounded-full shadow-md transition-transform duration-200 ${
+                      plugin.enabled ? "translate-x-7" : "translate-x-0"
+                    }`}
+                  />
+                </button>
+              </div>
+            );
+          })}
+        </div>
+      )}
+    </div>
+  );
+};
+
+export default PluginsManager;
@@ -5,22 +5,45 @@ import EditorSettings from "./EditorSettings";
 import KeymapsSettings from "./KeymapsSettings";
 import ScheduledNotesManager from "./ScheduledNotesManager";
 import TagManager from "./TagManager";
+import PluginsManager from "./PluginsManager";
+import AutomationsManager from "./AutomationsManager";
+import FeedsManager from "./FeedsManager";
+import TaskSyncManager from "./TaskSyncManager";
+import DuplicateFilesManager from "./DuplicateFilesManager";
+import LocalApiSettings from "./LocalApiSettings";
+import SnippetSettings from "./SnippetSettings";
+import CustomMenuSettings from "./CustomMenuSettings";
+import LinkPreviewSettings from "./LinkPreviewSettings";
+import TelemetrySettings from "./TelemetrySettings";
+import WatcherStatus from "./WatcherStatus";
 import useNotesStore from "../../store/notesStore";
 import useSettingsStore from "../../store/settingsStore";
 import useUIStore from "../../store/uiStore";
 import {
   exportWorkspaceAsZip,
   restoreWorkspaceFromZip,
-  exportSettingsAsJson,
-  importSettingsFromJson,
+  exportProfile,
+  importProfile,
 } from "../../utils/backup";
 import { checkForAppUpdate, installAppUpdate } from "../../utils/appUpdater";
+import {
+  generateDiagnosticsBundle,
+  profileWorkspace,
+  validateFrontmatter,
+  verifyWorkspace,
+} from "../../utils/diagnostics";
+import { storeSecret, deleteSecret } from "../../utils/secrets";
+import { SMTP_PASSWORD_SECRET } from "../../utils/email";
+import { recordFeatureUse } from "../../utils/telemetry";
 import { UpdateIcon } from "../icons/AppUpdateIcon";
 
 const BatchExportModal = lazy(() => import("../modals/BatchExportModal"));
 
 const normalizeWorkspacePath = (value) => (value ? value.replace(/\\/g, "/") : "");
 
+// Spotlight indexing only happens on macOS
+const isMac = navigator.userAgent.includes("Macintosh");
+
 const SettingsPage = ({ onOpenKeymapsModal }) => {
   const [isBackingUp, setIsBackingUp] = useState(false);
   const [isRestoringBackup, setIsRestoringBackup] = useState(false);
@@ -28,15 +51,44 @@ const SettingsPage = ({ onOpenKeymapsModal }) => {
   const [isExportingSettings, setIsExportingSettings] = useState(false);
   const [isImportingSettings, setIsImportingSettings] = useState(false);
   const [showRestoreConfirm, setShowRestoreConfirm] = useState(false);
+  const [isGeneratingDiagnostics, setIsGeneratingDiagnostics] = useState(false);
+  const [isProfiling, setIsProfiling] = useState(false);
+  const [performanceProfile, setPerformanceProfile] = useState(null);
+  const [isVerifying, setIsVerifying] = useState(false);
+  const [integrityReport, setIntegrityReport] = useState(null);
+  const [isValidatingSchema, setIsValidatingSchema] = useState(false);
+  const [schemaReport, setSchemaReport] = useState(null);
+  const [subscriptionDraft, setSubscriptionDraft] = useState(null);
+  const [smtpPassword, setSmtpPassword] = useState("");
   const appUpdate = useUIStore((state) => state.appUpdate);
 
   const {
     openRecentOnStartup,
     setOpenRecentOnStartup,
+    secureDelete,
+    setSecureDelete,
+    deleteConfirmation,
+    setDeleteConfirmationLimit,
+    spotlightIndex,
+    setSpotlightIndex,
+    updateChannel,
+    setUpdateChannel,
+    logLevel,
+    setLogLevel,
+    locale,
+    setLocale,
+    notificationCategories,
+    setNotificationCategory,
+    calendarSubscriptions,
+    setCalendarSubscriptions,
+    emailSettings,
+    setEmailSetting,
     workspaceProfiles,
     setWorkspaceSettingsEnabled,
     getSettingsExportPayload,
     importSettingsPayload,
+    applyBackendSettings,
+    applyShortcuts,
   } = useSettingsStore();
   const rootFolderPath = useNotesStore((state) => state.rootFolderPath);
   const currentWorkspaceName = rootFolderPath
@@ -129,9 +181,14 @@ const SettingsPage = ({ onOpenKeymapsModal }) => {
 
     setIsExportingSettings(true);
     try {
-      const path = await exportSettingsAsJson(getSettingsExportPayload());
+      const { customTemplates } = useNotesStore.getState();
+      const path = await exportProfile({
+        settings: getSettingsExportPayload(),
+        templates: customTemplates,
+      });
       if (path) {
-        addNotification("Settings exported", "success");
+        recordFeatureUse("profile_export");
+        addNotification("Profile exported", "success");
       }
     } catch (err) {
       console.error("Export settings failed:", err);
@@ -146,11 +203,34 @@ const SettingsPage = ({ onOpenKeymapsModal }) => {
 
     setIsImportingSettings(true);
     try {
-      const imported = await importSettingsFromJson();
+      const imported = await importProfile();
       if (!imported) return;
+      if (imported.legacy) {
+        importSettingsPayload(imported.legacy);
+        addNotification("Settings imported successfully", "success");
+        return;
+      }
 
-      importSettingsPayload(imported);
-      addNotification("Settings imported successfully", "success");
+      recordFeatureUse("profile_import");
+      const { settings, templates } = imported.frontend || {};
+      if (settings) importSettingsPayload(settings);
+      applyBackendSettings(imported.settings);
+      applyShortcuts(imported.keymap);
+
+      // Templates already here under the same name are kept as they are
+      const { customTemplates, addCustomTemplate } = useNotesStore.getState();
+      const existing = new Set(customTemplates.map((template) => template.name));
+      const added = (Array.isArray(templates) ? templates : []).filter(
+        (template) =>
+          template?.name && template.content !== undefined && !existing.has(template.name)
+      );
+      added.forEach((template) => addCustomTemplate({ ...template, id: undefined }));
+      addNotification(
+        added.length > 0
+          ? `Profile imported with ${added.length} new template${added.length !== 1 ? "s" : ""}`
+          : "Profile imported",
+        "success"
+      );
     } catch (err) {
       console.error("Import settings failed:", err);
       addNotification("Import failed: " + err.message, "error");
@@ -159,10 +239,81 @@ const SettingsPage = ({ onOpenKeymapsModal }) => {
     }
   };
 
+  const handleGenerateDiagnostics = async () => {
+    const { addNotification } = useUIStore.getState();
+    setIsGeneratingDiagnostics(true);
+    try {
+      const savedPath = await generateDiagnosticsBundle();
+      if (savedPath) {
+        addNotification("Diagnostics saved. Attach the zip to your bug report.", "success");
+      }
+    } catch (err) {
+      console.error("Generating diagnostics failed:", err);
+      addNotification("Failed to generate diagnostics: " + (err?.message || err), "error");
+    } finally {
+      setIsGeneratingDiagnostics(false);
+    }
+  };
+
+  const handleProfileWorkspace = async () => {
+    if (!rootFolderPath) return;
+    setIsProfiling(true);
+    try {
+      setPerformanceProfile(await profileWorkspace(rootFolderPath));
+    } catch (err) {
+      console.error("Profiling workspace failed:", err);
+      useUIStore
+        .getState()
+        .addNotification("Failed to profile workspace: " + (err?.message || err), "error");
+    } finally {
+      setIsProfiling(false);
+    }
+  };
+
+  const handleVerifyWorkspace = async () => {
+    if (!rootFolderPath) return;
+    setIsVerifying(true);
+    try {
+      setIntegrityReport(await verifyWorkspace(rootFolderPath));
+    } catch (err) {
+      console.error("Verifying workspace failed:", err);
+      useUIStore
+        .getState()
+        .addNotification("Failed to verify workspace: " + (err?.message || err), "error");
+    } finally {
+      setIsVerifying(false);
+    }
+  };
+
+  const handleValidateFrontmatter = async () => {
+    if (!rootFolderPath) return;
+    setIsValidatingSchema(true);
+    try {
+      setSchemaReport(await validateFrontmatter(rootFolderPath));
+    } catch (err) {
+      console.error("Validating frontmatter failed:", err);
+      useUIStore
+        .getState()
+        .addNotification("Failed to validate frontmatter: " + (err?.message || err), "error");
+    } finally {
+      setIsValidatingSchema(false);
+    }
+  };
+
   const handleCheckForUpdates = async () => {
     await checkForAppUpdate({ silent: false });
   };
 
+  const handleUpdateChannelChange = async (channel) => {
+    try {
+      await setUpdateChannel(channel);
+      await checkForAppUpdate({ silent: true });
+    } catch (err) {
+      console.error("Failed to change update channel:", err);
+      useUIStore.getState().addNotification("Failed to change update channel", "error");
+    }
+  };
+
   const isCheckingForUpdates = appUpdate.status === "checking";
   const isUpdating = ["downloading", "installing"].includes(appUpdate.status);
 
@@ -207,6 +358,52 @@ const SettingsPage = ({ onOpenKeymapsModal }) => {
             </div>
           </section>
 
+          {/* Language Section */}
+          <section className="space-y-4">
+            <header>
+              <h2 className="text-xl font-semibold text-text-primary flex items-center gap-2">
+                <svg
+                  className="w-5 h-5 text-accent"
+                  fill="none"
+                  stroke="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M3 5h12M9 3v2m1.048 9.5A18.022 18.022 0 016.412 9m6.088 9h7M11 21l5-10 5 10M12.751 5C11.783 10.77 8.07 15.61 3 18.129"
+                  />
+                </svg>
+                Language
+              </h2>
+              <p className="text-sm text-text-muted mt-1">
+                Used for the app menu and messages from Marky&apos;s file operations.
+              </p>
+            </header>
+            <div className="bg-white/5 rounded-xl border border-white/10 p-6">
+              <div className="flex items-center justify-between gap-4">
+                <p className="text-sm font-medium text-text-secondary">Menu and message language</p>
+                <select
+                  value={locale}
+                  onChange={(e) =>
+                    setLocale(e.target.value).catch((err) => {
+                      console.error("Failed to change language:", err);
+                      useUIStore.getState().addNotification("Failed to change language", "error");
+                    })
+                  }
+                  className="px-3 py-2 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent"
+                >
+                  <option value="system">System default</option>
+                  <option value="en">English</option>
+                  <option value="de">Deutsch</option>
+                  <option value="es">Español</option>
+                  <option value="fr">Français</option>
+                </select>
+              </div>
+            </div>
+          </section>
+
           {/* Editor Section */}
           <section className="space-y-4">
             <header>
@@ -319,6 +516,121 @@ const SettingsPage = ({ onOpenKeymapsModal }) => {
             </div>
           </section>
 
+          {/* Automations Section */}
+          <section className="space-y-4">
+            <header>
+              <h2 className="text-xl font-semibold text-text-primary flex items-center gap-2">
+                <svg
+                  className="w-5 h-5 text-accent"
+                  fill="none"
+                  stroke="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M13 10V3L4 14h7v7l9-11h-7z"
+                  />
+                </svg>
+                Automations
+              </h2>
+              <p className="text-sm text-text-muted mt-1">
+                Rules that run when notes in this workspace are saved or created. They are
+                stored in the workspace, so they follow it to other machines.
+              </p>
+            </header>
+            <div className="bg-white/5 rounded-xl border border-white/10 p-6">
+              <AutomationsManager />
+            </div>
+          </section>
+
+          {/* Feeds Section */}
+          <section className="space-y-4">
+            <header>
+              <h2 className="text-xl font-semibold text-text-primary flex items-center gap-2">
+                <svg
+                  className="w-5 h-5 text-accent"
+                  fill="none"
+                  stroke="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M6 5c7.18 0 13 5.82 13 13M6 11a7 7 0 017 7m-6 0a1 1 0 11-2 0 1 1 0 012 0z"
+                  />
+                </svg>
+                Feeds
+              </h2>
+              <p className="text-sm text-text-muted mt-1">
+                RSS and Atom subscriptions for this workspace. New posts are appended to a note
+                while Marky is running, and each post is only added once.
+              </p>
+            </header>
+            <div className="bg-white/5 rounded-xl border border-white/10 p-6">
+              <FeedsManager />
+            </div>
+          </section>
+
+          {/* Task Sync Section */}
+          <section className="space-y-4">
+            <header>
+              <h2 className="text-xl font-semibold text-text-primary flex items-center gap-2">
+                <svg
+                  className="w-5 h-5 text-accent"
+                  fill="none"
+                  stroke="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M9 5H7a2 2 0 00-2 2v12a2 2 0 002 2h10a2 2 0 002-2V7a2 2 0 00-2-2h-2M9 5a2 2 0 002 2h2a2 2 0 002-2M9 5a2 2 0 012-2h2a2 2 0 012 2m-6 9l2 2 4-4"
+                  />
+                </svg>
+                Task Sync
+              </h2>
+              <p className="text-sm text-text-muted mt-1">
+                Mirror open tasks to a todo.txt file or a Reminders list every few minutes. Tasks
+                completed there are ticked in your notes.
+              </p>
+            </header>
+            <div className="bg-white/5 rounded-xl border border-white/10 p-6">
+              <TaskSyncManager />
+            </div>
+          </section>
+
+          {/* Plugins Section */}
+          <section className="space-y-4">
+            <header>
+              <h2 className="text-xl font-semibold text-text-primary flex items-center gap-2">
+                <svg
+                  className="w-5 h-5 text-accent"
+                  fill="none"
+                  stroke="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M11 4a2 2 0 114 0v1a1 1 0 001 1h3a1 1 0 011 1v3a1 1 0 01-1 1h-1a2 2 0 100 4h1a1 1 0 011 1v3a1 1 0 01-1 1h-3a1 1 0 01-1-1v-1a2 2 0 10-4 0v1a1 1 0 01-1 1H7a1 1 0 01-1-1v-3a1 1 0 00-1-1H4a2 2 0 110-4h1a1 1 0 001-1V7a1 1 0 011-1h3a1 1 0 001-1V4z"
+                  />
+                </svg>
+                Plugins
+              </h2>
+              <p className="text-sm text-text-muted mt-1">
+                Plugins run in a sandbox and only get the permissions their manifest asks for.
+              </p>
+            </header>
+            <div className="bg-white/5 rounded-xl border border-white/10 p-6">
+              <PluginsManager />
+            </div>
+          </section>
+
           {/* Backup Section */}
           {/* Workspace Section */}
           <section className="space-y-4">
@@ -381,7 +693,7 @@ const SettingsPage = ({ onOpenKeymapsModal }) => {
                   </p>
                   <p className="text-xs text-text-muted mt-0.5">
                     {rootFolderPath
-                      ? `Keep theme, editor behavior, and keymaps specific to ${currentWorkspaceName}.`
+                      ? `Keep theme and editor behavior specific to ${currentWorkspaceName}.`
                       : "Open a workspace to create a per-workspace settings profile."}
                   </p>
                 </div>
@@ -415,6 +727,469 @@ const SettingsPage = ({ onOpenKeymapsModal }) => {
                   />
                 </button>
               </div>
+
+              <div className="flex items-center justify-between gap-4 border-t border-overlay-subtle pt-4">
+                <div>
+                  <p className="text-sm font-medium text-text-secondary">Secure delete</p>
+                  <p className="text-xs text-text-muted mt-0.5">
+                    Overwrite files before deleting them. Skipped on copy-on-write disks such as
+                    APFS and Btrfs, where it can’t reach the original data.
+                  </p>
+                </div>
+                <button
+                  onClick={() => setSecureDelete(!secureDelete)}
+                  className={`relative ml-4 w-14 h-7 rounded-full transition-all duration-200 shrink-0 ${
+                    secureDelete
+                      ? "bg-accent shadow-lg shadow-accent/30"
+                      : "bg-overlay-light hover:bg-overlay-medium"
+                  }`}
+                  aria-checked={secureDelete}
+                  role="switch"
+                  title={secureDelete ? "Disable secure delete" : "Enable secure delete"}
+                >
+                  <span
+                    className={`absolute top-1 left-1 w-5 h-5 bg-white rounded-full shadow-md transition-transform duration-200 ${
+                      secureDelete ? "translate-x-7" : "translate-x-0"
+                    }`}
+                  />
+                </button>
+              </div>
+
+              <div className="flex items-center justify-between gap-4 border-t border-overlay-subtle pt-4">
+                <div>
+                  <p className="text-sm font-medium text-text-secondary">Confirm large deletes</p>
+                  <p className="text-xs text-text-muted mt-0.5">
+                    Folders with more files or data than this show what's on disk before they can
+                    be deleted. Deleting a workspace always does. 0 turns a limit off.
+                  </p>
+                </div>
+                <div className="flex items-center gap-3 shrink-0">
+                  {[
+                    ["files", "files"],
+                    ["megabytes", "MB"],
+                  ].map(([key, unit]) => (
+                    <label key={key} className="flex items-center gap-1.5 text-xs text-text-muted">
+                      <input
+                        type="number"
+                        min={0}
+                        defaultValue={deleteConfirmation[key]}
+                        onBlur={(e) => {
+                          const value = Math.max(0, Number.parseInt(e.target.value, 10) || 0);
+                          if (value === deleteConfirmation[key]) return;
+                          setDeleteConfirmationLimit(key, value).catch((err) => {
+                            console.error("Failed to save delete confirmation limit:", err);
+                            useUIStore
+                              .getState()
+                              .addNotification("Failed to save delete confirmation limit", "error");
+                          });
+                        }}
+                        className="w-20 px-2 py-1.5 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent"
+                      />
+                      {unit}
+                    </label>
+                  ))}
+                </div>
+              </div>
+
+              {isMac && (
+                <div className="flex items-center justify-between gap-4 border-t border-overlay-subtle pt-4">
+                  <div>
+                    <p className="text-sm font-medium text-text-secondary">Show notes in Spotlight</p>
+                    <p className="text-xs text-text-muted mt-0.5">
+                      Index the notes in your workspaces so system-wide search finds them and opens
+                      them in Marky. Encrypted notes are left out.
+                    </p>
+                  </div>
+                  <button
+                    onClick={() => setSpotlightIndex(!spotlightIndex)}
+                    className={`relative ml-4 w-14 h-7 rounded-full transition-all duration-200 shrink-0 ${
+                      spotlightIndex
+                        ? "bg-accent shadow-lg shadow-accent/30"
+                        : "bg-overlay-light hover:bg-overlay-medium"
+                    }`}
+                    aria-checked={spotlightIndex}
+                    role="switch"
+                    title={spotlightIndex ? "Remove notes from Spotlight" : "Show notes in Spotlight"}
+                  >
+                    <span
+                      className={`absolute top-1 left-1 w-5 h-5 bg-white rounded-full shadow-md transition-transform duration-200 ${
+                        spotlightIndex ? "translate-x-7" : "translate-x-0"
+                      }`}
+                    />
+                  </button>
+                </div>
+              )}
+            </div>
+          </section>
+
+          {/* Notifications Section */}
+          <section className="space-y-4">
+            <header>
+              <h2 className="text-xl font-semibold text-text-primary flex items-center gap-2">
+                <svg
+                  className="w-5 h-5 text-accent"
+                  fill="none"
+                  stroke="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M15 17h5l-1.405-1.405A2.032 2.032 0 0118 14.158V11a6.002 6.002 0 00-4-5.659V5a2 2 0 10-4 0v.341C7.67 6.165 6 8.388 6 11v3.159c0 .538-.214 1.055-.595 1.436L4 17h5m6 0v1a3 3 0 11-6 0v-1m6 0H9"
+                  />
+                </svg>
+                Notifications
+              </h2>
+              <p className="text-sm text-text-muted mt-1">
+                System notifications shown while Marky is in the background.
+              </p>
+            </header>
+            <div className="bg-white/5 rounded-xl border border-white/10 p-6 space-y-4">
+              {[
+                [
+                  "external_changes",
+                  "Notes changed outside Marky",
+                  "When another app or a sync client edits a note in an open workspace.",
+                ],
+                [
+                  "operations",
+                  "Backups and exports",
+                  "When a workspace backup or batch export finishes.",
+                ],
+                [
+                  "sync_conflicts",
+                  "Sync conflicts",
+                  "When syncing leaves two versions of a note to reconcile.",
+                ],
+                [
+                  "reminders",
+                  "Reminders",
+                  "When a remind: time or @remind(…) in a note comes due.",
+                ],
+                [
+                  "badge",
+                  "Dock badge",
+                  "Counts tasks due today or overdue, or shows a dot while notes have unsaved changes.",
+                ],
+              ].map(([category, label, description]) => {
+                const enabled = notificationCategories?.[category] !== false;
+                return (
+                  <div key={category} className="flex items-center justify-between">
+                    <div>
+                      <p className="text-sm font-medium text-text-secondary">{label}</p>
+                      <p className="text-xs text-text-muted mt-0.5">{description}</p>
+                    </div>
+                    <button
+                      onClick={() => setNotificationCategory(category, !enabled)}
+                      className={`relative ml-4 w-14 h-7 rounded-full transition-all duration-200 shrink-0 ${
+                        enabled
+                          ? "bg-accent shadow-lg shadow-accent/30"
+                          : "bg-overlay-light hover:bg-overlay-medium"
+                      }`}
+                      aria-checked={enabled}
+                      role="switch"
+                      title={enabled ? `Mute ${label.toLowerCase()}` : `Unmute ${label.toLowerCase()}`}
+                    >
+                      <span
+                        className={`absolute top-1 left-1 w-5 h-5 bg-white rounded-full shadow-md transition-transform duration-200 ${
+                          enabled ? "translate-x-7" : "translate-x-0"
+                        }`}
+                      />
+                    </button>
+                  </div>
+                );
+              })}
+            </div>
+          </section>
+
+          {/* Calendar Section */}
+          <section className="space-y-4">
+            <header>
+              <h2 className="text-xl font-semibold text-text-primary flex items-center gap-2">
+                <svg
+                  className="w-5 h-5 text-accent"
+                  fill="none"
+                  stroke="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M8 7V3m8 4V3m-9 8h10M5 21h14a2 2 0 002-2V7a2 2 0 00-2-2H5a2 2 0 00-2 2v12a2 2 0 002 2z"
+                  />
+                </svg>
+                Calendar
+              </h2>
+              <p className="text-sm text-text-muted mt-1">
+                Calendars to pick today&apos;s meetings from when creating a meeting note.
+              </p>
+            </header>
+            <div className="bg-white/5 rounded-xl border border-white/10 p-6 space-y-3">
+              <label className="block text-sm font-medium text-text-secondary">
+                Subscriptions (one ICS URL or file path per line)
+              </label>
+              <textarea
+                value={subscriptionDraft ?? calendarSubscriptions.join("\n")}
+                onChange={(e) => setSubscriptionDraft(e.target.value)}
+                onBlur={() => {
+                  if (subscriptionDraft === null) return;
+                  setCalendarSubscriptions(subscriptionDraft.split("\n"))
+                    .catch((err) => {
+                      console.error("Failed to save calendar subscriptions:", err);
+                      useUIStore
+                        .getState()
+                        .addNotification("Failed to save calendar subscriptions", "error");
+                    })
+                    .finally(() => setSubscriptionDraft(null));
+                }}
+                rows={3}
+                placeholder="webcal://example.com/calendar.ics"
+                className="w-full px-3 py-2 text-sm font-mono rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent"
+              />
+              <p className="text-xs text-text-muted">
+                Meeting notes use a custom template with &quot;meeting&quot; in its name, a
+                Meeting template in the templates folder, or the built-in one. Templates can use{" "}
+                {"{{title}}, {{date}}, {{time}}, {{location}} and {{attendees}}"}.
+              </p>
+            </div>
+          </section>
+
+          {/* Link Previews Section */}
+          <section className="space-y-4">
+            <header>
+              <h2 className="text-xl font-semibold text-text-primary flex items-center gap-2">
+                <svg
+                  className="w-5 h-5 text-accent"
+                  fill="none"
+                  stroke="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M13.828 10.172a4 4 0 00-5.656 0l-4 4a4 4 0 105.656 5.656l1.102-1.101m-.758-4.899a4 4 0 005.656 0l4-4a4 4 0 00-5.656-5.656l-1.1 1.1"
+                  />
+                </svg>
+                Link Previews
+              </h2>
+              <p className="text-sm text-text-muted mt-1">
+                Page details for web links in the preview, fetched by Marky and kept on this device.
+              </p>
+            </header>
+            <div className="bg-white/5 rounded-xl border border-white/10 p-6">
+              <LinkPreviewSettings />
+            </div>
+          </section>
+
+          {/* Snippets Section */}
+          <section className="space-y-4">
+            <header>
+              <h2 className="text-xl font-semibold text-text-primary flex items-center gap-2">
+                <svg
+                  className="w-5 h-5 text-accent"
+                  fill="none"
+                  stroke="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M10 20l4-16m4 4l4 4-4 4M6 16l-4-4 4-4"
+                  />
+                </svg>
+                Snippets
+              </h2>
+              <p className="text-sm text-text-muted mt-1">
+                Abbreviations that expand into longer text, like a signature or a meeting outline.
+              </p>
+            </header>
+            <div className="bg-white/5 rounded-xl border border-white/10 p-6">
+              <SnippetSettings />
+            </div>
+          </section>
+
+          {/* Custom Menu Section */}
+          <section className="space-y-4">
+            <header>
+              <h2 className="text-xl font-semibold text-text-primary flex items-center gap-2">
+                <svg
+                  className="w-5 h-5 text-accent"
+                  fill="none"
+                  stroke="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M4 6h16M4 12h16M4 18h7"
+                  />
+                </svg>
+                Custom Menu
+              </h2>
+              <p className="text-sm text-text-muted mt-1">
+                Your own menu bar entries for opening a note, running an automation, or opening a link.
+              </p>
+            </header>
+            <div className="bg-white/5 rounded-xl border border-white/10 p-6">
+              <CustomMenuSettings />
+            </div>
+          </section>
+
+          {/* Email Section */}
+          <section className="space-y-4">
+            <header>
+              <h2 className="text-xl font-semibold text-text-primary flex items-center gap-2">
+                <svg
+                  className="w-5 h-5 text-accent"
+                  fill="none"
+                  stroke="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M3 8l7.89 5.26a2 2 0 002.22 0L21 8M5 19h14a2 2 0 002-2V7a2 2 0 00-2-2H5a2 2 0 00-2 2v10a2 2 0 002 2z"
+                  />
+                </svg>
+                Email
+              </h2>
+              <p className="text-sm text-text-muted mt-1">
+                How Email Note delivers: as a draft in your mail app, or sent directly over SMTP.
+              </p>
+            </header>
+            <div className="bg-white/5 rounded-xl border border-white/10 p-6 space-y-4">
+              <div className="flex items-center justify-between gap-4">
+                <p className="text-sm font-medium text-text-secondary">Delivery</p>
+                <select
+                  value={emailSettings.method}
+                  onChange={(e) =>
+                    setEmailSetting("method", e.target.value).catch((err) => {
+                      console.error("Failed to change email delivery:", err);
+                      useUIStore.getState().addNotification("Failed to save email settings", "error");
+                    })
+                  }
+                  className="px-3 py-2 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent"
+                >
+                  <option value="client">Open a draft in the mail app</option>
+                  <option value="smtp">Send with SMTP</option>
+                </select>
+              </div>
+              {emailSettings.method === "smtp" && (
+                <div className="grid grid-cols-1 md:grid-cols-2 gap-3">
+                  {[
+                    { key: "smtp_host", label: "Server", placeholder: "smtp.example.com" },
+                    { key: "smtp_port", label: "Port", placeholder: "587" },
+                    { key: "smtp_username", label: "Username", placeholder: "me@example.com" },
+                    { key: "from_address", label: "From address", placeholder: "me@example.com" },
+                  ].map((field) => (
+                    <label key={field.key} className="block text-xs text-text-muted space-y-1">
+                      <span>{field.label}</span>
+                      <input
+                        type={field.key === "smtp_port" ? "number" : "text"}
+                        defaultValue={emailSettings[field.key]}
+                        placeholder={field.placeholder}
+                        onBlur={(e) => {
+                          const value =
+                            field.key === "smtp_port"
+                              ? Number.parseInt(e.target.value, 10) || 587
+                              : e.target.value.trim();
+                          if (value === emailSettings[field.key]) return;
+                          setEmailSetting(field.key, value).catch((err) => {
+                            console.error("Failed to save email settings:", err);
+                            useUIStore
+                              .getState()
+                              .addNotification("Failed to save email settings", "error");
+                          });
+                        }}
+                        className="w-full px-3 py-2 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent"
+                      />
+                    </label>
+                  ))}
+                  <label className="block text-xs text-text-muted space-y-1">
+                    <span>Security</span>
+                    <select
+                      value={emailSettings.smtp_security}
+                      onChange={(e) =>
+                        setEmailSetting("smtp_security", e.target.value).catch((err) =>
+                          console.error("Failed to save email settings:", err)
+                        )
+                      }
+                      className="w-full px-3 py-2 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent"
+                    >
+                      <option value="starttls">STARTTLS</option>
+                      <option value="tls">TLS</option>
+                      <option value="none">None</option>
+                    </select>
+                  </label>
+                  <label className="block text-xs text-text-muted space-y-1">
+                    <span>Password (stored in the system keychain)</span>
+                    <div className="flex gap-2">
+                      <input
+                        type="password"
+                        value={smtpPassword}
+                        onChange={(e) => setSmtpPassword(e.target.value)}
+                        placeholder="••••••••"
+                        className="flex-1 px-3 py-2 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent"
+                      />
+                      <button
+                        onClick={async () => {
+                          const { addNotification } = useUIStore.getState();
+                          try {
+                            if (smtpPassword) {
+                              await storeSecret(SMTP_PASSWORD_SECRET, smtpPassword);
+                              addNotification("SMTP password saved", "success");
+                            } else {
+                              await deleteSecret(SMTP_PASSWORD_SECRET);
+                              addNotification("SMTP password removed", "info");
+                            }
+                            setSmtpPassword("");
+                          } catch (err) {
+                            addNotification("Failed to save SMTP password: " + err, "error");
+                          }
+                        }}
+                        className="px-3 py-2 text-sm rounded-lg bg-overlay-light hover:bg-overlay-subtle text-text-primary transition-colors"
+                      >
+                        {smtpPassword ? "Save" : "Clear"}
+                      </button>
+                    </div>
+                  </label>
+                </div>
+              )}
+            </div>
+          </section>
+
+          {/* Local API Section */}
+          <section className="space-y-4">
+            <header>
+              <h2 className="text-xl font-semibold text-text-primary flex items-center gap-2">
+                <svg
+                  className="w-5 h-5 text-accent"
+                  fill="none"
+                  stroke="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M8 9l3 3-3 3m5 0h3M5 20h14a2 2 0 002-2V6a2 2 0 00-2-2H5a2 2 0 00-2 2v12a2 2 0 002 2z"
+                  />
+                </svg>
+                Local API
+              </h2>
+              <p className="text-sm text-text-muted mt-1">
+                Let scripts, launchers, and editors read, create, update, and search notes over
+                HTTP while Marky is running.
+              </p>
+            </header>
+            <div className="bg-white/5 rounded-xl border border-white/10 p-6">
+              <LocalApiSettings />
             </div>
           </section>
 
@@ -478,6 +1253,24 @@ const SettingsPage = ({ onOpenKeymapsModal }) => {
                   </button>
                 </div>
               </div>
+
+              <div className="flex items-center justify-between gap-4 border-t border-overlay-subtle pt-4 mt-4">
+                <div>
+                  <p className="text-sm font-medium text-text-secondary">Update channel</p>
+                  <p className="text-xs text-text-muted mt-0.5">
+                    Beta builds arrive earlier but may be less stable.
+                  </p>
+                </div>
+                <select
+                  value={updateChannel}
+                  onChange={(e) => handleUpdateChannelChange(e.target.value)}
+                  disabled={isUpdating}
+                  className="px-3 py-2 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent"
+                >
+                  <option value="stable">Stable</option>
+                  <option value="beta">Beta</option>
+                </select>
+              </div>
             </div>
           </section>
 
@@ -559,6 +1352,308 @@ const SettingsPage = ({ onOpenKeymapsModal }) => {
             </div>
           </section>
 
+          <section className="space-y-4">
+            <header>
+              <h2 className="text-xl font-semibold text-text-primary flex items-center gap-2">
+                <svg
+                  className="w-5 h-5 text-accent"
+                  fill="none"
+                  stroke="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M9 5H7a2 2 0 00-2 2v12a2 2 0 002 2h10a2 2 0 002-2V7a2 2 0 00-2-2h-2M9 5a2 2 0 002 2h2a2 2 0 002-2M9 5a2 2 0 012-2h2a2 2 0 012 2m-6 9l2 2 4-4"
+                  />
+                </svg>
+                Diagnostics
+              </h2>
+              <p className="text-sm text-text-muted mt-1">
+                Collect details that help track down a bug.
+              </p>
+            </header>
+            <div className="bg-white/5 rounded-xl border border-white/10 p-6">
+              <div className="flex items-center justify-between gap-4">
+                <div>
+                  <p className="text-sm text-text-secondary">
+                    Save a diagnostics bundle to attach to a bug report.
+                  </p>
+                  <p className="text-xs text-text-muted mt-1">
+                    Includes app and OS versions, recent errors, crash reports, and note counts. No
+                    note names, paths, or contents.
+                  </p>
+                </div>
+                <button
+                  onClick={handleGenerateDiagnostics}
+                  disabled={isGeneratingDiagnostics}
+                  className={`px-4 py-2 rounded-lg font-medium text-sm transition-all flex items-center gap-2 shrink-0 border ${
+                    isGeneratingDiagnostics
+                      ? "bg-overlay-light text-text-muted cursor-not-allowed border-overlay-subtle"
+                      : "bg-overlay-subtle hover:bg-overlay-light text-text-primary border-overlay-subtle"
+                  }`}
+                >
+                  {isGeneratingDiagnostics ? "Generating..." : "Generate Diagnostics"}
+                </button>
+              </div>
+
+              <div className="flex items-center justify-between gap-4 border-t border-overlay-subtle pt-4 mt-4">
+                <div>
+                  <p className="text-sm font-medium text-text-secondary">Log level</p>
+                  <p className="text-xs text-text-muted mt-0.5">
+                    How much Marky writes to its log files. Raise it while reproducing a bug.
+                  </p>
+                </div>
+                <select
+                  value={logLevel}
+                  onChange={(e) =>
+                    setLogLevel(e.target.value).catch((err) => {
+                      console.error("Failed to change log level:", err);
+                      useUIStore.getState().addNotification("Failed to change log level", "error");
+                    })
+                  }
+                  className="px-3 py-2 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent"
+                >
+                  <option value="error">Errors only</option>
+                  <option value="warn">Warnings</option>
+                  <option value="info">Info</option>
+                  <option value="debug">Debug</option>
+                  <option value="trace">Trace</option>
+                </select>
+              </div>
+
+              <div className="border-t border-overlay-subtle pt-4 mt-4">
+                <WatcherStatus />
+              </div>
+
+              <div className="border-t border-overlay-subtle pt-4 mt-4">
+                <div className="flex items-center justify-between gap-4">
+                  <div>
+                    <p className="text-sm font-medium text-text-secondary">Workspace performance</p>
+                    <p className="text-xs text-text-muted mt-0.5">
+                      Time scanning, indexing, and watching this workspace and find folders that
+                      slow it down.
+                    </p>
+                  </div>
+                  <button
+                    onClick={handleProfileWorkspace}
+                    disabled={isProfiling || !rootFolderPath}
+                    className={`px-4 py-2 rounded-lg font-medium text-sm transition-all flex items-center gap-2 shrink-0 border ${
+                      isProfiling || !rootFolderPath
+                        ? "bg-overlay-light text-text-muted cursor-not-allowed border-overlay-subtle"
+                        : "bg-overlay-subtle hover:bg-overlay-light text-text-primary border-overlay-subtle"
+                    }`}
+                  >
+                    {isProfiling ? "Profiling..." : "Profile Workspace"}
+                  </button>
+                </div>
+
+                {performanceProfile && (
+                  <div className="mt-4 space-y-3 text-xs text-text-secondary">
+                    <div className="grid grid-cols-4 gap-2">
+                      {[
+                        ["Scan", performanceProfile.scan, "notes"],
+                        ["Index", performanceProfile.index, "notes"],
+                        ["Watcher", performanceProfile.watcher, "folders"],
+                        ["Full walk", performanceProfile.walk, "entries"],
+                      ].map(([label, phase, unit]) => (
+                        <div key={label} className="rounded-lg bg-overlay-subtle px-3 py-2">
+                          <p className="text-text-muted">{label}</p>
+                          <p className="text-sm font-medium text-text-primary">{phase.ms} ms</p>
+                          <p className="text-text-muted">
+                            {phase.count.toLocaleString()} {unit}
+                          </p>
+                        </div>
+                      ))}
+                    </div>
+
+                    {performanceProfile.hotspots.length === 0 ? (
+                      <p className="text-text-muted">No oversized folders found.</p>
+                    ) : (
+                      <ul className="space-y-1">
+                        {performanceProfile.hotspots.map((hotspot) => (
+                          <li
+                            key={hotspot.path}
+                            className="flex items-center justify-between gap-3 rounded-lg bg-overlay-subtle px-3 py-2"
+                          >
+                            <span className="font-mono truncate" title={hotspot.path}>
+                              {hotspot.path}
+                            </span>
+                            <span className="shrink-0 text-text-muted">
+                              {(hotspot.files + hotspot.directories).toLocaleString()} entries ·{" "}
+                              {hotspot.walk_ms} ms
+                              {hotspot.ignored ? " · ignored" : " · add to ignore globs?"}
+                            </span>
+                          </li>
+                        ))}
+                      </ul>
+                    )}
+                  </div>
+                )}
+              </div>
+
+              <div className="border-t border-overlay-subtle pt-4 mt-4">
+                <div className="flex items-center justify-between gap-4">
+                  <div>
+                    <p className="text-sm font-medium text-text-secondary">Workspace integrity</p>
+                    <p className="text-xs text-text-muted mt-0.5">
+                      Checksum every note and catch files damaged on disk or by sync since the
+                      last check.
+                    </p>
+                  </div>
+                  <button
+                    onClick={handleVerifyWorkspace}
+                    disabled={isVerifying || !rootFolderPath}
+                    className={`px-4 py-2 rounded-lg font-medium text-sm transition-all flex items-center gap-2 shrink-0 border ${
+                      isVerifying || !rootFolderPath
+                        ? "bg-overlay-light text-text-muted cursor-not-allowed border-overlay-subtle"
+                        : "bg-overlay-subtle hover:bg-overlay-light text-text-primary border-overlay-subtle"
+                    }`}
+                  >
+                    {isVerifying ? "Verifying..." : "Verify Workspace"}
+                  </button>
+                </div>
+
+                {integrityReport && (
+                  <div className="mt-4 space-y-3 text-xs text-text-secondary">
+                    <p className="text-text-muted">
+                      {integrityReport.checked.toLocaleString()} notes checked ·{" "}
+                      {integrityReport.added} new · {integrityReport.changed} edited ·{" "}
+                      {integrityReport.removed} removed
+                      {integrityReport.previous_check
+                        ? ` since ${new Date(integrityReport.previous_check).toLocaleString()}`
+                        : " · first check, checksums recorded"}
+                    </p>
+                    {integrityReport.issues.length === 0 ? (
+                      <p className="text-text-muted">No damaged notes found.</p>
+                    ) : (
+                      <ul className="space-y-1">
+                        {integrityReport.issues.map((issue) => (
+                          <li
+                            key={`${issue.path}-${issue.kind}`}
+                            className="flex items-center justify-between gap-3 rounded-lg bg-overlay-subtle px-3 py-2"
+                          >
+                            <span className="font-mono truncate" title={issue.path}>
+                              {issue.path.split(/[\\/]/).pop()}
+                            </span>
+                            <span className="shrink-0 text-red-400">{issue.detail}</span>
+                          </li>
+                        ))}
+                      </ul>
+                    )}
+                  </div>
+                )}
+              </div>
+
+              <div className="border-t border-overlay-subtle pt-4 mt-4">
+                <div className="flex items-center justify-between gap-4">
+                  <div>
+                    <p className="text-sm font-medium text-text-secondary">Frontmatter schema</p>
+                    <p className="text-xs text-text-muted mt-0.5">
+                      Check every note against the required fields, types, and allowed values in{" "}
+                      <code className="text-accent">.marky/schema.json</code>.
+                    </p>
+                  </div>
+                  <button
+                    onClick={handleValidateFrontmatter}
+                    disabled={isValidatingSchema || !rootFolderPath}
+                    className={`px-4 py-2 rounded-lg font-medium text-sm transition-all flex items-center gap-2 shrink-0 border ${
+                      isValidatingSchema || !rootFolderPath
+                        ? "bg-overlay-light text-text-muted cursor-not-allowed border-overlay-subtle"
+                        : "bg-overlay-subtle hover:bg-overlay-light text-text-primary border-overlay-subtle"
+                    }`}
+                  >
+                    {isValidatingSchema ? "Validating..." : "Validate Frontmatter"}
+                  </button>
+                </div>
+
+                {schemaReport && (
+                  <div className="mt-4 space-y-3 text-xs text-text-secondary">
+                    <p className="text-text-muted">
+                      {schemaReport.checked.toLocaleString()} notes checked ·{" "}
+                      {schemaReport.violations.length} violation
+                      {schemaReport.violations.length !== 1 ? "s" : ""}
+                    </p>
+                    {schemaReport.violations.length === 0 ? (
+                      <p className="text-text-muted">Every note matches the schema.</p>
+                    ) : (
+                      <ul className="space-y-1">
+                        {schemaReport.violations.map((violation, index) => (
+                          <li
+                            key={`${violation.path}-${violation.field}-${index}`}
+                            className="flex items-center justify-between gap-3 rounded-lg bg-overlay-subtle px-3 py-2"
+                          >
+                            <span className="font-mono truncate" title={violation.path}>
+                              {violation.path.split(/[\\/]/).pop()}
+                            </span>
+                            <span className="shrink-0 text-amber-400">{violation.message}</span>
+                          </li>
+                        ))}
+                      </ul>
+                    )}
+                  </div>
+                )}
+              </div>
+            </div>
+          </section>
+
+          <section className="space-y-4">
+            <header>
+              <h2 className="text-xl font-semibold text-text-primary flex items-center gap-2">
+                <svg
+                  className="w-5 h-5 text-accent"
+                  fill="none"
+                  stroke="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M9 19v-6a2 2 0 00-2-2H5a2 2 0 00-2 2v6a2 2 0 002 2h2a2 2 0 002-2zm0 0V9a2 2 0 012-2h2a2 2 0 012 2v10m-6 0a2 2 0 002 2h2a2 2 0 002-2m0 0V5a2 2 0 012-2h2a2 2 0 012 2v14a2 2 0 01-2 2h-2a2 2 0 01-2-2z"
+                  />
+                </svg>
+                Usage Statistics
+              </h2>
+              <p className="text-sm text-text-muted mt-1">
+                Optional, anonymous, and off unless you turn it on.
+              </p>
+            </header>
+            <div className="bg-white/5 rounded-xl border border-white/10 p-6">
+              <TelemetrySettings />
+            </div>
+          </section>
+
+          {/* Duplicate Files Section */}
+          <section className="space-y-4">
+            <header>
+              <h2 className="text-xl font-semibold text-text-primary flex items-center gap-2">
+                <svg
+                  className="w-5 h-5 text-accent"
+                  fill="none"
+                  stroke="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <path
+                    strokeLinecap="round"
+                    strokeLinejoin="round"
+                    strokeWidth={2}
+                    d="M8 16H6a2 2 0 01-2-2V6a2 2 0 012-2h8a2 2 0 012 2v2m-6 12h8a2 2 0 002-2v-8a2 2 0 00-2-2h-8a2 2 0 00-2 2v8a2 2 0 002 2z"
+                  />
+                </svg>
+                Duplicate Files
+              </h2>
+              <p className="text-sm text-text-muted mt-1">
+                Find identical notes and attachments left by imports or merged vaults. Keeping one
+                copy updates links to the others before they're deleted.
+              </p>
+            </header>
+            <div className="bg-white/5 rounded-xl border border-white/10 p-6">
+              <DuplicateFilesManager />
+            </div>
+          </section>
+
           {/* Batch Export Section */}
           <section className="space-y-4">
             <header>
@@ -634,18 +1729,18 @@ const SettingsPage = ({ onOpenKeymapsModal }) => {
                 Settings Portability
               </h2>
               <p className="text-sm text-text-muted mt-1">
-                Export your preferences and keyboard shortcuts, or restore them from a previous
-                export.
+                Move your setup to another machine or share it with a team as one profile file.
               </p>
             </header>
             <div className="bg-white/5 rounded-xl border border-white/10 p-6">
               <div className="flex items-center justify-between gap-4">
                 <div>
                   <p className="text-sm text-text-secondary">
-                    Save or load theme, editor preferences, and key bindings as a JSON file.
+                    Theme, editor preferences, key bindings, snippets, and custom templates.
                   </p>
                   <p className="text-xs text-text-muted mt-1">
-                    Useful for sharing settings between workspaces or machines.
+                    API, email, and calendar settings stay on this machine. Older settings exports
+                    can still be imported.
                   </p>
                 </div>
                 <div className="flex items-center gap-2 shrink-0">
@@ -666,7 +1761,7 @@ const SettingsPage = ({ onOpenKeymapsModal }) => {
                         d="M20 12v5a2 2 0 01-2 2H6a2 2 0 01-2-2v-5m12-4l-4-4m0 0L8 8m4-4v12"
                       />
                     </svg>
-                    {isImportingSettings ? "Importing..." : "Import Settings"}
+                    {isImportingSettings ? "Importing..." : "Import Profile"}
                   </button>
                   <button
                     onClick={handleExportSettings}
@@ -685,7 +1780,7 @@ const SettingsPage = ({ onOpenKeymapsModal }) => {
                         d="M4 16v1a3 3 0 003 3h10a3 3 0 003-3v-1m-4-4l-4 4m0 0l-4-4m4 4V4"
                       />
                     </svg>
-                    {isExportingSettings ? "Exporting..." : "Export Settings"}
+                    {isExportingSettings ? "Exporting..." : "Export Profile"}
                   </button>
                 </div>
               </div>
@@ -0,0 +1,90 @@
+import { useEffect, useState } from "react";
+import useUIStore from "../../store/uiStore";
+import { listSnippets, saveSnippets } from "../../utils/snippets";
+
+const inputClassName =
+  "w-full px-3 py-2 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-primary focus:outline-none focus:border-accent";
+
+// Edits a local copy of the snippet list and saves it whenever a field loses focus
+const SnippetSettings = () => {
+  const addNotification = useUIStore((state) => state.addNotification);
+  const [snippets, setSnippets] = useState([]);
+
+  useEffect(() => {
+    listSnippets()
+      .then(setSnippets)
+      .catch(() => addNotification("Failed to load snippets", "error"));
+  }, [addNotification]);
+
+  const save = (next) =>
+    saveSnippets(next)
+      .then((saved) => {
+        // Rows still being filled in (no key yet) stay on screen
+        setSnippets([...saved, ...next.filter((snippet) => !snippet.key.trim())]);
+      })
+      .catch(() => addNotification("Failed to save snippets", "error"));
+
+  const updateField = (index, field, value) => {
+    setSnippets((current) =>
+      current.map((snippet, idx) => (idx === index ? { ...snippet, [field]: value } : snippet))
+    );
+  };
+
+  const removeSnippet = (index) => save(snippets.filter((_, idx) => idx !== index));
+
+  return (
+    <div className="space-y-3">
+      {snippets.map((snippet, index) => (
+        <div key={index} className="grid grid-cols-[8rem_1fr_auto] gap-2 items-start">
+          <input
+            value={snippet.key}
+            onChange={(e) => updateField(index, "key", e.target.value)}
+            onBlur={() => save(snippets)}
+            placeholder=";sig"
+            aria-label="Snippet key"
+            className={`${inputClassName} font-mono`}
+          />
+          <div className="space-y-2">
+            <textarea
+              value={snippet.text}
+              onChange={(e) => updateField(index, "text", e.target.value)}
+              onBlur={() => save(snippets)}
+              rows={2}
+              placeholder="Text to insert"
+              aria-label="Snippet text"
+              className={`${inputClassName} font-mono`}
+            />
+            <input
+              value={snippet.description}
+              onChange={(e) => updateField(index, "description", e.target.value)}
+              onBlur={() => save(snippets)}
+              placeholder="Description (optional)"
+              aria-label="Snippet description"
+              className={inputClassName}
+            />
+          </div>
+          <button
+            onClick={() => removeSnippet(index)}
+            className="px-2 py-2 text-xs rounded-lg text-text-muted hover:text-red-400 hover:bg-overlay-light"
+            title="Remove snippet"
+          >
+            Remove
+          </button>
+        </div>
+      ))}
+      <button
+        onClick={() => setSnippets([...snippets, { key: "", text: "", description: "" }])}
+        className="px-3 py-1.5 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-secondary hover:text-text-primary hover:bg-overlay-light"
+      >
+        Add Snippet
+      </button>
+      <p className="text-xs text-text-muted">
+        Type a key in the editor and press Tab to expand it. Snippets can use{" "}
+        {"{{date}}, {{time}}, {{datetime}}, {{weekday}}, {{date:%d %B %Y}}, {{title}}"} and{" "}
+        {"{{cursor}}"} to place the cursor.
+      </p>
+    </div>
+  );
+};
+
+export default SnippetSettings;
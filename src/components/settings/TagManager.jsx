@@ -1,16 +1,8 @@
 import { useEffect, useMemo, useState } from "react";
-import useNotesStore from "../../store/notesStore";
+import useNotesStore, { normalizeTagValue as normalizeTag } from "../../store/notesStore";
 import useUIStore from "../../store/uiStore";
 import ConfirmDialog from "../modals/ConfirmDialog";
-
-const normalizeTag = (value) =>
-  (value || "")
-    .trim()
-    .replace(/^#+/, "")
-    .toLowerCase()
-    .replace(/[^a-z0-9_-]+/g, "-")
-    .replace(/-+/g, "-")
-    .replace(/^[-_]+|[-_]+$/g, "");
+import BulkTagModal from "../modals/BulkTagModal";
 
 const TagManager = () => {
   const tags = useNotesStore((state) => state.getAllTags());
@@ -22,6 +14,7 @@ const TagManager = () => {
   const [filter, setFilter] = useState("");
   const [isWorking, setIsWorking] = useState(false);
   const [pendingAction, setPendingAction] = useState(null);
+  const [showBulkTag, setShowBulkTag] = useState(false);
 
   useEffect(() => {
     if (!selectedTag && tags.length > 0) {
@@ -47,7 +40,7 @@ const TagManager = () => {
   const selectedTagCount =
     tags.find((entry) => entry.tag === normalizeTag(selectedTag))?.count ?? 0;
 
-  const requestAction = (action) => {
+  const requestAction = async (action) => {
     const source = normalizeTag(selectedTag);
     const target = normalizeTag(targetTag);
 
@@ -61,12 +54,30 @@ const TagManager = () => {
       return;
     }
 
+    // Dry run first so the confirmation says how many notes will be rewritten
+    let affected = selectedTagCount;
+    if (action !== "delete") {
+      try {
+        const preview = await applyTagOperation({
+          action,
+          sourceTag: source,
+          targetTag: target,
+          dryRun: true,
+        });
+        affected = preview.changedNotes;
+      } catch (error) {
+        addNotification(`Tag operation failed: ${error.message || error}`, "error", 5000);
+        return;
+      }
+    }
+    const scope = `${affected} note${affected !== 1 ? "s" : ""}`;
+
     const label =
       action === "delete"
-        ? `Delete tag #${source} from all notes?`
+        ? `Delete tag #${source} from ${scope}?`
         : action === "merge"
-          ? `Merge #${source} into #${target}?`
-          : `Rename #${source} to #${target}?`;
+          ? `Merge #${source} into #${target} in ${scope}?`
+          : `Rename #${source} to #${target} in ${scope}?`;
 
     const variant = action === "delete" ? "danger" : "warning";
 
@@ -93,7 +104,7 @@ const TagManager = () => {
       }
 
       const targetLabel = result.targetTag ? ` -> #${result.targetTag}` : "";
-      const message = `${action} #${result.sourceTag}${targetLabel}: ${result.changedNotes} note${result.changedNotes !== 1 ? "s" : ""} updated${result.failedNotes ? `, ${result.failedNotes} failed` : ""}`;
+      const message = `${action} #${result.sourceTag}${targetLabel}: ${result.changedNotes} note${result.changedNotes !== 1 ? "s" : ""} updated${result.failedNotes ? `, ${result.failedNotes} skipped` : ""}`;
       addNotification(message, result.failedNotes ? "warning" : "success", 5000);
 
       if (action !== "delete" && result.targetTag) {
@@ -108,6 +119,14 @@ const TagManager = () => {
     }
   };
 
+  const bulkTagModal = showBulkTag && (
+    <BulkTagModal
+      isOpen={showBulkTag}
+      onClose={() => setShowBulkTag(false)}
+      initialTag={normalizeTag(selectedTag)}
+    />
+  );
+
   if (tags.length === 0) {
     return (
       <div className="border border-border rounded-xl bg-sidebar-bg/40 px-6 py-8 text-center text-text-muted">
@@ -115,6 +134,14 @@ const TagManager = () => {
         <p className="text-sm">
           Add hashtags like <code className="text-accent">#todo</code> in notes to manage them here.
         </p>
+        <button
+          type="button"
+          onClick={() => setShowBulkTag(true)}
+          className="mt-4 px-3 py-2 rounded-lg text-sm font-medium border border-overlay-subtle bg-overlay-subtle text-text-primary hover:bg-overlay-light"
+        >
+          Tag Notes...
+        </button>
+        {bulkTagModal}
       </div>
     );
   }
@@ -245,12 +272,25 @@ const TagManager = () => {
           >
             Delete Tag From Notes
           </button>
+          <button
+            type="button"
+            onClick={() => setShowBulkTag(true)}
+            disabled={isWorking}
+            className={`px-3 py-2 rounded-lg text-sm font-medium border transition-colors ${
+              isWorking
+                ? "border-overlay-subtle bg-overlay-light text-text-muted cursor-not-allowed"
+                : "border-overlay-subtle bg-overlay-subtle text-text-primary hover:bg-overlay-light"
+            }`}
+          >
+            Tag Notes...
+          </button>
         </div>
 
         <p className="text-xs text-text-muted leading-relaxed">
-          Rename changes <code className="text-text-secondary">#old</code> to a new tag. Merge moves
-          all occurrences into an existing tag. Delete removes the tag token from notes. Changes are
-          written to disk immediately.
+          Rename changes <code className="text-text-secondary">#old</code> and{" "}
+          <code className="text-text-secondary">tags:</code> frontmatter entries to a new tag. Merge
+          moves all occurrences into an existing tag. Delete removes the tag from notes. Changes are
+          written to disk immediately; locked and encrypted notes are skipped.
         </p>
         <ConfirmDialog
           isOpen={Boolean(pendingAction)}
@@ -268,6 +308,7 @@ const TagManager = () => {
           onConfirm={executeAction}
           onCancel={() => setPendingAction(null)}
         />
+        {bulkTagModal}
       </div>
     </div>
   );
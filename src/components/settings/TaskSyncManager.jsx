@@ -0,0 +1,181 @@
+import { useEffect, useState } from "react";
+import { save } from "@tauri-apps/plugin-dialog";
+import useNotesStore from "../../store/notesStore";
+import useUIStore from "../../store/uiStore";
+import { getTaskSync, setTaskSync, getTaskSyncStatus, syncTasks } from "../../utils/taskSync";
+
+const inputClassName =
+  "w-full px-3 py-2 bg-overlay-subtle border border-overlay-subtle rounded-lg text-sm text-text-primary outline-none focus:border-accent/40";
+
+const emptyConfig = { todo_txt: null, reminders_list: null, include_undated: false };
+
+const describeStatus = (status) => {
+  if (!status?.last_synced) return "Not synced yet";
+  const synced = new Date(status.last_synced).toLocaleString();
+  if (status.last_error) return `Failed ${synced}: ${status.last_error}`;
+  return `Synced ${synced} · ${status.exported} exported, ${status.last_completed} completed elsewhere`;
+};
+
+const TaskSyncManager = () => {
+  const rootFolderPath = useNotesStore((state) => state.rootFolderPath);
+  const addNotification = useUIStore((state) => state.addNotification);
+
+  const [config, setConfig] = useState(emptyConfig);
+  const [status, setStatus] = useState(null);
+  const [isDirty, setIsDirty] = useState(false);
+  const [isSaving, setIsSaving] = useState(false);
+  const [isSyncing, setIsSyncing] = useState(false);
+
+  useEffect(() => {
+    if (!rootFolderPath) return;
+    getTaskSync(rootFolderPath)
+      .then((loaded) => {
+        setConfig(loaded);
+        setIsDirty(false);
+      })
+      .catch((error) => addNotification("Failed to load task sync: " + error, "error"));
+    getTaskSyncStatus(rootFolderPath).then(setStatus);
+  }, [rootFolderPath, addNotification]);
+
+  if (!rootFolderPath) {
+    return <p className="text-sm text-text-muted">Open a workspace to sync its tasks.</p>;
+  }
+
+  const update = (updates) => {
+    setConfig((current) => ({ ...current, ...updates }));
+    setIsDirty(true);
+  };
+
+  const handleBrowse = async () => {
+    const selected = await save({
+      defaultPath: config.todo_txt || "todo.txt",
+      filters: [{ name: "todo.txt", extensions: ["txt"] }],
+    });
+    if (!selected) return;
+    update({ todo_txt: typeof selected === "string" ? selected : selected.path });
+  };
+
+  const handleSave = async () => {
+    setIsSaving(true);
+    try {
+      setConfig(await setTaskSync(rootFolderPath, config));
+      setIsDirty(false);
+      addNotification("Task sync saved", "success", 1800);
+    } catch (error) {
+      addNotification(String(error), "error");
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const handleSync = async () => {
+    setIsSyncing(true);
+    try {
+      const updated = await syncTasks(rootFolderPath);
+      setStatus(updated);
+      if (updated.last_error) {
+        addNotification("Task sync failed: " + updated.last_error, "error");
+      } else {
+        addNotification("Tasks synced", "success", 1800);
+      }
+    } catch (error) {
+      addNotification("Task sync failed: " + error, "error");
+    } finally {
+      setIsSyncing(false);
+    }
+  };
+
+  const remindersAvailable = status?.reminders_available ?? false;
+
+  return (
+    <div className="space-y-4">
+      <label className="block">
+        <span className="block text-xs font-medium text-text-muted mb-1">todo.txt file</span>
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={config.todo_txt || ""}
+            onChange={(e) => update({ todo_txt: e.target.value || null })}
+            placeholder="Not exported"
+            className={inputClassName}
+          />
+          <button
+            type="button"
+            onClick={handleBrowse}
+            className="px-3 py-1.5 text-xs rounded-lg border border-overlay-subtle text-text-secondary hover:text-text-primary hover:border-overlay-light shrink-0"
+          >
+            Browse
+          </button>
+        </div>
+      </label>
+
+      <label className="block">
+        <span className="block text-xs font-medium text-text-muted mb-1">Reminders list</span>
+        <input
+          type="text"
+          value={config.reminders_list || ""}
+          onChange={(e) => update({ reminders_list: e.target.value || null })}
+          placeholder={remindersAvailable ? "Not synced, e.g. Marky" : "Only available on macOS"}
+          disabled={!remindersAvailable && !config.reminders_list}
+          className={`${inputClassName} disabled:opacity-50`}
+        />
+      </label>
+
+      <div className="flex items-center justify-between">
+        <div>
+          <p className="text-sm font-medium text-text-secondary">Include tasks without a date</p>
+          <p className="text-xs text-text-muted mt-0.5">
+            Otherwise only tasks with a due date are exported.
+          </p>
+        </div>
+        <button
+          onClick={() => update({ include_undated: !config.include_undated })}
+          className={`relative ml-4 w-14 h-7 rounded-full transition-all duration-200 shrink-0 ${
+            config.include_undated
+              ? "bg-accent shadow-lg shadow-accent/30"
+              : "bg-overlay-light hover:bg-overlay-medium"
+          }`}
+          aria-checked={config.include_undated}
+          role="switch"
+          title="Include tasks without a date"
+        >
+          <span
+            className={`absolute top-1 left-1 w-5 h-5 bg-white rounded-full shadow-md transition-transform duration-200 ${
+              config.include_undated ? "translate-x-7" : "translate-x-0"
+            }`}
+          />
+        </button>
+      </div>
+
+      <div className="flex flex-wrap items-center gap-2">
+        <span
+          className={`text-xs flex-1 min-w-0 truncate ${
+            status?.last_error ? "text-red-400" : "text-text-muted"
+          }`}
+          title={describeStatus(status)}
+        >
+          {describeStatus(status)}
+        </span>
+        <button
+          type="button"
+          onClick={handleSave}
+          disabled={!isDirty || isSaving}
+          className="px-3 py-1.5 text-xs rounded-lg bg-accent text-white hover:bg-accent/90 disabled:opacity-50"
+        >
+          {isSaving ? "Saving..." : "Save"}
+        </button>
+        <button
+          type="button"
+          onClick={handleSync}
+          disabled={isDirty || isSyncing || (!config.todo_txt && !config.reminders_list)}
+          title={isDirty ? "Save your changes first" : undefined}
+          className="px-3 py-1.5 text-xs rounded-lg border border-overlay-subtle text-text-secondary hover:text-text-primary hover:border-overlay-light disabled:opacity-50"
+        >
+          {isSyncing ? "Syncing..." : "Sync Now"}
+        </button>
+      </div>
+    </div>
+  );
+};
+
+export default TaskSyncManager;
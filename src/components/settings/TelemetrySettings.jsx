@@ -0,0 +1,109 @@
+import { useCallback, useEffect, useState } from "react";
+import useSettingsStore from "../../store/settingsStore";
+import useUIStore from "../../store/uiStore";
+import { clearTelemetry, getTelemetryPreview } from "../../utils/telemetry";
+
+const TelemetrySettings = () => {
+  const { telemetryEnabled, setTelemetryEnabled } = useSettingsStore();
+  const addNotification = useUIStore((state) => state.addNotification);
+  const [report, setReport] = useState(null);
+
+  const refresh = useCallback(() => {
+    getTelemetryPreview()
+      .then(setReport)
+      .catch(() => setReport(null));
+  }, []);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh, telemetryEnabled]);
+
+  const handleToggle = () => {
+    setTelemetryEnabled(!telemetryEnabled)
+      .then(refresh)
+      .catch((error) => {
+        console.error("Failed to save telemetry setting:", error);
+        addNotification("Failed to save usage statistics setting", "error");
+      });
+  };
+
+  const handleClear = async () => {
+    try {
+      await clearTelemetry();
+      refresh();
+      addNotification("Usage statistics cleared", "success");
+    } catch (error) {
+      addNotification(`Failed to clear usage statistics: ${error?.message || error}`, "error");
+    }
+  };
+
+  const handleCopy = async () => {
+    try {
+      await navigator.clipboard.writeText(JSON.stringify(report, null, 2));
+      addNotification("Report copied", "success");
+    } catch (error) {
+      addNotification(`Failed to copy the report: ${error?.message || error}`, "error");
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center justify-between gap-4">
+        <div>
+          <p className="text-sm font-medium text-text-secondary">Share anonymous usage statistics</p>
+          <p className="text-xs text-text-muted mt-0.5">
+            Counts how often features are used and how long saves, searches, and folder scans take,
+            to decide what to work on next. Never note names, paths, contents, or searches.
+          </p>
+        </div>
+        <button
+          onClick={handleToggle}
+          className={`relative ml-4 w-14 h-7 rounded-full transition-all duration-200 shrink-0 ${
+            telemetryEnabled
+              ? "bg-accent shadow-lg shadow-accent/30"
+              : "bg-overlay-light hover:bg-overlay-medium"
+          }`}
+          aria-checked={telemetryEnabled}
+          role="switch"
+          title={telemetryEnabled ? "Stop collecting usage statistics" : "Collect usage statistics"}
+        >
+          <span
+            className={`absolute top-1 left-1 w-5 h-5 bg-white rounded-full shadow-md transition-transform duration-200 ${
+              telemetryEnabled ? "translate-x-7" : "translate-x-0"
+            }`}
+          />
+        </button>
+      </div>
+
+      <div className="space-y-2 border-t border-overlay-subtle pt-4">
+        <div className="flex items-center justify-between gap-4">
+          <p className="text-sm font-medium text-text-secondary">What would be sent</p>
+          <div className="flex items-center gap-2 shrink-0">
+            <button
+              onClick={handleCopy}
+              disabled={!report}
+              className="px-3 py-1.5 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-secondary hover:text-text-primary hover:bg-overlay-light disabled:opacity-50"
+            >
+              Copy
+            </button>
+            <button
+              onClick={handleClear}
+              className="px-3 py-1.5 text-sm rounded-lg bg-overlay-subtle border border-overlay-light text-text-secondary hover:text-text-primary hover:bg-overlay-light"
+            >
+              Clear
+            </button>
+          </div>
+        </div>
+        <pre className="max-h-64 overflow-auto custom-scrollbar px-3 py-2 text-xs font-mono rounded-lg bg-overlay-subtle border border-overlay-light text-text-secondary whitespace-pre-wrap">
+          {report ? JSON.stringify(report, null, 2) : "Loading…"}
+        </pre>
+        <p className="text-xs text-text-muted">
+          Nothing is uploaded yet; the report stays on this device unless you copy it. Turning
+          this off deletes what was recorded.
+        </p>
+      </div>
+    </div>
+  );
+};
+
+export default TelemetrySettings;
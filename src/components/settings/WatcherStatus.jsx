@@ -0,0 +1,76 @@
+import { useCallback, useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { getWatcherStatus } from "../../utils/fileSystem";
+
+const formatTime = (ms) => (ms ? new Date(ms).toLocaleString() : null);
+
+const describe = (status) => {
+  if (!status) return { label: "Checking…", tone: "bg-overlay-light" };
+  if (!status.workspace) return { label: "No workspace open", tone: "bg-overlay-light" };
+  if (!status.live) return { label: "Off — rescans when Marky regains focus", tone: "bg-overlay-light" };
+  if (status.rescanning) return { label: "Catching up on missed changes…", tone: "bg-yellow-400" };
+  if (status.last_error) return { label: "Not keeping up", tone: "bg-red-400" };
+  return { label: "Healthy", tone: "bg-green-400" };
+};
+
+const WatcherStatus = () => {
+  const [status, setStatus] = useState(null);
+
+  const refresh = useCallback(() => {
+    getWatcherStatus()
+      .then(setStatus)
+      .catch(() => setStatus(null));
+  }, []);
+
+  useEffect(() => {
+    refresh();
+    let unlisten = null;
+    let mounted = true;
+    // A rescan finishes shortly after the event; check again once it has
+    listen("watch-overflow", () => {
+      refresh();
+      setTimeout(() => mounted && refresh(), 2000);
+    }).then((fn) => {
+      if (mounted) unlisten = fn;
+      else fn();
+    });
+    return () => {
+      mounted = false;
+      unlisten?.();
+    };
+  }, [refresh]);
+
+  const { label, tone } = describe(status);
+  const lastOverflow = formatTime(status?.last_overflow);
+
+  return (
+    <div className="flex items-center justify-between gap-4">
+      <div className="min-w-0">
+        <p className="text-sm font-medium text-text-secondary">File watching</p>
+        <p className="text-xs text-text-muted mt-0.5 flex items-center gap-2">
+          <span className={`inline-block w-2 h-2 rounded-full shrink-0 ${tone}`} />
+          {label}
+        </p>
+        {status?.last_error && (
+          <p className="text-xs text-text-muted mt-0.5 truncate" title={status.last_error}>
+            {status.last_error}
+          </p>
+        )}
+        {status?.overflows > 0 && (
+          <p className="text-xs text-text-muted mt-0.5">
+            Missed changes {status.overflows} {status.overflows === 1 ? "time" : "times"}, last at{" "}
+            {lastOverflow}; the workspace was rescanned each time.
+          </p>
+        )}
+      </div>
+      <button
+        onClick={refresh}
+        className="px-4 py-2 rounded-lg font-medium text-sm transition-all shrink-0 border bg-overlay-subtle hover:bg-overlay-light text-text-primary border-overlay-subtle"
+      >
+        Check
+      </button>
+    </div>
+  );
+};
+
+export default WatcherStatus;
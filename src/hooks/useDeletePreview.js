@@ -0,0 +1,53 @@
+import { useEffect, useState } from "react";
+import { previewDelete } from "../utils/fileSystem";
+
+const formatSize = (bytes) => {
+  if (bytes < 1024) return `${bytes} B`;
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+  if (bytes < 1024 * 1024 * 1024) return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+  return `${(bytes / (1024 * 1024 * 1024)).toFixed(1)} GB`;
+};
+
+/**
+ * Measures a folder on disk while its delete dialog is open. The backend only
+ * deletes workspaces and folders past the delete confirmation limits with the
+ * preview's token, so pass `preview?.token` along when the user confirms.
+ * @param {Object | null} item - The item about to be deleted; null when no dialog is open
+ * @returns {import("../utils/fileSystem").DeletePreview | null}
+ */
+export function useDeletePreview(item) {
+  const [preview, setPreview] = useState(null);
+  const filePath = item?.type === "folder" ? item.filePath : null;
+
+  useEffect(() => {
+    setPreview(null);
+    if (!filePath) return;
+
+    let cancelled = false;
+    previewDelete(filePath)
+      .then((result) => {
+        if (!cancelled) setPreview(result);
+      })
+      .catch((error) => console.error("Failed to preview delete:", error));
+    return () => {
+      cancelled = true;
+    };
+  }, [filePath]);
+
+  return preview;
+}
+
+/**
+ * The extra line a delete dialog shows when the preview needed confirmation
+ * @param {import("../utils/fileSystem").DeletePreview | null} preview
+ * @returns {string}
+ */
+export function describeDeletePreview(preview) {
+  if (!preview?.requires_confirmation) return "";
+
+  const files = `${preview.files.toLocaleString()} file${preview.files !== 1 ? "s" : ""}`;
+  const size = `${files} (${formatSize(preview.bytes)})`;
+  return preview.is_workspace_root
+    ? `This folder holds a workspace: ${size} on disk, hidden files included.`
+    : `That's ${size} on disk, hidden files included.`;
+}
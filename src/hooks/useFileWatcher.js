@@ -1,8 +1,11 @@
 import { useEffect, useRef, useCallback } from "react";
 import { listen } from "@tauri-apps/api/event";
-import { watchFolder, stopWatching } from "../utils/fileSystem";
+import { watchFolder, stopWatching, openConflictedCopy } from "../utils/fileSystem";
 import useNotesStore from "../store/notesStore";
 import useUIStore from "../store/uiStore";
+import { isNoteWindow } from "../utils/windowContext";
+import { openReminderNote } from "../utils/reminders";
+import { openClippedNote } from "../utils/localApi";
 
 const logWatcherDebug = (...args) => {
   if (!import.meta.env.DEV) return;
@@ -25,7 +28,15 @@ export function useFileWatcher() {
   const addNotification = useUIStore((state) => state.addNotification);
 
   const unlistenFileChangeRef = useRef(null);
+  const unlistenOverflowRef = useRef(null);
   const unlistenRecentNoteRef = useRef(null);
+  const unlistenNoteSavedRef = useRef(null);
+  const unlistenReminderRef = useRef(null);
+  const unlistenClipRef = useRef(null);
+  const unlistenConflictRef = useRef(null);
+  const unlistenOfflineRef = useRef(null);
+  const unlistenOnlineRef = useRef(null);
+  const removeVisibilityListenerRef = useRef(null);
   const isWatchingRef = useRef(false);
   const debounceTimerRef = useRef(null);
 
@@ -54,10 +65,24 @@ export function useFileWatcher() {
       }
 
       try {
-        // Start watching the folder
-        await watchFolder(rootFolderPath);
+        // Note windows ride on the main window's watcher; the backend routes their events
+        if (!isNoteWindow) {
+          const live = await watchFolder(rootFolderPath);
+          logWatcherDebug("File watcher started:", rootFolderPath, live ? "" : "(rescan on focus)");
+
+          // Without a live watcher, catch up on edits made elsewhere whenever the app returns
+          if (live === false) {
+            const handleVisibilityChange = () => {
+              if (mounted && document.visibilityState === "visible") {
+                debouncedRefresh();
+              }
+            };
+            document.addEventListener("visibilitychange", handleVisibilityChange);
+            removeVisibilityListenerRef.current = () =>
+              document.removeEventListener("visibilitychange", handleVisibilityChange);
+          }
+        }
         isWatchingRef.current = true;
-        logWatcherDebug("File watcher started:", rootFolderPath);
 
         // Listen for file change events from Rust backend
         unlistenFileChangeRef.current = await listen("file-change", (event) => {
@@ -69,6 +94,23 @@ export function useFileWatcher() {
           debouncedRefresh();
         });
 
+        // The OS dropped events under heavy churn; the backend rescans its index,
+        // and the tree is reloaded from disk rather than trusting what arrived
+        unlistenOverflowRef.current = await listen("watch-overflow", (event) => {
+          if (!mounted) return;
+
+          logWatcherDebug("File watcher missed changes:", event.payload);
+          debouncedRefresh();
+        });
+
+        // Another window saved a note this one may be showing
+        unlistenNoteSavedRef.current = await listen("note-saved", (event) => {
+          if (!mounted) return;
+
+          logWatcherDebug("Note saved in another window:", event.payload);
+          debouncedRefresh();
+        });
+
         unlistenRecentNoteRef.current = await listen("open-recent-note", (event) => {
           if (!mounted) return;
 
@@ -80,6 +122,85 @@ export function useFileWatcher() {
             selectNote(note.id);
           }
         });
+
+        unlistenReminderRef.current = await listen("reminder-due", (event) => {
+          if (!mounted) return;
+
+          const { path, title } = event.payload;
+          useUIStore.getState().addNotification(`Reminder: ${title}`, "info", 10000, {
+            label: "Open",
+            callback: () =>
+              openReminderNote(path).catch((error) =>
+                console.error("Failed to open reminder note:", error)
+              ),
+          });
+        });
+
+        // The browser clipper saved a page; list it now rather than after the watcher settles
+        unlistenClipRef.current = await listen("clip-received", (event) => {
+          if (!mounted) return;
+
+          const { path, title, appended } = event.payload;
+          refreshRootFromDisk().catch((error) =>
+            console.error("Failed to refresh workspace:", error)
+          );
+          useUIStore
+            .getState()
+            .addNotification(
+              appended ? `Clip added to note: ${title}` : `Clipped: ${title}`,
+              "success",
+              6000,
+              {
+                label: "Open",
+                callback: () =>
+                  openClippedNote(path).catch((error) =>
+                    console.error("Failed to open clipped note:", error)
+                  ),
+              }
+            );
+        });
+
+        // One side of an unmergeable edit was saved next to the note
+        unlistenConflictRef.current = await listen("sync-conflict", (event) => {
+          if (!mounted) return;
+
+          const { copy } = event.payload;
+          const name = copy.split(/[\\/]/).pop();
+          refreshRootFromDisk().catch((error) =>
+            console.error("Failed to refresh workspace:", error)
+          );
+          useUIStore
+            .getState()
+            .addNotification(`Conflicted copy saved: ${name}`, "warning", 8000, {
+              label: "Open",
+              callback: () =>
+                openConflictedCopy(copy).catch((error) =>
+                  console.error("Failed to open conflicted copy:", error)
+                ),
+            });
+        });
+
+        // The network share holding the workspace stopped answering, or came back
+        unlistenOfflineRef.current = await listen("workspace-offline", (event) => {
+          if (!mounted || !rootFolderPath.startsWith(event.payload.root)) return;
+
+          useUIStore
+            .getState()
+            .addNotification(
+              "Workspace share is offline. Changes can't be saved until it reconnects.",
+              "warning",
+              10000
+            );
+        });
+
+        unlistenOnlineRef.current = await listen("workspace-online", (event) => {
+          if (!mounted || !rootFolderPath.startsWith(event.payload.root)) return;
+
+          useUIStore.getState().addNotification("Workspace share reconnected", "success", 3000);
+          refreshRootFromDisk().catch((error) =>
+            console.error("Failed to refresh workspace:", error)
+          );
+        });
       } catch (error) {
         console.error("Failed to start file watcher:", error);
       }
@@ -98,15 +219,57 @@ export function useFileWatcher() {
         unlistenFileChangeRef.current = null;
       }
 
+      if (unlistenOverflowRef.current) {
+        unlistenOverflowRef.current();
+        unlistenOverflowRef.current = null;
+      }
+
       if (unlistenRecentNoteRef.current) {
         unlistenRecentNoteRef.current();
         unlistenRecentNoteRef.current = null;
       }
 
+      if (unlistenNoteSavedRef.current) {
+        unlistenNoteSavedRef.current();
+        unlistenNoteSavedRef.current = null;
+      }
+
+      if (unlistenReminderRef.current) {
+        unlistenReminderRef.current();
+        unlistenReminderRef.current = null;
+      }
+
+      if (unlistenClipRef.current) {
+        unlistenClipRef.current();
+        unlistenClipRef.current = null;
+      }
+
+      if (unlistenConflictRef.current) {
+        unlistenConflictRef.current();
+        unlistenConflictRef.current = null;
+      }
+
+      if (unlistenOfflineRef.current) {
+        unlistenOfflineRef.current();
+        unlistenOfflineRef.current = null;
+      }
+
+      if (unlistenOnlineRef.current) {
+        unlistenOnlineRef.current();
+        unlistenOnlineRef.current = null;
+      }
+
+      if (removeVisibilityListenerRef.current) {
+        removeVisibilityListenerRef.current();
+        removeVisibilityListenerRef.current = null;
+      }
+
       // Stop the Rust watcher
-      if (isWatchingRef.current) {
+      if (isWatchingRef.current && isNoteWindow) {
+        isWatchingRef.current = false;
+      } else if (isWatchingRef.current) {
         try {
-          await stopWatching();
+          await stopWatching(rootFolderPath);
           isWatchingRef.current = false;
           logWatcherDebug("File watcher stopped");
         } catch (error) {
@@ -125,7 +288,7 @@ export function useFileWatcher() {
       mounted = false;
       stopWatchingAndCleanup();
     };
-  }, [rootFolderPath, debouncedRefresh]);
+  }, [rootFolderPath, debouncedRefresh, refreshRootFromDisk]);
 
   return {
     isWatching: isWatchingRef.current,
@@ -0,0 +1,31 @@
+import { useEffect } from "react";
+import useNotesStore from "../store/notesStore";
+import useGitStore from "../store/gitStore";
+import { isNoteWindow } from "../utils/windowContext";
+
+/**
+ * Keeps the sidebar's git badges current: after every scan of the workspace,
+ * and when the app comes back to the foreground, since commits and checkouts
+ * made elsewhere only touch `.git`, which the watcher ignores.
+ */
+export function useGitStatus() {
+  const rootFolderPath = useNotesStore((state) => state.rootFolderPath);
+  const isLoading = useNotesStore((state) => state.isLoading);
+
+  useEffect(() => {
+    if (isNoteWindow || isLoading) return;
+    useGitStore.getState().refresh(rootFolderPath);
+  }, [rootFolderPath, isLoading]);
+
+  useEffect(() => {
+    if (isNoteWindow || !rootFolderPath) return;
+
+    const handleVisibilityChange = () => {
+      if (document.visibilityState === "visible") {
+        useGitStore.getState().refresh(rootFolderPath);
+      }
+    };
+    document.addEventListener("visibilitychange", handleVisibilityChange);
+    return () => document.removeEventListener("visibilitychange", handleVisibilityChange);
+  }, [rootFolderPath]);
+}
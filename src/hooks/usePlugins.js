@@ -0,0 +1,71 @@
+import { useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { isNoteWindow } from "../utils/windowContext";
+import {
+  restartPlugins,
+  stopAllPlugins,
+  runPluginCommand,
+  dispatchPluginEvent,
+} from "../utils/pluginHost";
+
+/**
+ * Hook to run enabled plugins in the main window
+ *
+ * Features:
+ * - Starts enabled plugins on mount and restarts them when one is toggled
+ * - Forwards file changes and note saves to plugins that subscribed
+ * - Runs plugin commands picked from the Plugins menu
+ */
+export function usePlugins() {
+  useEffect(() => {
+    // Note windows share the main window's plugins
+    if (isNoteWindow) return undefined;
+
+    let mounted = true;
+    const unlisteners = [];
+
+    const start = async () => {
+      try {
+        await restartPlugins();
+      } catch (error) {
+        console.error("Failed to start plugins:", error);
+      }
+    };
+
+    const subscribe = async () => {
+      const subscriptions = [
+        listen("plugins-changed", () => {
+          if (mounted) start();
+        }),
+        listen("file-change", (event) => {
+          if (mounted) dispatchPluginEvent("file-change", event.payload);
+        }),
+        listen("plugins://note-saved", (event) => {
+          if (mounted) dispatchPluginEvent("note-saved", event.payload);
+        }),
+        listen("plugin-command", (event) => {
+          if (!mounted) return;
+          const { plugin, command } = event.payload;
+          runPluginCommand(plugin, command);
+        }),
+      ];
+
+      for (const unlisten of await Promise.all(subscriptions)) {
+        if (mounted) {
+          unlisteners.push(unlisten);
+        } else {
+          unlisten();
+        }
+      }
+    };
+
+    start();
+    subscribe().catch((error) => console.error("Failed to listen for plugin events:", error));
+
+    return () => {
+      mounted = false;
+      unlisteners.forEach((unlisten) => unlisten());
+      stopAllPlugins();
+    };
+  }, []);
+}
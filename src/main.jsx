@@ -1,7 +1,8 @@
 import React from 'react'
 import ReactDOM from 'react-dom/client'
 import { getCurrentWindow } from '@tauri-apps/api/window'
-import App from './App.jsx'
+import SafeModeScreen from './components/layout/SafeModeScreen.jsx'
+import { getSafeMode } from './utils/safeMode'
 import './index.css'
 
 const appWindow = getCurrentWindow();
@@ -9,20 +10,37 @@ const appWindow = getCurrentWindow();
 // Use createRoot without StrictMode in production for better performance
 const root = ReactDOM.createRoot(document.getElementById('root'));
 
-// StrictMode causes double-renders in development which slows initial load
-if (import.meta.env.DEV) {
-  root.render(
-    <React.StrictMode>
-      <App />
-    </React.StrictMode>
-  );
-} else {
-  root.render(<App />);
-}
+const render = (element) => {
+  // StrictMode causes double-renders in development which slows initial load
+  if (import.meta.env.DEV) {
+    root.render(<React.StrictMode>{element}</React.StrictMode>);
+  } else {
+    root.render(element);
+  }
+};
 
-// Show window after paint
-requestAnimationFrame(() => {
+// The app is imported lazily so its persisted stores don't hydrate in safe mode
+const renderApp = async () => {
+  const { default: App } = await import('./App.jsx');
+  render(<App />);
+};
+
+const start = async () => {
+  if (await getSafeMode()) {
+    render(<SafeModeScreen onContinue={() => renderApp().catch(console.error)} />);
+  } else {
+    await renderApp();
+  }
+
+  // Show window after paint
   requestAnimationFrame(() => {
-    appWindow.show().catch(console.error);
+    requestAnimationFrame(() => {
+      appWindow.show().catch(console.error);
+    });
   });
-});
\ No newline at end of file
+};
+
+start().catch((error) => {
+  console.error('Failed to start Marky:', error);
+  appWindow.show().catch(console.error);
+});
@@ -0,0 +1,57 @@
+import { create } from "zustand";
+import { getGitStatuses } from "../utils/git";
+
+const normalize = (path) => (path || "").replace(/\\/g, "/").replace(/\/$/, "");
+
+const parentOf = (path) => path.slice(0, Math.max(path.lastIndexOf("/"), 0));
+
+// Git status of the workspace's files, for sidebar badges. Paths are kept
+// with forward slashes so lookups don't depend on the platform.
+const useGitStore = create((set, get) => ({
+  root: null,
+  isRepo: false,
+  files: {},
+  // Folders holding a changed (not ignored) file, so collapsed folders show it too
+  changedFolders: {},
+
+  refresh: async (root) => {
+    if (!root) {
+      set({ root: null, isRepo: false, files: {}, changedFolders: {} });
+      return;
+    }
+    let result;
+    try {
+      result = await getGitStatuses(root);
+    } catch {
+      return;
+    }
+    const normalizedRoot = normalize(root);
+    const files = {};
+    const changedFolders = {};
+    Object.entries(result.files).forEach(([path, status]) => {
+      const normalized = normalize(path);
+      files[normalized] = status;
+      if (status === "ignored") return;
+      for (let dir = parentOf(normalized); dir.length > normalizedRoot.length; dir = parentOf(dir)) {
+        changedFolders[dir] = true;
+      }
+    });
+    set({ root: normalizedRoot, isRepo: result.is_repo, files, changedFolders });
+  },
+
+  // A file's own status, or "ignored" when a folder above it is
+  statusFor: (filePath) => {
+    const { root, files } = get();
+    const path = normalize(filePath);
+    if (!root || !path.startsWith(root)) return null;
+    if (files[path]) return files[path];
+    for (let dir = parentOf(path); dir.length > root.length; dir = parentOf(dir)) {
+      if (files[dir] === "ignored") return "ignored";
+    }
+    return null;
+  },
+
+  hasChangesIn: (folderPath) => Boolean(get().changedFolders[normalize(folderPath)]),
+}));
+
+export default useGitStore;
@@ -1,5 +1,5 @@
 import { create } from "zustand";
-import { persist } from "zustand/middleware";
+import { persist, createJSONStorage } from "zustand/middleware";
 import {
   readMarkdownFile,
   createFolderOnDisk,
@@ -7,10 +7,39 @@ import {
   renameEntryOnDisk,
   deleteEntryOnDisk,
   moveEntryOnDisk,
+  archiveEntryOnDisk,
+  unarchiveEntryOnDisk,
+  setNoteLocked,
+  encryptNote,
+  decryptNote,
+  saveEncryptedNote,
+  removeNoteEncryption,
+  createVault,
+  unlockVault,
+  lockVault,
+  getVaultStatus,
   scanFolder,
   writeMarkdownFileOnDisk,
+  saveConflictCopy,
+  loadSession,
+  pinEntry,
+  listPinned,
 } from "../utils/fileSystem";
 import { resolveTemplateById } from "../data/templates";
+import { linkUnlinkedMentions } from "../utils/mentions";
+import { ensureNoteId } from "../utils/noteIds";
+import { runAutomation } from "../utils/automations";
+import { ensureBlockId } from "../utils/blocks";
+import { removeNoteProperty, setNoteProperty } from "../utils/properties";
+import { addTagToNotes, removeTagFromNotes, renameTag } from "../utils/tags";
+import { isNoteWindow, storageName } from "../utils/windowContext";
+
+// Note windows hydrate from the main window's state but never write it back
+const readOnlyLocalStorage = {
+  getItem: (name) => localStorage.getItem(name),
+  setItem: () => {},
+  removeItem: () => {},
+};
 
 // Special ID for the Settings tab
 export const SETTINGS_TAB_ID = "settings::special";
@@ -49,6 +78,12 @@ const stripCodeBlocks = (content) => {
   return stripped;
 };
 
+const isTagPath = (tag) =>
+  Boolean(tag) && !tag.startsWith("/") && !tag.endsWith("/") && !tag.includes("//");
+
+// `#project/client` is also under `#project`
+export const tagMatches = (noteTag, tag) => noteTag === tag || noteTag.startsWith(`${tag}/`);
+
 // Extract hashtags from markdown content
 const extractTags = (content) => {
   if (!content) return [];
@@ -56,53 +91,39 @@ const extractTags = (content) => {
   // Match #word (but not ##heading or ###heading)
   // Must be preceded by space, newline, or start of string
   // Must be followed by space, punctuation, or end of string
-  const tagRegex = /(?:^|[\s])#([a-zA-Z0-9_-]+)(?=[\s.,;!?)]|$)/g;
+  // `/` nests tags, and a trailing one is punctuation
+  const tagRegex = /(?:^|[\s])#([a-zA-Z0-9_/-]+)(?=[\s.,;!?)]|$)/g;
   const tags = new Set();
   let match;
 
   while ((match = tagRegex.exec(cleanContent)) !== null) {
-    const tag = match[1].toLowerCase();
-    tags.add(tag);
+    const tag = match[1].replace(/\/+$/, "").toLowerCase();
+    if (isTagPath(tag)) tags.add(tag);
   }
 
+  extractFrontmatterList(content, ["tags", "tag"])
+    .flatMap((entry) => entry.split(/[,\s]+/))
+    .map((entry) => entry.replace(/^#+/, "").toLowerCase())
+    .filter((entry) => /^[a-z0-9_/-]+$/.test(entry) && isTagPath(entry))
+    .forEach((entry) => tags.add(entry));
+
   return Array.from(tags).sort();
 };
 
-const normalizeTagValue = (value) => {
-  const cleaned = (value || "")
+export const normalizeTagValue = (value) =>
+  (value || "")
     .trim()
     .replace(/^#+/, "")
     .toLowerCase()
-    .replace(/[^a-z0-9_-]+/g, "-")
-    .replace(/-+/g, "-")
-    .replace(/^[-_]+|[-_]+$/g, "");
-  return cleaned;
-};
-
-const replaceTagInContent = (content, sourceTag, targetTag = null) => {
-  if (!content || !sourceTag) return content;
-
-  const normalizedSource = normalizeTagValue(sourceTag);
-  const normalizedTarget = targetTag ? normalizeTagValue(targetTag) : null;
-  const tagRegex = /(^|[\s])#([a-zA-Z0-9_-]+)(?=[\s.,;!?)]|$)/gm;
-
-  let changed = false;
-  const next = content.replace(tagRegex, (full, prefix, tagName) => {
-    if ((tagName || "").toLowerCase() !== normalizedSource) {
-      return full;
-    }
-
-    changed = true;
-    if (!normalizedTarget) {
-      return prefix;
-    }
-
-    return `${prefix}#${normalizedTarget}`;
-  });
-
-  if (!changed) return content;
-  return next;
-};
+    .split("/")
+    .map((segment) =>
+      segment
+        .replace(/[^a-z0-9_-]+/g, "-")
+        .replace(/-+/g, "-")
+        .replace(/^[-_]+|[-_]+$/g, "")
+    )
+    .filter(Boolean)
+    .join("/");
 
 const normalizeLinkTarget = (value) => (value ? value.trim().toLowerCase() : "");
 const buildNoteLinkKey = (name) => normalizeLinkTarget(stripExtension(name || ""));
@@ -121,7 +142,8 @@ const extractWikiLinks = (content) => {
     if (!inner) continue;
 
     const [targetRaw, aliasRaw] = inner.split("|");
-    const target = stripExtension((targetRaw || "").trim());
+    // `[[Note#Heading]]` links to the note; `[[#Heading]]` stays within this one
+    const target = stripExtension((targetRaw || "").split("#")[0].trim());
     if (!target) continue;
 
     const key = buildNoteLinkKey(target);
@@ -138,6 +160,83 @@ const extractWikiLinks = (content) => {
   return links;
 };
 
+// Values of the given frontmatter keys: inline `[a, b]`, block `- a`, or a scalar
+function extractFrontmatterList(content, keys) {
+  if (!content) return [];
+
+  const match = content
+    .replace(/^\uFEFF/, "")
+    .match(/^---\r?\n([\s\S]*?)\r?\n(?:---|\.\.\.)\s*(?:\r?\n|$)/);
+  if (!match) return [];
+
+  const unquote = (value) => value.trim().replace(/^(["'])(.*)\1$/, "$2").trim();
+  const values = [];
+  const lines = match[1].split(/\r?\n/);
+
+  lines.forEach((line, index) => {
+    const field = line.match(/^([\w-]+)\s*:\s*(.*)$/);
+    if (!field || !keys.includes(field[1])) return;
+
+    const value = field[2].trim();
+    if (value.startsWith("[")) {
+      value
+        .replace(/^\[|\]$/g, "")
+        .split(",")
+        .forEach((entry) => values.push(unquote(entry)));
+    } else if (value) {
+      values.push(unquote(value));
+    } else {
+      for (let next = index + 1; next < lines.length; next += 1) {
+        const item = lines[next].match(/^\s*-\s+(.*)$/);
+        if (!item) break;
+        values.push(unquote(item[1]));
+      }
+    }
+  });
+
+  return Array.from(new Set(values.filter(Boolean)));
+}
+
+// Alternative names from `aliases:` / `alias:` frontmatter
+const extractAliases = (content) => extractFrontmatterList(content, ["aliases", "alias"]);
+
+const noteAnswersTo = (note, key) =>
+  (note.linkKey || buildNoteLinkKey(note.name)) === key ||
+  (note.aliases || extractAliases(note.content)).some((alias) => buildNoteLinkKey(alias) === key);
+
+const ENCRYPTED_NOTE_HEADER = "-----BEGIN MARKY ENCRYPTED NOTE-----";
+
+const isEncryptedContent = (content) =>
+  typeof content === "string" && content.replace(/^\uFEFF/, "").startsWith(ENCRYPTED_NOTE_HEADER);
+
+// Passphrases and armored content of unlocked encrypted notes, keyed by normalized path.
+// Kept outside the store so neither ever reaches persisted state.
+const unlockedEncryptedNotes = new Map();
+
+// Root of the open workspace when it's an encrypted vault. Nothing under it may reach
+// localStorage: drafts, history snapshots, and persisted items would all be plaintext.
+let activeVaultRoot = null;
+
+const isInActiveVault = (filePath) => {
+  if (!activeVaultRoot || !filePath) return false;
+  const path = normalizePath(filePath);
+  return path === activeVaultRoot || path.startsWith(`${activeVaultRoot}/`);
+};
+
+// Unlocked notes hold plaintext in memory only; persist their armored form instead
+const toPersistableItem = (item) => {
+  if (!item?.decrypted) return item;
+  const entry = unlockedEncryptedNotes.get(normalizePath(item.filePath));
+  return {
+    ...item,
+    content: entry?.armored || "",
+    decrypted: false,
+    tags: [],
+    links: [],
+    aliases: [],
+  };
+};
+
 const ensureNoteMetadata = (item) => {
   if (!item || item.type !== "note") {
     return item;
@@ -146,12 +245,15 @@ const ensureNoteMetadata = (item) => {
   const linkKey = item.linkKey || buildNoteLinkKey(item.name);
   const links = item.links || extractWikiLinks(item.content);
   const tags = item.tags || extractTags(item.content);
+  const aliases = item.aliases || extractAliases(item.content);
 
   return {
     ...item,
     linkKey,
     links,
     tags,
+    aliases,
+    encrypted: Boolean(item.decrypted) || isEncryptedContent(item.content),
   };
 };
 
@@ -333,6 +435,13 @@ const calculateNextRun = (schedule, referenceDate = new Date()) => {
 };
 
 const pendingWriteTimers = new Map();
+// What each note's file held when it was last read or written, keyed by
+// normalized path: the common version a draft and an outside change are
+// merged from
+const diskBaselines = new Map();
+const rememberDiskContent = (filePath, content) => {
+  if (filePath) diskBaselines.set(normalizePath(filePath), content || "");
+};
 const pendingMetadataTimers = new Map();
 const DRAFT_STORAGE_KEY = "marky-draft-cache";
 const NOTE_HISTORY_KEY = "marky-note-history";
@@ -360,7 +469,7 @@ const writeNoteHistory = (history) => {
 };
 
 export const addNoteHistorySnapshot = (filePath, content) => {
-  if (!filePath || content == null) return;
+  if (!filePath || content == null || isInActiveVault(filePath)) return;
   const key = normalizePath(filePath);
   const history = readNoteHistory();
   const snapshots = Array.isArray(history[key]) ? history[key] : [];
@@ -421,7 +530,7 @@ const writeDraftCache = (cache) => {
 };
 
 const setDraftCacheEntry = (filePath, content, updatedAt = new Date().toISOString()) => {
-  if (!filePath) return;
+  if (!filePath || isInActiveVault(filePath)) return;
   const key = normalizePath(filePath);
   const cache = readDraftCache();
   cache[key] = { content, updatedAt };
@@ -568,6 +677,7 @@ const buildItemsFromFolderData = async ({ folderPath, folderName, files }, onPro
         normalizedPath: normalizedEntry,
         content: null,
         createdAt: now,
+        archived: Boolean(entry.archived),
       });
       pathToId.set(normalizedEntry, folderId);
     } else {
@@ -585,6 +695,9 @@ const buildItemsFromFolderData = async ({ folderPath, folderName, files }, onPro
         updatedAt: now,
         linkKey: buildNoteLinkKey(entry.name),
         links: extractWikiLinks(noteContent),
+        aliases: extractAliases(noteContent),
+        archived: Boolean(entry.archived),
+        locked: Boolean(entry.locked),
       });
     }
   });
@@ -621,8 +734,30 @@ const useNotesStore = create(
       customTemplates: [], // Array of {id, name, icon, description, content}
       scheduledNotes: [], // Array of scheduled note configurations
       recentWorkspaces: [], // Array of { path, name, lastOpenedAt }
+      cursorPositions: {}, // { [filePath]: { line, column, scroll_top } }, saved with the session
+      showArchived: false, // Include the workspace archive folder in the tree and search
+      isVaultWorkspace: false, // The open workspace is an encrypted vault
+      vaultLocked: false, // The vault is open but waiting for its passphrase
 
       setRootFolder: async (folderData) => {
+        const vaultStatus = await getVaultStatus(folderData.folderPath);
+        activeVaultRoot = vaultStatus.is_vault ? normalizePath(folderData.folderPath) : null;
+
+        // A locked vault can't be scanned; keep just the root until it's unlocked
+        if (vaultStatus.is_vault && !vaultStatus.unlocked) {
+          set((state) => ({
+            rootFolderPath: folderData.folderPath,
+            rootFolderId: null,
+            items: state.items.filter((item) => !item.filePath),
+            currentNoteId: null,
+            openNoteIds: [],
+            dirtyNoteIds: [],
+            isVaultWorkspace: true,
+            vaultLocked: true,
+          }));
+          return null;
+        }
+
         set({ isLoading: true, loadingProgress: null });
         const { items: fsItems, rootId } = await buildItemsFromFolderData(
           folderData,
@@ -633,6 +768,10 @@ const useNotesStore = create(
         const previousItems = get().items;
         const ephemeralItems = previousItems.filter((item) => !item.filePath);
         const combinedItems = [...fsItems, ...ephemeralItems].map(ensureNoteMetadata);
+        diskBaselines.clear();
+        fsItems
+          .filter((item) => item.type === "note")
+          .forEach((note) => rememberDiskContent(note.filePath, note.content));
         set({
           rootFolderPath: folderData.folderPath,
           rootFolderId: rootId,
@@ -642,6 +781,8 @@ const useNotesStore = create(
           expandedFolders: [rootId],
           isLoading: false,
           loadingProgress: null,
+          isVaultWorkspace: vaultStatus.is_vault,
+          vaultLocked: false,
         });
 
         // Record in recent workspaces (most-recent first, capped at 10)
@@ -664,11 +805,59 @@ const useNotesStore = create(
           };
         });
 
+        await get().restoreSession(folderData.folderPath);
+        await get().loadPins(folderData.folderPath);
+
         return rootId;
       },
 
       loadFolderFromSystem: (folderData) => get().setRootFolder(folderData),
 
+      setCursorPosition: (filePath, position) => {
+        if (!filePath) return;
+        set((state) => ({
+          cursorPositions: { ...state.cursorPositions, [filePath]: position },
+        }));
+      },
+
+      // Shape expected by the backend `save_session` command
+      getSessionSnapshot: () => {
+        const { items, openNoteIds, currentNoteId, expandedFolders, cursorPositions } = get();
+        const pathById = new Map(items.map((item) => [item.id, item.filePath]));
+        const openNotes = openNoteIds.map((id) => pathById.get(id)).filter(Boolean);
+        return {
+          open_notes: openNotes,
+          active_note: pathById.get(currentNoteId) || null,
+          cursors: Object.fromEntries(
+            Object.entries(cursorPositions).filter(([path]) => openNotes.includes(path))
+          ),
+          expanded_folders: expandedFolders.map((id) => pathById.get(id)).filter(Boolean),
+        };
+      },
+
+      restoreSession: async (folderPath) => {
+        const session = await loadSession(folderPath);
+        if (!session || get().rootFolderPath !== folderPath) return;
+
+        const { items, rootFolderId } = get();
+        const idByPath = new Map(
+          items.filter((item) => item.filePath).map((item) => [item.filePath, item.id])
+        );
+        const openNoteIds = session.open_notes.map((path) => idByPath.get(path)).filter(Boolean);
+        const expandedFolders = session.expanded_folders
+          .map((path) => idByPath.get(path))
+          .filter(Boolean);
+
+        set({
+          openNoteIds,
+          currentNoteId: idByPath.get(session.active_note) || null,
+          expandedFolders: rootFolderId
+            ? [rootFolderId, ...expandedFolders.filter((id) => id !== rootFolderId)]
+            : expandedFolders,
+          cursorPositions: session.cursors || {},
+        });
+      },
+
       refreshRootFromDisk: async (options = {}) => {
         const { focusPath, ensureExpandedPath } = options;
         const state = get();
@@ -677,7 +866,7 @@ const useNotesStore = create(
 
         set({ isLoading: true, loadingProgress: null });
         try {
-          const files = await scanFolder(rootFolderPath);
+          const files = await scanFolder(rootFolderPath, state.showArchived);
           const folderData = {
             folderPath: rootFolderPath,
             folderName: folderNameFromPath(rootFolderPath),
@@ -701,6 +890,23 @@ const useNotesStore = create(
 
           const combinedItems = fsItems
             .map((item) => {
+              const unlocked =
+                item.type === "note" ? unlockedEncryptedNotes.get(item.normalizedPath) : null;
+              if (unlocked) {
+                const previousNote = previousNotesById.get(item.id);
+                // Still the ciphertext we unlocked: keep showing the plaintext
+                if (previousNote?.decrypted && item.content === unlocked.armored) {
+                  return {
+                    ...previousNote,
+                    parentId: item.parentId,
+                  };
+                }
+                // Re-encrypted or replaced elsewhere, so the note locks again
+                unlockedEncryptedNotes.delete(item.normalizedPath);
+                recoveredDirtyIds.delete(item.id);
+                return ensureNoteMetadata(item);
+              }
+
               const draftEntry = item.type === "note" ? getDraftCacheEntry(item.filePath) : null;
 
               if (
@@ -726,10 +932,13 @@ const useNotesStore = create(
                 if (previousNote) {
                   const diskContent = item.content || "";
                   const localContent = previousNote.content || "";
+                  const baseContent = diskBaselines.get(item.normalizedPath);
 
-                  if (diskContent !== localContent) {
+                  // A file still holding what the draft started from hasn't changed
+                  if (diskContent !== localContent && diskContent !== baseContent) {
                     nextConflicts[item.id] = {
                       diskContent,
+                      baseContent: baseContent ?? null,
                       detectedAt: new Date().toISOString(),
                       filePath: item.filePath,
                     };
@@ -744,6 +953,7 @@ const useNotesStore = create(
               }
 
               if (item.type === "note") {
+                rememberDiskContent(item.filePath, item.content);
                 return ensureNoteMetadata({
                   ...item,
                 });
@@ -975,7 +1185,7 @@ const useNotesStore = create(
               : item
           );
           const nextNote = nextItems.find((item) => item.id === noteId && item.type === "note");
-          if (nextNote?.filePath) {
+          if (nextNote?.filePath && !nextNote.decrypted) {
             setDraftCacheEntry(nextNote.filePath, content, nextNote.updatedAt);
           }
 
@@ -999,6 +1209,7 @@ const useNotesStore = create(
               ? ensureNoteMetadata({
                   ...item,
                   content,
+                  aliases: extractAliases(content),
                 })
               : item
           ),
@@ -1019,10 +1230,20 @@ const useNotesStore = create(
         cancelPendingNoteWrite(note.filePath);
 
         try {
-          await writeMarkdownFileOnDisk(note.filePath, note.content);
+          if (note.decrypted) {
+            const entry = unlockedEncryptedNotes.get(normalizePath(note.filePath));
+            if (!entry) {
+              throw new Error("Unlock this note again before saving");
+            }
+            const armored = await saveEncryptedNote(note.filePath, note.content, entry.passphrase);
+            entry.armored = armored;
+          } else {
+            await writeMarkdownFileOnDisk(note.filePath, note.content);
+            rememberDiskContent(note.filePath, note.content);
 
-          // Record history snapshot before clearing dirty state
-          addNoteHistorySnapshot(note.filePath, note.content);
+            // Record history snapshot before clearing dirty state
+            addNoteHistorySnapshot(note.filePath, note.content);
+          }
 
           // Clear dirty state for this note
           set((current) => ({
@@ -1145,7 +1366,8 @@ const useNotesStore = create(
         }
       },
 
-      deleteItem: async (itemId) => {
+      // `confirmToken` comes from previewDelete, for folders past the delete confirmation limits
+      deleteItem: async (itemId, { confirmToken = null } = {}) => {
         const state = get();
         const item = state.items.find((entry) => entry.id === itemId);
         if (!item) return;
@@ -1217,7 +1439,7 @@ const useNotesStore = create(
         const parentPath = resolveFolderPath(item.parentId, state.items, state.rootFolderPath);
         const deletedWasCurrent = state.currentNoteId === itemId;
 
-        await deleteEntryOnDisk(item.filePath);
+        await deleteEntryOnDisk(item.filePath, confirmToken);
         await get().refreshRootFromDisk({
           ensureExpandedPath: parentPath || state.rootFolderPath,
         });
@@ -1341,6 +1563,194 @@ const useNotesStore = create(
         return buildId(item.type === "note" ? "note" : "folder", newPath);
       },
 
+      archiveItem: async (itemId) => {
+        const item = get().items.find((entry) => entry.id === itemId);
+        if (!item?.filePath) {
+          throw new Error("Only saved notes and folders can be archived");
+        }
+
+        cancelPendingNoteWrite(item.filePath);
+        const newPath = await archiveEntryOnDisk(item.filePath);
+        await get().refreshRootFromDisk();
+        return newPath;
+      },
+
+      unarchiveItem: async (itemId) => {
+        const item = get().items.find((entry) => entry.id === itemId);
+        if (!item?.filePath) return null;
+
+        cancelPendingNoteWrite(item.filePath);
+        const newPath = await unarchiveEntryOnDisk(item.filePath);
+        await get().refreshRootFromDisk({
+          focusPath: item.type === "note" ? newPath : undefined,
+        });
+        return newPath;
+      },
+
+      toggleNoteLock: async (noteId) => {
+        const note = get().items.find((item) => item.id === noteId && item.type === "note");
+        if (!note?.filePath) {
+          throw new Error("Only saved notes can be locked");
+        }
+
+        const locked = await setNoteLocked(note.filePath, !note.locked);
+        set((state) => ({
+          items: state.items.map((item) => (item.id === noteId ? { ...item, locked } : item)),
+        }));
+        return locked;
+      },
+
+      encryptNoteWithPassphrase: async (noteId, passphrase) => {
+        const state = get();
+        const note = state.items.find((item) => item.id === noteId && item.type === "note");
+        if (!note?.filePath) {
+          throw new Error("Only saved notes can be encrypted");
+        }
+        if (state.dirtyNoteIds.includes(noteId)) {
+          throw new Error("Save the note before encrypting it");
+        }
+
+        cancelPendingNoteWrite(note.filePath);
+        const armored = await encryptNote(note.filePath, passphrase);
+        removeDraftCacheEntry(note.filePath);
+        set((current) => ({
+          items: current.items.map((item) =>
+            item.id === noteId
+              ? ensureNoteMetadata({
+                  ...item,
+                  content: armored,
+                  tags: [],
+                  links: [],
+                  aliases: [],
+                })
+              : item
+          ),
+        }));
+      },
+
+      unlockEncryptedNote: async (noteId, passphrase) => {
+        const note = get().items.find((item) => item.id === noteId && item.type === "note");
+        if (!note?.filePath || !note.encrypted) return;
+
+        const armored = note.content;
+        const content = await decryptNote(note.filePath, passphrase);
+        unlockedEncryptedNotes.set(normalizePath(note.filePath), { passphrase, armored });
+        set((current) => ({
+          items: current.items.map((item) =>
+            item.id === noteId
+              ? ensureNoteMetadata({
+                  ...item,
+                  content,
+                  decrypted: true,
+                  tags: extractTags(content),
+                  links: extractWikiLinks(content),
+                  aliases: extractAliases(content),
+                })
+              : item
+          ),
+        }));
+      },
+
+      lockEncryptedNote: (noteId) => {
+        const note = get().items.find((item) => item.id === noteId && item.type === "note");
+        if (!note?.decrypted) return;
+
+        const persisted = toPersistableItem(note);
+        unlockedEncryptedNotes.delete(normalizePath(note.filePath));
+        set((current) => ({
+          items: current.items.map((item) => (item.id === noteId ? persisted : item)),
+          dirtyNoteIds: current.dirtyNoteIds.filter((id) => id !== noteId),
+        }));
+      },
+
+      decryptNoteToPlaintext: async (noteId, passphrase) => {
+        const note = get().items.find((item) => item.id === noteId && item.type === "note");
+        if (!note?.filePath || !note.encrypted) return;
+
+        const content = await removeNoteEncryption(note.filePath, passphrase);
+        unlockedEncryptedNotes.delete(normalizePath(note.filePath));
+        set((current) => ({
+          items: current.items.map((item) =>
+            item.id === noteId
+              ? ensureNoteMetadata({
+                  ...item,
+                  content,
+                  decrypted: false,
+                  tags: extractTags(content),
+                  links: extractWikiLinks(content),
+                  aliases: extractAliases(content),
+                })
+              : item
+          ),
+          dirtyNoteIds: current.dirtyNoteIds.filter((id) => id !== noteId),
+        }));
+      },
+
+      createVaultFromWorkspace: async (passphrase) => {
+        const { rootFolderPath } = get();
+        if (!rootFolderPath) {
+          throw new Error("Open a workspace first");
+        }
+        if (get().dirtyNoteIds.length > 0) {
+          throw new Error("Save your changes before encrypting the workspace");
+        }
+
+        await createVault(rootFolderPath, passphrase);
+        // Plaintext copies made before the conversion must not outlive it
+        clearDraftCache();
+        get()
+          .items.filter((item) => item.filePath)
+          .forEach((item) => removeNoteHistory(item.filePath));
+        return get().openVaultWorkspace(rootFolderPath);
+      },
+
+      unlockVaultWorkspace: async (passphrase) => {
+        const { rootFolderPath } = get();
+        if (!rootFolderPath) return null;
+
+        await unlockVault(rootFolderPath, passphrase);
+        return get().openVaultWorkspace(rootFolderPath);
+      },
+
+      openVaultWorkspace: async (folderPath) => {
+        const files = await scanFolder(folderPath, get().showArchived);
+        return get().setRootFolder({
+          folderPath,
+          folderName: folderNameFromPath(folderPath),
+          files,
+        });
+      },
+
+      lockVaultWorkspace: async () => {
+        const { rootFolderPath, isVaultWorkspace } = get();
+        if (!rootFolderPath || !isVaultWorkspace) return;
+        await lockVault(rootFolderPath);
+        get().handleVaultLocked(rootFolderPath);
+      },
+
+      // The backend has dropped the key (manually or after inactivity); drop the plaintext too
+      handleVaultLocked: (workspace) => {
+        const { rootFolderPath } = get();
+        if (!rootFolderPath || normalizePath(workspace) !== normalizePath(rootFolderPath)) {
+          return false;
+        }
+
+        set((state) => ({
+          rootFolderId: null,
+          items: state.items.filter((item) => !item.filePath),
+          currentNoteId: null,
+          openNoteIds: [],
+          dirtyNoteIds: [],
+          vaultLocked: true,
+        }));
+        return true;
+      },
+
+      setShowArchived: async (showArchived) => {
+        set({ showArchived });
+        await get().refreshRootFromDisk();
+      },
+
       moveItemToRoot: async (itemId) => {
         const { rootFolderId } = get();
         if (!rootFolderId) {
@@ -1481,11 +1891,19 @@ const useNotesStore = create(
         return get().noteConflicts[noteId] || null;
       },
 
-      resolveNoteConflict: (noteId, resolution = "keepLocal") => {
+      // Whichever side loses is kept in a conflicted copy first, so neither edit is lost
+      resolveNoteConflict: async (noteId, resolution = "keepLocal") => {
         const conflict = get().noteConflicts[noteId];
         if (!conflict) return false;
 
+        const note = get().items.find((item) => item.id === noteId && item.type === "note");
+        const losingContent = resolution === "useDisk" ? note?.content : conflict.diskContent;
+        if (conflict.filePath && losingContent) {
+          await saveConflictCopy(conflict.filePath, losingContent);
+        }
+
         if (resolution === "useDisk") {
+          rememberDiskContent(conflict.filePath, conflict.diskContent);
           set((current) => ({
             items: current.items.map((item) =>
               item.id === noteId && item.type === "note"
@@ -1512,6 +1930,32 @@ const useNotesStore = create(
         return true;
       },
 
+      // The draft now includes the disk version's changes, so that version is
+      // the base for any later outside edit; the note stays unsaved
+      applyMergedConflict: (noteId, mergedContent) => {
+        const conflict = get().noteConflicts[noteId];
+        if (!conflict) return false;
+        rememberDiskContent(conflict.filePath, conflict.diskContent);
+        set((current) => ({
+          items: current.items.map((item) =>
+            item.id === noteId && item.type === "note"
+              ? ensureNoteMetadata({
+                  ...item,
+                  content: mergedContent,
+                  updatedAt: new Date().toISOString(),
+                })
+              : item
+          ),
+          dirtyNoteIds: current.dirtyNoteIds.includes(noteId)
+            ? current.dirtyNoteIds
+            : [...current.dirtyNoteIds, noteId],
+          noteConflicts: Object.fromEntries(
+            Object.entries(current.noteConflicts).filter(([id]) => id !== String(noteId))
+          ),
+        }));
+        return true;
+      },
+
       getRecoveredDraft: (noteId) => {
         return get().recoveredDrafts[noteId] || null;
       },
@@ -1551,14 +1995,16 @@ const useNotesStore = create(
       },
 
       findNoteByLinkTarget: (target) => {
-        const key = buildNoteLinkKey(target);
+        const key = buildNoteLinkKey((target || "").split("#")[0]);
         if (!key) return null;
 
         const { items } = get();
+        const notes = items.filter((item) => item.type === "note");
+        // Titles win over aliases so a renamed note can't shadow one that owns the name
         return (
-          items.find(
-            (item) => item.type === "note" && (item.linkKey || buildNoteLinkKey(item.name)) === key
-          ) || null
+          notes.find((item) => (item.linkKey || buildNoteLinkKey(item.name)) === key) ||
+          notes.find((item) => noteAnswersTo(item, key)) ||
+          null
         );
       },
 
@@ -1583,7 +2029,10 @@ const useNotesStore = create(
 
         return links.map((link) => ({
           ...link,
-          note: notesByKey.get(link.key) || null,
+          note:
+            notesByKey.get(link.key) ||
+            items.find((item) => item.type === "note" && noteAnswersTo(item, link.key)) ||
+            null,
         }));
       },
 
@@ -1591,7 +2040,12 @@ const useNotesStore = create(
         const { items } = get();
         const notes = items.filter((item) => item.type === "note");
         const existingKeys = new Set(
-          notes.map((note) => note.linkKey || buildNoteLinkKey(note.name)).filter(Boolean)
+          notes
+            .flatMap((note) => [
+              note.linkKey || buildNoteLinkKey(note.name),
+              ...(note.aliases || extractAliases(note.content)).map(buildNoteLinkKey),
+            ])
+            .filter(Boolean)
         );
         const brokenByKey = new Map();
 
@@ -1630,12 +2084,16 @@ const useNotesStore = create(
 
         const targetKey = target.linkKey || buildNoteLinkKey(target.name);
         if (!targetKey) return [];
+        const targetKeys = new Set([
+          targetKey,
+          ...(target.aliases || extractAliases(target.content)).map(buildNoteLinkKey),
+        ]);
 
         return items
           .filter((item) => {
             if (item.type !== "note" || item.id === noteId) return false;
             const links = item.links || extractWikiLinks(item.content);
-            return links.some((link) => link.key === targetKey);
+            return links.some((link) => targetKeys.has(link.key));
           })
           .sort((a, b) => a.name.localeCompare(b.name));
       },
@@ -1652,14 +2110,58 @@ const useNotesStore = create(
       },
 
       togglePinNote: (noteId) => {
-        set((state) => {
-          const isPinned = state.pinnedNotes.includes(noteId);
-          return {
-            pinnedNotes: isPinned
-              ? state.pinnedNotes.filter((id) => id !== noteId)
-              : [...state.pinnedNotes, noteId],
-          };
+        const { pinnedNotes, items } = get();
+        const isPinned = pinnedNotes.includes(noteId);
+        set({
+          pinnedNotes: isPinned
+            ? pinnedNotes.filter((id) => id !== noteId)
+            : [...pinnedNotes, noteId],
         });
+
+        // Pins live in the backend so they survive restarts and feed the native menu
+        const item = items.find((entry) => entry.id === noteId);
+        if (item?.filePath) {
+          pinEntry(item.filePath, !isPinned, get().rootFolderPath)
+            .then((pins) => get().applyPins(pins))
+            .catch((error) => console.error("Failed to persist pin:", error));
+        }
+      },
+
+      applyPins: (pins) => {
+        if (!Array.isArray(pins)) return;
+        const idByPath = new Map(
+          get()
+            .items.filter((item) => item.filePath)
+            .map((item) => [item.filePath, item.id])
+        );
+        set((state) => ({
+          pinnedNotes: [
+            // Unsaved notes can't be pinned on disk, so keep their local pins
+            ...state.pinnedNotes.filter((id) => {
+              const item = state.items.find((entry) => entry.id === id);
+              return item && !item.filePath;
+            }),
+            ...pins.map((pin) => idByPath.get(pin.path)).filter(Boolean),
+          ],
+        }));
+      },
+
+      loadPins: async (folderPath) => {
+        let pins = await listPinned(folderPath);
+        if (get().rootFolderPath !== folderPath) return;
+
+        // First run against the backend: carry over pins kept in local storage
+        if (pins.length === 0) {
+          const { pinnedNotes, items } = get();
+          const localPaths = items
+            .filter((item) => item.filePath && pinnedNotes.includes(item.id))
+            .map((item) => item.filePath);
+          for (const path of localPaths) {
+            pins = (await pinEntry(path, true, folderPath).catch(() => null)) || pins;
+          }
+        }
+
+        get().applyPins(pins);
       },
 
       isPinned: (noteId) => {
@@ -1714,7 +2216,61 @@ const useNotesStore = create(
         set({ selectedTags: [] });
       },
 
-      applyTagOperation: async ({ action, sourceTag, targetTag }) => {
+      // Runs a backend tag edit on the notes at `paths`. Unsaved edits go to disk
+      // first so the backend rewrites what the user sees, then the rewritten
+      // notes are read back into the store.
+      runTagEdit: async (paths, edit) => {
+        const wanted = new Set(paths.map(normalizePath));
+        const state = get();
+        const dirty = state.items.filter(
+          (item) =>
+            item.type === "note" &&
+            item.filePath &&
+            state.dirtyNoteIds.includes(item.id) &&
+            wanted.has(normalizePath(item.filePath))
+        );
+        for (const note of dirty) {
+          cancelPendingNoteWrite(note.filePath);
+          await writeMarkdownFileOnDisk(note.filePath, note.content || "");
+          rememberDiskContent(note.filePath, note.content);
+        }
+
+        const report = await edit();
+        const changed = new Map();
+        for (const path of report.changed) {
+          try {
+            changed.set(normalizePath(path), await readMarkdownFile(path));
+          } catch (error) {
+            console.error(`Failed to reload retagged note ${path}:`, error);
+          }
+        }
+        const flushed = new Set(dirty.map((note) => note.id));
+
+        set((current) => ({
+          items: current.items.map((item) => {
+            if (item.type !== "note" || !item.filePath) return item;
+            const content = changed.get(normalizePath(item.filePath));
+            if (content === undefined) return item;
+            return ensureNoteMetadata({
+              ...item,
+              content,
+              tags: extractTags(content),
+              updatedAt: new Date().toISOString(),
+            });
+          }),
+          dirtyNoteIds: current.dirtyNoteIds.filter((id) => !flushed.has(id)),
+        }));
+        return report;
+      },
+
+      applyBulkTagEdit: async ({ action, paths, tag }) => {
+        const { runTagEdit } = get();
+        return runTagEdit(paths, () =>
+          action === "add" ? addTagToNotes(paths, tag) : removeTagFromNotes(paths, tag)
+        );
+      },
+
+      applyTagOperation: async ({ action, sourceTag, targetTag, dryRun = false }) => {
         const normalizedSource = normalizeTagValue(sourceTag);
         const normalizedTarget = normalizeTagValue(targetTag);
 
@@ -1730,84 +2286,128 @@ const useNotesStore = create(
           throw new Error("Source and target tag are the same");
         }
 
-        const state = get();
-        const notes = state.items.filter(
-          (item) => item.type === "note" && typeof item.content === "string"
-        );
+        const { rootFolderPath, items, runTagEdit } = get();
         const desiredTarget = action === "delete" ? null : normalizedTarget;
+        const paths = items
+          .filter(
+            (item) =>
+              item.type === "note" && item.filePath && (item.tags || []).includes(normalizedSource)
+          )
+          .map((item) => item.filePath);
+
+        const summarize = (report) => ({
+          changedNotes: report.changed.length,
+          failedNotes: report.skipped.length,
+          skipped: report.skipped,
+          sourceTag: normalizedSource,
+          targetTag: desiredTarget,
+        });
 
-        const candidates = notes
-          .map((note) => {
-            const nextContent = replaceTagInContent(note.content, normalizedSource, desiredTarget);
-            if (nextContent === note.content) return null;
-            return { note, nextContent };
-          })
-          .filter(Boolean);
-
-        if (candidates.length === 0) {
-          return {
-            changedNotes: 0,
-            failedNotes: 0,
-            sourceTag: normalizedSource,
-            targetTag: desiredTarget,
-          };
+        if (action === "delete") {
+          if (dryRun) return { changedNotes: paths.length, failedNotes: 0, skipped: [] };
+          if (paths.length === 0) return summarize({ changed: [], skipped: [] });
+          const report = await runTagEdit(paths, () =>
+            removeTagFromNotes(paths, normalizedSource)
+          );
+          set((current) => ({
+            selectedTags: current.selectedTags.filter((tag) => tag !== normalizedSource),
+          }));
+          return summarize(report);
         }
 
-        const successfulUpdates = new Map();
-        let failedNotes = 0;
+        if (dryRun) {
+          return summarize(
+            await renameTag(rootFolderPath, normalizedSource, normalizedTarget, true)
+          );
+        }
+        const report = await runTagEdit(paths, () =>
+          renameTag(rootFolderPath, normalizedSource, normalizedTarget)
+        );
+        set((current) => {
+          if (!current.selectedTags.includes(normalizedSource)) return {};
+          const selectedTags = current.selectedTags.filter((tag) => tag !== normalizedSource);
+          if (!selectedTags.includes(desiredTarget)) selectedTags.push(desiredTarget);
+          return { selectedTags };
+        });
+        return summarize(report);
+      },
 
-        for (const candidate of candidates) {
-          const { note, nextContent } = candidate;
-          try {
-            if (note.filePath) {
-              cancelPendingNoteWrite(note.filePath);
-              await writeMarkdownFileOnDisk(note.filePath, nextContent);
-            }
-            successfulUpdates.set(note.id, nextContent);
-          } catch (error) {
-            failedNotes += 1;
-            console.error(`Failed to apply tag operation to note ${note.name}:`, error);
-          }
+      // Sets (or, with a null value, removes) one frontmatter property. Goes
+      // through runTagEdit so unsaved edits reach disk first and the rewritten
+      // note is read back; resolves with the note's properties afterwards.
+      editNoteProperty: async (noteId, key, value) => {
+        const note = get().items.find((item) => item.id === noteId && item.type === "note");
+        if (!note?.filePath) {
+          throw new Error("Note has no file path");
         }
+        let properties = [];
+        await get().runTagEdit([note.filePath], async () => {
+          const edit =
+            value === null
+              ? await removeNoteProperty(note.filePath, key)
+              : await setNoteProperty(note.filePath, key, value);
+          properties = edit.properties;
+          return { changed: [note.filePath], skipped: [] };
+        });
+        return properties;
+      },
 
-        if (successfulUpdates.size > 0) {
-          set((current) => {
-            const updatedItems = current.items.map((item) => {
-              if (item.type !== "note") return item;
-              const nextContent = successfulUpdates.get(item.id);
-              if (nextContent === undefined) return item;
-
-              return ensureNoteMetadata({
-                ...item,
-                content: nextContent,
-                updatedAt: new Date().toISOString(),
-              });
-            });
+      // The note's stable id. Assigning one writes it into the frontmatter, so
+      // this reads the note back the same way a property edit does.
+      getStableNoteId: async (noteId) => {
+        const note = get().items.find((item) => item.id === noteId && item.type === "note");
+        if (!note?.filePath) {
+          throw new Error("Note has no file path");
+        }
+        let stableId = null;
+        await get().runTagEdit([note.filePath], async () => {
+          stableId = await ensureNoteId(note.filePath);
+          return { changed: [note.filePath], skipped: [] };
+        });
+        return stableId;
+      },
 
-            const dirtyNoteIds = current.dirtyNoteIds.filter((id) => !successfulUpdates.has(id));
-            const selectedTags = current.selectedTags.filter((tag) => tag !== normalizedSource);
-            if (
-              desiredTarget &&
-              current.selectedTags.includes(normalizedSource) &&
-              !selectedTags.includes(desiredTarget)
-            ) {
-              selectedTags.push(desiredTarget);
-            }
+      // The `^id` of the block on `line`, written into the note if it had none
+      getBlockId: async (noteId, line) => {
+        const note = get().items.find((item) => item.id === noteId && item.type === "note");
+        if (!note?.filePath) {
+          throw new Error("Note has no file path");
+        }
+        let blockId = null;
+        await get().runTagEdit([note.filePath], async () => {
+          blockId = await ensureBlockId(note.filePath, line);
+          return { changed: [note.filePath], skipped: [] };
+        });
+        return blockId;
+      },
 
-            return {
-              items: updatedItems,
-              dirtyNoteIds,
-              selectedTags,
-            };
-          });
+      // Runs one automation rule on a note, by name or id. Goes through
+      // runTagEdit since the rule may rewrite the note; resolves with the
+      // rule's log entry.
+      runAutomationOnNote: async (noteId, rule) => {
+        const { rootFolderPath, items, runTagEdit } = get();
+        const note = items.find((item) => item.id === noteId && item.type === "note");
+        if (!rootFolderPath || !note?.filePath) {
+          throw new Error("Open a note in a workspace first");
         }
+        let entry = null;
+        await runTagEdit([note.filePath], async () => {
+          entry = await runAutomation(rootFolderPath, rule, note.filePath);
+          return { changed: [note.filePath], skipped: [] };
+        });
+        return entry;
+      },
 
-        return {
-          changedNotes: successfulUpdates.size,
-          failedNotes,
-          sourceTag: normalizedSource,
-          targetTag: desiredTarget,
-        };
+      // Links the given mentions in the note at `path` to the note at `target`
+      linkMentions: async (path, target, mentions) => {
+        await get().runTagEdit([path], async () => {
+          await linkUnlinkedMentions(
+            path,
+            target,
+            mentions.map(({ start, end, text }) => ({ start, end, text }))
+          );
+          return { changed: [path], skipped: [] };
+        });
       },
 
       // Custom template management
@@ -2030,7 +2630,7 @@ const useNotesStore = create(
           if (item.type !== "note" || !item.content) return false;
 
           const noteTags = extractTags(item.content);
-          return selectedTags.every((tag) => noteTags.includes(tag));
+          return selectedTags.every((tag) => noteTags.some((noteTag) => tagMatches(noteTag, tag)));
         });
       },
 
@@ -2069,28 +2669,32 @@ const useNotesStore = create(
       },
     }),
     {
-      name: "marky-storage",
+      name: storageName,
+      storage: createJSONStorage(() => (isNoteWindow ? readOnlyLocalStorage : localStorage)),
       partialize: (state) => ({
-        items: state.items,
-        currentNoteId: state.currentNoteId,
-        expandedFolders: state.expandedFolders,
+        // A vault's tree is rebuilt from disk after unlocking, so none of it is persisted
+        items: state.isVaultWorkspace
+          ? state.items.filter((item) => !item.filePath)
+          : state.items.map(toPersistableItem),
+        currentNoteId: state.isVaultWorkspace ? null : state.currentNoteId,
+        expandedFolders: state.isVaultWorkspace ? [] : state.expandedFolders,
         rootFolderPath: state.rootFolderPath,
-        rootFolderId: state.rootFolderId,
-        recentNotes: state.recentNotes,
+        rootFolderId: state.isVaultWorkspace ? null : state.rootFolderId,
+        recentNotes: state.recentNotes.filter((note) => !isInActiveVault(note.filePath)),
         pinnedNotes: state.pinnedNotes,
-        openNoteIds: state.openNoteIds,
+        openNoteIds: state.isVaultWorkspace ? [] : state.openNoteIds,
         sidebarWidth: state.sidebarWidth,
         editorSplitRatio: state.editorSplitRatio,
         recentWorkspaces: state.recentWorkspaces,
+        showArchived: state.showArchived,
       }),
       onRehydrateStorage: () => (state) => {
         if (!state?.rootFolderPath) return;
         // Dynamically read the setting so we don't create a circular dep
         const { openRecentOnStartup } = window.__markySettings?.getState?.() ?? {};
         const shouldReopen = openRecentOnStartup !== false; // default true if setting not yet loaded
-        if (shouldReopen) {
-          state.refreshRootFromDisk?.();
-        } else {
+        // When reopening, the backend emits `open-folder` once the webview is ready
+        if (!shouldReopen) {
           // Clear the persisted workspace so the workspace-required modal shows
           state.rootFolderPath = null;
           state.rootFolderId = null;
@@ -0,0 +1,21 @@
+import { create } from "zustand";
+
+// Commands registered by running plugins, for the command palette
+const usePluginStore = create((set) => ({
+  commands: [],
+
+  registerCommand: (pluginId, id, name) =>
+    set((state) => ({
+      commands: [
+        ...state.commands.filter((cmd) => !(cmd.pluginId === pluginId && cmd.id === id)),
+        { pluginId, id, name },
+      ],
+    })),
+
+  clearCommands: (pluginId) =>
+    set((state) => ({
+      commands: state.commands.filter((cmd) => cmd.pluginId !== pluginId),
+    })),
+}));
+
+export default usePluginStore;
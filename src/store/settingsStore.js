@@ -1,5 +1,12 @@
 import { create } from "zustand";
 import { persist } from "zustand/middleware";
+import {
+  fromAccelerator,
+  getKeymap,
+  resetKeymap as resetStoredKeymap,
+  setShortcut,
+  toAccelerator,
+} from "../utils/keymap";
 
 // Theme definitions
 export const THEMES = [
@@ -160,7 +167,8 @@ export const ACCENT_COLORS = [
 export const DEFAULT_KEYMAPS = {
   newNote: { key: "n", modifiers: ["mod"], description: "Create new note" },
   newFolder: { key: "N", modifiers: ["mod", "shift"], description: "Create new folder" },
-  openFolder: { key: "o", modifiers: ["mod"], description: "Open folder" },
+  openFile: { key: "o", modifiers: ["mod"], description: "Open file" },
+  openFolder: { key: "O", modifiers: ["mod", "shift"], description: "Open folder" },
   save: { key: "s", modifiers: ["mod"], description: "Save current note" },
   closeTab: { key: "w", modifiers: ["mod"], description: "Close current tab" },
   commandPalette: { key: "k", modifiers: ["mod"], description: "Open command palette" },
@@ -168,6 +176,7 @@ export const DEFAULT_KEYMAPS = {
   editorSearch: { key: "f", modifiers: ["mod"], description: "Find in editor" },
   toggleSidebar: { key: "b", modifiers: ["mod"], description: "Toggle sidebar" },
   showShortcuts: { key: "/", modifiers: ["mod"], description: "Show keyboard shortcuts" },
+  openSettings: { key: ",", modifiers: ["mod"], description: "Open settings" },
   viewEditor: { key: "1", modifiers: ["mod"], description: "Editor only view" },
   viewSplit: { key: "2", modifiers: ["mod"], description: "Split view" },
   viewPreview: { key: "3", modifiers: ["mod"], description: "Preview only view" },
@@ -213,12 +222,12 @@ export const KEYMAP_CATEGORIES = [
   {
     name: "File Operations",
     iconPath: "M3 7v10a2 2 0 002 2h14a2 2 0 002-2V9a2 2 0 00-2-2h-6l-2-2H5a2 2 0 00-2 2z",
-    actions: ["newNote", "newFolder", "openFolder", "save"],
+    actions: ["newNote", "newFolder", "openFile", "openFolder", "save"],
   },
   {
     name: "Navigation",
     iconPath: "M21 21l-6-6m2-5a7 7 0 11-14 0 7 7 0 0114 0z",
-    actions: ["commandPalette", "search", "toggleSidebar"],
+    actions: ["commandPalette", "search", "toggleSidebar", "openSettings"],
   },
   {
     name: "View",
@@ -278,6 +287,7 @@ export const applyAccentColor = (colorId) => {
 
 // Helper to format keymap for display
 export const formatKeymap = (keymap) => {
+  if (!keymap.key) return ["None"];
   const parts = [];
   if (keymap.modifiers.includes("mod")) {
     parts.push(navigator.platform.includes("Mac") ? "⌘" : "Ctrl");
@@ -311,7 +321,6 @@ const createDefaultProfileSettings = () => ({
   typewriterMode: false,
   sidebarDensity: "comfortable",
   showSidebarMetadata: true,
-  keymaps: { ...DEFAULT_KEYMAPS },
 });
 
 const buildProfileSettingsSnapshot = (state) => ({
@@ -324,14 +333,29 @@ const buildProfileSettingsSnapshot = (state) => ({
   typewriterMode: state.typewriterMode,
   sidebarDensity: state.sidebarDensity,
   showSidebarMetadata: state.showSidebarMetadata,
-  keymaps: { ...DEFAULT_KEYMAPS, ...(state.keymaps || {}) },
 });
 
-const mergeProfileSettings = (profile = {}) => ({
-  ...createDefaultProfileSettings(),
-  ...profile,
-  keymaps: { ...DEFAULT_KEYMAPS, ...migrateLegacyKeymaps(profile?.keymaps || {}) },
-});
+// Profiles saved before the keymap moved to the backend still carry one; it's dropped
+const mergeProfileSettings = (profile = {}) => {
+  const settings = { ...createDefaultProfileSettings(), ...(profile || {}) };
+  delete settings.keymaps;
+  return settings;
+};
+
+// Keymaps from the backend's shortcut list; each records whether the user changed it
+const keymapsFromShortcuts = (shortcuts) =>
+  Object.fromEntries(
+    shortcuts
+      .filter((shortcut) => DEFAULT_KEYMAPS[shortcut.action])
+      .map((shortcut) => [
+        shortcut.action,
+        {
+          ...DEFAULT_KEYMAPS[shortcut.action],
+          ...fromAccelerator(shortcut.accelerator),
+          customized: shortcut.customized,
+        },
+      ])
+  );
 
 // Helper to check if a keyboard event matches a keymap
 export const matchesKeymap = (event, keymap) => {
@@ -365,12 +389,59 @@ const useSettingsStore = create(
       autosaveEnabled: false,
       autosaveDelay: 2000, // ms after last keystroke to auto-save
       typewriterMode: false,
+      pasteLinkTitles: true, // pasted URLs become [Page Title](url)
       sidebarDensity: "comfortable", // 'compact' | 'comfortable' | 'spacious'
       showSidebarMetadata: true,
       openRecentOnStartup: true,
+      secureDelete: false,
+      // Folders past either limit need a second look before deleting; 0 turns a limit off
+      deleteConfirmation: { files: 100, megabytes: 100 },
+      spotlightIndex: true, // macOS: notes show up in Spotlight
+      updateChannel: "stable", // 'stable' | 'beta'
+      logLevel: "info", // 'error' | 'warn' | 'info' | 'debug' | 'trace'
+      locale: "system", // 'system' | 'en' | 'de' | 'es' | 'fr'; menus and backend messages
+      // ICS files or http(s)/webcal URLs for the meeting-note picker
+      calendarSubscriptions: [],
+      // Cards and hover previews for web links; an empty allowlist allows any host
+      linkPreviews: {
+        enabled: true,
+        offline: false, // only show previews that are already cached
+        allowlist: [],
+      },
+      // Opt-in anonymous feature counts and timings; off until the user turns it on
+      telemetryEnabled: false,
+      // Native notifications shown while Marky is in the background, per category
+      notificationCategories: {
+        external_changes: true,
+        operations: true,
+        sync_conflicts: true,
+        reminders: true,
+        badge: true,
+      },
+      // Delivery for "Email Note"; the SMTP password is kept in the OS credential store
+      emailSettings: {
+        method: "client", // 'client' | 'smtp'
+        smtp_host: "",
+        smtp_port: 587,
+        smtp_security: "starttls", // 'tls' | 'starttls' | 'none'
+        smtp_username: "",
+        from_address: "",
+      },
+      // Local HTTP API for scripts and launchers; the token is kept in the OS credential store
+      apiSettings: {
+        enabled: false,
+        port: 27124,
+        mcp_enabled: false,
+        mcp_allow_append: false,
+        clipper_enabled: false,
+        clipper_folder: "Clippings",
+        tasks_feed_enabled: false,
+      },
 
-      // Keymaps (user customizations stored here)
+      // Keymaps, mirrored from the backend keymap store (which also drives the menu)
       keymaps: { ...DEFAULT_KEYMAPS },
+      // Whether shortcuts customized before the backend store existed were moved into it
+      keymapsMigrated: false,
 
       // Shared settings are used when a workspace has no dedicated profile
       sharedSettings: createDefaultProfileSettings(),
@@ -433,6 +504,9 @@ const useSettingsStore = create(
       setTypewriterMode: (enabled) => {
         get().syncProfileState({ typewriterMode: enabled });
       },
+      setPasteLinkTitles: (enabled) => {
+        set({ pasteLinkTitles: enabled });
+      },
       setSidebarDensity: (density) => {
         get().syncProfileState({ sidebarDensity: density });
       },
@@ -441,6 +515,99 @@ const useSettingsStore = create(
       },
       setOpenRecentOnStartup: (enabled) => {
         set({ openRecentOnStartup: enabled });
+        // The backend decides what to reopen at launch
+        import("@tauri-apps/api/core")
+          .then(({ invoke }) =>
+            invoke("set_setting", { key: "reopen_last_workspace", value: enabled })
+          )
+          .catch((error) => console.error("Failed to sync reopen setting:", error));
+      },
+
+      setSpotlightIndex: (enabled) => {
+        set({ spotlightIndex: enabled });
+        import("@tauri-apps/api/core")
+          .then(({ invoke }) => invoke("set_setting", { key: "spotlight", value: enabled }))
+          .catch((error) => console.error("Failed to sync Spotlight setting:", error));
+      },
+
+      setSecureDelete: (enabled) => {
+        set({ secureDelete: enabled });
+        // Deletion happens in the backend, which owns the setting
+        import("@tauri-apps/api/core")
+          .then(({ invoke }) => invoke("set_setting", { key: "secure_delete", value: enabled }))
+          .catch((error) => console.error("Failed to sync secure delete setting:", error));
+      },
+
+      setDeleteConfirmationLimit: async (key, value) => {
+        set((state) => ({ deleteConfirmation: { ...state.deleteConfirmation, [key]: value } }));
+        const { invoke } = await import("@tauri-apps/api/core");
+        const settings = await invoke("set_setting", { key: `delete_confirmation.${key}`, value });
+        set({ deleteConfirmation: settings.delete_confirmation });
+      },
+
+      setUpdateChannel: async (channel) => {
+        set({ updateChannel: channel });
+        const { invoke } = await import("@tauri-apps/api/core");
+        await invoke("set_setting", { key: "update_channel", value: channel });
+      },
+
+      setNotificationCategory: (category, enabled) => {
+        set((state) => ({
+          notificationCategories: { ...state.notificationCategories, [category]: enabled },
+        }));
+        import("@tauri-apps/api/core")
+          .then(({ invoke }) =>
+            invoke("set_setting", { key: `notifications.${category}`, value: enabled })
+          )
+          .catch((error) => console.error("Failed to sync notification setting:", error));
+      },
+
+      setEmailSetting: async (key, value) => {
+        set((state) => ({ emailSettings: { ...state.emailSettings, [key]: value } }));
+        const { invoke } = await import("@tauri-apps/api/core");
+        await invoke("set_setting", { key: `email.${key}`, value });
+      },
+
+      setApiSetting: async (key, value) => {
+        set((state) => ({ apiSettings: { ...state.apiSettings, [key]: value } }));
+        const { invoke } = await import("@tauri-apps/api/core");
+        await invoke("set_setting", { key: `api.${key}`, value });
+      },
+
+      setCalendarSubscriptions: async (subscriptions) => {
+        const cleaned = [...new Set(subscriptions.map((s) => s.trim()).filter(Boolean))];
+        set({ calendarSubscriptions: cleaned });
+        const { invoke } = await import("@tauri-apps/api/core");
+        await invoke("set_setting", { key: "calendar_subscriptions", value: cleaned });
+      },
+
+      setLinkPreviewSetting: async (key, value) => {
+        set((state) => ({ linkPreviews: { ...state.linkPreviews, [key]: value } }));
+        const { forgetLinkPreviews } = await import("../utils/linkPreviews");
+        forgetLinkPreviews();
+        const { invoke } = await import("@tauri-apps/api/core");
+        // The backend tidies up the allowlist, so its copy is kept
+        const settings = await invoke("set_setting", { key: `link_previews.${key}`, value });
+        set({ linkPreviews: settings.link_previews });
+      },
+
+      setTelemetryEnabled: async (enabled) => {
+        set({ telemetryEnabled: enabled });
+        const { invoke } = await import("@tauri-apps/api/core");
+        // Turning it off also makes the backend drop what it recorded
+        await invoke("set_setting", { key: "telemetry", value: enabled });
+      },
+
+      setLocale: async (locale) => {
+        set({ locale });
+        const { invoke } = await import("@tauri-apps/api/core");
+        await invoke("set_setting", { key: "locale", value: locale });
+      },
+
+      setLogLevel: async (level) => {
+        set({ logLevel: level });
+        const { invoke } = await import("@tauri-apps/api/core");
+        await invoke("set_log_level", { level });
       },
 
       setScrollSyncEnabled: (enabled) => {
@@ -459,26 +626,40 @@ const useSettingsStore = create(
         set({ isRecordingKeymap: isRecording });
       },
 
-      updateKeymap: (actionId, newKeymap) => {
-        get().syncProfileState((state) => ({
-          keymaps: {
-            ...state.keymaps,
-            [actionId]: { ...state.keymaps[actionId], ...newKeymap },
-          },
-        }));
+      applyShortcuts: (shortcuts) => {
+        set({ keymaps: { ...DEFAULT_KEYMAPS, ...keymapsFromShortcuts(shortcuts) } });
+      },
+
+      loadKeymap: async () => {
+        let shortcuts = await getKeymap();
+        if (!get().keymapsMigrated) {
+          const local = migrateLegacyKeymaps(get().keymaps || {});
+          if (!shortcuts.some((shortcut) => shortcut.customized)) {
+            for (const [actionId, keymap] of Object.entries(local)) {
+              const original = DEFAULT_KEYMAPS[actionId];
+              if (!original || !keymap?.key || areKeymapsEqual(keymap, original)) continue;
+              // One that now conflicts (say, with a menu item) keeps the default
+              shortcuts = await setShortcut(actionId, toAccelerator(keymap)).catch(
+                () => shortcuts
+              );
+            }
+          }
+          set({ keymapsMigrated: true });
+        }
+        get().applyShortcuts(shortcuts);
       },
 
-      resetKeymaps: () => {
-        get().syncProfileState({ keymaps: { ...DEFAULT_KEYMAPS } });
+      // Rejects with the reason when the shortcut is already taken
+      updateKeymap: async (actionId, newKeymap) => {
+        get().applyShortcuts(await setShortcut(actionId, toAccelerator(newKeymap)));
       },
 
-      resetKeymap: (actionId) => {
-        get().syncProfileState((state) => ({
-          keymaps: {
-            ...state.keymaps,
-            [actionId]: { ...DEFAULT_KEYMAPS[actionId] },
-          },
-        }));
+      resetKeymaps: async () => {
+        get().applyShortcuts(await resetStoredKeymap());
+      },
+
+      resetKeymap: async (actionId) => {
+        get().applyShortcuts(await setShortcut(actionId, null));
       },
 
       hasWorkspaceSettingsProfile: (workspacePath) => {
@@ -608,6 +789,24 @@ const useSettingsStore = create(
         applyAccentColor(activeSnapshot.accentColorId);
       },
 
+      // Refreshes the copies of backend-owned settings after the backend replaced them,
+      // as an imported profile does
+      applyBackendSettings: (settings) => {
+        if (!settings) return;
+        set((state) => ({
+          openRecentOnStartup: settings.reopen_last_workspace,
+          spotlightIndex: settings.spotlight,
+          secureDelete: settings.secure_delete,
+          deleteConfirmation: settings.delete_confirmation,
+          updateChannel: settings.update_channel,
+          logLevel: settings.log_level,
+          locale: settings.locale,
+          notificationCategories: { ...state.notificationCategories, ...settings.notifications },
+          linkPreviews: settings.link_previews,
+        }));
+        import("../utils/linkPreviews").then(({ forgetLinkPreviews }) => forgetLinkPreviews());
+      },
+
       // Initialize settings (call on app start)
       initializeSettings: () => {
         const state = get();
@@ -655,7 +854,21 @@ const useSettingsStore = create(
         sidebarDensity: state.sidebarDensity,
         showSidebarMetadata: state.showSidebarMetadata,
         keymaps: state.keymaps,
+        keymapsMigrated: state.keymapsMigrated,
         openRecentOnStartup: state.openRecentOnStartup,
+        pasteLinkTitles: state.pasteLinkTitles,
+        secureDelete: state.secureDelete,
+        deleteConfirmation: state.deleteConfirmation,
+        spotlightIndex: state.spotlightIndex,
+        linkPreviews: state.linkPreviews,
+        telemetryEnabled: state.telemetryEnabled,
+        updateChannel: state.updateChannel,
+        logLevel: state.logLevel,
+        locale: state.locale,
+        notificationCategories: state.notificationCategories,
+        calendarSubscriptions: state.calendarSubscriptions,
+        emailSettings: state.emailSettings,
+        apiSettings: state.apiSettings,
         sharedSettings: state.sharedSettings,
         workspaceProfiles: state.workspaceProfiles,
         activeWorkspacePath: state.activeWorkspacePath,
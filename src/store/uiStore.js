@@ -13,6 +13,9 @@ const useUIStore = create((set) => ({
   focusMode: false,
   showWorkspaceModal: false,
   setShowWorkspaceModal: (value) => set({ showWorkspaceModal: value }),
+  // { old: { path, name }, new: { path, name } } while two notes are being compared
+  comparingNotes: null,
+  setComparingNotes: (value) => set({ comparingNotes: value }),
 
   toggleFocusMode: () => set((state) => ({ focusMode: !state.focusMode })),
   setFocusMode: (value) => set({ focusMode: value }),
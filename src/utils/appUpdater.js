@@ -1,4 +1,5 @@
-import { check } from "@tauri-apps/plugin-updater";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import useUIStore from "../store/uiStore";
 
 const isTauriRuntime = () => Boolean(window.__TAURI_INTERNALS__);
@@ -26,7 +27,8 @@ export const checkForAppUpdate = async ({ silent = false } = {}) => {
   });
 
   try {
-    const update = await check();
+    // The backend checks the endpoint for the configured channel (stable or beta)
+    const update = await invoke("check_for_updates");
 
     if (!update) {
       setAppUpdate({
@@ -108,9 +110,17 @@ export const installAppUpdate = async (providedUpdate = null) => {
     }
   );
 
-  let downloaded = 0;
-  let contentLength = 0;
+  const reportProgress = (finished, progress) => {
+    const message = finished ? "Installing update..." : "Downloading update...";
+    setAppUpdate({
+      status: finished ? "installing" : "downloading",
+      message,
+      progress,
+    });
+    updateNotification(progressNotificationId, { message, progress });
+  };
 
+  const unlisteners = [];
   try {
     setAppUpdate({
       status: "downloading",
@@ -119,33 +129,17 @@ export const installAppUpdate = async (providedUpdate = null) => {
       error: null,
     });
 
-    await update.downloadAndInstall((event) => {
-      if (event.event === "Started") {
-        contentLength = event.data?.contentLength || 0;
-        downloaded = 0;
-      }
-
-      if (event.event === "Progress") {
-        downloaded += event.data?.chunkLength || 0;
-      }
-
-      if (event.event === "Finished") {
-        downloaded = contentLength || downloaded;
-      }
-
-      const progress = contentLength > 0 ? Math.min((downloaded / contentLength) * 100, 100) : 0;
-
-      setAppUpdate({
-        status: event.event === "Finished" ? "installing" : "downloading",
-        message: event.event === "Finished" ? "Installing update..." : "Downloading update...",
-        progress,
-      });
+    unlisteners.push(
+      await listen("update-download-progress", (event) => {
+        const { downloaded = 0, content_length: contentLength } = event.payload || {};
+        const progress =
+          contentLength > 0 ? Math.min((downloaded / contentLength) * 100, 100) : 0;
+        reportProgress(false, progress);
+      }),
+      await listen("update-downloaded", () => reportProgress(true, 100))
+    );
 
-      updateNotification(progressNotificationId, {
-        message: event.event === "Finished" ? "Installing update..." : "Downloading update...",
-        progress,
-      });
-    });
+    await invoke("install_update");
 
     setAppUpdate({
       status: "installed",
@@ -175,5 +169,8 @@ export const installAppUpdate = async (providedUpdate = null) => {
       type: "error",
       progress: null,
     });
+  } finally {
+    unlisteners.forEach((unlisten) => unlisten());
   }
 };
+
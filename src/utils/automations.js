@@ -0,0 +1,99 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} AutomationRule
+ * @property {string} id - Empty for new rules; the backend assigns one
+ * @property {string} name
+ * @property {boolean} enabled
+ * @property {boolean} dry_run - Only log what the rule would do
+ * @property {'save' | 'create'} trigger
+ * @property {string | null} tag - Only notes with this tag, without the `#`
+ * @property {string | null} folder - Only notes under this workspace-relative folder
+ * @property {'export_html' | 'apply_template' | 'add_tag'} action
+ * @property {string | null} template - For `apply_template`, a file in the templates folder
+ * @property {string | null} add_tag - For `add_tag`
+ * @property {string | null} export_folder - For `export_html`, defaults to `Exports`
+ */
+
+/**
+ * Automation rules stored in the workspace's `.marky/automations.json`
+ * @param {string} folderPath - Workspace root
+ * @returns {Promise<AutomationRule[]>}
+ */
+export async function getAutomations(folderPath) {
+  try {
+    return await invoke('get_automations', { folderPath });
+  } catch (error) {
+    console.error('Error loading automations:', error);
+    throw error;
+  }
+}
+
+/**
+ * Replace the workspace's automation rules
+ * @param {string} folderPath - Workspace root
+ * @param {AutomationRule[]} rules
+ * @returns {Promise<AutomationRule[]>} The rules as saved, with ids assigned
+ */
+export async function setAutomations(folderPath, rules) {
+  try {
+    return await invoke('set_automations', { folderPath, rules });
+  } catch (error) {
+    console.error('Error saving automations:', error);
+    throw error;
+  }
+}
+
+/**
+ * Run the workspace's rules against a note without changing anything
+ * @param {string} folderPath - Workspace root
+ * @param {string} filePath - Note to test against
+ * @param {'save' | 'create'} trigger
+ * @returns {Promise<Array<{time: string, rule_id: string, rule_name: string, trigger: string, path: string, action: string, dry_run: boolean, outcome: 'done' | 'skipped' | 'failed', message: string}>>}
+ */
+export async function dryRunAutomations(folderPath, filePath, trigger) {
+  try {
+    return await invoke('dry_run_automations', { folderPath, filePath, trigger });
+  } catch (error) {
+    console.error('Error testing automations:', error);
+    throw error;
+  }
+}
+
+/**
+ * Run one rule on a note now, regardless of its trigger and filters
+ * @param {string} folderPath - Workspace root
+ * @param {string} rule - The rule's name or id
+ * @param {string} filePath - Note to run it on
+ * @returns {Promise<object>} A log entry shaped like `dryRunAutomations` results
+ */
+export async function runAutomation(folderPath, rule, filePath) {
+  try {
+    return await invoke('run_automation', { folderPath, rule, filePath });
+  } catch (error) {
+    console.error('Error running automation:', error);
+    throw error;
+  }
+}
+
+/**
+ * Recent automation runs for a workspace, newest first
+ * @param {string} folderPath - Workspace root
+ * @returns {Promise<Array<object>>} Entries shaped like `dryRunAutomations` results
+ */
+export async function getAutomationLog(folderPath) {
+  try {
+    return await invoke('get_automation_log', { folderPath });
+  } catch (error) {
+    console.error('Error loading automation log:', error);
+    return [];
+  }
+}
+
+/**
+ * @param {string} folderPath - Workspace root
+ * @returns {Promise<void>}
+ */
+export async function clearAutomationLog(folderPath) {
+  return invoke('clear_automation_log', { folderPath });
+}
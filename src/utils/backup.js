@@ -1,6 +1,9 @@
 import JSZip from 'jszip';
+import { invoke } from '@tauri-apps/api/core';
 import { exists, mkdir, readFile, readTextFile, writeFile, writeTextFile } from '@tauri-apps/plugin-fs';
 import { open, save } from '@tauri-apps/plugin-dialog';
+import { notifyOperationFinished } from './notifications';
+import { ensureDiskSpace } from './fileSystem';
 
 /**
  * Export the current workspace as a .zip file
@@ -76,7 +79,9 @@ export async function exportWorkspaceAsZip(rootFolderPath, items, settings = nul
   const filePath = typeof savePath === 'string' ? savePath : savePath.path;
 
   // Write the zip file to disk
+  await ensureDiskSpace(filePath, zipData.byteLength);
   await writeFile(filePath, zipData);
+  notifyOperationFinished('Backup complete', `${folderName} was saved to ${filePath}`);
 
   return filePath;
 }
@@ -210,41 +215,46 @@ export async function restoreWorkspaceFromZip(options = {}) {
   };
 }
 
-// ─── Settings JSON export / import ───────────────────────────────────────────
+// ─── Profile export / import ─────────────────────────────────────────────────
 
 /**
- * Export the provided settings object as a .json file chosen by the user.
- * @param {object} settings  Plain settings object (from settingsStore)
+ * Export a profile: the backend's global settings (with snippets) and custom
+ * shortcuts, plus `frontend` (appearance, templates), in one file chosen by the user.
+ * @param {object} frontend  Frontend-owned part of the profile
  * @returns {Promise<string|null>} Saved file path, or null if cancelled
  */
-export async function exportSettingsAsJson(settings) {
+export async function exportProfile(frontend) {
   const date = new Date().toISOString().slice(0, 10);
   const savePath = await save({
-    defaultPath: `marky-settings-${date}.json`,
-    filters: [{ name: 'JSON', extensions: ['json'] }],
+    defaultPath: `marky-profile-${date}.json`,
+    filters: [{ name: 'Marky Profile', extensions: ['json'] }],
   });
   if (!savePath) return null;
 
   const filePath = typeof savePath === 'string' ? savePath : savePath.path;
-  await writeTextFile(filePath, JSON.stringify(settings, null, 2));
+  await invoke('export_profile', { path: filePath, frontend });
   return filePath;
 }
 
 /**
- * Let the user pick a previously-exported settings JSON and return its parsed
- * contents. Returns null if the user cancels.
- * @returns {Promise<object|null>}
+ * Let the user pick a profile and apply its backend part. Settings files
+ * exported before profiles existed come back as `{ legacy }` for the
+ * frontend to import itself.
+ * @returns {Promise<{settings: object, keymap: Array, frontend: object}|{legacy: object}|null>}
  */
-export async function importSettingsFromJson() {
+export async function importProfile() {
   const selected = await open({
     multiple: false,
-    filters: [{ name: 'JSON', extensions: ['json'] }],
+    filters: [{ name: 'Marky Profile', extensions: ['json'] }],
   });
   if (!selected) return null;
 
   const filePath = typeof selected === 'string' ? selected : selected.path;
-  const raw = await readTextFile(filePath);
-  return JSON.parse(raw);
+  const parsed = JSON.parse(await readTextFile(filePath));
+  if (parsed?.format !== 'marky-profile') {
+    return { legacy: parsed };
+  }
+  return await invoke('import_profile', { path: filePath });
 }
 
 // ─── Templates collection export / import ────────────────────────────────────
@@ -391,5 +401,9 @@ export async function batchExportNotes(notes, format, rootFolderPath, markedPars
 
   const filePath = typeof savePath === 'string' ? savePath : savePath.path;
   await writeFile(filePath, zipData);
+  notifyOperationFinished(
+    'Export complete',
+    `${notes.length} note${notes.length !== 1 ? 's' : ''} exported to ${filePath}`
+  );
   return filePath;
 }
@@ -0,0 +1,38 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} BlockReferences
+ * @property {string} id - The block's `^id`
+ * @property {number|null} line - Where the block starts, 1-based; null when the note no longer has it
+ * @property {string} text - The start of the block's text
+ * @property {{path: string, title: string}[]} references - Notes linking to it with `[[Note#^id]]`
+ */
+
+/**
+ * The `^id` of the block on a line, adding one to the note first if needed
+ * @param {string} path - Absolute note path
+ * @param {number} line - 1-based line inside the block
+ * @returns {Promise<string>}
+ */
+export async function ensureBlockId(path, line) {
+  try {
+    return await invoke('ensure_block_id', { path, line });
+  } catch (error) {
+    console.error('Error adding block id:', error);
+    throw error;
+  }
+}
+
+/**
+ * Blocks of a note that are referenced with `[[Note#^id]]`, and by which notes
+ * @param {string} path - Absolute note path
+ * @returns {Promise<BlockReferences[]>}
+ */
+export async function getBlockReferences(path) {
+  try {
+    return await invoke('get_block_references', { path });
+  } catch (error) {
+    console.error('Error loading block references:', error);
+    throw error;
+  }
+}
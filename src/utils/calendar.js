@@ -0,0 +1,112 @@
+import { invoke } from '@tauri-apps/api/core';
+import { save } from '@tauri-apps/plugin-dialog';
+
+/**
+ * Per-day calendar data for a workspace, computed from the backend index.
+ * Only days with something on them are returned, in date order.
+ * @param {string} folderPath
+ * @param {string} start - Inclusive `YYYY-MM-DD` local date
+ * @param {string} end - Inclusive `YYYY-MM-DD` local date, at most 400 days after `start`
+ * @returns {Promise<Array<{
+ *   date: string,
+ *   daily_note: {path: string, title: string} | null,
+ *   created: Array<{path: string, title: string}>,
+ *   modified: Array<{path: string, title: string}>,
+ *   tasks: Array<{path: string, title: string, text: string, done: boolean, line: number}>
+ * }>>}
+ */
+export async function getNotesByDate(folderPath, start, end) {
+  try {
+    return await invoke('get_notes_by_date', { folderPath, start, end });
+  } catch (error) {
+    console.error('Error loading calendar:', error);
+    throw error;
+  }
+}
+
+/**
+ * Nearest existing daily note before or after a date, skipping days without one,
+ * for back/forward journal navigation.
+ * @param {string} folderPath
+ * @param {string} date - `YYYY-MM-DD` local date, or the path of a daily note to step from
+ * @param {'previous' | 'next'} direction
+ * @returns {Promise<{path: string, title: string, date: string} | null>}
+ */
+export async function getAdjacentDailyNote(folderPath, date, direction) {
+  try {
+    return await invoke('get_adjacent_daily_note', { folderPath, date, direction });
+  } catch (error) {
+    console.error('Error finding adjacent daily note:', error);
+    throw error;
+  }
+}
+
+/**
+ * Today's events from the calendar subscriptions in settings (ICS files or
+ * http(s)/webcal URLs). Subscriptions that fail to load are skipped.
+ * @returns {Promise<Array<{
+ *   id: string,
+ *   title: string,
+ *   start: string,
+ *   end: string | null,
+ *   all_day: boolean,
+ *   location: string | null,
+ *   description: string | null,
+ *   attendees: string[],
+ *   source: string
+ * }>>}
+ */
+export async function listTodaysEvents() {
+  try {
+    return await invoke('list_todays_events');
+  } catch (error) {
+    console.error('Error loading calendar events:', error);
+    throw error;
+  }
+}
+
+/**
+ * Create a meeting note pre-filled with an event's title, time and attendees.
+ * Templates may use {{title}}, {{date}}, {{time}}, {{location}}, {{attendees}}
+ * and {{description}}.
+ * @param {string} eventId - An id from the last `listTodaysEvents` call
+ * @param {string} folderPath - Workspace root
+ * @param {string | null} parentFolderPath - Folder for the note; the workspace root when null
+ * @param {string | null} template - Meeting template content; the workspace's when null
+ * @returns {Promise<string>} Path of the new note
+ */
+export async function createNoteFromEvent(eventId, folderPath, parentFolderPath, template) {
+  try {
+    return await invoke('create_note_from_event', {
+      eventId,
+      folderPath,
+      parentFolderPath,
+      template,
+    });
+  } catch (error) {
+    console.error('Error creating meeting note:', error);
+    throw error;
+  }
+}
+
+/**
+ * Save the workspace's dated tasks as an iCalendar file, asking where first
+ * @param {string} folderPath
+ * @param {{asTodos?: boolean, includeDone?: boolean}} [options] - VTODOs instead of all-day events, and whether finished tasks are kept
+ * @returns {Promise<number|null>} How many tasks were written, or null if cancelled
+ */
+export async function exportTasksCalendar(folderPath, { asTodos = false, includeDone = false } = {}) {
+  const savePath = await save({
+    defaultPath: 'marky-tasks.ics',
+    filters: [{ name: 'iCalendar', extensions: ['ics'] }],
+  });
+  if (!savePath) return null;
+
+  const outputPath = typeof savePath === 'string' ? savePath : savePath.path;
+  try {
+    return await invoke('export_tasks_ics', { folderPath, outputPath, asTodos, includeDone });
+  } catch (error) {
+    console.error('Error exporting tasks calendar:', error);
+    throw error;
+  }
+}
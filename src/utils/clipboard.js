@@ -0,0 +1,18 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Put markdown on the system clipboard as HTML and RTF (with the markdown as
+ * plain text), so pasting into Gmail, Word and the like keeps the formatting
+ * @param {string | null} path - Note file path, used to inline embeds; with no
+ *   selection the saved note is copied
+ * @param {string | null} selection - Markdown to copy
+ * @returns {Promise<void>}
+ */
+export async function copyAsRichText(path, selection = null) {
+  try {
+    await invoke('copy_as_rich_text', { path, selection });
+  } catch (error) {
+    console.error('Error copying as rich text:', error);
+    throw error;
+  }
+}
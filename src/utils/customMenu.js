@@ -0,0 +1,37 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} CustomMenuEntry
+ * @property {string} label - What the menu shows
+ * @property {'open_note' | 'run_automation' | 'open_url'} action
+ * @property {string} target - A note path or name, an automation's name or id, or an http(s)/mailto URL
+ */
+
+/**
+ * Entries in the Custom menu
+ * @returns {Promise<CustomMenuEntry[]>}
+ */
+export async function getCustomMenu() {
+  try {
+    const settings = await invoke('get_settings');
+    return settings.custom_menu;
+  } catch (error) {
+    console.error('Error loading custom menu:', error);
+    throw error;
+  }
+}
+
+/**
+ * Replace the Custom menu's entries; the app menu is rebuilt from them
+ * @param {CustomMenuEntry[]} entries
+ * @returns {Promise<CustomMenuEntry[]>} The entries as saved, without incomplete or invalid ones
+ */
+export async function saveCustomMenu(entries) {
+  try {
+    const settings = await invoke('set_setting', { key: 'custom_menu', value: entries });
+    return settings.custom_menu;
+  } catch (error) {
+    console.error('Error saving custom menu:', error);
+    throw error;
+  }
+}
@@ -0,0 +1,70 @@
+import { invoke } from '@tauri-apps/api/core';
+import { save } from '@tauri-apps/plugin-dialog';
+
+/**
+ * Ask where to save a diagnostics bundle and have the backend write it.
+ * The bundle holds versions, settings, anonymized workspace counts, recent errors,
+ * and crash reports; never note names or contents.
+ * @returns {Promise<string|null>} The saved file path, or null if cancelled
+ */
+export async function generateDiagnosticsBundle() {
+  const date = new Date().toISOString().slice(0, 10);
+  const savePath = await save({
+    defaultPath: `marky-diagnostics-${date}.zip`,
+    filters: [{ name: 'Zip Archive', extensions: ['zip'] }],
+  });
+  if (!savePath) return null;
+
+  const outputPath = typeof savePath === 'string' ? savePath : savePath.path;
+  return invoke('generate_diagnostics', { outputPath });
+}
+
+/**
+ * Read the most recent backend log lines
+ * @param {'error'|'warn'|'info'|'debug'|'trace'} [level] - Minimum severity to include
+ * @param {number} [lines] - How many lines to return, newest last
+ * @returns {Promise<string[]>}
+ */
+export async function getRecentLogs(level = 'info', lines = 200) {
+  return invoke('get_recent_logs', { level, lines });
+}
+
+/**
+ * Change how much the backend logs; persisted with the other settings
+ * @param {'error'|'warn'|'info'|'debug'|'trace'} level
+ * @returns {Promise<string>} The level now in effect
+ */
+export async function setLogLevel(level) {
+  return invoke('set_log_level', { level });
+}
+
+/**
+ * Time scanning, index building, and watcher setup for a workspace and list
+ * the folders that dominate it (e.g. a stray node_modules)
+ * @param {string} folderPath
+ * @returns {Promise<{folder: string, total_ms: number, scan: {ms: number, count: number}, index: {ms: number, count: number}, watcher: {ms: number, count: number}, walk: {ms: number, count: number}, hotspots: Array<{path: string, files: number, directories: number, notes: number, walk_ms: number, ignored: boolean}>}>}
+ */
+export async function profileWorkspace(folderPath) {
+  return invoke('profile_workspace', { folderPath });
+}
+
+/**
+ * Checksum every note against the manifest from the last check and report
+ * damage: contents changed without a modification, truncation, invalid UTF-8,
+ * and empty notes
+ * @param {string} folderPath
+ * @returns {Promise<{checked: number, added: number, changed: number, removed: number, previous_check: string|null, issues: Array<{path: string, kind: 'corrupted'|'truncated'|'invalid_utf8'|'empty', detail: string}>}>}
+ */
+export async function verifyWorkspace(folderPath) {
+  return invoke('verify_workspace', { folderPath });
+}
+
+/**
+ * Check frontmatter against the workspace's `.marky/schema.json`: required
+ * fields, value types, and allowed values
+ * @param {string} path - A note, or a folder to check every note under
+ * @returns {Promise<{schema_path: string, checked: number, violations: Array<{path: string, field: string|null, kind: 'missing'|'wrong_type'|'not_allowed'|'unknown_field'|'invalid_frontmatter', message: string}>}>}
+ */
+export async function validateFrontmatter(path) {
+  return invoke('validate_frontmatter', { path });
+}
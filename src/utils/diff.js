@@ -1,3 +1,5 @@
+import { invoke } from "@tauri-apps/api/core";
+
 /**
  * Lightweight line-level diff using a simplified LCS (Longest Common Subsequence) approach.
  * No external dependencies.
@@ -66,3 +68,65 @@ export function computeLineDiff(oldText, newText) {
   result.reverse();
   return result;
 }
+
+/**
+ * @typedef {Object} DiffLine
+ * @property {'equal'|'insert'|'delete'} kind
+ * @property {number|null} old_line - 1-based line on the old side
+ * @property {number|null} new_line - 1-based line on the new side
+ * @property {Array<{text: string, changed: boolean}>} segments - Words, with the changed runs marked
+ */
+
+/**
+ * @typedef {Object} Diff
+ * @property {Array<{old_start: number, old_lines: number, new_start: number, new_lines: number, lines: DiffLine[]}>} hunks
+ * @property {number} added
+ * @property {number} removed
+ */
+
+/**
+ * Word-level diff of two texts, computed by the backend.
+ * @param {string} oldText
+ * @param {string} newText
+ * @returns {Promise<Diff>}
+ */
+export async function diffText(oldText, newText) {
+  try {
+    return await invoke("diff_text", { old: oldText, new: newText });
+  } catch (error) {
+    console.error("Error diffing text:", error);
+    throw error;
+  }
+}
+
+/**
+ * Word-level diff of two saved notes, `pathA` as the old side.
+ * @param {string} pathA
+ * @param {string} pathB
+ * @returns {Promise<Diff>}
+ */
+export async function diffNotes(pathA, pathB) {
+  try {
+    return await invoke("diff_notes", { pathA, pathB });
+  } catch (error) {
+    console.error("Error diffing notes:", error);
+    throw error;
+  }
+}
+
+/**
+ * A note's changes since its latest git commit, or since `snapshot` when the
+ * note isn't committed.
+ * @param {string} path
+ * @param {{content?: string, snapshot?: string}} [options] - `content` is an unsaved draft to
+ *   compare instead of the saved file; `snapshot` is the newest local-history snapshot
+ * @returns {Promise<{source: 'git'|'snapshot', commit: string|null, diff: Diff}>}
+ */
+export async function diffAgainstHead(path, { content = null, snapshot = null } = {}) {
+  try {
+    return await invoke("diff_against_head", { path, content, snapshot });
+  } catch (error) {
+    console.error("Error diffing against the last version:", error);
+    throw error;
+  }
+}
@@ -0,0 +1,38 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} DuplicateFileGroup
+ * @property {string} hash - SHA-256 of the shared contents
+ * @property {number} size - Size of each copy in bytes
+ * @property {Array<{name: string, path: string, is_note: boolean}>} files
+ */
+
+/**
+ * Group byte-identical files (notes and attachments), largest savings first
+ * @param {string} folderPath - Workspace root
+ * @returns {Promise<DuplicateFileGroup[]>}
+ */
+export async function findDuplicateFiles(folderPath) {
+  try {
+    return await invoke('find_duplicate_files', { folderPath });
+  } catch (error) {
+    console.error('Error finding duplicate files:', error);
+    throw error;
+  }
+}
+
+/**
+ * Keep one copy, point links to the others at it, and delete the others
+ * @param {string} folderPath - Workspace root
+ * @param {string} keepPath - The copy to keep
+ * @param {string[]} duplicatePaths - Copies to remove
+ * @returns {Promise<{relinked_notes: number, removed: number}>}
+ */
+export async function mergeDuplicateFiles(folderPath, keepPath, duplicatePaths) {
+  try {
+    return await invoke('merge_duplicate_files', { folderPath, keepPath, duplicatePaths });
+  } catch (error) {
+    console.error('Error merging duplicate files:', error);
+    throw error;
+  }
+}
@@ -0,0 +1,27 @@
+import { invoke } from '@tauri-apps/api/core';
+import { buildStandaloneHtml } from './noteExport';
+
+// Key the SMTP password is stored under in the OS credential store
+export const SMTP_PASSWORD_SECRET = 'email.smtp_password';
+
+/**
+ * Email a note, either as a draft opened in the default mail client or sent
+ * directly over the SMTP server in settings. Local files the note links to or
+ * embeds are attached.
+ * @param {string} path - Note file path
+ * @param {string} noteName
+ * @param {string} content - Markdown, used to render the HTML body
+ * @param {'html' | 'markdown' | 'attachment'} format - Rendered HTML inline,
+ *   markdown inline, or the note attached as .md and .html files
+ * @param {string[]} to - Recipients; optional when drafting in the mail client
+ * @returns {Promise<{method: string, draft_path: string | null, attachments: string[], skipped_attachments: string[]}>}
+ */
+export async function emailNote(path, noteName, content, format, to = []) {
+  try {
+    const html = format === 'markdown' ? null : buildStandaloneHtml(noteName, content);
+    return await invoke('email_note', { path, format, html, to });
+  } catch (error) {
+    console.error('Error emailing note:', error);
+    throw error;
+  }
+}
@@ -0,0 +1,18 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * A note's markdown with `![[Note]]`, `![[Note#Section]]` and `![[Note#^block]]`
+ * embeds inlined, recursively, so an export stands on its own. Embeds that
+ * can't be resolved, or that would loop, become a short italic notice
+ * @param {string} path - Absolute note path
+ * @param {string} [content] - Unsaved editor text to compose instead of the file
+ * @returns {Promise<string>}
+ */
+export async function composeNote(path, content = null) {
+  try {
+    return await invoke('compose_note', { path, content });
+  } catch (error) {
+    console.error('Error composing note:', error);
+    throw error;
+  }
+}
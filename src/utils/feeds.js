@@ -0,0 +1,94 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} Feed
+ * @property {string} id - Empty for new feeds; the backend assigns one
+ * @property {string} name
+ * @property {string} url - RSS or Atom address
+ * @property {boolean} enabled
+ * @property {'note' | 'daily'} target - A note of its own, or today's daily note
+ * @property {string | null} note - For `note`, a workspace-relative path; defaults to `Feeds/<name>.md`
+ * @property {number} interval_minutes - How often to check, at least 15
+ */
+
+/**
+ * @typedef {Object} FeedStatus
+ * @property {string} id
+ * @property {string | null} last_checked - RFC 3339 time
+ * @property {string | null} last_error
+ * @property {number} last_added - Items appended by the last check
+ * @property {number} seen - Items already read
+ */
+
+/**
+ * Feed subscriptions stored in the workspace's `.marky/feeds.json`
+ * @param {string} folderPath - Workspace root
+ * @returns {Promise<Feed[]>}
+ */
+export async function getFeeds(folderPath) {
+  try {
+    return await invoke('get_feeds', { folderPath });
+  } catch (error) {
+    console.error('Error loading feeds:', error);
+    throw error;
+  }
+}
+
+/**
+ * Replace the workspace's feed subscriptions
+ * @param {string} folderPath - Workspace root
+ * @param {Feed[]} feeds
+ * @returns {Promise<Feed[]>} The feeds as saved, with ids assigned
+ */
+export async function setFeeds(folderPath, feeds) {
+  try {
+    return await invoke('set_feeds', { folderPath, feeds });
+  } catch (error) {
+    console.error('Error saving feeds:', error);
+    throw error;
+  }
+}
+
+/**
+ * When each feed was last checked and how it went
+ * @param {string} folderPath - Workspace root
+ * @returns {Promise<FeedStatus[]>}
+ */
+export async function getFeedStatus(folderPath) {
+  try {
+    return await invoke('get_feed_status', { folderPath });
+  } catch (error) {
+    console.error('Error loading feed status:', error);
+    return [];
+  }
+}
+
+/**
+ * Check one feed now, or every feed that is due
+ * @param {string} folderPath - Workspace root
+ * @param {string | null} feedId
+ * @returns {Promise<FeedStatus[]>}
+ */
+export async function refreshFeeds(folderPath, feedId = null) {
+  try {
+    return await invoke('refresh_feeds', { folderPath, feedId });
+  } catch (error) {
+    console.error('Error checking feeds:', error);
+    throw error;
+  }
+}
+
+/**
+ * Mark everything a feed currently lists as read, without adding it to any note
+ * @param {string} folderPath - Workspace root
+ * @param {string} feedId
+ * @returns {Promise<FeedStatus[]>}
+ */
+export async function markFeedRead(folderPath, feedId) {
+  try {
+    return await invoke('mark_feed_read', { folderPath, feedId });
+  } catch (error) {
+    console.error('Error marking feed read:', error);
+    throw error;
+  }
+}
@@ -28,6 +28,16 @@ const buildFriendlyFsError = (error, action, targetPath) => {
     message = `“${label}” no longer exists on disk.`;
   } else if (/already exists|exists already|os error 17/.test(normalized)) {
     message = `A file or folder named “${label}” already exists.`;
+  } else if (/wrong vault passphrase/.test(normalized)) {
+    message = 'That passphrase doesn’t unlock this vault.';
+  } else if (/vault is locked/.test(normalized)) {
+    message = 'This vault is locked. Unlock it to continue.';
+  } else if (/wrong passphrase/.test(normalized)) {
+    message = 'That passphrase doesn’t unlock this note.';
+  } else if (/note is encrypted/.test(normalized)) {
+    message = `“${label}” is encrypted. Unlock it to make changes.`;
+  } else if (/note is locked/.test(normalized)) {
+    message = `“${label}” is locked. Unlock it to make changes.`;
   } else if (/invalid name|invalid filename|invalid source name/.test(normalized)) {
     message = `“${label}” isn’t a valid name.`;
   } else if (/reserved system name|reserved device name/.test(normalized)) {
@@ -49,6 +59,11 @@ const buildFriendlyFsError = (error, action, targetPath) => {
   const friendlyError = new Error(message);
   friendlyError.cause = error;
   friendlyError.rawMessage = rawMessage;
+  if (error?.code === 'insufficient_space') {
+    friendlyError.code = error.code;
+    friendlyError.required = error.required;
+    friendlyError.available = error.available;
+  }
   return friendlyError;
 };
 
@@ -56,6 +71,21 @@ const wrapFsError = (error, action, targetPath) => {
   throw buildFriendlyFsError(error, action, targetPath);
 };
 
+/**
+ * Fail before writing an export when its destination volume is too full
+ * @param {string} path - File about to be written
+ * @param {number} requiredBytes - Size of what will be written
+ * @returns {Promise<void>} Rejects with `code: 'insufficient_space'`, `required`, and `available`
+ */
+export async function ensureDiskSpace(path, requiredBytes) {
+  try {
+    await invoke('check_disk_space', { path, required: Math.ceil(requiredBytes) });
+  } catch (error) {
+    console.error('Not enough disk space:', error);
+    wrapFsError(error, 'write this file', path);
+  }
+}
+
 /**
  * Open a markdown file using native file picker
  * @returns {Promise<{content: string, path: string} | null>}
@@ -140,10 +170,13 @@ export async function openFolder() {
     // selected is a string path in Tauri v2
     const folderPath = typeof selected === 'string' ? selected : selected.path;
 
-    // Call Rust backend to scan folder
-    const files = await invoke('scan_folder_for_markdown', {
-      folderPath: folderPath
-    });
+    // A locked vault can't be scanned yet; the store asks for its passphrase first
+    const vaultStatus = await getVaultStatus(folderPath);
+    const files = vaultStatus.is_vault && !vaultStatus.unlocked
+      ? []
+      : await invoke('scan_folder_for_markdown', {
+        folderPath: folderPath
+      });
 
     return {
       folderPath: folderPath,
@@ -156,14 +189,44 @@ export async function openFolder() {
   }
 }
 
+/**
+ * Ask where to put a demo workspace, create it there, and scan it
+ * @returns {Promise<{folderPath: string, folderName: string, files: Array} | null>} Null when cancelled
+ */
+export async function createSampleWorkspace() {
+  try {
+    const selected = await open({
+      multiple: false,
+      directory: true,
+      title: 'Choose where to create the sample workspace'
+    });
+
+    if (!selected) return null;
+
+    const dest = typeof selected === 'string' ? selected : selected.path;
+    const folderPath = await invoke('create_sample_workspace', { dest });
+    const files = await invoke('scan_folder_for_markdown', { folderPath });
+
+    return {
+      folderPath,
+      folderName: folderPath.split(/[\\/]/).pop() || 'Folder',
+      files
+    };
+  } catch (error) {
+    console.error('Error creating sample workspace:', error);
+    wrapFsError(error, 'create the sample workspace', 'folder');
+  }
+}
+
 /**
  * Scan a folder path for markdown files without prompting the user
  * @param {string} folderPath
+ * @param {boolean} [includeArchived] - Also list notes in the archive folder
  * @returns {Promise<Array>}
  */
-export async function scanFolder(folderPath) {
+export async function scanFolder(folderPath, includeArchived = false) {
   try {
-    const files = await invoke('scan_folder_for_markdown', { folderPath });
+    const files = await invoke('scan_folder_for_markdown', { folderPath, includeArchived });
     return files;
   } catch (error) {
     console.error('Error scanning folder:', error);
@@ -178,7 +241,8 @@ export async function scanFolder(folderPath) {
  */
 export async function readMarkdownFile(filePath) {
   try {
-    const content = await readTextFile(filePath);
+    // Read through the backend so notes inside an unlocked vault come back decrypted
+    const content = await invoke('read_note', { filePath });
     return content;
   } catch (error) {
     console.error('Error reading file:', error);
@@ -245,15 +309,43 @@ export async function renameEntryOnDisk(sourcePath, newName) {
   }
 }
 
+/**
+ * @typedef {Object} DeletePreview
+ * @property {string} path
+ * @property {boolean} is_dir
+ * @property {number} files - Files that would be removed, hidden ones included
+ * @property {number} folders
+ * @property {number} bytes
+ * @property {boolean} is_workspace_root - The path is, or holds, a workspace
+ * @property {boolean} requires_confirmation - Too big to delete without `token`
+ * @property {string | null} token - Good for one delete of this path, for a few minutes
+ */
+
+/**
+ * What deleting a file or folder would remove
+ * @param {string} targetPath
+ * @returns {Promise<DeletePreview>}
+ */
+export async function previewDelete(targetPath) {
+  try {
+    return await invoke('preview_delete', { path: targetPath });
+  } catch (error) {
+    console.error('Error previewing delete:', error);
+    wrapFsError(error, 'check this item', targetPath);
+  }
+}
+
 /**
  * Delete a file or folder on disk
  * @param {string} targetPath
+ * @param {string | null} [confirmToken] - From `previewDelete`, for workspaces and large folders
  * @returns {Promise<void>}
  */
-export async function deleteEntryOnDisk(targetPath) {
+export async function deleteEntryOnDisk(targetPath, confirmToken = null) {
   try {
     await invoke('delete_entry', {
-      targetPath
+      targetPath,
+      confirmToken
     });
   } catch (error) {
     console.error('Error deleting entry:', error);
@@ -261,6 +353,34 @@ export async function deleteEntryOnDisk(targetPath) {
   }
 }
 
+/**
+ * Move a note or folder into the workspace archive, mirroring its folder
+ * @param {string} sourcePath
+ * @returns {Promise<string>} New path inside the archive
+ */
+export async function archiveEntryOnDisk(sourcePath) {
+  try {
+    return await invoke('archive_note', { path: sourcePath });
+  } catch (error) {
+    console.error('Error archiving entry:', error);
+    wrapFsError(error, 'archive this item', sourcePath);
+  }
+}
+
+/**
+ * Move an archived note or folder back to where it was archived from
+ * @param {string} sourcePath
+ * @returns {Promise<string>} Restored path
+ */
+export async function unarchiveEntryOnDisk(sourcePath) {
+  try {
+    return await invoke('unarchive_note', { path: sourcePath });
+  } catch (error) {
+    console.error('Error unarchiving entry:', error);
+    wrapFsError(error, 'unarchive this item', sourcePath);
+  }
+}
+
 /**
  * Move a file or folder to a different parent directory
  * @param {string} sourcePath
@@ -288,7 +408,7 @@ export async function moveEntryOnDisk(sourcePath, destFolderPath) {
  */
 export async function writeMarkdownFileOnDisk(filePath, content) {
   try {
-    await writeTextFile(filePath, content ?? '');
+    await invoke('save_note', { filePath, content: content ?? '' });
   } catch (error) {
     console.error('Error writing markdown file:', error);
     wrapFsError(error, 'save this note', filePath);
@@ -296,13 +416,174 @@ export async function writeMarkdownFileOnDisk(filePath, content) {
 }
 
 /**
- * Start watching a folder for file system changes
+ * Keep the losing side of an unmergeable edit as `Note (conflicted copy YYYY-MM-DD).md`
+ * next to the original; windows showing the workspace get a `sync-conflict` event
+ * @param {string} filePath - The original note
+ * @param {string} content - The version that would otherwise be lost
+ * @returns {Promise<string>} Path of the copy
+ */
+export async function saveConflictCopy(filePath, content) {
+  try {
+    return await invoke('save_conflict_copy', { filePath, content: content ?? '' });
+  } catch (error) {
+    console.error('Error saving conflicted copy:', error);
+    wrapFsError(error, 'save a conflicted copy of this note', filePath);
+  }
+}
+
+/**
+ * Open a conflicted copy from a `sync-conflict` event in the calling window
+ * @param {string} path
+ * @returns {Promise<void>}
+ */
+export async function openConflictedCopy(path) {
+  await invoke('open_recent_note', { path });
+}
+
+/**
+ * Lock or unlock a note; locked notes are read-only on disk and refused by save_note
+ * @param {string} filePath
+ * @param {boolean} locked
+ * @returns {Promise<boolean>} The new lock state
+ */
+export async function setNoteLocked(filePath, locked) {
+  try {
+    return await invoke('set_note_locked', { path: filePath, locked });
+  } catch (error) {
+    console.error('Error updating note lock:', error);
+    wrapFsError(error, locked ? 'lock this note' : 'unlock this note', filePath);
+  }
+}
+
+/**
+ * Encrypt a note in place with a passphrase
+ * @param {string} filePath
+ * @param {string} passphrase
+ * @returns {Promise<string>} The armored content now on disk
+ */
+export async function encryptNote(filePath, passphrase) {
+  try {
+    return await invoke('encrypt_note', { path: filePath, passphrase });
+  } catch (error) {
+    console.error('Error encrypting note:', error);
+    wrapFsError(error, 'encrypt this note', filePath);
+  }
+}
+
+/**
+ * Decrypt an encrypted note into memory without touching the file
+ * @param {string} filePath
+ * @param {string} passphrase
+ * @returns {Promise<string>} Plaintext content
+ */
+export async function decryptNote(filePath, passphrase) {
+  try {
+    return await invoke('decrypt_note', { path: filePath, passphrase });
+  } catch (error) {
+    console.error('Error decrypting note:', error);
+    wrapFsError(error, 'unlock this note', filePath);
+  }
+}
+
+/**
+ * Save edits to an unlocked encrypted note, re-encrypting with the same passphrase
+ * @param {string} filePath
+ * @param {string} content - Plaintext
+ * @param {string} passphrase
+ * @returns {Promise<string>} The armored content now on disk
+ */
+export async function saveEncryptedNote(filePath, content, passphrase) {
+  try {
+    return await invoke('save_encrypted_note', { path: filePath, content, passphrase });
+  } catch (error) {
+    console.error('Error saving encrypted note:', error);
+    wrapFsError(error, 'save this note', filePath);
+  }
+}
+
+/**
+ * Decrypt a note back to plain markdown on disk
+ * @param {string} filePath
+ * @param {string} passphrase
+ * @returns {Promise<string>} Plaintext content
+ */
+export async function removeNoteEncryption(filePath, passphrase) {
+  try {
+    return await invoke('remove_note_encryption', { path: filePath, passphrase });
+  } catch (error) {
+    console.error('Error removing note encryption:', error);
+    wrapFsError(error, 'remove encryption from this note', filePath);
+  }
+}
+
+/**
+ * Turn a workspace into an encrypted vault, encrypting every file and name in place
+ * @param {string} folderPath
+ * @param {string} passphrase
+ * @returns {Promise<{is_vault: boolean, unlocked: boolean, auto_lock_minutes: number}>}
+ */
+export async function createVault(folderPath, passphrase) {
+  try {
+    return await invoke('create_vault', { folderPath, passphrase });
+  } catch (error) {
+    console.error('Error creating vault:', error);
+    wrapFsError(error, 'encrypt this workspace', folderPath);
+  }
+}
+
+/**
+ * Unlock a vault so its notes can be read and written until it locks again
+ * @param {string} folderPath
+ * @param {string} passphrase
+ * @returns {Promise<{is_vault: boolean, unlocked: boolean, auto_lock_minutes: number}>}
+ */
+export async function unlockVault(folderPath, passphrase) {
+  try {
+    return await invoke('unlock_vault', { folderPath, passphrase });
+  } catch (error) {
+    console.error('Error unlocking vault:', error);
+    wrapFsError(error, 'unlock this vault', folderPath);
+  }
+}
+
+/**
+ * Forget a vault's key right away
  * @param {string} folderPath
  * @returns {Promise<void>}
  */
+export async function lockVault(folderPath) {
+  try {
+    await invoke('lock_vault', { folderPath });
+  } catch (error) {
+    console.error('Error locking vault:', error);
+    throw error;
+  }
+}
+
+/**
+ * Whether a folder is a vault and whether it's currently unlocked
+ * @param {string} folderPath
+ * @returns {Promise<{is_vault: boolean, unlocked: boolean, auto_lock_minutes: number}>}
+ */
+export async function getVaultStatus(folderPath) {
+  try {
+    return await invoke('get_vault_status', { folderPath });
+  } catch (error) {
+    console.error('Error reading vault status:', error);
+    return { is_vault: false, unlocked: false, auto_lock_minutes: 0 };
+  }
+}
+
+/**
+ * Start watching a folder for file system changes
+ * @param {string} folderPath
+ * @returns {Promise<boolean>} Whether changes are reported live; false when file
+ *   watching is turned off (the default on mobile) and the folder should be
+ *   rescanned when the app comes back to the foreground
+ */
 export async function watchFolder(folderPath) {
   try {
-    await invoke('watch_folder', { folderPath });
+    return await invoke('watch_folder', { folderPath });
   } catch (error) {
     console.error('Error starting folder watch:', error);
     wrapFsError(error, 'watch this folder', folderPath);
@@ -311,17 +592,144 @@ export async function watchFolder(folderPath) {
 
 /**
  * Stop watching for file system changes
+ * @param {string} [folderPath] - Folder to stop watching; defaults to the active one
  * @returns {Promise<void>}
  */
-export async function stopWatching() {
+export async function stopWatching(folderPath = null) {
   try {
-    await invoke('stop_watching');
+    await invoke('stop_watching', { folderPath });
   } catch (error) {
     console.error('Error stopping folder watch:', error);
     wrapFsError(error, 'stop watching this folder', 'folder');
   }
 }
 
+/**
+ * @typedef {Object} WatcherStatus
+ * @property {string | null} workspace - The folder this window is watching
+ * @property {boolean} live - Changes are reported as they happen
+ * @property {boolean} healthy - Live, with no error and no rescan in progress
+ * @property {number} overflows - Times the OS dropped events and a rescan ran
+ * @property {number | null} last_overflow - In ms since the epoch
+ * @property {string | null} last_error
+ * @property {boolean} rescanning
+ * @property {number | null} last_rescan - In ms since the epoch
+ */
+
+/**
+ * Whether this window's workspace is being watched and keeping up
+ * @returns {Promise<WatcherStatus>}
+ */
+export async function getWatcherStatus() {
+  try {
+    return await invoke('get_watcher_status');
+  } catch (error) {
+    console.error('Error loading watcher status:', error);
+    throw error;
+  }
+}
+
+/**
+ * Tell the backend the webview is listening; on launch it answers with an
+ * `open-folder` event for the last workspace when reopening is enabled
+ * @param {string|null} legacyFolderPath - Root remembered in local storage, used before the backend has history
+ * @returns {Promise<Object|null>} The reopened workspace entry, if any
+ */
+export async function notifyWebviewReady(legacyFolderPath = null) {
+  try {
+    return await invoke('webview_ready', { legacyFolderPath });
+  } catch (error) {
+    console.error('Error restoring last workspace:', error);
+    return null;
+  }
+}
+
+/**
+ * Persist the open tabs, cursors, and sidebar state for a workspace
+ * @param {string} folderPath - Workspace root
+ * @param {Object} state - { open_notes, active_note, cursors, expanded_folders }
+ * @returns {Promise<void>}
+ */
+export async function saveSession(folderPath, state) {
+  try {
+    await invoke('save_session', { folderPath, state });
+  } catch (error) {
+    console.error('Error saving session:', error);
+  }
+}
+
+/**
+ * Load the last saved session for a workspace
+ * @param {string} folderPath - Workspace root
+ * @returns {Promise<Object|null>} Session with notes that still exist, or null
+ */
+export async function loadSession(folderPath) {
+  try {
+    return await invoke('load_session', { folderPath });
+  } catch (error) {
+    console.error('Error loading session:', error);
+    return null;
+  }
+}
+
+/**
+ * Open a note in its own window, or focus the window already showing it
+ * @param {string} filePath - Absolute path to the note
+ * @returns {Promise<string>} Label of the note window
+ */
+export async function openNoteInNewWindow(filePath) {
+  try {
+    return await invoke('open_note_in_new_window', { filePath });
+  } catch (error) {
+    console.error('Error opening note window:', error);
+    wrapFsError(error, 'open this note in a new window', filePath);
+  }
+}
+
+/**
+ * Open a workspace in a separate window with its own tabs and watcher
+ * @param {string} folderPath - Workspace root
+ * @returns {Promise<string>} Label of the workspace window
+ */
+export async function openWorkspaceWindow(folderPath) {
+  try {
+    return await invoke('open_workspace_window', { folderPath });
+  } catch (error) {
+    console.error('Error opening workspace window:', error);
+    wrapFsError(error, 'open this folder in a new window', folderPath);
+  }
+}
+
+/**
+ * Pin or unpin a note or folder; toggles when `pinned` is omitted
+ * @param {string} path - Absolute path inside an open workspace
+ * @param {boolean} [pinned]
+ * @param {string} [folderPath] - Workspace root the path belongs to
+ * @returns {Promise<Array>} The workspace's pins after the change
+ */
+export async function pinEntry(path, pinned, folderPath = null) {
+  try {
+    return await invoke('pin_entry', { path, pinned, folderPath });
+  } catch (error) {
+    console.error('Error updating pin:', error);
+    wrapFsError(error, 'pin this item', path);
+  }
+}
+
+/**
+ * List a workspace's pins, dropping any whose files no longer exist
+ * @param {string} folderPath - Workspace root
+ * @returns {Promise<Array>} [{ path, name, is_dir, pinned_at }]
+ */
+export async function listPinned(folderPath) {
+  try {
+    return await invoke('list_pinned', { folderPath });
+  } catch (error) {
+    console.error('Error loading pins:', error);
+    return [];
+  }
+}
+
 /**
  * Copy files or folders from external sources to a target folder
  * @param {Array<string>} sourcePaths - Array of file/folder paths to copy
@@ -341,6 +749,22 @@ export async function copyEntriesToFolder(sourcePaths, destFolderPath) {
   }
 }
 
+/**
+ * Pick documents with the system document picker (Files on iOS, the Storage
+ * Access Framework on Android, the file dialog on desktop) and copy them into
+ * a workspace folder as notes
+ * @param {string} folderPath - Destination folder path
+ * @returns {Promise<Array<string>>} Paths of the imported notes; empty when the picker was cancelled
+ */
+export async function importDocuments(folderPath) {
+  try {
+    return await invoke('import_documents', { folderPath });
+  } catch (error) {
+    console.error('Error importing documents:', error);
+    wrapFsError(error, 'import these documents', folderPath);
+  }
+}
+
 /**
  * Check if running in Tauri environment
  * @returns {boolean}
@@ -0,0 +1,22 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} GitStatuses
+ * @property {boolean} is_repo - False outside a repository or without git installed
+ * @property {Object<string, 'modified' | 'added' | 'conflicted' | 'untracked' | 'ignored'>} files
+ *   Changed paths only; an ignored folder is listed once for everything in it
+ */
+
+/**
+ * Per-file git status for a workspace folder
+ * @param {string} folder
+ * @returns {Promise<GitStatuses>}
+ */
+export async function getGitStatuses(folder) {
+  try {
+    return await invoke('get_git_statuses', { folder });
+  } catch (error) {
+    console.error('Error reading git status:', error);
+    throw error;
+  }
+}
@@ -1,3 +1,4 @@
+import { invoke } from '@tauri-apps/api/core';
 import { save } from '@tauri-apps/plugin-dialog';
 import { writeFile, writeTextFile } from '@tauri-apps/plugin-fs';
 
@@ -190,3 +191,30 @@ export const saveGraphPng = async (svg, width, height, defaultName = 'marky-grap
     URL.revokeObjectURL(url);
   }
 };
+
+const GRAPH_DATA_FORMATS = {
+  dot: { extension: 'gv', name: 'Graphviz DOT' },
+  graphml: { extension: 'graphml', name: 'GraphML' },
+};
+
+/**
+ * Save the workspace's whole note graph as Graphviz DOT or GraphML, with
+ * folders as clusters and tags as node attributes
+ * @param {string} folderPath - Workspace root
+ * @param {'dot' | 'graphml'} format
+ * @returns {Promise<string | null>} The saved path, or null if cancelled
+ */
+export const saveGraphData = async (folderPath, format) => {
+  const { extension, name } = GRAPH_DATA_FORMATS[format];
+  const filePath = await requestSavePath(`marky-graph.${extension}`, [extension], name);
+  if (!filePath) return null;
+
+  try {
+    const data = await invoke('export_graph', { folderPath, format });
+    await writeTextFile(filePath, data);
+    return filePath;
+  } catch (error) {
+    console.error('Failed to export graph data:', error);
+    throw error;
+  }
+};
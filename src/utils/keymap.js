@@ -0,0 +1,103 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} Shortcut
+ * @property {string} action - Keymap action id, like `newNote`
+ * @property {string | null} accelerator - e.g. `CmdOrCtrl+Shift+N`; null when unassigned
+ * @property {string} default_accelerator
+ * @property {boolean} menu - Whether a native menu item shows it
+ * @property {boolean} customized
+ */
+
+// What the browser reports as `event.key` when Shift is held on a US layout
+const SHIFTED_KEYS = {
+  '`': '~', '1': '!', '2': '@', '3': '#', '4': '$', '5': '%', '6': '^', '7': '&',
+  '8': '*', '9': '(', '0': ')', '-': '_', '=': '+', '[': '{', ']': '}', '\\': '|',
+  ';': ':', "'": '"', ',': '<', '.': '>', '/': '?',
+};
+const UNSHIFTED_KEYS = Object.fromEntries(
+  Object.entries(SHIFTED_KEYS).map(([plain, shifted]) => [shifted, plain])
+);
+const NAMED_KEYS = { ' ': 'Space', ArrowUp: 'Up', ArrowDown: 'Down', ArrowLeft: 'Left', ArrowRight: 'Right' };
+const BROWSER_KEYS = Object.fromEntries(
+  Object.entries(NAMED_KEYS).map(([browser, named]) => [named, browser])
+);
+
+/**
+ * Turn a `{ key, modifiers }` keymap into an accelerator string
+ * @param {{ key: string, modifiers: string[] }} keymap
+ * @returns {string}
+ */
+export const toAccelerator = (keymap) => {
+  const parts = [];
+  if (keymap.modifiers.includes('mod')) parts.push('CmdOrCtrl');
+  if (keymap.modifiers.includes('shift')) parts.push('Shift');
+  if (keymap.modifiers.includes('alt')) parts.push('Alt');
+  const key = UNSHIFTED_KEYS[keymap.key] || NAMED_KEYS[keymap.key] || keymap.key;
+  parts.push(key.length === 1 ? key.toUpperCase() : key);
+  return parts.join('+');
+};
+
+/**
+ * Turn an accelerator string into the `{ key, modifiers }` shape key events
+ * are matched against; unassigned shortcuts get an empty key
+ * @param {string | null} accelerator
+ * @returns {{ key: string, modifiers: string[] }}
+ */
+export const fromAccelerator = (accelerator) => {
+  if (!accelerator) return { key: '', modifiers: [] };
+  const parts = accelerator.split('+');
+  const key = parts.pop();
+  const modifiers = [];
+  if (parts.includes('CmdOrCtrl')) modifiers.push('mod');
+  if (parts.includes('Shift')) modifiers.push('shift');
+  if (parts.includes('Alt')) modifiers.push('alt');
+
+  const shift = modifiers.includes('shift');
+  let eventKey = BROWSER_KEYS[key] || key;
+  if (key.length === 1) {
+    eventKey = shift ? SHIFTED_KEYS[key] || key.toUpperCase() : key.toLowerCase();
+  }
+  return { key: eventKey, modifiers };
+};
+
+/**
+ * Get every bindable action's shortcut
+ * @returns {Promise<Shortcut[]>}
+ */
+export async function getKeymap() {
+  try {
+    return await invoke('get_keymap');
+  } catch (error) {
+    console.error('Error loading keymap:', error);
+    throw error;
+  }
+}
+
+/**
+ * Bind an action; fails when another action or a menu item already uses the shortcut
+ * @param {string} action
+ * @param {string | null} accelerator - Empty unassigns it, null restores the default
+ * @returns {Promise<Shortcut[]>} The whole updated keymap
+ */
+export async function setShortcut(action, accelerator) {
+  try {
+    return await invoke('set_shortcut', { action, accelerator });
+  } catch (error) {
+    console.error('Error setting shortcut:', error);
+    throw error;
+  }
+}
+
+/**
+ * Restore every default shortcut
+ * @returns {Promise<Shortcut[]>}
+ */
+export async function resetKeymap() {
+  try {
+    return await invoke('reset_keymap');
+  } catch (error) {
+    console.error('Error resetting keymap:', error);
+    throw error;
+  }
+}
@@ -0,0 +1,56 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} LinkPreview
+ * @property {string} url - Where the page ended up after redirects
+ * @property {string | null} title
+ * @property {string | null} description
+ * @property {string | null} site_name
+ * @property {string | null} image - Data URL of the cached image
+ */
+
+// One request per URL for as long as the preview is open; hovering the same
+// link again, or rendering it as a card, reuses the answer
+const previews = new Map();
+
+/**
+ * Open Graph / Twitter card metadata for a web page, cached on disk by the
+ * backend. Rejects when previews are off, the host isn't allowlisted, or
+ * nothing is cached while offline.
+ * @param {string} url - http(s) URL
+ * @returns {Promise<LinkPreview>}
+ */
+export function fetchLinkPreview(url) {
+  if (!previews.has(url)) {
+    previews.set(
+      url,
+      invoke('fetch_link_preview', { url }).catch((error) => {
+        console.error('Error fetching link preview:', error);
+        throw error;
+      })
+    );
+  }
+  return previews.get(url);
+}
+
+/**
+ * Forget remembered answers, e.g. after the allowlist or offline setting
+ * changes.
+ */
+export function forgetLinkPreviews() {
+  previews.clear();
+}
+
+/**
+ * Delete every cached preview and image.
+ * @returns {Promise<void>}
+ */
+export async function clearLinkPreviews() {
+  forgetLinkPreviews();
+  try {
+    return await invoke('clear_link_previews');
+  } catch (error) {
+    console.error('Error clearing link previews:', error);
+    throw error;
+  }
+}
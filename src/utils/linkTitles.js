@@ -0,0 +1,16 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Fetch a web page's title. The backend does the request, so it isn't
+ * limited by CORS; it follows redirects and gives up after a few seconds.
+ * @param {string} url - http(s) URL
+ * @returns {Promise<string>}
+ */
+export async function fetchUrlTitle(url) {
+  try {
+    return await invoke('fetch_url_title', { url });
+  } catch (error) {
+    console.error('Error fetching page title:', error);
+    throw error;
+  }
+}
@@ -0,0 +1,55 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} LinkCandidate
+ * @property {'title'|'alias'|'heading'|'block'} kind
+ * @property {string} target - What goes between the brackets, e.g. `Note` or `Note#Heading`
+ * @property {string} label - The title, alias or heading that matched
+ * @property {string} path - The note's absolute path
+ */
+
+/**
+ * Ranked `[[` completions from the workspace index. `Note#` lists that note's
+ * headings and `Note#^` its block ids; `#` alone means the note at `path`
+ * @param {string} folderPath - Workspace root
+ * @param {string} prefix - Text typed after `[[`
+ * @param {string} [path] - The note being edited
+ * @param {number} [limit]
+ * @returns {Promise<LinkCandidate[]>}
+ */
+export async function getLinkCandidates(folderPath, prefix, path = null, limit = null) {
+  try {
+    return await invoke('get_link_candidates', { folderPath, prefix, path, limit });
+  } catch (error) {
+    console.error('Error loading link candidates:', error);
+    throw error;
+  }
+}
+
+/**
+ * @typedef {Object} LinkTarget
+ * @property {string} path - The linked note's absolute path
+ * @property {string|null} heading - What followed `#` in the link
+ * @property {{text: string, level: number, line: number, offset: number}|null} found -
+ * The matching heading, with its 1-based line and byte offset; null when the note has none
+ * @property {string|null} block - The id from `#^id`
+ * @property {{id: string, start_line: number, end_line: number, start: number, end: number}|null} block_range -
+ * The block carrying that id, as 1-based lines and byte offsets
+ */
+
+/**
+ * Resolve `Note`, `Note#Heading`, `#Heading`, `Note#^block-id` or
+ * `note.md#heading` to a note, matching the heading loosely by slug
+ * @param {string} folderPath - Workspace root
+ * @param {string} target - The link target, as written
+ * @param {string} [from] - The note holding the link, for `#Heading` and relative paths
+ * @returns {Promise<LinkTarget|null>}
+ */
+export async function resolveNoteLink(folderPath, target, from = null) {
+  try {
+    return await invoke('resolve_note_link', { folderPath, target, from });
+  } catch (error) {
+    console.error('Error resolving link:', error);
+    throw error;
+  }
+}
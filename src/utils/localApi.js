@@ -0,0 +1,96 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Whether the local HTTP API is listening, and where
+ * @returns {Promise<{running: boolean, port: number, url: string, error: string | null}>}
+ */
+export async function getApiStatus() {
+  return invoke('get_api_status');
+}
+
+/**
+ * Bearer token for the local API, created on first use
+ * @returns {Promise<string>}
+ */
+export async function getApiToken() {
+  try {
+    return await invoke('get_api_token');
+  } catch (error) {
+    console.error('Error reading API token:', error);
+    throw error;
+  }
+}
+
+/**
+ * Replace the API token; clients using the old one are rejected from then on
+ * @returns {Promise<string>} The new token
+ */
+export async function regenerateApiToken() {
+  try {
+    return await invoke('regenerate_api_token');
+  } catch (error) {
+    console.error('Error regenerating API token:', error);
+    throw error;
+  }
+}
+
+/**
+ * Token the browser clipper extension is paired with; it only allows `POST /clip`
+ * @returns {Promise<string>}
+ */
+export async function getClipperToken() {
+  try {
+    return await invoke('get_clipper_token');
+  } catch (error) {
+    console.error('Error reading clipper token:', error);
+    throw error;
+  }
+}
+
+/**
+ * Replace the clipper token, unpairing every browser that had the old one
+ * @returns {Promise<string>} The new token
+ */
+export async function regenerateClipperToken() {
+  try {
+    return await invoke('regenerate_clipper_token');
+  } catch (error) {
+    console.error('Error regenerating clipper token:', error);
+    throw error;
+  }
+}
+
+/**
+ * Open a note the clipper just saved, in whichever window shows its workspace
+ * @param {string} path - Absolute note path from the `clip-received` event
+ * @returns {Promise<void>}
+ */
+export async function openClippedNote(path) {
+  await invoke('open_recent_note', { path });
+}
+
+/**
+ * Subscription address calendar apps use to show dated tasks, token included
+ * @returns {Promise<string>}
+ */
+export async function getTasksFeedUrl() {
+  try {
+    return await invoke('get_tasks_feed_url');
+  } catch (error) {
+    console.error('Error reading tasks calendar address:', error);
+    throw error;
+  }
+}
+
+/**
+ * Replace the tasks calendar token; calendars subscribed to the old address stop updating
+ * @returns {Promise<string>} The new subscription address
+ */
+export async function regenerateTasksFeedToken() {
+  try {
+    return await invoke('regenerate_tasks_feed_token');
+  } catch (error) {
+    console.error('Error regenerating tasks calendar token:', error);
+    throw error;
+  }
+}
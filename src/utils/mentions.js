@@ -0,0 +1,43 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} UnlinkedMention
+ * @property {string} path - The note the mention is in
+ * @property {string} title
+ * @property {string} text - The mention as written
+ * @property {number} start - Byte offset in that note
+ * @property {number} end
+ * @property {number} line - 1-based
+ * @property {string} before - Context on the same line
+ * @property {string} after
+ */
+
+/**
+ * Find plain-text mentions of a note's title and aliases in other notes
+ * @param {string} path - Absolute path of the mentioned note
+ * @returns {Promise<UnlinkedMention[]>}
+ */
+export async function getUnlinkedMentions(path) {
+  try {
+    return await invoke('get_unlinked_mentions', { path });
+  } catch (error) {
+    console.error('Error finding unlinked mentions:', error);
+    throw error;
+  }
+}
+
+/**
+ * Turn mentions in one note into links to `target`
+ * @param {string} path - The note the mentions are in
+ * @param {string} target - Absolute path of the mentioned note
+ * @param {Array<{start: number, end: number, text: string}>} mentions
+ * @returns {Promise<string>} The note's new content
+ */
+export async function linkUnlinkedMentions(path, target, mentions) {
+  try {
+    return await invoke('link_unlinked_mentions', { path, target, mentions });
+  } catch (error) {
+    console.error('Error linking mentions:', error);
+    throw error;
+  }
+}
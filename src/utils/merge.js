@@ -0,0 +1,49 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {{kind: 'resolved', text: string}
+ *   | {kind: 'conflict', base: string, ours: string, theirs: string}} MergeChunk
+ */
+
+/**
+ * @typedef {Object} Merge
+ * @property {MergeChunk[]} chunks - The merged text in order, conflicts included
+ * @property {string} merged - Whole text with git-style conflict markers
+ * @property {number} conflicts
+ */
+
+/**
+ * Three-way merge of two edits made from a common version, by lines.
+ * @param {string} base - The version both edits started from
+ * @param {string} ours
+ * @param {string} theirs
+ * @returns {Promise<Merge>}
+ */
+export async function mergeTexts(base, ours, theirs) {
+  try {
+    return await invoke('merge_texts', { base, ours, theirs });
+  } catch (error) {
+    console.error('Error merging texts:', error);
+    throw error;
+  }
+}
+
+/**
+ * The merged text once every conflict has a pick.
+ * @param {MergeChunk[]} chunks
+ * @param {Array<'ours' | 'theirs' | 'both'>} picks - One per conflict, in order
+ * @returns {string}
+ */
+export function resolveMerge(chunks, picks) {
+  let conflict = 0;
+  return chunks
+    .map((chunk) => {
+      if (chunk.kind === 'resolved') return chunk.text;
+      const pick = picks[conflict++];
+      if (pick === 'ours') return chunk.ours;
+      if (pick === 'theirs') return chunk.theirs;
+      const ours = chunk.ours && !chunk.ours.endsWith('\n') ? `${chunk.ours}\n` : chunk.ours;
+      return ours + chunk.theirs;
+    })
+    .join('');
+}
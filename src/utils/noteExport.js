@@ -1,7 +1,9 @@
+import { invoke } from '@tauri-apps/api/core';
 import { save } from '@tauri-apps/plugin-dialog';
 import { writeFile } from '@tauri-apps/plugin-fs';
 import { PDFDocument, StandardFonts, rgb } from 'pdf-lib';
 import { marked } from 'marked';
+import { ensureDiskSpace } from './fileSystem';
 
 const PAGE_SIZE = { width: 595.28, height: 841.89 };
 const PAGE_MARGIN = 50;
@@ -189,7 +191,7 @@ export const buildStandaloneHtml = (noteName, markdownContent, markedParser = ma
 </html>`;
 };
 
-export const exportMarkdownToPdf = async (noteName, markdownContent) => {
+export const renderMarkdownToPdf = async (markdownContent) => {
   const pdfDoc = await PDFDocument.create();
   const regularFont = await pdfDoc.embedFont(StandardFonts.Helvetica);
   const boldFont = await pdfDoc.embedFont(StandardFonts.HelveticaBold);
@@ -420,6 +422,10 @@ export const exportMarkdownToPdf = async (noteName, markdownContent) => {
     renderTokens(tokens);
   }
 
+  return pdfDoc.save();
+};
+
+export const exportMarkdownToPdf = async (noteName, markdownContent) => {
   const savePath = await save({
     defaultPath: `${sanitizeFileName(noteName)}.pdf`,
     filters: [{
@@ -431,7 +437,75 @@ export const exportMarkdownToPdf = async (noteName, markdownContent) => {
   if (!savePath) return null;
 
   const filePath = typeof savePath === 'string' ? savePath : savePath.path;
-  const pdfBytes = await pdfDoc.save();
+  const pdfBytes = await renderMarkdownToPdf(markdownContent);
+  await ensureDiskSpace(filePath, pdfBytes.byteLength);
   await writeFile(filePath, pdfBytes);
   return filePath;
 };
+
+/**
+ * @typedef {Object} ExportOptions
+ * @property {boolean} [inline_embeds=true] - Inline `![[embeds]]` into each note
+ * @property {boolean} [embed_images=true] - Local images as data URLs; otherwise
+ *   they're linked relative to the export. PDFs always embed them.
+ * @property {string} [destination] - Skips the save or folder dialog
+ */
+
+/**
+ * @typedef {Object} ExportResult
+ * @property {string} destination - The file written, or the folder holding a folder export
+ * @property {number} exported
+ * @property {string[]} skipped - Notes left out (encrypted ones), relative to the folder
+ */
+
+let exportFormats = null;
+
+/**
+ * The formats the backend can export to here; PDF needs a webview that can
+ * print to a file, so it's missing on some platforms
+ * @returns {Promise<Array<'html' | 'pdf'>>}
+ */
+export async function getExportFormats() {
+  try {
+    exportFormats ??= await invoke('export_formats');
+    return exportFormats;
+  } catch (error) {
+    console.error('Error reading export formats:', error);
+    throw error;
+  }
+}
+
+/**
+ * Render a note to a standalone HTML page or a PDF in the backend, asking
+ * where to save it unless `options.destination` is set
+ * @param {string} path - Note file path
+ * @param {'html' | 'pdf'} format
+ * @param {string | null} [content] - Unsaved editor text, if any
+ * @param {ExportOptions} [options]
+ * @returns {Promise<ExportResult | null>} null when the dialog was cancelled
+ */
+export async function exportNote(path, format, content = null, options = {}) {
+  try {
+    return await invoke('export_note', { path, format, content, options });
+  } catch (error) {
+    console.error('Error exporting note:', error);
+    throw error;
+  }
+}
+
+/**
+ * Export every note in a folder, keeping its subfolders and pointing links
+ * between the notes at their exported files
+ * @param {string} folderPath
+ * @param {'html' | 'pdf'} format
+ * @param {ExportOptions} [options]
+ * @returns {Promise<ExportResult | null>} null when the dialog was cancelled
+ */
+export async function exportFolder(folderPath, format, options = {}) {
+  try {
+    return await invoke('export_folder', { folderPath, format, options });
+  } catch (error) {
+    console.error('Error exporting folder:', error);
+    throw error;
+  }
+}
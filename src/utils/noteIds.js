@@ -0,0 +1,31 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Get a note's stable id (frontmatter `id:`), assigning one if it has none.
+ * Ids survive renames and moves; resolve them with resolveNoteId
+ * @param {string} path - Absolute note path
+ * @returns {Promise<string>}
+ */
+export async function ensureNoteId(path) {
+  try {
+    return await invoke('ensure_note_id', { path });
+  } catch (error) {
+    console.error('Error assigning note id:', error);
+    throw error;
+  }
+}
+
+/**
+ * Find where the note with `id` lives now
+ * @param {string} folderPath - Workspace root
+ * @param {string} id
+ * @returns {Promise<string|null>} Absolute path, or null when no note has the id
+ */
+export async function resolveNoteId(folderPath, id) {
+  try {
+    return await invoke('resolve_note_id', { folderPath, id });
+  } catch (error) {
+    console.error('Error resolving note id:', error);
+    throw error;
+  }
+}
@@ -0,0 +1,37 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} LockHolder
+ * @property {string} app - `Marky`, or the other editor whose swap file was found
+ * @property {string} host
+ * @property {string} user
+ * @property {string | null} since - RFC 3339 time
+ */
+
+/**
+ * Take the edit lock on a note for this window, unless another Marky or editor has it open
+ * @param {string} filePath
+ * @param {boolean} [force] - Take it anyway after the user was warned
+ * @returns {Promise<{acquired: boolean, holder: LockHolder | null}>}
+ */
+export async function acquireNoteLock(filePath, force = false) {
+  try {
+    return await invoke('acquire_note_lock', { path: filePath, force });
+  } catch (error) {
+    console.error('Error acquiring note lock:', error);
+    throw error;
+  }
+}
+
+/**
+ * Give up this window's edit lock on a note
+ * @param {string} filePath
+ * @returns {Promise<void>}
+ */
+export async function releaseNoteLock(filePath) {
+  try {
+    await invoke('release_note_lock', { path: filePath });
+  } catch (error) {
+    console.error('Error releasing note lock:', error);
+  }
+}
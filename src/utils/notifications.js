@@ -0,0 +1,32 @@
+import { invoke } from '@tauri-apps/api/core';
+
+// Native notifications go through the backend, which drops them while a Marky
+// window has focus (toasts cover that) or when the category is muted.
+
+/**
+ * Announce a finished long-running operation, such as a backup or export
+ * @param {string} title
+ * @param {string} body
+ * @returns {Promise<void>}
+ */
+export async function notifyOperationFinished(title, body) {
+  try {
+    await invoke('send_notification', { category: 'operations', title, body });
+  } catch (error) {
+    console.error('Error sending notification:', error);
+  }
+}
+
+/**
+ * Report how many notes in this window have unsaved edits. The dock badge
+ * (taskbar overlay on Windows) shows tasks due today, or a dot for unsaved notes.
+ * @param {number} unsaved
+ * @returns {Promise<void>}
+ */
+export async function setBadge(unsaved) {
+  try {
+    await invoke('set_badge', { unsaved });
+  } catch (error) {
+    console.error('Error updating app badge:', error);
+  }
+}
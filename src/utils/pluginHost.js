@@ -0,0 +1,300 @@
+import { listPlugins, loadPlugin, pluginRequest } from './plugins';
+import usePluginStore from '../store/pluginStore';
+import useUIStore from '../store/uiStore';
+
+// Each plugin runs in its own Web Worker: no DOM, no Tauri bridge, and (without
+// the `network` permission) no network APIs. Everything else goes through the
+// `marky` object below, which messages the host. The worker is started from a
+// sandboxed frame whose CSP it inherits, so the missing APIs are backed by a
+// policy the plugin can't undo.
+const WORKER_PRELUDE = `
+(() => {
+  const pending = new Map();
+  const commands = new Map();
+  const listeners = new Map();
+  let nextRequestId = 0;
+
+  const request = (method, params = {}) =>
+    new Promise((resolve, reject) => {
+      const id = ++nextRequestId;
+      pending.set(id, { resolve, reject });
+      self.postMessage({ type: "request", id, method, params });
+    });
+
+  const reportError = (error) =>
+    self.postMessage({ type: "error", message: String(error && error.message ? error.message : error) });
+
+  self.onmessage = async (event) => {
+    const message = event.data || {};
+    if (message.type === "response") {
+      const callbacks = pending.get(message.id);
+      if (!callbacks) return;
+      pending.delete(message.id);
+      if (message.error !== undefined) {
+        callbacks.reject(new Error(message.error));
+      } else {
+        callbacks.resolve(message.result);
+      }
+    } else if (message.type === "command") {
+      const handler = commands.get(message.command);
+      if (!handler) return;
+      try {
+        await handler();
+      } catch (error) {
+        reportError(error);
+      }
+    } else if (message.type === "event") {
+      for (const handler of listeners.get(message.name) || []) {
+        try {
+          await handler(message.payload);
+        } catch (error) {
+          reportError(error);
+        }
+      }
+    }
+  };
+
+  // Nested workers would start without this prelude
+  const blocked = ["Worker", "SharedWorker", "importScripts"];
+  if (!__MARKY_PLUGIN__.permissions.includes("network")) {
+    blocked.push("fetch", "XMLHttpRequest", "WebSocket", "EventSource", "WebTransport", "caches");
+  }
+  for (const key of blocked) {
+    // Prototypes too, or WorkerGlobalScope.prototype.fetch would still be there
+    for (let target = self; target; target = Object.getPrototypeOf(target)) {
+      try {
+        delete target[key];
+      } catch {
+        // Not every runtime exposes all of these
+      }
+    }
+    try {
+      Object.defineProperty(self, key, { value: undefined, writable: false, configurable: false });
+    } catch {
+      // Already gone for good
+    }
+  }
+
+  self.marky = Object.freeze({
+    plugin: Object.freeze({ ...__MARKY_PLUGIN__ }),
+    commands: Object.freeze({
+      register(id, name, handler) {
+        commands.set(String(id), handler);
+        return request("commands.register", { id: String(id), name: String(name) });
+      },
+    }),
+    events: Object.freeze({
+      // "file-change" and "note-saved"; needs the "events" permission
+      on(name, handler) {
+        if (!listeners.has(name)) listeners.set(name, []);
+        listeners.get(name).push(handler);
+      },
+    }),
+    notes: Object.freeze({
+      list: () => request("notes.list"),
+      read: (path) => request("notes.read", { path }),
+      write: (path, content) => request("notes.write", { path, content }),
+      create: (name, content = "", folder) => request("notes.create", { name, content, folder }),
+    }),
+    ui: Object.freeze({
+      notify: (message, type = "info") => request("ui.notify", { message, type }),
+    }),
+  });
+})();
+`;
+
+// Runs in the sandboxed frame: starts the plugin's worker from the script it's
+// sent and relays messages between it and the host's port
+const FRAME_BOOTSTRAP = `
+addEventListener("message", (event) => {
+  const port = event.ports[0];
+  if (!port) return;
+  const url = URL.createObjectURL(new Blob([event.data.script], { type: "text/javascript" }));
+  const worker = new Worker(url);
+  URL.revokeObjectURL(url);
+  worker.onmessage = (message) => port.postMessage(message.data);
+  worker.onerror = (error) => {
+    error.preventDefault();
+    port.postMessage({ type: "crash", message: error.message });
+  };
+  port.onmessage = (message) => worker.postMessage(message.data);
+}, { once: true });
+`;
+
+// No remote scripts or eval anywhere, and no connections without `network`
+const frameCsp = (network) =>
+  [
+    "default-src 'none'",
+    "script-src 'unsafe-inline' blob:",
+    'worker-src blob:',
+    `connect-src ${network ? 'https: http: wss: ws:' : "'none'"}`,
+  ].join('; ');
+
+/**
+ * Start `script` in a worker inside a hidden, opaque-origin frame
+ * @param {string} script
+ * @param {boolean} network - Whether the worker may open connections
+ * @returns {Promise<{port: MessagePort, terminate: () => void}>}
+ */
+const startSandboxed = (script, network) =>
+  new Promise((resolve) => {
+    const frame = document.createElement('iframe');
+    frame.setAttribute('sandbox', 'allow-scripts');
+    frame.style.display = 'none';
+    frame.srcdoc =
+      `<!doctype html><meta http-equiv="Content-Security-Policy" content="${frameCsp(network)}">` +
+      `<script>${FRAME_BOOTSTRAP}</script>`;
+    frame.onload = () => {
+      const channel = new MessageChannel();
+      frame.contentWindow.postMessage({ script }, '*', [channel.port2]);
+      resolve({
+        port: channel.port1,
+        terminate: () => {
+          channel.port1.close();
+          frame.remove();
+        },
+      });
+    };
+    document.body.appendChild(frame);
+  });
+
+const EVENT_NAMES = ['file-change', 'note-saved'];
+
+// Plugin id -> { worker, manifest }
+const running = new Map();
+
+const notify = (message, type = 'info', duration) =>
+  useUIStore.getState().addNotification(message, type, duration);
+
+const handleRequest = async (plugin, method, params) => {
+  switch (method) {
+    case 'commands.register':
+      usePluginStore.getState().registerCommand(plugin.id, params.id, params.name);
+      return null;
+    case 'ui.notify': {
+      const type = ['info', 'success', 'warning', 'error'].includes(params.type)
+        ? params.type
+        : 'info';
+      notify(`${plugin.name}: ${params.message}`, type);
+      return null;
+    }
+    default:
+      return pluginRequest(plugin.id, method, params);
+  }
+};
+
+/**
+ * Start an enabled plugin in a fresh worker, replacing one already running
+ * @param {{id: string, manifest: object}} info - From `listPlugins`
+ * @returns {Promise<void>}
+ */
+export async function startPlugin(info) {
+  stopPlugin(info.id);
+  const manifest = info.manifest;
+  const source = await loadPlugin(info.id);
+
+  const meta = {
+    id: manifest.id,
+    name: manifest.name,
+    version: manifest.version,
+    permissions: manifest.permissions || [],
+  };
+  const script = `const __MARKY_PLUGIN__ = ${JSON.stringify(meta)};\n${WORKER_PRELUDE}\n${source}`;
+  const worker = await startSandboxed(script, meta.permissions.includes('network'));
+
+  worker.port.onmessage = async (event) => {
+    const message = event.data || {};
+    if (message.type === 'crash') {
+      console.error(`Plugin ${meta.id} crashed:`, message.message);
+      notify(`Plugin ${meta.name} stopped: ${message.message}`, 'error', 6000);
+      stopPlugin(meta.id);
+      return;
+    }
+    if (message.type === 'error') {
+      console.error(`Plugin ${meta.id} failed:`, message.message);
+      notify(`${meta.name}: ${message.message}`, 'error');
+      return;
+    }
+    if (message.type !== 'request') return;
+
+    try {
+      const result = await handleRequest(meta, message.method, message.params || {});
+      worker.port.postMessage({ type: 'response', id: message.id, result });
+    } catch (error) {
+      worker.port.postMessage({
+        type: 'response',
+        id: message.id,
+        error: String(error?.message || error),
+      });
+    }
+  };
+
+  running.set(meta.id, { worker, manifest });
+}
+
+/**
+ * Terminate a plugin's worker and drop its commands
+ * @param {string} id
+ */
+export function stopPlugin(id) {
+  const plugin = running.get(id);
+  if (plugin) {
+    plugin.worker.terminate();
+    running.delete(id);
+  }
+  usePluginStore.getState().clearCommands(id);
+}
+
+/**
+ * Stop every running plugin, then start those currently enabled
+ * @returns {Promise<void>}
+ */
+export async function restartPlugins() {
+  for (const id of [...running.keys()]) {
+    stopPlugin(id);
+  }
+
+  const plugins = await listPlugins();
+  for (const plugin of plugins.filter((p) => p.enabled && p.manifest)) {
+    try {
+      await startPlugin(plugin);
+    } catch (error) {
+      console.error(`Failed to start plugin ${plugin.id}:`, error);
+      notify(`Failed to start plugin ${plugin.manifest.name}: ${error}`, 'error');
+    }
+  }
+}
+
+export function stopAllPlugins() {
+  for (const id of [...running.keys()]) {
+    stopPlugin(id);
+  }
+}
+
+/**
+ * Run a command a plugin registered, from the palette or the Plugins menu
+ * @param {string} pluginId
+ * @param {string} command
+ */
+export function runPluginCommand(pluginId, command) {
+  const plugin = running.get(pluginId);
+  if (!plugin) {
+    notify('That plugin is not running', 'warning');
+    return;
+  }
+  plugin.worker.port.postMessage({ type: 'command', command });
+}
+
+/**
+ * Forward a workspace event to plugins with the `events` permission
+ * @param {'file-change' | 'note-saved'} name
+ * @param {any} payload
+ */
+export function dispatchPluginEvent(name, payload) {
+  if (!EVENT_NAMES.includes(name)) return;
+  for (const { worker, manifest } of running.values()) {
+    if ((manifest.permissions || []).includes('events')) {
+      worker.port.postMessage({ type: 'event', name, payload });
+    }
+  }
+}
@@ -0,0 +1,58 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Plugins installed in the plugins folder, with their manifests
+ * @returns {Promise<Array<{id: string, enabled: boolean, path: string, manifest: object | null, error: string | null}>>}
+ */
+export async function listPlugins() {
+  try {
+    return await invoke('list_plugins');
+  } catch (error) {
+    console.error('Error listing plugins:', error);
+    return [];
+  }
+}
+
+/**
+ * Folder plugins are installed into, one subfolder per plugin
+ * @returns {Promise<string>}
+ */
+export async function getPluginsFolder() {
+  return invoke('get_plugins_folder');
+}
+
+/**
+ * Enable or disable a plugin; every window restarts its plugins afterwards
+ * @param {string} id
+ * @param {boolean} enabled
+ * @returns {Promise<object>} The plugin's updated info
+ */
+export async function enablePlugin(id, enabled) {
+  try {
+    return await invoke('enable_plugin', { id, enabled });
+  } catch (error) {
+    console.error('Error enabling plugin:', error);
+    throw error;
+  }
+}
+
+/**
+ * Source of an enabled plugin's entry point
+ * @param {string} id
+ * @returns {Promise<string>}
+ */
+export async function loadPlugin(id) {
+  return invoke('load_plugin', { id });
+}
+
+/**
+ * Forward a plugin's note request to the backend, which checks it against the
+ * plugin's permissions and this window's workspace
+ * @param {string} pluginId
+ * @param {string} method - `notes.list`, `notes.read`, `notes.write` or `notes.create`
+ * @param {object} params
+ * @returns {Promise<any>}
+ */
+export async function pluginRequest(pluginId, method, params = {}) {
+  return invoke('plugin_request', { pluginId, method, params });
+}
@@ -0,0 +1,58 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} PropertyValue
+ * @property {'empty'|'text'|'number'|'boolean'|'date'|'list'|'link'|'yaml'} type
+ * @property {*} [value] - A string, number, boolean or string[]; links omit the brackets.
+ * `yaml` values are raw YAML the property editor can't change
+ */
+
+/**
+ * @typedef {PropertyValue & {key: string}} NoteProperty
+ */
+
+/**
+ * Read a note's frontmatter as typed properties, in the order they're written
+ * @param {string} path - Absolute note path
+ * @returns {Promise<NoteProperty[]>}
+ */
+export async function getNoteProperties(path) {
+  try {
+    return await invoke('get_note_properties', { path });
+  } catch (error) {
+    console.error('Error reading note properties:', error);
+    throw error;
+  }
+}
+
+/**
+ * Set one frontmatter property, rewriting only its lines. The value is
+ * checked against its type, e.g. dates must be YYYY-MM-DD
+ * @param {string} path - Absolute note path
+ * @param {string} key
+ * @param {PropertyValue} value
+ * @returns {Promise<{content: string, properties: NoteProperty[]}>}
+ */
+export async function setNoteProperty(path, key, value) {
+  try {
+    return await invoke('set_note_property', { path, key, value });
+  } catch (error) {
+    console.error('Error setting note property:', error);
+    throw error;
+  }
+}
+
+/**
+ * Remove one frontmatter property
+ * @param {string} path - Absolute note path
+ * @param {string} key
+ * @returns {Promise<{content: string, properties: NoteProperty[]}>}
+ */
+export async function removeNoteProperty(path, key) {
+  try {
+    return await invoke('remove_note_property', { path, key });
+  } catch (error) {
+    console.error('Error removing note property:', error);
+    throw error;
+  }
+}
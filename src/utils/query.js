@@ -0,0 +1,24 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} QueryResult
+ * @property {string[]} columns - Fields shown after the note title
+ * @property {Array<{path: string, title: string, values: Array<*>}>} rows - One value per column
+ * @property {number} total - Matches before LIMIT applied
+ */
+
+/**
+ * Run a metadata query against the workspace index, e.g.
+ * `status = "active" AND due < today SORT due`
+ * @param {string} folderPath - Workspace root
+ * @param {string} query
+ * @returns {Promise<QueryResult>}
+ */
+export async function queryNotes(folderPath, query) {
+  try {
+    return await invoke('query_notes', { folderPath, query });
+  } catch (error) {
+    console.error('Error running query:', error);
+    throw error;
+  }
+}
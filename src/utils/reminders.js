@@ -0,0 +1,28 @@
+import { invoke } from '@tauri-apps/api/core';
+
+// Reminders come from `remind:` frontmatter (a time or a list of times) and
+// inline `@remind(2024-06-01 09:00)` tokens. A bare date means 9:00 local time.
+// The backend schedules them for every open workspace and emits `reminder-due`.
+
+/**
+ * Upcoming reminders in a workspace, plus those from the last day
+ * @param {string} folderPath
+ * @returns {Promise<Array<{path: string, title: string, at: string}>>}
+ */
+export async function listReminders(folderPath) {
+  try {
+    return await invoke('list_reminders', { folderPath });
+  } catch (error) {
+    console.error('Error listing reminders:', error);
+    throw error;
+  }
+}
+
+/**
+ * Open the note a reminder belongs to in the calling window
+ * @param {string} path
+ * @returns {Promise<void>}
+ */
+export async function openReminderNote(path) {
+  await invoke('open_recent_note', { path });
+}
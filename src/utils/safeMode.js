@@ -0,0 +1,36 @@
+import { invoke } from '@tauri-apps/api/core';
+
+// Safe mode is chosen by the backend at launch (`--safe`, or Shift held) and
+// skips workspace restore, watchers, and the index until the user continues.
+
+/**
+ * Whether this launch started in safe mode
+ * @returns {Promise<boolean>}
+ */
+export async function getSafeMode() {
+  try {
+    return await invoke('get_safe_mode');
+  } catch (error) {
+    console.error('Error reading safe mode:', error);
+    return false;
+  }
+}
+
+/**
+ * Leave safe mode so the full app can mount in this window
+ * @returns {Promise<void>}
+ */
+export async function leaveSafeMode() {
+  await invoke('leave_safe_mode');
+}
+
+/**
+ * Forget all app state (settings, recent workspaces, sessions, window layout)
+ * and relaunch. Notes themselves are never touched.
+ * @returns {Promise<void>}
+ */
+export async function resetAppState() {
+  // Persisted stores live in localStorage; clear them before the backend relaunches
+  localStorage.clear();
+  await invoke('reset_app_state');
+}
@@ -0,0 +1,34 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} SearchLine
+ * @property {number} line - 1-based line number
+ * @property {string} snippet - The line, trimmed around its first hit if long
+ * @property {Array<[number, number]>} ranges - [start, end) highlights within the snippet
+ */
+
+/**
+ * @typedef {Object} SearchResult
+ * @property {string} path
+ * @property {string} title
+ * @property {number} score - Higher is better; only comparable within one search
+ * @property {boolean} title_match
+ * @property {SearchLine[]} matches - Empty for encrypted notes and title-only hits
+ */
+
+/**
+ * Ranked full-text search over the backend's workspace index. The last word
+ * matches as a prefix unless the query ends in a space or punctuation.
+ * @param {string} folderPath - Workspace root
+ * @param {string} query
+ * @param {number} [limit]
+ * @returns {Promise<SearchResult[]>}
+ */
+export async function searchWorkspace(folderPath, query, limit) {
+  try {
+    return await invoke('search_workspace', { folderPath, query, limit });
+  } catch (error) {
+    console.error('Error searching workspace:', error);
+    throw error;
+  }
+}
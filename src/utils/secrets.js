@@ -0,0 +1,47 @@
+import { invoke } from '@tauri-apps/api/core';
+
+// API tokens and passphrases go to the OS credential store through the backend,
+// never into settings or localStorage. Keys are namespaced, e.g. `sync.token`.
+
+/**
+ * Store a secret in the OS credential store, replacing any previous value
+ * @param {string} key
+ * @param {string} value
+ * @returns {Promise<void>}
+ */
+export async function storeSecret(key, value) {
+  try {
+    await invoke('store_secret', { key, value });
+  } catch (error) {
+    console.error('Error storing secret:', error);
+    throw error;
+  }
+}
+
+/**
+ * Read a secret from the OS credential store
+ * @param {string} key
+ * @returns {Promise<string | null>} The value, or null when nothing is stored
+ */
+export async function getSecret(key) {
+  try {
+    return await invoke('get_secret', { key });
+  } catch (error) {
+    console.error('Error reading secret:', error);
+    throw error;
+  }
+}
+
+/**
+ * Remove a secret from the OS credential store; missing secrets are ignored
+ * @param {string} key
+ * @returns {Promise<void>}
+ */
+export async function deleteSecret(key) {
+  try {
+    await invoke('delete_secret', { key });
+  } catch (error) {
+    console.error('Error deleting secret:', error);
+    throw error;
+  }
+}
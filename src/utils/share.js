@@ -0,0 +1,53 @@
+import { invoke } from '@tauri-apps/api/core';
+import { buildStandaloneHtml, renderMarkdownToPdf } from './noteExport';
+
+// The rendered export the backend writes out; the markdown format hands over
+// the note file itself
+const exportContents = async (noteName, content, format) => {
+  if (format === 'html') {
+    return Array.from(new TextEncoder().encode(buildStandaloneHtml(noteName, content)));
+  }
+  if (format === 'pdf') {
+    return Array.from(await renderMarkdownToPdf(content));
+  }
+  return null;
+};
+
+/**
+ * Open the system share sheet (macOS sharing picker, Windows Share UI) for a
+ * note, so it can be sent to Messages, AirDrop, Mail and the like.
+ * @param {string} path - Note file path
+ * @param {string} noteName
+ * @param {string} content - Markdown, used to render the HTML or PDF export
+ * @param {'html' | 'pdf' | 'markdown'} format - Exported HTML page, exported
+ *   PDF, or the markdown file itself
+ * @returns {Promise<void>}
+ */
+export async function shareNote(path, noteName, content, format = 'pdf') {
+  try {
+    const contents = await exportContents(noteName, content, format);
+    await invoke('share_note', { path, format, contents });
+  } catch (error) {
+    console.error('Error sharing note:', error);
+    throw error;
+  }
+}
+
+/**
+ * Start a native drag of a note out of the app as a real file, to drop into
+ * Finder, Explorer or a mail message. Call while the mouse button is held.
+ * @param {string} path - Note file path
+ * @param {string} noteName
+ * @param {string | null} content - Markdown, used to render the HTML or PDF export
+ * @param {'html' | 'pdf' | 'markdown'} format
+ * @returns {Promise<void>}
+ */
+export async function dragNoteOut(path, noteName, content, format = 'markdown') {
+  try {
+    const contents = await exportContents(noteName, content, format);
+    await invoke('drag_note', { path, format, contents });
+  } catch (error) {
+    console.error('Error dragging note out:', error);
+    throw error;
+  }
+}
@@ -0,0 +1,69 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} SmartFolder
+ * @property {string} id
+ * @property {string} name
+ * @property {string} query - A metadata query, e.g. `status = "active" SORT due`
+ * @property {number} created_at
+ * @property {number|null} [count] - Matching notes; null when the query no longer parses
+ * @property {string|null} [error]
+ */
+
+/**
+ * Saved smart folders for a workspace, with how many notes each one matches
+ * @param {string} folderPath - Workspace root
+ * @returns {Promise<SmartFolder[]>}
+ */
+export async function listSmartFolders(folderPath) {
+  try {
+    return await invoke('list_smart_folders', { folderPath });
+  } catch (error) {
+    console.error('Error listing smart folders:', error);
+    throw error;
+  }
+}
+
+/**
+ * Run a smart folder's query
+ * @param {string} folderPath - Workspace root
+ * @param {string} id
+ * @returns {Promise<import('./query').QueryResult>}
+ */
+export async function getSmartFolderContents(folderPath, id) {
+  try {
+    return await invoke('get_smart_folder_contents', { folderPath, id });
+  } catch (error) {
+    console.error('Error loading smart folder:', error);
+    throw error;
+  }
+}
+
+/**
+ * Create a smart folder, or update an existing one when `id` is given
+ * @param {string} folderPath - Workspace root
+ * @param {{id?: string, name: string, query: string}} folder
+ * @returns {Promise<SmartFolder>}
+ */
+export async function saveSmartFolder(folderPath, { id, name, query }) {
+  try {
+    return await invoke('save_smart_folder', { folderPath, id: id || null, name, query });
+  } catch (error) {
+    console.error('Error saving smart folder:', error);
+    throw error;
+  }
+}
+
+/**
+ * @param {string} folderPath - Workspace root
+ * @param {string} id
+ * @returns {Promise<void>}
+ */
+export async function deleteSmartFolder(folderPath, id) {
+  try {
+    await invoke('delete_smart_folder', { folderPath, id });
+  } catch (error) {
+    console.error('Error deleting smart folder:', error);
+    throw error;
+  }
+}
@@ -0,0 +1,58 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} Snippet
+ * @property {string} key - The abbreviation, like `;sig`
+ * @property {string} text - Expansion template
+ * @property {string} description
+ */
+
+/**
+ * @typedef {Object} SnippetExpansion
+ * @property {string} text
+ * @property {number | null} cursor - Offset of `{{cursor}}` in `text`, if it had one
+ */
+
+/**
+ * List the snippets defined in settings
+ * @returns {Promise<Snippet[]>}
+ */
+export async function listSnippets() {
+  try {
+    return await invoke('list_snippets');
+  } catch (error) {
+    console.error('Error listing snippets:', error);
+    throw error;
+  }
+}
+
+/**
+ * Expand a snippet, filling in dates, the note title and `variables`
+ * @param {string} key
+ * @param {string | null} path - The note being edited, for `{{title}}`
+ * @param {Record<string, string>} [variables]
+ * @returns {Promise<SnippetExpansion>}
+ */
+export async function expandSnippet(key, path, variables = {}) {
+  try {
+    return await invoke('expand_snippet', { key, path, variables });
+  } catch (error) {
+    console.error('Error expanding snippet:', error);
+    throw error;
+  }
+}
+
+/**
+ * Replace the snippet list in settings
+ * @param {Snippet[]} snippets
+ * @returns {Promise<Snippet[]>} The list as saved, without blank or repeated keys
+ */
+export async function saveSnippets(snippets) {
+  try {
+    const settings = await invoke('set_setting', { key: 'snippets', value: snippets });
+    return settings.snippets;
+  } catch (error) {
+    console.error('Error saving snippets:', error);
+    throw error;
+  }
+}
@@ -0,0 +1,46 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Words and phrases spell checking accepts in a workspace, shared through
+ * its `.marky/config.json`
+ * @param {string} folderPath - Workspace root
+ * @returns {Promise<string[]>}
+ */
+export async function getSpellcheckIgnore(folderPath) {
+  try {
+    return await invoke('get_spellcheck_ignore', { folderPath });
+  } catch (error) {
+    console.error('Error loading spellcheck ignore list:', error);
+    throw error;
+  }
+}
+
+/**
+ * Add a word or phrase to the workspace's ignore list
+ * @param {string} folderPath
+ * @param {string} word
+ * @returns {Promise<string[]>} The updated list
+ */
+export async function addSpellcheckIgnore(folderPath, word) {
+  try {
+    return await invoke('add_spellcheck_ignore', { folderPath, word });
+  } catch (error) {
+    console.error('Error adding to spellcheck ignore list:', error);
+    throw error;
+  }
+}
+
+/**
+ * Remove a word or phrase from the workspace's ignore list
+ * @param {string} folderPath
+ * @param {string} word
+ * @returns {Promise<string[]>} The updated list
+ */
+export async function removeSpellcheckIgnore(folderPath, word) {
+  try {
+    return await invoke('remove_spellcheck_ignore', { folderPath, word });
+  } catch (error) {
+    console.error('Error removing from spellcheck ignore list:', error);
+    throw error;
+  }
+}
@@ -0,0 +1,96 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} TagEditReport
+ * @property {string} tag - The tag as normalized by the backend
+ * @property {string[]} changed - Notes that were rewritten
+ * @property {number} unchanged - Notes that already had (or lacked) the tag
+ * @property {Array<{path: string, reason: string}>} skipped - Locked, encrypted, or vault notes
+ */
+
+/**
+ * Tag many notes at once: the `tags:` frontmatter list when a note has one,
+ * otherwise an inline `#tag`. Either every note is written or none is.
+ * @param {string[]} paths - Note file paths
+ * @param {string} tag - With or without the leading `#`
+ * @returns {Promise<TagEditReport>}
+ */
+export async function addTagToNotes(paths, tag) {
+  try {
+    return await invoke('add_tag_to_notes', { paths, tag });
+  } catch (error) {
+    console.error('Error adding tag to notes:', error);
+    throw error;
+  }
+}
+
+/**
+ * Remove a tag from many notes, inline `#tag`s and frontmatter entries alike
+ * @param {string[]} paths - Note file paths
+ * @param {string} tag - With or without the leading `#`
+ * @returns {Promise<TagEditReport>}
+ */
+export async function removeTagFromNotes(paths, tag) {
+  try {
+    return await invoke('remove_tag_from_notes', { paths, tag });
+  } catch (error) {
+    console.error('Error removing tag from notes:', error);
+    throw error;
+  }
+}
+
+/**
+ * Rename a tag in every note of the workspace, frontmatter included. Renaming
+ * onto a tag that already exists merges the two.
+ * @param {string} folderPath - Workspace root
+ * @param {string} oldTag
+ * @param {string} newTag
+ * @param {boolean} [dryRun] - Only report which notes would change
+ * @returns {Promise<TagEditReport>}
+ */
+export async function renameTag(folderPath, oldTag, newTag, dryRun = false) {
+  try {
+    return await invoke('rename_tag', { folderPath, oldTag, newTag, dryRun });
+  } catch (error) {
+    console.error('Error renaming tag:', error);
+    throw error;
+  }
+}
+
+/**
+ * @typedef {Object} TagNode
+ * @property {string} name - Last segment, `acme` for `project/client/acme`
+ * @property {string} tag - Full tag path
+ * @property {number} count - Notes tagged with this tag or anything under it
+ * @property {number} direct - Notes tagged with exactly this tag
+ * @property {TagNode[]} children
+ */
+
+/**
+ * Workspace tags nested on `/`, as indexed on disk
+ * @param {string} folderPath - Workspace root
+ * @returns {Promise<TagNode[]>}
+ */
+export async function getTagTree(folderPath) {
+  try {
+    return await invoke('get_tag_tree', { folderPath });
+  } catch (error) {
+    console.error('Error loading tag tree:', error);
+    throw error;
+  }
+}
+
+/**
+ * Notes tagged `prefix` or any tag nested under it
+ * @param {string} folderPath - Workspace root
+ * @param {string} prefix - e.g. `project` also matches `#project/client`
+ * @returns {Promise<Array<{path: string, title: string, tags: string[]}>>}
+ */
+export async function getNotesByTag(folderPath, prefix) {
+  try {
+    return await invoke('get_notes_by_tag', { folderPath, prefix });
+  } catch (error) {
+    console.error('Error loading notes by tag:', error);
+    throw error;
+  }
+}
@@ -0,0 +1,74 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} TaskSyncConfig
+ * @property {string | null} todo_txt - todo.txt file; relative paths start at the workspace root
+ * @property {string | null} reminders_list - Apple Reminders list name (macOS only)
+ * @property {boolean} include_undated - Export every open task, not only dated ones
+ */
+
+/**
+ * @typedef {Object} TaskSyncStatus
+ * @property {string | null} last_synced - RFC 3339 time
+ * @property {string | null} last_error
+ * @property {number} last_completed - Markdown tasks ticked by the last sync
+ * @property {number} exported - Tasks currently mirrored
+ * @property {boolean} reminders_available - Whether this platform can sync with Reminders
+ */
+
+/**
+ * Where the workspace's tasks are mirrored, from `.marky/task-sync.json`
+ * @param {string} folderPath - Workspace root
+ * @returns {Promise<TaskSyncConfig>}
+ */
+export async function getTaskSync(folderPath) {
+  try {
+    return await invoke('get_task_sync', { folderPath });
+  } catch (error) {
+    console.error('Error loading task sync:', error);
+    throw error;
+  }
+}
+
+/**
+ * Save where the workspace's tasks are mirrored
+ * @param {string} folderPath - Workspace root
+ * @param {TaskSyncConfig} config
+ * @returns {Promise<TaskSyncConfig>} The config as saved
+ */
+export async function setTaskSync(folderPath, config) {
+  try {
+    return await invoke('set_task_sync', { folderPath, config });
+  } catch (error) {
+    console.error('Error saving task sync:', error);
+    throw error;
+  }
+}
+
+/**
+ * When tasks were last synced and how it went
+ * @param {string} folderPath - Workspace root
+ * @returns {Promise<TaskSyncStatus | null>}
+ */
+export async function getTaskSyncStatus(folderPath) {
+  try {
+    return await invoke('get_task_sync_status', { folderPath });
+  } catch (error) {
+    console.error('Error loading task sync status:', error);
+    return null;
+  }
+}
+
+/**
+ * Bring completions back from todo.txt and Reminders, then export open tasks
+ * @param {string} folderPath - Workspace root
+ * @returns {Promise<TaskSyncStatus>}
+ */
+export async function syncTasks(folderPath) {
+  try {
+    return await invoke('sync_tasks', { folderPath });
+  } catch (error) {
+    console.error('Error syncing tasks:', error);
+    throw error;
+  }
+}
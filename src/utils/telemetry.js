@@ -0,0 +1,60 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * @typedef {Object} TelemetryReport
+ * @property {string} app_version
+ * @property {string} os
+ * @property {string} arch
+ * @property {number | null} since - When counting started, in ms since the epoch
+ * @property {Object<string, number>} features - Uses per feature
+ * @property {Object<string, {count: number, mean_ms: number, max_ms: number}>} timings
+ */
+
+/**
+ * Count one use of a feature. Does nothing unless the user opted in to
+ * usage statistics; `feature` must be one of the names the backend knows.
+ * Never throws; a lost count doesn't matter.
+ * @param {string} feature - e.g. 'search'
+ */
+export function recordFeatureUse(feature) {
+  invoke('record_feature_use', { feature }).catch((error) =>
+    console.error('Error recording feature use:', error)
+  );
+}
+
+/**
+ * Record how long something took, like `recordFeatureUse`.
+ * @param {string} name - e.g. 'search'
+ * @param {number} ms
+ */
+export function recordFeatureTiming(name, ms) {
+  invoke('record_feature_timing', { name, ms }).catch((error) =>
+    console.error('Error recording timing:', error)
+  );
+}
+
+/**
+ * Exactly what would be sent.
+ * @returns {Promise<TelemetryReport>}
+ */
+export async function getTelemetryPreview() {
+  try {
+    return await invoke('get_telemetry_preview');
+  } catch (error) {
+    console.error('Error loading usage statistics:', error);
+    throw error;
+  }
+}
+
+/**
+ * Forget everything recorded so far.
+ * @returns {Promise<void>}
+ */
+export async function clearTelemetry() {
+  try {
+    await invoke('clear_telemetry');
+  } catch (error) {
+    console.error('Error clearing usage statistics:', error);
+    throw error;
+  }
+}
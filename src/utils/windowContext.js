@@ -0,0 +1,23 @@
+import { getCurrentWindow } from "@tauri-apps/api/window";
+
+const currentLabel = () => {
+  try {
+    return getCurrentWindow().label;
+  } catch {
+    return "main";
+  }
+};
+
+export const windowLabel = currentLabel();
+
+// Set by the backend's initialization script when a note is opened in its own window
+export const noteWindowPath =
+  typeof window !== "undefined" ? window.__MARKY_NOTE_WINDOW__ || null : null;
+
+export const isNoteWindow = Boolean(noteWindowPath);
+
+// Each workspace window keeps its own tabs and recent notes; note windows read their opener's
+const storageLabel = isNoteWindow ? window.__MARKY_PARENT_WINDOW__ || "main" : windowLabel;
+
+export const storageName =
+  storageLabel === "main" ? "marky-storage" : `marky-storage:${storageLabel}`;